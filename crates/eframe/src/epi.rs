@@ -276,6 +276,10 @@ pub struct NativeOptions {
     ///
     /// The theme will automatically change when the dark vs light mode preference is changed.
     ///
+    /// This sets [`egui::Options::theme_preference`] to [`egui::ThemePreference::System`]
+    /// (or to [`Self::default_theme`] if `false`), and the system theme is
+    /// reported to egui in [`egui::RawInput::system_theme`].
+    ///
     /// Does not work on Linux (see <https://github.com/rust-windowing/winit/issues/1549>).
     ///
     /// See also [`Self::default_theme`].
@@ -407,6 +411,10 @@ impl Default for NativeOptions {
 pub struct WebOptions {
     /// Try to detect and follow the system preferred setting for dark vs light mode.
     ///
+    /// This sets [`egui::Options::theme_preference`] to [`egui::ThemePreference::System`]
+    /// (or to [`Self::default_theme`] if `false`), and the `prefers-color-scheme` of the browser
+    /// is reported to egui in [`egui::RawInput::system_theme`].
+    ///
     /// See also [`Self::default_theme`].
     ///
     /// Default: `true`.
@@ -484,6 +492,37 @@ impl Theme {
     }
 }
 
+impl From<Theme> for egui::Theme {
+    fn from(theme: Theme) -> Self {
+        match theme {
+            Theme::Dark => Self::Dark,
+            Theme::Light => Self::Light,
+        }
+    }
+}
+
+impl From<egui::Theme> for Theme {
+    fn from(theme: egui::Theme) -> Self {
+        match theme {
+            egui::Theme::Dark => Self::Dark,
+            egui::Theme::Light => Self::Light,
+        }
+    }
+}
+
+/// The [`egui::Options::theme_preference`] for the `follow_system_theme`
+/// and `default_theme` options of [`NativeOptions`] or `WebOptions`.
+pub(crate) fn theme_preference(
+    follow_system_theme: bool,
+    default_theme: Theme,
+) -> egui::ThemePreference {
+    if follow_system_theme {
+        egui::ThemePreference::System
+    } else {
+        egui::Theme::from(default_theme).into()
+    }
+}
+
 // ----------------------------------------------------------------------------
 
 /// WebGL Context options
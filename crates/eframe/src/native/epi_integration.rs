@@ -174,6 +174,13 @@ impl EpiIntegration {
             .clone()
             .unwrap_or_else(|| std::sync::Arc::new(load_default_egui_icon()));
 
+        egui_ctx.options_mut(|o| {
+            o.theme_preference = epi::theme_preference(
+                native_options.follow_system_theme,
+                native_options.default_theme,
+            );
+        });
+
         let app_icon_setter = super::app_icon::AppTitleIconSetter::new(
             native_options
                 .viewport
@@ -247,9 +254,8 @@ impl EpiIntegration {
                 ..
             } => self.can_drag_window = true,
             WindowEvent::ThemeChanged(winit_theme) if self.follow_system_theme => {
-                let theme = theme_from_winit_theme(*winit_theme);
-                self.frame.info.system_theme = Some(theme);
-                self.egui_ctx.set_visuals(theme.egui_visuals());
+                // egui itself switches the visuals, see `egui::Options::theme_preference`:
+                self.frame.info.system_theme = Some(theme_from_winit_theme(*winit_theme));
             }
             _ => {}
         }
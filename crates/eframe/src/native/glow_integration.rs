@@ -661,6 +661,7 @@ impl GlowWinitRunning {
             &clipped_primitives,
             &textures_delta,
         );
+        integration.egui_ctx.recycle_primitives(clipped_primitives);
 
         {
             let screenshot_requested = std::mem::take(&mut viewport.screenshot_requested);
@@ -1465,6 +1466,7 @@ fn render_immediate_viewport(
         &clipped_primitives,
         &textures_delta,
     );
+    egui_ctx.recycle_primitives(clipped_primitives);
 
     {
         crate::profile_scope!("swap_buffers");
@@ -679,6 +679,7 @@ impl WgpuWinitRunning {
             &textures_delta,
             screenshot_requested,
         );
+        egui_ctx.recycle_primitives(clipped_primitives);
         if let Some(screenshot) = screenshot {
             egui_winit
                 .egui_input_mut()
@@ -1009,6 +1010,7 @@ fn render_immediate_viewport(
         &textures_delta,
         false,
     );
+    egui_ctx.recycle_primitives(clipped_primitives);
 
     egui_winit.handle_platform_output(window, platform_output);
 
@@ -15,6 +15,7 @@ pub struct AppRunner {
     last_save_time: f64,
     pub(crate) ime: Option<egui::output::IMEOutput>,
     pub(crate) mutable_text_under_cursor: bool,
+    pointer_locked: bool,
 
     // Output for the last run:
     textures_delta: TexturesDelta,
@@ -63,6 +64,9 @@ impl AppRunner {
             // On web, the browser controls the zoom factor:
             o.zoom_with_keyboard = false;
             o.zoom_factor = 1.0;
+
+            o.theme_preference =
+                epi::theme_preference(web_options.follow_system_theme, web_options.default_theme);
         });
 
         let theme = system_theme.unwrap_or(web_options.default_theme);
@@ -114,11 +118,13 @@ impl AppRunner {
             last_save_time: now_sec(),
             ime: None,
             mutable_text_under_cursor: false,
+            pointer_locked: false,
             textures_delta: Default::default(),
             clipped_primitives: None,
         };
 
         runner.input.raw.max_texture_side = Some(runner.painter.max_texture_side());
+        runner.input.raw.system_theme = super::system_theme().map(Into::into);
         runner
             .input
             .raw
@@ -225,6 +231,7 @@ impl AppRunner {
             ) {
                 log::error!("Failed to paint: {}", super::string_from_js_value(&err));
             }
+            self.egui_ctx.recycle_primitives(clipped_primitives);
         }
     }
 
@@ -242,14 +249,21 @@ impl AppRunner {
             cursor_icon,
             open_url,
             copied_text,
-            events: _, // already handled
+            events: _,       // already handled
+            sound_events: _, // for the app to play
+            haptics,
             mutable_text_under_cursor,
             ime,
+            request_eyedropper: _, // not supported, since `RawInput::eyedropper` is never set
+            pointer_lock,
             #[cfg(feature = "accesskit")]
                 accesskit_update: _, // not currently implemented
         } = platform_output;
 
         super::set_cursor_icon(cursor_icon);
+        for haptic in haptics {
+            super::vibrate(haptic);
+        }
         if let Some(open) = open_url {
             super::open_url(&open.url, open.new_tab);
         }
@@ -264,6 +278,11 @@ impl AppRunner {
 
         self.mutable_text_under_cursor = mutable_text_under_cursor;
 
+        if self.pointer_locked != pointer_lock {
+            self.pointer_locked = pointer_lock;
+            super::set_pointer_lock(self.canvas_id(), pointer_lock);
+        }
+
         if self.ime != ime {
             super::text_agent::move_text_cursor(ime, self.canvas_id());
             self.ime = ime;
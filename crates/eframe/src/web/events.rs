@@ -182,7 +182,10 @@ pub(crate) fn install_document_events(runner_ref: &WebRunner) -> Result<(), JsVa
                 if let Ok(text) = data.get_data("text") {
                     let text = text.replace("\r\n", "\n");
                     if !text.is_empty() {
-                        runner.input.raw.events.push(egui::Event::Paste(text));
+                        runner.input.raw.events.push(egui::Event::Paste {
+                            text,
+                            source: egui::PasteSource::Clipboard,
+                        });
                         runner.needs_repaint.repaint_asap();
                     }
                     event.stop_propagation();
@@ -265,7 +268,8 @@ pub(crate) fn install_color_scheme_change_event(runner_ref: &WebRunner) -> Resul
             |event, runner| {
                 let theme = theme_from_dark_mode(event.matches());
                 runner.frame.info.system_theme = Some(theme);
-                runner.egui_ctx().set_visuals(theme.egui_visuals());
+                // egui itself switches the visuals, see `egui::Options::theme_preference`:
+                runner.input.raw.system_theme = Some(theme.into());
                 runner.needs_repaint.repaint_asap();
             },
         )?;
@@ -329,6 +333,9 @@ pub(crate) fn install_canvas_events(runner_ref: &WebRunner) -> Result<(), JsValu
         |event: web_sys::MouseEvent, runner| {
             let pos = pos_from_mouse_event(runner.canvas_id(), &event);
             runner.input.raw.events.push(egui::Event::PointerMoved(pos));
+            // The raw motion keeps coming while the pointer is locked, see `PlatformOutput::pointer_lock`:
+            let delta = egui::vec2(event.movement_x() as f32, event.movement_y() as f32);
+            runner.input.raw.events.push(egui::Event::MouseMoved(delta));
             runner.needs_repaint.repaint_asap();
             event.stop_propagation();
             event.prevent_default();
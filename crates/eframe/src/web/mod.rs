@@ -192,6 +192,35 @@ fn set_cursor_icon(cursor: egui::CursorIcon) -> Option<()> {
         .ok()
 }
 
+/// Lock and hide the pointer with the Pointer Lock API, or release it again.
+///
+/// Browsers only grant the lock during a user gesture, which is why we also run the app logic
+/// in the `mouseup` handler. The user can always release the lock with Escape,
+/// after which we only ask again once egui stops requesting it and then starts again.
+fn set_pointer_lock(canvas_id: &str, lock: bool) -> Option<()> {
+    if lock {
+        canvas_element(canvas_id)?.request_pointer_lock();
+    } else {
+        web_sys::window()?.document()?.exit_pointer_lock();
+    }
+    Some(())
+}
+
+/// Vibrate the device, if the browser supports it (most do on Android).
+fn vibrate(haptic: egui::output::HapticKind) -> Option<()> {
+    use egui::output::HapticKind;
+
+    let milliseconds = match haptic {
+        HapticKind::Detent => 5,
+        HapticKind::Drop => 15,
+        HapticKind::MenuOpened => 25,
+    };
+    web_sys::window()?
+        .navigator()
+        .vibrate_with_duration(milliseconds);
+    Some(())
+}
+
 /// Set the clipboard text.
 #[cfg(web_sys_unstable_apis)]
 fn set_clipboard_text(s: &str) {
@@ -79,6 +79,9 @@ pub struct State {
     any_pointer_button_down: bool,
     current_cursor_icon: Option<egui::CursorIcon>,
 
+    /// See [`egui::PlatformOutput::pointer_lock`].
+    pointer_locked: bool,
+
     clipboard: clipboard::Clipboard,
 
     /// If `true`, mouse inputs will be treated as touches.
@@ -125,6 +128,7 @@ impl State {
             pointer_pos_in_points: None,
             any_pointer_button_down: false,
             current_cursor_icon: None,
+            pointer_locked: false,
 
             clipboard: clipboard::Clipboard::new(
                 display_target.display_handle().ok().map(|h| h.as_raw()),
@@ -245,6 +249,11 @@ impl State {
             .or_default()
             .native_pixels_per_point = Some(window.scale_factor() as f32);
 
+        if self.egui_input.system_theme.is_none() {
+            // After this we learn about changes from `WindowEvent::ThemeChanged`:
+            self.egui_input.system_theme = window.theme().map(theme_from_winit_theme);
+        }
+
         self.egui_input.take()
     }
 
@@ -387,6 +396,13 @@ impl State {
                     consumed: false,
                 }
             }
+            WindowEvent::ThemeChanged(theme) => {
+                self.egui_input.system_theme = Some(theme_from_winit_theme(*theme));
+                EventResponse {
+                    repaint: true,
+                    consumed: false,
+                }
+            }
             WindowEvent::HoveredFile(path) => {
                 self.egui_input.hovered_files.push(egui::HoveredFile {
                     path: Some(path.clone()),
@@ -446,7 +462,6 @@ impl State {
             | WindowEvent::Occluded(_)
             | WindowEvent::Resized(_)
             | WindowEvent::Moved(_)
-            | WindowEvent::ThemeChanged(_)
             | WindowEvent::TouchpadPressure { .. }
             | WindowEvent::CloseRequested => EventResponse {
                 repaint: true,
@@ -454,13 +469,12 @@ impl State {
             },
 
             // Things we completely ignore:
-            WindowEvent::ActivationTokenDone { .. }
-            | WindowEvent::AxisMotion { .. }
-            | WindowEvent::SmartMagnify { .. }
-            | WindowEvent::TouchpadRotate { .. } => EventResponse {
-                repaint: false,
-                consumed: false,
-            },
+            WindowEvent::ActivationTokenDone { .. } | WindowEvent::AxisMotion { .. } => {
+                EventResponse {
+                    repaint: false,
+                    consumed: false,
+                }
+            }
 
             WindowEvent::TouchpadMagnify { delta, .. } => {
                 // Positive delta values indicate magnification (zooming in).
@@ -472,6 +486,24 @@ impl State {
                     consumed: self.egui_ctx.wants_pointer_input(),
                 }
             }
+
+            WindowEvent::TouchpadRotate { delta, .. } => {
+                // winit gives us degrees, counter-clockwise:
+                let angle = -delta.to_radians();
+                self.egui_input.events.push(egui::Event::Rotate(angle));
+                EventResponse {
+                    repaint: true,
+                    consumed: self.egui_ctx.wants_pointer_input(),
+                }
+            }
+
+            WindowEvent::SmartMagnify { .. } => {
+                self.egui_input.events.push(egui::Event::SmartZoom);
+                EventResponse {
+                    repaint: true,
+                    consumed: self.egui_ctx.wants_pointer_input(),
+                }
+            }
         }
     }
 
@@ -602,6 +634,9 @@ impl State {
         // touch …
         if self.pointer_touch_id.is_none() || self.pointer_touch_id.unwrap() == touch.id {
             // … emit PointerButton resp. PointerMoved events to emulate mouse
+            if let Some(pen) = pen_state(touch) {
+                self.egui_input.events.push(egui::Event::Pen(Some(pen)));
+            }
             match touch.phase {
                 winit::event::TouchPhase::Started => {
                     self.pointer_touch_id = Some(touch.id);
@@ -740,7 +775,10 @@ impl State {
                     if let Some(contents) = self.clipboard.get() {
                         let contents = contents.replace("\r\n", "\n");
                         if !contents.is_empty() {
-                            self.egui_input.events.push(egui::Event::Paste(contents));
+                            self.egui_input.events.push(egui::Event::Paste {
+                                text: contents,
+                                source: egui::PasteSource::Clipboard,
+                            });
                         }
                     }
                     return;
@@ -796,13 +834,25 @@ impl State {
             open_url,
             copied_text,
             events: _,                    // handled elsewhere
+            sound_events: _,              // for the app to play
+            haptics: _,                   // only used in eframe web
             mutable_text_under_cursor: _, // only used in eframe web
             ime,
+            request_eyedropper: _, // not supported, since `RawInput::eyedropper` is never set
+            pointer_lock,
             #[cfg(feature = "accesskit")]
             accesskit_update,
         } = platform_output;
 
-        self.set_cursor_icon(window, cursor_icon);
+        if self.pointer_locked != pointer_lock {
+            self.pointer_locked = pointer_lock;
+            set_pointer_lock(&self.egui_ctx, window, pointer_lock);
+            self.current_cursor_icon = None; // Set it again once unlocked
+        }
+
+        if !self.pointer_locked {
+            self.set_cursor_icon(window, cursor_icon);
+        }
 
         if let Some(open_url) = open_url {
             open_url_in_browser(&open_url.url);
@@ -868,6 +918,46 @@ impl State {
     }
 }
 
+/// Lock and hide the cursor, or release it again.
+///
+/// Not all platforms support [`CursorGrabMode::Locked`], so we fall back to [`CursorGrabMode::Confined`].
+/// We keep getting the raw mouse motion through [`winit::event::DeviceEvent::MouseMotion`] either way.
+///
+/// On release we go back to whatever grab the user last set with [`ViewportCommand::CursorGrab`].
+fn set_pointer_lock(egui_ctx: &egui::Context, window: &Window, lock: bool) {
+    crate::profile_function!();
+    if lock {
+        if let Err(err) = window
+            .set_cursor_grab(CursorGrabMode::Locked)
+            .or_else(|_| window.set_cursor_grab(CursorGrabMode::Confined))
+        {
+            log::warn!("Failed to lock the pointer: {err}");
+        }
+    } else {
+        let cursor_grab = egui_ctx
+            .data(|data| data.get_temp(user_cursor_grab_id(window)))
+            .unwrap_or_default();
+        if let Err(err) = window.set_cursor_grab(cursor_grab_mode(cursor_grab)) {
+            log::warn!("Failed to release the pointer: {err}");
+        }
+    }
+    window.set_cursor_visible(!lock);
+}
+
+/// Where we remember the last [`ViewportCommand::CursorGrab`] of a window,
+/// so releasing the pointer lock doesn't undo it.
+fn user_cursor_grab_id(window: &Window) -> egui::Id {
+    egui::Id::new("egui_winit_user_cursor_grab").with(window.id())
+}
+
+fn cursor_grab_mode(cursor_grab: egui::viewport::CursorGrab) -> CursorGrabMode {
+    match cursor_grab {
+        egui::viewport::CursorGrab::None => CursorGrabMode::None,
+        egui::viewport::CursorGrab::Confined => CursorGrabMode::Confined,
+        egui::viewport::CursorGrab::Locked => CursorGrabMode::Locked,
+    }
+}
+
 /// Update the given viewport info with the current state of the window.
 ///
 /// Call before [`State::take_egui_input`].
@@ -1001,6 +1091,13 @@ fn is_paste_command(modifiers: egui::Modifiers, keycode: egui::Key) -> bool {
         || (cfg!(target_os = "windows") && modifiers.shift && keycode == egui::Key::Insert)
 }
 
+fn theme_from_winit_theme(theme: winit::window::Theme) -> egui::Theme {
+    match theme {
+        winit::window::Theme::Dark => egui::Theme::Dark,
+        winit::window::Theme::Light => egui::Theme::Light,
+    }
+}
+
 fn translate_mouse_button(button: winit::event::MouseButton) -> Option<egui::PointerButton> {
     match button {
         winit::event::MouseButton::Left => Some(egui::PointerButton::Primary),
@@ -1012,6 +1109,23 @@ fn translate_mouse_button(button: winit::event::MouseButton) -> Option<egui::Poi
     }
 }
 
+/// winit only tells touches from a pen (an Apple Pencil on iOS) apart by their altitude angle.
+fn pen_state(touch: &winit::event::Touch) -> Option<egui::PenState> {
+    match touch.force {
+        Some(winit::event::Force::Calibrated {
+            force,
+            max_possible_force,
+            altitude_angle: Some(altitude_angle),
+        }) => Some(egui::PenState {
+            pressure: (force / max_possible_force) as f32,
+            // The direction of the tilt is unknown, so lean towards the user:
+            tilt: egui::vec2(0.0, std::f32::consts::FRAC_PI_2 - altitude_angle as f32),
+            eraser: false,
+        }),
+        _ => None,
+    }
+}
+
 fn key_from_winit_key(key: &winit::keyboard::Key) -> Option<egui::Key> {
     match key {
         winit::keyboard::Key::Named(named_key) => key_from_named_key(*named_key),
@@ -1320,6 +1434,9 @@ fn process_viewport_command(
                 log::debug!("ViewportCommand::InnerSize ignored by winit");
             }
         }
+        ViewportCommand::InnerSizeToContent => {
+            // egui replaces this with `InnerSize`
+        }
         ViewportCommand::BeginResize(direction) => {
             if let Err(err) = window.drag_resize_window(match direction {
                 egui::viewport::ResizeDirection::North => ResizeDirection::North,
@@ -1447,11 +1564,8 @@ fn process_viewport_command(
             }
         }
         ViewportCommand::CursorGrab(o) => {
-            if let Err(err) = window.set_cursor_grab(match o {
-                egui::viewport::CursorGrab::None => CursorGrabMode::None,
-                egui::viewport::CursorGrab::Confined => CursorGrabMode::Confined,
-                egui::viewport::CursorGrab::Locked => CursorGrabMode::Locked,
-            }) {
+            egui_ctx.data_mut(|data| data.insert_temp(user_cursor_grab_id(window), o));
+            if let Err(err) = window.set_cursor_grab(cursor_grab_mode(o)) {
                 log::warn!("{command:?}: {err}");
             }
         }
@@ -1464,6 +1578,9 @@ fn process_viewport_command(
         ViewportCommand::Screenshot => {
             *screenshot_requested = true;
         }
+        ViewportCommand::RepaintPolicy(_) => {
+            // egui already applies this to the repaint delays.
+        }
     }
 }
 
@@ -1544,6 +1661,7 @@ pub fn create_winit_window_builder<T>(
         app_id: _app_id,
 
         mouse_passthrough: _, // handled in `apply_viewport_builder_to_window`
+        inner_size_to_content: _, // handled by egui
     } = viewport_builder;
 
     let mut window_builder = winit::window::WindowBuilder::new()
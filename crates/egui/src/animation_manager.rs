@@ -1,8 +1,83 @@
 use crate::{
-    emath::{remap_clamp, NumExt as _},
-    Id, IdMap, InputState,
+    emath::{lerp, remap_clamp, NumExt as _},
+    Id, IdMap, InputState, Options,
 };
 
+/// How to animate a value, see e.g. [`crate::Context::animate_bool_with_options`].
+///
+/// All animations can be turned off with [`Options::animations_enabled`].
+///
+/// ```
+/// # let ctx = egui::Context::default();
+/// # let id = egui::Id::new("fade");
+/// # let is_visible = true;
+/// let ease_out = |t: f32| t * (2.0 - t);
+/// let opacity = ctx.animate_bool_with_options(
+///     id,
+///     is_visible,
+///     egui::AnimationOptions::default()
+///         .with_duration(0.25)
+///         .with_easing(ease_out),
+/// );
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct AnimationOptions {
+    /// How long the animation takes, in seconds.
+    ///
+    /// `None` means [`crate::Style::animation_time`].
+    pub duration: Option<f32>,
+
+    /// Maps how far along the animation is in time (0-1) to how far along the value is (0-1).
+    ///
+    /// Default: linear.
+    pub easing: fn(f32) -> f32,
+
+    /// Jump straight to the new value instead of animating.
+    pub disabled: bool,
+}
+
+impl Default for AnimationOptions {
+    fn default() -> Self {
+        Self {
+            duration: None,
+            easing: |t| t,
+            disabled: false,
+        }
+    }
+}
+
+impl AnimationOptions {
+    /// Jump straight to the new value instead of animating.
+    pub const DISABLED: Self = Self {
+        duration: None,
+        easing: |t| t,
+        disabled: true,
+    };
+
+    /// How long the animation takes, in seconds.
+    #[inline]
+    pub fn with_duration(mut self, seconds: f32) -> Self {
+        self.duration = Some(seconds);
+        self
+    }
+
+    /// Maps how far along the animation is in time (0-1) to how far along the value is (0-1).
+    #[inline]
+    pub fn with_easing(mut self, easing: fn(f32) -> f32) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// How long the animation takes, in seconds, or zero if it is disabled.
+    pub(crate) fn duration(&self, options: &Options) -> f32 {
+        if self.disabled || !options.animations_enabled {
+            0.0
+        } else {
+            self.duration.unwrap_or(options.style.animation_time)
+        }
+    }
+}
+
 #[derive(Clone, Default)]
 pub(crate) struct AnimationManager {
     bools: IdMap<BoolAnim>,
@@ -65,10 +140,12 @@ impl AnimationManager {
         }
     }
 
+    /// See [`crate::Context::animate_value_with_options`] for documentation
     pub fn animate_value(
         &mut self,
         input: &InputState,
         animation_time: f32,
+        easing: fn(f32) -> f32,
         id: Id,
         value: f32,
     ) -> f32 {
@@ -85,24 +162,23 @@ impl AnimationManager {
                 value
             }
             Some(anim) => {
+                if animation_time <= 0.0 {
+                    anim.from_value = value;
+                    anim.to_value = value;
+                    return value;
+                }
+
                 let time_since_toggle = (input.time - anim.toggle_time) as f32;
                 // On the frame we toggle we don't want to return the old value,
                 // so we extrapolate forwards:
                 let time_since_toggle = time_since_toggle + input.predicted_dt;
-                let current_value = remap_clamp(
-                    time_since_toggle,
-                    0.0..=animation_time,
-                    anim.from_value..=anim.to_value,
-                );
+                let t = remap_clamp(time_since_toggle, 0.0..=animation_time, 0.0..=1.0);
+                let current_value = lerp(anim.from_value..=anim.to_value, easing(t));
                 if anim.to_value != value {
                     anim.from_value = current_value; //start new animation from current position of playing animation
                     anim.to_value = value;
                     anim.toggle_time = input.time;
                 }
-                if animation_time == 0.0 {
-                    anim.from_value = value;
-                    anim.to_value = value;
-                }
                 current_value
             }
         }
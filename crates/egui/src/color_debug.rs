@@ -0,0 +1,337 @@
+//! Debug tools for checking that a theme works for everyone:
+//! color-blindness simulation and a text contrast checker.
+//!
+//! Turn them on with [`Context::set_color_debug_options`], or from [`Context::devtools_ui`].
+//!
+//! Both are implemented as a shape mapper (see [`Context::add_shape_mapper`]),
+//! so they work with any ui code, and only cost anything when turned on.
+
+use epaint::{ClippedShape, RectShape, TextShape};
+
+use crate::*;
+
+/// The name of the shape mapper used by [`Context::set_color_debug_options`].
+const MAPPER_NAME: &str = "egui_color_debug";
+
+/// A type of color blindness that can be simulated.
+///
+/// The simulation uses the matrices from
+/// "A Physiologically-based Model for Simulation of Color Vision Deficiency" by Machado et al. (2009),
+/// at full severity.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum ColorBlindness {
+    /// No red cones (about 1% of men).
+    Protanopia,
+
+    /// No green cones (about 1% of men).
+    Deuteranopia,
+
+    /// No blue cones (very rare).
+    Tritanopia,
+}
+
+impl ColorBlindness {
+    pub const ALL: [Self; 3] = [Self::Protanopia, Self::Deuteranopia, Self::Tritanopia];
+
+    /// Transforms linear RGB.
+    fn matrix(self) -> [[f32; 3]; 3] {
+        match self {
+            Self::Protanopia => [
+                [0.152_286, 1.052_583, -0.204_868],
+                [0.114_503, 0.786_281, 0.099_216],
+                [-0.003_882, -0.048_116, 1.051_998],
+            ],
+            Self::Deuteranopia => [
+                [0.367_322, 0.860_646, -0.227_968],
+                [0.280_085, 0.672_501, 0.047_413],
+                [-0.011_820, 0.042_940, 0.968_881],
+            ],
+            Self::Tritanopia => [
+                [1.255_528, -0.076_749, -0.178_779],
+                [-0.078_411, 0.930_809, 0.147_602],
+                [0.004_733, 0.691_367, 0.303_900],
+            ],
+        }
+    }
+
+    /// How a person with this color blindness would see the given color.
+    pub fn simulate(self, color: Color32) -> Color32 {
+        // The matrix is linear, so we can apply it directly to premultiplied colors:
+        let [r, g, b, a] = Rgba::from(color).to_array();
+        let [r, g, b] = self
+            .matrix()
+            .map(|row| (row[0] * r + row[1] * g + row[2] * b).clamp(0.0, a.max(0.0)));
+        Rgba::from_rgba_premultiplied(r, g, b, a).into()
+    }
+}
+
+impl std::fmt::Display for ColorBlindness {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Protanopia => "Protanopia (red-blind)",
+            Self::Deuteranopia => "Deuteranopia (green-blind)",
+            Self::Tritanopia => "Tritanopia (blue-blind)",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Which level of the [WCAG contrast requirements](https://www.w3.org/TR/WCAG21/#contrast-minimum) to check for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum ContrastLevel {
+    /// At least 4.5:1 for normal text, and 3:1 for large text.
+    Aa,
+
+    /// At least 7:1 for normal text, and 4.5:1 for large text.
+    Aaa,
+}
+
+impl ContrastLevel {
+    /// The smallest acceptable [`contrast_ratio`] for text of the given size (in points).
+    ///
+    /// Text of 24 points or more is considered large.
+    pub fn min_ratio(self, font_size: f32) -> f32 {
+        let large = 24.0 <= font_size;
+        match (self, large) {
+            (Self::Aa, false) | (Self::Aaa, true) => 4.5,
+            (Self::Aa, true) => 3.0,
+            (Self::Aaa, false) => 7.0,
+        }
+    }
+}
+
+/// The [WCAG contrast ratio](https://www.w3.org/TR/WCAG21/#dfn-contrast-ratio) between two opaque colors,
+/// from 1.0 (no contrast) to 21.0 (black on white).
+///
+/// Any transparency is ignored.
+pub fn contrast_ratio(a: Color32, b: Color32) -> f32 {
+    let luminance = |color: Color32| {
+        let [r, g, b, _] = Rgba::from(color.to_opaque()).to_array();
+        0.2126 * r + 0.7152 * g + 0.0722 * b
+    };
+    let (a, b) = (luminance(a), luminance(b));
+    (a.max(b) + 0.05) / (a.min(b) + 0.05)
+}
+
+/// Options for the color debug tools.
+///
+/// Set with [`Context::set_color_debug_options`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ColorDebugOptions {
+    /// Show everything as it would look to someone with this kind of color blindness.
+    ///
+    /// Only affects shapes, not the contents of images.
+    pub simulate_color_blindness: Option<ColorBlindness>,
+
+    /// Put a marker around all text that doesn't have enough contrast to its background.
+    ///
+    /// The check uses the original colors, i.e. it ignores [`Self::simulate_color_blindness`].
+    /// Only filled rectangles painted below the text are considered as its background.
+    pub contrast_check: Option<ContrastLevel>,
+}
+
+impl ColorDebugOptions {
+    pub fn ui(&mut self, ui: &mut Ui) {
+        let Self {
+            simulate_color_blindness,
+            contrast_check,
+        } = self;
+
+        ComboBox::from_label("Simulate color blindness")
+            .selected_text(simulate_color_blindness.map_or("Off".to_owned(), |c| c.to_string()))
+            .show_ui(ui, |ui| {
+                ui.selectable_value(simulate_color_blindness, None, "Off");
+                for kind in ColorBlindness::ALL {
+                    ui.selectable_value(simulate_color_blindness, Some(kind), kind.to_string());
+                }
+            });
+
+        ui.horizontal(|ui| {
+            ui.label("Check text contrast:");
+            ui.selectable_value(contrast_check, None, "Off");
+            ui.selectable_value(contrast_check, Some(ContrastLevel::Aa), "AA");
+            ui.selectable_value(contrast_check, Some(ContrastLevel::Aaa), "AAA");
+        });
+    }
+}
+
+/// Add, replace or remove the shape mapper implementing the given options.
+pub(crate) fn set_options(ctx: &Context, options: ColorDebugOptions) {
+    if options == ColorDebugOptions::default() {
+        ctx.remove_shape_mapper(MAPPER_NAME);
+    } else {
+        ctx.add_shape_mapper(
+            MAPPER_NAME,
+            std::sync::Arc::new(move |shapes| map_shapes(options, shapes)),
+        );
+    }
+}
+
+fn map_shapes(options: ColorDebugOptions, shapes: &mut Vec<ClippedShape>) {
+    crate::profile_function!();
+
+    // Find the problems before we change any colors:
+    let markers = options
+        .contrast_check
+        .map(|level| find_low_contrast_text(level, shapes))
+        .unwrap_or_default();
+
+    if let Some(kind) = options.simulate_color_blindness {
+        for clipped_shape in shapes.iter_mut() {
+            epaint::shape_transform::adjust_colors(&mut clipped_shape.shape, &|color| {
+                if *color != Color32::PLACEHOLDER {
+                    *color = kind.simulate(*color);
+                }
+            });
+        }
+    }
+
+    // Added last, so that the markers are on top and keep their colors:
+    shapes.extend(markers);
+}
+
+/// Returns a marker for each text with too little contrast.
+fn find_low_contrast_text(level: ContrastLevel, shapes: &[ClippedShape]) -> Vec<ClippedShape> {
+    let mut backgrounds: Vec<(Rect, Color32)> = vec![];
+    let mut markers = vec![];
+
+    for ClippedShape { clip_rect, shape } in shapes {
+        for_each_shape(shape, &mut |shape| match shape {
+            Shape::Rect(RectShape {
+                rect,
+                fill,
+                fill_texture_id,
+                ..
+            }) => {
+                if *fill != Color32::TRANSPARENT && *fill_texture_id == TextureId::default() {
+                    backgrounds.push((rect.intersect(*clip_rect), *fill));
+                }
+            }
+            Shape::Text(text_shape) => {
+                let Some((text_color, font_size)) = text_color_and_size(text_shape) else {
+                    return;
+                };
+                let text_rect = text_shape.visual_bounding_rect().intersect(*clip_rect);
+                if !text_rect.is_positive() {
+                    return;
+                }
+
+                // Blend all the backgrounds that are below the text:
+                let mut background = Color32::TRANSPARENT;
+                for (rect, fill) in &backgrounds {
+                    if rect.contains(text_rect.center()) {
+                        background = blend(background, *fill);
+                    }
+                }
+                if !background.is_opaque() {
+                    return; // We don't know what's behind egui
+                }
+
+                let ratio = contrast_ratio(blend(background, text_color), background);
+                if ratio < level.min_ratio(font_size) {
+                    markers.push(ClippedShape {
+                        clip_rect: Rect::EVERYTHING,
+                        shape: Shape::rect_stroke(
+                            text_rect.expand(1.0),
+                            0.0,
+                            (1.5, Color32::from_rgb(255, 0, 255)),
+                        ),
+                    });
+                }
+            }
+            _ => {}
+        });
+    }
+
+    markers
+}
+
+/// Call `f` for the shape, or for each shape in a [`Shape::Vec`].
+fn for_each_shape(shape: &Shape, f: &mut impl FnMut(&Shape)) {
+    if let Shape::Vec(shapes) = shape {
+        for shape in shapes {
+            for_each_shape(shape, f);
+        }
+    } else {
+        f(shape);
+    }
+}
+
+/// The color of the first glyph, and the font size of the first section.
+fn text_color_and_size(text_shape: &TextShape) -> Option<(Color32, f32)> {
+    let glyph_color = text_shape
+        .galley
+        .rows
+        .iter()
+        .find_map(|row| {
+            row.visuals.mesh.vertices[row.visuals.glyph_vertex_range.clone()]
+                .first()
+                .map(|vertex| vertex.color)
+        })?;
+
+    let mut color = text_shape.override_text_color.unwrap_or(glyph_color);
+    if color == Color32::PLACEHOLDER {
+        color = text_shape.fallback_color;
+    }
+    if text_shape.opacity_factor < 1.0 {
+        color = color.gamma_multiply(text_shape.opacity_factor);
+    }
+    if color == Color32::TRANSPARENT {
+        return None;
+    }
+
+    let font_size = text_shape.galley.job.sections.first()?.format.font_id.size;
+    Some((color, font_size))
+}
+
+/// Premultiplied "over" blending, in gamma space like the painters.
+fn blend(below: Color32, above: Color32) -> Color32 {
+    let alpha = above.a() as f32 / 255.0;
+    let [r, g, b, a] = std::array::from_fn(|i| {
+        (above[i] as f32 + below[i] as f32 * (1.0 - alpha)).round() as u8
+    });
+    Color32::from_rgba_premultiplied(r, g, b, a)
+}
+
+#[test]
+fn test_contrast_ratio() {
+    assert!((contrast_ratio(Color32::BLACK, Color32::WHITE) - 21.0).abs() < 0.01);
+    assert!((contrast_ratio(Color32::WHITE, Color32::BLACK) - 21.0).abs() < 0.01);
+    assert_eq!(contrast_ratio(Color32::RED, Color32::RED), 1.0);
+}
+
+#[test]
+fn test_low_contrast_markers() {
+    let ctx = Context::default();
+    let num_markers = |text_color: Color32| {
+        let output = ctx.run(Default::default(), |ctx| {
+            CentralPanel::default()
+                .frame(Frame::none().fill(Color32::WHITE))
+                .show(ctx, |ui| {
+                    ui.colored_label(text_color, "Hello");
+                });
+        });
+        find_low_contrast_text(ContrastLevel::Aa, &output.shapes).len()
+    };
+
+    assert_eq!(num_markers(Color32::BLACK), 0);
+    assert_eq!(num_markers(Color32::LIGHT_GRAY), 1);
+}
+
+#[test]
+fn test_set_color_debug_options() {
+    let ctx = Context::default();
+    let options = ColorDebugOptions {
+        simulate_color_blindness: Some(ColorBlindness::Deuteranopia),
+        ..Default::default()
+    };
+    ctx.set_color_debug_options(options);
+    assert_eq!(ctx.color_debug_options(), options);
+    assert!(ctx.remove_shape_mapper(MAPPER_NAME));
+
+    ctx.set_color_debug_options(Default::default());
+    assert!(!ctx.remove_shape_mapper(MAPPER_NAME));
+}
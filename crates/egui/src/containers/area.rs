@@ -75,7 +75,11 @@ pub struct Area {
     pivot: Align2,
     anchor: Option<(Align2, Vec2)>,
     new_pos: Option<Pos2>,
+    new_screen_pos: Option<Pos2>,
     edges_padded_for_resize: bool,
+    escape_viewport: bool,
+    snap: Option<SnapOptions>,
+    kinetic: bool,
 }
 
 impl Area {
@@ -90,9 +94,13 @@ impl Area {
             order: Order::Middle,
             default_pos: None,
             new_pos: None,
+            new_screen_pos: None,
             pivot: Align2::LEFT_TOP,
             anchor: None,
             edges_padded_for_resize: false,
+            escape_viewport: false,
+            snap: None,
+            kinetic: false,
         }
     }
 
@@ -162,6 +170,21 @@ impl Area {
         self
     }
 
+    /// Positions the window in global screen coordinates, and prevents it from being moved.
+    ///
+    /// This is useful for overlays that should stay at a fixed place on the physical screen,
+    /// or line up with another viewport (see [`Context::pos_from_viewport`]).
+    ///
+    /// The position is converted to the coordinates of the current viewport
+    /// using [`crate::ViewportInfo::inner_rect`].
+    /// If the integration doesn't report that, the position is used as-is.
+    #[inline]
+    pub fn fixed_screen_pos(mut self, screen_pos: impl Into<Pos2>) -> Self {
+        self.new_screen_pos = Some(screen_pos.into());
+        self.movable = false;
+        self
+    }
+
     /// Constrains this area to the screen bounds.
     #[inline]
     pub fn constrain(mut self, constrain: bool) -> Self {
@@ -179,6 +202,28 @@ impl Area {
         self
     }
 
+    /// Snap to the screen edges, other windows, or a grid while being dragged.
+    ///
+    /// Default: `None`.
+    #[inline]
+    pub fn snap(mut self, snap: SnapOptions) -> Self {
+        self.snap = Some(snap);
+        self
+    }
+
+    /// Let the area glide on when it is thrown,
+    /// i.e. released while being dragged quickly, e.g. on a touch screen.
+    ///
+    /// It slows down and settles inside the screen, against the edge it was thrown at
+    /// (or against whatever it snaps to, see [`Self::snap`]).
+    ///
+    /// Default: `false`.
+    #[inline]
+    pub fn kinetic(mut self, kinetic: bool) -> Self {
+        self.kinetic = kinetic;
+        self
+    }
+
     /// Where the "root" of the area is.
     ///
     /// For instance, if you set this to [`Align2::RIGHT_TOP`]
@@ -216,6 +261,25 @@ impl Area {
         self.movable(false)
     }
 
+    /// If the area would be clipped by the edge of the viewport,
+    /// show it in a small borderless child viewport instead, positioned in screen coordinates.
+    ///
+    /// This is useful for popups, tooltips and menus close to the edge of a window.
+    ///
+    /// Escaping only happens if the integration supports multiple viewports and
+    /// reports [`crate::ViewportInfo::inner_rect`].
+    /// When viewports are embedded (see [`Context::embed_viewports`]),
+    /// the area is shown in the current viewport as usual.
+    ///
+    /// Anchored areas never escape.
+    ///
+    /// Default: `false`.
+    #[inline]
+    pub fn escape_viewport(mut self, escape_viewport: bool) -> Self {
+        self.escape_viewport = escape_viewport;
+        self
+    }
+
     pub(crate) fn get_pivot(&self) -> Align2 {
         if let Some((pivot, _)) = self.anchor {
             pivot
@@ -255,6 +319,10 @@ impl Area {
         ctx: &Context,
         add_contents: impl FnOnce(&mut Ui) -> R,
     ) -> InnerResponse<R> {
+        if let Some((rect, screen_pos)) = self.escaped_rect(ctx) {
+            return self.show_escaped(ctx, rect, screen_pos, add_contents);
+        }
+
         let prepared = self.begin(ctx);
         let mut content_ui = prepared.content_ui(ctx);
         let inner = add_contents(&mut content_ui);
@@ -262,6 +330,106 @@ impl Area {
         InnerResponse { inner, response }
     }
 
+    /// Convert [`Self::fixed_screen_pos`] to the coordinates of the current viewport.
+    fn resolve_screen_pos(mut self, ctx: &Context) -> Self {
+        if let Some(screen_pos) = self.new_screen_pos.take() {
+            self.new_pos = Some(ctx.screen_to_viewport_pos(screen_pos).unwrap_or(screen_pos));
+        }
+        self
+    }
+
+    /// Will this area be shown in its own viewport this frame?
+    ///
+    /// See [`Self::escape_viewport`].
+    pub(crate) fn escapes_viewport(&self, ctx: &Context) -> bool {
+        self.escaped_rect(ctx).is_some()
+    }
+
+    /// If this area should escape into its own viewport, returns its rect
+    /// in the coordinates of the current viewport, and the screen position of its left-top corner.
+    fn escaped_rect(&self, ctx: &Context) -> Option<(Rect, Pos2)> {
+        if !self.escape_viewport || self.anchor.is_some() || ctx.embed_viewports() {
+            return None;
+        }
+
+        let area = self.resolve_screen_pos(ctx);
+
+        // We need to know the size of the area from the previous frame:
+        let mut state = ctx.memory(|mem| mem.areas().get(area.id).copied())?;
+        state.pivot = area.pivot;
+        state.pivot_pos = area.new_pos.unwrap_or(state.pivot_pos);
+        let rect = state.rect();
+
+        if !rect.is_positive() || ctx.screen_rect().contains_rect(rect) {
+            return None;
+        }
+
+        Some((rect, ctx.viewport_to_screen_pos(rect.min)?))
+    }
+
+    fn show_escaped<R>(
+        self,
+        ctx: &Context,
+        rect: Rect,
+        screen_pos: Pos2,
+        add_contents: impl FnOnce(&mut Ui) -> R,
+    ) -> InnerResponse<R> {
+        crate::profile_function!();
+
+        let area = self.resolve_screen_pos(ctx);
+        let layer_id = area.layer();
+        let viewport_id = ViewportId(area.id.with("escaped_viewport"));
+        let builder = ViewportBuilder::default()
+            .with_title("egui popup")
+            .with_decorations(false)
+            .with_resizable(false)
+            .with_transparent(true)
+            .with_taskbar(false)
+            .with_active(false)
+            .with_always_on_top()
+            .with_mouse_passthrough(!area.interactable)
+            .with_position(screen_pos)
+            .with_inner_size(rect.size());
+
+        // In the new viewport the area fills the whole viewport:
+        let child_area = Self {
+            movable: false,
+            constrain: false,
+            constrain_rect: None,
+            default_pos: None,
+            pivot: Align2::LEFT_TOP,
+            anchor: None,
+            new_pos: Some(Pos2::ZERO),
+            new_screen_pos: None,
+            escape_viewport: false,
+            snap: None,
+            kinetic: false,
+            ..area
+        };
+
+        let InnerResponse { inner, response } =
+            ctx.show_viewport_immediate(viewport_id, builder, |ctx, _class| {
+                child_area.show(ctx, add_contents)
+            });
+
+        // Remember the new size, so we know if we still need to escape next frame:
+        ctx.memory_mut(|mem| {
+            let areas = mem.areas_mut();
+            if let Some(mut state) = areas.get(area.id).copied() {
+                state.pivot = area.pivot;
+                state.pivot_pos = area.new_pos.unwrap_or(state.pivot_pos);
+                state.size = response.rect.size();
+                areas.set_state(layer_id, state);
+            }
+        });
+
+        let rect = Rect::from_min_size(rect.min, response.rect.size());
+        InnerResponse {
+            inner,
+            response: response.with_new_rect(rect),
+        }
+    }
+
     pub(crate) fn begin(self, ctx: &Context) -> Prepared {
         let Self {
             id,
@@ -276,7 +444,11 @@ impl Area {
             constrain,
             constrain_rect,
             edges_padded_for_resize,
-        } = self;
+            new_screen_pos: _,
+            escape_viewport: _,
+            snap,
+            kinetic,
+        } = self.resolve_screen_pos(ctx);
 
         let layer_id = LayerId::new(order, id);
 
@@ -289,7 +461,7 @@ impl Area {
             });
         let is_new = state.is_none();
         if is_new {
-            ctx.request_repaint(); // if we don't know the previous size we are likely drawing the area in the wrong place
+            ctx.request_repaint_from(id); // if we don't know the previous size we are likely drawing the area in the wrong place
         }
         let mut state = state.unwrap_or_else(|| State {
             pivot_pos: default_pos.unwrap_or_else(|| automatic_area_position(ctx)),
@@ -329,8 +501,67 @@ impl Area {
                 enabled,
             });
 
+            // Where the area would be without snapping, so that it doesn't stick to what it snaps to:
+            let unsnapped_id = interact_id.with("unsnapped_pos");
             if movable && move_response.dragged() {
-                state.pivot_pos += move_response.drag_delta();
+                if let Some(snap) = &snap {
+                    let unsnapped_pos = ctx
+                        .data(|d| d.get_temp(unsnapped_id))
+                        .unwrap_or_else(|| state.left_top_pos())
+                        + move_response.drag_delta();
+                    ctx.data_mut(|d| d.insert_temp(unsnapped_id, unsnapped_pos));
+                    let rect = Rect::from_min_size(unsnapped_pos, state.size);
+                    state.set_left_top_pos(snap::snap_rect(ctx, layer_id, rect, snap).min);
+                } else {
+                    state.pivot_pos += move_response.drag_delta();
+                }
+            } else if snap.is_some() {
+                ctx.data_mut(|d| d.remove::<Pos2>(unsnapped_id));
+            }
+
+            if movable && kinetic {
+                let glide_id = interact_id.with("glide");
+                if move_response.drag_stopped() {
+                    let velocity = ctx.input(|i| i.pointer.velocity());
+                    let pos = state.left_top_pos();
+                    if MIN_THROW_SPEED <= velocity.length() {
+                        let target = glide_target(
+                            ctx,
+                            layer_id,
+                            state.rect(),
+                            velocity,
+                            snap,
+                            constrain_rect,
+                        );
+                        // Glide from where the area is now:
+                        ctx.animate_value_with_options(
+                            glide_id.with(0),
+                            pos.x,
+                            AnimationOptions::DISABLED,
+                        );
+                        ctx.animate_value_with_options(
+                            glide_id.with(1),
+                            pos.y,
+                            AnimationOptions::DISABLED,
+                        );
+                        ctx.data_mut(|d| d.insert_temp(glide_id, target));
+                    }
+                } else if move_response.dragged() || pointer_pressed_on_area(ctx, layer_id) {
+                    // Catch it:
+                    ctx.data_mut(|d| d.remove::<Pos2>(glide_id));
+                } else if let Some(target) = ctx.data(|d| d.get_temp::<Pos2>(glide_id)) {
+                    let options = AnimationOptions::default()
+                        .with_duration(GLIDE_TIME)
+                        .with_easing(|t| 1.0 - (1.0 - t).powi(3));
+                    let pos = pos2(
+                        ctx.animate_value_with_options(glide_id.with(0), target.x, options),
+                        ctx.animate_value_with_options(glide_id.with(1), target.y, options),
+                    );
+                    state.set_left_top_pos(pos);
+                    if pos == target {
+                        ctx.data_mut(|d| d.remove::<Pos2>(glide_id));
+                    }
+                }
             }
 
             if (move_response.dragged() || move_response.clicked())
@@ -338,7 +569,7 @@ impl Area {
                 || !ctx.memory(|m| m.areas().visible_last_frame(&layer_id))
             {
                 ctx.memory_mut(|m| m.areas_mut().move_to_top(layer_id));
-                ctx.request_repaint();
+                ctx.request_repaint_from(layer_id.id);
             }
 
             move_response
@@ -475,6 +706,35 @@ impl Prepared {
     }
 }
 
+/// How long a thrown [`Area::kinetic`] glides.
+const GLIDE_TIME: f32 = 0.5;
+
+/// How fast (in points per second) an [`Area::kinetic`] must be released to glide.
+const MIN_THROW_SPEED: f32 = 100.0;
+
+/// Where an [`Area::kinetic`] glides to (its left top corner),
+/// when it is released at `rect` with the pointer moving at `velocity`.
+fn glide_target(
+    ctx: &Context,
+    layer_id: LayerId,
+    rect: Rect,
+    velocity: Vec2,
+    snap: Option<SnapOptions>,
+    constrain_rect: Option<Rect>,
+) -> Pos2 {
+    // The glide starts at `velocity` and slows down with the easing of the animation:
+    let target = rect.translate(velocity * GLIDE_TIME / 3.0);
+    let mut target = ctx.constrain_window_rect_to_area(target, constrain_rect);
+    if let Some(snap) = snap {
+        let snap = SnapOptions {
+            show_guides: false,
+            ..snap
+        };
+        target = snap::snap_rect(ctx, layer_id, target, &snap);
+    }
+    target.min
+}
+
 fn pointer_pressed_on_area(ctx: &Context, layer_id: LayerId) -> bool {
     if let Some(pointer_pos) = ctx.pointer_interact_pos() {
         let any_pressed = ctx.input(|i| i.pointer.any_pressed());
@@ -553,3 +813,39 @@ fn automatic_area_position(ctx: &Context) -> Pos2 {
     }
     best_pos
 }
+
+#[test]
+fn test_kinetic_area() {
+    use crate::test_harness::Harness;
+
+    let mut harness = Harness::new(|ctx| {
+        Window::new("Thrown")
+            .default_pos(pos2(100.0, 100.0))
+            .kinetic(true)
+            .show(ctx, |ui| {
+                ui.label("Throw me");
+            });
+        Window::new("Dropped")
+            .default_pos(pos2(100.0, 300.0))
+            .show(ctx, |ui| {
+                ui.label("Drop me");
+            });
+    });
+    harness.set_size(vec2(800.0, 600.0));
+    let window_rect = |harness: &Harness<'_>, id: &str| {
+        harness
+            .ctx()
+            .memory(|mem| mem.area_rect(Id::new(id)))
+            .unwrap()
+    };
+
+    // Throw both windows quickly to the right:
+    for id in ["Thrown", "Dropped"] {
+        let from = window_rect(&harness, id).center();
+        harness.drag(from, from + vec2(400.0, 0.0));
+    }
+
+    // The kinetic window glides on until it hits the edge of the screen:
+    assert!((window_rect(&harness, "Thrown").right() - 800.0).abs() < 1.0);
+    assert_eq!(window_rect(&harness, "Dropped").left(), 500.0);
+}
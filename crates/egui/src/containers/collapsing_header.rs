@@ -65,7 +65,7 @@ impl CollapsingState {
 
     pub fn toggle(&mut self, ui: &Ui) {
         self.state.open = !self.state.open;
-        ui.ctx().request_repaint();
+        ui.ctx().request_repaint_from(self.id);
     }
 
     /// 0 for closed, 1 for open, with tweening
@@ -588,6 +588,9 @@ impl CollapsingHeader {
                 }
             }
 
+            if !galley.elided {
+                header_response = ui.on_hover_text_if_clipped(header_response, &galley, text_pos);
+            }
             ui.painter().galley(text_pos, galley, visuals.text_color());
         }
 
@@ -1,6 +1,9 @@
 use epaint::Shape;
 
-use crate::{style::WidgetVisuals, *};
+use crate::{
+    style::{WidgetState, WidgetVisuals},
+    *,
+};
 
 #[allow(unused_imports)] // Documentation
 use crate::style::Spacing;
@@ -322,11 +325,9 @@ fn combo_box_dyn<'c, R>(
 
         if ui.is_rect_visible(rect) {
             let icon_rect = Align2::RIGHT_CENTER.align_size_within_rect(icon_size, rect);
-            let visuals = if is_popup_open {
-                &ui.visuals().widgets.open
-            } else {
-                ui.style().interact(&response)
-            };
+            let visuals = &ui
+                .style()
+                .interact_with_state(WidgetState::from_response(&response).open(is_popup_open));
 
             if let Some(icon) = icon {
                 icon(
@@ -409,11 +410,9 @@ fn button_frame(
     let response = ui.interact(outer_rect, id, sense);
 
     if ui.is_rect_visible(outer_rect) {
-        let visuals = if is_popup_open {
-            &ui.visuals().widgets.open
-        } else {
-            ui.style().interact(&response)
-        };
+        let visuals = &ui
+            .style()
+            .interact_with_state(WidgetState::from_response(&response).open(is_popup_open));
 
         ui.painter().set(
             where_to_put_background,
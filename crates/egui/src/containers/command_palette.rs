@@ -0,0 +1,379 @@
+//! A Ctrl+P style list of commands to search through, see [`CommandPalette`].
+
+use crate::*;
+
+/// How many recently used commands are remembered, per palette.
+const MAX_RECENT: usize = 20;
+
+/// A Ctrl+P style popup for searching through a list of commands and picking one.
+///
+/// It opens with its [`Self::shortcut`], or [`Self::open`], and dims the rest of the screen
+/// until a command is picked, or it is closed with Escape or a click outside of it.
+///
+/// The commands are filtered with a fuzzy search: typing `"of"` finds `"Open file"`.
+/// The arrow keys move the selection, and Enter picks the selected command.
+/// Recently picked commands are ranked first, and remembered in [`Memory`].
+///
+/// ```
+/// # egui::__run_test_ctx(|ctx| {
+/// let picked = egui::CommandPalette::new("commands")
+///     .command("open", "Open file…")
+///     .command("save", "Save")
+///     .command("quit", "Quit")
+///     .show(ctx);
+///
+/// if picked == Some(egui::Id::new("save")) {
+///     // …
+/// }
+/// # });
+/// ```
+#[must_use = "You should call .show()"]
+pub struct CommandPalette {
+    id: Id,
+    commands: Vec<(Id, String)>,
+    shortcut: Option<KeyboardShortcut>,
+    hint_text: WidgetText,
+    width: f32,
+}
+
+/// What is typed into an open [`CommandPalette`].
+#[derive(Clone, Default)]
+struct State {
+    open: bool,
+    query: String,
+
+    /// Index into the matching commands.
+    selected: usize,
+}
+
+impl CommandPalette {
+    pub fn new(id_source: impl std::hash::Hash) -> Self {
+        Self {
+            id: Id::new(id_source),
+            commands: Vec::new(),
+            shortcut: Some(KeyboardShortcut::new(Modifiers::COMMAND, Key::P)),
+            hint_text: "Search commands…".into(),
+            width: 400.0,
+        }
+    }
+
+    /// Add a command to pick from.
+    ///
+    /// When it is picked, [`Self::show`] returns `Id::new(id)`.
+    #[inline]
+    pub fn command(mut self, id: impl std::hash::Hash, label: impl Into<String>) -> Self {
+        self.commands.push((Id::new(id), label.into()));
+        self
+    }
+
+    /// Add several commands, see [`Self::command`].
+    pub fn commands(
+        mut self,
+        commands: impl IntoIterator<Item = (impl std::hash::Hash, impl Into<String>)>,
+    ) -> Self {
+        self.commands.extend(
+            commands
+                .into_iter()
+                .map(|(id, label)| (Id::new(id), label.into())),
+        );
+        self
+    }
+
+    /// The shortcut that opens and closes the palette.
+    ///
+    /// Default: Ctrl+P (⌘P on Mac).
+    #[inline]
+    pub fn shortcut(mut self, shortcut: impl Into<Option<KeyboardShortcut>>) -> Self {
+        self.shortcut = shortcut.into();
+        self
+    }
+
+    /// Shown in the search field when it is empty.
+    #[inline]
+    pub fn hint_text(mut self, hint_text: impl Into<WidgetText>) -> Self {
+        self.hint_text = hint_text.into();
+        self
+    }
+
+    /// Width of the palette in points. Default: `400.0`.
+    #[inline]
+    pub fn width(mut self, width: f32) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Open the palette with this `id_source`, e.g. from a menu button.
+    pub fn open(ctx: &Context, id_source: impl std::hash::Hash) {
+        let state = State {
+            open: true,
+            ..Default::default()
+        };
+        ctx.data_mut(|d| d.insert_temp(Id::new(id_source), state));
+    }
+
+    /// Close the palette with this `id_source`.
+    pub fn close(ctx: &Context, id_source: impl std::hash::Hash) {
+        ctx.data_mut(|d| d.insert_temp(Id::new(id_source), State::default()));
+    }
+
+    /// Is the palette with this `id_source` open?
+    pub fn is_open(ctx: &Context, id_source: impl std::hash::Hash) -> bool {
+        ctx.data(|d| d.get_temp::<State>(Id::new(id_source)))
+            .map_or(false, |state| state.open)
+    }
+
+    /// Show the palette, if it is open.
+    ///
+    /// Returns the id of the command that was picked this frame, if any.
+    pub fn show(self, ctx: &Context) -> Option<Id> {
+        let Self {
+            id,
+            commands,
+            shortcut,
+            hint_text,
+            width,
+        } = self;
+
+        let mut state = ctx.data(|d| d.get_temp::<State>(id)).unwrap_or_default();
+        if let Some(shortcut) = &shortcut {
+            if ctx.input_mut(|i| i.consume_shortcut(shortcut)) {
+                state = State {
+                    open: !state.open,
+                    ..Default::default()
+                };
+            }
+        }
+        if !state.open {
+            ctx.data_mut(|d| d.insert_temp(id, state));
+            return None;
+        }
+
+        // Take these keys before the search field sees them:
+        let (up, down, enter, escape) = ctx.input_mut(|i| {
+            (
+                i.consume_key(Modifiers::NONE, Key::ArrowUp),
+                i.consume_key(Modifiers::NONE, Key::ArrowDown),
+                i.consume_key(Modifiers::NONE, Key::Enter),
+                i.consume_key(Modifiers::NONE, Key::Escape),
+            )
+        });
+        let recent_id = id.with("recent");
+        let recent: Vec<Id> = ctx
+            .data_mut(|d| d.get_persisted(recent_id))
+            .unwrap_or_default();
+
+        // Dim everything behind the palette, and close it on a click outside of it:
+        let screen_rect = ctx.screen_rect();
+        let overlay = Area::new(id.with("overlay"))
+            .order(Order::Foreground)
+            .fixed_pos(screen_rect.min)
+            .show(ctx, |ui| {
+                ui.painter()
+                    .rect_filled(screen_rect, 0.0, Color32::from_black_alpha(100));
+                ui.allocate_rect(screen_rect, Sense::click())
+            })
+            .inner;
+
+        let mut picked = None;
+        let area = Area::new(id)
+            .order(Order::Foreground)
+            .anchor(Align2::CENTER_TOP, vec2(0.0, 0.15 * screen_rect.height()))
+            .show(ctx, |ui| {
+                Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.set_width(width);
+
+                    let search = ui.add(
+                        TextEdit::singleline(&mut state.query)
+                            .hint_text(hint_text)
+                            .desired_width(f32::INFINITY),
+                    );
+                    search.request_focus();
+                    if search.changed() {
+                        state.selected = 0;
+                    }
+
+                    let matches = rank(&commands, &state.query, &recent);
+                    let last = matches.len().saturating_sub(1);
+                    if up {
+                        state.selected = state.selected.saturating_sub(1);
+                    }
+                    if down {
+                        state.selected += 1;
+                    }
+                    state.selected = state.selected.min(last);
+                    if enter {
+                        picked = matches.get(state.selected).copied();
+                    }
+
+                    ui.separator();
+                    if matches.is_empty() {
+                        ui.weak("No matching commands");
+                    }
+                    ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                        ui.with_layout(Layout::top_down_justified(Align::LEFT), |ui| {
+                            for (row, &index) in matches.iter().enumerate() {
+                                let is_selected = row == state.selected;
+                                let response =
+                                    ui.selectable_label(is_selected, commands[index].1.as_str());
+                                if is_selected && (up || down) {
+                                    response.scroll_to_me(None);
+                                }
+                                if response.clicked() {
+                                    picked = Some(index);
+                                }
+                            }
+                        });
+                    });
+                });
+            });
+        ctx.move_to_top(area.response.layer_id);
+
+        let picked = picked.map(|index| commands[index].0);
+        if let Some(picked) = picked {
+            ctx.data_mut(|d| {
+                let recent = d.get_persisted_mut_or_default::<Vec<Id>>(recent_id);
+                recent.retain(|&id| id != picked);
+                recent.insert(0, picked);
+                recent.truncate(MAX_RECENT);
+            });
+        }
+        if picked.is_some() || escape || overlay.clicked() {
+            state = State::default();
+        }
+        ctx.data_mut(|d| d.insert_temp(id, state));
+
+        picked
+    }
+}
+
+/// The indices of the commands that match `query`, best first.
+///
+/// Recently used commands are ranked higher, and first of all when the query is empty.
+fn rank(commands: &[(Id, String)], query: &str, recent: &[Id]) -> Vec<usize> {
+    let mut matches: Vec<(usize, i32)> = commands
+        .iter()
+        .enumerate()
+        .filter_map(|(index, (id, label))| {
+            let score = fuzzy_score(query, label)?;
+            let recency = recent
+                .iter()
+                .position(|recent| recent == id)
+                .map_or(0, |position| (MAX_RECENT - position) as i32);
+            Some((index, score + recency))
+        })
+        .collect();
+    matches.sort_by_key(|&(index, score)| (std::cmp::Reverse(score), index));
+    matches.into_iter().map(|(index, _)| index).collect()
+}
+
+/// How well `query` matches `text`, or `None` if it doesn't.
+///
+/// All characters of the query must be in the text, in the same order, ignoring case and spaces.
+/// Matches at the start of words, and runs of consecutive matches, score higher.
+fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+    let mut query = query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .peekable();
+
+    let mut score = 0;
+    let mut previous: Option<char> = None;
+    let mut previous_matched = false;
+    for c in text.chars() {
+        let Some(&wanted) = query.peek() else {
+            break;
+        };
+        if c.to_lowercase().next() == Some(wanted) {
+            score += 1;
+            if previous_matched {
+                score += 5;
+            }
+            let word_start = previous.map_or(true, |previous| {
+                !previous.is_alphanumeric() || (previous.is_lowercase() && c.is_uppercase())
+            });
+            if word_start {
+                score += 10;
+            }
+            query.next();
+            previous_matched = true;
+        } else {
+            score -= 1;
+            previous_matched = false;
+        }
+        previous = Some(c);
+    }
+
+    query.peek().is_none().then_some(score)
+}
+
+#[test]
+fn test_fuzzy_score() {
+    assert_eq!(fuzzy_score("", "Anything"), Some(0));
+    assert_eq!(fuzzy_score("xyz", "Open file"), None);
+    assert_eq!(fuzzy_score("fo", "Open file"), None, "order matters");
+
+    // Word starts and consecutive matches win:
+    let score = |query| fuzzy_score(query, "Open file").unwrap();
+    assert!(score("of") > score("pi"));
+    assert!(score("open") > score("oe"));
+    assert!(score("OPEN") == score("open"));
+    assert!(fuzzy_score("sa", "Save all").unwrap() > fuzzy_score("sa", "Reset camera").unwrap());
+}
+
+#[test]
+fn test_command_palette() {
+    use crate::test_harness::Harness;
+    use std::cell::RefCell;
+
+    let picked = RefCell::new(vec![]);
+    let mut harness = Harness::new(|ctx| {
+        CentralPanel::default().show(ctx, |ui| {
+            ui.label("Behind");
+        });
+        let palette = CommandPalette::new("palette")
+            .command("open", "Open file")
+            .command("save", "Save")
+            .command("save_all", "Save all");
+        if let Some(id) = palette.show(ctx) {
+            picked.borrow_mut().push(id);
+        }
+    });
+    let labels = |harness: &Harness<'_>| {
+        let mut nodes = harness.query_all(|info| info.typ == WidgetType::SelectableLabel);
+        nodes.sort_by(|a, b| a.rect.rect.top().total_cmp(&b.rect.rect.top()));
+        nodes
+            .into_iter()
+            .map(|node| (node.info.label.unwrap(), node.info.selected.unwrap()))
+            .collect::<Vec<_>>()
+    };
+
+    // Opened with the shortcut:
+    assert!(labels(&harness).is_empty());
+    harness.set_modifiers(Modifiers::COMMAND);
+    harness.press_key(Key::P);
+    harness.set_modifiers(Modifiers::NONE);
+    assert!(CommandPalette::is_open(harness.ctx(), "palette"));
+    assert_eq!(labels(&harness).len(), 3);
+
+    // Fuzzy search and keyboard navigation:
+    harness.type_text("sa");
+    harness.press_key(Key::ArrowDown);
+    assert_eq!(
+        labels(&harness),
+        vec![("Save".to_owned(), false), ("Save all".to_owned(), true)]
+    );
+    harness.press_key(Key::Enter);
+    assert_eq!(*picked.borrow(), vec![Id::new("save_all")]);
+    assert!(!CommandPalette::is_open(harness.ctx(), "palette"));
+
+    // Recently used commands come first:
+    CommandPalette::open(harness.ctx(), "palette");
+    harness.run();
+    assert_eq!(labels(&harness)[0], ("Save all".to_owned(), true));
+
+    // Clicking outside closes it:
+    harness.click(pos2(10.0, 590.0));
+    assert!(!CommandPalette::is_open(harness.ctx(), "palette"));
+    assert_eq!(picked.borrow().len(), 1);
+}
@@ -0,0 +1,991 @@
+//! Tabs that the user can rearrange into splits and floating windows, like in an IDE.
+//!
+//! See [`Dock`].
+
+use crate::util::id_type_map::SerializableAny;
+use crate::*;
+
+/// How the two children of a [`DockNode::Split`] are placed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum SplitDirection {
+    /// Side by side, the first one to the left.
+    Horizontal,
+
+    /// Above each other, the first one on top.
+    Vertical,
+}
+
+/// A group of tabs, of which one is shown at a time.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct TabGroup<Tab> {
+    pub tabs: Vec<Tab>,
+
+    /// Index of the tab that is shown.
+    pub active: usize,
+}
+
+impl<Tab> Default for TabGroup<Tab> {
+    fn default() -> Self {
+        Self {
+            tabs: Vec::new(),
+            active: 0,
+        }
+    }
+}
+
+impl<Tab> TabGroup<Tab> {
+    pub fn new(tabs: Vec<Tab>) -> Self {
+        Self { tabs, active: 0 }
+    }
+
+    /// Add a tab at the end, and show it.
+    pub fn push(&mut self, tab: Tab) {
+        self.tabs.push(tab);
+        self.active = self.tabs.len() - 1;
+    }
+
+    /// Remove the tab at the given index, keeping the same tab active if possible.
+    pub fn remove(&mut self, index: usize) -> Tab {
+        let tab = self.tabs.remove(index);
+        if index < self.active || self.tabs.len() <= self.active {
+            self.active = self.active.saturating_sub(1);
+        }
+        tab
+    }
+}
+
+/// Part of the layout of a [`DockState`]: either a group of tabs, or a split in two.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum DockNode<Tab> {
+    Tabs(TabGroup<Tab>),
+
+    Split {
+        direction: SplitDirection,
+
+        /// How much of the space goes to the first child, in `0..=1`.
+        fraction: f32,
+
+        children: Box<[DockNode<Tab>; 2]>,
+    },
+}
+
+impl<Tab> Default for DockNode<Tab> {
+    fn default() -> Self {
+        Self::Tabs(TabGroup::default())
+    }
+}
+
+impl<Tab> DockNode<Tab> {
+    /// A group of tabs.
+    pub fn tabs(tabs: Vec<Tab>) -> Self {
+        Self::Tabs(TabGroup::new(tabs))
+    }
+
+    /// `left` and `right` side by side, with `fraction` of the width going to `left`.
+    pub fn horizontal(left: Self, right: Self, fraction: f32) -> Self {
+        Self::Split {
+            direction: SplitDirection::Horizontal,
+            fraction,
+            children: Box::new([left, right]),
+        }
+    }
+
+    /// `top` above `bottom`, with `fraction` of the height going to `top`.
+    pub fn vertical(top: Self, bottom: Self, fraction: f32) -> Self {
+        Self::Split {
+            direction: SplitDirection::Vertical,
+            fraction,
+            children: Box::new([top, bottom]),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        match self {
+            Self::Tabs(group) => group.tabs.is_empty(),
+            Self::Split { .. } => false,
+        }
+    }
+
+    fn for_each_tab<'a>(&'a self, f: &mut impl FnMut(&'a Tab)) {
+        match self {
+            Self::Tabs(group) => group.tabs.iter().for_each(f),
+            Self::Split { children, .. } => {
+                children[0].for_each_tab(f);
+                children[1].for_each_tab(f);
+            }
+        }
+    }
+
+    fn first_group_mut(&mut self) -> &mut TabGroup<Tab> {
+        match self {
+            Self::Tabs(group) => group,
+            Self::Split { children, .. } => children[0].first_group_mut(),
+        }
+    }
+
+    fn node_mut(&mut self, path: &[usize]) -> Option<&mut Self> {
+        match (path.split_first(), self) {
+            (None, node) => Some(node),
+            (Some((&i, rest)), Self::Split { children, .. }) => children.get_mut(i)?.node_mut(rest),
+            (Some(_), Self::Tabs(_)) => None,
+        }
+    }
+
+    /// Replace splits with an empty side by the other side.
+    fn remove_empty(&mut self) {
+        if let Self::Split { children, .. } = self {
+            children[0].remove_empty();
+            children[1].remove_empty();
+            if let Some(keep) = [1, 0].into_iter().find(|&i| children[1 - i].is_empty()) {
+                *self = std::mem::take(&mut children[keep]);
+            }
+        }
+    }
+}
+
+/// A group of tabs in its own [`Window`], or its own viewport (see [`Dock::floating_viewports`]).
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct FloatingTabs<Tab> {
+    /// The id of the window.
+    pub id: Id,
+
+    /// Where the window was created, relative to the viewport of the dock.
+    pub pos: Pos2,
+
+    pub group: TabGroup<Tab>,
+}
+
+/// The layout of a [`Dock`], and all its tabs.
+///
+/// By default [`Dock::show`] stores this in [`Memory::data`],
+/// so that it is persisted together with the rest of the egui state.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct DockState<Tab> {
+    pub root: DockNode<Tab>,
+
+    /// Tabs that have been dragged out of the dock, top-most last.
+    pub floating: Vec<FloatingTabs<Tab>>,
+
+    /// Used to give each floating window a unique id.
+    next_floating_nr: u64,
+}
+
+impl<Tab> DockState<Tab> {
+    /// All tabs in a single group.
+    pub fn new(tabs: Vec<Tab>) -> Self {
+        Self::from_root(DockNode::tabs(tabs))
+    }
+
+    /// Start with the given layout, e.g. made with [`DockNode::horizontal`].
+    pub fn from_root(root: DockNode<Tab>) -> Self {
+        Self {
+            root,
+            floating: Vec::new(),
+            next_floating_nr: 0,
+        }
+    }
+
+    /// All tabs, in no particular order.
+    pub fn tabs(&self) -> Vec<&Tab> {
+        let mut tabs = Vec::new();
+        self.root.for_each_tab(&mut |tab| tabs.push(tab));
+        for floating in &self.floating {
+            tabs.extend(&floating.group.tabs);
+        }
+        tabs
+    }
+
+    /// Add a tab to the first group of the dock, and show it.
+    pub fn push_tab(&mut self, tab: Tab) {
+        self.root.first_group_mut().push(tab);
+    }
+
+    /// Close the floating window with the given id.
+    ///
+    /// The tabs for which `on_close` returns `false` are put back in the dock.
+    fn close_floating(&mut self, id: Id, mut on_close: impl FnMut(&mut Tab) -> bool) {
+        let Some(index) = self.floating.iter().position(|floating| floating.id == id) else {
+            return;
+        };
+        for mut tab in self.floating.remove(index).group.tabs {
+            if !on_close(&mut tab) {
+                self.push_tab(tab);
+            }
+        }
+    }
+
+    fn group_mut(&mut self, location: &GroupLocation) -> Option<&mut TabGroup<Tab>> {
+        match location.surface {
+            Surface::Root => match self.root.node_mut(&location.path)? {
+                DockNode::Tabs(group) => Some(group),
+                DockNode::Split { .. } => None,
+            },
+            Surface::Floating(id) => self
+                .floating
+                .iter_mut()
+                .find(|floating| floating.id == id)
+                .map(|floating| &mut floating.group),
+        }
+    }
+
+    /// Move a tab to the given group (splitting it if `zone` is not [`DropZone::Center`]),
+    /// or to a new floating window of the dock with the given id at `float_pos` if `to` is `None`.
+    fn move_tab(
+        &mut self,
+        dock_id: Id,
+        from: &TabLocation,
+        to: Option<(GroupLocation, DropZone)>,
+        float_pos: Pos2,
+    ) {
+        let Some(source) = self.group_mut(&from.group) else {
+            return;
+        };
+        let num_tabs = source.tabs.len();
+        if num_tabs <= from.index {
+            return;
+        }
+
+        match &to {
+            Some((target, zone)) => {
+                let same_group = *target == from.group;
+                if same_group && (*zone == DropZone::Center || num_tabs == 1) {
+                    return; // Nowhere to move
+                }
+                let can_split = target.surface == Surface::Root;
+                if self.group_mut(target).is_none() || (*zone != DropZone::Center && !can_split) {
+                    return;
+                }
+            }
+            None => {
+                if matches!(from.group.surface, Surface::Floating(_)) && num_tabs == 1 {
+                    return; // Already alone in a window
+                }
+            }
+        }
+
+        let Some(tab) = self
+            .group_mut(&from.group)
+            .map(|group| group.remove(from.index))
+        else {
+            return;
+        };
+
+        match to {
+            Some((target, DropZone::Center)) => {
+                if let Some(group) = self.group_mut(&target) {
+                    group.push(tab);
+                }
+            }
+            Some((target, zone)) => {
+                if let Some(node) = self.root.node_mut(&target.path) {
+                    let old = std::mem::take(node);
+                    let new = DockNode::tabs(vec![tab]);
+                    *node = match zone {
+                        DropZone::Left => DockNode::horizontal(new, old, 0.5),
+                        DropZone::Right => DockNode::horizontal(old, new, 0.5),
+                        DropZone::Top => DockNode::vertical(new, old, 0.5),
+                        DropZone::Bottom | DropZone::Center => DockNode::vertical(old, new, 0.5),
+                    };
+                }
+            }
+            None => {
+                self.floating.push(FloatingTabs {
+                    id: dock_id.with(("floating", self.next_floating_nr)),
+                    pos: float_pos,
+                    group: TabGroup::new(vec![tab]),
+                });
+                self.next_floating_nr += 1;
+            }
+        }
+
+        self.root.remove_empty();
+        self.floating
+            .retain(|floating| !floating.group.tabs.is_empty());
+    }
+}
+
+impl<Tab: SerializableAny> DockState<Tab> {
+    /// Load the state of the [`Dock`] with the given id (see [`Dock::id`]).
+    pub fn load(ctx: &Context, id: Id) -> Option<Self> {
+        ctx.data_mut(|d| d.get_persisted(id))
+    }
+
+    pub fn store(self, ctx: &Context, id: Id) {
+        ctx.data_mut(|d| d.insert_persisted(id, self));
+    }
+}
+
+/// Shows your tabs in a [`Dock`].
+pub trait TabViewer {
+    type Tab;
+
+    /// The text on the tab.
+    fn title(&mut self, tab: &mut Self::Tab) -> WidgetText;
+
+    /// Show the contents of the tab.
+    fn ui(&mut self, ui: &mut Ui, tab: &mut Self::Tab);
+
+    /// Called when the user closes the floating window the tab is in.
+    ///
+    /// Return `false` to put the tab back in the dock instead of removing it.
+    fn on_close(&mut self, _tab: &mut Self::Tab) -> bool {
+        true
+    }
+
+    /// A unique id for the tab, used as the id of its [`Ui`].
+    ///
+    /// This should stay the same when the tab is moved, so that e.g. scroll positions are remembered.
+    /// By default the title is used.
+    fn id(&mut self, tab: &mut Self::Tab) -> Id {
+        Id::new(self.title(tab).text())
+    }
+}
+
+/// Where a dragged tab will end up, relative to the group of tabs it is dropped on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum DropZone {
+    /// Added to the group.
+    Center,
+
+    /// In a new group, splitting the target group.
+    Left,
+    Right,
+    Top,
+    Bottom,
+}
+
+impl DropZone {
+    fn at(rect: Rect, pos: Pos2, allow_split: bool) -> Self {
+        if !allow_split {
+            return Self::Center;
+        }
+        let rel = (pos - rect.min) / rect.size();
+        let (dist, zone) = [
+            (rel.x, Self::Left),
+            (1.0 - rel.x, Self::Right),
+            (rel.y, Self::Top),
+            (1.0 - rel.y, Self::Bottom),
+        ]
+        .into_iter()
+        .min_by(|a, b| a.0.total_cmp(&b.0))
+        .unwrap_or((1.0, Self::Center));
+        if dist < 0.25 {
+            zone
+        } else {
+            Self::Center
+        }
+    }
+
+    /// Where the tab will be shown, for previewing the drop.
+    fn preview_rect(self, rect: Rect) -> Rect {
+        let mut preview = rect;
+        match self {
+            Self::Center => {}
+            Self::Left => preview.max.x = rect.center().x,
+            Self::Right => preview.min.x = rect.center().x,
+            Self::Top => preview.max.y = rect.center().y,
+            Self::Bottom => preview.min.y = rect.center().y,
+        }
+        preview
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Surface {
+    /// The dock itself.
+    Root,
+
+    /// A floating window.
+    Floating(Id),
+}
+
+/// Which group of tabs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct GroupLocation {
+    surface: Surface,
+
+    /// Path to the group in the tree of [`DockNode`]s, for [`Surface::Root`].
+    path: Vec<usize>,
+}
+
+/// A tab that is being dragged.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct TabLocation {
+    group: GroupLocation,
+    index: usize,
+}
+
+/// A group of tabs that a tab can be dropped on.
+struct DropTarget {
+    location: GroupLocation,
+    rect: Rect,
+
+    /// For floating windows: the layer of the window.
+    layer_id: Option<LayerId>,
+}
+
+/// A container of tabs that the user can rearrange by dragging them:
+/// into another group, to the side of a group to split it,
+/// or outside the dock to put the tab in its own [`Window`].
+///
+/// The layout is stored in [`Memory::data`], see [`DockState`].
+///
+/// Floating tabs are shown in a [`Window`] in the same viewport as the dock,
+/// or in their own native window with [`Self::floating_viewports`].
+/// Closing a floating window removes its tabs, see [`TabViewer::on_close`].
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// struct MyTabs;
+///
+/// impl egui::TabViewer for MyTabs {
+///     type Tab = String;
+///
+///     fn title(&mut self, tab: &mut String) -> egui::WidgetText {
+///         tab.as_str().into()
+///     }
+///
+///     fn ui(&mut self, ui: &mut egui::Ui, tab: &mut String) {
+///         ui.label(format!("Contents of {tab}"));
+///     }
+/// }
+///
+/// egui::Dock::new("my_dock").show(
+///     ui,
+///     || egui::DockState::new(vec!["Files".to_owned(), "Editor".to_owned()]),
+///     &mut MyTabs,
+/// );
+/// # });
+/// ```
+#[must_use = "You should call .show()"]
+#[derive(Clone, Debug)]
+pub struct Dock {
+    id_source: Id,
+    allow_floating: bool,
+    floating_viewports: bool,
+}
+
+impl Dock {
+    pub fn new(id_source: impl std::hash::Hash) -> Self {
+        Self {
+            id_source: Id::new(id_source),
+            allow_floating: true,
+            floating_viewports: false,
+        }
+    }
+
+    /// Can tabs be dragged out of the dock into their own window?
+    ///
+    /// Default: `true`.
+    #[inline]
+    pub fn allow_floating(mut self, allow_floating: bool) -> Self {
+        self.allow_floating = allow_floating;
+        self
+    }
+
+    /// Show floating tabs in their own viewport (native window) instead of a [`Window`].
+    ///
+    /// Falls back to a [`Window`] if the backend does not support multiple viewports,
+    /// see [`Context::embed_viewports`].
+    ///
+    /// Default: `false`.
+    #[inline]
+    pub fn floating_viewports(mut self, floating_viewports: bool) -> Self {
+        self.floating_viewports = floating_viewports;
+        self
+    }
+
+    /// The id used to store the [`DockState`] when shown in this [`Ui`].
+    pub fn id(&self, ui: &Ui) -> Id {
+        ui.make_persistent_id(self.id_source)
+    }
+
+    /// Fill the available space with the dock.
+    ///
+    /// The layout is loaded from [`Memory::data`], or created with `default_state` the first time.
+    pub fn show<Tab: SerializableAny>(
+        self,
+        ui: &mut Ui,
+        default_state: impl FnOnce() -> DockState<Tab>,
+        viewer: &mut impl TabViewer<Tab = Tab>,
+    ) -> Response {
+        let id = self.id(ui);
+        let mut state = DockState::load(ui.ctx(), id).unwrap_or_else(default_state);
+        let response = self.show_state(ui, &mut state, viewer);
+        state.store(ui.ctx(), id);
+        response
+    }
+
+    /// Like [`Self::show`], but with a [`DockState`] that you store yourself.
+    pub fn show_state<Tab>(
+        self,
+        ui: &mut Ui,
+        state: &mut DockState<Tab>,
+        viewer: &mut impl TabViewer<Tab = Tab>,
+    ) -> Response {
+        let id = self.id(ui);
+        let drag_id = id.with("dragged_tab");
+        let rect = ui.available_rect_before_wrap();
+        let response = ui.allocate_rect(rect, Sense::hover());
+
+        let mut dragged: Option<TabLocation> = ui.data(|d| d.get_temp(drag_id));
+        let mut targets = Vec::new();
+
+        let mut path = Vec::new();
+        show_node(
+            ui,
+            id,
+            &mut state.root,
+            rect,
+            &mut path,
+            viewer,
+            &mut targets,
+            &mut dragged,
+        );
+
+        let floating_viewports = self.floating_viewports && !ui.ctx().embed_viewports();
+        let dock_viewport_rect = ui.input(|i| i.viewport().inner_rect);
+
+        // The pointer of the viewport a tab is being dragged from, in the coordinates of the dock:
+        let mut pointer_from_viewport = None;
+
+        let mut closed = Vec::new();
+        for floating in &mut state.floating {
+            let location = GroupLocation {
+                surface: Surface::Floating(floating.id),
+                path: Vec::new(),
+            };
+            let group = &mut floating.group;
+            let title = group
+                .tabs
+                .get_mut(group.active)
+                .map_or_else(WidgetText::default, |tab| viewer.title(tab));
+            let mut open = true;
+
+            if floating_viewports {
+                let mut builder = ViewportBuilder::default()
+                    .with_title(title.text())
+                    .with_inner_size(vec2(320.0, 240.0));
+                if let Some(dock_viewport_rect) = dock_viewport_rect {
+                    builder =
+                        builder.with_position(dock_viewport_rect.min + floating.pos.to_vec2());
+                }
+                ui.ctx().show_viewport_immediate(
+                    ViewportId(floating.id),
+                    builder,
+                    |ctx, _class| {
+                        CentralPanel::default().show(ctx, |ui| {
+                            let rect = ui.available_rect_before_wrap();
+                            ui.allocate_rect(rect, Sense::hover());
+                            show_group(ui, group, rect, &location, viewer, &mut dragged);
+                        });
+
+                        let (close_requested, viewport_rect, pointer_pos, released) =
+                            ctx.input(|i| {
+                                (
+                                    i.viewport().close_requested(),
+                                    i.viewport().inner_rect,
+                                    i.pointer.interact_pos(),
+                                    !i.pointer.primary_down(),
+                                )
+                            });
+                        open = !close_requested;
+                        if dragged
+                            .as_ref()
+                            .map_or(false, |from| from.group == location)
+                        {
+                            let offset = viewport_rect.zip(dock_viewport_rect).map(
+                                |(viewport_rect, dock_rect)| viewport_rect.min - dock_rect.min,
+                            );
+                            let pointer_pos =
+                                pointer_pos.zip(offset).map(|(pos, offset)| pos + offset);
+                            pointer_from_viewport = Some((pointer_pos, released));
+                        }
+                    },
+                );
+            } else {
+                let window = Window::new(title)
+                    .id(floating.id)
+                    .open(&mut open)
+                    .default_pos(floating.pos)
+                    .default_size(vec2(320.0, 240.0))
+                    .show(ui.ctx(), |ui| {
+                        let rect = ui.available_rect_before_wrap();
+                        ui.allocate_rect(rect, Sense::hover());
+                        show_group(ui, group, rect, &location, viewer, &mut dragged);
+                    });
+                if let Some(window) = window {
+                    targets.push(DropTarget {
+                        location: location.clone(),
+                        rect: window.response.rect,
+                        layer_id: Some(window.response.layer_id),
+                    });
+                }
+            }
+
+            if !open {
+                if dragged
+                    .as_ref()
+                    .map_or(false, |from| from.group == location)
+                {
+                    dragged = None;
+                }
+                closed.push(floating.id);
+            }
+        }
+        for floating_id in closed {
+            state.close_floating(floating_id, |tab| viewer.on_close(tab));
+        }
+
+        if let Some(from) = &dragged {
+            let (pointer_pos, released) = pointer_from_viewport.unwrap_or_else(|| {
+                ui.input(|i| (i.pointer.interact_pos(), !i.pointer.primary_down()))
+            });
+            let target = pointer_pos.and_then(|pos| {
+                let layer_at_pos = ui.ctx().layer_id_at(pos);
+                targets
+                    .iter()
+                    .filter(|target| match target.layer_id {
+                        Some(layer_id) => layer_at_pos == Some(layer_id),
+                        None => target.rect.contains(pos),
+                    })
+                    // Floating windows are on top of the dock:
+                    .max_by_key(|target| target.layer_id.is_some())
+                    .map(|target| {
+                        let allow_split = target.location.surface == Surface::Root;
+                        let zone = DropZone::at(target.rect, pos, allow_split);
+                        (target, zone)
+                    })
+            });
+
+            if released {
+                if let Some(pos) = pointer_pos {
+                    let to = target.map(|(target, zone)| (target.location.clone(), zone));
+                    if to.is_some() || self.allow_floating {
+                        state.move_tab(id, from, to, pos);
+                    }
+                }
+                dragged = None;
+            } else {
+                ui.ctx().set_cursor_icon(CursorIcon::Grabbing);
+                let preview_rect = match target {
+                    Some((target, zone)) => Some(zone.preview_rect(target.rect)),
+                    None if self.allow_floating => {
+                        pointer_pos.map(|pos| Rect::from_min_size(pos, vec2(160.0, 100.0)))
+                    }
+                    None => None,
+                };
+                if let Some(preview_rect) = preview_rect {
+                    let painter = ui
+                        .ctx()
+                        .layer_painter(LayerId::new(Order::Foreground, id.with("drop_preview")));
+                    let visuals = ui.visuals();
+                    painter.rect(
+                        preview_rect.shrink(2.0),
+                        visuals.window_rounding,
+                        visuals.selection.bg_fill.gamma_multiply(0.3),
+                        visuals.selection.stroke,
+                    );
+                }
+            }
+        }
+
+        ui.data_mut(|d| match dragged {
+            Some(dragged) => d.insert_temp(drag_id, dragged),
+            None => d.remove::<TabLocation>(drag_id),
+        });
+
+        response
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn show_node<Tab>(
+    ui: &mut Ui,
+    dock_id: Id,
+    node: &mut DockNode<Tab>,
+    rect: Rect,
+    path: &mut Vec<usize>,
+    viewer: &mut impl TabViewer<Tab = Tab>,
+    targets: &mut Vec<DropTarget>,
+    dragged: &mut Option<TabLocation>,
+) {
+    match node {
+        DockNode::Tabs(group) => {
+            let location = GroupLocation {
+                surface: Surface::Root,
+                path: path.clone(),
+            };
+            show_group(ui, group, rect, &location, viewer, dragged);
+            targets.push(DropTarget {
+                location,
+                rect,
+                layer_id: None,
+            });
+        }
+        DockNode::Split {
+            direction,
+            fraction,
+            children,
+        } => {
+            let separator_width = ui.spacing().item_spacing.x;
+            let horizontal = *direction == SplitDirection::Horizontal;
+            let range = if horizontal {
+                rect.x_range()
+            } else {
+                rect.y_range()
+            };
+            let available = (range.span() - separator_width).at_least(0.0);
+            let split_at = range.min + available * *fraction;
+
+            let (mut first, mut separator, mut second) = (rect, rect, rect);
+            if horizontal {
+                first.max.x = split_at;
+                separator.min.x = split_at;
+                separator.max.x = split_at + separator_width;
+                second.min.x = separator.max.x;
+            } else {
+                first.max.y = split_at;
+                separator.min.y = split_at;
+                separator.max.y = split_at + separator_width;
+                second.min.y = separator.max.y;
+            }
+
+            let separator_response = ui.interact(
+                separator,
+                dock_id.with("separator").with(&path),
+                Sense::drag(),
+            );
+            if separator_response.hovered() || separator_response.dragged() {
+                ui.ctx().set_cursor_icon(if horizontal {
+                    CursorIcon::ResizeHorizontal
+                } else {
+                    CursorIcon::ResizeVertical
+                });
+            }
+            if separator_response.dragged() && 0.0 < available {
+                if let Some(pos) = separator_response.interact_pointer_pos() {
+                    let pos = if horizontal { pos.x } else { pos.y };
+                    *fraction =
+                        ((pos - range.min - 0.5 * separator_width) / available).clamp(0.1, 0.9);
+                }
+            }
+
+            let stroke = ui.style().interact(&separator_response).bg_stroke;
+            if horizontal {
+                ui.painter()
+                    .vline(separator.center().x, separator.y_range(), stroke);
+            } else {
+                ui.painter()
+                    .hline(separator.x_range(), separator.center().y, stroke);
+            }
+
+            for (i, (child, rect)) in children.iter_mut().zip([first, second]).enumerate() {
+                path.push(i);
+                show_node(ui, dock_id, child, rect, path, viewer, targets, dragged);
+                path.pop();
+            }
+        }
+    }
+}
+
+/// Show the tab bar and the active tab.
+fn show_group<Tab>(
+    ui: &mut Ui,
+    group: &mut TabGroup<Tab>,
+    rect: Rect,
+    location: &GroupLocation,
+    viewer: &mut impl TabViewer<Tab = Tab>,
+    dragged: &mut Option<TabLocation>,
+) {
+    let bar_height = ui.spacing().interact_size.y;
+    let bar_rect = Rect::from_min_max(rect.min, pos2(rect.max.x, rect.min.y + bar_height));
+    let content_rect = Rect::from_min_max(pos2(rect.min.x, bar_rect.max.y), rect.max);
+
+    ui.painter()
+        .rect_filled(bar_rect, 0.0, ui.visuals().faint_bg_color);
+    ui.painter().hline(
+        bar_rect.x_range(),
+        bar_rect.max.y,
+        ui.visuals().widgets.noninteractive.bg_stroke,
+    );
+
+    let mut bar_ui = ui.child_ui(bar_rect, Layout::left_to_right(Align::Center));
+    bar_ui.set_clip_rect(bar_rect.intersect(ui.clip_rect()));
+    for (index, tab) in group.tabs.iter_mut().enumerate() {
+        let title = viewer.title(tab);
+        let response = bar_ui
+            .push_id(index, |ui| {
+                ui.add(SelectableLabel::new(index == group.active, title))
+            })
+            .inner
+            .interact(Sense::drag());
+        if response.clicked() || response.drag_started() {
+            group.active = index;
+        }
+        if response.drag_started() {
+            *dragged = Some(TabLocation {
+                group: location.clone(),
+                index,
+            });
+        }
+    }
+
+    if let Some(tab) = group.tabs.get_mut(group.active) {
+        let content_rect = content_rect.shrink(ui.spacing().item_spacing.x);
+        let id = viewer.id(tab);
+        let mut content_ui =
+            ui.child_ui_with_id_source(content_rect, Layout::top_down(Align::Min), id);
+        content_ui.set_clip_rect(content_rect.intersect(ui.clip_rect()));
+        viewer.ui(&mut content_ui, tab);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn root(path: &[usize]) -> GroupLocation {
+        GroupLocation {
+            surface: Surface::Root,
+            path: path.to_vec(),
+        }
+    }
+
+    fn tab(group: GroupLocation, index: usize) -> TabLocation {
+        TabLocation { group, index }
+    }
+
+    #[test]
+    fn split_and_merge() {
+        let mut state = DockState::new(vec!["a", "b", "c"]);
+
+        state.move_tab(
+            Id::NULL,
+            &tab(root(&[]), 2),
+            Some((root(&[]), DropZone::Right)),
+            Pos2::ZERO,
+        );
+        assert_eq!(
+            state.root,
+            DockNode::horizontal(
+                DockNode::tabs(vec!["a", "b"]),
+                DockNode::tabs(vec!["c"]),
+                0.5
+            )
+        );
+
+        // Moving the last tab out of a group removes the split:
+        state.move_tab(
+            Id::NULL,
+            &tab(root(&[1]), 0),
+            Some((root(&[0]), DropZone::Center)),
+            Pos2::ZERO,
+        );
+        assert_eq!(
+            state.root,
+            DockNode::Tabs(TabGroup {
+                tabs: vec!["a", "b", "c"],
+                active: 2,
+            })
+        );
+    }
+
+    #[test]
+    fn float_and_dock_again() {
+        let mut state = DockState::new(vec!["a", "b"]);
+
+        state.move_tab(Id::NULL, &tab(root(&[]), 0), None, pos2(10.0, 20.0));
+        assert_eq!(state.root, DockNode::tabs(vec!["b"]));
+        assert_eq!(state.floating.len(), 1);
+        assert_eq!(state.floating[0].group.tabs, vec!["a"]);
+        assert_eq!(state.floating[0].pos, pos2(10.0, 20.0));
+
+        let floating = GroupLocation {
+            surface: Surface::Floating(state.floating[0].id),
+            path: vec![],
+        };
+        state.move_tab(
+            Id::NULL,
+            &tab(floating, 0),
+            Some((root(&[]), DropZone::Top)),
+            Pos2::ZERO,
+        );
+        assert!(state.floating.is_empty());
+        assert_eq!(
+            state.root,
+            DockNode::vertical(DockNode::tabs(vec!["a"]), DockNode::tabs(vec!["b"]), 0.5)
+        );
+    }
+
+    #[test]
+    fn close_floating() {
+        let dock_id = Id::new("dock");
+        let mut state = DockState::new(vec!["a", "b", "c"]);
+        state.move_tab(dock_id, &tab(root(&[]), 0), None, Pos2::ZERO);
+        state.move_tab(dock_id, &tab(root(&[]), 0), None, Pos2::ZERO);
+        assert_eq!(state.floating[0].id, dock_id.with(("floating", 0)));
+        assert_eq!(state.floating[1].id, dock_id.with(("floating", 1)));
+
+        let id = state.floating[0].id;
+        state.close_floating(id, |_| true);
+        assert_eq!(state.tabs(), vec![&"c", &"b"]);
+
+        // Tabs that refuse to close go back to the dock:
+        let id = state.floating[0].id;
+        state.close_floating(id, |_| false);
+        assert!(state.floating.is_empty());
+        assert_eq!(
+            state.root,
+            DockNode::Tabs(TabGroup {
+                tabs: vec!["c", "b"],
+                active: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn drag_tab_to_split() {
+        use crate::test_harness::Harness;
+
+        struct Viewer;
+
+        impl TabViewer for Viewer {
+            type Tab = String;
+
+            fn title(&mut self, tab: &mut String) -> WidgetText {
+                tab.as_str().into()
+            }
+
+            fn ui(&mut self, ui: &mut Ui, tab: &mut String) {
+                ui.label(format!("Contents of {tab}"));
+            }
+        }
+
+        let mut harness = Harness::new(|ctx| {
+            CentralPanel::default().show(ctx, |ui| {
+                Dock::new("dock").show(
+                    ui,
+                    || DockState::new(vec!["Left".to_owned(), "Right".to_owned()]),
+                    &mut Viewer,
+                );
+            });
+        });
+
+        let screen = harness.ctx().screen_rect();
+        harness
+            .get_by_label("Right")
+            .drag_to(pos2(screen.right() - 20.0, screen.center().y));
+
+        // Both tabs are now shown, side by side:
+        let left = harness.query_by_label("Contents of Left").unwrap();
+        let right = harness.query_by_label("Contents of Right").unwrap();
+        assert!(left.rect.rect.right() < right.rect.rect.left());
+        assert!(right.rect.rect.left() < screen.right());
+    }
+}
@@ -0,0 +1,388 @@
+//! A panel at the bottom of a [`Ui`] or screen that snaps between a few heights, see [`Drawer`].
+
+use crate::*;
+
+/// If the handle of a [`Drawer`] is released faster than this (in points per second),
+/// it is flung to the next snap height in that direction, instead of the closest one.
+const FLING_VELOCITY: f32 = 500.0;
+
+/// The heights a [`Drawer`] snaps to.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum DrawerSnap {
+    /// Only the top of the drawer is shown, see [`Drawer::peek_height`].
+    #[default]
+    Peek,
+
+    /// Part of the available height, see [`Drawer::half_fraction`].
+    Half,
+
+    /// All the available height.
+    Full,
+}
+
+impl DrawerSnap {
+    /// From lowest to highest.
+    pub const ALL: [Self; 3] = [Self::Peek, Self::Half, Self::Full];
+
+    /// The next higher snap height, going from [`Self::Full`] back to [`Self::Peek`].
+    pub fn cycle(self) -> Self {
+        match self {
+            Self::Peek => Self::Half,
+            Self::Half => Self::Full,
+            Self::Full => Self::Peek,
+        }
+    }
+}
+
+/// The state of a [`Drawer`], persisted per id.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct DrawerState {
+    /// The height the drawer rests at when it isn't being dragged.
+    pub snap: DrawerSnap,
+}
+
+impl DrawerState {
+    pub fn load(ctx: &Context, id: Id) -> Option<Self> {
+        ctx.data_mut(|d| d.get_persisted(id))
+    }
+
+    /// Store the state, e.g. to open the drawer from code.
+    pub fn store(self, ctx: &Context, id: Id) {
+        ctx.data_mut(|d| d.insert_persisted(id, self));
+    }
+}
+
+/// A panel that covers the bottom of a [`Ui`] or screen,
+/// and that can be dragged by its handle between a few [`DrawerSnap`] heights.
+///
+/// This is the bottom-sheet of mobile apps, but also works well for e.g. a log console on desktop.
+/// Releasing the handle while moving it quickly flings the drawer to the next height in that direction,
+/// and clicking the handle cycles through the heights.
+/// The drawer animates between the heights, and remembers which one it is at.
+///
+/// Like [`TopBottomPanel`], add it before any [`CentralPanel`].
+///
+/// ```
+/// # egui::__run_test_ctx(|ctx| {
+/// egui::Drawer::new("console").show(ctx, |ui| {
+///     ui.heading("Console");
+///     ui.label("Drag me up!");
+/// });
+/// egui::CentralPanel::default().show(ctx, |ui| {
+///     ui.label("Main contents");
+/// });
+/// # });
+/// ```
+#[must_use = "You should call .show()"]
+pub struct Drawer {
+    id: Id,
+    frame: Option<Frame>,
+    peek_height: f32,
+    half_fraction: f32,
+    default_snap: DrawerSnap,
+}
+
+impl Drawer {
+    /// The id should be globally unique, e.g. `Id::new("my_drawer")`.
+    pub fn new(id: impl Into<Id>) -> Self {
+        Self {
+            id: id.into(),
+            frame: None,
+            peek_height: 48.0,
+            half_fraction: 0.5,
+            default_snap: DrawerSnap::Peek,
+        }
+    }
+
+    /// The height of [`DrawerSnap::Peek`], including the handle.
+    ///
+    /// Default: `48.0`.
+    #[inline]
+    pub fn peek_height(mut self, peek_height: f32) -> Self {
+        self.peek_height = peek_height;
+        self
+    }
+
+    /// How much of the available height [`DrawerSnap::Half`] covers.
+    ///
+    /// Default: `0.5`.
+    #[inline]
+    pub fn half_fraction(mut self, half_fraction: f32) -> Self {
+        self.half_fraction = half_fraction;
+        self
+    }
+
+    /// Where the drawer starts out, before the user has moved it.
+    ///
+    /// Default: [`DrawerSnap::Peek`].
+    #[inline]
+    pub fn default_snap(mut self, default_snap: DrawerSnap) -> Self {
+        self.default_snap = default_snap;
+        self
+    }
+
+    /// Change the background color, margins, etc.
+    #[inline]
+    pub fn frame(mut self, frame: Frame) -> Self {
+        self.frame = Some(frame);
+        self
+    }
+}
+
+impl Drawer {
+    /// Show the drawer at the bottom of a [`Ui`].
+    pub fn show_inside<R>(
+        self,
+        ui: &mut Ui,
+        add_contents: impl FnOnce(&mut Ui) -> R,
+    ) -> InnerResponse<R> {
+        self.show_inside_dyn(ui, Box::new(add_contents))
+    }
+
+    fn show_inside_dyn<'c, R>(
+        self,
+        ui: &mut Ui,
+        add_contents: Box<dyn FnOnce(&mut Ui) -> R + 'c>,
+    ) -> InnerResponse<R> {
+        let Self {
+            id,
+            frame,
+            peek_height,
+            half_fraction,
+            default_snap,
+        } = self;
+
+        let available_rect = ui.available_rect_before_wrap();
+        let full_height = available_rect.height();
+        let peek_height = peek_height.at_most(full_height);
+        let snap_height = |snap: DrawerSnap| match snap {
+            DrawerSnap::Peek => peek_height,
+            DrawerSnap::Half => (half_fraction * full_height).clamp(peek_height, full_height),
+            DrawerSnap::Full => full_height,
+        };
+
+        let mut state =
+            DrawerState::load(ui.ctx(), id).unwrap_or(DrawerState { snap: default_snap });
+
+        let handle_id = id.with("__handle");
+        let height_id = id.with("__height");
+        let last_height = ui.data(|d| d.get_temp::<f32>(height_id));
+        let mut drag_height = None;
+
+        // First we read the handle interaction results, to avoid frame latency in the drag:
+        if let Some(handle_response) = ui.ctx().read_response(handle_id) {
+            let last_height = last_height.unwrap_or_else(|| snap_height(state.snap));
+            if handle_response.dragged() {
+                // Follow the pointer since it was pressed, without the latency of deciding it is a drag:
+                let drag_start_id = id.with("__drag_start_height");
+                if handle_response.drag_started() {
+                    ui.data_mut(|d| d.insert_temp(drag_start_id, last_height));
+                }
+                let start_height = ui
+                    .data(|d| d.get_temp(drag_start_id))
+                    .unwrap_or(last_height);
+                let dragged_y = ui.input(|i| {
+                    i.pointer
+                        .press_origin()
+                        .zip(i.pointer.interact_pos())
+                        .map_or(0.0, |(origin, pos)| pos.y - origin.y)
+                });
+                let height = start_height - dragged_y;
+                drag_height = Some(height.clamp(peek_height, full_height));
+            } else if handle_response.drag_stopped() {
+                let velocity = ui.input(|i| i.pointer.velocity().y);
+                state.snap = snap_after_drag(last_height, velocity, snap_height);
+            } else if handle_response.clicked() {
+                state.snap = state.snap.cycle();
+            }
+        }
+
+        let height = if let Some(drag_height) = drag_height {
+            // Follow the pointer, and animate from here once released:
+            ui.ctx()
+                .animate_value_with_time(height_id.with("animation"), drag_height, 0.0)
+        } else {
+            ui.ctx().animate_value_with_time(
+                height_id.with("animation"),
+                snap_height(state.snap),
+                ui.style().animation_time,
+            )
+        };
+
+        let mut panel_rect = available_rect;
+        panel_rect.min.y = panel_rect.max.y - height;
+        ui.ctx().check_for_id_clash(id, panel_rect, "Drawer");
+
+        let handle_height = ui.spacing().interact_size.y;
+        let handle_rect = Rect::from_min_size(
+            panel_rect.min,
+            vec2(panel_rect.width(), handle_height.at_most(height)),
+        );
+
+        let mut panel_ui = ui.child_ui_with_id_source(panel_rect, Layout::top_down(Align::Min), id);
+        panel_ui.set_clip_rect(panel_rect.intersect(ui.clip_rect()));
+        let frame = frame.unwrap_or_else(|| Frame::side_top_panel(ui.style()));
+        let InnerResponse { inner, response } = frame.show(&mut panel_ui, |ui| {
+            ui.set_min_size(panel_rect.size() - frame.total_margin().sum());
+            ui.add_space(handle_height - frame.inner_margin.top);
+            add_contents(ui)
+        });
+
+        {
+            let mut cursor = ui.cursor();
+            cursor.max.y = panel_rect.min.y;
+            ui.set_cursor(cursor);
+        }
+        ui.expand_to_include_rect(panel_rect);
+
+        // Now we do the actual handle interaction, on top of all the contents:
+        let handle_response = ui.interact(handle_rect, handle_id, Sense::click_and_drag());
+        if handle_response.dragged() {
+            ui.ctx().set_cursor_icon(CursorIcon::Grabbing);
+        } else if handle_response.hovered() {
+            ui.ctx().set_cursor_icon(CursorIcon::Grab);
+        }
+
+        {
+            let painter = ui.painter();
+            painter.hline(
+                panel_rect.x_range(),
+                painter.round_to_pixel(panel_rect.top()),
+                ui.visuals().widgets.noninteractive.bg_stroke,
+            );
+            let grip_rect = Rect::from_center_size(handle_rect.center(), vec2(32.0, 4.0));
+            let visuals = ui.style().interact(&handle_response);
+            painter.rect_filled(grip_rect, 2.0, visuals.fg_stroke.color);
+        }
+
+        state.store(ui.ctx(), id);
+        ui.data_mut(|d| d.insert_temp(height_id, height));
+
+        InnerResponse::new(inner, response)
+    }
+
+    /// Show the drawer at the bottom of the screen.
+    pub fn show<R>(
+        self,
+        ctx: &Context,
+        add_contents: impl FnOnce(&mut Ui) -> R,
+    ) -> InnerResponse<R> {
+        self.show_dyn(ctx, Box::new(add_contents))
+    }
+
+    fn show_dyn<'c, R>(
+        self,
+        ctx: &Context,
+        add_contents: Box<dyn FnOnce(&mut Ui) -> R + 'c>,
+    ) -> InnerResponse<R> {
+        let layer_id = LayerId::background();
+        let available_rect = ctx.available_rect();
+
+        let clip_rect = ctx.screen_rect();
+        let mut panel_ui = Ui::new(ctx.clone(), layer_id, self.id, available_rect, clip_rect);
+
+        let inner_response = self.show_inside_dyn(&mut panel_ui, add_contents);
+        let rect = inner_response.response.rect;
+
+        ctx.frame_state_mut(|state| {
+            state.allocate_bottom_panel(Rect::from_min_max(rect.min, available_rect.max));
+        });
+
+        inner_response
+    }
+}
+
+/// Where the drawer goes when its handle is released at `height`, moving at `velocity_y` points per second.
+fn snap_after_drag(
+    height: f32,
+    velocity_y: f32,
+    snap_height: impl Fn(DrawerSnap) -> f32,
+) -> DrawerSnap {
+    let eps = 1.0;
+    if velocity_y < -FLING_VELOCITY {
+        // Flung up:
+        DrawerSnap::ALL
+            .into_iter()
+            .find(|&snap| height + eps < snap_height(snap))
+            .unwrap_or(DrawerSnap::Full)
+    } else if FLING_VELOCITY < velocity_y {
+        // Flung down:
+        DrawerSnap::ALL
+            .into_iter()
+            .rev()
+            .find(|&snap| snap_height(snap) < height - eps)
+            .unwrap_or(DrawerSnap::Peek)
+    } else {
+        DrawerSnap::ALL
+            .into_iter()
+            .min_by(|&a, &b| {
+                let distance = |snap| (snap_height(snap) - height).abs();
+                distance(a).total_cmp(&distance(b))
+            })
+            .unwrap_or_default()
+    }
+}
+
+#[test]
+fn test_drawer() {
+    use crate::test_harness::Harness;
+    use std::cell::Cell;
+
+    let rect = Cell::new(Rect::NOTHING);
+    let mut harness = Harness::new(|ctx| {
+        rect.set(Drawer::new("drawer").show(ctx, |_ui| {}).response.rect);
+    });
+    harness.set_size(vec2(400.0, 600.0));
+    harness.run();
+    assert_eq!(rect.get().height(), 48.0);
+    assert_eq!(rect.get().bottom(), 600.0);
+    let handle_pos = || rect.get().center_top() + vec2(0.0, 4.0);
+
+    // Clicking the handle goes to the next height:
+    harness.click(handle_pos());
+    assert_eq!(rect.get().height(), 300.0);
+
+    // Flinging it up goes all the way:
+    harness.drag(handle_pos(), handle_pos() - vec2(0.0, 100.0));
+    assert_eq!(rect.get().height(), 600.0);
+    assert_eq!(
+        DrawerState::load(harness.ctx(), Id::new("drawer")),
+        Some(DrawerState {
+            snap: DrawerSnap::Full
+        })
+    );
+
+    // Slowly dragging it down goes to the closest height:
+    let mut pos = handle_pos();
+    harness.hover(pos);
+    harness.push_event(Event::PointerButton {
+        pos,
+        button: PointerButton::Primary,
+        pressed: true,
+        modifiers: Default::default(),
+    });
+    harness.step();
+    for _ in 0..100 {
+        pos.y += 2.0;
+        harness.push_event(Event::PointerMoved(pos));
+        harness.step();
+    }
+    assert!(
+        (rect.get().height() - 400.0).abs() < 4.0,
+        "{:?}",
+        rect.get()
+    );
+    for _ in 0..10 {
+        harness.step(); // Stop, so that it isn't a fling
+    }
+    harness.push_event(Event::PointerButton {
+        pos,
+        button: PointerButton::Primary,
+        pressed: false,
+        modifiers: Default::default(),
+    });
+    harness.run();
+    assert_eq!(rect.get().height(), 300.0);
+}
@@ -5,21 +5,31 @@
 pub(crate) mod area;
 pub mod collapsing_header;
 mod combo_box;
+pub(crate) mod command_palette;
+pub mod dock;
+pub(crate) mod drawer;
 pub(crate) mod frame;
 pub mod panel;
 pub mod popup;
 pub(crate) mod resize;
 pub mod scroll_area;
+pub(crate) mod snap;
+pub(crate) mod toolbar;
 pub(crate) mod window;
 
 pub use {
     area::Area,
     collapsing_header::{CollapsingHeader, CollapsingResponse},
     combo_box::*,
+    command_palette::CommandPalette,
+    dock::{Dock, DockNode, DockState, SplitDirection, TabGroup, TabViewer},
+    drawer::{Drawer, DrawerSnap, DrawerState},
     frame::Frame,
     panel::{CentralPanel, SidePanel, TopBottomPanel},
     popup::*,
     resize::Resize,
     scroll_area::ScrollArea,
+    snap::SnapOptions,
+    toolbar::{Toolbar, ToolbarItems},
     window::Window,
 };
@@ -39,6 +39,48 @@ impl PanelState {
     }
 }
 
+/// How many points each press of an arrow key resizes a panel.
+const KEYBOARD_RESIZE_STEP: f32 = 8.0;
+
+/// How much to grow a panel (or shrink, if negative) this frame
+/// because its resize separator has keyboard focus and the arrow keys were pressed.
+fn keyboard_resize_delta(ctx: &Context, resize_id: Id, shrink_key: Key, grow_key: Key) -> f32 {
+    if !ctx.memory(|mem| mem.has_focus(resize_id)) {
+        return 0.0;
+    }
+
+    // The arrow keys resize the panel instead of moving the focus:
+    let horizontal = matches!(grow_key, Key::ArrowLeft | Key::ArrowRight);
+    ctx.memory_mut(|mem| {
+        mem.set_focus_lock_filter(
+            resize_id,
+            EventFilter {
+                horizontal_arrows: horizontal,
+                vertical_arrows: !horizontal,
+                ..Default::default()
+            },
+        );
+    });
+
+    let steps = ctx.input(|i| i.num_presses(grow_key) as f32 - i.num_presses(shrink_key) as f32);
+    steps * KEYBOARD_RESIZE_STEP
+}
+
+/// Was the resize separator with this rect double-clicked this frame?
+fn resize_double_clicked(ctx: &Context, resize_rect: Rect) -> bool {
+    ctx.input(|i| {
+        i.pointer.button_double_clicked(PointerButton::Primary)
+            && i.pointer
+                .interact_pos()
+                .map_or(false, |pos| resize_rect.contains(pos))
+    })
+}
+
+/// How much room the contents of the panel needed last frame, including the margins of its frame.
+fn last_content_size(ctx: &Context, resize_id: Id) -> Option<Vec2> {
+    ctx.data(|d| d.get_temp(resize_id))
+}
+
 // ----------------------------------------------------------------------------
 
 /// [`Left`](Side::Left) or [`Right`](Side::Right)
@@ -69,6 +111,14 @@ impl Side {
             Self::Right => rect.right(),
         }
     }
+
+    /// The arrow keys that shrink and grow the panel.
+    fn resize_keys(self) -> (Key, Key) {
+        match self {
+            Self::Left => (Key::ArrowLeft, Key::ArrowRight),
+            Self::Right => (Key::ArrowRight, Key::ArrowLeft),
+        }
+    }
 }
 
 /// A panel that covers the entire left or right side of a [`Ui`] or screen.
@@ -98,6 +148,8 @@ pub struct SidePanel {
     show_separator_line: bool,
     default_width: f32,
     width_range: Rangef,
+    width_range_fraction: Option<Rangef>,
+    collapsed_width: Option<f32>,
 }
 
 impl SidePanel {
@@ -121,6 +173,8 @@ impl SidePanel {
             show_separator_line: true,
             default_width: 200.0,
             width_range: Rangef::new(96.0, f32::INFINITY),
+            width_range_fraction: None,
+            collapsed_width: None,
         }
     }
 
@@ -135,6 +189,9 @@ impl SidePanel {
     /// * A [`Separator`].
     /// * A [`TextEdit`].
     /// * …
+    ///
+    /// Double-clicking the edge fits the panel to its contents,
+    /// and the edge can also be focused with the keyboard and moved with the arrow keys.
     #[inline]
     pub fn resizable(mut self, resizable: bool) -> Self {
         self.resizable = resizable;
@@ -192,6 +249,63 @@ impl SidePanel {
         self
     }
 
+    /// The allowable width range for the panel, as fractions of the width available to it.
+    ///
+    /// For instance, `0.1..=0.5` keeps the panel between a tenth and a half of the available width,
+    /// also when the window is resized.
+    ///
+    /// The resulting range is clamped to [`Self::width_range`], so where they conflict,
+    /// [`Self::width_range`] wins.
+    #[inline]
+    pub fn width_range_fraction(mut self, fraction: impl Into<Rangef>) -> Self {
+        self.width_range_fraction = Some(fraction.into());
+        self
+    }
+
+    /// Allow the panel to be collapsed into a narrow strip, e.g. with only icons in it.
+    ///
+    /// Collapse and expand the panel with [`Self::set_collapsed`].
+    /// While collapsed, the panel is `collapsed_width` points wide (including its frame margins),
+    /// and cannot be resized. Collapsing and expanding is animated.
+    ///
+    /// Use [`Self::is_collapsed`] to decide what to show in the panel:
+    ///
+    /// ```
+    /// # egui::__run_test_ctx(|ctx| {
+    /// let collapsed = egui::SidePanel::is_collapsed(ctx, "tools");
+    /// egui::SidePanel::left("tools")
+    ///     .collapsible(40.0)
+    ///     .show(ctx, |ui| {
+    ///         let icon = if collapsed { "▶" } else { "◀" };
+    ///         if ui.button(icon).clicked() {
+    ///             egui::SidePanel::set_collapsed(ui.ctx(), "tools", !collapsed);
+    ///         }
+    ///         if !collapsed {
+    ///             ui.label("Tools");
+    ///         }
+    ///     });
+    /// # });
+    /// ```
+    #[inline]
+    pub fn collapsible(mut self, collapsed_width: f32) -> Self {
+        self.collapsed_width = Some(collapsed_width);
+        self
+    }
+
+    /// Is the panel with this id collapsed? See [`Self::collapsible`].
+    ///
+    /// This is `true` as soon as [`Self::set_collapsed`] is called, before the animation has finished.
+    pub fn is_collapsed(ctx: &Context, id: impl Into<Id>) -> bool {
+        let id = id.into().with("__collapsed");
+        ctx.data_mut(|d| d.get_persisted(id)).unwrap_or(false)
+    }
+
+    /// Collapse or expand the panel with this id. See [`Self::collapsible`].
+    pub fn set_collapsed(ctx: &Context, id: impl Into<Id>, collapsed: bool) {
+        let id = id.into().with("__collapsed");
+        ctx.data_mut(|d| d.insert_persisted(id, collapsed));
+    }
+
     /// Change the background color, margins, etc.
     #[inline]
     pub fn frame(mut self, frame: Frame) -> Self {
@@ -224,9 +338,30 @@ impl SidePanel {
             show_separator_line,
             default_width,
             width_range,
+            width_range_fraction,
+            collapsed_width,
         } = self;
 
+        let timer = crate::frame_profiler::start_timer(ui.ctx());
+
         let available_rect = ui.available_rect_before_wrap();
+
+        let width_range = if let Some(fraction) = width_range_fraction {
+            let available_width = available_rect.width();
+            let min = clamp_to_range(fraction.min * available_width, width_range);
+            let max = clamp_to_range(fraction.max * available_width, width_range);
+            Rangef::new(min, max.at_least(min))
+        } else {
+            width_range
+        };
+
+        // How far along the panel is to being collapsed, from 0 (expanded) to 1 (collapsed):
+        let collapse_t = collapsed_width.map_or(0.0, |_| {
+            let collapsed = Self::is_collapsed(ui.ctx(), id);
+            ui.ctx().animate_bool(id.with("__collapse_anim"), collapsed)
+        });
+        let resizable = resizable && collapse_t == 0.0;
+
         let mut panel_rect = available_rect;
         {
             let mut width = default_width;
@@ -234,12 +369,16 @@ impl SidePanel {
                 width = state.rect.width();
             }
             width = clamp_to_range(width, width_range).at_most(available_rect.width());
+            if let Some(collapsed_width) = collapsed_width {
+                width = lerp(width..=collapsed_width.at_most(width), collapse_t);
+            }
             side.set_rect_width(&mut panel_rect, width);
             ui.ctx().check_for_id_clash(id, panel_rect, "SidePanel");
         }
 
         let resize_id = id.with("__resize");
         let mut resize_hover = false;
+        let mut resize_focused = false;
         let mut is_resizing = false;
         if resizable {
             // First we read the resize interaction results, to avoid frame latency in the resize:
@@ -254,18 +393,50 @@ impl SidePanel {
                             clamp_to_range(width, width_range).at_most(available_rect.width());
                         side.set_rect_width(&mut panel_rect, width);
                     }
+                } else if resize_hover && resize_double_clicked(ui.ctx(), resize_response.rect) {
+                    // Fit the panel to how much room its contents needed last frame:
+                    if let Some(content_size) = last_content_size(ui.ctx(), resize_id) {
+                        let width = clamp_to_range(content_size.x, width_range)
+                            .at_most(available_rect.width());
+                        side.set_rect_width(&mut panel_rect, width);
+                    }
                 }
             }
+
+            let (shrink_key, grow_key) = side.resize_keys();
+            let delta = keyboard_resize_delta(ui.ctx(), resize_id, shrink_key, grow_key);
+            if delta != 0.0 {
+                let width = clamp_to_range(panel_rect.width() + delta, width_range)
+                    .at_most(available_rect.width());
+                side.set_rect_width(&mut panel_rect, width);
+            }
         }
 
         let mut panel_ui = ui.child_ui_with_id_source(panel_rect, Layout::top_down(Align::Min), id);
         panel_ui.expand_to_include_rect(panel_rect);
         let frame = frame.unwrap_or_else(|| Frame::side_top_panel(ui.style()));
-        let inner_response = frame.show(&mut panel_ui, |ui| {
+        let min_width = if collapse_t > 0.0 {
+            // Don't let the contents stick out of the collapsing panel:
+            panel_ui.set_clip_rect(panel_ui.clip_rect().intersect(panel_rect));
+            panel_rect.width() - frame.total_margin().sum().x
+        } else {
+            width_range.min
+        };
+        let InnerResponse {
+            inner: (inner, content_size),
+            mut response,
+        } = frame.show(&mut panel_ui, |ui| {
+            let inner = add_contents(ui);
+            let content_size = ui.min_rect().size();
             ui.set_min_height(ui.max_rect().height()); // Make sure the frame fills the full height
-            ui.set_min_width(width_range.min);
-            add_contents(ui)
+            ui.set_min_width(min_width);
+            (inner, content_size)
         });
+        if collapse_t > 0.0 {
+            side.set_rect_width(&mut response.rect, panel_rect.width());
+        }
+        let content_size = content_size + frame.total_margin().sum();
+        let inner_response = InnerResponse::new(inner, response);
 
         let rect = inner_response.response.rect;
 
@@ -292,19 +463,24 @@ impl SidePanel {
                 .expand2(vec2(ui.style().interaction.resize_grab_radius_side, 0.0));
             let resize_response = ui.interact(resize_rect, resize_id, Sense::drag());
             resize_hover = resize_response.hovered();
+            resize_focused = resize_response.has_focus();
             is_resizing = resize_response.dragged();
+            ui.data_mut(|d| d.insert_temp(resize_id, content_size));
         }
 
         if resize_hover || is_resizing {
             ui.ctx().set_cursor_icon(CursorIcon::ResizeHorizontal);
         }
 
-        PanelState { rect }.store(ui.ctx(), id);
+        if collapse_t == 0.0 {
+            // While collapsed we keep the expanded width, to expand to it again:
+            PanelState { rect }.store(ui.ctx(), id);
+        }
 
         {
             let stroke = if is_resizing {
                 ui.style().visuals.widgets.active.fg_stroke // highly visible
-            } else if resize_hover {
+            } else if resize_hover || resize_focused {
                 ui.style().visuals.widgets.hovered.fg_stroke // highly visible
             } else if show_separator_line {
                 // TODO(emilk): distinguish resizable from non-resizable
@@ -320,6 +496,10 @@ impl SidePanel {
             ui.painter().vline(resize_x, rect.y_range(), stroke);
         }
 
+        crate::frame_profiler::record_panel(ui.ctx(), timer, || {
+            format!("{side:?} panel {}", id.short_debug_format())
+        });
+
         inner_response
     }
 
@@ -522,6 +702,14 @@ impl TopBottomSide {
             Self::Bottom => rect.bottom(),
         }
     }
+
+    /// The arrow keys that shrink and grow the panel.
+    fn resize_keys(self) -> (Key, Key) {
+        match self {
+            Self::Top => (Key::ArrowUp, Key::ArrowDown),
+            Self::Bottom => (Key::ArrowDown, Key::ArrowUp),
+        }
+    }
 }
 
 /// A panel that covers the entire top or bottom of a [`Ui`] or screen.
@@ -588,6 +776,9 @@ impl TopBottomPanel {
     /// * A [`Separator`].
     /// * A [`TextEdit`].
     /// * …
+    ///
+    /// Double-clicking the edge fits the panel to its contents,
+    /// and the edge can also be focused with the keyboard and moved with the arrow keys.
     #[inline]
     pub fn resizable(mut self, resizable: bool) -> Self {
         self.resizable = resizable;
@@ -682,6 +873,8 @@ impl TopBottomPanel {
             height_range,
         } = self;
 
+        let timer = crate::frame_profiler::start_timer(ui.ctx());
+
         let available_rect = ui.available_rect_before_wrap();
         let mut panel_rect = available_rect;
         {
@@ -698,6 +891,7 @@ impl TopBottomPanel {
 
         let resize_id = id.with("__resize");
         let mut resize_hover = false;
+        let mut resize_focused = false;
         let mut is_resizing = false;
         if resizable {
             // First we read the resize interaction results, to avoid frame latency in the resize:
@@ -712,18 +906,40 @@ impl TopBottomPanel {
                             clamp_to_range(height, height_range).at_most(available_rect.height());
                         side.set_rect_height(&mut panel_rect, height);
                     }
+                } else if resize_hover && resize_double_clicked(ui.ctx(), resize_response.rect) {
+                    // Fit the panel to how much room its contents needed last frame:
+                    if let Some(content_size) = last_content_size(ui.ctx(), resize_id) {
+                        let height = clamp_to_range(content_size.y, height_range)
+                            .at_most(available_rect.height());
+                        side.set_rect_height(&mut panel_rect, height);
+                    }
                 }
             }
+
+            let (shrink_key, grow_key) = side.resize_keys();
+            let delta = keyboard_resize_delta(ui.ctx(), resize_id, shrink_key, grow_key);
+            if delta != 0.0 {
+                let height = clamp_to_range(panel_rect.height() + delta, height_range)
+                    .at_most(available_rect.height());
+                side.set_rect_height(&mut panel_rect, height);
+            }
         }
 
         let mut panel_ui = ui.child_ui_with_id_source(panel_rect, Layout::top_down(Align::Min), id);
         panel_ui.expand_to_include_rect(panel_rect);
         let frame = frame.unwrap_or_else(|| Frame::side_top_panel(ui.style()));
-        let inner_response = frame.show(&mut panel_ui, |ui| {
+        let InnerResponse {
+            inner: (inner, content_size),
+            response,
+        } = frame.show(&mut panel_ui, |ui| {
+            let inner = add_contents(ui);
+            let content_size = ui.min_rect().size();
             ui.set_min_width(ui.max_rect().width()); // Make the frame fill full width
             ui.set_min_height(height_range.min);
-            add_contents(ui)
+            (inner, content_size)
         });
+        let content_size = content_size + frame.total_margin().sum();
+        let inner_response = InnerResponse::new(inner, response);
 
         let rect = inner_response.response.rect;
 
@@ -751,7 +967,9 @@ impl TopBottomPanel {
                 .expand2(vec2(0.0, ui.style().interaction.resize_grab_radius_side));
             let resize_response = ui.interact(resize_rect, resize_id, Sense::drag());
             resize_hover = resize_response.hovered();
+            resize_focused = resize_response.has_focus();
             is_resizing = resize_response.dragged();
+            ui.data_mut(|d| d.insert_temp(resize_id, content_size));
         }
 
         if resize_hover || is_resizing {
@@ -763,7 +981,7 @@ impl TopBottomPanel {
         {
             let stroke = if is_resizing {
                 ui.style().visuals.widgets.active.fg_stroke // highly visible
-            } else if resize_hover {
+            } else if resize_hover || resize_focused {
                 ui.style().visuals.widgets.hovered.fg_stroke // highly visible
             } else if show_separator_line {
                 // TODO(emilk): distinguish resizable from non-resizable
@@ -779,6 +997,10 @@ impl TopBottomPanel {
             ui.painter().hline(rect.x_range(), resize_y, stroke);
         }
 
+        crate::frame_profiler::record_panel(ui.ctx(), timer, || {
+            format!("{side:?} panel {}", id.short_debug_format())
+        });
+
         inner_response
     }
 
@@ -1019,25 +1241,39 @@ impl CentralPanel {
         ui: &mut Ui,
         add_contents: impl FnOnce(&mut Ui) -> R,
     ) -> InnerResponse<R> {
-        self.show_inside_dyn(ui, Box::new(add_contents))
+        self.show_inside_dyn(ui, Box::new(add_contents)).0
     }
 
     /// Show the panel inside a [`Ui`].
+    ///
+    /// Also returns the part of the panel used by the contents, including the frame margins.
     fn show_inside_dyn<'c, R>(
         self,
         ui: &mut Ui,
         add_contents: Box<dyn FnOnce(&mut Ui) -> R + 'c>,
-    ) -> InnerResponse<R> {
+    ) -> (InnerResponse<R>, Rect) {
         let Self { frame } = self;
 
+        let timer = crate::frame_profiler::start_timer(ui.ctx());
+
         let panel_rect = ui.available_rect_before_wrap();
         let mut panel_ui = ui.child_ui(panel_rect, Layout::top_down(Align::Min));
 
         let frame = frame.unwrap_or_else(|| Frame::central_panel(ui.style()));
-        frame.show(&mut panel_ui, |ui| {
+        let InnerResponse {
+            inner: (inner, content_rect),
+            response,
+        } = frame.show(&mut panel_ui, |ui| {
+            let inner = add_contents(ui);
+            let content_rect = ui.min_rect();
             ui.expand_to_include_rect(ui.max_rect()); // Expand frame to include it all
-            add_contents(ui)
-        })
+            (inner, content_rect)
+        });
+        let content_rect = frame.total_margin().expand_rect(content_rect);
+
+        crate::frame_profiler::record_panel(ui.ctx(), timer, || "Central panel".to_owned());
+
+        (InnerResponse::new(inner, response), content_rect)
     }
 
     /// Show the panel at the top level.
@@ -1062,10 +1298,12 @@ impl CentralPanel {
         let clip_rect = ctx.screen_rect();
         let mut panel_ui = Ui::new(ctx.clone(), layer_id, id, available_rect, clip_rect);
 
-        let inner_response = self.show_inside_dyn(&mut panel_ui, add_contents);
+        let (inner_response, content_rect) = self.show_inside_dyn(&mut panel_ui, add_contents);
 
         // Only inform ctx about what we actually used, so we can shrink the native window to fit.
-        ctx.frame_state_mut(|state| state.allocate_central_panel(inner_response.response.rect));
+        ctx.frame_state_mut(|state| {
+            state.allocate_central_panel(inner_response.response.rect, content_rect);
+        });
 
         inner_response
     }
@@ -1075,3 +1313,121 @@ fn clamp_to_range(x: f32, range: Rangef) -> f32 {
     let range = range.as_positive();
     x.clamp(range.min, range.max)
 }
+
+#[test]
+fn test_panel_fit_to_contents() {
+    use crate::test_harness::Harness;
+
+    let id = Id::new("left");
+    let mut harness = Harness::new(|ctx| {
+        SidePanel::left(id).show(ctx, |ui| {
+            ui.allocate_space(vec2(100.0, 20.0));
+        });
+        CentralPanel::default().show(ctx, |_ui| {});
+    });
+    let width = |harness: &Harness<'_>| PanelState::load(harness.ctx(), id).unwrap().rect.width();
+    let margin = Frame::side_top_panel(&harness.ctx().style())
+        .total_margin()
+        .sum()
+        .x;
+    assert_eq!(width(&harness), 200.0);
+
+    // Double-click the edge to fit the panel to its contents:
+    let edge = pos2(200.0, 300.0);
+    harness.click(edge);
+    harness.click(edge);
+    assert_eq!(width(&harness), 100.0 + margin);
+}
+
+#[test]
+fn test_panel_keyboard_resize() {
+    use crate::test_harness::Harness;
+
+    let id = Id::new("left");
+    let mut harness = Harness::new(|ctx| {
+        SidePanel::left(id).show(ctx, |ui| {
+            ui.separator();
+        });
+        CentralPanel::default().show(ctx, |_ui| {});
+    });
+    let width = |harness: &Harness<'_>| PanelState::load(harness.ctx(), id).unwrap().rect.width();
+    assert_eq!(width(&harness), 200.0);
+
+    // Focus the edge and resize the panel with the arrow keys:
+    harness.press_key(Key::Tab);
+    assert!(harness
+        .ctx()
+        .memory(|mem| mem.has_focus(id.with("__resize"))));
+    harness.press_key(Key::ArrowRight);
+    harness.press_key(Key::ArrowRight);
+    assert_eq!(width(&harness), 200.0 + 2.0 * KEYBOARD_RESIZE_STEP);
+    harness.press_key(Key::ArrowLeft);
+    assert_eq!(width(&harness), 200.0 + KEYBOARD_RESIZE_STEP);
+}
+
+#[test]
+fn test_panel_width_range_fraction() {
+    use crate::test_harness::Harness;
+
+    let panel_width = std::cell::Cell::new(0.0);
+    let mut harness = Harness::new(|ctx| {
+        let response = SidePanel::left("left")
+            .width_range_fraction(0.1..=0.2)
+            .show(ctx, |ui| {
+                ui.separator();
+            })
+            .response;
+        panel_width.set(response.rect.width());
+    });
+    assert_eq!(panel_width.get(), 160.0);
+
+    harness.set_size(vec2(400.0, 600.0));
+    assert_eq!(
+        panel_width.get(),
+        96.0,
+        "The width_range minimum wins over the fraction"
+    );
+
+    // The fraction is of the width left over by other panels, not of the screen:
+    let panel_width = std::cell::Cell::new(0.0);
+    let _harness = Harness::new(|ctx| {
+        SidePanel::right("right")
+            .exact_width(400.0)
+            .show(ctx, |_ui| {});
+        let response = SidePanel::left("left")
+            .width_range(10.0..=1000.0)
+            .width_range_fraction(0.1..=0.2)
+            .show(ctx, |ui| {
+                ui.separator();
+            })
+            .response;
+        panel_width.set(response.rect.width());
+    });
+    assert_eq!(panel_width.get(), 80.0);
+}
+
+#[test]
+fn test_panel_collapse() {
+    use crate::test_harness::Harness;
+
+    let panel_width = std::cell::Cell::new(0.0);
+    let mut harness = Harness::new(|ctx| {
+        let response = SidePanel::left("left")
+            .collapsible(40.0)
+            .show(ctx, |ui| {
+                ui.separator();
+            })
+            .response;
+        panel_width.set(response.rect.width());
+    });
+    assert_eq!(panel_width.get(), 200.0);
+
+    SidePanel::set_collapsed(harness.ctx(), "left", true);
+    harness.run();
+    assert_eq!(panel_width.get(), 40.0);
+
+    // Expands to the width it had before:
+    SidePanel::set_collapsed(harness.ctx(), "left", false);
+    harness.run();
+    assert_eq!(panel_width.get(), 200.0);
+}
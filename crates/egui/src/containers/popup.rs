@@ -4,6 +4,50 @@ use crate::*;
 
 // ----------------------------------------------------------------------------
 
+/// Keeps track of how long the pointer has been over each widget,
+/// and when a tooltip was last visible.
+///
+/// Used for [`crate::style::Interaction::tooltip_delay`], [`crate::style::Interaction::tooltip_grace_period`],
+/// [`crate::style::Interaction::submenu_open_delay`] and [`Response::on_hover_prefetch`].
+#[derive(Clone, Debug, Default)]
+pub(crate) struct HoverTimer {
+    /// The hovered widgets, when their hover started, and the last frame they were hovered.
+    ///
+    /// There can be more than one, e.g. a widget in a tooltip and the widget showing the tooltip.
+    hovered: IdMap<(f64, u64)>,
+
+    /// The last time a tooltip was visible.
+    last_shown: Option<f64>,
+}
+
+impl HoverTimer {
+    /// Call each frame the widget is hovered.
+    ///
+    /// Returns how long it has been hovered, in seconds.
+    pub(crate) fn hover_duration(&mut self, widget_id: Id, time: f64, frame_nr: u64) -> f32 {
+        // Forget the widgets that were not hovered last frame (or already this frame):
+        self.hovered
+            .retain(|_, (_, last_frame_nr)| frame_nr <= *last_frame_nr + 1);
+
+        let (start_time, last_frame_nr) = self.hovered.entry(widget_id).or_insert((time, frame_nr));
+        *last_frame_nr = frame_nr;
+        (time - *start_time) as f32
+    }
+
+    /// Call each frame a tooltip is visible.
+    pub(crate) fn tooltip_shown(&mut self, time: f64) {
+        self.last_shown = Some(time);
+    }
+
+    /// Was a tooltip visible less than `grace_period` seconds ago?
+    pub(crate) fn in_grace_period(&self, time: f64, grace_period: f32) -> bool {
+        self.last_shown
+            .map_or(false, |last_shown| time - last_shown <= grace_period as f64)
+    }
+}
+
+// ----------------------------------------------------------------------------
+
 /// Same state for all tooltips.
 #[derive(Clone, Debug, Default)]
 pub(crate) struct TooltipState {
@@ -188,7 +232,12 @@ fn show_tooltip_at_avoid_dyn<'c, R>(
         position.y -= expected_size.y;
     }
 
-    position = position.at_most(ctx.screen_rect().max - expected_size);
+    // If the tooltip can escape into its own viewport, we let it go beyond the screen:
+    let may_escape = ctx.options(|o| o.popups_escape_viewport) && !ctx.embed_viewports();
+
+    if !may_escape {
+        position = position.at_most(ctx.screen_rect().max - expected_size);
+    }
 
     // check if we intersect the avoid_rect
     {
@@ -206,7 +255,9 @@ fn show_tooltip_at_avoid_dyn<'c, R>(
         }
     }
 
-    let position = position.at_least(ctx.screen_rect().min);
+    if !may_escape {
+        position = position.at_least(ctx.screen_rect().min);
+    }
 
     let area_id = frame_state.common_id.with(frame_state.count);
 
@@ -262,6 +313,7 @@ fn show_tooltip_area_dyn<'c, R>(
         .fixed_pos(window_pos)
         .constrain_to(ctx.screen_rect())
         .interactable(false)
+        .escape_viewport(ctx.options(|o| o.popups_escape_viewport))
         .show(ctx, |ui| {
             Frame::popup(&ctx.style())
                 .show(ui, |ui| {
@@ -291,6 +343,231 @@ pub fn was_tooltip_open_last_frame(ctx: &Context, tooltip_id: Id) -> bool {
     false
 }
 
+// ----------------------------------------------------------------------------
+
+/// When does a popup close by itself?
+///
+/// See [`PopupOptions::close_behavior`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum PopupCloseBehavior {
+    /// Close when the user clicks outside the popup
+    /// (and outside the widget it is anchored to).
+    ///
+    /// Clicks in child popups count as clicks inside.
+    #[default]
+    CloseOnClickOutside,
+
+    /// Close on any click, also inside the popup.
+    ///
+    /// Good for drop-down lists, where selecting an item should close the popup.
+    CloseOnClick,
+
+    /// Only close when asked to, e.g. with [`PopupManager::close`] (or Escape).
+    IgnoreClicks,
+}
+
+/// How a popup shown with [`Ui::popup_with_options`] behaves.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct PopupOptions {
+    /// When to close the popup on clicks.
+    pub close_behavior: PopupCloseBehavior,
+
+    /// Close the popup when the user presses Escape.
+    ///
+    /// Only the top-most popup is closed by each press.
+    pub close_on_escape: bool,
+
+    /// Dim everything behind the popup, and block all interaction with it.
+    pub modal: bool,
+}
+
+impl Default for PopupOptions {
+    fn default() -> Self {
+        Self {
+            close_behavior: PopupCloseBehavior::default(),
+            close_on_escape: true,
+            modal: false,
+        }
+    }
+}
+
+/// Keeps track of which popups are open, stored in [`Memory::popups`].
+///
+/// Popups are things like combo-boxes, color pickers, menus,
+/// and other things shown with [`Ui::popup`].
+///
+/// Open popups form a stack: a popup that is opened from within another popup
+/// is its child, and stays open together with it.
+/// Opening a popup from anywhere else closes all other popups.
+#[derive(Clone, Debug, Default)]
+pub struct PopupManager {
+    /// Bottom-to-top. Each popup is the child of the one below it.
+    stack: Vec<Id>,
+
+    /// The popups whose contents are currently being shown, outermost first,
+    /// and whether a click was seen inside any of their children.
+    showing: Vec<(Id, bool)>,
+}
+
+impl PopupManager {
+    /// Is the given popup open?
+    #[inline]
+    pub fn is_open(&self, popup_id: Id) -> bool {
+        self.stack.contains(&popup_id)
+    }
+
+    /// Is any popup open?
+    #[inline]
+    pub fn any_open(&self) -> bool {
+        !self.stack.is_empty()
+    }
+
+    /// The top-most open popup, if any.
+    #[inline]
+    pub fn top(&self) -> Option<Id> {
+        self.stack.last().copied()
+    }
+
+    /// All open popups, bottom-to-top.
+    #[inline]
+    pub fn open_ids(&self) -> &[Id] {
+        &self.stack
+    }
+
+    /// Open the given popup.
+    ///
+    /// If called from within the contents of an open popup, the new popup becomes its child.
+    /// All other popups are closed.
+    pub fn open(&mut self, popup_id: Id) {
+        let parent = self
+            .showing
+            .iter()
+            .rev()
+            .find_map(|(id, _)| self.stack.iter().position(|open| open == id));
+        self.stack.truncate(parent.map_or(0, |i| i + 1));
+        self.stack.push(popup_id);
+    }
+
+    /// Close the given popup, and all its children.
+    pub fn close(&mut self, popup_id: Id) {
+        if let Some(i) = self.stack.iter().position(|id| *id == popup_id) {
+            self.stack.truncate(i);
+        }
+    }
+
+    /// Close all popups.
+    pub fn close_all(&mut self) {
+        self.stack.clear();
+    }
+
+    /// Toggle the given popup between closed and open.
+    pub fn toggle(&mut self, popup_id: Id) {
+        if self.is_open(popup_id) {
+            self.close(popup_id);
+        } else {
+            self.open(popup_id);
+        }
+    }
+
+    /// Call before showing the contents of the given popup,
+    /// so that popups opened from within it become its children.
+    pub(crate) fn begin_showing(&mut self, popup_id: Id) {
+        self.showing.push((popup_id, false));
+    }
+
+    /// Call after showing the contents of a popup, see [`Self::begin_showing`].
+    ///
+    /// Returns `true` if one of its children saw a click inside it.
+    pub(crate) fn end_showing(&mut self) -> bool {
+        self.showing
+            .pop()
+            .map_or(false, |(_, child_clicked)| child_clicked)
+    }
+}
+
+/// Shows the popup in the given area if it is open, and closes it according to `options`.
+///
+/// This is the common implementation of [`Ui::popup`], [`popup_above_or_below_widget`] etc.
+pub(crate) fn show_popup_area<R>(
+    ui: &Ui,
+    popup_id: Id,
+    anchor: &Response,
+    options: PopupOptions,
+    area: Area,
+    add_contents: impl FnOnce(&mut Ui) -> R,
+) -> Option<InnerResponse<R>> {
+    let ctx = ui.ctx();
+    if !ctx.memory(|mem| mem.is_popup_open(popup_id)) {
+        return None;
+    }
+
+    if options.modal {
+        show_modal_backdrop(ctx, popup_id);
+    }
+
+    ctx.memory_mut(|mem| mem.popups.begin_showing(popup_id));
+    let inner = area.show(ctx, add_contents);
+    let child_clicked = ctx.memory_mut(|mem| mem.popups.end_showing());
+
+    if options.modal {
+        ctx.move_to_top(inner.response.layer_id);
+    }
+
+    let click_pos = ctx.input(|i| {
+        i.pointer
+            .any_click()
+            .then(|| i.pointer.interact_pos())
+            .flatten()
+    });
+    let clicked_inside = child_clicked
+        || click_pos.map_or(false, |pos| {
+            inner.response.contains_pointer() || inner.response.rect.contains(pos)
+        });
+    let clicked_anchor = click_pos.map_or(false, |pos| {
+        anchor.contains_pointer() || anchor.rect.contains(pos)
+    });
+
+    if clicked_inside {
+        // Let the parent know that the click was inside one of its children:
+        ctx.memory_mut(|mem| {
+            if let Some((_, child_clicked)) = mem.popups.showing.last_mut() {
+                *child_clicked = true;
+            }
+        });
+    }
+
+    let close = match options.close_behavior {
+        PopupCloseBehavior::CloseOnClickOutside => {
+            click_pos.is_some() && !clicked_inside && !clicked_anchor
+        }
+        PopupCloseBehavior::CloseOnClick => click_pos.is_some() && !clicked_anchor,
+        PopupCloseBehavior::IgnoreClicks => false,
+    } || (options.close_on_escape
+        && ctx.memory(|mem| mem.popups.top()) == Some(popup_id)
+        && ctx.input_mut(|i| i.consume_key(Modifiers::NONE, Key::Escape)));
+
+    if close {
+        ctx.memory_mut(|mem| mem.popups.close(popup_id));
+    }
+
+    Some(inner)
+}
+
+/// Dims the screen below a modal popup, and eats all clicks on it.
+fn show_modal_backdrop(ctx: &Context, popup_id: Id) {
+    let screen_rect = ctx.screen_rect();
+    Area::new(popup_id.with("__modal_backdrop"))
+        .order(Order::Foreground)
+        .fixed_pos(screen_rect.min)
+        .show(ctx, |ui| {
+            let (rect, _) = ui.allocate_exact_size(screen_rect.size(), Sense::click_and_drag());
+            ui.painter()
+                .rect_filled(rect, 0.0, Color32::from_black_alpha(96));
+        });
+}
+
 /// Helper for [`popup_above_or_below_widget`].
 pub fn popup_below_widget<R>(
     ui: &Ui,
@@ -314,6 +591,7 @@ pub fn popup_below_widget<R>(
 /// The opened popup will have the same width as the parent.
 ///
 /// You must open the popup with [`Memory::open_popup`] or  [`Memory::toggle_popup`].
+/// It is closed on any click, or when pressing Escape, see [`PopupCloseBehavior::CloseOnClick`].
 ///
 /// Returns `None` if the popup is not open.
 ///
@@ -339,37 +617,207 @@ pub fn popup_above_or_below_widget<R>(
     above_or_below: AboveOrBelow,
     add_contents: impl FnOnce(&mut Ui) -> R,
 ) -> Option<R> {
-    if ui.memory(|mem| mem.is_popup_open(popup_id)) {
-        let (pos, pivot) = match above_or_below {
-            AboveOrBelow::Above => (widget_response.rect.left_top(), Align2::LEFT_BOTTOM),
-            AboveOrBelow::Below => (widget_response.rect.left_bottom(), Align2::LEFT_TOP),
-        };
+    let (pos, pivot) = match above_or_below {
+        AboveOrBelow::Above => (widget_response.rect.left_top(), Align2::LEFT_BOTTOM),
+        AboveOrBelow::Below => (widget_response.rect.left_bottom(), Align2::LEFT_TOP),
+    };
 
-        let inner = Area::new(popup_id)
-            .order(Order::Foreground)
-            .constrain(true)
-            .fixed_pos(pos)
-            .pivot(pivot)
-            .show(ui.ctx(), |ui| {
-                let frame = Frame::popup(ui.style());
-                let frame_margin = frame.total_margin();
-                frame
-                    .show(ui, |ui| {
-                        ui.with_layout(Layout::top_down_justified(Align::LEFT), |ui| {
-                            ui.set_width(widget_response.rect.width() - frame_margin.sum().x);
-                            add_contents(ui)
-                        })
-                        .inner
-                    })
-                    .inner
+    let area = Area::new(popup_id)
+        .order(Order::Foreground)
+        .constrain(true)
+        .fixed_pos(pos)
+        .pivot(pivot)
+        .escape_viewport(ui.ctx().options(|o| o.popups_escape_viewport));
+
+    let options = PopupOptions {
+        close_behavior: PopupCloseBehavior::CloseOnClick,
+        ..Default::default()
+    };
+
+    show_popup_area(ui, popup_id, widget_response, options, area, |ui| {
+        let frame = Frame::popup(ui.style());
+        let frame_margin = frame.total_margin();
+        frame
+            .show(ui, |ui| {
+                ui.with_layout(Layout::top_down_justified(Align::LEFT), |ui| {
+                    ui.set_width(widget_response.rect.width() - frame_margin.sum().x);
+                    add_contents(ui)
+                })
+                .inner
             })
-            .inner;
+            .inner
+    })
+    .map(|inner| inner.inner)
+}
 
-        if ui.input(|i| i.key_pressed(Key::Escape)) || widget_response.clicked_elsewhere() {
-            ui.memory_mut(|mem| mem.close_popup());
-        }
-        Some(inner)
-    } else {
-        None
+#[test]
+fn test_tooltip_delay_and_grace_period() {
+    use crate::test_harness::Harness;
+
+    let mut harness = Harness::new_ui(|ui| {
+        ui.button("A").on_hover_text("Tooltip A");
+        ui.button("B").on_hover_text("Tooltip B");
+    });
+    let a = harness.get_by_label("A").node().rect;
+    let b = harness.get_by_label("B").node().rect;
+    let is_open = |harness: &Harness<'_>, id: Id| {
+        was_tooltip_open_last_frame(harness.ctx(), id.with("__tooltip"))
+    };
+
+    harness.hover(a.interact_rect.center());
+    assert!(!is_open(&harness, a.id), "Should wait for the delay");
+
+    for _ in 0..30 {
+        harness.step();
     }
+    assert!(is_open(&harness, a.id));
+
+    // Moving to the next widget shows its tooltip right away:
+    harness.push_event(Event::PointerMoved(b.interact_rect.center()));
+    harness.step();
+    harness.step();
+    assert!(is_open(&harness, b.id));
+}
+
+#[test]
+fn test_nested_popups() {
+    use crate::test_harness::Harness;
+
+    let parent_id = Id::new("parent");
+    let child_id = Id::new("child");
+    let mut harness = Harness::new_ui(|ui| {
+        let response = ui.button("Open parent");
+        if response.clicked() {
+            ui.memory_mut(|mem| mem.toggle_popup(parent_id));
+        }
+        ui.popup(parent_id, &response, |ui| {
+            let response = ui.button("Open child");
+            if response.clicked() {
+                ui.memory_mut(|mem| mem.toggle_popup(child_id));
+            }
+            ui.popup(child_id, &response, |ui| {
+                ui.label("Child");
+            });
+        });
+    });
+    let open_ids =
+        |harness: &Harness<'_>| harness.ctx().memory(|mem| mem.popups.open_ids().to_vec());
+
+    harness.get_by_label("Open parent").click();
+    harness.get_by_label("Open child").click();
+    assert_eq!(open_ids(&harness), vec![parent_id, child_id]);
+
+    // A click in the child is a click inside the parent:
+    harness.get_by_label("Child").click();
+    assert_eq!(open_ids(&harness), vec![parent_id, child_id]);
+
+    // Escape closes the top-most popup:
+    harness.press_key(Key::Escape);
+    assert_eq!(open_ids(&harness), vec![parent_id]);
+
+    harness.click(pos2(700.0, 500.0));
+    assert_eq!(open_ids(&harness), vec![]);
+}
+
+#[test]
+fn test_modal_popup() {
+    use crate::test_harness::Harness;
+
+    let popup_id = Id::new("modal");
+    let mut clicked_below = false;
+    let mut harness = Harness::new_ui(|ui| {
+        let response = ui.button("Below");
+        clicked_below |= response.clicked();
+        let options = PopupOptions {
+            close_behavior: PopupCloseBehavior::IgnoreClicks,
+            modal: true,
+            ..Default::default()
+        };
+        ui.popup_with_options(popup_id, &response, options, |ui| {
+            ui.label("Modal");
+        });
+    });
+    let below = harness.get_by_label("Below").interact_rect().center();
+    harness.ctx().memory_mut(|mem| mem.open_popup(popup_id));
+    harness.run();
+
+    harness.click(below);
+    assert!(harness.ctx().memory(|mem| mem.is_popup_open(popup_id)));
+    drop(harness);
+    assert!(!clicked_below, "The modal should block clicks");
+}
+
+#[test]
+fn test_clipped_popup_escapes_into_own_viewport() {
+    use crate::test_harness::Harness;
+    use std::{cell::RefCell, rc::Rc};
+
+    // Record the viewports that are opened, and run them like a native integration would:
+    let shown_viewports = Rc::new(RefCell::new(Vec::new()));
+    Context::set_immediate_viewport_renderer({
+        let shown_viewports = shown_viewports.clone();
+        move |ctx, viewport| {
+            shown_viewports
+                .borrow_mut()
+                .push((viewport.ids.this, viewport.builder.position));
+            let size = viewport.builder.inner_size.unwrap_or(Vec2::splat(100.0));
+            let raw_input = RawInput {
+                viewport_id: viewport.ids.this,
+                screen_rect: Some(Rect::from_min_size(Pos2::ZERO, size)),
+                ..Default::default()
+            };
+            let _ = ctx.run(raw_input, viewport.viewport_ui_cb);
+        }
+    });
+
+    let popup_id = Id::new("popup");
+    let mut harness = Harness::new_ui(|ui| {
+        // Close to the bottom edge, so the popup doesn't fit below the button:
+        ui.add_space(ui.available_height() - 30.0);
+        let response = ui.button("Open");
+        if response.clicked() {
+            ui.memory_mut(|mem| mem.toggle_popup(popup_id));
+        }
+        popup_below_widget(ui, popup_id, &response, |ui| {
+            ui.set_min_height(200.0);
+            ui.label("Popup contents");
+        });
+    });
+    harness.ctx().set_embed_viewports(false);
+    harness
+        .ctx()
+        .options_mut(|o| o.popups_escape_viewport = true);
+    let inner_rect = Rect::from_min_size(pos2(100.0, 50.0), vec2(800.0, 600.0));
+    harness.set_viewport_inner_rect(inner_rect);
+    assert!(shown_viewports.borrow().is_empty());
+
+    harness.get_by_label("Open").click();
+
+    let escaped = shown_viewports.borrow().last().copied();
+    let (viewport_id, position) = escaped.expect("The clipped popup should get its own viewport");
+    assert_eq!(viewport_id, ViewportId(popup_id.with("escaped_viewport")));
+    let position = position.expect("The viewport should be placed in screen coordinates");
+    assert!(
+        inner_rect.left() <= position.x && inner_rect.bottom() - 30.0 <= position.y,
+        "The popup should be placed below the button, in screen coordinates: {position:?}"
+    );
+}
+
+#[test]
+fn test_hover_timer_is_per_widget() {
+    let mut timer = HoverTimer::default();
+    let (outer, inner) = (Id::new("outer"), Id::new("inner"));
+
+    assert_eq!(timer.hover_duration(outer, 0.0, 0), 0.0);
+
+    // A widget in the tooltip of `outer` is hovered too:
+    assert_eq!(timer.hover_duration(outer, 1.0, 1), 1.0);
+    assert_eq!(timer.hover_duration(inner, 1.0, 1), 0.0);
+    assert_eq!(timer.hover_duration(outer, 2.0, 2), 2.0);
+    assert_eq!(timer.hover_duration(inner, 2.0, 2), 1.0);
+
+    // `outer` is no longer hovered, so its hover starts over:
+    assert_eq!(timer.hover_duration(inner, 3.0, 3), 2.0);
+    assert_eq!(timer.hover_duration(outer, 4.0, 4), 0.0);
+    assert_eq!(timer.hover_duration(inner, 4.0, 4), 3.0);
 }
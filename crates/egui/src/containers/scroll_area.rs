@@ -1,6 +1,6 @@
 #![allow(clippy::needless_range_loop)]
 
-use crate::*;
+use crate::{os::OperatingSystem, *};
 
 #[derive(Clone, Copy, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
@@ -22,6 +22,21 @@ pub struct State {
     #[cfg_attr(feature = "serde", serde(skip))]
     vel: Vec2,
 
+    /// How far the contents have been dragged past their ends, see [`ScrollStyle::overscroll`].
+    ///
+    /// The contents move less than this, see [`rubber_band`].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    overscroll: Vec2,
+
+    /// How far we could scroll last frame.
+    #[cfg_attr(feature = "serde", serde(skip, default = "unknown_max_offset"))]
+    max_offset: Vec2,
+
+    /// The offset we are smoothly scrolling to, and over how many seconds,
+    /// see [`Response::scroll_to_me_animated`].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    offset_target: [Option<(f32, f32)>; 2],
+
     /// Mouse offset relative to the top of the handle when started moving the handle.
     scroll_start_offset_from_top_left: [Option<f32>; 2],
 
@@ -31,6 +46,11 @@ pub struct State {
     scroll_stuck_to_end: Vec2b,
 }
 
+/// Until we have shown the contents, we don't know how far we can scroll.
+fn unknown_max_offset() -> Vec2 {
+    Vec2::INFINITY
+}
+
 impl Default for State {
     fn default() -> Self {
         Self {
@@ -39,6 +59,9 @@ impl Default for State {
             content_is_too_large: Vec2b::FALSE,
             scroll_bar_interaction: Vec2b::FALSE,
             vel: Vec2::ZERO,
+            overscroll: Vec2::ZERO,
+            max_offset: unknown_max_offset(),
+            offset_target: [None; 2],
             scroll_start_offset_from_top_left: [None; 2],
             scroll_stuck_to_end: Vec2b::TRUE,
         }
@@ -58,6 +81,16 @@ impl State {
     pub fn velocity(&self) -> Vec2 {
         self.vel
     }
+
+    /// How far the contents are currently pulled past their ends, see [`ScrollStyle::overscroll`].
+    ///
+    /// Negative when pulled past the start, positive when pulled past the end.
+    pub fn overscroll(&self, inner_size: Vec2) -> Vec2 {
+        vec2(
+            rubber_band(self.overscroll.x, inner_size.x),
+            rubber_band(self.overscroll.y, inner_size.y),
+        )
+    }
 }
 
 pub struct ScrollAreaOutput<R> {
@@ -165,6 +198,12 @@ pub struct ScrollArea {
     /// end position until user manually changes position. It will become true
     /// again once scroll handle makes contact with end.
     stick_to_end: Vec2b,
+
+    /// Scroll to this row in [`Self::show_rows`] or [`Self::show_variable_rows`].
+    scroll_to_row: Option<(usize, Option<Align>)>,
+
+    /// Share the scroll offset with the other scroll areas with the same id.
+    sync_with: Option<Id>,
 }
 
 impl ScrollArea {
@@ -208,6 +247,8 @@ impl ScrollArea {
             scrolling_enabled: true,
             drag_to_scroll: true,
             stick_to_end: Vec2b::FALSE,
+            scroll_to_row: None,
+            sync_with: None,
         }
     }
 
@@ -400,6 +441,52 @@ impl ScrollArea {
         self.stick_to_end[1] = stick;
         self
     }
+
+    /// Scroll so that the given row becomes visible.
+    ///
+    /// Only used by [`Self::show_rows`] and [`Self::show_variable_rows`],
+    /// where the row doesn't need to be shown for the scroll area to know where it is.
+    ///
+    /// If `align` is not provided, it'll scroll enough to bring the row into view.
+    ///
+    /// See also: [`Ui::scroll_to_rect`].
+    #[inline]
+    pub fn scroll_to_row(mut self, row: usize, align: Option<Align>) -> Self {
+        self.scroll_to_row = Some((row, align));
+        self
+    }
+
+    /// Keep this scroll area scrolled to the same offset as all other scroll areas
+    /// that sync with the same id, e.g. a header row and the table below it,
+    /// or a code editor and its minimap.
+    ///
+    /// Only the directions this scroll area can scroll in are synced,
+    /// so a horizontal header can follow a table that scrolls in both directions.
+    ///
+    /// The shared offset is kept in [`crate::Memory`], see [`crate::Memory::set_synced_scroll_offset`].
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// let sync_id = egui::Id::new("table_scroll");
+    /// egui::ScrollArea::horizontal()
+    ///     .id_source("header")
+    ///     .sync_with(sync_id)
+    ///     .show(ui, |ui| {
+    ///         ui.label("A wide header");
+    ///     });
+    /// egui::ScrollArea::both()
+    ///     .id_source("body")
+    ///     .sync_with(sync_id)
+    ///     .show(ui, |ui| {
+    ///         ui.label("A wide and long body");
+    ///     });
+    /// # });
+    /// ```
+    #[inline]
+    pub fn sync_with(mut self, sync_id: impl Into<Id>) -> Self {
+        self.sync_with = Some(sync_id.into());
+        self
+    }
 }
 
 struct Prepared {
@@ -438,9 +525,19 @@ struct Prepared {
 
     scrolling_enabled: bool,
     stick_to_end: Vec2b,
+
+    /// Did we drag or fling the contents this frame, so that going past the ends is overscroll?
+    can_overscroll: Vec2b,
+
+    /// The id we sync the offset with, and the offset we started the frame with.
+    sync_with: Option<(Id, Vec2)>,
 }
 
 impl ScrollArea {
+    fn id(ui: &Ui, id_source: Option<Id>) -> Id {
+        ui.make_persistent_id(id_source.unwrap_or_else(|| Id::new("scroll_area")))
+    }
+
     fn begin(self, ui: &mut Ui) -> Prepared {
         let Self {
             scroll_enabled,
@@ -454,12 +551,13 @@ impl ScrollArea {
             scrolling_enabled,
             drag_to_scroll,
             stick_to_end,
+            scroll_to_row: _,
+            sync_with,
         } = self;
 
         let ctx = ui.ctx().clone();
 
-        let id_source = id_source.unwrap_or_else(|| Id::new("scroll_area"));
-        let id = ui.make_persistent_id(id_source);
+        let id = Self::id(ui, id_source);
         ctx.check_for_id_clash(
             id,
             Rect::from_min_size(ui.available_rect_before_wrap().min, Vec2::ZERO),
@@ -467,9 +565,36 @@ impl ScrollArea {
         );
         let mut state = State::load(&ctx, id).unwrap_or_default();
 
+        if let Some(sync_id) = sync_with {
+            if let Some(synced_offset) = ctx.memory(|mem| mem.synced_scroll_offset(sync_id)) {
+                for d in 0..2 {
+                    if scroll_enabled[d] {
+                        // The others may be able to scroll further:
+                        state.offset[d] = synced_offset[d].at_most(state.max_offset[d]);
+                    }
+                }
+            }
+        }
+
         state.offset.x = offset_x.unwrap_or(state.offset.x);
         state.offset.y = offset_y.unwrap_or(state.offset.y);
 
+        for d in 0..2 {
+            if let Some((target, duration)) = state.offset_target[d] {
+                let target = target.clamp(0.0, state.max_offset[d]);
+                state.offset[d] = ctx.animate_value_with_options(
+                    id.with("offset_target").with(d),
+                    target,
+                    AnimationOptions::default().with_duration(duration),
+                );
+                if state.offset[d] == target {
+                    state.offset_target[d] = None;
+                }
+            }
+        }
+
+        let sync_with = sync_with.map(|sync_id| (sync_id, state.offset));
+
         let show_bars: Vec2b = match scroll_bar_visibility {
             ScrollBarVisibility::AlwaysHidden => Vec2b::FALSE,
             ScrollBarVisibility::VisibleWhenNeeded => state.show_scroll,
@@ -518,7 +643,11 @@ impl ScrollArea {
             }
         }
 
-        let content_max_rect = Rect::from_min_size(inner_rect.min - state.offset, content_max_size);
+        let scroll_style = ui.spacing().scroll;
+        let overscroll = state.overscroll(inner_size);
+
+        let content_max_rect =
+            Rect::from_min_size(inner_rect.min - state.offset - overscroll, content_max_size);
         let mut content_ui = ui.child_ui(content_max_rect, *ui.layout());
 
         {
@@ -541,7 +670,10 @@ impl ScrollArea {
             content_ui.set_clip_rect(content_clip_rect);
         }
 
-        let viewport = Rect::from_min_size(Pos2::ZERO + state.offset, inner_size);
+        let viewport = Rect::from_min_size(Pos2::ZERO + state.offset + overscroll, inner_size);
+
+        let mut dragged = false;
+        let mut can_overscroll = Vec2b::FALSE;
 
         if (scrolling_enabled && drag_to_scroll)
             && (state.content_is_too_large[0] || state.content_is_too_large[1])
@@ -549,16 +681,48 @@ impl ScrollArea {
             // Drag contents to scroll (for touch screens mostly).
             // We must do this BEFORE adding content to the `ScrollArea`,
             // or we will steal input from the widgets we contain.
-            let content_response = ui.interact(inner_rect, id.with("area"), Sense::drag());
+            let content_response = ui
+                .interact(inner_rect, id.with("area"), Sense::drag())
+                .with_extra_pointers();
+            dragged = content_response.dragged();
+            let pointer = content_response
+                .dragging_pointer()
+                .unwrap_or(PointerId::PRIMARY);
+
+            if dragged {
+                let kinetic = match scroll_style.kinetic_scrolling {
+                    KineticScrolling::Never => false,
+                    KineticScrolling::Touch => {
+                        ui.input(|i| i.any_touches())
+                            || matches!(ctx.os(), OperatingSystem::Android | OperatingSystem::IOS)
+                    }
+                    KineticScrolling::Always => true,
+                };
 
-            if content_response.dragged() {
                 for d in 0..2 {
                     if scroll_enabled[d] {
                         ui.input(|input| {
-                            state.offset[d] -= input.pointer.delta()[d];
-                            state.vel[d] = input.pointer.velocity()[d];
+                            let pointer = input.pointer_state(pointer).unwrap_or(&input.pointer);
+                            let mut delta = -pointer.delta()[d];
+
+                            // Dragging back towards the contents first undoes the overscroll:
+                            if state.overscroll[d] * delta < 0.0 {
+                                let overscroll = state.overscroll[d] + delta;
+                                if overscroll * state.overscroll[d] > 0.0 {
+                                    state.overscroll[d] = overscroll;
+                                    delta = 0.0;
+                                } else {
+                                    state.overscroll[d] = 0.0;
+                                    delta = overscroll;
+                                }
+                            }
+
+                            state.offset[d] += delta;
+                            state.offset_target[d] = None;
+                            state.vel[d] = if kinetic { pointer.velocity()[d] } else { 0.0 };
                         });
                         state.scroll_stuck_to_end[d] = false;
+                        can_overscroll[d] = scroll_style.overscroll;
                     } else {
                         state.vel[d] = 0.0;
                     }
@@ -566,10 +730,9 @@ impl ScrollArea {
             } else {
                 // Kinetic scrolling
                 let stop_speed = 20.0; // Pixels per second.
-                let friction_coeff = 1000.0; // Pixels per second squared.
                 let dt = ui.input(|i| i.unstable_dt);
 
-                let friction = friction_coeff * dt;
+                let friction = scroll_style.kinetic_friction * dt;
                 if friction > state.vel.length() || state.vel.length() < stop_speed {
                     state.vel = Vec2::ZERO;
                 } else {
@@ -577,11 +740,31 @@ impl ScrollArea {
                     // Offset has an inverted coordinate system compared to
                     // the velocity, so we subtract it instead of adding it
                     state.offset -= state.vel * dt;
-                    ctx.request_repaint();
+                    can_overscroll = Vec2b::new(
+                        scroll_enabled[0] && scroll_style.overscroll,
+                        scroll_enabled[1] && scroll_style.overscroll,
+                    );
+                    ctx.request_animation_repaint_from(id);
                 }
             }
         }
 
+        if !dragged && state.overscroll != Vec2::ZERO {
+            // Bounce back:
+            let dt = if ctx.options(|o| o.animations_enabled) {
+                ui.input(|i| i.stable_dt).at_most(0.1)
+            } else {
+                f32::INFINITY
+            };
+            state.overscroll *= (-OVERSCROLL_BOUNCE_RATE * dt).exp();
+            for d in 0..2 {
+                if rubber_band(state.overscroll[d], inner_size[d]).abs() < 0.5 {
+                    state.overscroll[d] = 0.0;
+                }
+            }
+            ctx.request_animation_repaint_from(id);
+        }
+
         Prepared {
             id,
             state,
@@ -595,12 +778,15 @@ impl ScrollArea {
             viewport,
             scrolling_enabled,
             stick_to_end,
+            can_overscroll,
+            sync_with,
         }
     }
 
     /// Show the [`ScrollArea`], and add the contents to the viewport.
     ///
-    /// If the inner area can be very long, consider using [`Self::show_rows`] instead.
+    /// If the inner area can be very long, consider using [`Self::show_rows`]
+    /// or [`Self::show_variable_rows`] instead.
     pub fn show<R>(
         self,
         ui: &mut Ui,
@@ -611,6 +797,8 @@ impl ScrollArea {
 
     /// Efficiently show only the visible part of a large number of rows.
     ///
+    /// All rows must have the same height. See [`Self::show_variable_rows`] for rows of different heights.
+    ///
     /// ```
     /// # egui::__run_test_ui(|ui| {
     /// let text_style = egui::TextStyle::Body;
@@ -634,9 +822,19 @@ impl ScrollArea {
     ) -> ScrollAreaOutput<R> {
         let spacing = ui.spacing().item_spacing;
         let row_height_with_spacing = row_height_sans_spacing + spacing.y;
+        let scroll_to_row = self.scroll_to_row;
         self.show_viewport(ui, |ui, viewport| {
             ui.set_height((row_height_with_spacing * total_rows as f32 - spacing.y).at_least(0.0));
 
+            if let Some((row, align)) = scroll_to_row {
+                let top = ui.max_rect().top() + row as f32 * row_height_with_spacing;
+                let rect = Rect::from_x_y_ranges(
+                    ui.max_rect().x_range(),
+                    top..=top + row_height_sans_spacing,
+                );
+                ui.scroll_to_rect(rect, align);
+            }
+
             let mut min_row = (viewport.min.y / row_height_with_spacing).floor() as usize;
             let mut max_row = (viewport.max.y / row_height_with_spacing).ceil() as usize + 1;
             if max_row > total_rows {
@@ -658,6 +856,140 @@ impl ScrollArea {
         })
     }
 
+    /// Efficiently show only the visible part of a large number of rows of different heights.
+    ///
+    /// `estimate_row_height` is asked for the height (excluding spacing) of every row once,
+    /// and `add_row` is only called for the rows that are visible.
+    /// The real height of those rows is remembered and replaces the estimate,
+    /// so the better the estimate, the less the scroll bar jumps around while scrolling.
+    /// The heights are stored next to the [`State`] of the scroll area,
+    /// so use [`Self::id_source`] if you show different lists in the same place.
+    ///
+    /// Finding the visible rows, or the position of a row for [`Self::scroll_to_row`],
+    /// takes logarithmic time, so this works well for millions of rows.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// let total_rows = 1_000_000;
+    /// let row_height = ui.text_style_height(&egui::TextStyle::Body);
+    /// egui::ScrollArea::vertical()
+    ///     .scroll_to_row(500_000, Some(egui::Align::Center))
+    ///     .show_variable_rows(
+    ///         ui,
+    ///         total_rows,
+    ///         |row| if row % 10 == 0 { 3.0 * row_height } else { row_height },
+    ///         |ui, row| {
+    ///             if row % 10 == 0 {
+    ///                 ui.heading(format!("Heading {row}"));
+    ///             } else {
+    ///                 ui.label(format!("Row {row}"));
+    ///             }
+    ///         },
+    ///     );
+    /// # });
+    /// ```
+    pub fn show_variable_rows(
+        self,
+        ui: &mut Ui,
+        total_rows: usize,
+        estimate_row_height: impl Fn(usize) -> f32,
+        mut add_row: impl FnMut(&mut Ui, usize),
+    ) -> ScrollAreaOutput<()> {
+        let spacing = ui.spacing().item_spacing;
+        let id = Self::id(ui, self.id_source);
+
+        let mut row_heights =
+            ui.data_mut(|d| std::mem::take(d.get_temp_mut_or_default::<RowHeights>(id)));
+        row_heights.resize(total_rows, spacing.y, estimate_row_height);
+        if self.scroll_to_row.is_some() {
+            row_heights.scroll_to_row = self.scroll_to_row;
+        }
+
+        // How much the first visible row changed height when we measured it:
+        let mut first_row_delta = 0.0;
+        // Where to scroll to show `scroll_to_row`:
+        let mut scroll_offset = None;
+
+        let mut output = self.show_viewport(ui, |ui, viewport| {
+            ui.set_height((row_heights.total_height() - spacing.y).at_least(0.0));
+
+            let top = ui.max_rect().top();
+
+            if 0 < total_rows {
+                let first_row = row_heights.row_at(viewport.min.y);
+                let first_row_top = row_heights.offset_of_row(first_row);
+                let rect = Rect::from_x_y_ranges(
+                    ui.max_rect().x_range(),
+                    top + first_row_top..=top + viewport.max.y.max(first_row_top),
+                );
+
+                let shown_rows = ui
+                    .allocate_ui_at_rect(rect, |viewport_ui| {
+                        let mut any_height_changed = false;
+                        let mut y = first_row_top;
+                        let mut row = first_row;
+                        while row < total_rows && y < viewport.max.y {
+                            let response = viewport_ui.push_id(row, |ui| add_row(ui, row)).response;
+                            let height = response.rect.height();
+                            let delta = row_heights.set_height_of_row(row, height);
+                            if row == first_row && first_row_top < viewport.min.y {
+                                first_row_delta = delta;
+                            }
+                            any_height_changed |= delta != 0.0;
+                            y += height + spacing.y;
+                            row += 1;
+                        }
+                        (first_row..row, any_height_changed)
+                    })
+                    .inner;
+
+                // Now that we know the height of the visible rows:
+                if let Some((row, align)) = row_heights.scroll_to_row {
+                    let row = row.min(total_rows - 1);
+                    let row_top = row_heights.offset_of_row(row);
+                    let row_height = row_heights.height_of_row(row);
+                    let visible_height = viewport.height();
+                    let offset = match align {
+                        Some(align) => row_top - align.to_factor() * (visible_height - row_height),
+                        None if row_top < viewport.min.y => row_top,
+                        None if viewport.max.y < row_top + row_height => {
+                            row_top + row_height - visible_height
+                        }
+                        None => viewport.min.y,
+                    };
+                    let max_offset = row_heights.total_height() - spacing.y - visible_height;
+                    let offset = offset.at_most(max_offset).at_least(0.0);
+
+                    // Once the row is shown where we want it, and we have measured everything around it,
+                    // we are done. Until then, measuring the rows can move it, so we keep scrolling to it.
+                    let (shown_rows, any_height_changed) = shown_rows;
+                    if shown_rows.contains(&row) && !any_height_changed && offset == viewport.min.y
+                    {
+                        row_heights.scroll_to_row = None;
+                    } else {
+                        scroll_offset = Some(offset);
+                    }
+                    first_row_delta = 0.0;
+                }
+            }
+        });
+
+        if let Some(offset) = scroll_offset {
+            output.state.offset.y = offset;
+            output.state.store(ui.ctx(), id);
+            ui.ctx().request_repaint_from(id);
+        } else if first_row_delta != 0.0 {
+            // Keep the rows below the first visible row where they are on screen:
+            output.state.offset.y += first_row_delta;
+            output.state.store(ui.ctx(), id);
+            ui.ctx().request_repaint_from(id);
+        }
+
+        ui.data_mut(|d| d.insert_temp(id, row_heights));
+
+        output
+    }
+
     /// This can be used to only paint the visible part of the contents.
     ///
     /// `add_contents` is given the viewport rectangle, which is the relative view of the content.
@@ -706,6 +1038,8 @@ impl Prepared {
             viewport: _,
             scrolling_enabled,
             stick_to_end,
+            can_overscroll,
+            sync_with,
         } = self;
 
         let content_size = content_ui.min_size();
@@ -717,41 +1051,69 @@ impl Prepared {
                     .ctx()
                     .frame_state_mut(|state| state.scroll_target[d].take());
                 if let Some((scroll, align)) = scroll_target {
-                    let min = content_ui.min_rect().min[d];
                     let clip_rect = content_ui.clip_rect();
-                    let visible_range = min..=min + clip_rect.size()[d];
-                    let (start, end) = (scroll.min, scroll.max);
-                    let clip_start = clip_rect.min[d];
-                    let clip_end = clip_rect.max[d];
-                    let mut spacing = ui.spacing().item_spacing[d];
-
-                    let delta = if let Some(align) = align {
-                        let center_factor = align.to_factor();
-
-                        let offset =
-                            lerp(scroll, center_factor) - lerp(visible_range, center_factor);
-
-                        // Depending on the alignment we need to add or subtract the spacing
-                        spacing *= remap(center_factor, 0.0..=1.0, -1.0..=1.0);
-
-                        offset + spacing - state.offset[d]
-                    } else if start < clip_start && end < clip_end {
-                        -(clip_start - start + spacing).min(clip_end - end - spacing)
-                    } else if end > clip_end && start > clip_start {
-                        (end - clip_end + spacing).min(start - clip_start - spacing)
-                    } else {
-                        // Ui is already in view, no need to adjust scroll.
-                        0.0
-                    };
+                    let delta = scroll_target_delta(
+                        content_ui.min_rect().min[d],
+                        Rangef::new(clip_rect.min[d], clip_rect.max[d]),
+                        state.offset[d],
+                        ui.spacing().item_spacing[d],
+                        scroll,
+                        align,
+                    );
 
                     if delta != 0.0 {
                         state.offset[d] += delta;
-                        ui.ctx().request_repaint();
+                        state.offset_target[d] = None;
+                        ui.ctx().request_repaint_from(id);
                     }
                 }
             }
         }
 
+        let animated_scroll_target = ui.ctx().frame_state(|state| state.animated_scroll_target);
+        if let Some((rect, align, duration)) = animated_scroll_target {
+            if content_ui.min_rect().expand(0.5).contains_rect(rect) {
+                let mut delta = Vec2::ZERO;
+                for d in 0..2 {
+                    if scroll_enabled[d] {
+                        // Unlike the clip rect, this also works when we are scrolled out of view ourselves:
+                        delta[d] = scroll_target_delta(
+                            content_ui.min_rect().min[d],
+                            Rangef::new(inner_rect.min[d], inner_rect.max[d]),
+                            state.offset[d],
+                            ui.spacing().item_spacing[d],
+                            Rangef::new(rect.min[d], rect.max[d]),
+                            align,
+                        );
+                    }
+                }
+
+                for d in 0..2 {
+                    if delta[d] != 0.0 {
+                        // Start the animation from where we are now:
+                        let animation_id = id.with("offset_target").with(d);
+                        ui.ctx().animate_value_with_options(
+                            animation_id,
+                            state.offset[d],
+                            AnimationOptions::DISABLED,
+                        );
+                        state.offset_target[d] = Some((state.offset[d] + delta[d], duration));
+                        ui.ctx().request_repaint_from(id);
+                    }
+                }
+
+                // Leave the part of us that the rect will be shown in for the enclosing scroll areas:
+                let rect = rect.translate(-delta);
+                let shown_rect = Rect::from_min_max(
+                    rect.min.clamp(inner_rect.min, inner_rect.max),
+                    rect.max.clamp(inner_rect.min, inner_rect.max),
+                );
+                ui.ctx().frame_state_mut(|state| {
+                    state.animated_scroll_target = Some((shown_rect, align, duration));
+                });
+            }
+        }
+
         let inner_rect = {
             // At this point this is the available size for the inner rect.
             let mut inner_size = inner_rect.size();
@@ -776,6 +1138,20 @@ impl Prepared {
         );
 
         let max_offset = content_size - inner_rect.size();
+
+        for d in 0..2 {
+            if can_overscroll[d] {
+                // Whatever was dragged or flung past the ends becomes overscroll, and stops the fling:
+                let clamped_offset = state.offset[d].at_most(max_offset[d]).at_least(0.0);
+                let overscroll = state.offset[d] - clamped_offset;
+                if overscroll != 0.0 {
+                    state.overscroll[d] += overscroll;
+                    state.offset[d] = clamped_offset;
+                    state.vel[d] = 0.0;
+                }
+            }
+        }
+
         let is_hovering_outer_rect = ui.rect_contains_pointer(outer_rect);
         if scrolling_enabled && is_hovering_outer_rect {
             let always_scroll_enabled_direction = ui.style().always_scroll_the_only_direction
@@ -796,6 +1172,7 @@ impl Prepared {
 
                     if scrolling_up || scrolling_down {
                         state.offset[d] -= scroll_delta;
+                        state.offset_target[d] = None;
 
                         // Clear scroll delta so no parent scroll will use it:
                         ui.ctx().input_mut(|input| {
@@ -861,9 +1238,11 @@ impl Prepared {
                 let is_hovering_bar_area = is_hovering_outer_rect
                     && ui.rect_contains_pointer(max_bar_rect)
                     || state.scroll_bar_interaction[d];
-                let is_hovering_bar_area_t = ui
-                    .ctx()
-                    .animate_bool(id.with((d, "bar_hover")), is_hovering_bar_area);
+                let is_hovering_bar_area_t = ui.ctx().animate_bool_in_rect(
+                    id.with((d, "bar_hover")),
+                    is_hovering_bar_area,
+                    max_bar_rect,
+                );
                 let width = show_factor
                     * lerp(
                         scroll_style.floating_width..=scroll_style.bar_width,
@@ -929,7 +1308,9 @@ impl Prepared {
             } else {
                 Sense::hover()
             };
-            let response = ui.interact(outer_scroll_rect, interact_id, sense);
+            let response = ui
+                .interact(outer_scroll_rect, interact_id, sense)
+                .with_extra_pointers();
 
             state.scroll_bar_interaction[d] = response.hovered() || response.dragged();
 
@@ -949,6 +1330,7 @@ impl Prepared {
 
                 let new_handle_top = pointer_pos[d] - *scroll_start_offset_from_top_left;
                 state.offset[d] = remap(new_handle_top, main_range, 0.0..=content_size[d]);
+                state.offset_target[d] = None;
 
                 // some manual action taken, scroll not stuck
                 state.scroll_stuck_to_end[d] = false;
@@ -1017,9 +1399,10 @@ impl Prepared {
                     if response.hovered() || response.dragged() {
                         scroll_style.interact_handle_opacity
                     } else {
-                        let is_hovering_outer_rect_t = ui.ctx().animate_bool(
+                        let is_hovering_outer_rect_t = ui.ctx().animate_bool_in_rect(
                             id.with((d, "is_hovering_outer_rect")),
                             is_hovering_outer_rect,
+                            outer_scroll_rect,
                         );
                         lerp(
                             scroll_style.dormant_handle_opacity
@@ -1070,7 +1453,7 @@ impl Prepared {
         ui.advance_cursor_after_rect(outer_rect);
 
         if show_scroll_this_frame != state.show_scroll {
-            ui.ctx().request_repaint();
+            ui.ctx().request_repaint_from(id);
         }
 
         let available_offset = content_size - inner_rect.size();
@@ -1091,9 +1474,512 @@ impl Prepared {
 
         state.show_scroll = show_scroll_this_frame;
         state.content_is_too_large = content_is_too_large;
+        state.max_offset = available_offset.max(Vec2::ZERO);
+
+        if let Some((sync_id, start_offset)) = sync_with {
+            // Only share what changed because of this scroll area,
+            // and not just because it has less content than the others:
+            let clamped_start_offset = start_offset.min(available_offset).max(Vec2::ZERO);
+            let changed = ui.ctx().memory_mut(|mem| {
+                let previous = mem.synced_scroll_offset(sync_id);
+                let mut synced_offset = previous.unwrap_or(state.offset);
+                for d in 0..2 {
+                    if scroll_enabled[d] && state.offset[d] != clamped_start_offset[d] {
+                        synced_offset[d] = state.offset[d];
+                    }
+                }
+                mem.set_synced_scroll_offset(sync_id, synced_offset);
+                previous.map_or(false, |previous| previous != synced_offset)
+            });
+            if changed {
+                // The scroll areas shown before this one need to catch up:
+                ui.ctx().request_repaint_from(id);
+            }
+        }
 
         state.store(ui.ctx(), id);
 
         (content_size, state)
     }
 }
+
+/// The heights of the rows of [`ScrollArea::show_variable_rows`], excluding spacing.
+///
+/// Stored as a Fenwick tree, so that changing the height of a row, adding a row,
+/// and finding the offset of a row are all logarithmic in the number of rows.
+#[derive(Clone, Debug, Default)]
+struct RowHeights {
+    /// The height of each row.
+    heights: Vec<f32>,
+
+    /// `tree[i]` is the sum of the heights of the `i & (i + 1)..=i` rows.
+    ///
+    /// We use `f64` so that the sums stay accurate for millions of rows.
+    tree: Vec<f64>,
+
+    /// The item spacing between the rows.
+    spacing: f32,
+
+    /// Set by [`ScrollArea::scroll_to_row`], and kept until the row is shown.
+    scroll_to_row: Option<(usize, Option<Align>)>,
+}
+
+impl RowHeights {
+    /// Make room for `total_rows`, estimating the height of rows we haven't seen before.
+    fn resize(&mut self, total_rows: usize, spacing: f32, estimate: impl Fn(usize) -> f32) {
+        self.spacing = spacing;
+
+        // `tree[i]` only depends on the rows up to `i`, so we can drop rows from the end,
+        // and add new rows one at a time:
+        self.heights.truncate(total_rows);
+        self.tree.truncate(total_rows);
+        for row in self.heights.len()..total_rows {
+            let height = estimate(row).at_least(0.0);
+            let first = row & (row + 1);
+            let sum = self.prefix_sum(row) - self.prefix_sum(first) + height as f64;
+            self.heights.push(height);
+            self.tree.push(sum);
+        }
+    }
+
+    /// Including the spacing after the last row.
+    fn total_height(&self) -> f32 {
+        self.offset_of_row(self.heights.len())
+    }
+
+    fn height_of_row(&self, row: usize) -> f32 {
+        self.heights[row]
+    }
+
+    /// The sum of the heights of the rows before this one, without spacing.
+    fn prefix_sum(&self, row: usize) -> f64 {
+        let mut sum = 0.0;
+        let mut i = row.min(self.tree.len());
+        while 0 < i {
+            sum += self.tree[i - 1];
+            i &= i - 1;
+        }
+        sum
+    }
+
+    /// The sum of the heights of all rows before this one, and the spacing after each of them.
+    fn offset_of_row(&self, row: usize) -> f32 {
+        let row = row.min(self.tree.len());
+        (self.prefix_sum(row) + row as f64 * self.spacing as f64) as f32
+    }
+
+    /// Returns how much the height changed.
+    fn set_height_of_row(&mut self, row: usize, height: f32) -> f32 {
+        let delta = height - self.heights[row];
+        if delta != 0.0 {
+            self.heights[row] = height;
+            let mut i = row;
+            while i < self.tree.len() {
+                self.tree[i] += delta as f64;
+                i |= i + 1;
+            }
+        }
+        delta
+    }
+
+    /// The row at the given offset from the top.
+    ///
+    /// There must be at least one row.
+    fn row_at(&self, offset: f32) -> usize {
+        let offset = offset as f64;
+        let spacing = self.spacing as f64;
+        let mut sum = 0.0;
+        let mut row = 0;
+        let mut step = self.tree.len().next_power_of_two();
+        while 0 < step {
+            let next = row + step;
+            if next <= self.tree.len()
+                && sum + self.tree[next - 1] + next as f64 * spacing <= offset
+            {
+                sum += self.tree[next - 1];
+                row = next;
+            }
+            step /= 2;
+        }
+        row.min(self.heights.len() - 1)
+    }
+}
+
+#[test]
+fn test_row_heights() {
+    let mut heights = RowHeights::default();
+    heights.resize(1000, 4.0, |row| (row % 7) as f32);
+    let offset_of_row = |heights: &RowHeights, row: usize| -> f32 {
+        heights.heights[..row].iter().sum::<f32>() + row as f32 * heights.spacing
+    };
+
+    for row in [0, 1, 2, 100, 999, 1000] {
+        assert_eq!(heights.offset_of_row(row), offset_of_row(&heights, row));
+    }
+    assert_eq!(heights.set_height_of_row(10, 14.0), 14.0 - 3.0);
+    for row in [0, 10, 11, 100, 999, 1000] {
+        assert_eq!(heights.offset_of_row(row), offset_of_row(&heights, row));
+    }
+    assert_eq!(heights.total_height(), offset_of_row(&heights, 1000));
+
+    for row in [0, 1, 10, 11, 500, 999] {
+        let top = heights.offset_of_row(row);
+        assert_eq!(heights.row_at(top), row);
+        assert_eq!(heights.row_at(top + 0.5 * heights.height_of_row(row)), row);
+    }
+    assert_eq!(heights.row_at(-10.0), 0);
+    assert_eq!(heights.row_at(1e9), 999);
+
+    // Growing keeps the heights we already know:
+    heights.resize(2000, 4.0, |_| 1.0);
+    assert_eq!(heights.height_of_row(10), 14.0);
+    assert_eq!(heights.height_of_row(1500), 1.0);
+    for row in [0, 10, 999, 1000, 1001, 1500, 2000] {
+        assert_eq!(heights.offset_of_row(row), offset_of_row(&heights, row));
+    }
+
+    // So does shrinking, and changing the spacing:
+    heights.resize(700, 2.0, |_| unreachable!());
+    assert_eq!(heights.height_of_row(10), 14.0);
+    for row in [0, 10, 11, 699, 700] {
+        assert_eq!(heights.offset_of_row(row), offset_of_row(&heights, row));
+    }
+}
+
+#[test]
+fn test_show_variable_rows_scroll_to_row() {
+    use crate::test_harness::Harness;
+
+    let total_rows = 100_000;
+    let row_height = |row: usize| 10.0 + (row % 3) as f32 * 5.0;
+
+    let scroll_to = std::cell::Cell::new(Some((50_000, Align::TOP)));
+    let shown_rows = std::cell::RefCell::new(Vec::new());
+    let inner_rect = std::cell::Cell::new(Rect::NOTHING);
+
+    let mut harness = Harness::new_ui(|ui| {
+        let mut scroll_area = ScrollArea::vertical();
+        if let Some((row, align)) = scroll_to.take() {
+            scroll_area = scroll_area.scroll_to_row(row, Some(align));
+        }
+        shown_rows.borrow_mut().clear();
+        let output = scroll_area.show_variable_rows(
+            ui,
+            total_rows,
+            |_| 12.0,
+            |ui, row| {
+                let (rect, _) = ui.allocate_exact_size(vec2(10.0, row_height(row)), Sense::hover());
+                shown_rows.borrow_mut().push((row, rect));
+            },
+        );
+        inner_rect.set(output.inner_rect);
+    });
+    let spacing = harness.ctx().style().spacing.item_spacing.y;
+    let shown_row = |row: usize| {
+        shown_rows
+            .borrow()
+            .iter()
+            .find(|(r, _)| *r == row)
+            .map(|(_, rect)| *rect)
+            .unwrap_or_else(|| panic!("Row {row} is not shown"))
+    };
+
+    let rect = shown_row(50_000);
+    assert_eq!(rect.top(), inner_rect.get().top());
+    assert_eq!(rect.height(), row_height(50_000));
+
+    scroll_to.set(Some((99_000, Align::Center)));
+    harness.run();
+    let rect = shown_row(99_000);
+    assert_eq!(rect.center().y, inner_rect.get().center().y);
+
+    scroll_to.set(Some((total_rows - 1, Align::TOP)));
+    harness.run();
+    let rect = shown_row(total_rows - 1);
+    assert_eq!(
+        rect.bottom(),
+        inner_rect.get().bottom(),
+        "Can't scroll past the end"
+    );
+
+    scroll_to.set(Some((0, Align::TOP)));
+    harness.run();
+    assert_eq!(shown_rows.borrow()[0].0, 0);
+    assert_eq!(shown_row(0).top(), inner_rect.get().top());
+    assert_eq!(shown_row(1).top(), shown_row(0).bottom() + spacing);
+}
+
+/// How much to change the scroll offset along one axis to show `range` (in screen coordinates),
+/// aligned according to `align`, or just enough to bring it into view if `align` is `None`.
+///
+/// The contents start at `content_min`, and are shown in the `visible` part of the screen.
+fn scroll_target_delta(
+    content_min: f32,
+    visible: Rangef,
+    offset: f32,
+    mut spacing: f32,
+    range: Rangef,
+    align: Option<Align>,
+) -> f32 {
+    let visible_range = content_min..=content_min + visible.span();
+    let (start, end) = (range.min, range.max);
+    let (clip_start, clip_end) = (visible.min, visible.max);
+
+    if let Some(align) = align {
+        let center_factor = align.to_factor();
+
+        let target_offset = lerp(range, center_factor) - lerp(visible_range, center_factor);
+
+        // Depending on the alignment we need to add or subtract the spacing
+        spacing *= remap(center_factor, 0.0..=1.0, -1.0..=1.0);
+
+        target_offset + spacing - offset
+    } else if start < clip_start && end < clip_end {
+        -(clip_start - start + spacing).min(clip_end - end - spacing)
+    } else if end > clip_end && start > clip_start {
+        (end - clip_end + spacing).min(start - clip_start - spacing)
+    } else {
+        // Ui is already in view, no need to adjust scroll.
+        0.0
+    }
+}
+
+/// How quickly the contents bounce back from overscroll, per second.
+const OVERSCROLL_BOUNCE_RATE: f32 = 12.0;
+
+/// How far the contents move when dragged `overscroll` points past their end,
+/// in a scroll area that is `size` points large.
+///
+/// The further you drag, the less the contents follow, never moving further than `size`.
+fn rubber_band(overscroll: f32, size: f32) -> f32 {
+    const RESISTANCE: f32 = 0.55;
+    if overscroll == 0.0 || size <= 0.0 {
+        return 0.0;
+    }
+    let fraction = 1.0 - 1.0 / (overscroll.abs() * RESISTANCE / size + 1.0);
+    overscroll.signum() * fraction * size
+}
+
+#[test]
+fn test_rubber_band() {
+    assert_eq!(rubber_band(0.0, 100.0), 0.0);
+    assert!(0.0 < rubber_band(10.0, 100.0) && rubber_band(10.0, 100.0) < 10.0);
+    assert_eq!(rubber_band(-10.0, 100.0), -rubber_band(10.0, 100.0));
+    assert!(rubber_band(1e6, 100.0) < 100.0);
+}
+
+#[test]
+fn test_kinetic_scrolling() {
+    use crate::test_harness::Harness;
+
+    let offset_after_drag = |kinetic_scrolling: KineticScrolling| {
+        let offset = std::cell::Cell::new(0.0);
+        let mut harness = Harness::new_ui(|ui| {
+            let output = ScrollArea::vertical().show(ui, |ui| {
+                for i in 0..300 {
+                    ui.label(format!("Row {i}"));
+                }
+            });
+            offset.set(output.state.offset.y);
+        });
+        harness
+            .ctx()
+            .style_mut(|style| style.spacing.scroll.kinetic_scrolling = kinetic_scrolling);
+        harness.drag(pos2(400.0, 400.0), pos2(400.0, 200.0));
+        drop(harness);
+        offset.get()
+    };
+
+    let still = offset_after_drag(KineticScrolling::Never);
+    assert!(100.0 < still && still <= 200.0, "{still}");
+    // A mouse is not a touch screen:
+    assert_eq!(offset_after_drag(KineticScrolling::Touch), still);
+    let flung = offset_after_drag(KineticScrolling::Always);
+    assert!(still + 100.0 < flung, "{still} {flung}");
+}
+
+#[test]
+fn test_overscroll() {
+    use crate::test_harness::Harness;
+
+    let first_row_top = std::cell::Cell::new(0.0);
+    let inner_top = std::cell::Cell::new(0.0);
+    let mut harness = Harness::new_ui(|ui| {
+        let output = ScrollArea::vertical().show(ui, |ui| {
+            for i in 0..300 {
+                let response = ui.label(format!("Row {i}"));
+                if i == 0 {
+                    first_row_top.set(response.rect.top());
+                }
+            }
+        });
+        inner_top.set(output.inner_rect.top());
+    });
+    harness
+        .ctx()
+        .style_mut(|style| style.spacing.scroll.overscroll = true);
+    harness.run();
+    assert_eq!(first_row_top.get(), inner_top.get());
+
+    // Pull the contents down past their top:
+    let from = pos2(400.0, 100.0);
+    harness.hover(from);
+    harness.push_event(Event::PointerButton {
+        pos: from,
+        button: PointerButton::Primary,
+        pressed: true,
+        modifiers: Modifiers::NONE,
+    });
+    for y in [120.0, 140.0, 160.0, 180.0, 200.0] {
+        harness.push_event(Event::PointerMoved(pos2(400.0, y)));
+        harness.step();
+    }
+    harness.step();
+    let pulled = first_row_top.get() - inner_top.get();
+    assert!(0.0 < pulled && pulled < 100.0, "{pulled}");
+
+    // Let go, and it bounces back:
+    harness.push_event(Event::PointerButton {
+        pos: pos2(400.0, 200.0),
+        button: PointerButton::Primary,
+        pressed: false,
+        modifiers: Modifiers::NONE,
+    });
+    harness.run();
+    assert_eq!(first_row_top.get(), inner_top.get());
+}
+
+#[test]
+fn test_sync_with() {
+    use crate::test_harness::Harness;
+
+    let offsets = std::cell::Cell::new([0.0; 2]);
+    let mut harness = Harness::new_ui(|ui| {
+        // The labels fill the columns, so don't let them take the drags:
+        ui.style_mut().interaction.selectable_labels = false;
+        ui.columns(2, |columns| {
+            for (i, ui) in columns.iter_mut().enumerate() {
+                let output = ScrollArea::vertical()
+                    .id_source(i)
+                    .sync_with("synced")
+                    .show(ui, |ui| {
+                        // The second one is shorter:
+                        for row in 0..(100 - 20 * i) {
+                            ui.label(format!("Row {row}"));
+                        }
+                    });
+                let mut both = offsets.get();
+                both[i] = output.state.offset.y;
+                offsets.set(both);
+            }
+        });
+    });
+
+    // Keep the offsets where the drags leave them:
+    harness
+        .ctx()
+        .style_mut(|style| style.spacing.scroll.kinetic_scrolling = KineticScrolling::Never);
+
+    // Drag the first one, and the second one follows:
+    harness.drag(pos2(200.0, 400.0), pos2(200.0, 300.0));
+    let [first, second] = offsets.get();
+    assert!(50.0 < first, "{first}");
+    assert_eq!(first, second);
+
+    // Drag the second one, and the first one follows:
+    harness.drag(pos2(600.0, 400.0), pos2(600.0, 350.0));
+    harness.run();
+    let [first, second] = offsets.get();
+    assert_eq!(first, second);
+
+    // Scroll the first one further than the second one can go:
+    harness
+        .ctx()
+        .memory_mut(|mem| mem.set_synced_scroll_offset(Id::new("synced"), vec2(0.0, 1e6)));
+    harness.run();
+    let [first, second] = offsets.get();
+    assert!(second < first, "{first} {second}");
+
+    // The second one being stuck at its end doesn't affect the first one:
+    harness.drag(pos2(200.0, 400.0), pos2(200.0, 450.0));
+    harness.run();
+    let [first_after, _] = offsets.get();
+    assert!(first_after < first && second < first_after, "{first_after}");
+}
+
+#[test]
+fn test_scroll_to_me_animated() {
+    use crate::test_harness::Harness;
+    use std::cell::Cell;
+
+    let scroll_to_target = Cell::new(false);
+    let scroll_to_top = Cell::new(false);
+    let top_id = Cell::new(Id::NULL);
+    let top_rect = Cell::new(Rect::NOTHING);
+    let target_rect = Cell::new(Rect::NOTHING);
+    let inner_rect = Cell::new(Rect::NOTHING);
+    let mut harness = Harness::new_ui(|ui| {
+        if scroll_to_top.replace(false) {
+            assert!(ui.scroll_to_id(top_id.get()));
+            assert!(!ui.scroll_to_id(Id::new("no such widget")));
+        }
+        ScrollArea::vertical().id_source("outer").show(ui, |ui| {
+            let top = ui.button("Top");
+            top_id.set(top.id);
+            top_rect.set(top.rect);
+            for i in 0..50 {
+                ui.label(format!("Outer {i}"));
+            }
+            let output = ScrollArea::vertical()
+                .id_source("inner")
+                .max_height(100.0)
+                .show(ui, |ui| {
+                    for i in 0..50 {
+                        let response = ui.label(format!("Inner {i}"));
+                        if i == 40 {
+                            target_rect.set(response.rect);
+                            if scroll_to_target.replace(false) {
+                                response.scroll_to_me_animated(Align::Center, 0.5);
+                            }
+                        }
+                    }
+                });
+            inner_rect.set(output.inner_rect);
+            for i in 0..50 {
+                ui.label(format!("Outer {i}"));
+            }
+        });
+    });
+    let screen_rect = harness.ctx().screen_rect();
+    assert!(!screen_rect.intersects(target_rect.get()));
+
+    // Both scroll areas scroll, smoothly:
+    scroll_to_target.set(true);
+    for _ in 0..5 {
+        harness.step();
+    }
+    let halfway = target_rect.get();
+    harness.run();
+    let target = target_rect.get();
+    assert_ne!(halfway, target);
+    assert!(inner_rect.get().contains_rect(target), "{target:?}");
+    assert!(screen_rect.contains_rect(target), "{target:?}");
+    assert!(!screen_rect.intersects(top_rect.get()));
+
+    scroll_to_top.set(true);
+    harness.run();
+    assert!(screen_rect.contains_rect(top_rect.get()));
+}
+
+#[cfg(feature = "persistence")]
+#[test]
+fn test_max_offset_is_unknown_after_restoring() {
+    let state = State {
+        offset: vec2(0.0, 1000.0),
+        max_offset: vec2(0.0, 10.0),
+        ..Default::default()
+    };
+    let restored: State = ron::from_str(&ron::to_string(&state).unwrap()).unwrap();
+    assert_eq!(restored.offset, state.offset);
+    assert_eq!(restored.max_offset, Vec2::INFINITY);
+}
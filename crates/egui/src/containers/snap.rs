@@ -0,0 +1,210 @@
+//! Snapping of [`Window`]s and [`Area`]s while they are being dragged.
+
+use crate::*;
+
+/// Where a dragged [`Window`] snaps to, see [`Window::snap`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct SnapOptions {
+    /// How close (in points) an edge of the window must be to something to snap to it.
+    ///
+    /// Default: `8.0`.
+    pub distance: f32,
+
+    /// Snap to the edges of the screen.
+    ///
+    /// Default: `true`.
+    pub screen_edges: bool,
+
+    /// Snap to the edges of other windows, both to line up with them and to sit next to them.
+    ///
+    /// Default: `true`.
+    pub windows: bool,
+
+    /// Snap to a grid with this spacing (in points).
+    ///
+    /// A spacing that isn't positive and finite means no grid.
+    ///
+    /// Default: `None`.
+    pub grid: Option<f32>,
+
+    /// Show a line where the window snapped to something.
+    ///
+    /// Default: `true`.
+    pub show_guides: bool,
+}
+
+impl Default for SnapOptions {
+    fn default() -> Self {
+        Self {
+            distance: 8.0,
+            screen_edges: true,
+            windows: true,
+            grid: None,
+            show_guides: true,
+        }
+    }
+}
+
+impl SnapOptions {
+    /// Snap to a grid with the given spacing, in addition to the screen edges and other windows.
+    #[inline]
+    pub fn with_grid(mut self, spacing: f32) -> Self {
+        self.grid = Some(spacing);
+        self
+    }
+}
+
+/// Move `rect` (the rectangle of the area with the given layer, where it was dragged to)
+/// to whatever it should snap to, and paint the guides.
+pub(crate) fn snap_rect(
+    ctx: &Context,
+    layer_id: LayerId,
+    rect: Rect,
+    options: &SnapOptions,
+) -> Rect {
+    let screen_rect = ctx.screen_rect();
+
+    let mut targets = Vec::new();
+    if options.screen_edges {
+        targets.push(screen_rect);
+    }
+    if options.windows {
+        ctx.memory(|mem| {
+            let areas = mem.areas();
+            for other in areas.visible_layer_ids() {
+                if other.order == Order::Middle && other != layer_id {
+                    if let Some(state) = areas.get(other.id) {
+                        targets.push(state.rect());
+                    }
+                }
+            }
+        });
+    }
+
+    let expanded = rect.expand(options.distance);
+    let x_targets = targets
+        .iter()
+        .filter(|target| *target == &screen_rect || target.y_range().intersects(expanded.y_range()))
+        .flat_map(|target| [target.left(), target.right()]);
+    let y_targets = targets
+        .iter()
+        .filter(|target| *target == &screen_rect || target.x_range().intersects(expanded.x_range()))
+        .flat_map(|target| [target.top(), target.bottom()]);
+
+    let x_grid = [rect.left(), rect.right()]
+        .map(|edge| grid_line(edge, options.grid))
+        .into_iter()
+        .flatten();
+    let y_grid = [rect.top(), rect.bottom()]
+        .map(|edge| grid_line(edge, options.grid))
+        .into_iter()
+        .flatten();
+
+    let x_snap = snap_offset(rect.x_range(), x_targets.chain(x_grid), options.distance);
+    let y_snap = snap_offset(rect.y_range(), y_targets.chain(y_grid), options.distance);
+
+    let offset = vec2(
+        x_snap.map_or(0.0, |(offset, _)| offset),
+        y_snap.map_or(0.0, |(offset, _)| offset),
+    );
+
+    if options.show_guides {
+        let painter =
+            ctx.layer_painter(LayerId::new(Order::Debug, layer_id.id.with("snap_guides")));
+        let stroke = ctx.style().visuals.selection.stroke;
+        if let Some((_, x)) = x_snap {
+            painter.vline(x, screen_rect.y_range(), stroke);
+        }
+        if let Some((_, y)) = y_snap {
+            painter.hline(screen_rect.x_range(), y, stroke);
+        }
+    }
+
+    rect.translate(offset)
+}
+
+/// The line of a grid with the given spacing that is closest to `edge`.
+///
+/// `None` if there is no grid, or if the spacing isn't positive and finite.
+fn grid_line(edge: f32, spacing: Option<f32>) -> Option<f32> {
+    let spacing = spacing.filter(|spacing| spacing.is_finite() && 0.0 < *spacing)?;
+    Some((edge / spacing).round() * spacing)
+}
+
+/// The smallest offset that moves one of the ends of `range` onto one of the `targets`,
+/// and that target, if it is within `distance`.
+fn snap_offset(
+    range: Rangef,
+    targets: impl Iterator<Item = f32>,
+    distance: f32,
+) -> Option<(f32, f32)> {
+    let mut best: Option<(f32, f32)> = None;
+    for target in targets {
+        for edge in [range.min, range.max] {
+            let offset = target - edge;
+            if offset.abs() <= distance && best.map_or(true, |(best, _)| offset.abs() < best.abs())
+            {
+                best = Some((offset, target));
+            }
+        }
+    }
+    best
+}
+
+#[test]
+fn test_snap_offset() {
+    let range = Rangef::new(12.0, 112.0);
+    assert_eq!(snap_offset(range, [0.0, 500.0].into_iter(), 8.0), None);
+    assert_eq!(
+        snap_offset(range, [0.0, 500.0].into_iter(), 16.0),
+        Some((-12.0, 0.0))
+    );
+    // The closest target wins, for either end:
+    assert_eq!(
+        snap_offset(range, [0.0, 115.0].into_iter(), 16.0),
+        Some((3.0, 115.0))
+    );
+}
+
+#[test]
+fn test_grid_line() {
+    assert_eq!(grid_line(23.0, Some(10.0)), Some(20.0));
+    assert_eq!(grid_line(-27.0, Some(10.0)), Some(-30.0));
+    assert_eq!(grid_line(23.0, None), None);
+    for spacing in [0.0, -10.0, f32::NAN, f32::INFINITY] {
+        assert_eq!(grid_line(23.0, Some(spacing)), None, "spacing: {spacing}");
+    }
+}
+
+#[test]
+fn test_snap_window_to_screen_edge() {
+    use crate::test_harness::Harness;
+
+    let mut harness = Harness::new(|ctx| {
+        Window::new("Snappy")
+            .default_pos(pos2(100.0, 100.0))
+            .snap(SnapOptions::default())
+            .show(ctx, |ui| {
+                ui.label("Drag me");
+            });
+    });
+    let window_rect = |harness: &Harness<'_>| {
+        harness
+            .ctx()
+            .memory(|mem| mem.area_rect(Id::new("Snappy")))
+            .unwrap()
+    };
+
+    let before = window_rect(&harness);
+    let from = before.center();
+
+    // Drag the window close to the top left corner of the screen:
+    let to = from - (before.min - pos2(5.0, 6.0));
+    harness.drag(from, to);
+    assert_eq!(window_rect(&harness).min, Pos2::ZERO);
+
+    // Far from the edges nothing snaps:
+    harness.drag(to, to + vec2(100.0, 100.0));
+    assert_eq!(window_rect(&harness).min, pos2(100.0, 100.0));
+}
@@ -0,0 +1,196 @@
+//! A horizontal bar of tools that moves whatever doesn't fit into an overflow menu, see [`Toolbar`].
+
+use crate::*;
+
+/// A horizontal row of items, like the buttons of a ribbon or toolbar.
+///
+/// The items that do not fit into the available width are moved into an overflow menu
+/// at the end of the bar, which is opened with a "»" button.
+/// Which items fit is recalculated each frame, so the bar adapts when the available width changes.
+///
+/// Each item is shown by a closure that can be called twice per frame:
+/// once to measure it (see [`Ui::measure`]) and once to actually show it,
+/// either in the bar or in the overflow menu.
+/// Use [`Ui::close_menu`] to close the overflow menu when an item in it is clicked.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// # let mut bold = false;
+/// egui::Toolbar::new().show(ui, |toolbar| {
+///     toolbar.add(|ui| {
+///         if ui.button("Cut").clicked() {
+///             ui.close_menu();
+///         }
+///     });
+///     toolbar.add(|ui| {
+///         ui.toggle_value(&mut bold, "Bold");
+///     });
+///     toolbar.separator();
+///     toolbar.add(|ui| {
+///         ui.hyperlink_to("Help", "https://www.egui.rs/");
+///     });
+/// });
+/// # });
+/// ```
+#[must_use = "You should call .show()"]
+pub struct Toolbar {
+    overflow_text: WidgetText,
+}
+
+impl Default for Toolbar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Toolbar {
+    pub fn new() -> Self {
+        Self {
+            overflow_text: "»".into(),
+        }
+    }
+
+    /// The text of the button that opens the overflow menu. Default: "»".
+    #[inline]
+    pub fn overflow_text(mut self, overflow_text: impl Into<WidgetText>) -> Self {
+        self.overflow_text = overflow_text.into();
+        self
+    }
+
+    /// Show the items added in `add_items`.
+    ///
+    /// The inner value is the number of items that were moved into the overflow menu.
+    pub fn show<'a>(
+        self,
+        ui: &mut Ui,
+        add_items: impl FnOnce(&mut ToolbarItems<'a>),
+    ) -> InnerResponse<usize> {
+        let Self { overflow_text } = self;
+
+        let mut items = ToolbarItems { items: vec![] };
+        add_items(&mut items);
+        let mut items = items.items;
+
+        ui.horizontal(|ui| {
+            let item_widths: Vec<f32> = items
+                .iter_mut()
+                .map(|item| ui.measure(|ui| item(ui)).x)
+                .collect();
+
+            let spacing = ui.spacing().item_spacing.x;
+            let width_of = |widths: &[f32]| {
+                widths.iter().sum::<f32>() + spacing * widths.len().saturating_sub(1) as f32
+            };
+
+            let available_width = ui.available_width();
+            let num_shown = if width_of(&item_widths) <= available_width {
+                items.len()
+            } else {
+                let overflow_width = ui
+                    .measure(|ui| {
+                        ui.add(Button::new(overflow_text.clone()));
+                    })
+                    .x;
+                let width_for_items = available_width - overflow_width - spacing;
+                (0..items.len())
+                    .rev()
+                    .find(|&n| width_of(&item_widths[..n]) <= width_for_items)
+                    .unwrap_or(0)
+            };
+
+            let mut overflowing = items.split_off(num_shown);
+            for item in &mut items {
+                item(ui);
+            }
+            if !overflowing.is_empty() {
+                ui.menu_button(overflow_text, |ui| {
+                    for item in &mut overflowing {
+                        item(ui);
+                    }
+                });
+            }
+            overflowing.len()
+        })
+    }
+}
+
+/// The items of a [`Toolbar`], in the order they are shown.
+pub struct ToolbarItems<'a> {
+    items: Vec<Box<dyn FnMut(&mut Ui) + 'a>>,
+}
+
+impl<'a> ToolbarItems<'a> {
+    /// Add an item, e.g. a button.
+    ///
+    /// The closure may be called twice per frame, see [`Toolbar`].
+    pub fn add(&mut self, add_contents: impl FnMut(&mut Ui) + 'a) {
+        self.items.push(Box::new(add_contents));
+    }
+
+    /// Add a [`Separator`], which is vertical in the bar and horizontal in the overflow menu.
+    pub fn separator(&mut self) {
+        self.add(|ui| {
+            ui.separator();
+        });
+    }
+}
+
+#[test]
+fn test_toolbar_overflow() {
+    use crate::test_harness::Harness;
+    use std::cell::Cell;
+
+    let num_overflowing = Cell::new(0);
+    let clicked = Cell::new(None);
+
+    let mut harness = Harness::new_ui(|ui| {
+        let response = Toolbar::new().show(ui, |toolbar| {
+            for i in 0..10 {
+                let clicked = &clicked;
+                toolbar.add(move |ui| {
+                    if ui.button(format!("Tool {i}")).clicked() {
+                        clicked.set(Some(i));
+                        ui.close_menu();
+                    }
+                });
+            }
+        });
+        num_overflowing.set(response.inner);
+    });
+
+    assert_eq!(num_overflowing.get(), 0);
+    assert!(harness.query_by_label("»").is_none());
+
+    // A narrow screen moves the last tools into the overflow menu:
+    harness.set_size(vec2(250.0, 400.0));
+    harness.run();
+    let num_shown = 10 - num_overflowing.get();
+    assert!(0 < num_shown && num_shown < 10, "{num_shown}");
+    let tools_right = harness
+        .query_by_label(&format!("Tool {}", num_shown - 1))
+        .unwrap()
+        .rect
+        .rect
+        .right();
+    let overflow_rect = harness.query_by_label("»").unwrap().rect.rect;
+    assert!(tools_right < overflow_rect.left());
+    assert!(overflow_rect.right() <= 250.0);
+    assert!(harness
+        .query_by_label(&format!("Tool {num_shown}"))
+        .is_none());
+
+    // The overflowing tools can be clicked in the menu:
+    harness.get_by_label("»").click();
+    harness.run();
+    harness.get_by_label("Tool 9").click();
+    harness.step();
+    assert_eq!(clicked.get(), Some(9));
+    harness.run();
+    assert!(harness.query_by_label("Tool 9").is_none());
+
+    // Everything fits again on a wide screen:
+    harness.set_size(vec2(1000.0, 400.0));
+    harness.run();
+    assert_eq!(num_overflowing.get(), 0);
+    assert!(harness.query_by_label("Tool 9").is_some());
+}
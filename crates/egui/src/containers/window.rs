@@ -33,6 +33,7 @@ use super::*;
 pub struct Window<'open> {
     title: WidgetText,
     open: Option<&'open mut bool>,
+    name: Option<String>,
     area: Area,
     frame: Option<Frame>,
     resize: Resize,
@@ -54,6 +55,7 @@ impl<'open> Window<'open> {
         Self {
             title,
             open: None,
+            name: None,
             area,
             frame: None,
             resize: Resize::default()
@@ -85,6 +87,35 @@ impl<'open> Window<'open> {
         self
     }
 
+    /// Let the window be opened and closed by name, from anywhere,
+    /// with [`Context::open_window`], [`Context::close_window`] and [`Context::toggle_window`].
+    ///
+    /// The window gets a close button, and starts out open unless
+    /// [`Context::register_window`] said otherwise.
+    /// The open state is stored in [`Memory`], so it is persisted.
+    ///
+    /// If you also use [`Self::open`], the `bool` is updated to match.
+    ///
+    /// ```
+    /// # egui::__run_test_ctx(|ctx| {
+    /// egui::Window::new("Inspector")
+    ///     .registered_as("inspector")
+    ///     .show(ctx, |ui| {
+    ///         ui.label("…");
+    ///     });
+    ///
+    /// // Somewhere else, e.g. in a menu:
+    /// if ctx.is_window_open("inspector") {
+    ///     ctx.close_window("inspector");
+    /// }
+    /// # });
+    /// ```
+    #[inline]
+    pub fn registered_as(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
     /// If `false` the window will be grayed out and non-interactive.
     #[inline]
     pub fn enabled(mut self, enabled: bool) -> Self {
@@ -216,6 +247,33 @@ impl<'open> Window<'open> {
         self
     }
 
+    /// Snap the window to the screen edges, other windows, and/or a grid while it is dragged.
+    ///
+    /// ```
+    /// # egui::__run_test_ctx(|ctx| {
+    /// egui::Window::new("Snappy")
+    ///     .snap(egui::SnapOptions::default().with_grid(16.0))
+    ///     .show(ctx, |ui| {
+    ///         ui.label("Drag me near an edge");
+    ///     });
+    /// # });
+    /// ```
+    #[inline]
+    pub fn snap(mut self, snap: SnapOptions) -> Self {
+        self.area = self.area.snap(snap);
+        self
+    }
+
+    /// Let the window glide on when it is thrown,
+    /// i.e. released while being dragged quickly, e.g. on a touch screen.
+    ///
+    /// See [`Area::kinetic`].
+    #[inline]
+    pub fn kinetic(mut self, kinetic: bool) -> Self {
+        self.area = self.area.kinetic(kinetic);
+        self
+    }
+
     /// Where the "root" of the window is.
     ///
     /// For instance, if you set this to [`Align2::RIGHT_TOP`]
@@ -366,11 +424,26 @@ impl<'open> Window<'open> {
     /// Returns `Some(InnerResponse { inner: None })` if the window is collapsed.
     #[inline]
     pub fn show<R>(
-        self,
+        mut self,
         ctx: &Context,
         add_contents: impl FnOnce(&mut Ui) -> R,
     ) -> Option<InnerResponse<Option<R>>> {
-        self.show_dyn(ctx, Box::new(add_contents))
+        let Some(name) = self.name.clone() else {
+            return self.show_dyn(ctx, Box::new(add_contents));
+        };
+
+        // The registry decides if a registered window is open:
+        let user_open = self.open.take();
+        let mut window: Window<'_> = self;
+        let mut open = ctx.register_window(&name, user_open.as_deref().map_or(true, |open| *open));
+        window.open = Some(&mut open);
+        let response = window.show_dyn(ctx, Box::new(add_contents));
+
+        ctx.set_window_open(&name, open);
+        if let Some(user_open) = user_open {
+            *user_open = open;
+        }
+        response
     }
 
     fn show_dyn<'c, R>(
@@ -381,6 +454,7 @@ impl<'open> Window<'open> {
         let Window {
             title,
             open,
+            name: _, // already handled by `show`
             area,
             frame,
             resize,
@@ -428,6 +502,35 @@ impl<'open> Window<'open> {
             (0.0, 0.0)
         };
 
+        // The window can't be narrower than its title bar,
+        // which we need to know before we resize it, to keep the opposite edge in place.
+        let title_bar_min_width = if with_title_bar {
+            let style = ctx.style();
+            let height = ctx
+                .fonts(|f| title.font_height(f, &style))
+                .max(style.spacing.interact_size.y);
+            let title_galley = title.clone().into_galley_impl(
+                ctx,
+                &style,
+                false,
+                f32::INFINITY,
+                TextStyle::Heading.into(),
+                Align::LEFT,
+            );
+            title_bar_min_width(
+                &style.spacing,
+                height,
+                title_galley.size().x,
+                collapsible || open.is_some(),
+            )
+        } else {
+            0.0
+        };
+        let min_size = vec2(
+            resize.min_size.x.max(title_bar_min_width),
+            resize.min_size.y,
+        );
+
         // First check for resize to avoid frame delay:
         let last_frame_outer_rect = area.state().rect();
         let resize_interaction =
@@ -441,6 +544,7 @@ impl<'open> Window<'open> {
             resize_interaction,
             ctx,
             margins,
+            (min_size + margins, resize.max_size + margins),
             area_layer_id,
             &mut area,
             resize_id,
@@ -553,6 +657,13 @@ impl<'open> Window<'open> {
     }
 }
 
+/// Which windows are open, by the names given to [`Window::registered_as`] and [`Context::register_window`].
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub(crate) struct WindowRegistry {
+    pub(crate) open: std::collections::BTreeMap<String, bool>,
+}
+
 fn paint_resize_corner(
     ui: &Ui,
     possible: &PossibleInteractions,
@@ -641,19 +752,38 @@ impl std::ops::BitOrAssign for SideResponse {
 
 impl ResizeInteraction {
     pub fn set_cursor(&self, ctx: &Context) {
-        let left = self.left.any();
-        let right = self.right.any();
-        let top = self.top.any();
-        let bottom = self.bottom.any();
-
-        if (left && top) || (right && bottom) {
-            ctx.set_cursor_icon(CursorIcon::ResizeNwSe);
-        } else if (right && top) || (left && bottom) {
-            ctx.set_cursor_icon(CursorIcon::ResizeNeSw);
-        } else if left || right {
-            ctx.set_cursor_icon(CursorIcon::ResizeHorizontal);
-        } else if bottom || top {
-            ctx.set_cursor_icon(CursorIcon::ResizeVertical);
+        if let Some(cursor_icon) = self.cursor_icon() {
+            ctx.set_cursor_icon(cursor_icon);
+        }
+    }
+
+    /// The cursor showing which edge or corner is hovered or dragged.
+    fn cursor_icon(&self) -> Option<CursorIcon> {
+        let x = if self.left.any() {
+            -1
+        } else if self.right.any() {
+            1
+        } else {
+            0
+        };
+        let y = if self.top.any() {
+            -1
+        } else if self.bottom.any() {
+            1
+        } else {
+            0
+        };
+
+        match (x, y) {
+            (-1, -1) => Some(CursorIcon::ResizeNorthWest),
+            (1, -1) => Some(CursorIcon::ResizeNorthEast),
+            (-1, 1) => Some(CursorIcon::ResizeSouthWest),
+            (1, 1) => Some(CursorIcon::ResizeSouthEast),
+            (-1, _) => Some(CursorIcon::ResizeWest),
+            (1, _) => Some(CursorIcon::ResizeEast),
+            (_, -1) => Some(CursorIcon::ResizeNorth),
+            (_, 1) => Some(CursorIcon::ResizeSouth),
+            _ => None,
         }
     }
 
@@ -670,11 +800,13 @@ fn resize_response(
     resize_interaction: ResizeInteraction,
     ctx: &Context,
     margins: Vec2,
+    (min_size, max_size): (Vec2, Vec2),
     area_layer_id: LayerId,
     area: &mut area::Prepared,
     resize_id: Id,
 ) {
-    let Some(new_rect) = move_and_resize_window(ctx, &resize_interaction) else {
+    let Some(new_rect) = move_and_resize_window(ctx, &resize_interaction, min_size, max_size)
+    else {
         return;
     };
     let mut new_rect = ctx.round_rect_to_pixels(new_rect);
@@ -696,7 +828,15 @@ fn resize_response(
     ctx.memory_mut(|mem| mem.areas_mut().move_to_top(area_layer_id));
 }
 
-fn move_and_resize_window(ctx: &Context, interaction: &ResizeInteraction) -> Option<Rect> {
+/// The new outer rect of the window, keeping its size within `min_size..=max_size`.
+///
+/// The edges that are not being dragged stay where they are.
+fn move_and_resize_window(
+    ctx: &Context,
+    interaction: &ResizeInteraction,
+    min_size: Vec2,
+    max_size: Vec2,
+) -> Option<Rect> {
     if !interaction.any_dragged() {
         return None;
     }
@@ -705,15 +845,27 @@ fn move_and_resize_window(ctx: &Context, interaction: &ResizeInteraction) -> Opt
     let mut rect = interaction.start_rect; // prevent drift
 
     if interaction.left.drag {
-        rect.min.x = ctx.round_to_pixel(pointer_pos.x);
+        rect.min.x = ctx
+            .round_to_pixel(pointer_pos.x)
+            .at_most(rect.max.x - min_size.x)
+            .at_least(rect.max.x - max_size.x);
     } else if interaction.right.drag {
-        rect.max.x = ctx.round_to_pixel(pointer_pos.x);
+        rect.max.x = ctx
+            .round_to_pixel(pointer_pos.x)
+            .at_least(rect.min.x + min_size.x)
+            .at_most(rect.min.x + max_size.x);
     }
 
     if interaction.top.drag {
-        rect.min.y = ctx.round_to_pixel(pointer_pos.y);
+        rect.min.y = ctx
+            .round_to_pixel(pointer_pos.y)
+            .at_most(rect.max.y - min_size.y)
+            .at_least(rect.max.y - max_size.y);
     } else if interaction.bottom.drag {
-        rect.max.y = ctx.round_to_pixel(pointer_pos.y);
+        rect.max.y = ctx
+            .round_to_pixel(pointer_pos.y)
+            .at_least(rect.min.y + min_size.y)
+            .at_most(rect.min.y + max_size.y);
     }
 
     Some(rect)
@@ -949,7 +1101,6 @@ fn show_title_bar(
             .max(ui.spacing().interact_size.y);
         ui.set_min_height(height);
 
-        let item_spacing = ui.spacing().item_spacing;
         let button_size = Vec2::splat(ui.spacing().icon_width);
 
         let pad = (height - button_size.y) / 2.0; // calculated so that the icon is on the diagonal (if window padding is symmetrical)
@@ -961,12 +1112,12 @@ fn show_title_bar(
 
         let title_galley = title.into_galley(ui, Some(false), f32::INFINITY, TextStyle::Heading);
 
-        let minimum_width = if collapsible || show_close_button {
-            // If at least one button is shown we make room for both buttons (since title is centered):
-            2.0 * (pad + button_size.x + item_spacing.x) + title_galley.size().x
-        } else {
-            pad + title_galley.size().x + pad
-        };
+        let minimum_width = title_bar_min_width(
+            ui.spacing(),
+            height,
+            title_galley.size().x,
+            collapsible || show_close_button,
+        );
         let min_rect = Rect::from_min_size(ui.min_rect().min, vec2(minimum_width, height));
         let id = ui.advance_cursor_after_rect(min_rect);
 
@@ -984,6 +1135,23 @@ fn show_title_bar(
     TitleBar { rect, ..title_bar }
 }
 
+/// The width of the title bar, given the height of the title bar and the width of the title text.
+fn title_bar_min_width(
+    spacing: &style::Spacing,
+    height: f32,
+    title_width: f32,
+    show_buttons: bool,
+) -> f32 {
+    let button_width = spacing.icon_width;
+    let pad = (height - button_width) / 2.0;
+    if show_buttons {
+        // If at least one button is shown we make room for both buttons (since title is centered):
+        2.0 * (pad + button_width + spacing.item_spacing.x) + title_width
+    } else {
+        pad + title_width + pad
+    }
+}
+
 impl TitleBar {
     /// Finishes painting of the title bar when the window content size already known.
     ///
@@ -1084,6 +1252,7 @@ impl TitleBar {
 fn close_button(ui: &mut Ui, rect: Rect) -> Response {
     let close_id = ui.auto_id_with("window_close_button");
     let response = ui.interact(rect, close_id, Sense::click());
+    response.widget_info(|| WidgetInfo::labeled(WidgetType::Button, "Close"));
     ui.expand_to_include_rect(response.rect);
 
     let visuals = ui.style().interact(&response);
@@ -1095,3 +1264,76 @@ fn close_button(ui: &mut Ui, rect: Rect) -> Response {
         .line_segment([rect.right_top(), rect.left_bottom()], stroke);
     response
 }
+
+#[test]
+fn test_resize_from_left_edge() {
+    use crate::test_harness::Harness;
+
+    let window_rect = std::cell::Cell::new(Rect::NOTHING);
+    let mut harness = Harness::new(|ctx| {
+        let response = Window::new("Window")
+            .default_pos(pos2(200.0, 100.0))
+            .min_width(150.0)
+            .show(ctx, |ui| {
+                ui.allocate_space(ui.available_size()); // fill the window
+            });
+        window_rect.set(response.unwrap().response.rect);
+    });
+
+    let start = window_rect.get();
+    let left_edge = pos2(start.left(), start.center().y);
+    harness.hover(left_edge);
+    assert_eq!(
+        harness.output().platform_output.cursor_icon,
+        CursorIcon::ResizeWest
+    );
+
+    harness.drag(left_edge, left_edge - vec2(100.0, 0.0));
+    let rect = window_rect.get();
+    assert_eq!(rect.left(), start.left() - 100.0);
+    assert_eq!(rect.right(), start.right());
+
+    // Dragging far past the minimum size keeps the right edge in place:
+    let left_edge = pos2(rect.left(), rect.center().y);
+    harness.drag(left_edge, left_edge + vec2(500.0, 0.0));
+    let rect = window_rect.get();
+    assert_eq!(rect.right(), start.right());
+    assert!(150.0 <= rect.width() && rect.width() < 200.0);
+}
+
+#[test]
+fn test_registered_window() {
+    use crate::test_harness::Harness;
+
+    let mut harness = Harness::new(|ctx| {
+        ctx.register_window("settings", false);
+        Window::new("Inspector")
+            .registered_as("inspector")
+            .show(ctx, |ui| ui.label("Inspecting"));
+        Window::new("Settings")
+            .registered_as("settings")
+            .show(ctx, |ui| ui.label("Settings go here"));
+    });
+    let is_shown = |harness: &Harness<'_>, label| harness.query_by_label(label).is_some();
+
+    assert!(is_shown(&harness, "Inspecting"));
+    assert!(!is_shown(&harness, "Settings go here"));
+    assert_eq!(
+        harness.ctx().registered_windows(),
+        vec![
+            ("inspector".to_owned(), true),
+            ("settings".to_owned(), false)
+        ]
+    );
+
+    harness.ctx().toggle_window("settings");
+    harness.ctx().close_window("inspector");
+    harness.run();
+    assert!(!is_shown(&harness, "Inspecting"));
+    assert!(is_shown(&harness, "Settings go here"));
+
+    // The close button closes it in the registry:
+    harness.get_by_label("Close").click();
+    assert!(!harness.ctx().is_window_open("settings"));
+    assert!(!is_shown(&harness, "Settings go here"));
+}
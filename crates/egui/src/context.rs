@@ -1,6 +1,13 @@
 #![warn(missing_docs)] // Let's keep `Context` well-documented.
 
-use std::{borrow::Cow, cell::RefCell, panic::Location, sync::Arc, time::Duration};
+use std::{
+    borrow::Cow,
+    cell::RefCell,
+    collections::{BTreeMap, BTreeSet},
+    panic::Location,
+    sync::Arc,
+    time::Duration,
+};
 
 use ahash::HashMap;
 use epaint::{
@@ -8,21 +15,22 @@ use epaint::{
 };
 
 use crate::{
-    animation_manager::AnimationManager,
+    animation_manager::{AnimationManager, AnimationOptions},
     data::output::PlatformOutput,
     frame_state::FrameState,
     input_state::*,
     layers::GraphicLayers,
     load::{Bytes, Loaders, SizedTexture},
-    memory::Options,
+    memory::{InteractionState, Options},
     os::OperatingSystem,
     output::FullOutput,
     util::IdTypeMap,
     viewport::ViewportClass,
+    window::WindowRegistry,
     TextureHandle, ViewportCommand, *,
 };
 
-use self::{hit_test::WidgetHits, interaction::InteractionSnapshot};
+use self::interaction::InteractionSnapshot;
 
 /// Information given to the backend about when it is time to repaint the ui.
 ///
@@ -40,6 +48,21 @@ pub struct RequestRepaintInfo {
     /// This can be compared to [`Context::frame_nr`] to see if we've already
     /// triggered the painting of the next frame.
     pub current_frame_nr: u64,
+
+    /// The part of the viewport that needs repainting, in points, or `None` for all of it.
+    ///
+    /// This is set when only small things change, like a blinking text cursor,
+    /// see [`Context::request_repaint_rect_after`].
+    /// Repaint requests during the same frame grow this rect,
+    /// and the callback is called again when they do.
+    ///
+    /// egui still runs the whole ui, so integrations can ignore this and repaint everything.
+    pub rect: Option<Rect>,
+
+    /// The policy of the viewport, set with [`ViewportCommand::RepaintPolicy`].
+    ///
+    /// egui has already applied it to [`Self::delay`].
+    pub policy: RepaintPolicy,
 }
 
 // ----------------------------------------------------------------------------
@@ -73,17 +96,45 @@ impl Default for WrappedTextureManager {
 /// Generic event callback.
 pub type ContextCallback = Arc<dyn Fn(&Context) + Send + Sync>;
 
+/// Called with the shapes of a frame just before they are tessellated.
+///
+/// The arguments are the shapes and the `pixels_per_point` passed to [`Context::tessellate`].
+/// The shapes can be modified, e.g. to add an overlay on top of everything else.
+///
+/// See [`Context::on_before_tessellate`].
+pub type TessellateCallback = Arc<dyn Fn(&Context, &mut Vec<ClippedShape>, f32) + Send + Sync>;
+
+/// Modifies the final shapes of a frame, e.g. to remap all colors.
+///
+/// See [`Context::add_shape_mapper`].
+pub type ShapeMapper = Arc<dyn Fn(&mut Vec<ClippedShape>) + Send + Sync>;
+
 #[derive(Clone)]
 struct NamedContextCallback {
     debug_name: &'static str,
     callback: ContextCallback,
 }
 
+#[derive(Clone)]
+struct NamedTessellateCallback {
+    debug_name: &'static str,
+    callback: TessellateCallback,
+}
+
+#[derive(Clone)]
+struct NamedShapeMapper {
+    debug_name: &'static str,
+    mapper: ShapeMapper,
+}
+
 /// Callbacks that users can register
 #[derive(Clone, Default)]
 struct Plugins {
     pub on_begin_frame: Vec<NamedContextCallback>,
+    pub on_after_input: Vec<NamedContextCallback>,
     pub on_end_frame: Vec<NamedContextCallback>,
+    pub on_before_tessellate: Vec<NamedTessellateCallback>,
+    pub shape_mappers: Vec<NamedShapeMapper>,
 }
 
 impl Plugins {
@@ -103,9 +154,42 @@ impl Plugins {
         Self::call(ctx, "on_begin_frame", &self.on_begin_frame);
     }
 
+    fn on_after_input(&self, ctx: &Context) {
+        Self::call(ctx, "on_after_input", &self.on_after_input);
+    }
+
     fn on_end_frame(&self, ctx: &Context) {
         Self::call(ctx, "on_end_frame", &self.on_end_frame);
     }
+
+    fn on_before_tessellate(
+        &self,
+        ctx: &Context,
+        shapes: &mut Vec<ClippedShape>,
+        pixels_per_point: f32,
+    ) {
+        crate::profile_scope!("plugins", "on_before_tessellate");
+        for NamedTessellateCallback {
+            debug_name: _name,
+            callback,
+        } in &self.on_before_tessellate
+        {
+            crate::profile_scope!("plugin", _name);
+            (callback)(ctx, shapes, pixels_per_point);
+        }
+    }
+
+    fn map_shapes(&self, shapes: &mut Vec<ClippedShape>) {
+        crate::profile_scope!("plugins", "shape_mappers");
+        for NamedShapeMapper {
+            debug_name: _name,
+            mapper,
+        } in &self.shape_mappers
+        {
+            crate::profile_scope!("shape_mapper", _name);
+            (mapper)(shapes);
+        }
+    }
 }
 
 // ----------------------------------------------------------------------------
@@ -121,13 +205,16 @@ impl ContextImpl {
             &mut viewport.repaint.causes,
         );
         viewport.repaint.causes.clear();
+        viewport.repaint.animating = false;
 
         viewport.repaint.prev_frame_paint_delay = viewport.repaint.repaint_delay;
 
         if viewport.repaint.outstanding == 0 {
             // We are repainting now, so we can wait a while for the next repaint.
             viewport.repaint.repaint_delay = Duration::MAX;
+            viewport.repaint.rect = Some(Rect::NOTHING);
         } else {
+            // Repaint the same region again:
             viewport.repaint.repaint_delay = Duration::ZERO;
             viewport.repaint.outstanding -= 1;
             if let Some(callback) = &self.request_repaint_callback {
@@ -135,23 +222,57 @@ impl ContextImpl {
                     viewport_id,
                     delay: Duration::ZERO,
                     current_frame_nr: viewport.repaint.frame_nr,
+                    rect: viewport.repaint.rect,
+                    policy: viewport.repaint.policy,
                 });
             }
         }
     }
 
     fn request_repaint(&mut self, viewport_id: ViewportId, cause: RepaintCause) {
-        self.request_repaint_after(Duration::ZERO, viewport_id, cause);
+        self.request_repaint_after(Duration::ZERO, viewport_id, cause, None);
+    }
+
+    /// Animations always repaint right away, even when [`Context::set_max_idle_fps`] is set.
+    fn request_animation_repaint(
+        &mut self,
+        viewport_id: ViewportId,
+        cause: RepaintCause,
+        rect: Option<Rect>,
+    ) {
+        self.viewports
+            .entry(viewport_id)
+            .or_default()
+            .repaint
+            .animating = true;
+        self.request_repaint_after(Duration::ZERO, viewport_id, cause, rect);
     }
 
+    /// `rect` is the part of the viewport that needs repainting, or `None` for all of it.
     fn request_repaint_after(
         &mut self,
         delay: Duration,
         viewport_id: ViewportId,
         cause: RepaintCause,
+        rect: Option<Rect>,
     ) {
         let viewport = self.viewports.entry(viewport_id).or_default();
 
+        let mut delay = delay;
+        match viewport.repaint.policy {
+            RepaintPolicy::Reactive => {
+                if delay == Duration::ZERO && viewport.repaint.idle && !viewport.repaint.animating {
+                    if let Some(max_idle_fps) = self.max_idle_fps {
+                        delay = Duration::from_secs_f32(1.0 / max_idle_fps);
+                    }
+                }
+            }
+            RepaintPolicy::Continuous => {}
+            RepaintPolicy::Throttled(fps) => {
+                delay = delay.max(Duration::from_secs_f32(1.0 / fps.max(1) as f32));
+            }
+        }
+
         if delay == Duration::ZERO {
             // Each request results in two repaints, just to give some things time to settle.
             // This solves some corner-cases of missing repaints on frame-delayed responses.
@@ -163,19 +284,31 @@ impl ContextImpl {
             // Hovering a tooltip is a good example of a case where we want to repaint after a delay.
         }
 
+        let num_counted = viewport.repaint.cause_counts.len();
+        if let Some(count) = viewport.repaint.cause_counts.get_mut(&cause) {
+            *count += 1;
+        } else if num_counted < MAX_COUNTED_REPAINT_CAUSES {
+            viewport.repaint.cause_counts.insert(cause.clone(), 1);
+        }
         viewport.repaint.causes.push(cause);
 
+        let prev_rect = viewport.repaint.rect;
+        viewport.repaint.rect = prev_rect.zip(rect).map(|(a, b)| a.union(b));
+
         // We save some CPU time by only calling the callback if we need to.
         // If the new delay is greater or equal to the previous lowest,
+        // and the region to repaint didn't grow,
         // it means we have already called the callback, and don't need to do it again.
-        if delay < viewport.repaint.repaint_delay {
-            viewport.repaint.repaint_delay = delay;
+        if delay < viewport.repaint.repaint_delay || viewport.repaint.rect != prev_rect {
+            viewport.repaint.repaint_delay = viewport.repaint.repaint_delay.min(delay);
 
             if let Some(callback) = &self.request_repaint_callback {
                 (callback)(RequestRepaintInfo {
                     viewport_id,
-                    delay,
+                    delay: viewport.repaint.repaint_delay,
                     current_frame_nr: viewport.repaint.frame_nr,
+                    rect: viewport.repaint.rect,
+                    policy: viewport.repaint.policy,
                 });
             }
         }
@@ -223,12 +356,32 @@ struct ViewportState {
     /// Has this viewport been updated this frame?
     used: bool,
 
+    /// [`Context::content_size`] at the end of the last frame.
+    content_size: Option<Vec2>,
+
+    /// The last size we resized the viewport to, because of [`ViewportBuilder::inner_size_to_content`].
+    fitted_size: Option<Vec2>,
+
+    /// [`Context::missing_glyphs`] at the end of the last frame.
+    missing_glyphs: BTreeSet<char>,
+
     /// Written to during the frame.
     widgets_this_frame: WidgetRects,
 
     /// Read
     widgets_prev_frame: WidgetRects,
 
+    /// The layers of [`Self::widgets_prev_frame`], sorted back-to-front.
+    ///
+    /// Updated at the start of each frame.
+    widget_layer_order: Vec<LayerId>,
+
+    /// The AccessKit nodes of the last frame, if AccessKit is enabled.
+    ///
+    /// Used by [`Context::accesskit_query`].
+    #[cfg(feature = "accesskit")]
+    accesskit_nodes_prev_frame: IdMap<accesskit::Node>,
+
     /// State related to repaint scheduling.
     repaint: ViewportRepaintInfo,
 
@@ -243,6 +396,9 @@ struct ViewportState {
     /// Based on the widgets from last frame, and input in this frame.
     interact_widgets: InteractionSnapshot,
 
+    /// Like [`Self::interact_widgets`], but for each of [`InputState::extra_pointers`].
+    extra_pointer_interactions: BTreeMap<PointerId, (InteractionState, InteractionSnapshot)>,
+
     // ----------------------
     // The output of a frame:
     //
@@ -250,16 +406,39 @@ struct ViewportState {
     // Most of the things in `PlatformOutput` are not actually viewport dependent.
     output: PlatformOutput,
     commands: Vec<ViewportCommand>,
+
+    /// Timings collected when [`Context::set_profiling`] is on.
+    profiler: crate::frame_profiler::FrameProfiler,
+
+    /// How long widgets have been hovered, for tooltips, sub-menus and prefetching.
+    hover_timer: crate::popup::HoverTimer,
+}
+
+/// A widget found by [`Context::accesskit_query`].
+#[cfg(feature = "accesskit")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct AccessKitMatch {
+    /// The id of the widget.
+    pub id: Id,
+
+    /// Where the widget is, in points.
+    pub rect: Rect,
+
+    /// Everything AccessKit knows about the widget.
+    pub node: accesskit::Node,
 }
 
 /// What called [`Context::request_repaint`]?
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct RepaintCause {
     /// What file had the call that requested the repaint?
     pub file: String,
 
     /// What line number of the the call that requested the repaint?
     pub line: u32,
+
+    /// See [`Self::widget`].
+    widget: Option<Id>,
 }
 
 impl RepaintCause {
@@ -271,16 +450,56 @@ impl RepaintCause {
         Self {
             file: caller.file().to_owned(),
             line: caller.line(),
+            widget: None,
+        }
+    }
+
+    /// Capture the call site, and which widget asked.
+    #[track_caller]
+    pub fn from_widget(id: Id) -> Self {
+        Self {
+            widget: Some(id),
+            ..Self::new()
         }
     }
+
+    /// The widget (or animation) that requested the repaint, if known.
+    ///
+    /// See [`Context::request_repaint_from`].
+    #[inline]
+    pub fn widget(&self) -> Option<Id> {
+        self.widget
+    }
 }
 
 impl std::fmt::Display for RepaintCause {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}:{}", self.file, self.line)
+        write!(f, "{}:{}", self.file, self.line)?;
+        if let Some(widget) = self.widget {
+            write!(f, " ({})", widget.short_debug_format())?;
+        }
+        Ok(())
     }
 }
 
+/// We stop counting new [`RepaintCause`]s after this many different ones,
+/// so an app animating lots of different ids doesn't grow the counts forever.
+const MAX_COUNTED_REPAINT_CAUSES: usize = 1000;
+
+/// Where [`InputState::time`] comes from, see [`Context::set_time_source`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum TimeSource {
+    /// Use [`RawInput::time`], i.e. the wall clock of the integration.
+    #[default]
+    WallClock,
+
+    /// Advance the time by exactly this many seconds each frame, whatever the wall clock says.
+    ///
+    /// This also sets [`InputState::stable_dt`] and [`InputState::predicted_dt`] to it,
+    /// so animations and the text cursor blinking are the same on every run.
+    Fixed(f32),
+}
+
 /// Per-viewport state related to repaint scheduling.
 struct ViewportRepaintInfo {
     /// Monotonically increasing counter.
@@ -297,6 +516,11 @@ struct ViewportRepaintInfo {
     /// While positive, keep requesting repaints. Decrement at the start of each frame.
     outstanding: u8,
 
+    /// The part of the viewport that needs repainting, or `None` for all of it.
+    ///
+    /// [`Rect::NOTHING`] if no repaint has been requested since the start of the frame.
+    rect: Option<Rect>,
+
     /// What caused repaints during this frame?
     causes: Vec<RepaintCause>,
 
@@ -304,6 +528,20 @@ struct ViewportRepaintInfo {
     /// (i.e: why are we updating now?)
     prev_causes: Vec<RepaintCause>,
 
+    /// How many times each cause has requested a repaint, see [`Context::repaint_cause_counts`].
+    cause_counts: HashMap<RepaintCause, u64>,
+
+    /// Did this frame have no input, so immediate repaints can be throttled?
+    ///
+    /// See [`Context::set_max_idle_fps`].
+    idle: bool,
+
+    /// Has an animation requested a repaint this frame?
+    animating: bool,
+
+    /// See [`ViewportCommand::RepaintPolicy`].
+    policy: RepaintPolicy,
+
     /// What was the output of `repaint_delay` on the previous frame?
     ///
     /// If this was zero, we are repainting as quickly as possible
@@ -321,9 +559,14 @@ impl Default for ViewportRepaintInfo {
 
             // Let's run a couple of frames at the start, because why not.
             outstanding: 1,
+            rect: None,
 
             causes: Default::default(),
             prev_causes: Default::default(),
+            cause_counts: Default::default(),
+            idle: false,
+            animating: false,
+            policy: RepaintPolicy::Reactive,
 
             prev_frame_paint_delay: Duration::MAX,
         }
@@ -338,6 +581,19 @@ impl ViewportRepaintInfo {
 
 // ----------------------------------------------------------------------------
 
+/// A viewport recreated by [`Context::restore_session`].
+///
+/// It is shown by its parent each frame, until it is closed.
+#[derive(Clone)]
+struct RestoredViewport {
+    id: ViewportId,
+    parent: ViewportId,
+    builder: ViewportBuilder,
+    viewport_ui_cb: Arc<RestoredViewportUiCallback>,
+}
+
+// ----------------------------------------------------------------------------
+
 #[derive(Default)]
 struct ContextImpl {
     /// Since we could have multiple viewports across multiple monitors with
@@ -374,6 +630,24 @@ struct ContextImpl {
 
     paint_stats: PaintStats,
 
+    /// See [`Context::set_profiling`].
+    profiling: bool,
+
+    /// See [`Context::set_color_debug_options`].
+    color_debug_options: crate::ColorDebugOptions,
+
+    /// See [`crate::Options::theme_preference`].
+    theme_state: crate::theme::ThemeState,
+
+    /// Has [`Context::devtools_ui`] been shown? If so, we record widget callsites.
+    devtools_active: bool,
+
+    /// Is a [`crate::test_harness::Harness`] driving us? If so, we record [`WidgetInfo`]s.
+    record_widget_infos: bool,
+
+    /// Tessellation buffers handed back by [`Context::recycle_primitives`].
+    mesh_pool: epaint::MeshPool,
+
     request_repaint_callback: Option<Box<dyn Fn(RequestRepaintInfo) + Send + Sync>>,
 
     viewport_parents: ViewportIdMap<ViewportId>,
@@ -381,6 +655,28 @@ struct ContextImpl {
 
     embed_viewports: bool,
 
+    /// Viewports recreated by [`Context::restore_session`], in parent-before-child order.
+    restored_viewports: Vec<RestoredViewport>,
+
+    /// See [`Context::start_input_recording`], with the time of the first recorded frame.
+    input_recording: Option<(crate::InputRecording, Option<f64>)>,
+
+    /// See [`Context::replay`].
+    input_replay: Option<crate::input_recording::InputReplay>,
+
+    /// See [`Context::set_time_travel_capacity`].
+    time_travel: crate::time_travel::TimeTravel,
+
+    /// See [`Context::set_time_source`].
+    time_source: TimeSource,
+
+    /// See [`Context::set_max_idle_fps`].
+    max_idle_fps: Option<f32>,
+
+    /// The missing glyphs we have already warned about, so we only do it once.
+    #[cfg(feature = "log")]
+    warned_missing_glyphs: BTreeSet<char>,
+
     #[cfg(feature = "accesskit")]
     is_accesskit_enabled: bool,
     #[cfg(feature = "accesskit")]
@@ -391,6 +687,35 @@ struct ContextImpl {
 
 impl ContextImpl {
     fn begin_frame_mut(&mut self, mut new_raw_input: RawInput) {
+        let mut replay_delay = None;
+        if let Some(replay) = &mut self.input_replay {
+            let time = self
+                .viewports
+                .get(&new_raw_input.viewport_id)
+                .map_or(0.0, |viewport| viewport.input.time);
+            replay_delay = replay.replace(&mut new_raw_input, time);
+            if replay.is_done() {
+                self.input_replay = None;
+            }
+        }
+        if let Some((recording, start_time)) = &mut self.input_recording {
+            recording.record(&new_raw_input, start_time);
+        }
+
+        let is_root = new_raw_input.viewport_id == ViewportId::ROOT;
+        if is_root {
+            let stepped = self.time_travel.handle_keys(&new_raw_input.events);
+            let viewport = self.viewports.entry(ViewportId::ROOT).or_default();
+            self.time_travel.begin_frame(
+                &mut self.memory,
+                &mut viewport.input,
+                &mut viewport.widgets_prev_frame,
+            );
+            if stepped {
+                viewport.repaint.outstanding = 1;
+            }
+        }
+
         let viewport_id = new_raw_input.viewport_id;
         let parent_id = new_raw_input
             .viewports
@@ -404,6 +729,21 @@ impl ContextImpl {
 
         self.begin_frame_repaint_logic(viewport_id);
 
+        if self
+            .viewports
+            .entry(viewport_id)
+            .or_default()
+            .repaint
+            .policy
+            == RepaintPolicy::Continuous
+        {
+            self.request_repaint(viewport_id, RepaintCause::new());
+        }
+
+        if let Some((replay_viewport_id, delay)) = replay_delay {
+            self.request_repaint_after(delay, replay_viewport_id, RepaintCause::new(), None);
+        }
+
         let viewport = self.viewports.entry(viewport_id).or_default();
 
         if is_outermost_viewport {
@@ -421,6 +761,19 @@ impl ContextImpl {
                 // but the `screen_rect` is the most important part.
             }
         }
+        {
+            let viewport = self.viewports.entry(self.viewport_id()).or_default();
+            self.memory
+                .options
+                .input_sanitizer
+                .sanitize(&mut new_raw_input, viewport.input.screen_rect);
+
+            if let TimeSource::Fixed(dt) = self.time_source {
+                new_raw_input.time = Some(viewport.input.time + dt as f64);
+                new_raw_input.predicted_dt = dt;
+            }
+        }
+
         let pixels_per_point = self.memory.options.zoom_factor
             * new_raw_input
                 .viewport()
@@ -431,6 +784,12 @@ impl ContextImpl {
 
         let viewport = self.viewports.entry(self.viewport_id()).or_default();
 
+        let nothing_focused = self.memory.focus().is_none();
+        viewport
+            .input
+            .gamepad
+            .begin_frame(&mut new_raw_input.events, nothing_focused);
+
         self.memory
             .begin_frame(&viewport.input, &new_raw_input, &all_viewport_ids);
 
@@ -438,11 +797,39 @@ impl ContextImpl {
             new_raw_input,
             viewport.repaint.requested_immediate_repaint_prev_frame(),
             pixels_per_point,
+            self.memory.options.long_press,
         );
+        if is_root {
+            self.time_travel.after_input(
+                viewport.repaint.frame_nr,
+                &mut self.memory,
+                &mut viewport.input,
+                &mut viewport.widgets_prev_frame,
+            );
+        }
+        let time_until_long_press = viewport.input.pointer.time_until_long_press();
+        viewport.repaint.idle =
+            viewport.input.events.is_empty() && !viewport.input.pointer.any_down();
+
+        if is_outermost_viewport
+            && self
+                .theme_state
+                .begin_frame(&mut self.memory.options, &mut viewport.input)
+        {
+            self.request_repaint(viewport_id, RepaintCause::new());
+        }
+
+        if let Some(delay) = time_until_long_press {
+            // Make sure we notice when the press turns into a long-press:
+            let delay = Duration::from_secs_f32(delay);
+            self.request_repaint_after(delay, viewport_id, RepaintCause::new(), None);
+        }
 
+        let viewport = self.viewports.entry(self.viewport_id()).or_default();
         viewport.frame_state.begin_frame(&viewport.input);
 
         {
+            crate::trace_span!("egui::interaction");
             let area_order: HashMap<LayerId, usize> = self
                 .memory
                 .areas()
@@ -464,12 +851,14 @@ impl ContextImpl {
                 }
             });
 
+            viewport.widget_layer_order = layers;
+
             viewport.hits = if let Some(pos) = viewport.input.pointer.interact_pos() {
                 let interact_radius = self.memory.options.style.interaction.interact_radius;
 
                 crate::hit_test::hit_test(
                     &viewport.widgets_prev_frame,
-                    &layers,
+                    &viewport.widget_layer_order,
                     &self.memory.layer_transforms,
                     pos,
                     interact_radius,
@@ -482,9 +871,55 @@ impl ContextImpl {
                 &viewport.interact_widgets,
                 &viewport.widgets_prev_frame,
                 &viewport.hits,
-                &viewport.input,
+                &viewport.input.pointer,
+                self.memory.interaction_mut(),
+            );
+
+            let multi_touch = viewport.input.multi_touch();
+            let gesture_hits = if let Some(info) = &multi_touch {
+                crate::hit_test::hit_test(
+                    &viewport.widgets_prev_frame,
+                    &viewport.widget_layer_order,
+                    &self.memory.layer_transforms,
+                    info.start_center_pos,
+                    self.memory.options.style.interaction.interact_radius,
+                )
+            } else {
+                WidgetHits::default()
+            };
+            viewport.interact_widgets.gesture = crate::interaction::claim_gesture(
+                multi_touch.as_ref(),
+                &gesture_hits,
                 self.memory.interaction_mut(),
             );
+
+            let mut prev_extra_interactions =
+                std::mem::take(&mut viewport.extra_pointer_interactions);
+            for (id, pointer) in &viewport.input.extra_pointers {
+                let (mut interaction, prev_snapshot) =
+                    prev_extra_interactions.remove(id).unwrap_or_default();
+                let hits = if let Some(pos) = pointer.interact_pos() {
+                    crate::hit_test::hit_test(
+                        &viewport.widgets_prev_frame,
+                        &viewport.widget_layer_order,
+                        &self.memory.layer_transforms,
+                        pos,
+                        self.memory.options.style.interaction.interact_radius,
+                    )
+                } else {
+                    WidgetHits::default()
+                };
+                let snapshot = crate::interaction::interact(
+                    &prev_snapshot,
+                    &viewport.widgets_prev_frame,
+                    &hits,
+                    pointer,
+                    &mut interaction,
+                );
+                viewport
+                    .extra_pointer_interactions
+                    .insert(*id, (interaction, snapshot));
+            }
         }
 
         // Ensure we register the background area so panels and background ui can catch clicks:
@@ -621,6 +1056,15 @@ impl ContextImpl {
         self.viewports.entry(self.viewport_id()).or_default()
     }
 
+    /// See [`Context::content_size`].
+    fn content_size(&mut self) -> Option<Vec2> {
+        let mut used = self.viewport().frame_state.used_by_contents;
+        for window in self.memory.areas().visible_windows() {
+            used = used.union(window.rect());
+        }
+        used.is_positive().then(|| used.max - Pos2::ZERO)
+    }
+
     fn viewport_for(&mut self, viewport_id: ViewportId) -> &mut ViewportState {
         self.viewports.entry(viewport_id).or_default()
     }
@@ -705,6 +1149,8 @@ impl Default for Context {
         crate::debug_text::register(&ctx);
         crate::text_selection::LabelSelectionState::register(&ctx);
         crate::DragAndDrop::register(&ctx);
+        crate::toasts::Toasts::register(&ctx);
+        crate::frame_profiler::register(&ctx);
 
         ctx
     }
@@ -748,7 +1194,10 @@ impl Context {
         crate::profile_function!();
 
         self.begin_frame(new_input);
-        run_ui(self);
+        {
+            crate::trace_span!("egui::run_ui");
+            run_ui(self);
+        }
         self.end_frame()
     }
 
@@ -771,8 +1220,15 @@ impl Context {
     /// ```
     pub fn begin_frame(&self, new_input: RawInput) {
         crate::profile_function!();
+        crate::trace_span!("egui::begin_frame");
+        let frame_start = crate::frame_profiler::start_timer(self);
         self.read(|ctx| ctx.plugins.clone()).on_begin_frame(self);
-        self.write(|ctx| ctx.begin_frame_mut(new_input));
+        self.write(|ctx| {
+            ctx.begin_frame_mut(new_input);
+            ctx.viewport().profiler.begin_frame(frame_start);
+        });
+        self.read(|ctx| ctx.plugins.clone()).on_after_input(self);
+        self.show_restored_viewports();
     }
 }
 
@@ -884,6 +1340,24 @@ impl Context {
         self.write(move |ctx| writer(&mut ctx.viewport().frame_state))
     }
 
+    /// Read-write access to the [`crate::popup::HoverTimer`] of the current viewport.
+    #[inline]
+    pub(crate) fn hover_timer_mut<R>(
+        &self,
+        writer: impl FnOnce(&mut crate::popup::HoverTimer) -> R,
+    ) -> R {
+        self.write(move |ctx| writer(&mut ctx.viewport().hover_timer))
+    }
+
+    /// How long has the widget been hovered, in seconds?
+    ///
+    /// Call this each frame the widget is hovered, or the hover starts over.
+    pub(crate) fn hover_duration(&self, widget_id: Id) -> f32 {
+        let time = self.input(|i| i.time);
+        let frame_nr = self.frame_nr();
+        self.hover_timer_mut(|timer| timer.hover_duration(widget_id, time, frame_nr))
+    }
+
     /// Read-only access to [`Fonts`].
     ///
     /// Not valid until first call to [`Context::run()`].
@@ -1019,14 +1493,18 @@ impl Context {
     ///
     /// If the widget already exists, its state (sense, Rect, etc) will be updated.
     #[allow(clippy::too_many_arguments)]
+    #[track_caller]
     pub(crate) fn create_widget(&self, mut w: WidgetRect) -> Response {
         if !w.enabled {
             w.sense.click = false;
             w.sense.drag = false;
         }
 
+        let callsite = Location::caller();
+
         // Remember this widget
         self.write(|ctx| {
+            let record_callsites = ctx.devtools_active;
             let viewport = ctx.viewport();
 
             // We add all widgets here, even non-interactive ones,
@@ -1034,6 +1512,10 @@ impl Context {
             // but also to know when we have reached the widget we are checking for cover.
             viewport.widgets_this_frame.insert(w.layer_id, w);
 
+            if record_callsites {
+                viewport.widgets_this_frame.set_callsite(w.id, callsite);
+            }
+
             if w.sense.focusable {
                 ctx.memory.interested_in_focus(w.id);
             }
@@ -1086,8 +1568,8 @@ impl Context {
             .map_or(false, |response| response.contains_pointer)
     }
 
-    /// Do all interaction for an existing widget, without (re-)registering it.
-    fn get_response(&self, widget_rect: WidgetRect) -> Response {
+    /// A response for a widget that is never interacted with, e.g. one in a [`Ui::measure`] pass.
+    pub(crate) fn inert_response(&self, widget_rect: WidgetRect) -> Response {
         let WidgetRect {
             id,
             layer_id,
@@ -1097,9 +1579,7 @@ impl Context {
             enabled,
         } = widget_rect;
 
-        let highlighted = self.frame_state(|fs| fs.highlight_this_frame.contains(&id));
-
-        let mut res = Response {
+        Response {
             ctx: self.clone(),
             layer_id,
             id,
@@ -1109,7 +1589,7 @@ impl Context {
             enabled,
             contains_pointer: false,
             hovered: false,
-            highlighted,
+            highlighted: false,
             clicked: Default::default(),
             double_clicked: Default::default(),
             triple_clicked: Default::default(),
@@ -1119,7 +1599,15 @@ impl Context {
             is_pointer_button_down_on: false,
             interact_pointer_pos: None,
             changed: false,
-        };
+        }
+    }
+
+    /// Do all interaction for an existing widget, without (re-)registering it.
+    fn get_response(&self, widget_rect: WidgetRect) -> Response {
+        let WidgetRect { id, sense, .. } = widget_rect;
+
+        let mut res = self.inert_response(widget_rect);
+        res.highlighted = self.frame_state(|fs| fs.highlight_this_frame.contains(&id));
 
         let clicked_elsewhere = res.clicked_elsewhere();
 
@@ -1191,11 +1679,16 @@ impl Context {
                 res.hovered = false;
             }
 
-            if clicked_elsewhere && memory.has_focus(id) {
+            // e.g. clicking a cell to insert a reference to it into the formula being edited:
+            let clicked_keeps_focus = viewport.interact_widgets.clicked.map_or(false, |clicked| {
+                memory.keeps_focus_when_interacting(clicked)
+            });
+
+            if clicked_elsewhere && memory.has_focus(id) && !clicked_keeps_focus {
                 memory.surrender_focus(id);
             }
 
-            if res.dragged() && !memory.has_focus(id) {
+            if res.dragged() && !memory.has_focus(id) && !memory.keeps_focus_when_interacting(id) {
                 // e.g.: remove focus from a widget when you drag something else
                 memory.stop_text_input();
             }
@@ -1215,6 +1708,24 @@ impl Context {
         Self::layer_painter(self, LayerId::debug())
     }
 
+    /// Paint on top of all the ui, e.g. a custom cursor, a crosshair or a measurement.
+    ///
+    /// The overlay can never be interacted with, so it won't steal the hover or clicks from the widgets below.
+    ///
+    /// ```
+    /// # egui::__run_test_ctx(|ctx| {
+    /// if let Some(pos) = ctx.pointer_latest_pos() {
+    ///     let stroke = egui::Stroke::new(1.0, egui::Color32::RED);
+    ///     let painter = ctx.overlay_painter();
+    ///     painter.hline(painter.clip_rect().x_range(), pos.y, stroke);
+    ///     painter.vline(pos.x, painter.clip_rect().y_range(), stroke);
+    /// }
+    /// # });
+    /// ```
+    pub fn overlay_painter(&self) -> Painter {
+        Self::layer_painter(self, LayerId::overlay())
+    }
+
     /// Print this text next to the cursor at the end of the frame.
     ///
     /// If you call this multiple times, the text will be appended.
@@ -1288,6 +1799,24 @@ impl Context {
         self.output_mut(|o| o.copied_text = text);
     }
 
+    /// Ask the integration to play a sound, see [`crate::PlatformOutput::sound_events`].
+    ///
+    /// Ignored unless [`crate::style::Interaction::emit_sounds`] is set.
+    pub fn emit_sound(&self, sound: crate::output::UiSoundEvent) {
+        if self.style().interaction.emit_sounds {
+            self.output_mut(|o| o.sound_events.push(sound));
+        }
+    }
+
+    /// Ask the integration for haptic feedback, see [`crate::PlatformOutput::haptics`].
+    ///
+    /// Ignored unless [`crate::style::Interaction::emit_haptics`] is set.
+    pub fn emit_haptic(&self, haptic: crate::output::HapticKind) {
+        if self.style().interaction.emit_haptics {
+            self.output_mut(|o| o.haptics.push(haptic));
+        }
+    }
+
     /// Format the given shortcut in a human-readable way (e.g. `Ctrl+Shift+X`).
     ///
     /// Can be used to get the text for [`Button::shortcut_text`].
@@ -1376,6 +1905,26 @@ impl Context {
         self.write(|ctx| ctx.request_repaint(id, cause));
     }
 
+    /// Like [`Self::request_repaint`], but also records which widget asked for it.
+    ///
+    /// This shows up in [`Self::repaint_causes`] and [`Self::repaint_cause_counts`],
+    /// which helps finding out what keeps an app repainting.
+    /// See also [`Response::request_repaint`].
+    #[track_caller]
+    pub fn request_repaint_from(&self, widget: Id) {
+        let cause = RepaintCause::from_widget(widget);
+        let viewport_id = self.viewport_id();
+        self.write(|ctx| ctx.request_repaint(viewport_id, cause));
+    }
+
+    /// Like [`Self::request_repaint_from`], but for something that moves every frame on its own,
+    /// like a kinetic scroll, which shouldn't be slowed down by [`Self::set_max_idle_fps`].
+    #[track_caller]
+    pub(crate) fn request_animation_repaint_from(&self, widget: Id) {
+        let cause = RepaintCause::from_widget(widget);
+        self.write(|ctx| ctx.request_animation_repaint(ctx.viewport_id(), cause, None));
+    }
+
     /// Request repaint after at most the specified duration elapses.
     ///
     /// The backend can chose to repaint sooner, for instance if some other code called
@@ -1440,7 +1989,19 @@ impl Context {
     #[track_caller]
     pub fn request_repaint_after_for(&self, duration: Duration, id: ViewportId) {
         let cause = RepaintCause::new();
-        self.write(|ctx| ctx.request_repaint_after(duration, id, cause));
+        self.write(|ctx| ctx.request_repaint_after(duration, id, cause, None));
+    }
+
+    /// Like [`Self::request_repaint_after`], but only the given part of the current viewport (in points) will change.
+    ///
+    /// Use this for small animations that keep going, like a blinking text cursor,
+    /// so that integrations that support it only need to redraw that part of the screen,
+    /// see [`RequestRepaintInfo::rect`].
+    #[track_caller]
+    pub fn request_repaint_rect_after(&self, duration: Duration, rect: Rect) {
+        let cause = RepaintCause::new();
+        let id = self.viewport_id();
+        self.write(|ctx| ctx.request_repaint_after(duration, id, cause, Some(rect)));
     }
 
     /// Was a repaint requested last frame for the current viewport?
@@ -1479,6 +2040,59 @@ impl Context {
         .unwrap_or_default()
     }
 
+    /// How many times each cause has requested a repaint of the current viewport,
+    /// with the most frequent first.
+    ///
+    /// Counts since the start, or since [`Self::clear_repaint_cause_counts`].
+    /// Use this to find the top offenders when an app repaints more often than you'd expect.
+    pub fn repaint_cause_counts(&self) -> Vec<(RepaintCause, u64)> {
+        let mut counts: Vec<_> = self.read(|ctx| {
+            ctx.viewports
+                .get(&ctx.viewport_id())
+                .map(|v| v.repaint.cause_counts.clone())
+                .unwrap_or_default()
+                .into_iter()
+                .collect()
+        });
+        counts.sort_by(|(a_cause, a), (b_cause, b)| {
+            b.cmp(a)
+                .then_with(|| (&a_cause.file, a_cause.line).cmp(&(&b_cause.file, b_cause.line)))
+        });
+        counts
+    }
+
+    /// Limit how often we repaint while nothing is happening, to save battery.
+    ///
+    /// On frames without any input, where no animation, kinetic scroll or smooth scroll is running,
+    /// every call to [`Self::request_repaint`] is turned into
+    /// `request_repaint_after(1.0 / max_idle_fps)`.
+    /// So a spinner or a progress bar keeps updating, but slower,
+    /// without having to find every widget that asks for a repaint.
+    ///
+    /// `None` (the default) turns this off.
+    pub fn set_max_idle_fps(&self, max_idle_fps: Option<f32>) {
+        let max_idle_fps = max_idle_fps.filter(|fps| 0.0 < *fps && fps.is_finite());
+        self.write(|ctx| ctx.max_idle_fps = max_idle_fps);
+    }
+
+    /// See [`Self::set_max_idle_fps`].
+    pub fn max_idle_fps(&self) -> Option<f32> {
+        self.read(|ctx| ctx.max_idle_fps)
+    }
+
+    /// Start counting [`Self::repaint_cause_counts`] from zero again.
+    pub fn clear_repaint_cause_counts(&self) {
+        self.write(|ctx| {
+            let viewport_id = ctx.viewport_id();
+            ctx.viewports
+                .entry(viewport_id)
+                .or_default()
+                .repaint
+                .cause_counts
+                .clear();
+        });
+    }
+
     /// For integrations: this callback will be called when an egui user calls [`Self::request_repaint`] or [`Self::request_repaint_after`].
     ///
     /// This lets you wake up a sleeping UI thread.
@@ -1494,10 +2108,24 @@ impl Context {
 }
 
 /// Callbacks
+///
+/// These let plugins and integrations run code at defined phases of each frame,
+/// without the app having to call them.
+/// In order, a frame consists of:
+///
+/// 1. [`Self::on_begin_frame`]: before the input of the new frame has been processed.
+/// 2. [`Self::on_after_input`]: the input has been processed, but the user ui code has not run yet.
+/// 3. The user ui code.
+/// 4. [`Self::on_end_frame`]: after the user ui code, before the output is collected.
+/// 5. [`Self::on_before_tessellate`]: when the integration tessellates the output shapes.
 impl Context {
     /// Call the given callback at the start of each frame
     /// of each viewport.
     ///
+    /// This is called before the input of the new frame has been processed,
+    /// so [`Self::input`] still shows the previous frame.
+    /// See also [`Self::on_after_input`].
+    ///
     /// This can be used for egui _plugins_.
     /// See [`crate::debug_text`] for an example.
     pub fn on_begin_frame(&self, debug_name: &'static str, cb: ContextCallback) {
@@ -1508,9 +2136,25 @@ impl Context {
         self.write(|ctx| ctx.plugins.on_begin_frame.push(named_cb));
     }
 
+    /// Call the given callback at the start of each frame of each viewport,
+    /// once the new input has been processed, but before the user ui code runs.
+    ///
+    /// This is a good place to look at or react to [`Self::input`], e.g. for global shortcuts.
+    pub fn on_after_input(&self, debug_name: &'static str, cb: ContextCallback) {
+        let named_cb = NamedContextCallback {
+            debug_name,
+            callback: cb,
+        };
+        self.write(|ctx| ctx.plugins.on_after_input.push(named_cb));
+    }
+
     /// Call the given callback at the end of each frame
     /// of each viewport.
     ///
+    /// This is called after the user ui code,
+    /// so anything painted here ends up on top of the ui of the app,
+    /// e.g. a debug HUD or FPS counter.
+    ///
     /// This can be used for egui _plugins_.
     /// See [`crate::debug_text`] for an example.
     pub fn on_end_frame(&self, debug_name: &'static str, cb: ContextCallback) {
@@ -1520,19 +2164,80 @@ impl Context {
         };
         self.write(|ctx| ctx.plugins.on_end_frame.push(named_cb));
     }
-}
 
-impl Context {
-    /// Tell `egui` which fonts to use.
-    ///
-    /// The default `egui` fonts only support latin and cyrillic alphabets,
-    /// but you can call this to install additional fonts that support e.g. korean characters.
+    /// Call the given callback each time [`Self::tessellate`] is called,
+    /// just before the shapes are tessellated.
     ///
-    /// The new fonts will become active at the start of the next frame.
-    pub fn set_fonts(&self, font_definitions: FontDefinitions) {
-        crate::profile_function!();
+    /// The callback can inspect and modify the final shapes of the frame,
+    /// which are in the coordinates of the viewport that was just ended.
+    pub fn on_before_tessellate(&self, debug_name: &'static str, cb: TessellateCallback) {
+        let named_cb = NamedTessellateCallback {
+            debug_name,
+            callback: cb,
+        };
+        self.write(|ctx| ctx.plugins.on_before_tessellate.push(named_cb));
+    }
 
-        let pixels_per_point = self.pixels_per_point();
+    /// Add a function that modifies the final shapes of each frame, just before they are tessellated.
+    ///
+    /// This runs after all [`Self::on_before_tessellate`] callbacks,
+    /// in the order the mappers were added,
+    /// so it affects everything that is painted, including overlays added by plugins.
+    ///
+    /// This lets you apply global effects without touching the ui code,
+    /// e.g. simulating color blindness, forcing a high-contrast palette,
+    /// or watermarking screenshots.
+    ///
+    /// Adding a mapper with the same `debug_name` as an existing one replaces it.
+    ///
+    /// ```
+    /// # let ctx = egui::Context::default();
+    /// ctx.add_shape_mapper("grayscale", std::sync::Arc::new(|shapes| {
+    ///     for clipped_shape in shapes {
+    ///         egui::epaint::shape_transform::adjust_colors(&mut clipped_shape.shape, &|color| {
+    ///             let [r, g, b, a] = color.to_array();
+    ///             let gray = ((r as u32 + g as u32 + b as u32) / 3) as u8;
+    ///             *color = egui::Color32::from_rgba_premultiplied(gray, gray, gray, a);
+    ///         });
+    ///     }
+    /// }));
+    /// ```
+    pub fn add_shape_mapper(&self, debug_name: &'static str, mapper: ShapeMapper) {
+        let named_mapper = NamedShapeMapper { debug_name, mapper };
+        self.write(|ctx| {
+            let mappers = &mut ctx.plugins.shape_mappers;
+            if let Some(existing) = mappers.iter_mut().find(|m| m.debug_name == debug_name) {
+                *existing = named_mapper;
+            } else {
+                mappers.push(named_mapper);
+            }
+        });
+    }
+
+    /// Remove the shape mapper added with [`Self::add_shape_mapper`] under the given name.
+    ///
+    /// Returns `false` if there was no such mapper.
+    pub fn remove_shape_mapper(&self, debug_name: &'static str) -> bool {
+        self.write(|ctx| {
+            let mappers = &mut ctx.plugins.shape_mappers;
+            let len_before = mappers.len();
+            mappers.retain(|m| m.debug_name != debug_name);
+            mappers.len() != len_before
+        })
+    }
+}
+
+impl Context {
+    /// Tell `egui` which fonts to use.
+    ///
+    /// The default `egui` fonts only support latin and cyrillic alphabets,
+    /// but you can call this to install additional fonts that support e.g. korean characters.
+    ///
+    /// The new fonts will become active at the start of the next frame.
+    pub fn set_fonts(&self, font_definitions: FontDefinitions) {
+        crate::profile_function!();
+
+        let pixels_per_point = self.pixels_per_point();
 
         let mut update_fonts = true;
 
@@ -1550,6 +2255,17 @@ impl Context {
         }
     }
 
+    /// The characters no font has a glyph for, in the text laid out during the last frame.
+    ///
+    /// These are shown as a replacement character (`◻`).
+    /// Add fonts for them with [`Self::set_fonts`].
+    /// With the `log` feature, egui also warns about each of them once.
+    ///
+    /// See also [`crate::style::DebugOptions::show_missing_glyphs`].
+    pub fn missing_glyphs(&self) -> BTreeSet<char> {
+        self.write(|ctx| ctx.viewport().missing_glyphs.clone())
+    }
+
     /// The [`Style`] used by all subsequent windows, panels etc.
     pub fn style(&self) -> Arc<Style> {
         self.options(|opt| opt.style.clone())
@@ -1796,6 +2512,7 @@ impl Context {
     #[must_use]
     pub fn end_frame(&self) -> FullOutput {
         crate::profile_function!();
+        let end_frame_start = crate::frame_profiler::start_timer(self);
 
         if self.options(|o| o.zoom_with_keyboard) {
             crate::gui_zoom::zoom_with_keyboard(self);
@@ -1806,7 +2523,7 @@ impl Context {
         #[cfg(debug_assertions)]
         self.debug_painting();
 
-        self.write(|ctx| ctx.end_frame())
+        self.write(|ctx| ctx.end_frame(end_frame_start))
     }
 
     #[cfg(debug_assertions)]
@@ -1859,6 +2576,7 @@ impl Context {
                     drag_stopped: _,
                     contains_pointer,
                     hovered,
+                    gesture,
                 } = interact_widgets;
 
                 if false {
@@ -1877,6 +2595,24 @@ impl Context {
                 for &widget in &dragged {
                     paint_widget_id(widget, "dragged", Color32::GREEN);
                 }
+                for &widget in &gesture {
+                    paint_widget_id(widget, "gesture", Color32::YELLOW);
+                }
+            }
+        }
+
+        if self.style().debug.measure {
+            let (press_origin, latest_pos, any_down) = self.input(|i| {
+                (
+                    i.pointer.press_origin(),
+                    i.pointer.latest_pos(),
+                    i.pointer.any_down(),
+                )
+            });
+            if let (Some(from), Some(to), true) = (press_origin, latest_pos, any_down) {
+                let widgets = self.write(|ctx| ctx.viewport().widgets_this_frame.clone());
+                let snap = |pos| snap_to_widget_edges(&widgets, pos, 6.0);
+                self.overlay_painter().measurement(snap(from), snap(to));
             }
         }
 
@@ -1903,17 +2639,72 @@ impl Context {
     }
 }
 
+/// Move `pos` to the closest edges of the widgets within `max_dist`, for measuring the distances between them.
+#[cfg(debug_assertions)]
+fn snap_to_widget_edges(widgets: &WidgetRects, pos: Pos2, max_dist: f32) -> Pos2 {
+    let mut snapped = pos;
+    let mut best = vec2(max_dist, max_dist);
+    for widget in widgets.layers().flat_map(|(_, rects)| rects) {
+        let rect = widget.rect;
+        if rect.expand(max_dist).y_range().contains(pos.y) {
+            for x in [rect.left(), rect.right()] {
+                if (x - pos.x).abs() < best.x {
+                    best.x = (x - pos.x).abs();
+                    snapped.x = x;
+                }
+            }
+        }
+        if rect.expand(max_dist).x_range().contains(pos.x) {
+            for y in [rect.top(), rect.bottom()] {
+                if (y - pos.y).abs() < best.y {
+                    best.y = (y - pos.y).abs();
+                    snapped.y = y;
+                }
+            }
+        }
+    }
+    snapped
+}
+
 impl ContextImpl {
-    fn end_frame(&mut self) -> FullOutput {
+    fn end_frame(&mut self, end_frame_start: Option<web_time::Instant>) -> FullOutput {
         let ended_viewport_id = self.viewport_id();
+        let content_size = self.content_size();
         let viewport = self.viewports.entry(ended_viewport_id).or_default();
         let pixels_per_point = viewport.input.pixels_per_point;
 
         viewport.repaint.frame_nr += 1;
 
+        viewport.content_size = content_size;
+        if viewport.builder.inner_size_to_content == Some(true) {
+            if let Some(content_size) = content_size {
+                // Ignore tiny changes, so that rounding can't make us resize back and forth forever:
+                let changed = viewport.fitted_size.map_or(true, |fitted_size| {
+                    0.5 < (fitted_size - content_size).abs().max_elem()
+                });
+                if changed {
+                    viewport.fitted_size = Some(content_size);
+                    viewport
+                        .commands
+                        .push(ViewportCommand::InnerSize(content_size));
+                }
+            }
+        }
+
         self.memory.end_frame(&viewport.frame_state.used_ids);
 
         if let Some(fonts) = self.fonts.get(&pixels_per_point.into()) {
+            viewport.missing_glyphs = fonts.take_missing_glyphs();
+            #[cfg(feature = "log")]
+            for &c in &viewport.missing_glyphs {
+                if self.warned_missing_glyphs.insert(c) {
+                    log::warn!(
+                        "No font has a glyph for {c:?} (U+{:04X}); add a font for it with Context::set_fonts",
+                        c as u32
+                    );
+                }
+            }
+
             let tex_mngr = &mut self.tex_manager.0.write();
             if let Some(font_image_delta) = fonts.font_image_delta() {
                 // A partial font atlas update, e.g. a new glyph has been entered.
@@ -1942,8 +2733,16 @@ impl ContextImpl {
         // Inform the backend of all textures that have been updated (including font atlas).
         let textures_delta = self.tex_manager.0.write().take_delta();
 
+        if let Some(end_frame_start) = end_frame_start {
+            let fonts = self.fonts.get(&pixels_per_point.into());
+            viewport
+                .profiler
+                .end_frame(end_frame_start, fonts, &textures_delta);
+        }
+
         #[cfg_attr(not(feature = "accesskit"), allow(unused_mut))]
         let mut platform_output: PlatformOutput = std::mem::take(&mut viewport.output);
+        viewport.input.pointer.locked = platform_output.pointer_lock;
 
         #[cfg(feature = "accesskit")]
         {
@@ -1951,18 +2750,16 @@ impl ContextImpl {
             let state = viewport.frame_state.accesskit_state.take();
             if let Some(state) = state {
                 let root_id = crate::accesskit_root_id().accesskit_id();
-                let nodes = {
-                    state
-                        .node_builders
-                        .into_iter()
-                        .map(|(id, builder)| {
-                            (
-                                id.accesskit_id(),
-                                builder.build(&mut self.accesskit_node_classes),
-                            )
-                        })
-                        .collect()
-                };
+                viewport.accesskit_nodes_prev_frame = state
+                    .node_builders
+                    .into_iter()
+                    .map(|(id, builder)| (id, builder.build(&mut self.accesskit_node_classes)))
+                    .collect();
+                let nodes = viewport
+                    .accesskit_nodes_prev_frame
+                    .iter()
+                    .map(|(id, node)| (id.accesskit_id(), node.clone()))
+                    .collect();
                 let focus_id = self.memory.focus().map_or(root_id, |id| id.accesskit_id());
                 platform_output.accesskit_update = Some(accesskit::TreeUpdate {
                     nodes,
@@ -1993,7 +2790,10 @@ impl ContextImpl {
             viewport.widgets_this_frame.clear();
         }
 
-        if repaint_needed || viewport.input.wants_repaint() {
+        if viewport.input.wants_repaint() {
+            // E.g. smooth scrolling, which shouldn't be throttled by `max_idle_fps`:
+            self.request_animation_repaint(ended_viewport_id, RepaintCause::new(), None);
+        } else if repaint_needed {
             self.request_repaint(ended_viewport_id, RepaintCause::new());
         }
 
@@ -2052,7 +2852,26 @@ impl ContextImpl {
                     // Let the primary immediate viewport handle the commands of its children too.
                     // This can make things easier for the backend, as otherwise we may get commands
                     // that affect a viewport while its egui logic is running.
-                    std::mem::take(&mut viewport.commands)
+                    let content_size = viewport.content_size;
+                    let mut pending = vec![];
+                    let commands = std::mem::take(&mut viewport.commands)
+                        .into_iter()
+                        .filter_map(|command| match command {
+                            ViewportCommand::InnerSizeToContent => match content_size {
+                                Some(content_size) => {
+                                    Some(ViewportCommand::InnerSize(content_size))
+                                }
+                                None => {
+                                    // Nothing to fit yet, so try again after the next frame:
+                                    pending.push(command);
+                                    None
+                                }
+                            },
+                            command => Some(command),
+                        })
+                        .collect();
+                    viewport.commands = pending;
+                    commands
                 } else {
                     vec![]
                 };
@@ -2081,7 +2900,7 @@ impl ContextImpl {
             self.memory.set_viewport_id(viewport_id);
         }
 
-        let active_pixels_per_point: std::collections::BTreeSet<OrderedFloat<f32>> = self
+        let active_pixels_per_point: BTreeSet<OrderedFloat<f32>> = self
             .viewports
             .values()
             .map(|v| v.input.pixels_per_point.into())
@@ -2115,13 +2934,20 @@ impl Context {
     /// `pixels_per_point` is used for feathering (anti-aliasing).
     /// For this you can use [`FullOutput::pixels_per_point`], [`Self::pixels_per_point`],
     /// or whatever is appropriate for your viewport.
+    ///
+    /// Once you have painted the result, you can hand it back with [`Self::recycle_primitives`]
+    /// so that its buffers are reused next time.
     pub fn tessellate(
         &self,
-        shapes: Vec<ClippedShape>,
+        mut shapes: Vec<ClippedShape>,
         pixels_per_point: f32,
     ) -> Vec<ClippedPrimitive> {
         crate::profile_function!();
 
+        let plugins = self.read(|ctx| ctx.plugins.clone());
+        plugins.on_before_tessellate(self, &mut shapes, pixels_per_point);
+        plugins.map_shapes(&mut shapes);
+
         // A tempting optimization is to reuse the tessellation from last frame if the
         // shapes are the same, but just comparing the shapes takes about 50% of the time
         // it takes to tessellate them, so it is not a worth optimization.
@@ -2139,6 +2965,7 @@ impl Context {
                 (atlas.size(), atlas.prepared_discs())
             };
 
+            let tessellation_start = ctx.profiling.then(web_time::Instant::now);
             let paint_stats = PaintStats::from_shapes(&shapes);
             let clipped_primitives = {
                 crate::profile_scope!("tessellator::tessellate_shapes");
@@ -2148,13 +2975,32 @@ impl Context {
                     font_tex_size,
                     prepared_discs,
                 )
-                .tessellate_shapes(shapes)
+                .tessellate_shapes_with_pool(shapes, &mut ctx.mesh_pool)
             };
-            ctx.paint_stats = paint_stats.with_clipped_primitives(&clipped_primitives);
+            ctx.paint_stats = PaintStats {
+                mesh_pool: ctx.mesh_pool.take_stats(),
+                ..paint_stats.with_clipped_primitives(&clipped_primitives)
+            };
+            if let Some(tessellation_start) = tessellation_start {
+                let seconds = tessellation_start.elapsed().as_secs_f32();
+                let last_viewport = ctx.last_viewport;
+                ctx.viewport_for(last_viewport)
+                    .profiler
+                    .set_tessellation_time(seconds);
+            }
             clipped_primitives
         })
     }
 
+    /// Hand back the output of [`Self::tessellate`] once it has been painted.
+    ///
+    /// The vertex and index buffers will then be reused the next time you call [`Self::tessellate`],
+    /// instead of allocating new ones each frame.
+    /// How well this works is shown in the paint stats of [`Self::inspection_ui`].
+    pub fn recycle_primitives(&self, clipped_primitives: Vec<ClippedPrimitive>) {
+        self.write(|ctx| ctx.mesh_pool.recycle(clipped_primitives));
+    }
+
     // ---------------------------------------------------------------------
 
     /// Position and size of the egui area.
@@ -2188,6 +3034,18 @@ impl Context {
         self.used_rect().max - Pos2::ZERO
     }
 
+    /// The size needed to show all panels and windows of the current viewport,
+    /// with the [`CentralPanel`] shrunk to what its contents used.
+    ///
+    /// Unlike [`Self::used_size`] this doesn't grow with the viewport,
+    /// so a native window can be resized to it, see [`ViewportCommand::InnerSizeToContent`].
+    /// Note that side and top/bottom panels still span the full height or width of the viewport.
+    ///
+    /// `None` if nothing has been shown yet.
+    pub fn content_size(&self) -> Option<Vec2> {
+        self.write(|ctx| ctx.content_size())
+    }
+
     // ---------------------------------------------------------------------
 
     /// Is the pointer (mouse/touch) over any egui area?
@@ -2242,9 +3100,12 @@ impl Context {
 
     /// Is an egui context menu open?
     pub fn is_context_menu_open(&self) -> bool {
-        self.data(|d| {
+        let popup_id = self.data(|d| {
             d.get_temp::<crate::menu::BarState>(menu::CONTEXT_MENU_ID_STR.into())
-                .map_or(false, |state| state.has_root())
+                .and_then(|state| state.root_popup_id())
+        });
+        popup_id.map_or(false, |popup_id| {
+            self.memory(|mem| mem.popups.is_open(popup_id))
         })
     }
 }
@@ -2395,6 +3256,50 @@ impl Context {
     pub fn set_debug_on_hover(&self, debug_on_hover: bool) {
         self.style_mut(|style| style.debug.debug_on_hover = debug_on_hover);
     }
+
+    /// Turn on/off the built-in frame profiler.
+    ///
+    /// When on, egui measures where the time of each frame goes
+    /// (input, each panel, tessellation, …) and shows a summary in an overlay window.
+    /// Closing the window turns the profiler off again.
+    ///
+    /// The measurements are also available with [`Self::frame_profile`].
+    pub fn set_profiling(&self, profiling: bool) {
+        self.write(|ctx| ctx.profiling = profiling);
+    }
+
+    /// Is the built-in frame profiler on?
+    ///
+    /// See [`Self::set_profiling`].
+    pub fn profiling(&self) -> bool {
+        self.read(|ctx| ctx.profiling)
+    }
+
+    /// The timings of the last finished frame of the current viewport.
+    ///
+    /// Only collected when [`Self::set_profiling`] is on.
+    pub fn frame_profile(&self) -> crate::FrameProfile {
+        self.write(|ctx| ctx.viewport().profiler.last().clone())
+    }
+
+    /// Simulate color blindness and/or mark text with too little contrast.
+    ///
+    /// This is useful for checking that your theme and custom colors work for everyone.
+    /// It is implemented with [`Self::add_shape_mapper`],
+    /// so it affects everything egui paints, except the contents of images.
+    pub fn set_color_debug_options(&self, options: crate::ColorDebugOptions) {
+        self.write(|ctx| ctx.color_debug_options = options);
+        crate::color_debug::set_options(self, options);
+    }
+
+    /// See [`Self::set_color_debug_options`].
+    pub fn color_debug_options(&self) -> crate::ColorDebugOptions {
+        self.read(|ctx| ctx.color_debug_options)
+    }
+
+    pub(crate) fn add_panel_profile(&self, name: String, seconds: f32) {
+        self.write(|ctx| ctx.viewport().profiler.add_panel(name, seconds));
+    }
 }
 
 /// ## Animation
@@ -2408,16 +3313,51 @@ impl Context {
     /// The function will call [`Self::request_repaint()`] when appropriate.
     ///
     /// The animation time is taken from [`Style::animation_time`].
+    ///
+    /// If [`crate::Options::animations_enabled`] is `false`, this returns `0.0` or `1.0` right away.
     #[track_caller] // To track repaint cause
     pub fn animate_bool(&self, id: Id, value: bool) -> f32 {
-        let animation_time = self.style().animation_time;
-        self.animate_bool_with_time(id, value, animation_time)
+        self.animate_bool_with_options(id, value, AnimationOptions::default())
     }
 
     /// Like [`Self::animate_bool`] but allows you to control the animation time.
     #[track_caller] // To track repaint cause
     pub fn animate_bool_with_time(&self, id: Id, target_value: bool, animation_time: f32) -> f32 {
-        let animated_value = self.write(|ctx| {
+        let options = AnimationOptions::default().with_duration(animation_time);
+        self.animate_bool_with_options(id, target_value, options)
+    }
+
+    /// Like [`Self::animate_bool`] but allows you to control the duration and easing,
+    /// or to turn off this animation.
+    #[track_caller] // To track repaint cause
+    pub fn animate_bool_with_options(
+        &self,
+        id: Id,
+        target_value: bool,
+        options: AnimationOptions,
+    ) -> f32 {
+        self.animate_bool_impl(id, target_value, options, None)
+    }
+
+    /// Like [`Self::animate_bool`], for animations that only change what is painted in `rect`,
+    /// like a widget fading in and out as it is hovered.
+    ///
+    /// See [`Self::request_repaint_rect_after`].
+    #[track_caller] // To track repaint cause
+    pub fn animate_bool_in_rect(&self, id: Id, value: bool, rect: Rect) -> f32 {
+        self.animate_bool_impl(id, value, AnimationOptions::default(), Some(rect))
+    }
+
+    #[track_caller] // To track repaint cause
+    fn animate_bool_impl(
+        &self,
+        id: Id,
+        target_value: bool,
+        options: AnimationOptions,
+        rect: Option<Rect>,
+    ) -> f32 {
+        let linear_value = self.write(|ctx| {
+            let animation_time = options.duration(&ctx.memory.options);
             ctx.animation_manager.animate_bool(
                 &ctx.viewports.entry(ctx.viewport_id()).or_default().input,
                 animation_time,
@@ -2425,11 +3365,18 @@ impl Context {
                 target_value,
             )
         });
-        let animation_in_progress = 0.0 < animated_value && animated_value < 1.0;
+        let animation_in_progress = 0.0 < linear_value && linear_value < 1.0;
         if animation_in_progress {
-            self.request_repaint();
+            let cause = RepaintCause::from_widget(id);
+            self.write(|ctx| ctx.request_animation_repaint(ctx.viewport_id(), cause, rect));
+        }
+
+        // Ease in the direction we are going:
+        if target_value {
+            (options.easing)(linear_value)
+        } else {
+            1.0 - (options.easing)(1.0 - linear_value)
         }
-        animated_value
     }
 
     /// Smoothly animate an `f32` value.
@@ -2438,17 +3385,35 @@ impl Context {
     /// When it is called with a new value, it linearly interpolates to it in the given time.
     #[track_caller] // To track repaint cause
     pub fn animate_value_with_time(&self, id: Id, target_value: f32, animation_time: f32) -> f32 {
+        let options = AnimationOptions::default().with_duration(animation_time);
+        self.animate_value_with_options(id, target_value, options)
+    }
+
+    /// Like [`Self::animate_value_with_time`] but allows you to control the duration and easing,
+    /// or to turn off this animation.
+    ///
+    /// If [`crate::Options::animations_enabled`] is `false`, this returns `target_value` right away.
+    #[track_caller] // To track repaint cause
+    pub fn animate_value_with_options(
+        &self,
+        id: Id,
+        target_value: f32,
+        options: AnimationOptions,
+    ) -> f32 {
         let animated_value = self.write(|ctx| {
+            let animation_time = options.duration(&ctx.memory.options);
             ctx.animation_manager.animate_value(
                 &ctx.viewports.entry(ctx.viewport_id()).or_default().input,
                 animation_time,
+                options.easing,
                 id,
                 target_value,
             )
         });
         let animation_in_progress = animated_value != target_value;
         if animation_in_progress {
-            self.request_repaint();
+            let cause = RepaintCause::from_widget(id);
+            self.write(|ctx| ctx.request_animation_repaint(ctx.viewport_id(), cause, None));
         }
 
         animated_value
@@ -2473,6 +3438,94 @@ impl Context {
         }
     }
 
+    /// Show a list of all widgets of the previous frame, with their [`Id`], rect, layer,
+    /// and where in the code they were created.
+    ///
+    /// Hover or click a widget in the list to highlight it on screen.
+    /// This is very useful for debugging the layout of large apps.
+    ///
+    /// Put this in a [`crate::Window`].
+    ///
+    /// Once this has been shown, egui starts recording where each widget is created,
+    /// which adds a small overhead to every widget.
+    pub fn devtools_ui(&self, ui: &mut Ui) {
+        let (widgets, layer_order) = self.write(|ctx| {
+            ctx.devtools_active = true;
+            let viewport = ctx.viewport();
+            (
+                viewport.widgets_prev_frame.clone(),
+                viewport.widget_layer_order.clone(),
+            )
+        });
+        crate::devtools::devtools_ui(self, ui, &widgets, &layer_order);
+    }
+
+    /// Start recording what each widget reports with [`Response::widget_info`].
+    ///
+    /// Used by [`crate::test_harness::Harness`] to find widgets by their label.
+    pub(crate) fn enable_widget_info_recording(&self) {
+        self.write(|ctx| ctx.record_widget_infos = true);
+    }
+
+    pub(crate) fn record_widget_info(&self, id: Id, make_info: impl FnOnce() -> WidgetInfo) {
+        if self.read(|ctx| ctx.record_widget_infos) {
+            let info = make_info();
+            self.write(|ctx| ctx.viewport().widgets_this_frame.set_info(id, info));
+        }
+    }
+
+    /// Read the [`WidgetRects`] of the last finished frame of the current viewport.
+    ///
+    /// This is what egui uses for hit-testing and interaction during the current frame.
+    /// Don't call other [`Context`] methods from within the closure, or you will deadlock.
+    ///
+    /// ```
+    /// # let ctx = egui::Context::default();
+    /// let num_clickable = ctx.widget_rects_prev_frame(|widgets| {
+    ///     widgets
+    ///         .layers()
+    ///         .flat_map(|(_, rects)| rects)
+    ///         .filter(|w| w.sense.click)
+    ///         .count()
+    /// });
+    /// ```
+    pub fn widget_rects_prev_frame<R>(&self, reader: impl FnOnce(&WidgetRects) -> R) -> R {
+        self.write(move |ctx| reader(&ctx.viewport().widgets_prev_frame))
+    }
+
+    /// Read the [`WidgetRects`] of the widgets added so far this frame to the current viewport.
+    ///
+    /// Don't call other [`Context`] methods from within the closure, or you will deadlock.
+    pub fn widget_rects_this_frame<R>(&self, reader: impl FnOnce(&WidgetRects) -> R) -> R {
+        self.write(move |ctx| reader(&ctx.viewport().widgets_this_frame))
+    }
+
+    /// The layers with widgets in the last frame, sorted back-to-front.
+    ///
+    /// Use with [`WidgetRects::iter_back_to_front`].
+    pub fn widget_layer_order(&self) -> Vec<LayerId> {
+        self.write(|ctx| ctx.viewport().widget_layer_order.clone())
+    }
+
+    /// Which widgets of the last frame are at the given position,
+    /// and which of them would get a click or drag that started there?
+    ///
+    /// This is the same hit-test egui uses for the pointer,
+    /// including [`style::Interaction::interact_radius`] and layer transforms.
+    pub fn widget_hits_at(&self, pos: Pos2) -> WidgetHits {
+        self.write(|ctx| {
+            let interact_radius = ctx.memory.options.style.interaction.interact_radius;
+            let viewport = ctx.viewports.entry(ctx.viewport_id()).or_default();
+            crate::hit_test::hit_test(
+                &viewport.widgets_prev_frame,
+                &viewport.widget_layer_order,
+                &ctx.memory.layer_transforms,
+                pos,
+                interact_radius,
+            )
+        })
+    }
+
     /// Show the state of egui, including its input and output.
     pub fn inspection_ui(&self, ui: &mut Ui) {
         use crate::containers::*;
@@ -2527,6 +3580,23 @@ impl Context {
                 for cause in causes {
                     ui.label(cause.to_string());
                 }
+
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    ui.label("Top offenders:");
+                    if ui.button("Reset").clicked() {
+                        ui.ctx().clear_repaint_cause_counts();
+                    }
+                });
+                Grid::new("repaint_cause_counts")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for (cause, count) in ui.ctx().repaint_cause_counts().into_iter().take(10) {
+                            ui.label(count.to_string());
+                            ui.label(cause.to_string());
+                            ui.end_row();
+                        }
+                    });
             });
 
         CollapsingHeader::new("📥 Input")
@@ -2740,6 +3810,174 @@ impl Context {
     }
 }
 
+/// ## Named windows
+impl Context {
+    fn window_registry<R>(&self, f: impl FnOnce(&mut WindowRegistry) -> R) -> R {
+        self.data_mut(|d| f(d.get_persisted_mut_or_default::<WindowRegistry>(Id::NULL)))
+    }
+
+    /// Make a window (or anything else) with this name known to the registry,
+    /// unless it already is, and return whether it is open.
+    ///
+    /// [`Window::registered_as`] calls this for you, with `open_by_default = true`.
+    /// Call it before the window is first shown to have it start out closed.
+    ///
+    /// For an [`Area`], use the result to decide whether to show it:
+    /// ```
+    /// # egui::__run_test_ctx(|ctx| {
+    /// if ctx.register_window("hud", true) {
+    ///     egui::Area::new("hud").show(ctx, |ui| {
+    ///         ui.label("FPS: 60");
+    ///     });
+    /// }
+    /// # });
+    /// ```
+    pub fn register_window(&self, name: &str, open_by_default: bool) -> bool {
+        self.window_registry(|registry| {
+            *registry
+                .open
+                .entry(name.to_owned())
+                .or_insert(open_by_default)
+        })
+    }
+
+    /// Is the window with this name open?
+    ///
+    /// `false` for names that haven't been registered.
+    pub fn is_window_open(&self, name: &str) -> bool {
+        self.window_registry(|registry| registry.open.get(name).copied().unwrap_or(false))
+    }
+
+    /// Open or close the window with this name, registering it if needed.
+    pub fn set_window_open(&self, name: &str, open: bool) {
+        self.window_registry(|registry| registry.open.insert(name.to_owned(), open));
+    }
+
+    /// Open the window with this name.
+    pub fn open_window(&self, name: &str) {
+        self.set_window_open(name, true);
+    }
+
+    /// Close the window with this name.
+    pub fn close_window(&self, name: &str) {
+        self.set_window_open(name, false);
+    }
+
+    /// Open the window with this name if it is closed, and close it if it is open.
+    pub fn toggle_window(&self, name: &str) {
+        self.window_registry(|registry| {
+            let open = registry.open.entry(name.to_owned()).or_insert(false);
+            *open = !*open;
+        });
+    }
+
+    /// The names of all registered windows, in alphabetical order, and whether they are open.
+    ///
+    /// Useful for a "Windows" menu:
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// for (name, mut open) in ui.ctx().registered_windows() {
+    ///     if ui.checkbox(&mut open, &name).changed() {
+    ///         ui.ctx().set_window_open(&name, open);
+    ///     }
+    /// }
+    /// # });
+    /// ```
+    pub fn registered_windows(&self) -> Vec<(String, bool)> {
+        self.window_registry(|registry| {
+            registry
+                .open
+                .iter()
+                .map(|(name, open)| (name.clone(), *open))
+                .collect()
+        })
+    }
+}
+
+/// ## Feature flags
+impl Context {
+    /// Set the default of the feature flag with this name, and describe what it does.
+    ///
+    /// The description is shown in [`Self::feature_flags_ui`].
+    pub fn register_flag(&self, name: &str, default: bool, description: impl Into<String>) {
+        crate::feature_flags::FeatureFlags::register(self, name, default, description.into());
+    }
+
+    /// Is the feature flag with this name on?
+    ///
+    /// Use this to gate experimental UI:
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// if ui.ctx().flag("new_editor") {
+    ///     ui.label("The new editor");
+    /// } else {
+    ///     ui.label("The old editor");
+    /// }
+    /// # });
+    /// ```
+    ///
+    /// Flags are off unless registered with another default using [`Self::register_flag`],
+    /// or turned on with [`Self::set_flag`] or in [`Self::feature_flags_ui`].
+    /// Flags changed at runtime are persisted (with the `persistence` feature).
+    pub fn flag(&self, name: &str) -> bool {
+        crate::feature_flags::FeatureFlags::is_enabled(self, name)
+    }
+
+    /// Turn the feature flag with this name on or off, overriding its default.
+    pub fn set_flag(&self, name: &str, enabled: bool) {
+        crate::feature_flags::FeatureFlags::set_enabled(self, name, Some(enabled));
+    }
+
+    /// Set all feature flags back to their defaults.
+    pub fn reset_flags(&self) {
+        crate::feature_flags::FeatureFlags::reset(self);
+    }
+
+    /// Show a checkbox for each feature flag that has been registered or queried.
+    pub fn feature_flags_ui(&self, ui: &mut Ui) {
+        crate::feature_flags::FeatureFlags::ui(self, ui);
+    }
+}
+
+/// ## Dismissible
+impl Context {
+    /// Has the user dismissed the [`Ui::dismissible`] with this `id_source`?
+    pub fn is_dismissed(&self, id_source: impl std::hash::Hash) -> bool {
+        let id = Id::new(id_source).with("dismissed");
+        self.data_mut(|d| d.get_persisted::<bool>(id).unwrap_or(false))
+    }
+
+    /// Dismiss the [`Ui::dismissible`] with this `id_source`,
+    /// or pass `false` to show it again, e.g. from a "Reset tips" button.
+    pub fn set_dismissed(&self, id_source: impl std::hash::Hash, dismissed: bool) {
+        let id = Id::new(id_source).with("dismissed");
+        self.data_mut(|d| {
+            if dismissed {
+                d.insert_persisted(id, true);
+            } else {
+                d.remove::<bool>(id);
+            }
+        });
+    }
+}
+
+/// ## Toasts
+impl Context {
+    /// Show a short notification in a corner of the current viewport.
+    ///
+    /// Toasts are stacked on top of each other, and go away after their [`Toast::duration`].
+    /// Which corner they are shown in is set by [`Style::toast_anchor`].
+    ///
+    /// ```
+    /// # egui::__run_test_ctx(|ctx| {
+    /// ctx.toast(egui::Toast::success("Saved"));
+    /// # });
+    /// ```
+    pub fn toast(&self, toast: Toast) {
+        crate::toasts::Toasts::add(self, toast);
+    }
+}
+
 /// ## Accessibility
 impl Context {
     /// Call the provided function with the given ID pushed on the stack of
@@ -2808,6 +4046,54 @@ impl Context {
         self.write(|ctx| ctx.is_accesskit_enabled = true);
     }
 
+    /// Find the widgets of the last frame with the given AccessKit role and name.
+    ///
+    /// The name is usually the text on the widget, e.g. the label of a button or checkbox.
+    ///
+    /// This lets tests and automation tools locate widgets by what they are,
+    /// instead of by where they are on screen.
+    /// It only works once [`Self::enable_accesskit`] has been called and at least one frame has run.
+    ///
+    /// The results are sorted by position, top to bottom, then left to right.
+    ///
+    /// ```
+    /// # let ctx = egui::Context::default();
+    /// ctx.enable_accesskit();
+    /// let _ = ctx.run(Default::default(), |ctx| {
+    ///     egui::CentralPanel::default().show(ctx, |ui| {
+    ///         ui.button("Save");
+    ///     });
+    /// });
+    /// let found = ctx.accesskit_query(egui::accesskit::Role::Button, "Save");
+    /// assert_eq!(found.len(), 1);
+    /// ```
+    #[cfg(feature = "accesskit")]
+    pub fn accesskit_query(&self, role: accesskit::Role, name: &str) -> Vec<AccessKitMatch> {
+        let mut matches: Vec<AccessKitMatch> = self.write(|ctx| {
+            ctx.viewport()
+                .accesskit_nodes_prev_frame
+                .iter()
+                .filter(|(_, node)| node.role() == role && node.name() == Some(name))
+                .map(|(id, node)| AccessKitMatch {
+                    id: *id,
+                    rect: node.bounds().map_or(Rect::NOTHING, |bounds| {
+                        Rect::from_min_max(
+                            pos2(bounds.x0 as f32, bounds.y0 as f32),
+                            pos2(bounds.x1 as f32, bounds.y1 as f32),
+                        )
+                    }),
+                    node: node.clone(),
+                })
+                .collect()
+        });
+        matches.sort_by(|a, b| {
+            (a.rect.min.y, a.rect.min.x)
+                .partial_cmp(&(b.rect.min.y, b.rect.min.x))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        matches
+    }
+
     /// Return a tree update that the egui integration should provide to the
     /// AccessKit adapter if it cannot immediately run the egui application
     /// to get a full tree update after running [`Context::enable_accesskit`].
@@ -3064,6 +4350,38 @@ impl Context {
         self.read(|ctx| ctx.parent_viewport_id())
     }
 
+    /// Convert a position in the current viewport to global screen coordinates.
+    ///
+    /// Returns `None` if the integration doesn't report where the viewport is on screen.
+    ///
+    /// See also [`ViewportInfo::local_to_screen_pos`].
+    pub fn viewport_to_screen_pos(&self, pos: Pos2) -> Option<Pos2> {
+        self.input(|i| i.viewport().local_to_screen_pos(pos))
+    }
+
+    /// Convert a position in global screen coordinates to the coordinates of the current viewport.
+    ///
+    /// Returns `None` if the integration doesn't report where the viewport is on screen.
+    ///
+    /// See also [`ViewportInfo::screen_to_local_pos`].
+    pub fn screen_to_viewport_pos(&self, screen_pos: Pos2) -> Option<Pos2> {
+        self.input(|i| i.viewport().screen_to_local_pos(screen_pos))
+    }
+
+    /// Convert a position in the coordinates of another viewport to the coordinates of the current viewport.
+    ///
+    /// Returns `None` if the integration doesn't report where the viewports are on screen.
+    pub fn pos_from_viewport(&self, viewport_id: ViewportId, pos: Pos2) -> Option<Pos2> {
+        self.input(|i| {
+            let screen_pos = i
+                .raw
+                .viewports
+                .get(&viewport_id)?
+                .local_to_screen_pos(pos)?;
+            i.viewport().screen_to_local_pos(screen_pos)
+        })
+    }
+
     /// For integrations: Set this to render a sync viewport.
     ///
     /// This will only set the callback for the current thread,
@@ -3119,7 +4437,13 @@ impl Context {
             self.request_repaint_of(self.parent_viewport_id());
         }
 
-        self.write(|ctx| ctx.viewport_for(id).commands.push(command));
+        self.write(|ctx| {
+            let viewport = ctx.viewport_for(id);
+            if let ViewportCommand::RepaintPolicy(policy) = command {
+                viewport.repaint.policy = policy;
+            }
+            viewport.commands.push(command);
+        });
     }
 
     /// Show a deferred viewport, creating a new native window, if possible.
@@ -3258,6 +4582,336 @@ impl Context {
     }
 }
 
+/// ## Input recording
+impl Context {
+    /// Start recording the [`RawInput`] of every frame, see [`Self::stop_input_recording`].
+    ///
+    /// Restarts the recording if already recording.
+    pub fn start_input_recording(&self) {
+        self.write(|ctx| ctx.input_recording = Some(Default::default()));
+    }
+
+    /// Stop recording, and get everything recorded since [`Self::start_input_recording`].
+    ///
+    /// `None` if not recording.
+    pub fn stop_input_recording(&self) -> Option<crate::InputRecording> {
+        self.write(|ctx| ctx.input_recording.take())
+            .map(|(recording, _)| recording)
+    }
+
+    /// Are we between [`Self::start_input_recording`] and [`Self::stop_input_recording`]?
+    pub fn is_recording_input(&self) -> bool {
+        self.read(|ctx| ctx.input_recording.is_some())
+    }
+
+    /// Feed a recording back to egui, one recorded frame per frame, instead of the real input.
+    ///
+    /// The recorded times are kept relative to when the replay starts,
+    /// and we request repaints at the recorded pace, so the replay runs like the original.
+    /// This is deterministic as long as the app behaves the same given the same input.
+    ///
+    /// Replaces any replay already running.
+    ///
+    /// ```
+    /// # let ctx = egui::Context::default();
+    /// # let run_ui = |_ctx: &egui::Context| {};
+    /// ctx.start_input_recording();
+    /// let _ = ctx.run(Default::default(), run_ui);
+    /// let recording = ctx.stop_input_recording().unwrap();
+    ///
+    /// ctx.replay(recording);
+    /// while ctx.is_replaying() {
+    ///     let _ = ctx.run(Default::default(), run_ui);
+    /// }
+    /// ```
+    pub fn replay(&self, recording: crate::InputRecording) {
+        let replay = crate::input_recording::InputReplay::new(recording);
+        self.write(|ctx| ctx.input_replay = (!replay.is_done()).then_some(replay));
+        self.request_repaint();
+    }
+
+    /// Is a [`Self::replay`] still running?
+    pub fn is_replaying(&self) -> bool {
+        self.read(|ctx| ctx.input_replay.is_some())
+    }
+
+    /// Stop a [`Self::replay`] before it is done, going back to the real input.
+    pub fn stop_replay(&self) {
+        self.write(|ctx| ctx.input_replay = None);
+    }
+
+    /// Where [`InputState::time`] comes from, starting with the next frame.
+    ///
+    /// With [`TimeSource::Fixed`] the time, and so all animations, advance by the same step each frame,
+    /// which makes screenshots in tests and captured videos reproducible.
+    ///
+    /// ```
+    /// # let ctx = egui::Context::default();
+    /// // Record a video at 30 fps, however long each frame takes to render:
+    /// ctx.set_time_source(egui::TimeSource::Fixed(1.0 / 30.0));
+    /// ```
+    pub fn set_time_source(&self, time_source: TimeSource) {
+        self.write(|ctx| ctx.time_source = time_source);
+    }
+
+    /// See [`Self::set_time_source`].
+    pub fn time_source(&self) -> TimeSource {
+        self.read(|ctx| ctx.time_source)
+    }
+}
+
+/// ## Time travel
+impl Context {
+    /// Keep the state of the last `frames` frames of the root viewport, so you can step back through them.
+    ///
+    /// Each frame we store a copy of the [`Memory`] and [`InputState`],
+    /// so this is only meant for debugging, e.g. to find out why something flickered once.
+    /// Zero (the default) turns it off.
+    ///
+    /// See [`Self::time_travel_to`] and [`Self::time_travel_ui`].
+    pub fn set_time_travel_capacity(&self, frames: usize) {
+        self.write(|ctx| ctx.time_travel.set_capacity(frames));
+    }
+
+    /// How many frames we can travel back through, see [`Self::set_time_travel_capacity`].
+    pub fn time_travel_frames(&self) -> usize {
+        self.read(|ctx| ctx.time_travel.len())
+    }
+
+    /// Re-run the ui against the state of a recorded frame, where `0` is the oldest one.
+    ///
+    /// Every following frame restores the [`Memory`] and [`InputState`] of that frame,
+    /// so your ui shows exactly what it showed back then, and nothing is recorded.
+    /// State outside of egui (e.g. in your app) is not restored.
+    ///
+    /// While traveling, the real input is ignored, except for the left and right arrow keys
+    /// which step through the frames, and escape which goes back to live.
+    /// Pass `None` to go back to live, where you left off.
+    pub fn time_travel_to(&self, position: Option<usize>) {
+        self.write(|ctx| {
+            let len = ctx.time_travel.len();
+            ctx.time_travel.position = position.filter(|&position| position < len);
+        });
+        self.request_repaint_of(ViewportId::ROOT);
+    }
+
+    /// The recorded frame we are re-running, if any. See [`Self::time_travel_to`].
+    pub fn time_travel_position(&self) -> Option<usize> {
+        self.read(|ctx| ctx.time_travel.position)
+    }
+
+    /// Show controls for time travel, and the input of the frame we are at.
+    ///
+    /// Put this in a [`crate::Window`].
+    pub fn time_travel_ui(&self, ui: &mut Ui) {
+        let (capacity, len, position) = self.read(|ctx| {
+            let time_travel = &ctx.time_travel;
+            let position = time_travel.position.and_then(|position| {
+                let (frame_nr, description) = time_travel.describe(position)?;
+                Some((position, frame_nr, description))
+            });
+            (time_travel.capacity, time_travel.len(), position)
+        });
+        crate::time_travel::time_travel_ui(self, ui, capacity, len, position);
+    }
+}
+
+/// ## Session restore
+impl Context {
+    /// Collect all live viewports into a [`ViewportSession`], e.g. before shutting down.
+    ///
+    /// For each viewport, `app_state` is called to get the application state to store with it,
+    /// which you will be given back in [`Self::restore_session`].
+    ///
+    /// The position and size of each viewport is taken from the latest
+    /// [`crate::ViewportInfo`] given by the integration.
+    ///
+    /// No lock is held while `app_state` is called.
+    pub fn save_session(
+        &self,
+        mut app_state: impl FnMut(ViewportId) -> Vec<u8>,
+    ) -> ViewportSession {
+        crate::profile_function!();
+
+        let mut entries: Vec<(usize, ViewportSessionEntry)> = self.read(|ctx| {
+            let depth_of = |mut id: ViewportId| {
+                let mut depth = 0;
+                while id != ViewportId::ROOT && depth <= ctx.viewport_parents.len() {
+                    id = ctx.viewport_parents.get(&id).copied().unwrap_or_default();
+                    depth += 1;
+                }
+                depth
+            };
+
+            let live_ids = std::iter::once(ViewportId::ROOT).chain(
+                ctx.viewport_parents
+                    .keys()
+                    .copied()
+                    .filter(|&id| id != ViewportId::ROOT && ctx.viewports.contains_key(&id)),
+            );
+
+            live_ids
+                .map(|id| {
+                    let viewport = ctx.viewports.get(&id);
+                    let mut builder = viewport.map(|v| v.builder.clone()).unwrap_or_default();
+                    if let Some(info) = viewport.and_then(|v| v.input.raw.viewports.get(&id)) {
+                        if let Some(outer_rect) = info.outer_rect {
+                            builder.position = Some(outer_rect.min);
+                        }
+                        if let Some(inner_rect) = info.inner_rect {
+                            builder.inner_size = Some(inner_rect.size());
+                        }
+                        builder.maximized = info.maximized.or(builder.maximized);
+                        builder.fullscreen = info.fullscreen.or(builder.fullscreen);
+                    }
+
+                    let entry = ViewportSessionEntry {
+                        id,
+                        parent: ctx.viewport_parents.get(&id).copied().unwrap_or_default(),
+                        class: viewport.map(|v| v.class).unwrap_or_default(),
+                        builder,
+                        app_state: Default::default(),
+                    };
+                    (depth_of(id), entry)
+                })
+                .collect()
+        });
+
+        // Parents before children, and deterministic:
+        entries.sort_by_key(|(depth, entry)| (*depth, entry.id.0.value()));
+
+        ViewportSession {
+            viewports: entries
+                .into_iter()
+                .map(|(_, mut entry)| {
+                    entry.app_state = app_state(entry.id);
+                    entry
+                })
+                .collect(),
+        }
+    }
+
+    /// Recreate the viewports of a [`ViewportSession`] saved with [`Self::save_session`].
+    ///
+    /// `restore_ui` is called once for each non-root viewport with its id and
+    /// [`ViewportSessionEntry::app_state`], and should return the ui callback for it,
+    /// or `None` to not restore that viewport.
+    ///
+    /// Each restored viewport is then shown as a deferred viewport (see [`Self::show_viewport_deferred`])
+    /// by its parent every frame, until the user closes it
+    /// or you call [`Self::forget_restored_viewport`].
+    /// A viewport whose parent is not restored is not restored either.
+    ///
+    /// The root viewport always exists, so it is not recreated,
+    /// but it is moved and resized to where it was when the session was saved.
+    /// Its application state can be read with [`ViewportSession::get`].
+    pub fn restore_session(
+        &self,
+        session: ViewportSession,
+        mut restore_ui: impl FnMut(ViewportId, &[u8]) -> Option<Arc<RestoredViewportUiCallback>>,
+    ) {
+        crate::profile_function!();
+
+        let mut restored_ids: ViewportIdSet = std::iter::once(ViewportId::ROOT).collect();
+
+        for entry in session.viewports {
+            let ViewportSessionEntry {
+                id,
+                parent,
+                class: _,
+                builder,
+                app_state,
+            } = entry;
+
+            if id == ViewportId::ROOT {
+                if let Some(position) = builder.position {
+                    self.send_viewport_cmd_to(id, ViewportCommand::OuterPosition(position));
+                }
+                if let Some(inner_size) = builder.inner_size {
+                    self.send_viewport_cmd_to(id, ViewportCommand::InnerSize(inner_size));
+                }
+                continue;
+            }
+
+            if !restored_ids.contains(&parent) {
+                continue;
+            }
+
+            if let Some(viewport_ui_cb) = restore_ui(id, &app_state) {
+                restored_ids.insert(id);
+                self.write(|ctx| {
+                    ctx.restored_viewports.retain(|v| v.id != id);
+                    ctx.restored_viewports.push(RestoredViewport {
+                        id,
+                        parent,
+                        builder,
+                        viewport_ui_cb,
+                    });
+                });
+            }
+        }
+
+        self.request_repaint_of(ViewportId::ROOT);
+    }
+
+    /// The ids of all viewports restored by [`Self::restore_session`] that are still open.
+    pub fn restored_viewport_ids(&self) -> Vec<ViewportId> {
+        self.read(|ctx| ctx.restored_viewports.iter().map(|v| v.id).collect())
+    }
+
+    /// Stop showing a viewport restored by [`Self::restore_session`], closing it.
+    ///
+    /// This also forgets all restored viewports that are children of it.
+    pub fn forget_restored_viewport(&self, id: ViewportId) {
+        self.write(|ctx| {
+            let mut forgotten: ViewportIdSet = std::iter::once(id).collect();
+            ctx.restored_viewports.retain(|v| {
+                if forgotten.contains(&v.id) || forgotten.contains(&v.parent) {
+                    forgotten.insert(v.id);
+                    false
+                } else {
+                    true
+                }
+            });
+        });
+    }
+
+    /// Show the restored viewports that are children of the current viewport.
+    fn show_restored_viewports(&self) {
+        let viewport_id = self.viewport_id();
+        let restored: Vec<RestoredViewport> = self.read(|ctx| {
+            ctx.restored_viewports
+                .iter()
+                .filter(|v| v.parent == viewport_id)
+                .cloned()
+                .collect()
+        });
+
+        for RestoredViewport {
+            id,
+            parent: _,
+            builder,
+            viewport_ui_cb,
+        } in restored
+        {
+            let close_requested = self.input(|i| {
+                i.raw
+                    .viewports
+                    .get(&id)
+                    .map_or(false, |info| info.close_requested())
+            });
+            if close_requested {
+                self.forget_restored_viewport(id);
+                continue;
+            }
+
+            self.show_viewport_deferred(id, builder, move |ctx, class| {
+                (viewport_ui_cb)(ctx, class);
+            });
+        }
+    }
+}
+
 /// ## Interaction
 impl Context {
     /// Read you what widgets are currently being interacted with.
@@ -3265,6 +4919,38 @@ impl Context {
         self.write(|w| reader(&w.viewport().interact_widgets))
     }
 
+    /// What widgets the given pointer is interacting with this frame.
+    ///
+    /// [`PointerId::PRIMARY`] gives the same as [`Self::interaction_snapshot`].
+    /// Returns `None` for pointers that are not in [`InputState::extra_pointers`].
+    ///
+    /// See also [`Response::hovered_by_pointer`] and [`Response::dragged_by_pointer`].
+    pub fn pointer_interaction_snapshot<R>(
+        &self,
+        pointer: PointerId,
+        reader: impl FnOnce(&InteractionSnapshot) -> R,
+    ) -> Option<R> {
+        self.write(|w| {
+            let viewport = w.viewport();
+            if pointer == PointerId::PRIMARY {
+                Some(reader(&viewport.interact_widgets))
+            } else {
+                viewport
+                    .extra_pointer_interactions
+                    .get(&pointer)
+                    .map(|(_, snapshot)| reader(snapshot))
+            }
+        })
+    }
+
+    /// The widget the given pointer is currently dragging, if any.
+    ///
+    /// See [`Self::dragged_id`].
+    pub fn pointer_dragged_id(&self, pointer: PointerId) -> Option<Id> {
+        self.pointer_interaction_snapshot(pointer, |i| i.dragged)
+            .flatten()
+    }
+
     /// The widget currently being dragged, if any.
     ///
     /// For widgets that sense both clicks and drags, this will
@@ -3342,3 +5028,667 @@ fn context_impl_send_sync() {
     fn assert_send_sync<T: Send + Sync>() {}
     assert_send_sync::<Context>();
 }
+
+#[test]
+fn session_save_and_restore() {
+    let child_id = ViewportId::from_hash_of("child");
+
+    let ctx = Context::default();
+    ctx.set_embed_viewports(false);
+    let _ = ctx.run(Default::default(), |ctx| {
+        ctx.show_viewport_deferred(
+            child_id,
+            ViewportBuilder::default().with_title("Child"),
+            |_, _| {},
+        );
+    });
+
+    let session = ctx.save_session(|id| {
+        if id == child_id {
+            b"child".to_vec()
+        } else {
+            vec![]
+        }
+    });
+    assert_eq!(session.viewports.len(), 2);
+    assert_eq!(session.viewports[0].id, ViewportId::ROOT);
+    let child = session.get(child_id).unwrap();
+    assert_eq!(child.parent, ViewportId::ROOT);
+    assert_eq!(child.app_state, b"child");
+    assert_eq!(child.builder.title.as_deref(), Some("Child"));
+
+    let ctx = Context::default();
+    ctx.set_embed_viewports(false);
+    ctx.restore_session(session, |id, app_state| {
+        assert_eq!(id, child_id);
+        assert_eq!(app_state, b"child");
+        Some(Arc::new(|_, _| {}))
+    });
+    assert_eq!(ctx.restored_viewport_ids(), vec![child_id]);
+
+    let output = ctx.run(Default::default(), |_| {});
+    assert!(output.viewport_output.contains_key(&child_id));
+
+    ctx.forget_restored_viewport(child_id);
+    let output = ctx.run(Default::default(), |_| {});
+    assert!(!output.viewport_output.contains_key(&child_id));
+}
+
+#[test]
+fn frame_phase_callbacks() {
+    use crate::mutex::Mutex;
+
+    let phases = Arc::new(Mutex::new(Vec::new()));
+    let push = |phases: &Arc<Mutex<Vec<&'static str>>>, phase: &'static str| {
+        let phases = phases.clone();
+        Arc::new(move |_ctx: &Context| phases.lock().push(phase))
+    };
+
+    let ctx = Context::default();
+    ctx.on_begin_frame("test", push(&phases, "begin_frame"));
+    ctx.on_after_input("test", push(&phases, "after_input"));
+    ctx.on_end_frame("test", push(&phases, "end_frame"));
+    ctx.on_before_tessellate("test", {
+        let phases = phases.clone();
+        Arc::new(move |_ctx, shapes, _pixels_per_point| {
+            phases.lock().push("before_tessellate");
+            shapes.clear();
+        })
+    });
+
+    let output = ctx.run(Default::default(), |_ctx| phases.lock().push("ui"));
+    let primitives = ctx.tessellate(output.shapes, output.pixels_per_point);
+    assert!(primitives.is_empty());
+
+    assert_eq!(
+        *phases.lock(),
+        [
+            "begin_frame",
+            "after_input",
+            "ui",
+            "end_frame",
+            "before_tessellate"
+        ]
+    );
+}
+
+#[test]
+fn shape_mappers() {
+    let ctx = Context::default();
+    let run = |ctx: &Context| {
+        let output = ctx.run(Default::default(), |ctx| {
+            CentralPanel::default().show(ctx, |ui| {
+                ui.label("Hello");
+            });
+        });
+        ctx.tessellate(output.shapes, output.pixels_per_point)
+    };
+    assert!(!run(&ctx).is_empty());
+
+    ctx.add_shape_mapper("test", Arc::new(|shapes| shapes.truncate(1)));
+    assert_eq!(run(&ctx).len(), 1);
+
+    // Same name replaces the old mapper:
+    ctx.add_shape_mapper("test", Arc::new(|shapes| shapes.clear()));
+    assert!(run(&ctx).is_empty());
+
+    assert!(ctx.remove_shape_mapper("test"));
+    assert!(!ctx.remove_shape_mapper("test"));
+    assert!(!run(&ctx).is_empty());
+}
+
+#[cfg(feature = "accesskit")]
+#[test]
+fn accesskit_text_widgets() {
+    let ctx = Context::default();
+    ctx.enable_accesskit();
+    let mut text = String::new();
+    let _ = ctx.run(Default::default(), |ctx| {
+        CentralPanel::default().show(ctx, |ui| {
+            ui.label("Saved").accesskit_live_region();
+            ui.add(TextEdit::singleline(&mut text).hint_text("Name"));
+            ui.add(DragValue::new(&mut 42.0).suffix(" m"));
+        });
+    });
+
+    let status = ctx.accesskit_query(accesskit::Role::StaticText, "Saved");
+    assert_eq!(status[0].node.live(), Some(accesskit::Live::Polite));
+
+    let nodes = ctx.read(|ctx| {
+        ctx.viewports[&ViewportId::ROOT]
+            .accesskit_nodes_prev_frame
+            .clone()
+    });
+    let text_edit = nodes
+        .values()
+        .find(|node| node.role() == accesskit::Role::TextInput)
+        .unwrap();
+    assert_eq!(text_edit.placeholder(), Some("Name"));
+    let drag_value = nodes
+        .values()
+        .find(|node| node.role() == accesskit::Role::SpinButton)
+        .unwrap();
+    assert_eq!(drag_value.value(), Some("42 m"));
+}
+
+#[cfg(feature = "accesskit")]
+#[test]
+fn accesskit_drag_value_exposes_the_text_being_edited() {
+    use crate::test_harness::Harness;
+
+    for in_slider in [false, true] {
+        let mut value = 42.0;
+        let mut harness = Harness::new_ui(|ui| {
+            if in_slider {
+                ui.add(Slider::new(&mut value, 0.0..=100.0).suffix(" m"));
+            } else {
+                ui.add(DragValue::new(&mut value).suffix(" m"));
+            }
+        });
+        harness.ctx().enable_accesskit();
+        let spin_button_value = |harness: &Harness<'_>| {
+            harness.ctx().read(|ctx| {
+                ctx.viewports[&ViewportId::ROOT]
+                    .accesskit_nodes_prev_frame
+                    .values()
+                    .find(|node| node.role() == accesskit::Role::SpinButton)
+                    .and_then(|node| node.value().map(ToOwned::to_owned))
+            })
+        };
+
+        harness
+            .get_by(|info| info.typ == WidgetType::DragValue)
+            .click();
+        harness.type_text("7");
+        assert_eq!(
+            spin_button_value(&harness).as_deref(),
+            Some("7"),
+            "While editing, the screen reader should get what the user typed (in_slider: {in_slider})"
+        );
+
+        harness.press_key(Key::Enter);
+        assert_eq!(spin_button_value(&harness).as_deref(), Some("7 m"));
+        drop(harness);
+        assert_eq!(value, 7.0);
+    }
+}
+
+#[test]
+fn test_inner_size_to_content() {
+    let ctx = Context::default();
+    let run = |text: &str| {
+        let output = ctx.run(Default::default(), |ctx| {
+            CentralPanel::default().show(ctx, |ui| {
+                ui.label(text);
+            });
+        });
+        output.viewport_output[&ViewportId::ROOT].commands.clone()
+    };
+    let inner_sizes = |commands: Vec<ViewportCommand>| -> Vec<Vec2> {
+        commands
+            .into_iter()
+            .filter_map(|command| match command {
+                ViewportCommand::InnerSize(size) => Some(size),
+                _ => None,
+            })
+            .collect()
+    };
+
+    // Nothing has been shown yet, so the command waits for the contents:
+    ctx.send_viewport_cmd(ViewportCommand::InnerSizeToContent);
+    let output = ctx.run(Default::default(), |_ctx| {});
+    assert!(output.viewport_output[&ViewportId::ROOT]
+        .commands
+        .is_empty());
+    assert_eq!(ctx.content_size(), None);
+    let commands = run("Hello");
+    let content_size = ctx.content_size().unwrap();
+    assert_eq!(inner_sizes(commands), vec![content_size]);
+
+    assert!(run("Hello").is_empty());
+    assert!(content_size.x < ctx.screen_rect().width());
+
+    ctx.send_viewport_cmd(ViewportCommand::InnerSizeToContent);
+    assert_eq!(inner_sizes(run("Hello")), vec![content_size]);
+
+    // Keep fitting the contents as they change:
+    ctx.write(|ctx| {
+        ctx.viewport_for(ViewportId::ROOT)
+            .builder
+            .inner_size_to_content = Some(true);
+    });
+    assert_eq!(inner_sizes(run("Hello")), vec![content_size]);
+    assert!(run("Hello").is_empty());
+    let sizes = inner_sizes(run("Hello, world!"));
+    assert_eq!(sizes.len(), 1);
+    assert!(content_size.x < sizes[0].x);
+}
+
+#[test]
+fn test_request_repaint_rect() {
+    use crate::mutex::Mutex;
+
+    let ctx = Context::default();
+    let infos = Arc::new(Mutex::new(Vec::new()));
+    ctx.set_request_repaint_callback({
+        let infos = infos.clone();
+        move |info| infos.lock().push(info)
+    });
+    let run = |add_contents: &dyn Fn(&Context)| {
+        let _ = ctx.run(Default::default(), |ctx| add_contents(ctx));
+        infos
+            .lock()
+            .drain(..)
+            .map(|info| info.rect)
+            .collect::<Vec<_>>()
+    };
+    let small = Rect::from_min_size(pos2(10.0, 10.0), vec2(2.0, 16.0));
+    let other = Rect::from_min_size(pos2(100.0, 10.0), vec2(2.0, 16.0));
+
+    // Settle the startup repaints:
+    for _ in 0..3 {
+        run(&|_| {});
+    }
+
+    assert_eq!(
+        run(&|ctx| ctx.request_repaint_rect_after(Duration::from_millis(500), small)),
+        vec![Some(small)]
+    );
+
+    // The rect grows with more requests in the same frame:
+    assert_eq!(
+        run(&|ctx| {
+            ctx.request_repaint_rect_after(Duration::from_millis(500), small);
+            ctx.request_repaint_rect_after(Duration::from_millis(500), other);
+        }),
+        vec![Some(small), Some(small.union(other))]
+    );
+
+    // Anything else needs a full repaint:
+    assert_eq!(
+        run(&|ctx| {
+            ctx.request_repaint_rect_after(Duration::from_millis(500), small);
+            ctx.request_repaint_after(Duration::from_millis(100));
+        }),
+        vec![Some(small), None]
+    );
+}
+
+#[test]
+fn test_animation_options() {
+    let ctx = Context::default();
+    let run = |time: f64, add_contents: &dyn Fn(&Context) -> f32| {
+        let mut value = 0.0;
+        let output = ctx.run(
+            RawInput {
+                time: Some(time),
+                predicted_dt: 0.0,
+                ..Default::default()
+            },
+            |ctx| value = add_contents(ctx),
+        );
+        let repaint = output.viewport_output[&ViewportId::ROOT]
+            .repaint_delay
+            .is_zero();
+        (value, repaint)
+    };
+    let id = Id::new("value");
+    let squared = AnimationOptions::default()
+        .with_duration(1.0)
+        .with_easing(|t| t * t);
+
+    run(0.0, &|ctx| ctx.animate_value_with_options(id, 0.0, squared));
+    run(0.0, &|ctx| ctx.animate_value_with_options(id, 1.0, squared));
+    let (value, repaint) = run(0.5, &|ctx| ctx.animate_value_with_options(id, 1.0, squared));
+    assert_eq!(value, 0.25);
+    assert!(repaint);
+
+    // A single animation can opt out:
+    let (value, _) = run(0.6, &|ctx| {
+        ctx.animate_value_with_options(id, 2.0, AnimationOptions::DISABLED)
+    });
+    assert_eq!(value, 2.0);
+
+    // All animations can be turned off:
+    let id = Id::new("bool");
+    run(1.0, &|ctx| ctx.animate_bool(id, false));
+    ctx.options_mut(|options| options.animations_enabled = false);
+    for _ in 0..3 {
+        run(2.0, &|_| 0.0); // settle
+    }
+    assert_eq!(run(2.0, &|ctx| ctx.animate_bool(id, true)), (1.0, false));
+    assert_eq!(
+        run(2.0, &|ctx| ctx.animate_value_with_time(id, 5.0, 1.0)),
+        (5.0, false)
+    );
+}
+
+#[cfg(feature = "tracing")]
+#[test]
+fn test_tracing_spans() {
+    use epaint::mutex::Mutex;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use tracing::{span, Event, Metadata, Subscriber};
+
+    /// Records the names of all spans that are created.
+    #[derive(Default)]
+    struct SpanNames {
+        next_id: AtomicU64,
+        names: Mutex<Vec<&'static str>>,
+    }
+
+    impl Subscriber for &'static SpanNames {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &span::Attributes<'_>) -> span::Id {
+            self.names.lock().push(span.metadata().name());
+            span::Id::from_u64(self.next_id.fetch_add(1, Ordering::Relaxed) + 1)
+        }
+
+        fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+
+        fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+
+        fn event(&self, _: &Event<'_>) {}
+
+        fn enter(&self, _: &span::Id) {}
+
+        fn exit(&self, _: &span::Id) {}
+    }
+
+    let spans: &'static SpanNames = Box::leak(Box::default());
+    tracing::subscriber::with_default(spans, || {
+        let ctx = Context::default();
+        let output = ctx.run(RawInput::default(), |ctx| {
+            crate::CentralPanel::default().show(ctx, |ui| {
+                ui.add(crate::Label::new("Hello"));
+            });
+        });
+        ctx.tessellate(output.shapes, output.pixels_per_point);
+    });
+
+    let names = spans.names.lock().clone();
+    for name in [
+        "egui::begin_frame",
+        "egui::interaction",
+        "egui::run_ui",
+        "epaint::layout",
+        "epaint::tessellate",
+    ] {
+        assert!(names.contains(&name), "missing span {name:?} in {names:?}");
+    }
+    if cfg!(debug_assertions) {
+        assert!(names.contains(&"egui::widget"));
+    }
+}
+
+#[test]
+fn test_overlay_painter() {
+    use crate::test_harness::Harness;
+    use std::cell::Cell;
+
+    let hovered = Cell::new(false);
+    let mut harness = Harness::new_ui(|ui| {
+        hovered.set(ui.button("Under the overlay").hovered());
+        ui.ctx().overlay_painter().rect_filled(
+            ui.ctx().screen_rect(),
+            0.0,
+            crate::Color32::from_black_alpha(100),
+        );
+    });
+    harness.run();
+
+    // The overlay covers everything, but doesn't take the hover:
+    harness.get_by_label("Under the overlay").hover();
+    assert!(hovered.get());
+    assert_eq!(
+        harness.ctx().layer_id_at(pos2(10.0, 10.0)),
+        Some(LayerId::background())
+    );
+}
+
+#[cfg(debug_assertions)]
+#[test]
+fn test_snap_to_widget_edges() {
+    let mut widgets = WidgetRects::default();
+    let rect = Rect::from_min_max(pos2(10.0, 10.0), pos2(50.0, 30.0));
+    widgets.insert(
+        LayerId::background(),
+        WidgetRect {
+            id: Id::new("widget"),
+            layer_id: LayerId::background(),
+            rect,
+            interact_rect: rect,
+            sense: Sense::hover(),
+            enabled: true,
+        },
+    );
+
+    assert_eq!(
+        snap_to_widget_edges(&widgets, pos2(53.0, 20.0), 6.0),
+        pos2(50.0, 20.0)
+    );
+    assert_eq!(
+        snap_to_widget_edges(&widgets, pos2(48.0, 34.0), 6.0),
+        pos2(50.0, 30.0)
+    );
+    assert_eq!(
+        snap_to_widget_edges(&widgets, pos2(80.0, 20.0), 6.0),
+        pos2(80.0, 20.0)
+    );
+}
+
+#[test]
+fn test_fixed_time_source() {
+    use crate::test_harness::Harness;
+
+    let mut harness = Harness::new(|ctx| {
+        CentralPanel::default().show(ctx, |_ui| {});
+    });
+    harness.ctx().set_time_source(TimeSource::Fixed(0.25));
+    harness.step();
+    let start = harness.ctx().input(|i| i.time);
+    for _ in 0..4 {
+        harness.step();
+    }
+    harness.ctx().input(|i| {
+        assert_eq!(i.time, start + 1.0);
+        assert_eq!(i.unstable_dt, 0.25);
+        assert_eq!(i.stable_dt, 0.25);
+    });
+
+    // Animations take the same number of frames every time:
+    let id = Id::new("anim");
+    let ctx = harness.ctx().clone();
+    assert_eq!(ctx.animate_bool_with_time(id, false, 1.0), 0.0);
+    harness.step();
+    ctx.animate_bool_with_time(id, true, 1.0);
+    harness.step();
+    harness.step();
+    assert_eq!(ctx.animate_bool_with_time(id, true, 1.0), 0.5);
+}
+
+#[test]
+fn test_missing_glyphs() {
+    use crate::test_harness::Harness;
+
+    // Ethiopic, which none of the default fonts cover:
+    let mut harness = Harness::new_ui(|ui| {
+        ui.label("Hello \u{1200} world");
+    });
+    harness.run();
+    let missing = harness.ctx().missing_glyphs();
+    assert_eq!(missing.into_iter().collect::<Vec<_>>(), vec!['\u{1200}']);
+
+    let mut harness = Harness::new_ui(|ui| {
+        ui.label("Hello world");
+    });
+    harness.run();
+    assert!(harness.ctx().missing_glyphs().is_empty());
+}
+
+#[test]
+fn test_repaint_cause_counts() {
+    use crate::test_harness::Harness;
+
+    let id = Id::new("spinning");
+    let mut harness = Harness::new_ui(|ui| {
+        let response = ui.label("Spinning");
+        response.request_repaint();
+        for _ in 0..2 {
+            ui.ctx().request_repaint_from(id);
+        }
+    });
+    harness.ctx().clear_repaint_cause_counts();
+    harness.step();
+    harness.step();
+
+    let counts = harness.ctx().repaint_cause_counts();
+    assert_eq!(counts[0].0.widget(), Some(id));
+    assert_eq!(counts[0].1, 4);
+    assert!(counts[0].0.file.ends_with("context.rs"));
+    assert_eq!(counts[1].1, 2);
+    assert!(harness
+        .ctx()
+        .repaint_causes()
+        .iter()
+        .any(|cause| cause.widget() == Some(id)));
+}
+
+#[test]
+fn test_widget_repaint_causes() {
+    use crate::test_harness::Harness;
+    use std::cell::Cell;
+
+    let progress_bar_id = Cell::new(Id::NULL);
+    let mut harness = Harness::new_ui(|ui| {
+        let response = ui.add(ProgressBar::new(0.5).animate(true));
+        progress_bar_id.set(response.id);
+    });
+    harness.step();
+
+    // The built-in widgets say who keeps repainting:
+    assert!(harness
+        .ctx()
+        .repaint_causes()
+        .iter()
+        .any(|cause| cause.widget() == Some(progress_bar_id.get())));
+}
+
+#[test]
+fn test_max_idle_fps() {
+    use crate::test_harness::Harness;
+    use std::cell::Cell;
+
+    let animate = Cell::new(false);
+    let mut harness = Harness::new(|ctx| {
+        CentralPanel::default().show(ctx, |_ui| {});
+        ctx.request_repaint();
+        ctx.animate_bool(Id::new("anim"), animate.get());
+    });
+    let delay =
+        |harness: &Harness<'_>| harness.output().viewport_output[&ViewportId::ROOT].repaint_delay;
+
+    harness.step();
+    assert_eq!(delay(&harness), Duration::ZERO);
+
+    // Without input, we repaint at most 10 times per second
+    // (after the extra frame of the last immediate repaint):
+    harness.ctx().set_max_idle_fps(Some(10.0));
+    harness.step();
+    harness.step();
+    assert_eq!(delay(&harness), Duration::from_secs_f32(0.1));
+
+    // But not while there is input:
+    harness.push_event(Event::PointerMoved(pos2(10.0, 10.0)));
+    harness.step();
+    assert_eq!(delay(&harness), Duration::ZERO);
+    harness.step();
+    harness.step();
+    assert_eq!(delay(&harness), Duration::from_secs_f32(0.1));
+
+    // Nor while animating:
+    animate.set(true);
+    harness.step();
+    assert_eq!(delay(&harness), Duration::ZERO);
+}
+
+#[test]
+fn test_max_idle_fps_keeps_scrolling_smooth() {
+    use crate::test_harness::Harness;
+    use std::cell::Cell;
+
+    let offset = Cell::new(Vec2::ZERO);
+    let fling = |max_idle_fps| {
+        let mut harness = Harness::new_ui(|ui| {
+            let output = ScrollArea::vertical().max_height(200.0).show(ui, |ui| {
+                ui.allocate_space(vec2(100.0, 10_000.0));
+            });
+            offset.set(output.state.offset);
+        });
+        harness.ctx().set_max_idle_fps(max_idle_fps);
+        harness.run();
+        // Runs until egui stops asking for immediate repaints:
+        harness.drag(pos2(50.0, 150.0), pos2(50.0, 50.0));
+        offset.get()
+    };
+
+    // The kinetic scroll after letting go isn't cut short:
+    let unthrottled = fling(None);
+    assert!(100.0 < unthrottled.y, "{unthrottled:?}");
+    assert_eq!(fling(Some(10.0)), unthrottled);
+
+    // Nor is smooth scrolling with the mouse wheel:
+    let mut harness = Harness::new_ui(|ui| {
+        ScrollArea::vertical().max_height(200.0).show(ui, |ui| {
+            ui.allocate_space(vec2(100.0, 10_000.0));
+        });
+    });
+    harness.ctx().set_max_idle_fps(Some(10.0));
+    harness.hover(pos2(50.0, 50.0));
+    harness.push_event(Event::Scroll(vec2(0.0, -500.0)));
+    harness.step();
+    harness.step();
+    assert_eq!(
+        harness.output().viewport_output[&ViewportId::ROOT].repaint_delay,
+        Duration::ZERO
+    );
+}
+
+#[test]
+fn test_repaint_policy() {
+    use crate::test_harness::Harness;
+    use std::cell::Cell;
+
+    let request_repaint = Cell::new(false);
+    let mut harness = Harness::new(|ctx| {
+        CentralPanel::default().show(ctx, |_ui| {});
+        if request_repaint.get() {
+            ctx.request_repaint();
+        }
+    });
+    let delay =
+        |harness: &Harness<'_>| harness.output().viewport_output[&ViewportId::ROOT].repaint_delay;
+    harness.run();
+    assert_eq!(delay(&harness), Duration::MAX);
+
+    // Continuous repaints without being asked to:
+    let ctx = harness.ctx().clone();
+    ctx.send_viewport_cmd(ViewportCommand::RepaintPolicy(RepaintPolicy::Continuous));
+    harness.step();
+    harness.step();
+    assert_eq!(delay(&harness), Duration::ZERO);
+
+    // Throttled limits the requests:
+    ctx.send_viewport_cmd(ViewportCommand::RepaintPolicy(RepaintPolicy::Throttled(20)));
+    request_repaint.set(true);
+    harness.step();
+    harness.step();
+    assert_eq!(delay(&harness), Duration::from_secs_f32(0.05));
+
+    ctx.send_viewport_cmd(ViewportCommand::RepaintPolicy(RepaintPolicy::Reactive));
+    harness.step();
+    assert_eq!(delay(&harness), Duration::ZERO);
+}
@@ -1,6 +1,6 @@
 //! The input needed by egui.
 
-use epaint::ColorImage;
+use epaint::{Color32, ColorImage};
 
 use crate::{emath::*, Key, ViewportId, ViewportIdMap};
 
@@ -73,6 +73,19 @@ pub struct RawInput {
     ///
     /// False when the user alt-tab away from the application, for instance.
     pub focused: bool,
+
+    /// The theme of the operating system, if known.
+    ///
+    /// `egui-winit` fills this in from the window, and `eframe` on web from `prefers-color-scheme`.
+    /// `None` will be treated as "same as last frame".
+    /// Used when [`crate::Options::theme_preference`] is [`crate::ThemePreference::System`].
+    pub system_theme: Option<crate::Theme>,
+
+    /// Can the integration let the user pick a color from the screen?
+    ///
+    /// If so, the color pickers show an eyedropper button,
+    /// see [`crate::PlatformOutput::request_eyedropper`].
+    pub eyedropper: bool,
 }
 
 impl Default for RawInput {
@@ -89,6 +102,8 @@ impl Default for RawInput {
             hovered_files: Default::default(),
             dropped_files: Default::default(),
             focused: true, // integrations opt into global focus tracking
+            system_theme: None,
+            eyedropper: false,
         }
     }
 }
@@ -117,6 +132,8 @@ impl RawInput {
             hovered_files: self.hovered_files.clone(),
             dropped_files: std::mem::take(&mut self.dropped_files),
             focused: self.focused,
+            system_theme: self.system_theme,
+            eyedropper: self.eyedropper,
         }
     }
 
@@ -134,6 +151,8 @@ impl RawInput {
             mut hovered_files,
             mut dropped_files,
             focused,
+            system_theme,
+            eyedropper,
         } = newer;
 
         self.viewport_id = viewport_ids;
@@ -147,6 +166,8 @@ impl RawInput {
         self.hovered_files.append(&mut hovered_files);
         self.dropped_files.append(&mut dropped_files);
         self.focused = focused;
+        self.system_theme = system_theme.or(self.system_theme);
+        self.eyedropper = eyedropper;
     }
 }
 
@@ -235,6 +256,45 @@ impl ViewportInfo {
             .any(|&event| event == ViewportEvent::Close)
     }
 
+    /// Convert a position in the coordinates of this viewport to global screen coordinates.
+    ///
+    /// This uses [`Self::inner_rect`], so returns `None` if the position of the viewport is unknown.
+    pub fn local_to_screen_pos(&self, pos: Pos2) -> Option<Pos2> {
+        let inner_rect = self.inner_rect?;
+        Some(inner_rect.min + pos.to_vec2())
+    }
+
+    /// Convert a position in global screen coordinates to the coordinates of this viewport.
+    ///
+    /// This uses [`Self::inner_rect`], so returns `None` if the position of the viewport is unknown.
+    pub fn screen_to_local_pos(&self, screen_pos: Pos2) -> Option<Pos2> {
+        let inner_rect = self.inner_rect?;
+        Some(screen_pos - inner_rect.min.to_vec2())
+    }
+
+    /// Convert a rectangle in the coordinates of this viewport to global screen coordinates.
+    ///
+    /// See [`Self::local_to_screen_pos`].
+    pub fn local_to_screen_rect(&self, rect: Rect) -> Option<Rect> {
+        let inner_rect = self.inner_rect?;
+        Some(rect.translate(inner_rect.min.to_vec2()))
+    }
+
+    /// Convert a rectangle in global screen coordinates to the coordinates of this viewport.
+    ///
+    /// See [`Self::screen_to_local_pos`].
+    pub fn screen_to_local_rect(&self, rect: Rect) -> Option<Rect> {
+        let inner_rect = self.inner_rect?;
+        Some(rect.translate(-inner_rect.min.to_vec2()))
+    }
+
+    /// The [`Self::outer_rect`] in the coordinates of this viewport.
+    ///
+    /// The top-left corner is usually negative, since the decorations are outside the contents.
+    pub fn local_outer_rect(&self) -> Option<Rect> {
+        self.screen_to_local_rect(self.outer_rect?)
+    }
+
     pub fn ui(&self, ui: &mut crate::Ui) {
         let Self {
             parent,
@@ -339,6 +399,21 @@ pub struct DroppedFile {
     pub bytes: Option<std::sync::Arc<[u8]>>,
 }
 
+/// Where the text of an [`Event::Paste`] came from.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum PasteSource {
+    /// The system clipboard, e.g. from Cmd+V or a "Paste" menu item.
+    #[default]
+    Clipboard,
+
+    /// The primary selection, e.g. from a middle-click on Linux.
+    PrimarySelection,
+
+    /// Something else, e.g. text dropped onto the window.
+    Other,
+}
+
 /// An input event generated by the integration.
 ///
 /// This only covers events that egui cares about.
@@ -352,7 +427,16 @@ pub enum Event {
     Cut,
 
     /// The integration detected a "paste" event (e.g. Cmd+V).
-    Paste(String),
+    ///
+    /// Unlike [`Self::Text`], this is inserted as a whole,
+    /// e.g. as a single undo step in a [`crate::TextEdit`].
+    Paste {
+        /// The text that was pasted.
+        text: String,
+
+        /// Where the text came from.
+        source: PasteSource,
+    },
 
     /// Text input, e.g. via keyboard.
     ///
@@ -424,6 +508,20 @@ pub enum Event {
     /// On touch-up first send `PointerButton{pressed: false, …}` followed by `PointerLeft`.
     PointerGone,
 
+    /// A [`Self::PointerMoved`], [`Self::PointerButton`] or [`Self::PointerGone`]
+    /// of another pointer than the primary one, e.g. a second mouse,
+    /// or the finger of another user of a touch table.
+    ///
+    /// The primary pointer keeps using the plain events.
+    /// Each extra pointer is tracked in [`crate::InputState::extra_pointers`].
+    ExtraPointer {
+        /// Which pointer moved. Events for [`PointerId::PRIMARY`] are ignored.
+        id: PointerId,
+
+        /// The pointer event.
+        event: Box<Event>,
+    },
+
     /// How many points (logical pixels) the user scrolled.
     ///
     /// The direction of the vector indicates how to move the _content_ that is being viewed.
@@ -445,6 +543,18 @@ pub enum Event {
     /// * `zoom > 1`: pinch spread
     Zoom(f32),
 
+    /// Rotation in radians this frame, e.g. from a two-finger rotate gesture on a trackpad.
+    ///
+    /// Positive is clockwise, like [`crate::MultiTouchInfo::rotation_delta`].
+    /// See [`crate::InputState::rotation_delta`].
+    Rotate(f32),
+
+    /// The user asked to zoom to (or back out of) what is under the pointer,
+    /// e.g. with a two-finger double-tap on a trackpad.
+    ///
+    /// See [`crate::InputState::smart_zoom`].
+    SmartZoom,
+
     /// IME composition start.
     CompositionStart,
 
@@ -477,6 +587,15 @@ pub enum Event {
         force: Option<f32>,
     },
 
+    /// The pointer is a pen (stylus), and this is its new state.
+    ///
+    /// Report this *before* the [`Self::PointerMoved`] and [`Self::PointerButton`] it belongs to,
+    /// whenever the pressure, tilt or eraser changes.
+    /// `None` when the pointer is no longer a pen, e.g. when switching to the mouse.
+    ///
+    /// See [`crate::PointerState::pen`].
+    Pen(Option<PenState>),
+
     /// A raw mouse wheel event as sent by the backend (minus the z coordinate),
     /// for implementing alternative custom controls.
     /// Note that the same event can also trigger [`Self::Zoom`] and [`Self::Scroll`],
@@ -496,6 +615,13 @@ pub enum Event {
     /// The native window gained or lost focused (e.g. the user clicked alt-tab).
     WindowFocused(bool),
 
+    /// The theme egui uses changed, e.g. because the user switched the theme of the operating system.
+    ///
+    /// This is sent by egui itself (not the integration),
+    /// so that you can update any custom colors.
+    /// See [`crate::Options::theme_preference`].
+    ThemeChanged(crate::Theme),
+
     /// An assistive technology (e.g. screen reader) requested an action.
     #[cfg(feature = "accesskit")]
     AccessKitActionRequest(accesskit::ActionRequest),
@@ -505,6 +631,84 @@ pub enum Event {
         viewport_id: crate::ViewportId,
         image: std::sync::Arc<ColorImage>,
     },
+
+    /// The color the user picked from the screen,
+    /// in reply to [`crate::PlatformOutput::request_eyedropper`].
+    ///
+    /// `None` if the user cancelled.
+    EyedropperColor(Option<Color32>),
+
+    /// A button on a gamepad (controller) was pressed or released.
+    ///
+    /// egui maps the D-pad to the arrow keys, [`GamepadButton::South`] to [`Key::Enter`]
+    /// and [`GamepadButton::East`] to [`Key::Escape`], so that the focus can be moved around and used.
+    /// See [`crate::GamepadState`].
+    GamepadButton {
+        button: GamepadButton,
+        pressed: bool,
+    },
+
+    /// An axis of a gamepad (controller) moved.
+    ///
+    /// The sticks go from `-1.0` to `1.0`, with positive y being up.
+    /// The triggers go from `0.0` to `1.0`.
+    ///
+    /// egui maps the left stick to the arrow keys, like the D-pad,
+    /// and uses the right stick to adjust the focused [`crate::Slider`] or [`crate::DragValue`].
+    /// See [`crate::GamepadState`].
+    GamepadAxis { axis: GamepadAxis, value: f32 },
+}
+
+/// A button on a gamepad, see [`Event::GamepadButton`].
+///
+/// The face buttons are named by where they are,
+/// so e.g. [`Self::South`] is "A" on an Xbox controller and "✕" on a PlayStation controller.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum GamepadButton {
+    /// The bottom face button. Activates the focused widget.
+    South,
+
+    /// The right face button. Goes back, like [`Key::Escape`].
+    East,
+
+    /// The left face button.
+    West,
+
+    /// The top face button.
+    North,
+
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+
+    LeftShoulder,
+    RightShoulder,
+
+    /// The left stick, when pressed in.
+    LeftStick,
+
+    /// The right stick, when pressed in.
+    RightStick,
+
+    /// The right one of the buttons in the middle, e.g. "Start" or "Menu".
+    Start,
+
+    /// The left one of the buttons in the middle, e.g. "Select", "Back" or "View".
+    Select,
+}
+
+/// An axis of a gamepad, see [`Event::GamepadAxis`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum GamepadAxis {
+    LeftStickX,
+    LeftStickY,
+    RightStickX,
+    RightStickY,
+    LeftTrigger,
+    RightTrigger,
 }
 
 /// Mouse button (or similar for touch input)
@@ -998,6 +1202,8 @@ impl RawInput {
             hovered_files,
             dropped_files,
             focused,
+            system_theme,
+            eyedropper,
         } = self;
 
         ui.label(format!("Active viwport: {viewport_id:?}"));
@@ -1022,6 +1228,8 @@ impl RawInput {
         ui.label(format!("hovered_files: {}", hovered_files.len()));
         ui.label(format!("dropped_files: {}", dropped_files.len()));
         ui.label(format!("focused: {focused}"));
+        ui.label(format!("system_theme: {system_theme:?}"));
+        ui.label(format!("eyedropper: {eyedropper}"));
         ui.scope(|ui| {
             ui.set_min_height(150.0);
             ui.label(format!("events: {events:#?}"))
@@ -1042,6 +1250,45 @@ pub struct TouchDeviceId(pub u64);
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct TouchId(pub u64);
 
+/// Identifies one of several pointers that are tracked at the same time,
+/// e.g. multiple mice or the users of a multi-user touch table.
+///
+/// See [`Event::ExtraPointer`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct PointerId(pub u64);
+
+impl PointerId {
+    /// The pointer of the plain pointer events, i.e. [`crate::InputState::pointer`].
+    pub const PRIMARY: Self = Self(0);
+}
+
+/// The state of a pen (stylus), see [`Event::Pen`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct PenState {
+    /// How hard the pen is pressed, from `0.0` (not touching) to `1.0` (maximum pressure).
+    pub pressure: f32,
+
+    /// How much the pen leans to the right (`x`) and towards the user (`y`), in radians.
+    ///
+    /// Zero when the pen is perpendicular to the screen.
+    pub tilt: Vec2,
+
+    /// Is this the eraser end of the pen (or is the eraser button held)?
+    pub eraser: bool,
+}
+
+impl Default for PenState {
+    fn default() -> Self {
+        Self {
+            pressure: 0.0,
+            tilt: Vec2::ZERO,
+            eraser: false,
+        }
+    }
+}
+
 /// In what phase a touch event is in.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
@@ -1158,3 +1405,345 @@ impl EventFilter {
         }
     }
 }
+
+// ----------------------------------------------------------------------------
+
+/// Cleans up [`RawInput`] before egui uses it, for input from sources that may misbehave,
+/// e.g. remote clients or scripts.
+///
+/// Set it in [`crate::Options::input_sanitizer`].
+/// The default only drops values that are NaN or infinite.
+/// Use [`Self::strict`] for input you don't trust.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct InputSanitizer {
+    /// Drop events with positions or deltas that are NaN or infinite, and zoom factors that aren't positive.
+    ///
+    /// Such a [`RawInput::screen_rect`] or [`RawInput::time`] is ignored,
+    /// and a negative or invalid [`RawInput::predicted_dt`] is replaced with `1/60`.
+    /// The same goes for the scale factor, sizes and rects in [`RawInput::viewports`].
+    pub drop_invalid: bool,
+
+    /// Move pointer and touch positions outside of the screen onto its edge.
+    pub clamp_to_screen: bool,
+
+    /// Clamp each component of scroll, mouse wheel and mouse motion deltas to this, in points.
+    pub max_delta: f32,
+
+    /// Clamp zoom factors to `1/max_zoom ..= max_zoom`.
+    pub max_zoom: f32,
+
+    /// Truncate typed, pasted and composed text to this many characters.
+    pub max_text_len: usize,
+
+    /// Use at most this many pointer motion and scroll events each frame.
+    ///
+    /// When there are more, consecutive ones are merged first, and then the oldest ones are dropped.
+    /// Other events, like key presses, typed text and button clicks, are never dropped.
+    pub max_motion_events_per_frame: usize,
+}
+
+impl Default for InputSanitizer {
+    fn default() -> Self {
+        Self {
+            drop_invalid: true,
+            clamp_to_screen: false,
+            max_delta: f32::INFINITY,
+            max_zoom: f32::INFINITY,
+            max_text_len: usize::MAX,
+            max_motion_events_per_frame: usize::MAX,
+        }
+    }
+}
+
+impl InputSanitizer {
+    /// Limits suitable for input from untrusted sources.
+    pub fn strict() -> Self {
+        Self {
+            drop_invalid: true,
+            clamp_to_screen: true,
+            max_delta: 1000.0,
+            max_zoom: 10.0,
+            max_text_len: 10_000,
+            max_motion_events_per_frame: 100,
+        }
+    }
+
+    /// Clean up the input, where `screen_rect` is the screen rect to use if the input doesn't have one.
+    pub(crate) fn sanitize(&self, input: &mut RawInput, screen_rect: Rect) {
+        crate::profile_function!();
+
+        if self.drop_invalid {
+            let is_invalid_rect = |rect: &Rect| !rect.is_finite() || rect.is_negative();
+            if input.screen_rect.as_ref().map_or(false, is_invalid_rect) {
+                input.screen_rect = None;
+            }
+            if input.time.map_or(false, |time| !time.is_finite()) {
+                input.time = None;
+            }
+            if !input.predicted_dt.is_finite() || input.predicted_dt < 0.0 {
+                input.predicted_dt = 1.0 / 60.0;
+            }
+
+            for info in input.viewports.values_mut() {
+                if info
+                    .native_pixels_per_point
+                    .map_or(false, |ppp| !ppp.is_finite() || ppp <= 0.0)
+                {
+                    info.native_pixels_per_point = None;
+                }
+                if info
+                    .monitor_size
+                    .map_or(false, |size| !size.is_finite() || size.min_elem() < 0.0)
+                {
+                    info.monitor_size = None;
+                }
+                if info.inner_rect.as_ref().map_or(false, is_invalid_rect) {
+                    info.inner_rect = None;
+                }
+                if info.outer_rect.as_ref().map_or(false, is_invalid_rect) {
+                    info.outer_rect = None;
+                }
+            }
+        }
+
+        let screen_rect = input.screen_rect.unwrap_or(screen_rect);
+        input
+            .events
+            .retain_mut(|event| self.sanitize_event(event, screen_rect));
+
+        let num_motion_events = |events: &[Event]| events.iter().filter(|e| is_motion(e)).count();
+        if num_motion_events(&input.events) > self.max_motion_events_per_frame {
+            self.merge_motion(&mut input.events);
+        }
+        let num_motion_events = num_motion_events(&input.events);
+        if num_motion_events > self.max_motion_events_per_frame {
+            // Keep the latest ones, so that the pointer ends up in the right place:
+            let mut num_to_drop = num_motion_events - self.max_motion_events_per_frame;
+            input.events.retain(|event| {
+                if num_to_drop > 0 && is_motion(event) {
+                    num_to_drop -= 1;
+                    false
+                } else {
+                    true
+                }
+            });
+        }
+    }
+
+    /// Only keep the last of each run of consecutive [`Event::PointerMoved`],
+    /// and add up the deltas of runs of [`Event::MouseMoved`], [`Event::Scroll`] and [`Event::MouseWheel`].
+    fn merge_motion(&self, events: &mut Vec<Event>) {
+        let max_delta = Vec2::splat(self.max_delta);
+        let mut merged: Vec<Event> = Vec::with_capacity(events.len());
+        for event in events.drain(..) {
+            match (merged.last_mut(), &event) {
+                (Some(Event::PointerMoved(last)), Event::PointerMoved(pos)) => *last = *pos,
+                (Some(Event::MouseMoved(last)), Event::MouseMoved(delta))
+                | (Some(Event::Scroll(last)), Event::Scroll(delta)) => {
+                    *last = (*last + *delta).clamp(-max_delta, max_delta);
+                }
+                (
+                    Some(Event::MouseWheel {
+                        unit: last_unit,
+                        delta: last,
+                        modifiers: last_modifiers,
+                    }),
+                    Event::MouseWheel {
+                        unit,
+                        delta,
+                        modifiers,
+                    },
+                ) if last_unit == unit && last_modifiers == modifiers => {
+                    *last = (*last + *delta).clamp(-max_delta, max_delta);
+                }
+                _ => merged.push(event),
+            }
+        }
+        *events = merged;
+    }
+
+    /// Returns `false` if the event should be dropped.
+    fn sanitize_event(&self, event: &mut Event, screen_rect: Rect) -> bool {
+        if let Event::ExtraPointer { event, .. } = event {
+            return self.sanitize_event(event, screen_rect);
+        }
+
+        let pos = match event {
+            Event::PointerMoved(pos)
+            | Event::PointerButton { pos, .. }
+            | Event::Touch { pos, .. } => Some(pos),
+            _ => None,
+        };
+        if let Some(pos) = pos {
+            if !pos.is_finite() {
+                return !self.drop_invalid;
+            }
+            if self.clamp_to_screen && screen_rect.is_finite() {
+                *pos = pos.clamp(screen_rect.min, screen_rect.max);
+            }
+        }
+
+        match event {
+            Event::MouseMoved(delta) | Event::Scroll(delta) | Event::MouseWheel { delta, .. } => {
+                if !delta.is_finite() {
+                    return !self.drop_invalid;
+                }
+                *delta = delta.clamp(Vec2::splat(-self.max_delta), Vec2::splat(self.max_delta));
+            }
+            Event::Zoom(factor) => {
+                if !factor.is_finite() || *factor <= 0.0 {
+                    return !self.drop_invalid;
+                }
+                *factor = factor.clamp(1.0 / self.max_zoom, self.max_zoom);
+            }
+            Event::Rotate(angle) => {
+                if !angle.is_finite() {
+                    return !self.drop_invalid;
+                }
+                *angle = angle.clamp(-std::f32::consts::PI, std::f32::consts::PI);
+            }
+            Event::Touch {
+                force: Some(force), ..
+            } => {
+                if !force.is_finite() {
+                    return !self.drop_invalid;
+                }
+                *force = force.clamp(0.0, 1.0);
+            }
+            Event::Pen(Some(pen)) => {
+                if !pen.pressure.is_finite() || !pen.tilt.is_finite() {
+                    return !self.drop_invalid;
+                }
+                pen.pressure = pen.pressure.clamp(0.0, 1.0);
+                pen.tilt = pen.tilt.clamp(
+                    Vec2::splat(-std::f32::consts::FRAC_PI_2),
+                    Vec2::splat(std::f32::consts::FRAC_PI_2),
+                );
+            }
+            Event::Text(text)
+            | Event::Paste { text, .. }
+            | Event::CompositionUpdate(text)
+            | Event::CompositionEnd(text) => {
+                if let Some((byte_index, _)) = text.char_indices().nth(self.max_text_len) {
+                    text.truncate(byte_index);
+                }
+            }
+            _ => {}
+        }
+        true
+    }
+}
+
+/// Pointer motion and scrolling, which can come in floods that are safe to thin out.
+fn is_motion(event: &Event) -> bool {
+    matches!(
+        event,
+        Event::PointerMoved(_)
+            | Event::MouseMoved(_)
+            | Event::Scroll(_)
+            | Event::MouseWheel { .. }
+            | Event::Touch {
+                phase: TouchPhase::Move,
+                ..
+            }
+    ) || matches!(event, Event::ExtraPointer { event, .. } if is_motion(event))
+}
+
+#[test]
+fn test_input_sanitizer() {
+    let screen_rect = Rect::from_min_size(Pos2::ZERO, vec2(800.0, 600.0));
+    let mut input = RawInput {
+        screen_rect: Some(screen_rect),
+        predicted_dt: f32::NAN,
+        events: vec![
+            Event::PointerMoved(pos2(f32::NAN, 10.0)),
+            Event::PointerMoved(pos2(-50.0, 10_000.0)),
+            Event::Scroll(vec2(0.0, 1e30)),
+            Event::Zoom(0.0),
+            Event::Zoom(1e9),
+            Event::Text("x".repeat(20_000)),
+        ],
+        ..Default::default()
+    };
+    InputSanitizer::strict().sanitize(&mut input, Rect::EVERYTHING);
+
+    assert_eq!(input.predicted_dt, 1.0 / 60.0);
+    assert_eq!(
+        input.events[..4],
+        [
+            Event::PointerMoved(pos2(0.0, 600.0)),
+            Event::Scroll(vec2(0.0, 1000.0)),
+            Event::Zoom(10.0),
+            Event::Text("x".repeat(10_000)),
+        ]
+    );
+
+    // Rate limiting:
+    let key = |pressed| Event::Key {
+        key: Key::A,
+        physical_key: None,
+        pressed,
+        repeat: false,
+        modifiers: Modifiers::NONE,
+    };
+    let mut events: Vec<Event> = (0..500)
+        .map(|i| Event::PointerMoved(pos2(i as f32, 0.0)))
+        .collect();
+    events.extend((0..200).map(|_| key(true)));
+    events.push(key(false));
+    let mut input = RawInput {
+        events,
+        ..Default::default()
+    };
+    InputSanitizer::strict().sanitize(&mut input, screen_rect);
+
+    assert_eq!(input.events.len(), 202, "key presses are never dropped");
+    assert_eq!(input.events[0], Event::PointerMoved(pos2(499.0, 0.0)));
+    assert_eq!(input.events.last(), Some(&key(false)));
+
+    // Motion that can't be merged drops the oldest:
+    let events: Vec<Event> = (0..300)
+        .flat_map(|i| [Event::PointerMoved(pos2(i as f32, 0.0)), key(true)])
+        .collect();
+    let mut input = RawInput {
+        events,
+        ..Default::default()
+    };
+    InputSanitizer::strict().sanitize(&mut input, screen_rect);
+
+    let moves: Vec<&Event> = input.events.iter().filter(|e| is_motion(e)).collect();
+    assert_eq!(moves.len(), 100);
+    assert_eq!(moves[0], &Event::PointerMoved(pos2(200.0, 0.0)));
+    assert_eq!(input.events.len(), 400);
+
+    // Invalid viewport info is ignored:
+    let mut input = RawInput::default();
+    input.viewports.insert(
+        crate::ViewportId::ROOT,
+        ViewportInfo {
+            native_pixels_per_point: Some(f32::NAN),
+            monitor_size: Some(vec2(-1.0, 600.0)),
+            inner_rect: Some(Rect::from_min_max(pos2(100.0, 0.0), pos2(0.0, 100.0))),
+            outer_rect: Some(screen_rect),
+            ..Default::default()
+        },
+    );
+    InputSanitizer::default().sanitize(&mut input, screen_rect);
+
+    let info = &input.viewports[&crate::ViewportId::ROOT];
+    assert_eq!(info.native_pixels_per_point, None);
+    assert_eq!(info.monitor_size, None);
+    assert_eq!(info.inner_rect, None);
+    assert_eq!(info.outer_rect, Some(screen_rect));
+
+    // By default, egui ignores invalid positions:
+    let ctx = crate::Context::default();
+    let input = RawInput {
+        events: vec![Event::PointerMoved(pos2(f32::NAN, f32::NAN))],
+        ..Default::default()
+    };
+    let _ = ctx.run(input, |_| {});
+    assert_eq!(ctx.input(|i| i.pointer.hover_pos()), None);
+}
@@ -5,7 +5,20 @@ use crate::{ViewportIdMap, ViewportOutput, WidgetType};
 /// What egui emits each frame from [`crate::Context::run`].
 ///
 /// The backend should use this.
+///
+/// With the `serde` feature this can be serialized, e.g. to record and replay frames,
+/// or to render them in another process.
+/// Textures are only included when they change, so a recording of consecutive frames
+/// needs to be replayed from the start.
+/// [`epaint::Shape::Callback`] and [`ViewportOutput::viewport_ui_cb`] can't be serialized:
+/// the former results in an error, and the latter is skipped.
+/// A compact binary format such as `bincode` works well for this.
+///
+/// Text is serialized as its [`epaint::text::LayoutJob`], without the laid out glyphs,
+/// so after deserializing you need to call [`Self::layout_text`] before painting.
 #[derive(Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct FullOutput {
     /// Non-rendering related output.
     pub platform_output: PlatformOutput,
@@ -21,6 +34,7 @@ pub struct FullOutput {
     /// What to paint.
     ///
     /// You can use [`crate::Context::tessellate`] to turn this into triangles.
+    #[cfg_attr(feature = "serde", serde(with = "serde_shapes"))]
     pub shapes: Vec<epaint::ClippedShape>,
 
     /// The number of physical pixels per logical ui point, for the viewport that was updated.
@@ -62,6 +76,174 @@ impl FullOutput {
             }
         }
     }
+
+    /// Lay out the text of all [`Self::shapes`] again, e.g. after deserializing.
+    ///
+    /// Use the fonts of the [`crate::Context`] that you tessellate with, so that the glyphs
+    /// end up in its font texture:
+    ///
+    /// ```
+    /// # let ctx = egui::Context::default();
+    /// # let mut output = ctx.run(Default::default(), |_| {});
+    /// ctx.fonts(|fonts| output.layout_text(fonts));
+    /// ```
+    pub fn layout_text(&mut self, fonts: &epaint::Fonts) {
+        fn layout_shape(shape: &mut epaint::Shape, fonts: &epaint::Fonts) {
+            match shape {
+                epaint::Shape::Text(text) => {
+                    text.galley = fonts.layout_job((*text.galley.job).clone());
+                }
+                epaint::Shape::Vec(shapes) => {
+                    for shape in shapes {
+                        layout_shape(shape, fonts);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        for clipped in &mut self.shapes {
+            layout_shape(&mut clipped.shape, fonts);
+        }
+    }
+}
+
+/// Serializes [`epaint::Shape::Text`] as its [`epaint::text::LayoutJob`] instead of its [`epaint::Galley`].
+///
+/// The deserialized galleys have no rows, until [`FullOutput::layout_text`] is called.
+#[cfg(feature = "serde")]
+mod serde_shapes {
+    use std::sync::Arc;
+
+    use epaint::{
+        text::LayoutJob, ClippedShape, Color32, Galley, Pos2, Rect, Shape, Stroke, TextShape,
+    };
+
+    #[derive(serde::Serialize)]
+    enum ShapeRef<'a> {
+        Text {
+            pos: Pos2,
+            job: &'a LayoutJob,
+            pixels_per_point: f32,
+            underline: Stroke,
+            fallback_color: Color32,
+            override_text_color: Option<Color32>,
+            opacity_factor: f32,
+            angle: f32,
+        },
+        Vec(Vec<ShapeRef<'a>>),
+        Other(&'a Shape),
+    }
+
+    #[derive(serde::Deserialize)]
+    enum ShapeData {
+        Text {
+            pos: Pos2,
+            job: LayoutJob,
+            pixels_per_point: f32,
+            underline: Stroke,
+            fallback_color: Color32,
+            override_text_color: Option<Color32>,
+            opacity_factor: f32,
+            angle: f32,
+        },
+        Vec(Vec<ShapeData>),
+        Other(Shape),
+    }
+
+    impl<'a> From<&'a Shape> for ShapeRef<'a> {
+        fn from(shape: &'a Shape) -> Self {
+            match shape {
+                Shape::Text(text) => {
+                    let TextShape {
+                        pos,
+                        galley,
+                        underline,
+                        fallback_color,
+                        override_text_color,
+                        opacity_factor,
+                        angle,
+                    } = text;
+                    Self::Text {
+                        pos: *pos,
+                        job: &galley.job,
+                        pixels_per_point: galley.pixels_per_point,
+                        underline: *underline,
+                        fallback_color: *fallback_color,
+                        override_text_color: *override_text_color,
+                        opacity_factor: *opacity_factor,
+                        angle: *angle,
+                    }
+                }
+                Shape::Vec(shapes) => Self::Vec(shapes.iter().map(Self::from).collect()),
+                shape => Self::Other(shape),
+            }
+        }
+    }
+
+    impl From<ShapeData> for Shape {
+        fn from(shape: ShapeData) -> Self {
+            match shape {
+                ShapeData::Text {
+                    pos,
+                    job,
+                    pixels_per_point,
+                    underline,
+                    fallback_color,
+                    override_text_color,
+                    opacity_factor,
+                    angle,
+                } => {
+                    let galley = Galley {
+                        job: Arc::new(job),
+                        rows: Vec::new(),
+                        elided: false,
+                        missing_glyphs: Vec::new(),
+                        rect: Rect::NOTHING,
+                        mesh_bounds: Rect::NOTHING,
+                        num_vertices: 0,
+                        num_indices: 0,
+                        pixels_per_point,
+                    };
+                    Self::Text(TextShape {
+                        pos,
+                        galley: Arc::new(galley),
+                        underline,
+                        fallback_color,
+                        override_text_color,
+                        opacity_factor,
+                        angle,
+                    })
+                }
+                ShapeData::Vec(shapes) => Self::Vec(shapes.into_iter().map(Self::from).collect()),
+                ShapeData::Other(shape) => shape,
+            }
+        }
+    }
+
+    pub fn serialize<S: serde::Serializer>(
+        shapes: &[ClippedShape],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(
+            shapes
+                .iter()
+                .map(|ClippedShape { clip_rect, shape }| (clip_rect, ShapeRef::from(shape))),
+        )
+    }
+
+    pub fn deserialize<'de, D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<ClippedShape>, D::Error> {
+        let shapes: Vec<(Rect, ShapeData)> = serde::Deserialize::deserialize(deserializer)?;
+        Ok(shapes
+            .into_iter()
+            .map(|(clip_rect, shape)| ClippedShape {
+                clip_rect,
+                shape: shape.into(),
+            })
+            .collect())
+    }
 }
 
 /// Information about text being edited.
@@ -109,6 +291,17 @@ pub struct PlatformOutput {
     /// Events that may be useful to e.g. a screen reader.
     pub events: Vec<OutputEvent>,
 
+    /// Sounds the integration may want to play, e.g. a click when a button is clicked.
+    ///
+    /// Only emitted if [`crate::style::Interaction::emit_sounds`] is set.
+    pub sound_events: Vec<UiSoundEvent>,
+
+    /// Haptic feedback (vibration) the integration may give on touch devices,
+    /// e.g. a tick when a slider passes a step.
+    ///
+    /// Only emitted if [`crate::style::Interaction::emit_haptics`] is set.
+    pub haptics: Vec<HapticKind>,
+
     /// Is there a mutable [`TextEdit`](crate::TextEdit) under the cursor?
     /// Use by `eframe` web to show/hide mobile keyboard and IME agent.
     pub mutable_text_under_cursor: bool,
@@ -118,6 +311,20 @@ pub struct PlatformOutput {
     /// Useful for IME.
     pub ime: Option<IMEOutput>,
 
+    /// A color picker wants the user to pick a color from anywhere on the screen.
+    ///
+    /// Only set if [`crate::RawInput::eyedropper`] is.
+    /// The integration should let the user pick a color,
+    /// and then reply with [`crate::Event::EyedropperColor`].
+    pub request_eyedropper: bool,
+
+    /// Lock the mouse pointer to the window and hide it, e.g. for looking around in a 3D viewport.
+    ///
+    /// This must be requested each frame the lock should be held, e.g. with [`crate::Response::request_pointer_lock`].
+    /// While locked, the integration should keep sending raw mouse motion with [`crate::Event::MouseMoved`],
+    /// which can be read with [`crate::PointerState::locked_delta`].
+    pub pointer_lock: bool,
+
     /// The difference in the widget tree since last frame.
     ///
     /// NOTE: this needs to be per-viewport.
@@ -151,8 +358,12 @@ impl PlatformOutput {
             open_url,
             copied_text,
             mut events,
+            mut sound_events,
+            mut haptics,
             mutable_text_under_cursor,
             ime,
+            request_eyedropper,
+            pointer_lock,
             #[cfg(feature = "accesskit")]
             accesskit_update,
         } = newer;
@@ -165,8 +376,12 @@ impl PlatformOutput {
             self.copied_text = copied_text;
         }
         self.events.append(&mut events);
+        self.sound_events.append(&mut sound_events);
+        self.haptics.append(&mut haptics);
         self.mutable_text_under_cursor = mutable_text_under_cursor;
         self.ime = ime.or(self.ime);
+        self.request_eyedropper |= request_eyedropper;
+        self.pointer_lock |= pointer_lock;
 
         #[cfg(feature = "accesskit")]
         {
@@ -408,6 +623,42 @@ impl Default for CursorIcon {
     }
 }
 
+/// Something that happened in the ui this frame that the integration may want to play a sound for,
+/// see [`PlatformOutput::sound_events`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum UiSoundEvent {
+    /// A widget was clicked.
+    Click(WidgetType),
+
+    /// The pointer started hovering an interactive widget.
+    Hover(WidgetType),
+
+    /// A checkbox, radio button or similar was turned on or off.
+    Toggle { widget_type: WidgetType, on: bool },
+
+    /// Something the user did was rejected, e.g. text that couldn't be parsed as a number.
+    ///
+    /// Custom widgets can emit this with [`crate::Context::emit_sound`].
+    Error,
+}
+
+/// A kind of haptic feedback the integration may give, see [`PlatformOutput::haptics`].
+///
+/// They are ordered from the lightest to the strongest.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum HapticKind {
+    /// A dragged [`crate::Slider`] passed one of its steps or ticks, like the notch of a dial.
+    Detent,
+
+    /// Something was dropped at the end of a drag-and-drop.
+    Drop,
+
+    /// A context menu opened, e.g. after a long press.
+    MenuOpened,
+}
+
 /// Things that happened during this frame that the integration may be interested in.
 ///
 /// In particular, these events may be useful for accessibility, i.e. for screen readers.
@@ -675,3 +926,40 @@ impl WidgetInfo {
         description.trim().to_owned()
     }
 }
+
+#[cfg(feature = "persistence")]
+#[test]
+fn test_full_output_serde_round_trip() {
+    let ctx = crate::Context::default();
+    let output = ctx.run(Default::default(), |ctx| {
+        crate::CentralPanel::default().show(ctx, |ui| {
+            ui.label("Hello");
+            let _ = ui.button("World");
+        });
+    });
+    assert!(!output.shapes.is_empty());
+    assert!(!output.textures_delta.set.is_empty(), "the font texture");
+
+    let ron = ron::to_string(&output).unwrap();
+    assert!(
+        ron.contains("Hello") && !ron.contains("glyphs"),
+        "text is stored as its job"
+    );
+    let mut restored: FullOutput = ron::from_str(&ron).unwrap();
+    assert!(restored.platform_output == output.platform_output);
+    assert!(restored.textures_delta == output.textures_delta);
+    assert_ne!(
+        restored.shapes, output.shapes,
+        "galleys are not laid out yet"
+    );
+    ctx.fonts(|fonts| restored.layout_text(fonts));
+    assert_eq!(restored.shapes, output.shapes);
+    assert_eq!(restored.pixels_per_point, output.pixels_per_point);
+    assert_eq!(ron::to_string(&restored).unwrap(), ron);
+
+    let callback = epaint::Shape::Callback(epaint::PaintCallback {
+        rect: crate::Rect::ZERO,
+        callback: std::sync::Arc::new(()),
+    });
+    assert!(ron::to_string(&callback).is_err());
+}
@@ -0,0 +1,204 @@
+//! The ui of [`Context::devtools_ui`].
+
+use crate::*;
+
+/// What the user has typed/selected in the devtools.
+#[derive(Clone, Default)]
+struct State {
+    /// Only show widgets whose id, layer or callsite contains this.
+    filter: String,
+
+    /// Only show widgets that sense clicks or drags.
+    only_interactive: bool,
+
+    /// Highlight this widget, even when not hovered in the list.
+    selected: Option<Id>,
+}
+
+impl State {
+    fn load(ctx: &Context) -> Self {
+        ctx.data(|data| data.get_temp(Id::new("egui_devtools")))
+            .unwrap_or_default()
+    }
+
+    fn store(self, ctx: &Context) {
+        ctx.data_mut(|data| data.insert_temp(Id::new("egui_devtools"), self));
+    }
+}
+
+fn callsite_string(callsite: Option<&std::panic::Location<'_>>) -> String {
+    callsite.map_or_else(
+        || "?".to_owned(),
+        |callsite| format!("{}:{}", callsite.file(), callsite.line()),
+    )
+}
+
+/// `widgets` should be the widgets of the previous frame,
+/// since the current frame is still in progress.
+///
+/// The widgets are listed back-to-front, following `layer_order`.
+pub(crate) fn devtools_ui(
+    ctx: &Context,
+    ui: &mut Ui,
+    widgets: &WidgetRects,
+    layer_order: &[LayerId],
+) {
+    let mut state = State::load(ctx);
+
+    ui.horizontal(|ui| {
+        ui.label("Filter:");
+        ui.text_edit_singleline(&mut state.filter);
+        if ui.button("Clear").clicked() {
+            state.filter.clear();
+        }
+    });
+    ui.checkbox(&mut state.only_interactive, "Only interactive widgets");
+
+    let filter = state.filter.to_lowercase();
+
+    let rows: Vec<(WidgetRect, String, String)> = widgets
+        .iter_back_to_front(layer_order)
+        .copied()
+        .filter(|w| !state.only_interactive || w.sense.interactive())
+        .map(|w| {
+            let layer = w.layer_id.short_debug_format();
+            let callsite = callsite_string(widgets.callsite(w.id));
+            (w, layer, callsite)
+        })
+        .filter(|(w, layer, callsite)| {
+            filter.is_empty()
+                || w.id.short_debug_format().to_lowercase().contains(&filter)
+                || layer.to_lowercase().contains(&filter)
+                || callsite.to_lowercase().contains(&filter)
+        })
+        .collect();
+
+    ui.label(format!(
+        "Showing {} of {} widgets of the previous frame",
+        rows.len(),
+        widgets.len()
+    ));
+
+    CollapsingHeader::new("Hot paths")
+        .default_open(false)
+        .show(ui, |ui| {
+            ui.label("The places in the code that create the most widgets:");
+            let mut counts: ahash::HashMap<&str, usize> = Default::default();
+            for (_, _, callsite) in &rows {
+                *counts.entry(callsite.as_str()).or_default() += 1;
+            }
+            let mut counts: Vec<_> = counts.into_iter().collect();
+            counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+            Grid::new("devtools_hot_paths")
+                .num_columns(2)
+                .striped(true)
+                .show(ui, |ui| {
+                    for (callsite, count) in counts.into_iter().take(20) {
+                        ui.monospace(count.to_string());
+                        ui.monospace(callsite);
+                        ui.end_row();
+                    }
+                });
+        });
+
+    CollapsingHeader::new("Colors")
+        .default_open(false)
+        .show(ui, |ui| {
+            let mut options = ctx.color_debug_options();
+            options.ui(ui);
+            if options != ctx.color_debug_options() {
+                ctx.set_color_debug_options(options);
+            }
+        });
+
+    ui.separator();
+
+    let mut hovered = None;
+
+    ScrollArea::both()
+        .auto_shrink([false, true])
+        .max_height(400.0)
+        .show_rows(
+            ui,
+            ui.text_style_height(&TextStyle::Monospace),
+            rows.len(),
+            |ui, range| {
+                for (w, layer, callsite) in &rows[range] {
+                    let is_selected = state.selected == Some(w.id);
+                    let text = format!(
+                        "{:4} {:<14} {:>5.0}x{:<5.0} {}",
+                        w.id.short_debug_format(),
+                        layer,
+                        w.rect.width(),
+                        w.rect.height(),
+                        callsite,
+                    );
+                    let response = ui.add(SelectableLabel::new(
+                        is_selected,
+                        RichText::new(text).monospace(),
+                    ));
+                    if response.clicked() {
+                        state.selected = if is_selected { None } else { Some(w.id) };
+                    }
+                    if response.hovered() {
+                        hovered = Some(*w);
+                    }
+                    response.on_hover_ui(|ui| {
+                        ui.label(format!("Id: {:?}", w.id));
+                        ui.label(format!("Layer: {:?}", w.layer_id));
+                        ui.label(format!("Rect: {:?}", w.rect));
+                        ui.label(format!("Interact rect: {:?}", w.interact_rect));
+                        ui.label(format!("Sense: {:?}", w.sense));
+                        ui.label(format!("Enabled: {}", w.enabled));
+                        ui.label(format!("Created at: {callsite}"));
+                    });
+                }
+            },
+        );
+
+    // Highlight the hovered and selected widgets:
+    let painter = ctx.debug_painter();
+    if let Some(selected) = state.selected.and_then(|id| widgets.get(id)) {
+        painter.debug_rect(selected.rect, Color32::LIGHT_BLUE, "selected");
+    }
+    if let Some(hovered) = hovered {
+        painter.debug_rect(hovered.rect, Color32::YELLOW, "");
+        painter.rect_filled(
+            hovered.interact_rect,
+            0.0,
+            Color32::YELLOW.gamma_multiply(0.1),
+        );
+    }
+
+    state.store(ctx);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn callsite_is_in_the_calling_code() {
+        let ctx = Context::default();
+        let mut ids = Vec::new();
+        let _ = ctx.run(RawInput::default(), |ctx| {
+            CentralPanel::default().show(ctx, |ui| {
+                ctx.devtools_ui(ui);
+                ids = vec![
+                    ui.button("Button").id,
+                    ui.add(Label::new("Label")).id,
+                    ui.checkbox(&mut true, "Checkbox").id,
+                ];
+            });
+        });
+
+        ctx.widget_rects_prev_frame(|widgets| {
+            for id in ids {
+                let callsite = widgets
+                    .callsite(id)
+                    .expect("The callsite should be recorded");
+                assert_eq!(callsite.file(), file!(), "{id:?} was created at {callsite}");
+            }
+        });
+    }
+}
@@ -1,6 +1,12 @@
 use std::{any::Any, sync::Arc};
 
-use crate::{Context, CursorIcon, Id};
+use epaint::{emath::TSTransform, ClippedShape};
+
+use crate::{Context, CursorIcon, Id, LayerId, Order, Pos2, ViewportId, ViewportIdSet};
+
+/// How long to keep a payload that was released over another viewport,
+/// if that viewport never gets to run a frame (e.g. because it was closed).
+const HANDOFF_TIMEOUT: f64 = 1.0;
 
 /// Tracking of drag-and-drop payload.
 ///
@@ -13,39 +19,165 @@ use crate::{Context, CursorIcon, Id};
 /// - [`crate::Response::dnd_hover_payload`]
 /// - [`crate::Response::dnd_release_payload`]
 ///
+/// The payload is shared by all viewports of the same [`Context`],
+/// so a drag started in one viewport can be dropped in another one.
+/// This requires the integration to report [`crate::ViewportInfo::inner_rect`]
+/// for the viewports involved.
+///
 /// See [this example](https://github.com/emilk/egui/blob/master/crates/egui_demo_lib/src/demo/drag_and_drop.rs).
 #[doc(alias = "drag and drop")]
 #[derive(Clone, Default)]
 pub struct DragAndDrop {
     /// If set, something is currently being dragged
     payload: Option<Arc<dyn Any + Send + Sync>>,
+
+    /// The viewport in which the drag started.
+    source_viewport: ViewportId,
+
+    /// The latest position of the pointer, in global screen coordinates (points).
+    ///
+    /// `None` if we don't know where the viewports are on screen.
+    pointer_screen_pos: Option<Pos2>,
+
+    /// The viewport the pointer is currently over, if known.
+    hovered_viewport: Option<ViewportId>,
+
+    /// The pointer was released over [`Self::hovered_viewport`], which is not the source viewport.
+    ///
+    /// The payload is kept until that viewport has had a frame to accept it,
+    /// or until [`HANDOFF_TIMEOUT`] has passed.
+    released_in_other_viewport: bool,
+
+    /// When the pointer was released in the other viewport, in [`crate::InputState::time`].
+    released_time: f64,
+
+    /// What to paint at the pointer while dragging, relative to the pointer position.
+    ///
+    /// Used to draw the drag ghost in viewports other than the source viewport.
+    ghost: Arc<Vec<ClippedShape>>,
 }
 
 impl DragAndDrop {
     pub(crate) fn register(ctx: &Context) {
-        ctx.on_end_frame("debug_text", std::sync::Arc::new(Self::end_frame));
+        ctx.on_end_frame("drag_and_drop", std::sync::Arc::new(Self::end_frame));
     }
 
     fn end_frame(ctx: &Context) {
-        let pointer_released = ctx.input(|i| i.pointer.any_released());
+        let viewport_id = ctx.viewport_id();
+        let (pointer_released, pointer_pos, has_pointer, time) = ctx.input(|i| {
+            (
+                i.pointer.any_released(),
+                i.pointer.latest_pos(),
+                i.pointer.has_pointer(),
+                i.time,
+            )
+        });
+
+        // Where is the pointer on screen, and which viewport is it over?
+        let screen_pos = pointer_pos.and_then(|pos| ctx.viewport_to_screen_pos(pos));
+        let viewport_under_pointer = screen_pos.and_then(|pos| viewport_at_screen_pos(ctx, pos));
+        let known_viewports: ViewportIdSet =
+            ctx.input(|i| i.raw.viewports.keys().copied().collect());
 
         let mut is_dragging = false;
+        let mut repaint_viewports = [None; 2];
 
         ctx.data_mut(|data| {
             let state = data.get_temp_mut_or_default::<Self>(Id::NULL);
 
-            if pointer_released {
-                state.payload = None;
+            if state.payload.is_none() {
+                return;
+            }
+
+            if state.released_in_other_viewport {
+                let target = state.hovered_viewport;
+                let target_exists =
+                    target.map_or(false, |target| known_viewports.contains(&target));
+                if target == Some(viewport_id)
+                    || !target_exists
+                    || HANDOFF_TIMEOUT < time - state.released_time
+                {
+                    // The target viewport has now had its chance to accept the payload,
+                    // or it is gone.
+                    *state = Self::default();
+                } else {
+                    // Keep poking the target viewport until it has run a frame:
+                    repaint_viewports[0] = target;
+                }
+                return;
             }
 
-            is_dragging = state.payload.is_some();
+            // The source viewport usually captures the pointer while dragging,
+            // but on some platforms the viewport under the pointer gets the events instead:
+            let is_tracking_pointer = viewport_id == state.source_viewport || has_pointer;
+            let previous_hovered = state.hovered_viewport;
+            if is_tracking_pointer && screen_pos.is_some() {
+                state.pointer_screen_pos = screen_pos;
+                state.hovered_viewport = viewport_under_pointer;
+            }
+
+            if pointer_released {
+                match state.hovered_viewport {
+                    Some(target) if is_tracking_pointer && target != viewport_id => {
+                        state.released_in_other_viewport = true;
+                        state.released_time = time;
+                        repaint_viewports[0] = Some(target);
+                    }
+                    _ => {
+                        *state = Self::default();
+                    }
+                }
+            } else {
+                is_dragging = true;
+                // The viewport the payload is over needs to paint the ghost,
+                // and the one it just left needs to stop painting it:
+                repaint_viewports = [state.hovered_viewport, previous_hovered]
+                    .map(|hovered| hovered.filter(|&hovered| hovered != viewport_id));
+                if repaint_viewports[0] == repaint_viewports[1] {
+                    repaint_viewports[1] = None;
+                }
+            }
         });
 
+        for repaint_viewport in repaint_viewports.into_iter().flatten() {
+            ctx.request_repaint_of(repaint_viewport);
+        }
+
         if is_dragging {
             ctx.set_cursor_icon(CursorIcon::Grabbing);
+            Self::paint_foreign_ghost(ctx);
         }
     }
 
+    /// Paint the drag ghost if the pointer is over this viewport, but the drag started elsewhere.
+    fn paint_foreign_ghost(ctx: &Context) {
+        let Some(pointer_pos) = Self::foreign_pointer_pos(ctx) else {
+            return;
+        };
+        let ghost = ctx.data(|data| {
+            data.get_temp::<Self>(Id::NULL)
+                .map(|state| state.ghost)
+                .unwrap_or_default()
+        });
+        if ghost.is_empty() {
+            return;
+        }
+
+        let layer_id = LayerId::new(Order::Tooltip, Id::new("dnd_foreign_ghost"));
+        let transform = TSTransform::from_translation(pointer_pos.to_vec2());
+        ctx.graphics_mut(|graphics| {
+            let list = graphics.entry(layer_id);
+            for ClippedShape {
+                clip_rect,
+                mut shape,
+            } in ghost.iter().cloned()
+            {
+                shape.transform(transform);
+                list.add(transform * clip_rect, shape);
+            }
+        });
+    }
+
     /// Set a drag-and-drop payload.
     ///
     /// This can be read by [`Self::payload`] until the pointer is released.
@@ -53,17 +185,37 @@ impl DragAndDrop {
     where
         Payload: Any + Send + Sync,
     {
+        let viewport_id = ctx.viewport_id();
         ctx.data_mut(|data| {
             let state = data.get_temp_mut_or_default::<Self>(Id::NULL);
+            if state.payload.is_none() {
+                state.source_viewport = viewport_id;
+                state.hovered_viewport = Some(viewport_id);
+                state.released_in_other_viewport = false;
+            }
             state.payload = Some(Arc::new(payload));
         });
     }
 
+    /// Set what to paint at the pointer while dragging,
+    /// when the pointer is over a viewport other than the one the drag started in.
+    ///
+    /// The shapes should be positioned relative to the pointer,
+    /// i.e. with the pointer at the origin.
+    ///
+    /// This is done automatically by [`crate::Ui::dnd_drag_source`].
+    pub fn set_ghost(ctx: &Context, ghost: Vec<ClippedShape>) {
+        ctx.data_mut(|data| {
+            let state = data.get_temp_mut_or_default::<Self>(Id::NULL);
+            state.ghost = Arc::new(ghost);
+        });
+    }
+
     /// Clears the payload, setting it to `None`.
     pub fn clear_payload(ctx: &Context) {
         ctx.data_mut(|data| {
             let state = data.get_temp_mut_or_default::<Self>(Id::NULL);
-            state.payload = None;
+            *state = Self::default();
         });
     }
 
@@ -122,4 +274,188 @@ impl DragAndDrop {
             state.map_or(false, |state| state.payload.is_some())
         })
     }
+
+    /// The viewport in which the current drag started, if anything is being dragged.
+    pub fn source_viewport(ctx: &Context) -> Option<ViewportId> {
+        ctx.data(|data| {
+            let state = data.get_temp::<Self>(Id::NULL)?;
+            state.payload.is_some().then_some(state.source_viewport)
+        })
+    }
+
+    /// The viewport the pointer is over while dragging, if known.
+    pub fn hovered_viewport(ctx: &Context) -> Option<ViewportId> {
+        ctx.data(|data| {
+            let state = data.get_temp::<Self>(Id::NULL)?;
+            state.payload.as_ref()?;
+            state.hovered_viewport
+        })
+    }
+
+    /// If a payload from another viewport is being dragged over the current viewport,
+    /// this is where the pointer is, in the coordinates of the current viewport.
+    ///
+    /// Use this instead of the pointer position in [`crate::InputState`],
+    /// since the current viewport often does not receive pointer events
+    /// while another viewport is dragging.
+    pub fn foreign_pointer_pos(ctx: &Context) -> Option<Pos2> {
+        let viewport_id = ctx.viewport_id();
+        let screen_pos = ctx.data(|data| {
+            let state = data.get_temp::<Self>(Id::NULL)?;
+            state.payload.as_ref()?;
+            let is_foreign =
+                state.source_viewport != viewport_id && state.hovered_viewport == Some(viewport_id);
+            is_foreign.then_some(state.pointer_screen_pos).flatten()
+        })?;
+        ctx.screen_to_viewport_pos(screen_pos)
+    }
+
+    /// Was a payload from another viewport released over the current viewport this frame?
+    pub fn released_from_other_viewport(ctx: &Context) -> bool {
+        let viewport_id = ctx.viewport_id();
+        ctx.data(|data| {
+            data.get_temp::<Self>(Id::NULL).map_or(false, |state| {
+                state.payload.is_some()
+                    && state.released_in_other_viewport
+                    && state.hovered_viewport == Some(viewport_id)
+            })
+        })
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// Which viewport contains the given screen position?
+///
+/// Prefers the current viewport, in case viewports overlap.
+fn viewport_at_screen_pos(ctx: &Context, screen_pos: Pos2) -> Option<ViewportId> {
+    let current = ctx.viewport_id();
+    ctx.input(|i| {
+        let contains = |id: &ViewportId| {
+            i.raw.viewports.get(id).map_or(false, |info| {
+                info.minimized != Some(true)
+                    && info
+                        .inner_rect
+                        .map_or(false, |rect| rect.contains(screen_pos))
+            })
+        };
+        if contains(&current) {
+            Some(current)
+        } else {
+            i.raw.viewports.keys().find(|id| contains(id)).copied()
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use crate::*;
+
+    fn other_viewport() -> ViewportId {
+        ViewportId::from_hash_of("other")
+    }
+
+    /// The root viewport is at the top left of the screen, and another one 100 points to the right of it.
+    fn raw_input(time: f64, other_is_open: bool, events: Vec<Event>) -> RawInput {
+        let root_rect = Rect::from_min_size(Pos2::ZERO, vec2(100.0, 100.0));
+        let mut viewports = ViewportIdMap::default();
+        viewports.insert(
+            ViewportId::ROOT,
+            ViewportInfo {
+                inner_rect: Some(root_rect),
+                ..Default::default()
+            },
+        );
+        if other_is_open {
+            viewports.insert(
+                other_viewport(),
+                ViewportInfo {
+                    inner_rect: Some(Rect::from_min_size(pos2(200.0, 0.0), vec2(100.0, 100.0))),
+                    ..Default::default()
+                },
+            );
+        }
+        RawInput {
+            viewports,
+            screen_rect: Some(root_rect),
+            time: Some(time),
+            events,
+            ..Default::default()
+        }
+    }
+
+    fn pointer_button(pressed: bool) -> Event {
+        Event::PointerButton {
+            pos: pos2(250.0, 10.0),
+            button: PointerButton::Primary,
+            pressed,
+            modifiers: Modifiers::NONE,
+        }
+    }
+
+    /// Drag from the root viewport and release over the other viewport.
+    fn drag_to_other_viewport(ctx: &Context) {
+        let app = |ctx: &Context| {
+            if ctx.input(|i| i.pointer.any_pressed()) {
+                DragAndDrop::set_payload(ctx, 42_i32);
+            }
+        };
+        let press = vec![
+            Event::PointerMoved(pos2(10.0, 10.0)),
+            Event::PointerButton {
+                pos: pos2(10.0, 10.0),
+                button: PointerButton::Primary,
+                pressed: true,
+                modifiers: Modifiers::NONE,
+            },
+        ];
+        let _ = ctx.run(raw_input(0.0, true, press), app);
+        let _ = ctx.run(
+            raw_input(0.1, true, vec![Event::PointerMoved(pos2(250.0, 10.0))]),
+            app,
+        );
+        assert_eq!(DragAndDrop::hovered_viewport(ctx), Some(other_viewport()));
+        let _ = ctx.run(raw_input(0.2, true, vec![pointer_button(false)]), app);
+    }
+
+    #[test]
+    fn payload_moving_to_other_viewport_repaints_it() {
+        let ctx = Context::default();
+        let repainted = Arc::new(Mutex::new(Vec::new()));
+        ctx.set_request_repaint_callback({
+            let repainted = repainted.clone();
+            move |info| repainted.lock().unwrap().push(info.viewport_id)
+        });
+
+        drag_to_other_viewport(&ctx);
+
+        assert!(repainted.lock().unwrap().contains(&other_viewport()));
+        assert!(
+            DragAndDrop::has_any_payload(&ctx),
+            "The payload should wait for the other viewport to accept it"
+        );
+    }
+
+    #[test]
+    fn released_payload_times_out() {
+        let ctx = Context::default();
+        drag_to_other_viewport(&ctx);
+
+        // The other viewport never runs a frame:
+        let _ = ctx.run(raw_input(0.5, true, vec![]), |_| {});
+        assert!(DragAndDrop::has_any_payload(&ctx));
+        let _ = ctx.run(raw_input(2.0, true, vec![]), |_| {});
+        assert!(!DragAndDrop::has_any_payload(&ctx));
+    }
+
+    #[test]
+    fn released_payload_is_cleared_when_target_closes() {
+        let ctx = Context::default();
+        drag_to_other_viewport(&ctx);
+
+        let _ = ctx.run(raw_input(0.3, false, vec![]), |_| {});
+        assert!(!DragAndDrop::has_any_payload(&ctx));
+    }
 }
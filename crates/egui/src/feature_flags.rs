@@ -0,0 +1,133 @@
+//! Named on/off switches for experimental features, see [`Context::flag`].
+
+use std::collections::BTreeMap;
+
+use crate::*;
+
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+struct FeatureFlag {
+    /// The value given by the app.
+    default: bool,
+
+    /// Set at runtime, e.g. by QA in [`Context::feature_flags_ui`].
+    enabled: Option<bool>,
+
+    /// Shown on hover in [`Context::feature_flags_ui`].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    description: String,
+}
+
+impl FeatureFlag {
+    fn is_enabled(&self) -> bool {
+        self.enabled.unwrap_or(self.default)
+    }
+}
+
+/// All feature flags, stored in [`Memory::data`] so that runtime changes are persisted.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub(crate) struct FeatureFlags {
+    flags: BTreeMap<String, FeatureFlag>,
+}
+
+impl FeatureFlags {
+    fn with<R>(ctx: &Context, f: impl FnOnce(&mut Self) -> R) -> R {
+        ctx.data_mut(|d| f(d.get_persisted_mut_or_default::<Self>(Id::NULL)))
+    }
+
+    pub(crate) fn register(ctx: &Context, name: &str, default: bool, description: String) {
+        Self::with(ctx, |flags| {
+            let flag = flags.flags.entry(name.to_owned()).or_default();
+            flag.default = default;
+            flag.description = description;
+        });
+    }
+
+    pub(crate) fn is_enabled(ctx: &Context, name: &str) -> bool {
+        Self::with(ctx, |flags| {
+            flags.flags.entry(name.to_owned()).or_default().is_enabled()
+        })
+    }
+
+    pub(crate) fn set_enabled(ctx: &Context, name: &str, enabled: Option<bool>) {
+        Self::with(ctx, |flags| {
+            flags.flags.entry(name.to_owned()).or_default().enabled = enabled;
+        });
+    }
+
+    pub(crate) fn reset(ctx: &Context) {
+        Self::with(ctx, |flags| {
+            for flag in flags.flags.values_mut() {
+                flag.enabled = None;
+            }
+        });
+    }
+
+    pub(crate) fn ui(ctx: &Context, ui: &mut Ui) {
+        let mut flags = Self::with(ctx, |flags| flags.clone());
+        if flags.flags.is_empty() {
+            ui.weak("No feature flags");
+            return;
+        }
+
+        for (name, flag) in &mut flags.flags {
+            ui.horizontal(|ui| {
+                let mut enabled = flag.is_enabled();
+                let mut response = ui.checkbox(&mut enabled, name.as_str());
+                if !flag.description.is_empty() {
+                    response = response.on_hover_text(&flag.description);
+                }
+                if response.changed() {
+                    flag.enabled = Some(enabled);
+                }
+                if flag.enabled.is_some()
+                    && ui
+                        .add_enabled(flag.is_enabled() != flag.default, Button::new("⟲").small())
+                        .on_hover_text(format!("Reset to default ({})", flag.default))
+                        .clicked()
+                {
+                    flag.enabled = None;
+                }
+            });
+        }
+
+        let any_set = flags.flags.values().any(|flag| flag.enabled.is_some());
+        if ui
+            .add_enabled(any_set, Button::new("Reset all to defaults"))
+            .clicked()
+        {
+            for flag in flags.flags.values_mut() {
+                flag.enabled = None;
+            }
+        }
+
+        Self::with(ctx, |stored| *stored = flags);
+    }
+}
+
+#[test]
+fn test_feature_flags() {
+    use crate::test_harness::Harness;
+
+    let mut harness = Harness::new_ui(|ui| {
+        let ctx = ui.ctx().clone();
+        ctx.register_flag("new_editor", true, "The rewritten text editor");
+        ctx.feature_flags_ui(ui);
+    });
+
+    let ctx = harness.ctx().clone();
+    assert!(ctx.flag("new_editor"));
+    assert!(!ctx.flag("unknown"), "unregistered flags are off");
+
+    harness.step();
+    harness.get_by_label("unknown").click();
+    assert!(ctx.flag("unknown"));
+
+    ctx.set_flag("new_editor", false);
+    assert!(!ctx.flag("new_editor"));
+
+    harness.get_by_label("Reset all to defaults").click();
+    assert!(ctx.flag("new_editor"));
+    assert!(!ctx.flag("unknown"));
+}
@@ -0,0 +1,233 @@
+//! A simple built-in profiler, summarizing where the time of each frame goes.
+//!
+//! Turn it on with [`Context::set_profiling`].
+//!
+//! This uses a handful of internal timers, so it works without any external profiler.
+//! For a more detailed picture, use the `puffin` feature.
+
+use web_time::Instant;
+
+use crate::*;
+
+/// Timings and counters for one frame of one viewport.
+///
+/// Only collected when [`Context::set_profiling`] is on.
+/// Get the latest one with [`Context::frame_profile`].
+///
+/// All times are in seconds.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FrameProfile {
+    /// Time from the start of [`Context::begin_frame`] to the end of [`Context::end_frame`].
+    pub frame: f32,
+
+    /// Time spent processing input (and doing hit-tests) in [`Context::begin_frame`].
+    pub input: f32,
+
+    /// Time spent laying out each panel, in the order they were shown.
+    ///
+    /// Nested panels are included in the time of their parent panel.
+    pub panels: Vec<(String, f32)>,
+
+    /// Time spent in [`Context::end_frame`].
+    pub end_frame: f32,
+
+    /// Time spent in [`Context::tessellate`] after this frame.
+    pub tessellation: f32,
+
+    /// Number of [`Galley`]s laid out this frame, i.e. the ones not found in the cache.
+    pub galleys_laid_out: usize,
+
+    /// Number of [`Galley`]s in the cache at the end of the frame.
+    pub galleys_cached: usize,
+
+    /// Number of textures that were created or updated this frame.
+    pub texture_uploads: usize,
+
+    /// Size of all texture uploads this frame, in bytes.
+    pub texture_upload_bytes: usize,
+}
+
+/// Per-viewport state of the profiler.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct FrameProfiler {
+    /// When the current frame started, if we are profiling it.
+    frame_start: Option<Instant>,
+
+    /// The frame in progress.
+    current: FrameProfile,
+
+    /// The last finished frame.
+    last: FrameProfile,
+}
+
+impl FrameProfiler {
+    pub(crate) fn begin_frame(&mut self, start: Option<Instant>) {
+        self.frame_start = start;
+        self.current = FrameProfile::default();
+        if let Some(start) = start {
+            self.current.input = start.elapsed().as_secs_f32();
+        }
+    }
+
+    pub(crate) fn add_panel(&mut self, name: String, seconds: f32) {
+        if self.frame_start.is_some() {
+            self.current.panels.push((name, seconds));
+        }
+    }
+
+    pub(crate) fn end_frame(
+        &mut self,
+        end_frame_start: Instant,
+        fonts: Option<&epaint::Fonts>,
+        textures_delta: &TexturesDelta,
+    ) {
+        let Some(frame_start) = self.frame_start.take() else {
+            return; // profiling was turned on in the middle of the frame
+        };
+
+        let mut profile = std::mem::take(&mut self.current);
+        profile.frame = frame_start.elapsed().as_secs_f32();
+        profile.end_frame = end_frame_start.elapsed().as_secs_f32();
+        if let Some(fonts) = fonts {
+            profile.galleys_laid_out = fonts.num_galleys_laid_out();
+            profile.galleys_cached = fonts.num_galleys_in_cache();
+        }
+        profile.texture_uploads = textures_delta.set.len();
+        profile.texture_upload_bytes = textures_delta
+            .set
+            .iter()
+            .map(|(_, delta)| {
+                delta.image.width() * delta.image.height() * delta.image.bytes_per_pixel()
+            })
+            .sum();
+        self.last = profile;
+    }
+
+    pub(crate) fn set_tessellation_time(&mut self, seconds: f32) {
+        self.last.tessellation = seconds;
+    }
+
+    pub(crate) fn last(&self) -> &FrameProfile {
+        &self.last
+    }
+}
+
+/// Start a timer, if profiling is on.
+pub(crate) fn start_timer(ctx: &Context) -> Option<Instant> {
+    ctx.profiling().then(Instant::now)
+}
+
+/// Record how long a panel took, if [`start_timer`] started a timer.
+pub(crate) fn record_panel(ctx: &Context, start: Option<Instant>, name: impl FnOnce() -> String) {
+    if let Some(start) = start {
+        let seconds = start.elapsed().as_secs_f32();
+        let name = name();
+        ctx.add_panel_profile(name, seconds);
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// Register the overlay showing the [`FrameProfile`].
+///
+/// This is a built-in plugin in egui,
+/// meaning [`Context`] calls this from its `Default` implementation.
+pub(crate) fn register(ctx: &Context) {
+    ctx.on_end_frame("frame_profiler", std::sync::Arc::new(show_overlay));
+}
+
+fn show_overlay(ctx: &Context) {
+    if !ctx.profiling() {
+        return;
+    }
+
+    let profile = ctx.frame_profile();
+
+    let mut open = true;
+    Window::new("⏱ Frame profiler")
+        .id(Id::new("egui_frame_profiler"))
+        .open(&mut open)
+        .resizable(false)
+        .default_pos(ctx.screen_rect().right_top() + vec2(-240.0, 32.0))
+        .show(ctx, |ui| profile.ui(ui));
+
+    if !open {
+        ctx.set_profiling(false);
+    }
+}
+
+impl FrameProfile {
+    /// Show the timings in a table.
+    pub fn ui(&self, ui: &mut Ui) {
+        let Self {
+            frame,
+            input,
+            panels,
+            end_frame,
+            tessellation,
+            galleys_laid_out,
+            galleys_cached,
+            texture_uploads,
+            texture_upload_bytes,
+        } = self;
+
+        let ms = |seconds: &f32| format!("{:.2} ms", 1e3 * seconds);
+
+        Grid::new("frame_profile")
+            .num_columns(2)
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("Frame");
+                ui.monospace(ms(frame));
+                ui.end_row();
+
+                ui.label("Input");
+                ui.monospace(ms(input));
+                ui.end_row();
+
+                for (name, seconds) in panels {
+                    ui.label(name);
+                    ui.monospace(ms(seconds));
+                    ui.end_row();
+                }
+
+                ui.label("End of frame");
+                ui.monospace(ms(end_frame));
+                ui.end_row();
+
+                ui.label("Tessellation");
+                ui.monospace(ms(tessellation));
+                ui.end_row();
+
+                ui.label("Galleys")
+                    .on_hover_text("Laid out this frame / cached");
+                ui.monospace(format!("{galleys_laid_out} / {galleys_cached}"));
+                ui.end_row();
+
+                ui.label("Texture uploads");
+                ui.monospace(format!(
+                    "{texture_uploads} ({:.2} MB)",
+                    *texture_upload_bytes as f64 / 1e6
+                ));
+                ui.end_row();
+            });
+    }
+}
+
+#[test]
+fn test_frame_profile() {
+    let ctx = Context::default();
+    ctx.set_profiling(true);
+    for _ in 0..2 {
+        let _ = ctx.run(Default::default(), |ctx| {
+            CentralPanel::default().show(ctx, |ui| {
+                ui.label("Hello");
+            });
+        });
+    }
+
+    let profile = ctx.frame_profile();
+    assert!(0.0 < profile.frame);
+    assert_eq!(profile.panels.len(), 1);
+    assert_eq!(profile.panels[0].0, "Central panel");
+}
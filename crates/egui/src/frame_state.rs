@@ -33,6 +33,9 @@ pub(crate) struct FrameState {
     /// How much space is used by panels.
     pub(crate) used_by_panels: Rect,
 
+    /// Like [`Self::used_by_panels`], but only counting what the contents of the [`CentralPanel`] used.
+    pub(crate) used_by_contents: Rect,
+
     /// If a tooltip has been shown this frame, where was it?
     /// This is used to prevent multiple tooltips to cover each other.
     /// Initialized to `None` at the start of each frame.
@@ -41,6 +44,13 @@ pub(crate) struct FrameState {
     /// horizontal, vertical
     pub(crate) scroll_target: [Option<(Rangef, Option<Align>)>; 2],
 
+    /// Scroll this rect into view over this many seconds, see [`Response::scroll_to_me_animated`].
+    ///
+    /// Unlike [`Self::scroll_target`] this is not only used by the innermost [`ScrollArea`]:
+    /// each [`ScrollArea`] containing the rect replaces it with the part of itself
+    /// that the rect will be shown in, for the enclosing [`ScrollArea`] to scroll to.
+    pub(crate) animated_scroll_target: Option<(Rect, Option<Align>, f32)>,
+
     #[cfg(feature = "accesskit")]
     pub(crate) accesskit_state: Option<AccessKitFrameState>,
 
@@ -61,8 +71,10 @@ impl Default for FrameState {
             available_rect: Rect::NAN,
             unused_rect: Rect::NAN,
             used_by_panels: Rect::NAN,
+            used_by_contents: Rect::NAN,
             tooltip_state: None,
             scroll_target: [None, None],
+            animated_scroll_target: None,
             #[cfg(feature = "accesskit")]
             accesskit_state: None,
             highlight_this_frame: Default::default(),
@@ -82,8 +94,10 @@ impl FrameState {
             available_rect,
             unused_rect,
             used_by_panels,
+            used_by_contents,
             tooltip_state,
             scroll_target,
+            animated_scroll_target,
             #[cfg(feature = "accesskit")]
             accesskit_state,
             highlight_this_frame,
@@ -97,8 +111,10 @@ impl FrameState {
         *available_rect = input.screen_rect();
         *unused_rect = input.screen_rect();
         *used_by_panels = Rect::NOTHING;
+        *used_by_contents = Rect::NOTHING;
         *tooltip_state = None;
         *scroll_target = [None, None];
+        *animated_scroll_target = None;
 
         #[cfg(debug_assertions)]
         {
@@ -133,6 +149,7 @@ impl FrameState {
         self.available_rect.min.x = panel_rect.max.x;
         self.unused_rect.min.x = panel_rect.max.x;
         self.used_by_panels = self.used_by_panels.union(panel_rect);
+        self.used_by_contents = self.used_by_contents.union(panel_rect);
     }
 
     /// Shrink `available_rect`.
@@ -144,6 +161,7 @@ impl FrameState {
         self.available_rect.max.x = panel_rect.min.x;
         self.unused_rect.max.x = panel_rect.min.x;
         self.used_by_panels = self.used_by_panels.union(panel_rect);
+        self.used_by_contents = self.used_by_contents.union(panel_rect);
     }
 
     /// Shrink `available_rect`.
@@ -155,6 +173,7 @@ impl FrameState {
         self.available_rect.min.y = panel_rect.max.y;
         self.unused_rect.min.y = panel_rect.max.y;
         self.used_by_panels = self.used_by_panels.union(panel_rect);
+        self.used_by_contents = self.used_by_contents.union(panel_rect);
     }
 
     /// Shrink `available_rect`.
@@ -166,12 +185,15 @@ impl FrameState {
         self.available_rect.max.y = panel_rect.min.y;
         self.unused_rect.max.y = panel_rect.min.y;
         self.used_by_panels = self.used_by_panels.union(panel_rect);
+        self.used_by_contents = self.used_by_contents.union(panel_rect);
     }
 
-    pub(crate) fn allocate_central_panel(&mut self, panel_rect: Rect) {
+    /// `content_rect` is the part of the panel that its contents used (including margins).
+    pub(crate) fn allocate_central_panel(&mut self, panel_rect: Rect, content_rect: Rect) {
         // Note: we do not shrink `available_rect`, because
         // we allow windows to cover the CentralPanel.
         self.unused_rect = Rect::NOTHING; // Nothing left unused after this
         self.used_by_panels = self.used_by_panels.union(panel_rect);
+        self.used_by_contents = self.used_by_contents.union(content_rect);
     }
 }
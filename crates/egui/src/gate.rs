@@ -0,0 +1,84 @@
+use crate::WidgetText;
+
+/// How [`crate::Ui::gate`] restricts what the user can do with its contents.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// # let is_admin = false;
+/// # let mut name = String::from("Ferris");
+/// let gate = if is_admin {
+///     egui::Gate::Enabled
+/// } else {
+///     egui::Gate::read_only_with_reason("Only admins can rename users")
+/// };
+/// ui.gate(gate, |ui| {
+///     ui.text_edit_singleline(&mut name);
+/// });
+/// # });
+/// ```
+#[derive(Clone, Default)]
+pub enum Gate {
+    /// No restrictions.
+    #[default]
+    Enabled,
+
+    /// The widgets are grayed out and do not react to input, like in [`crate::Ui::add_enabled_ui`].
+    Disabled {
+        /// Shown when hovering the disabled contents.
+        reason: Option<WidgetText>,
+    },
+
+    /// The widgets show their values like normal, but reject any edits.
+    ///
+    /// See [`crate::Ui::set_read_only`].
+    ReadOnly {
+        /// Shown when hovering the read-only contents.
+        reason: Option<WidgetText>,
+    },
+
+    /// The widgets are invisible, but still take up space, like in [`crate::Ui::add_visible_ui`].
+    Hidden,
+}
+
+impl Gate {
+    /// Disable the contents, without telling the user why.
+    pub fn disabled() -> Self {
+        Self::Disabled { reason: None }
+    }
+
+    /// Disable the contents, and tell the user why when they hover it.
+    pub fn disabled_with_reason(reason: impl Into<WidgetText>) -> Self {
+        Self::Disabled {
+            reason: Some(reason.into()),
+        }
+    }
+
+    /// Make the contents read-only, without telling the user why.
+    pub fn read_only() -> Self {
+        Self::ReadOnly { reason: None }
+    }
+
+    /// Make the contents read-only, and tell the user why when they hover it.
+    pub fn read_only_with_reason(reason: impl Into<WidgetText>) -> Self {
+        Self::ReadOnly {
+            reason: Some(reason.into()),
+        }
+    }
+
+    /// [`Self::Enabled`] if `enabled`, else [`Self::disabled_with_reason`].
+    pub fn enabled_or(enabled: bool, reason: impl Into<WidgetText>) -> Self {
+        if enabled {
+            Self::Enabled
+        } else {
+            Self::disabled_with_reason(reason)
+        }
+    }
+
+    /// Why the contents are restricted, if said.
+    pub fn reason(&self) -> Option<&WidgetText> {
+        match self {
+            Self::Disabled { reason } | Self::ReadOnly { reason } => reason.as_ref(),
+            Self::Enabled | Self::Hidden => None,
+        }
+    }
+}
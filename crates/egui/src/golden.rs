@@ -0,0 +1,479 @@
+//! Golden tests of what widgets paint and which widgets they create.
+//!
+//! [`run_ui`] creates a [`Harness`] with fixed settings (default fonts, dark mode, no animations,
+//! one pixel per point), so that the output only changes when egui itself changes.
+//! [`shapes`] and [`widgets`] turn the last frame into readable text, one line per shape or widget,
+//! which [`assert_golden`] compares with an expected string.
+//!
+//! Numbers are compared with a small tolerance, so that rounding differences don't fail the tests.
+//! When a test fails, the diff and the full actual output are printed, ready to be pasted into the test.
+
+use crate::{test_harness::Harness, *};
+
+/// How much two numbers in a golden string may differ.
+const TOLERANCE: f64 = 0.1;
+
+/// A [`Harness`] with fixed settings showing `add_contents` in a [`CentralPanel`], run until settled.
+pub(crate) fn run_ui<'a>(add_contents: impl FnMut(&mut Ui) + 'a) -> Harness<'a> {
+    let mut harness = Harness::new_ui(add_contents);
+    let ctx = harness.ctx().clone();
+    ctx.set_fonts(FontDefinitions::default());
+    ctx.options_mut(|options| {
+        options.theme_preference = ThemePreference::Dark;
+        options.animations_enabled = false;
+        options.zoom_factor = 1.0;
+    });
+    ctx.set_visuals(Visuals::dark());
+    harness.set_size(vec2(400.0, 300.0));
+    harness
+}
+
+/// All shapes painted in the last frame, one per line, in paint order.
+pub(crate) fn shapes(harness: &Harness<'_>) -> String {
+    let mut lines = vec![];
+    for clipped in &harness.output().shapes {
+        shape_lines(&clipped.shape, &mut lines);
+    }
+    lines.join("\n")
+}
+
+fn shape_lines(shape: &Shape, lines: &mut Vec<String>) {
+    match shape {
+        Shape::Noop => {}
+        Shape::Vec(shapes) => {
+            for shape in shapes {
+                shape_lines(shape, lines);
+            }
+        }
+        Shape::Circle(circle) => lines.push(format!(
+            "Circle {} r={:.1} fill={} stroke={}",
+            pos(circle.center),
+            circle.radius,
+            color(circle.fill),
+            stroke(circle.stroke)
+        )),
+        Shape::LineSegment { points, stroke: s } => lines.push(format!(
+            "Line {} {} stroke={}",
+            pos(points[0]),
+            pos(points[1]),
+            stroke(*s)
+        )),
+        Shape::Path(path) => lines.push(format!(
+            "Path {} points={} fill={} stroke={}",
+            if path.closed { "closed" } else { "open" },
+            path.points
+                .iter()
+                .map(|p| pos(*p))
+                .collect::<Vec<_>>()
+                .join(" "),
+            color(path.fill),
+            stroke(path.stroke)
+        )),
+        Shape::Rect(rect_shape) => {
+            let Rounding { nw, ne, sw, se } = rect_shape.rounding;
+            let rounding = if nw == ne && nw == sw && nw == se {
+                format!("{nw:.1}")
+            } else {
+                format!("[{nw:.1} {ne:.1} {sw:.1} {se:.1}]")
+            };
+            lines.push(format!(
+                "Rect {} rounding={rounding} fill={} stroke={}",
+                rect(rect_shape.rect),
+                color(rect_shape.fill),
+                stroke(rect_shape.stroke)
+            ));
+        }
+        Shape::Text(text) => lines.push(format!(
+            "Text {} {:?} color={}",
+            pos(text.pos),
+            text.galley.text(),
+            color(text.override_text_color.unwrap_or_else(|| text_color(text)))
+        )),
+        Shape::Mesh(mesh) => lines.push(format!(
+            "Mesh {} vertices={} indices={}",
+            rect(mesh.calc_bounds()),
+            mesh.vertices.len(),
+            mesh.indices.len()
+        )),
+        Shape::QuadraticBezier(bezier) => lines.push(format!(
+            "QuadraticBezier {}",
+            bezier.points.map(pos).join(" ")
+        )),
+        Shape::CubicBezier(bezier) => {
+            lines.push(format!("CubicBezier {}", bezier.points.map(pos).join(" ")))
+        }
+        Shape::Callback(callback) => lines.push(format!("Callback {}", rect(callback.rect))),
+    }
+}
+
+/// The color of the first glyph of the text, or the fallback color.
+fn text_color(text: &epaint::TextShape) -> Color32 {
+    text.galley
+        .job
+        .sections
+        .first()
+        .map(|section| section.format.color)
+        .filter(|c| *c != Color32::PLACEHOLDER)
+        .unwrap_or(text.fallback_color)
+}
+
+/// All widgets of the last frame that reported a [`WidgetInfo`], one per line,
+/// grouped by layer and in the order they were created.
+pub(crate) fn widgets(harness: &Harness<'_>) -> String {
+    harness.ctx().widget_rects_prev_frame(|widgets| {
+        let mut layers: Vec<_> = widgets.layers().collect();
+        layers.sort_by_key(|(layer_id, _)| (layer_id.order, layer_id.id.value()));
+        layers
+            .into_iter()
+            .flat_map(|(_, rects)| rects.iter())
+            .filter_map(|widget_rect| {
+                let info = widgets.info(widget_rect.id)?;
+                Some(widget_line(widget_rect, info))
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    })
+}
+
+fn widget_line(widget_rect: &WidgetRect, info: &WidgetInfo) -> String {
+    let mut line = format!(
+        "{:?} id={} {}",
+        info.typ,
+        widget_rect.id.short_debug_format(),
+        rect(widget_rect.rect)
+    );
+    if let Some(label) = &info.label {
+        line += &format!(" label={label:?}");
+    }
+    if let Some(text) = &info.current_text_value {
+        line += &format!(" text={text:?}");
+    }
+    if let Some(selected) = info.selected {
+        line += &format!(" selected={selected}");
+    }
+    if let Some(value) = info.value {
+        line += &format!(" value={value:.1}");
+    }
+    if !widget_rect.enabled {
+        line += " disabled";
+    }
+    line
+}
+
+fn pos(pos: Pos2) -> String {
+    format!("[{:.1} {:.1}]", pos.x, pos.y)
+}
+
+fn rect(rect: Rect) -> String {
+    format!("{}..{}", pos(rect.min), pos(rect.max))
+}
+
+fn color(color: Color32) -> String {
+    let [r, g, b, a] = color.to_array();
+    format!("#{r:02x}{g:02x}{b:02x}{a:02x}")
+}
+
+fn stroke(stroke: Stroke) -> String {
+    if stroke.is_empty() {
+        "none".to_owned()
+    } else {
+        format!("{:.1} {}", stroke.width, color(stroke.color))
+    }
+}
+
+/// Panics with a readable diff unless `actual` matches `expected`.
+///
+/// Leading and trailing whitespace and empty lines are ignored,
+/// so `expected` can be an indented multi-line string.
+/// Numbers may differ by a small tolerance.
+#[track_caller]
+pub(crate) fn assert_golden(actual: &str, expected: &str) {
+    let actual = golden_lines(actual);
+    let expected = golden_lines(expected);
+
+    if actual.len() == expected.len()
+        && actual.iter().zip(&expected).all(|(a, e)| lines_match(a, e))
+    {
+        return;
+    }
+
+    panic!(
+        "Golden mismatch (- expected, + actual):\n{}\n\nActual:\n{}\n",
+        diff(&expected, &actual),
+        actual.join("\n")
+    );
+}
+
+fn golden_lines(text: &str) -> Vec<&str> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect()
+}
+
+/// A line is either text, which must match exactly, or a number, which must match within [`TOLERANCE`].
+#[derive(Debug, PartialEq)]
+enum Token<'a> {
+    Text(&'a str),
+    Number(f64),
+}
+
+fn tokenize(line: &str) -> Vec<Token<'_>> {
+    let bytes = line.as_bytes();
+    let is_number_start = |i: usize| {
+        bytes[i].is_ascii_digit()
+            || (bytes[i] == b'-' && bytes.get(i + 1).map_or(false, u8::is_ascii_digit))
+    };
+
+    let mut tokens = vec![];
+    let mut i = 0;
+    while i < bytes.len() {
+        let start = i;
+        if is_number_start(i) {
+            i += 1;
+            while i < bytes.len() && (bytes[i].is_ascii_digit() || bytes[i] == b'.') {
+                i += 1;
+            }
+            match line[start..i].parse() {
+                Ok(number) => tokens.push(Token::Number(number)),
+                Err(_) => tokens.push(Token::Text(&line[start..i])),
+            }
+        } else {
+            while i < bytes.len() && !is_number_start(i) {
+                i += 1;
+            }
+            tokens.push(Token::Text(&line[start..i]));
+        }
+    }
+    tokens
+}
+
+fn lines_match(actual: &str, expected: &str) -> bool {
+    let actual = tokenize(actual);
+    let expected = tokenize(expected);
+    actual.len() == expected.len()
+        && actual.iter().zip(&expected).all(|pair| match pair {
+            (Token::Number(a), Token::Number(e)) => (a - e).abs() <= TOLERANCE + 1e-6,
+            (a, e) => a == e,
+        })
+}
+
+/// A line-by-line diff, based on the longest common subsequence of matching lines.
+fn diff(expected: &[&str], actual: &[&str]) -> String {
+    let (n, m) = (expected.len(), actual.len());
+
+    // lcs[i][j] is the length of the longest common subsequence of expected[i..] and actual[j..]
+    let mut lcs = vec![vec![0_usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if lines_match(actual[j], expected[i]) {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut out = vec![];
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && lines_match(actual[j], expected[i]) {
+            out.push(format!("  {}", actual[j]));
+            i += 1;
+            j += 1;
+        } else if j < m && (i == n || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            out.push(format!("+ {}", actual[j]));
+            j += 1;
+        } else {
+            out.push(format!("- {}", expected[i]));
+            i += 1;
+        }
+    }
+    out.join("\n")
+}
+
+#[test]
+fn test_golden_comparison() {
+    assert!(lines_match("Rect [8.0 8.0]", "Rect [8.04 7.95]"));
+    assert!(!lines_match("Rect [8.0 8.0]", "Rect [8.5 8.0]"));
+    assert!(!lines_match("Rect [8.0 8.0]", "Circle [8.0 8.0]"));
+    assert!(lines_match(
+        "Line [-1.0 2.0] #1b1b1bff",
+        "Line [-1.0 2.0] #1b1b1bff"
+    ));
+    assert!(!lines_match("fill=#1b1b1bff", "fill=#1c1b1bff"));
+
+    assert_golden("a 1\nb 2\n", "\n    a 1.0\n    b 2.0\n");
+
+    let diff = diff(&["a", "b", "c"], &["a", "x", "c", "d"]);
+    assert_eq!(diff, "  a\n+ x\n- b\n  c\n+ d");
+}
+
+#[test]
+#[should_panic(expected = "Golden mismatch")]
+fn test_golden_mismatch() {
+    assert_golden("a 1", "a 2");
+}
+
+#[test]
+fn test_golden_button() {
+    let harness = run_ui(|ui| {
+        let _ = ui.button("OK");
+    });
+    assert_golden(
+        &widgets(&harness),
+        r#"
+        Button id=7C42 [8.0 8.0]..[33.5 26.0] label="OK"
+        "#,
+    );
+    assert_golden(
+        &shapes(&harness),
+        r#"
+        Rect [0.0 0.0]..[400.0 300.0] rounding=0.0 fill=#1b1b1bff stroke=none
+        Rect [8.0 8.0]..[33.5 26.0] rounding=2.0 fill=#3c3c3cff stroke=none
+        Text [12.0 10.0] "OK" color=#b4b4b4ff
+        "#,
+    );
+}
+
+#[test]
+fn test_golden_checkbox() {
+    let mut checked = false;
+    let mut harness = run_ui(|ui| {
+        ui.checkbox(&mut checked, "Check me");
+    });
+    assert_golden(
+        &widgets(&harness),
+        r#"
+        Checkbox id=7C42 [8.0 8.0]..[80.8 26.0] label="Check me" selected=false
+        "#,
+    );
+    assert_golden(
+        &shapes(&harness),
+        r#"
+        Rect [0.0 0.0]..[400.0 300.0] rounding=0.0 fill=#1b1b1bff stroke=none
+        Rect [8.0 10.0]..[22.0 24.0] rounding=2.0 fill=#3c3c3cff stroke=none
+        Text [26.0 10.0] "Check me" color=#b4b4b4ff
+        "#,
+    );
+
+    harness.get_by_label("Check me").click();
+    assert_golden(
+        &widgets(&harness),
+        r#"
+        Checkbox id=7C42 [8.0 8.0]..[80.8 26.0] label="Check me" selected=true
+        "#,
+    );
+    assert_golden(
+        &shapes(&harness),
+        r#"
+        Rect [0.0 0.0]..[400.0 300.0] rounding=0.0 fill=#1b1b1bff stroke=none
+        Rect [7.0 9.0]..[23.0 25.0] rounding=3.0 fill=#464646ff stroke=1.0 #969696ff
+        Path open points=[11.0 17.0] [15.0 21.0] [19.0 13.0] fill=#00000000 stroke=1.5 #f0f0f0ff
+        Text [26.0 10.0] "Check me" color=#f0f0f0ff
+        "#,
+    );
+}
+
+#[test]
+fn test_golden_horizontal_layout() {
+    let mut on = true;
+    let harness = run_ui(|ui| {
+        ui.horizontal(|ui| {
+            ui.label("Name");
+            let _ = ui.button("A");
+            ui.checkbox(&mut on, "B");
+        });
+        let _ = ui.button("Below");
+    });
+    assert_golden(
+        &widgets(&harness),
+        r#"
+        Label id=EF0D [8.0 10.0]..[40.8 24.0] label="Name"
+        Button id=85E5 [48.8 8.0]..[64.8 26.0] label="A"
+        Checkbox id=F9AA [72.8 8.0]..[112.8 26.0] label="B" selected=true
+        Button id=7808 [8.0 29.0]..[50.7 47.0] label="Below"
+        "#,
+    );
+}
+
+#[test]
+fn test_golden_disabled() {
+    let harness = run_ui(|ui| {
+        ui.add_enabled(false, Button::new("Disabled"));
+        ui.add_enabled_ui(false, |ui| {
+            let _ = ui.button("Also disabled");
+        });
+        let _ = ui.button("Enabled");
+    });
+    assert_golden(
+        &widgets(&harness),
+        r#"
+        Button id=7C42 [8.0 8.0]..[63.3 26.0] label="Disabled" disabled
+        Button id=9152 [8.0 29.0]..[87.3 47.0] label="Also disabled" disabled
+        Button id=2F26 [8.0 50.0]..[60.3 68.0] label="Enabled"
+        "#,
+    );
+}
+
+#[test]
+fn test_golden_collapsing_header() {
+    let mut harness = run_ui(|ui| {
+        ui.collapsing("Details", |ui| {
+            ui.label("Hidden until opened");
+        });
+    });
+    assert_golden(
+        &widgets(&harness),
+        r#"
+        CollapsingHeader id=C96A [8.0 8.0]..[68.3 26.0] label="Details"
+        "#,
+    );
+
+    harness.get_by_label("Details").click();
+    assert_golden(
+        &widgets(&harness),
+        r#"
+        CollapsingHeader id=C96A [8.0 8.0]..[68.3 26.0] label="Details"
+        Label id=BB85 [26.0 29.0]..[139.3 43.0] label="Hidden until opened"
+        "#,
+    );
+}
+
+#[test]
+fn test_golden_radio_buttons() {
+    let mut choice = 0;
+    let mut harness = run_ui(|ui| {
+        ui.radio_value(&mut choice, 0, "First");
+        ui.radio_value(&mut choice, 1, "Second");
+    });
+    harness.get_by_label("Second").click();
+    assert_golden(
+        &widgets(&harness),
+        r#"
+        RadioButton id=7C42 [8.0 8.0]..[49.8 26.0] label="First" selected=false
+        RadioButton id=7808 [8.0 29.0]..[66.3 47.0] label="Second" selected=true
+        "#,
+    );
+}
+
+#[test]
+fn test_golden_slider_and_text_edit() {
+    let mut value = 50.0;
+    let mut text = String::new();
+    let mut harness = run_ui(|ui| {
+        ui.add(Slider::new(&mut value, 0.0..=100.0));
+        ui.text_edit_singleline(&mut text);
+    });
+    harness
+        .get_by(|info| info.typ == WidgetType::TextEdit)
+        .type_text("Hello");
+    assert_golden(
+        &widgets(&harness),
+        r#"
+        Slider id=EF0D [8.0 8.0]..[108.0 26.0] value=50.0
+        DragValue id=85E5 [116.0 8.0]..[156.0 26.0] value=50.0
+        TextEdit id=9152 [8.0 29.0]..[296.0 47.4] text="Hello"
+        "#,
+    );
+}
@@ -38,6 +38,26 @@ impl State {
         self.row_heights.get(row).copied()
     }
 
+    /// Make sure a cell spanning several columns and/or rows fits,
+    /// by growing the last column and/or row it spans.
+    fn fit_span(&mut self, span: &CellSpan, min_cell_size: Vec2, spacing: Vec2) {
+        for col in span.col..span.col + span.cols {
+            self.set_min_col_width(col, min_cell_size.x);
+        }
+        for row in span.row..span.row + span.rows {
+            self.set_min_row_height(row, min_cell_size.y);
+        }
+
+        let cols = span.col..span.col + span.cols;
+        let width = self.col_widths[cols].iter().sum::<f32>() + (span.cols - 1) as f32 * spacing.x;
+        self.col_widths[span.col + span.cols - 1] += (span.size.x - width).at_least(0.0);
+
+        let rows = span.row..span.row + span.rows;
+        let height =
+            self.row_heights[rows].iter().sum::<f32>() + (span.rows - 1) as f32 * spacing.y;
+        self.row_heights[span.row + span.rows - 1] += (span.size.y - height).at_least(0.0);
+    }
+
     fn full_width(&self, x_spacing: f32) -> f32 {
         self.col_widths.iter().sum::<f32>()
             + (self.col_widths.len().at_least(1) - 1) as f32 * x_spacing
@@ -46,6 +66,18 @@ impl State {
 
 // ----------------------------------------------------------------------------
 
+/// A cell covering more than one column or row, see [`Grid::cell_span`].
+#[derive(Clone, Debug)]
+struct CellSpan {
+    col: usize,
+    row: usize,
+    cols: usize,
+    rows: usize,
+
+    /// The size of the contents.
+    size: Vec2,
+}
+
 // type alias for boxed function to determine row color during grid generation
 type ColorPickerFn = Box<dyn Send + Sync + Fn(usize, &Style) -> Option<Color32>>;
 
@@ -75,6 +107,15 @@ pub(crate) struct GridLayout {
     // Cursor:
     col: usize,
     row: usize,
+
+    /// How many columns and rows the next cell covers.
+    next_span: (usize, usize),
+
+    /// How to align the next cell, if not [`Align2::LEFT_CENTER`].
+    next_align: Option<Align2>,
+
+    /// Cells covering more than one column or row so far.
+    spans: Vec<CellSpan>,
 }
 
 impl GridLayout {
@@ -109,6 +150,9 @@ impl GridLayout {
 
             col: 0,
             row: 0,
+            next_span: (1, 1),
+            next_align: None,
+            spans: Vec::new(),
         }
     }
 }
@@ -126,12 +170,57 @@ impl GridLayout {
             .unwrap_or(self.min_cell_size.y)
     }
 
+    /// The width of the columns the next cell covers, last frame.
+    fn prev_span_width(&self) -> f32 {
+        let cols = self.next_span.0;
+        (self.col..self.col + cols)
+            .map(|col| self.prev_col_width(col))
+            .sum::<f32>()
+            + (cols - 1) as f32 * self.spacing.x
+    }
+
+    /// The height of the rows the next cell covers, last frame.
+    fn prev_span_height(&self) -> f32 {
+        let rows = self.next_span.1;
+        (self.row..self.row + rows)
+            .map(|row| self.prev_row_height(row))
+            .sum::<f32>()
+            + (rows - 1) as f32 * self.spacing.y
+    }
+
+    /// Is this cell covered by a cell spanning several rows from a row above?
+    fn is_covered(&self, col: usize, row: usize) -> bool {
+        self.spans.iter().any(|span| {
+            span.row < row
+                && row < span.row + span.rows
+                && (span.col..span.col + span.cols).contains(&col)
+        })
+    }
+
+    /// Move the cursor past cells covered from rows above.
+    fn skip_covered_cells(&mut self, cursor: &mut Rect) {
+        while self.is_covered(self.col, self.row) {
+            cursor.min.x += self.prev_col_width(self.col) + self.spacing.x;
+            self.col += 1;
+        }
+    }
+
+    /// Let the next cell cover this many columns and rows.
+    pub(crate) fn set_next_span(&mut self, cols: usize, rows: usize) {
+        self.next_span = (cols.at_least(1), rows.at_least(1));
+    }
+
+    /// Align the next cell like this.
+    pub(crate) fn set_next_align(&mut self, align: Align2) {
+        self.next_align = Some(align);
+    }
+
     pub(crate) fn wrap_text(&self) -> bool {
         self.max_cell_size.x.is_finite()
     }
 
     pub(crate) fn available_rect(&self, region: &Region) -> Rect {
-        let is_last_column = Some(self.col + 1) == self.num_columns;
+        let is_last_column = Some(self.col + self.next_span.0) == self.num_columns;
 
         let width = if is_last_column {
             // The first frame we don't really know the widths of the previous columns,
@@ -147,6 +236,8 @@ impl GridLayout {
         } else if self.max_cell_size.x.is_finite() {
             // TODO(emilk): should probably heed `prev_state` here too
             self.max_cell_size.x
+        } else if self.next_span.0 > 1 {
+            self.prev_span_width()
         } else {
             // If we want to allow width-filling widgets like [`Separator`] in one of the first cells
             // then we need to make sure they don't spill out of the first cell:
@@ -170,16 +261,20 @@ impl GridLayout {
     }
 
     pub(crate) fn next_cell(&self, cursor: Rect, child_size: Vec2) -> Rect {
-        let width = self.prev_state.col_width(self.col).unwrap_or(0.0);
-        let height = self.prev_row_height(self.row);
+        let width = if self.next_span.0 > 1 {
+            self.prev_span_width()
+        } else {
+            self.prev_state.col_width(self.col).unwrap_or(0.0)
+        };
+        let height = self.prev_span_height();
         let size = child_size.max(vec2(width, height));
         Rect::from_min_size(cursor.min, size)
     }
 
-    #[allow(clippy::unused_self)]
     pub(crate) fn align_size_within_rect(&self, size: Vec2, frame: Rect) -> Rect {
-        // TODO(emilk): allow this alignment to be customized
-        Align2::LEFT_CENTER.align_size_within_rect(size, frame)
+        self.next_align
+            .unwrap_or(Align2::LEFT_CENTER)
+            .align_size_within_rect(size, frame)
     }
 
     pub(crate) fn justify_and_align(&self, frame: Rect, size: Vec2) -> Rect {
@@ -212,13 +307,31 @@ impl GridLayout {
             }
         }
 
-        self.curr_state
-            .set_min_col_width(self.col, widget_rect.width().max(self.min_cell_size.x));
-        self.curr_state
-            .set_min_row_height(self.row, widget_rect.height().max(self.min_cell_size.y));
+        let (cols, rows) = self.next_span;
+        if cols == 1 {
+            self.curr_state
+                .set_min_col_width(self.col, widget_rect.width().max(self.min_cell_size.x));
+        }
+        if rows == 1 {
+            self.curr_state
+                .set_min_row_height(self.row, widget_rect.height().max(self.min_cell_size.y));
+        }
+        if cols > 1 || rows > 1 {
+            self.spans.push(CellSpan {
+                col: self.col,
+                row: self.row,
+                cols,
+                rows,
+                size: widget_rect.size(),
+            });
+        }
+
+        cursor.min.x += self.prev_span_width() + self.spacing.x;
+        self.col += cols;
+        self.next_span = (1, 1);
+        self.next_align = None;
 
-        cursor.min.x += self.prev_col_width(self.col) + self.spacing.x;
-        self.col += 1;
+        self.skip_covered_cells(cursor);
     }
 
     fn paint_row(&mut self, cursor: &Rect, painter: &Painter) {
@@ -253,11 +366,16 @@ impl GridLayout {
         self.row += 1;
 
         self.paint_row(cursor, painter);
+        self.skip_covered_cells(cursor);
     }
 
     pub(crate) fn save(&self) {
-        if self.curr_state != self.prev_state {
-            self.curr_state.clone().store(&self.ctx, self.id);
+        let mut curr_state = self.curr_state.clone();
+        for span in &self.spans {
+            curr_state.fit_span(span, self.min_cell_size, self.spacing);
+        }
+        if curr_state != self.prev_state {
+            curr_state.store(&self.ctx, self.id);
             self.ctx.request_repaint();
         }
     }
@@ -268,7 +386,10 @@ impl GridLayout {
 /// A simple grid layout.
 ///
 /// The cells are always laid out left to right, top-down.
-/// The contents of each cell will be aligned to the left and center.
+/// The contents of each cell will be aligned to the left and center,
+/// unless changed with [`Grid::cell_align`].
+///
+/// A cell can cover several columns and/or rows with [`Grid::cell_span`].
 ///
 /// If you want to add multiple widgets to a cell you need to group them with
 /// [`Ui::horizontal`], [`Ui::vertical`] etc.
@@ -391,6 +512,44 @@ impl Grid {
 }
 
 impl Grid {
+    /// Let the next cell added to the grid `ui` cover `cols` columns and `rows` rows.
+    ///
+    /// The cells it covers in the rows below are skipped when you add cells to those rows.
+    /// Does nothing outside of a [`Grid`].
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// egui::Grid::new("spans").show(ui, |ui| {
+    ///     egui::Grid::cell_span(ui, 2, 1);
+    ///     ui.heading("A heading over two columns");
+    ///     ui.end_row();
+    ///
+    ///     egui::Grid::cell_span(ui, 1, 2);
+    ///     ui.label("Two rows high");
+    ///     ui.label("First row, second column");
+    ///     ui.end_row();
+    ///
+    ///     ui.label("Second row, second column");
+    ///     ui.end_row();
+    /// });
+    /// # });
+    /// ```
+    pub fn cell_span(ui: &mut Ui, cols: usize, rows: usize) {
+        if let Some(grid) = ui.grid_mut() {
+            grid.set_next_span(cols, rows);
+        }
+    }
+
+    /// Align the contents of the next cell added to the grid `ui` like this,
+    /// instead of [`Align2::LEFT_CENTER`].
+    ///
+    /// Does nothing outside of a [`Grid`].
+    pub fn cell_align(ui: &mut Ui, align: Align2) {
+        if let Some(grid) = ui.grid_mut() {
+            grid.set_next_align(align);
+        }
+    }
+
     pub fn show<R>(self, ui: &mut Ui, add_contents: impl FnOnce(&mut Ui) -> R) -> InnerResponse<R> {
         self.show_dyn(ui, Box::new(add_contents))
     }
@@ -462,3 +621,46 @@ fn striped_row_color(row: usize, style: &Style) -> Option<Color32> {
     }
     None
 }
+
+#[test]
+fn test_grid_cell_span() {
+    use crate::test_harness::Harness;
+
+    let harness = Harness::new_ui(|ui| {
+        Grid::new("grid").show(ui, |ui| {
+            Grid::cell_span(ui, 2, 1);
+            ui.label("A very wide heading covering two columns");
+            ui.end_row();
+
+            Grid::cell_span(ui, 1, 2);
+            Grid::cell_align(ui, Align2::CENTER_CENTER);
+            ui.label("Tall");
+            ui.label("a");
+            ui.end_row();
+
+            ui.label("b");
+            ui.end_row();
+
+            ui.label("c");
+            Grid::cell_align(ui, Align2::RIGHT_CENTER);
+            ui.label("d");
+            ui.end_row();
+        });
+    });
+
+    let rect = |label: &str| harness.query_by_label(label).unwrap().rect.rect;
+    let heading = rect("A very wide heading covering two columns");
+    let (tall, a, b, c, d) = (rect("Tall"), rect("a"), rect("b"), rect("c"), rect("d"));
+
+    // The covered cell is skipped, so "b" ends up in the second column:
+    assert_eq!(a.left(), b.left());
+    assert!(b.top() > a.bottom());
+
+    // The tall cell is centered in its two rows:
+    assert!(tall.top() > a.top() && tall.bottom() < b.bottom());
+
+    // The heading makes the two columns wide enough for it:
+    assert_eq!(c.left(), heading.left());
+    assert!(d.right() >= heading.right() - 0.5);
+    assert!(d.left() > a.left(), "the last cell is right-aligned");
+}
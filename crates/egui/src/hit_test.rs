@@ -53,10 +53,9 @@ pub fn hit_test(
     let mut closest_hit = None;
 
     // First pass: find the few widgets close to the given position, sorted back-to-front.
-    let mut close: Vec<WidgetRect> = layer_order
-        .iter()
-        .filter(|layer| layer.order.allow_interaction())
-        .flat_map(|&layer_id| widgets.get_layer(layer_id))
+    let mut close: Vec<WidgetRect> = widgets
+        .iter_back_to_front(layer_order)
+        .filter(|w| w.layer_id.order.allow_interaction())
         .filter(|&w| {
             let pos_in_layer = pos_in_layers.get(&w.layer_id).copied().unwrap_or(pos);
             let dist_sq = w.interact_rect.distance_sq_to_pos(pos_in_layer);
@@ -418,4 +417,39 @@ mod tests {
         assert_eq!(hits.click.unwrap().id, Id::new("fg-right-label"));
         assert_eq!(hits.drag.unwrap().id, Id::new("fg-right-label"));
     }
+
+    #[test]
+    fn widget_hits_at_window_over_panel() {
+        let ctx = Context::default();
+        let mut below = Id::NULL;
+        let mut above = Id::NULL;
+        for _ in 0..2 {
+            let _ = ctx.run(Default::default(), |ctx| {
+                CentralPanel::default().show(ctx, |ui| {
+                    below = ui.button("Below").id;
+                });
+                Window::new("Window")
+                    .fixed_pos(pos2(0.0, 0.0))
+                    .show(ctx, |ui| {
+                        above = ui.button("Above").id;
+                    });
+            });
+        }
+
+        let layer_order = ctx.widget_layer_order();
+        assert_eq!(layer_order.first(), Some(&LayerId::background()));
+
+        let (order, above_rect) = ctx.widget_rects_prev_frame(|widgets| {
+            let order: Vec<Id> = widgets
+                .iter_back_to_front(&layer_order)
+                .map(|w| w.id)
+                .filter(|id| *id == below || *id == above)
+                .collect();
+            (order, *widgets.get(above).unwrap())
+        });
+        assert_eq!(order, vec![below, above]);
+
+        let hits = ctx.widget_hits_at(above_rect.interact_rect.center());
+        assert_eq!(hits.click.map(|w| w.id), Some(above));
+    }
 }
@@ -0,0 +1,187 @@
+//! Recording the input of a [`Context`], and replaying it later.
+//!
+//! Useful for reproducing bugs, and for automated demos.
+//! Start with [`Context::start_input_recording`], and feed it back with [`Context::replay`].
+
+use std::time::Duration;
+
+use crate::*;
+
+/// The [`RawInput`] of one frame of an [`InputRecording`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct RecordedFrame {
+    /// Seconds since the recording started.
+    pub time: f64,
+
+    /// The input as given to [`Context::run`], before sanitizing.
+    pub raw_input: RawInput,
+}
+
+/// All the input a [`Context`] got while recording, frame by frame.
+///
+/// Made with [`Context::start_input_recording`] and [`Context::stop_input_recording`].
+/// With the `serde` feature you can save it to a file, e.g. to attach it to a bug report.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct InputRecording {
+    pub frames: Vec<RecordedFrame>,
+}
+
+impl InputRecording {
+    /// Seconds from the first to the last frame.
+    pub fn duration(&self) -> f64 {
+        self.frames.last().map_or(0.0, |frame| frame.time)
+    }
+
+    /// Record the input of another frame.
+    pub(crate) fn record(&mut self, raw_input: &RawInput, start_time: &mut Option<f64>) {
+        let time = match (raw_input.time, *start_time) {
+            (Some(time), Some(start_time)) => time - start_time,
+            (Some(time), None) => {
+                *start_time = Some(time);
+                0.0
+            }
+            // Without a clock we assume the frames come as predicted:
+            (None, _) => self
+                .frames
+                .last()
+                .map_or(0.0, |last| last.time + raw_input.predicted_dt as f64),
+        };
+        self.frames.push(RecordedFrame {
+            time,
+            raw_input: raw_input.clone(),
+        });
+    }
+}
+
+/// An [`InputRecording`] being fed to the [`Context`] instead of the real input.
+pub(crate) struct InputReplay {
+    recording: InputRecording,
+
+    /// The index of the frame to replay next.
+    next_frame: usize,
+
+    /// The time of the real input when the replay started.
+    start_time: Option<f64>,
+}
+
+impl InputReplay {
+    pub fn new(recording: InputRecording) -> Self {
+        Self {
+            recording,
+            next_frame: 0,
+            start_time: None,
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.recording.frames.len() <= self.next_frame
+    }
+
+    /// Replace the real input with the next recorded frame, if it is for the same viewport.
+    ///
+    /// `time` is the time of the latest input, for when the real input has none.
+    /// Returns which viewport the next recorded frame is for, and how long to wait until it,
+    /// so that viewport can be asked to repaint. Otherwise a multi-viewport replay could stall.
+    pub fn replace(
+        &mut self,
+        raw_input: &mut RawInput,
+        time: f64,
+    ) -> Option<(ViewportId, Duration)> {
+        let frame = self.recording.frames.get(self.next_frame)?;
+        if frame.raw_input.viewport_id != raw_input.viewport_id {
+            // It is another viewport's turn:
+            return Some((frame.raw_input.viewport_id, Duration::ZERO));
+        }
+        self.next_frame += 1;
+
+        // Keep the clock going forward, so animations and double-clicks behave like when recorded:
+        let start_time = *self
+            .start_time
+            .get_or_insert(raw_input.time.unwrap_or(time));
+        *raw_input = frame.raw_input.clone();
+        raw_input.time = Some(start_time + frame.time);
+
+        let next = self.recording.frames.get(self.next_frame)?;
+        Some((
+            next.raw_input.viewport_id,
+            Duration::from_secs_f64((next.time - frame.time).max(0.0)),
+        ))
+    }
+}
+
+#[test]
+fn test_record_and_replay() {
+    use crate::test_harness::Harness;
+    use std::cell::Cell;
+
+    let clicks = Cell::new(0);
+    let mut harness = Harness::new_ui(|ui| {
+        if ui.button("Click me").clicked() {
+            clicks.set(clicks.get() + 1);
+        }
+    });
+    harness.run();
+
+    harness.ctx().start_input_recording();
+    assert!(harness.ctx().is_recording_input());
+    harness.get_by_label("Click me").click();
+    let recording = harness.ctx().stop_input_recording().unwrap();
+    assert!(!harness.ctx().is_recording_input());
+    assert_eq!(clicks.get(), 1);
+    assert!(recording.frames.len() >= 2);
+    assert!(recording.frames.windows(2).all(|w| w[0].time <= w[1].time));
+
+    // Move the real pointer away; the replay clicks the button anyway:
+    harness.hover(pos2(500.0, 500.0));
+    harness.ctx().replay(recording.clone());
+    let mut frames = 0;
+    while harness.ctx().is_replaying() {
+        harness.step();
+        frames += 1;
+    }
+    assert_eq!(frames, recording.frames.len());
+    assert_eq!(clicks.get(), 2);
+
+    // The clock keeps going forward:
+    let time = harness.ctx().input(|i| i.time);
+    harness.step();
+    assert!(time <= harness.ctx().input(|i| i.time));
+}
+
+#[test]
+fn test_replay_multiple_viewports() {
+    let child = ViewportId::from_hash_of("child");
+    let frame = |time, viewport_id| RecordedFrame {
+        time,
+        raw_input: RawInput {
+            viewport_id,
+            ..Default::default()
+        },
+    };
+    let mut replay = InputReplay::new(InputRecording {
+        frames: vec![frame(0.0, ViewportId::ROOT), frame(0.5, child)],
+    });
+
+    let mut root_input = RawInput::default();
+    assert_eq!(
+        replay.replace(&mut root_input, 0.0),
+        Some((child, Duration::from_secs_f64(0.5)))
+    );
+
+    // The root viewport has to wait, and the child is asked to repaint right away:
+    assert_eq!(
+        replay.replace(&mut RawInput::default(), 0.1),
+        Some((child, Duration::ZERO))
+    );
+    assert!(!replay.is_done());
+
+    let mut child_input = RawInput {
+        viewport_id: child,
+        ..Default::default()
+    };
+    assert_eq!(replay.replace(&mut child_input, 0.2), None);
+    assert_eq!(child_input.time, Some(0.5));
+    assert!(replay.is_done());
+}
@@ -1,3 +1,4 @@
+mod gamepad_state;
 mod touch_state;
 
 use crate::data::input::*;
@@ -5,6 +6,7 @@ use crate::{emath::*, util::History};
 use std::collections::{BTreeMap, HashSet};
 
 pub use crate::Key;
+pub use gamepad_state::GamepadState;
 pub use touch_state::MultiTouchInfo;
 use touch_state::TouchState;
 
@@ -17,6 +19,54 @@ const MAX_CLICK_DURATION: f64 = 0.6; // TODO(emilk): move to settings
 /// The new pointer press must come within this many seconds from previous pointer release
 const MAX_DOUBLE_CLICK_DELAY: f64 = 0.3; // TODO(emilk): move to settings
 
+/// When holding down the primary button counts as a long-press, see [`PointerState::long_press_started`].
+///
+/// A long-press is how touch screen users open context menus.
+///
+/// Set it in [`crate::Options::long_press`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct LongPressOptions {
+    /// How long the primary button must be held down, in seconds.
+    ///
+    /// Default: 0.5
+    pub duration: f32,
+
+    /// How far the pointer may move while held down, in points.
+    ///
+    /// Default: 10.0 (fingers are not very precise).
+    pub max_dist: f32,
+}
+
+impl Default for LongPressOptions {
+    fn default() -> Self {
+        Self {
+            duration: 0.5,
+            max_dist: 10.0,
+        }
+    }
+}
+
+impl LongPressOptions {
+    pub fn ui(&mut self, ui: &mut crate::Ui) {
+        let Self { duration, max_dist } = self;
+        ui.horizontal(|ui| {
+            ui.label("Long-press duration");
+            ui.add(
+                crate::DragValue::new(duration)
+                    .clamp_range(0.1..=5.0)
+                    .speed(0.01)
+                    .suffix(" s"),
+            );
+        });
+        ui.horizontal(|ui| {
+            ui.label("Long-press max movement");
+            ui.add(crate::DragValue::new(max_dist).clamp_range(0.0..=100.0));
+        });
+    }
+}
+
 /// Input state that egui updates each frame.
 ///
 /// You can check if `egui` is using the inputs using
@@ -29,10 +79,18 @@ pub struct InputState {
     /// State of the mouse or simple touch gestures which can be mapped to mouse operations.
     pub pointer: PointerState,
 
+    /// State of any other pointers than [`Self::pointer`], from [`Event::ExtraPointer`].
+    ///
+    /// A pointer is forgotten once it is gone and has no buttons down.
+    pub extra_pointers: BTreeMap<PointerId, PointerState>,
+
     /// State of touches, except those covered by PointerState (like clicks and drags).
     /// (We keep a separate [`TouchState`] for each encountered touch device.)
     touch_states: BTreeMap<TouchDeviceId, TouchState>,
 
+    /// State of the gamepads, from [`Event::GamepadButton`] and [`Event::GamepadAxis`].
+    pub gamepad: GamepadState,
+
     /// Used for smoothing the scroll delta.
     unprocessed_scroll_delta: Vec2,
 
@@ -71,6 +129,12 @@ pub struct InputState {
     /// * `zoom > 1`: pinch spread
     zoom_factor_delta: f32,
 
+    /// Rotation in radians this frame from [`Event::Rotate`].
+    rotation_delta: f32,
+
+    /// Was there an [`Event::SmartZoom`] this frame?
+    smart_zoom: bool,
+
     /// Position and size of the egui area.
     pub screen_rect: Rect,
 
@@ -144,11 +208,15 @@ impl Default for InputState {
         Self {
             raw: Default::default(),
             pointer: Default::default(),
+            extra_pointers: Default::default(),
             touch_states: Default::default(),
+            gamepad: Default::default(),
             unprocessed_scroll_delta: Vec2::ZERO,
             raw_scroll_delta: Vec2::ZERO,
             smooth_scroll_delta: Vec2::ZERO,
             zoom_factor_delta: 1.0,
+            rotation_delta: 0.0,
+            smart_zoom: false,
             screen_rect: Rect::from_min_size(Default::default(), vec2(10_000.0, 10_000.0)),
             pixels_per_point: 1.0,
             max_texture_side: 2048,
@@ -171,6 +239,7 @@ impl InputState {
         mut new: RawInput,
         requested_immediate_repaint_prev_frame: bool,
         pixels_per_point: f32,
+        long_press: LongPressOptions,
     ) -> Self {
         crate::profile_function!();
 
@@ -190,11 +259,15 @@ impl InputState {
         for touch_state in self.touch_states.values_mut() {
             touch_state.begin_frame(time, &new, self.pointer.interact_pos);
         }
-        let pointer = self.pointer.begin_frame(time, &new);
+        let pointer = self.pointer.begin_frame(time, &new.events, long_press);
+        let extra_pointers =
+            extra_pointers_begin_frame(self.extra_pointers, time, &new.events, long_press);
 
         let mut keys_down = self.keys_down;
         let mut raw_scroll_delta = Vec2::ZERO;
         let mut zoom_factor_delta = 1.0;
+        let mut rotation_delta = 0.0;
+        let mut smart_zoom = false;
         for event in &mut new.events {
             match event {
                 Event::Key {
@@ -216,6 +289,12 @@ impl InputState {
                 Event::Zoom(factor) => {
                     zoom_factor_delta *= *factor;
                 }
+                Event::Rotate(angle) => {
+                    rotation_delta += *angle;
+                }
+                Event::SmartZoom => {
+                    smart_zoom = true;
+                }
                 _ => {}
             }
         }
@@ -252,11 +331,15 @@ impl InputState {
 
         Self {
             pointer,
+            extra_pointers,
             touch_states: self.touch_states,
+            gamepad: self.gamepad,
             unprocessed_scroll_delta,
             raw_scroll_delta,
             smooth_scroll_delta,
             zoom_factor_delta,
+            rotation_delta,
+            smart_zoom,
             screen_rect,
             pixels_per_point,
             max_texture_side: new.max_texture_side.unwrap_or(self.max_texture_side),
@@ -272,6 +355,26 @@ impl InputState {
         }
     }
 
+    /// The state of the given pointer, if it is known.
+    ///
+    /// [`PointerId::PRIMARY`] is always known, and is the same as [`Self::pointer`].
+    pub fn pointer_state(&self, id: PointerId) -> Option<&PointerState> {
+        if id == PointerId::PRIMARY {
+            Some(&self.pointer)
+        } else {
+            self.extra_pointers.get(&id)
+        }
+    }
+
+    /// The ids of all pointers that are on the screen or have a button down, primary first.
+    pub fn pointer_ids(&self) -> impl Iterator<Item = PointerId> + '_ {
+        let primary = self.pointer.has_pointer() || self.pointer.any_down();
+        primary
+            .then_some(PointerId::PRIMARY)
+            .into_iter()
+            .chain(self.extra_pointers.keys().copied())
+    }
+
     /// Info about the active viewport
     #[inline]
     pub fn viewport(&self) -> &ViewportInfo {
@@ -322,9 +425,32 @@ impl InputState {
         )
     }
 
+    /// Rotation in radians this frame (e.g. from a two-finger rotate gesture).
+    ///
+    /// Positive is clockwise.
+    /// This is the rotation of a multi-touch gesture, or else from [`Event::Rotate`] (trackpads).
+    #[inline(always)]
+    pub fn rotation_delta(&self) -> f32 {
+        self.multi_touch()
+            .map_or(self.rotation_delta, |touch| touch.rotation_delta)
+    }
+
+    /// Did the user ask to zoom to (or back out of) what is under the pointer this frame?
+    ///
+    /// This is a two-finger double-tap on a trackpad, see [`Event::SmartZoom`].
+    /// Canvases and image viewers usually toggle between fitting the content and zooming in on the pointer.
+    #[inline(always)]
+    pub fn smart_zoom(&self) -> bool {
+        self.smart_zoom
+    }
+
     /// The [`crate::Context`] will call this at the end of each frame to see if we need a repaint.
     pub fn wants_repaint(&self) -> bool {
         self.pointer.wants_repaint()
+            || self
+                .extra_pointers
+                .values()
+                .any(PointerState::wants_repaint)
             || self.unprocessed_scroll_delta.abs().max_elem() > 0.2
             || !self.events.is_empty()
     }
@@ -622,6 +748,12 @@ pub struct PointerState {
     /// May be unavailable on some integrations.
     motion: Option<Vec2>,
 
+    /// Was the pointer locked by [`crate::PlatformOutput::pointer_lock`] last frame?
+    pub(crate) locked: bool,
+
+    /// The latest state of the pen, if the pointer is one.
+    pen: Option<PenState>,
+
     /// Current velocity of pointer.
     velocity: Vec2,
 
@@ -646,6 +778,19 @@ pub struct PointerState {
     /// Did [`Self::is_decidedly_dragging`] go from `false` to `true` this frame?
     pub(crate) started_decidedly_dragging: bool,
 
+    /// When the current press turns into a long-press.
+    long_press: LongPressOptions,
+
+    /// Set to `true` if the pointer has moved too much (since being pressed)
+    /// for it to be registered as a long-press.
+    has_moved_too_much_for_a_long_press: bool,
+
+    /// Has the current press of the primary button turned into a long-press?
+    is_long_pressing: bool,
+
+    /// Did [`Self::is_long_pressing`] go from `false` to `true` this frame?
+    started_long_press: bool,
+
     /// When did the pointer get click last?
     /// Used to check for double-clicks.
     last_click_time: f64,
@@ -670,6 +815,8 @@ impl Default for PointerState {
             interact_pos: None,
             delta: Vec2::ZERO,
             motion: None,
+            locked: false,
+            pen: None,
             velocity: Vec2::ZERO,
             pos_history: History::new(0..1000, 0.1),
             down: Default::default(),
@@ -677,6 +824,10 @@ impl Default for PointerState {
             press_start_time: None,
             has_moved_too_much_for_a_click: false,
             started_decidedly_dragging: false,
+            long_press: Default::default(),
+            has_moved_too_much_for_a_long_press: false,
+            is_long_pressing: false,
+            started_long_press: false,
             last_click_time: std::f64::NEG_INFINITY,
             last_last_click_time: std::f64::NEG_INFINITY,
             last_move_time: std::f64::NEG_INFINITY,
@@ -685,12 +836,53 @@ impl Default for PointerState {
     }
 }
 
+/// Update each extra pointer with its own [`Event::ExtraPointer`] events.
+fn extra_pointers_begin_frame(
+    mut pointers: BTreeMap<PointerId, PointerState>,
+    time: f64,
+    events: &[Event],
+    long_press: LongPressOptions,
+) -> BTreeMap<PointerId, PointerState> {
+    let mut events_per_pointer: BTreeMap<PointerId, Vec<Event>> = Default::default();
+    for event in events {
+        if let Event::ExtraPointer { id, event } = event {
+            if *id != PointerId::PRIMARY {
+                events_per_pointer
+                    .entry(*id)
+                    .or_default()
+                    .push((**event).clone());
+            }
+        }
+    }
+    for id in events_per_pointer.keys() {
+        pointers.entry(*id).or_default();
+    }
+
+    pointers
+        .into_iter()
+        .map(|(id, pointer)| {
+            let events = events_per_pointer.get(&id).map_or(&[][..], Vec::as_slice);
+            (id, pointer.begin_frame(time, events, long_press))
+        })
+        .filter(|(_, pointer)| {
+            pointer.has_pointer() || pointer.any_down() || !pointer.pointer_events.is_empty()
+        })
+        .collect()
+}
+
 impl PointerState {
     #[must_use]
-    pub(crate) fn begin_frame(mut self, time: f64, new: &RawInput) -> Self {
+    pub(crate) fn begin_frame(
+        mut self,
+        time: f64,
+        events: &[Event],
+        long_press: LongPressOptions,
+    ) -> Self {
         let was_decidedly_dragging = self.is_decidedly_dragging();
 
         self.time = time;
+        self.long_press = long_press;
+        self.started_long_press = false;
 
         self.pointer_events.clear();
 
@@ -700,8 +892,11 @@ impl PointerState {
             self.motion = Some(Vec2::ZERO);
         }
 
-        for event in &new.events {
+        for event in events {
             match event {
+                Event::PointerMoved(_) if self.locked => {
+                    // The pointer stays where it was locked, so the same widget stays hovered.
+                }
                 Event::PointerMoved(pos) => {
                     let pos = *pos;
 
@@ -711,6 +906,8 @@ impl PointerState {
                     if let Some(press_origin) = self.press_origin {
                         self.has_moved_too_much_for_a_click |=
                             press_origin.distance(pos) > MAX_CLICK_DIST;
+                        self.has_moved_too_much_for_a_long_press |=
+                            press_origin.distance(pos) > long_press.max_dist;
                     }
 
                     self.pointer_events.push(PointerEvent::Moved(pos));
@@ -739,12 +936,15 @@ impl PointerState {
                         self.press_origin = Some(pos);
                         self.press_start_time = Some(time);
                         self.has_moved_too_much_for_a_click = false;
+                        self.has_moved_too_much_for_a_long_press = false;
+                        self.is_long_pressing = false;
                         self.pointer_events.push(PointerEvent::Pressed {
                             position: pos,
                             button,
                         });
                     } else {
-                        let clicked = self.could_any_button_be_click();
+                        // A long-press is not also a click:
+                        let clicked = self.could_any_button_be_click() && !self.is_long_pressing;
 
                         let click = if clicked {
                             let double_click =
@@ -776,12 +976,15 @@ impl PointerState {
 
                         self.press_origin = None;
                         self.press_start_time = None;
+                        self.is_long_pressing = false;
                     }
 
                     self.down[button as usize] = pressed; // must be done after the above call to `could_any_button_be_click`
                 }
+                Event::Pen(pen) => self.pen = *pen,
                 Event::PointerGone => {
                     self.latest_pos = None;
+                    self.pen = None;
                     // NOTE: we do NOT clear `self.interact_pos` here. It will be cleared next frame.
                 }
                 Event::MouseMoved(delta) => *self.motion.get_or_insert(Vec2::ZERO) += *delta,
@@ -816,6 +1019,11 @@ impl PointerState {
 
         self.started_decidedly_dragging = self.is_decidedly_dragging() && !was_decidedly_dragging;
 
+        if self.time_until_long_press() == Some(0.0) {
+            self.is_long_pressing = true;
+            self.started_long_press = true;
+        }
+
         self
     }
 
@@ -823,6 +1031,26 @@ impl PointerState {
         !self.pointer_events.is_empty() || self.delta != Vec2::ZERO
     }
 
+    /// The pressure, tilt and eraser state of the pen, if the pointer is one.
+    ///
+    /// See [`Event::Pen`].
+    #[inline(always)]
+    pub fn pen(&self) -> Option<PenState> {
+        self.pen
+    }
+
+    /// How hard the pointer is pressed, from `0.0` to `1.0`.
+    ///
+    /// For a pen this is its pressure.
+    /// Pointers without pressure (like a mouse) are `1.0` while any button is down, else `0.0`.
+    pub fn pressure(&self) -> f32 {
+        match self.pen {
+            Some(pen) => pen.pressure,
+            None if self.any_down() => 1.0,
+            None => 0.0,
+        }
+    }
+
     /// How much the pointer moved compared to last frame, in points.
     #[inline(always)]
     pub fn delta(&self) -> Vec2 {
@@ -837,6 +1065,25 @@ impl PointerState {
         self.motion
     }
 
+    /// Is the pointer locked to the window, as requested by [`crate::Response::request_pointer_lock`] last frame?
+    #[inline(always)]
+    pub fn is_locked(&self) -> bool {
+        self.locked
+    }
+
+    /// How much the mouse moved since the last frame while the pointer is locked, in unspecified units.
+    ///
+    /// This is the raw [`Self::motion`], which is not stopped by the screen edges.
+    /// Zero when the pointer is not locked, see [`crate::Response::request_pointer_lock`].
+    #[inline]
+    pub fn locked_delta(&self) -> Vec2 {
+        if self.locked {
+            self.motion.unwrap_or_default()
+        } else {
+            Vec2::ZERO
+        }
+    }
+
     /// Current velocity of pointer.
     #[inline(always)]
     pub fn velocity(&self) -> Vec2 {
@@ -1051,6 +1298,35 @@ impl PointerState {
             && !self.any_click()
     }
 
+    /// Did the primary button get held down long enough for a long-press this frame?
+    ///
+    /// This happens once per press, while the button is still down.
+    /// The release of a long-press is not a click.
+    ///
+    /// See also [`crate::Response::long_pressed`] and [`LongPressOptions`].
+    #[inline(always)]
+    pub fn long_press_started(&self) -> bool {
+        self.started_long_press
+    }
+
+    /// Is the primary button held down after a long-press?
+    #[inline(always)]
+    pub fn is_long_pressing(&self) -> bool {
+        self.is_long_pressing
+    }
+
+    /// If the primary button is down and could still become a long-press,
+    /// how many seconds until it does?
+    pub fn time_until_long_press(&self) -> Option<f32> {
+        if !self.primary_down() || self.is_long_pressing || self.has_moved_too_much_for_a_long_press
+        {
+            return None;
+        }
+        let press_start_time = self.press_start_time?;
+        let held = (self.time - press_start_time) as f32;
+        Some((self.long_press.duration - held).max(0.0))
+    }
+
     /// Is the primary button currently down?
     #[inline(always)]
     pub fn primary_down(&self) -> bool {
@@ -1075,13 +1351,17 @@ impl InputState {
         let Self {
             raw,
             pointer,
+            extra_pointers,
             touch_states,
+            gamepad,
 
             unprocessed_scroll_delta,
             raw_scroll_delta,
             smooth_scroll_delta,
 
             zoom_factor_delta,
+            rotation_delta,
+            smart_zoom,
             screen_rect,
             pixels_per_point,
             max_texture_side,
@@ -1109,12 +1389,20 @@ impl InputState {
                 pointer.ui(ui);
             });
 
+        for (id, pointer) in extra_pointers {
+            ui.collapsing(format!("🖱 Pointer {}", id.0), |ui| {
+                pointer.ui(ui);
+            });
+        }
+
         for (device_id, touch_state) in touch_states {
             ui.collapsing(format!("Touch State [device {}]", device_id.0), |ui| {
                 touch_state.ui(ui);
             });
         }
 
+        ui.collapsing("🎮 Gamepad", |ui| gamepad.ui(ui));
+
         if cfg!(debug_assertions) {
             ui.label(format!(
                 "unprocessed_scroll_delta: {unprocessed_scroll_delta:?} points"
@@ -1125,6 +1413,8 @@ impl InputState {
             "smooth_scroll_delta: {smooth_scroll_delta:?} points"
         ));
         ui.label(format!("zoom_factor_delta: {zoom_factor_delta:4.2}x"));
+        ui.label(format!("rotation_delta: {rotation_delta:.3} rad"));
+        ui.label(format!("smart_zoom: {smart_zoom}"));
         ui.label(format!("screen_rect: {screen_rect:?} points"));
         ui.label(format!(
             "{pixels_per_point} physical pixels for each logical point"
@@ -1158,6 +1448,8 @@ impl PointerState {
             interact_pos,
             delta,
             motion,
+            locked,
+            pen,
             velocity,
             pos_history: _,
             down,
@@ -1165,6 +1457,10 @@ impl PointerState {
             press_start_time,
             has_moved_too_much_for_a_click,
             started_decidedly_dragging,
+            long_press: _,
+            has_moved_too_much_for_a_long_press,
+            is_long_pressing,
+            started_long_press,
             last_click_time,
             last_last_click_time,
             pointer_events,
@@ -1175,6 +1471,8 @@ impl PointerState {
         ui.label(format!("interact_pos: {interact_pos:?}"));
         ui.label(format!("delta: {delta:?}"));
         ui.label(format!("motion: {motion:?}"));
+        ui.label(format!("locked: {locked}"));
+        ui.label(format!("pen: {pen:?}"));
         ui.label(format!(
             "velocity: [{:3.0} {:3.0}] points/sec",
             velocity.x, velocity.y
@@ -1188,6 +1486,11 @@ impl PointerState {
         ui.label(format!(
             "started_decidedly_dragging: {started_decidedly_dragging}"
         ));
+        ui.label(format!(
+            "has_moved_too_much_for_a_long_press: {has_moved_too_much_for_a_long_press}"
+        ));
+        ui.label(format!("is_long_pressing: {is_long_pressing}"));
+        ui.label(format!("started_long_press: {started_long_press}"));
         ui.label(format!("last_click_time: {last_click_time:#?}"));
         ui.label(format!("last_last_click_time: {last_last_click_time:#?}"));
         ui.label(format!("last_move_time: {last_move_time:#?}"));
@@ -0,0 +1,209 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::{Event, GamepadAxis, GamepadButton, Key, Modifiers};
+
+/// How far a stick must be pushed (`0.0..=1.0`) to move the focus, like pressing an arrow key.
+const NAVIGATION_THRESHOLD: f32 = 0.5;
+
+/// Stick values smaller than this are ignored, since sticks rarely rest exactly at zero.
+const DEAD_ZONE: f32 = 0.2;
+
+/// The state of the gamepads (controllers), from [`Event::GamepadButton`] and [`Event::GamepadAxis`].
+///
+/// All gamepads are treated as one.
+///
+/// egui maps the gamepad to the keyboard, so that the focus can be moved around and used without a mouse:
+/// * The D-pad and the left stick are arrow keys.
+///   While nothing has focus, they are [`Key::Tab`] (with shift for up and left) instead, to focus the first widget.
+/// * [`GamepadButton::South`] is [`Key::Enter`], which clicks the focused widget.
+/// * [`GamepadButton::East`] is [`Key::Escape`].
+///
+/// The right stick adjusts the focused [`crate::Slider`] or [`crate::DragValue`], see [`Self::analog_adjust`].
+#[derive(Clone, Debug, Default)]
+pub struct GamepadState {
+    /// The latest value of each axis that has moved.
+    axes: BTreeMap<GamepadAxis, f32>,
+
+    /// The buttons that are down.
+    down: BTreeSet<GamepadButton>,
+}
+
+impl GamepadState {
+    /// Update with the gamepad events of a new frame,
+    /// inserting the [`Event::Key`]s they map to after them.
+    pub(crate) fn begin_frame(&mut self, events: &mut Vec<Event>, nothing_focused: bool) {
+        if !events.iter().any(|event| {
+            matches!(
+                event,
+                Event::GamepadButton { .. } | Event::GamepadAxis { .. }
+            )
+        }) {
+            return;
+        }
+
+        let key_events = |key: Key, pressed: bool| {
+            let is_arrow = matches!(
+                key,
+                Key::ArrowUp | Key::ArrowDown | Key::ArrowLeft | Key::ArrowRight
+            );
+            let keys = if is_arrow && !pressed {
+                // The focus may have changed since the press, so release both:
+                vec![(key, Modifiers::NONE), (Key::Tab, Modifiers::NONE)]
+            } else if is_arrow && nothing_focused {
+                let backwards = matches!(key, Key::ArrowUp | Key::ArrowLeft);
+                let modifiers = if backwards {
+                    Modifiers::SHIFT
+                } else {
+                    Modifiers::NONE
+                };
+                vec![(Key::Tab, modifiers)]
+            } else {
+                vec![(key, Modifiers::NONE)]
+            };
+            keys.into_iter().map(move |(key, modifiers)| Event::Key {
+                key,
+                physical_key: None,
+                pressed,
+                repeat: false,
+                modifiers,
+            })
+        };
+
+        for event in std::mem::take(events) {
+            let mut keys = vec![];
+            match &event {
+                Event::GamepadButton { button, pressed } => {
+                    if *pressed {
+                        self.down.insert(*button);
+                    } else {
+                        self.down.remove(button);
+                    }
+                    let key = match button {
+                        GamepadButton::South => Some(Key::Enter),
+                        GamepadButton::East => Some(Key::Escape),
+                        GamepadButton::DPadUp => Some(Key::ArrowUp),
+                        GamepadButton::DPadDown => Some(Key::ArrowDown),
+                        GamepadButton::DPadLeft => Some(Key::ArrowLeft),
+                        GamepadButton::DPadRight => Some(Key::ArrowRight),
+                        _ => None,
+                    };
+                    keys.extend(key.map(|key| (key, *pressed)));
+                }
+                Event::GamepadAxis { axis, value } => {
+                    let old_value = self.axes.insert(*axis, *value).unwrap_or(0.0);
+                    let arrow_keys = match axis {
+                        GamepadAxis::LeftStickX => Some((Key::ArrowLeft, Key::ArrowRight)),
+                        GamepadAxis::LeftStickY => Some((Key::ArrowDown, Key::ArrowUp)),
+                        _ => None,
+                    };
+                    if let Some((negative_key, positive_key)) = arrow_keys {
+                        // Press the arrow key when the stick is pushed far enough, and release it when it comes back:
+                        for (key, sign) in [(negative_key, -1.0), (positive_key, 1.0)] {
+                            let was_pushed = NAVIGATION_THRESHOLD <= sign * old_value;
+                            let is_pushed = NAVIGATION_THRESHOLD <= sign * value;
+                            if was_pushed != is_pushed {
+                                keys.push((key, is_pushed));
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            }
+            events.push(event);
+            for (key, pressed) in keys {
+                events.extend(key_events(key, pressed));
+            }
+        }
+    }
+
+    /// The latest value of this axis, see [`Event::GamepadAxis`].
+    pub fn axis(&self, axis: GamepadAxis) -> f32 {
+        self.axes.get(&axis).copied().unwrap_or(0.0)
+    }
+
+    /// Is this button held down?
+    pub fn is_down(&self, button: GamepadButton) -> bool {
+        self.down.contains(&button)
+    }
+
+    /// How much to adjust the focused value, from the x axis of the right stick.
+    ///
+    /// In `-1.0..=1.0`, and zero within the dead zone around the center.
+    pub fn analog_adjust(&self) -> f32 {
+        let x = self.axis(GamepadAxis::RightStickX).clamp(-1.0, 1.0);
+        if x.abs() < DEAD_ZONE {
+            0.0
+        } else {
+            x.signum() * (x.abs() - DEAD_ZONE) / (1.0 - DEAD_ZONE)
+        }
+    }
+
+    pub(crate) fn ui(&self, ui: &mut crate::Ui) {
+        let Self { axes, down } = self;
+        ui.label(format!("axes: {axes:?}"));
+        ui.label(format!("down: {down:?}"));
+    }
+}
+
+#[test]
+fn test_gamepad_navigation() {
+    use crate::{test_harness::Harness, DragValue, Slider};
+    use std::cell::Cell;
+
+    let clicks = Cell::new(0);
+    let slider_value = Cell::new(50.0);
+    let drag_value = Cell::new(0.0);
+    let mut harness = Harness::new_ui(|ui| {
+        if ui.button("Button").clicked() {
+            clicks.set(clicks.get() + 1);
+        }
+        let mut value = slider_value.get();
+        ui.add(Slider::new(&mut value, 0.0..=100.0));
+        slider_value.set(value);
+        let mut value = drag_value.get();
+        ui.add(DragValue::new(&mut value));
+        drag_value.set(value);
+    });
+    harness.run();
+
+    let button = |harness: &mut Harness<'_>, button| {
+        for pressed in [true, false] {
+            harness.push_event(Event::GamepadButton { button, pressed });
+            harness.step();
+        }
+    };
+    let axis = |harness: &mut Harness<'_>, axis, value| {
+        harness.push_event(Event::GamepadAxis { axis, value });
+        harness.step();
+    };
+
+    // The D-pad focuses the first widget, and the south button clicks it:
+    button(&mut harness, GamepadButton::DPadDown);
+    button(&mut harness, GamepadButton::South);
+    assert_eq!(clicks.get(), 1);
+
+    // The stick moves the focus to the slider, and the other stick moves it:
+    axis(&mut harness, GamepadAxis::LeftStickY, -0.9);
+    axis(&mut harness, GamepadAxis::LeftStickY, 0.0);
+    axis(&mut harness, GamepadAxis::RightStickX, 1.0);
+    for _ in 0..30 {
+        harness.step();
+    }
+    axis(&mut harness, GamepadAxis::RightStickX, 0.0);
+    let value = slider_value.get();
+    assert!(70.0 < value && value < 80.0, "{value}");
+
+    // Same for the drag value:
+    button(&mut harness, GamepadButton::DPadDown);
+    axis(&mut harness, GamepadAxis::RightStickX, -0.6);
+    for _ in 0..10 {
+        harness.step();
+    }
+    axis(&mut harness, GamepadAxis::RightStickX, 0.0);
+    assert!(drag_value.get() < -10.0, "{}", drag_value.get());
+    assert_eq!(slider_value.get(), value);
+
+    // The east button lets go of the focus:
+    button(&mut harness, GamepadButton::East);
+    assert_eq!(harness.ctx().memory(|mem| mem.focus()), None);
+}
@@ -15,6 +15,14 @@ pub struct MultiTouchInfo {
     /// Position of the pointer at the time the gesture started.
     pub start_pos: Pos2,
 
+    /// The center of the touches (fingers) at the time the gesture started.
+    ///
+    /// This decides which widget gets the gesture, see [`crate::Response::multi_touch`].
+    pub start_center_pos: Pos2,
+
+    /// The center of the touches (fingers) this frame.
+    pub center_pos: Pos2,
+
     /// Number of touches (fingers) on the surface. Value is ≥ 2 since for a single touch no
     /// [`MultiTouchInfo`] is created.
     pub num_touches: usize,
@@ -86,6 +94,7 @@ pub(crate) struct TouchState {
 struct GestureState {
     start_time: f64,
     start_pointer_pos: Pos2,
+    start_avg_pos: Pos2,
     pinch_type: PinchType,
     previous: Option<DynGestureState>,
     current: DynGestureState,
@@ -200,6 +209,8 @@ impl TouchState {
             MultiTouchInfo {
                 start_time: state.start_time,
                 start_pos: state.start_pointer_pos,
+                start_center_pos: state.start_avg_pos,
+                center_pos: state.current.avg_pos,
                 num_touches: self.active_touches.len(),
                 zoom_delta,
                 zoom_delta_2d: zoom_delta2,
@@ -221,6 +232,7 @@ impl TouchState {
                 self.gesture_state = Some(GestureState {
                     start_time: time,
                     start_pointer_pos: pointer_pos,
+                    start_avg_pos: dyn_state.avg_pos,
                     pinch_type: PinchType::classify(&self.active_touches),
                     previous: None,
                     current: dyn_state,
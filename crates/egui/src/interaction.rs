@@ -50,6 +50,11 @@ pub struct InteractionSnapshot {
     /// This is usually a larger set than [`Self::hovered`],
     /// and can be used for e.g. drag-and-drop zones.
     pub contains_pointer: IdSet,
+
+    /// The widget that gets the multi-touch gesture (e.g. pinch or rotate) going on this frame.
+    ///
+    /// See [`crate::Response::multi_touch`].
+    pub gesture: Option<Id>,
 }
 
 impl InteractionSnapshot {
@@ -61,6 +66,7 @@ impl InteractionSnapshot {
             drag_stopped,
             hovered,
             contains_pointer,
+            gesture,
         } = self;
 
         fn id_ui<'a>(ui: &mut crate::Ui, widgets: impl IntoIterator<Item = &'a Id>) {
@@ -93,6 +99,10 @@ impl InteractionSnapshot {
             ui.label("contains_pointer");
             id_ui(ui, contains_pointer);
             ui.end_row();
+
+            ui.label("gesture");
+            id_ui(ui, gesture);
+            ui.end_row();
         });
     }
 }
@@ -101,7 +111,7 @@ pub(crate) fn interact(
     prev_snapshot: &InteractionSnapshot,
     widgets: &WidgetRects,
     hits: &WidgetHits,
-    input: &InputState,
+    pointer: &PointerState,
     interaction: &mut InteractionState,
 ) -> InteractionSnapshot {
     crate::profile_function!();
@@ -125,7 +135,7 @@ pub(crate) fn interact(
     let mut dragged = prev_snapshot.dragged;
 
     // Note: in the current code a press-release in the same frame is NOT considered a drag.
-    for pointer_event in &input.pointer.pointer_events {
+    for pointer_event in &pointer.pointer_events {
         match pointer_event {
             PointerEvent::Moved(_) => {}
 
@@ -165,7 +175,7 @@ pub(crate) fn interact(
                 // This widget is sensitive to both clicks and drags.
                 // When the mouse first is pressed, it could be either,
                 // so we postpone the decision until we know.
-                input.pointer.is_decidedly_dragging()
+                pointer.is_decidedly_dragging()
             } else {
                 // This widget is just sensitive to drags, so we can mark it as dragged right away:
                 widget.sense.drag
@@ -223,5 +233,31 @@ pub(crate) fn interact(
         drag_stopped,
         contains_pointer,
         hovered,
+        gesture: None, // see `claim_gesture`
+    }
+}
+
+/// Which widget gets the multi-touch gesture going on this frame, if any.
+///
+/// A gesture is claimed once, when it starts, by the widget that would be dragged
+/// at the center of the fingers (`hits_at_start`), and the widget keeps it until the fingers are lifted.
+/// This way two widgets (e.g. a plot in a scroll area) never get the same gesture.
+pub(crate) fn claim_gesture(
+    multi_touch: Option<&MultiTouchInfo>,
+    hits_at_start: &WidgetHits,
+    interaction: &mut InteractionState,
+) -> Option<Id> {
+    let Some(info) = multi_touch else {
+        interaction.gesture_claim = None;
+        return None;
+    };
+
+    match interaction.gesture_claim {
+        Some((start_time, id)) if start_time == info.start_time => id,
+        _ => {
+            let id = hits_at_start.drag.map(|w| w.id);
+            interaction.gesture_claim = Some((info.start_time, id));
+            id
+        }
     }
 }
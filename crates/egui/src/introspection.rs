@@ -94,6 +94,7 @@ impl Widget for &epaint::stats::PaintStats {
                 clipped_primitives,
                 vertices,
                 indices,
+                mesh_pool,
             } = self;
 
             ui.label("Intermediate:");
@@ -121,6 +122,23 @@ impl Widget for &epaint::stats::PaintStats {
             label(ui, indices, "indices").on_hover_text("Three 32-bit indices per triangles");
             ui.add_space(10.0);
 
+            let epaint::stats::MeshPoolStats {
+                meshes_reused,
+                meshes_allocated,
+                meshes_grown,
+                bytes_allocated,
+                meshes_pooled,
+            } = mesh_pool;
+            ui.label("Tessellation buffers:");
+            ui.label(format!("{meshes_reused:6} reused meshes"));
+            ui.label(format!("{meshes_allocated:6} new meshes"));
+            ui.label(format!("{meshes_grown:6} grown meshes"))
+                .on_hover_text("Reused meshes that needed more space");
+            ui.label(format!("{:.2} MB allocated", *bytes_allocated as f64 / 1e6));
+            ui.label(format!("{meshes_pooled:6} pooled meshes"))
+                .on_hover_text("Meshes handed back by the integration, ready for reuse");
+            ui.add_space(10.0);
+
             // ui.label("Total:");
             // ui.label(self.total().format(""));
         })
@@ -194,12 +212,14 @@ impl Widget for &memory::InteractionState {
         let memory::InteractionState {
             potential_click_id,
             potential_drag_id,
+            gesture_claim,
             focus: _,
         } = self;
 
         ui.vertical(|ui| {
             ui.label(format!("potential_click_id: {potential_click_id:?}"));
             ui.label(format!("potential_drag_id: {potential_drag_id:?}"));
+            ui.label(format!("gesture_claim: {gesture_claim:?}"));
         })
         .response
     }
@@ -25,18 +25,25 @@ pub enum Order {
     /// You cannot interact with these.
     Tooltip,
 
+    /// Things the app paints on top of all its ui, like custom cursors or crosshairs.
+    /// You cannot interact with these.
+    ///
+    /// See [`crate::Context::overlay_painter`].
+    Overlay,
+
     /// Debug layer, always painted last / on top
     Debug,
 }
 
 impl Order {
-    const COUNT: usize = 6;
+    const COUNT: usize = 7;
     const ALL: [Self; Self::COUNT] = [
         Self::Background,
         Self::PanelResizeLine,
         Self::Middle,
         Self::Foreground,
         Self::Tooltip,
+        Self::Overlay,
         Self::Debug,
     ];
     pub const TOP: Self = Self::Debug;
@@ -49,7 +56,7 @@ impl Order {
             | Self::Middle
             | Self::Foreground
             | Self::Debug => true,
-            Self::Tooltip => false,
+            Self::Tooltip | Self::Overlay => false,
         }
     }
 
@@ -61,6 +68,7 @@ impl Order {
             Self::Middle => "middl",
             Self::Foreground => "foreg",
             Self::Tooltip => "toolt",
+            Self::Overlay => "overl",
             Self::Debug => "debug",
         }
     }
@@ -87,6 +95,14 @@ impl LayerId {
         }
     }
 
+    /// See [`crate::Context::overlay_painter`].
+    pub fn overlay() -> Self {
+        Self {
+            order: Order::Overlay,
+            id: Id::new("overlay"),
+        }
+    }
+
     pub fn background() -> Self {
         Self {
             order: Order::Background,
@@ -0,0 +1,624 @@
+//! A layout where widgets are placed by linear constraints between their edges,
+//! for complex dialogs that are awkward to build out of nested horizontal and vertical layouts.
+//!
+//! ```
+//! # egui::__run_test_ui(|ui| {
+//! use egui::layout_constraints::ConstraintLayout;
+//!
+//! let mut name = String::new();
+//! ConstraintLayout::new("dialog").show(ui, |layout| {
+//!     let (label, _) = layout.add(|ui| ui.label("Name:"));
+//!     let (edit, _) = layout.add(|ui| ui.text_edit_singleline(&mut name));
+//!     let (ok, ok_button) = layout.add(|ui| ui.button("OK"));
+//!
+//!     layout.constrain(edit.left().equals(label.right() + 8.0));
+//!     layout.constrain(label.center_y().equals(edit.center_y()));
+//!     layout.constrain(ok.top().equals(edit.bottom() + 8.0));
+//!     layout.constrain(ok.right().equals(edit.right()));
+//!     layout.constrain(ok.width().at_least(100.0));
+//!     if ok_button.inner.clicked() { /* … */ }
+//! });
+//! # });
+//! ```
+//!
+//! The constraints are solved at the end of each frame, and the result is used the next frame,
+//! just like [`crate::Grid`] uses the column widths of the previous frame.
+//! The solution is cached, and only recomputed when the constraints or the sizes of the widgets change.
+//!
+//! The solver is simpler than a full Cassowary implementation:
+//! it minimizes the squared errors of all constraints, weighted by their [`Strength`],
+//! so conflicts are resolved in favor of the stronger constraints,
+//! but between constraints of equal strength the result is a compromise.
+
+use std::ops::{Add, Mul, Neg, Sub};
+
+use crate::*;
+
+/// How many times to update which inequalities are active, at most.
+const MAX_ITERATIONS: usize = 20;
+
+/// How hard the solver tries to satisfy a [`Constraint`].
+///
+/// When constraints conflict, the stronger ones win.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Strength {
+    /// Used for where items end up if nothing else is said about them.
+    Weak,
+
+    /// Used for the natural size of each item.
+    Strong,
+
+    /// The default.
+    #[default]
+    Required,
+}
+
+impl Strength {
+    /// The weight of the squared error.
+    fn weight(self) -> f64 {
+        match self {
+            Self::Weak => 1.0,
+            Self::Strong => 1e3,
+            Self::Required => 1e6,
+        }
+    }
+}
+
+/// An item added with [`ConstraintUi::add`], used to refer to its edges in constraints.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Item(usize);
+
+/// The unknowns of each item, in order.
+const LEFT: usize = 0;
+const TOP: usize = 1;
+const WIDTH: usize = 2;
+const HEIGHT: usize = 3;
+const VARS_PER_ITEM: usize = 4;
+
+impl Item {
+    fn var(self, var: usize) -> Expr {
+        Expr {
+            terms: vec![(self.0 * VARS_PER_ITEM + var, 1.0)],
+            constant: 0.0,
+        }
+    }
+
+    pub fn left(self) -> Expr {
+        self.var(LEFT)
+    }
+
+    pub fn top(self) -> Expr {
+        self.var(TOP)
+    }
+
+    pub fn width(self) -> Expr {
+        self.var(WIDTH)
+    }
+
+    pub fn height(self) -> Expr {
+        self.var(HEIGHT)
+    }
+
+    pub fn right(self) -> Expr {
+        self.left() + self.width()
+    }
+
+    pub fn bottom(self) -> Expr {
+        self.top() + self.height()
+    }
+
+    pub fn center_x(self) -> Expr {
+        self.left() + self.width() * 0.5
+    }
+
+    pub fn center_y(self) -> Expr {
+        self.top() + self.height() * 0.5
+    }
+}
+
+/// A linear combination of the edges and sizes of items, plus a constant.
+///
+/// Build them from the methods of [`Item`] and [`ConstraintUi`], and `+`, `-` and `*`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Expr {
+    /// Variable index and coefficient, with each variable at most once.
+    terms: Vec<(usize, f32)>,
+    constant: f32,
+}
+
+impl Expr {
+    /// A constraint that this is equal to `rhs`.
+    pub fn equals(self, rhs: impl Into<Self>) -> Constraint {
+        Constraint::new(self - rhs.into(), Relation::Equal)
+    }
+
+    /// A constraint that this is at least `rhs`.
+    pub fn at_least(self, rhs: impl Into<Self>) -> Constraint {
+        Constraint::new(self - rhs.into(), Relation::AtLeast)
+    }
+
+    /// A constraint that this is at most `rhs`.
+    pub fn at_most(self, rhs: impl Into<Self>) -> Constraint {
+        Constraint::new(rhs.into() - self, Relation::AtLeast)
+    }
+
+    fn add_term(&mut self, var: usize, coeff: f32) {
+        if let Some((_, c)) = self.terms.iter_mut().find(|(v, _)| *v == var) {
+            *c += coeff;
+        } else {
+            self.terms.push((var, coeff));
+        }
+    }
+
+    fn eval(&self, values: &[f32]) -> f32 {
+        self.constant
+            + self
+                .terms
+                .iter()
+                .map(|&(var, coeff)| coeff * values[var])
+                .sum::<f32>()
+    }
+}
+
+impl From<f32> for Expr {
+    #[inline]
+    fn from(constant: f32) -> Self {
+        Self {
+            terms: Vec::new(),
+            constant,
+        }
+    }
+}
+
+impl Add for Expr {
+    type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self {
+        for (var, coeff) in rhs.terms {
+            self.add_term(var, coeff);
+        }
+        self.constant += rhs.constant;
+        self
+    }
+}
+
+impl Add<f32> for Expr {
+    type Output = Self;
+
+    #[inline]
+    fn add(mut self, rhs: f32) -> Self {
+        self.constant += rhs;
+        self
+    }
+}
+
+impl Sub for Expr {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        self + -rhs
+    }
+}
+
+impl Sub<f32> for Expr {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: f32) -> Self {
+        self + -rhs
+    }
+}
+
+impl Mul<f32> for Expr {
+    type Output = Self;
+
+    fn mul(mut self, factor: f32) -> Self {
+        for (_, coeff) in &mut self.terms {
+            *coeff *= factor;
+        }
+        self.constant *= factor;
+        self
+    }
+}
+
+impl Neg for Expr {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        self * -1.0
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum Relation {
+    /// `expr == 0`
+    Equal,
+
+    /// `expr >= 0`
+    AtLeast,
+}
+
+/// A relation between two [`Expr`]essions, see [`ConstraintUi::constrain`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Constraint {
+    expr: Expr,
+    relation: Relation,
+    strength: Strength,
+}
+
+impl Constraint {
+    fn new(expr: Expr, relation: Relation) -> Self {
+        Self {
+            expr,
+            relation,
+            strength: Strength::Required,
+        }
+    }
+
+    /// How hard to try to satisfy this constraint. Default: [`Strength::Required`].
+    #[inline]
+    pub fn strength(mut self, strength: Strength) -> Self {
+        self.strength = strength;
+        self
+    }
+}
+
+impl std::hash::Hash for Constraint {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        for (var, coeff) in &self.expr.terms {
+            var.hash(state);
+            coeff.to_bits().hash(state);
+        }
+        self.expr.constant.to_bits().hash(state);
+        self.relation.hash(state);
+        self.strength.hash(state);
+    }
+}
+
+/// Change `values` to satisfy the constraints as well as possible.
+///
+/// Minimizes the weighted sum of squared errors of all equalities,
+/// and of the inequalities that would otherwise be violated.
+/// The current `values` are only used to break ties, e.g. for unconstrained values.
+fn solve(values: &mut [f32], constraints: &[Constraint]) {
+    crate::profile_function!();
+
+    let initial: Vec<f64> = values.iter().map(|&v| v as f64).collect();
+    let mut active: Vec<bool> = constraints
+        .iter()
+        .map(|c| c.relation == Relation::Equal || c.expr.eval(values) < 0.0)
+        .collect();
+
+    let mut solution = initial.clone();
+    for _ in 0..MAX_ITERATIONS {
+        solution = least_squares(&initial, constraints, &active);
+
+        // An active inequality that ends up above its bound is pushing the wrong way, so release it.
+        // An inactive one that ends up below its bound is needed.
+        let mut changed = false;
+        for (constraint, active) in constraints.iter().zip(&mut active) {
+            if constraint.relation == Relation::AtLeast {
+                let error: f64 = constraint.expr.constant as f64
+                    + constraint
+                        .expr
+                        .terms
+                        .iter()
+                        .map(|&(var, coeff)| coeff as f64 * solution[var])
+                        .sum::<f64>();
+                let should_be_active = error < 0.0 || (*active && error == 0.0);
+                changed |= *active != should_be_active;
+                *active = should_be_active;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    for (value, solved) in values.iter_mut().zip(solution) {
+        *value = solved as f32;
+    }
+}
+
+/// Minimize the weighted sum of the squared errors of the active constraints,
+/// plus a tiny pull towards `initial`, by solving the normal equations.
+fn least_squares(initial: &[f64], constraints: &[Constraint], active: &[bool]) -> Vec<f64> {
+    const REGULARIZATION: f64 = 1e-6;
+
+    let n = initial.len();
+    let mut matrix = vec![0.0; n * n];
+    let mut rhs: Vec<f64> = initial.iter().map(|&v| REGULARIZATION * v).collect();
+    for i in 0..n {
+        matrix[i * n + i] = REGULARIZATION;
+    }
+
+    for (constraint, _) in constraints.iter().zip(active).filter(|(_, &a)| a) {
+        let weight = constraint.strength.weight();
+        let terms = &constraint.expr.terms;
+        for &(row, a) in terms {
+            for &(col, b) in terms {
+                matrix[row * n + col] += weight * a as f64 * b as f64;
+            }
+            rhs[row] -= weight * a as f64 * constraint.expr.constant as f64;
+        }
+    }
+
+    // Gaussian elimination with partial pivoting:
+    for col in 0..n {
+        let pivot = (col..n)
+            .max_by(|&a, &b| {
+                matrix[a * n + col]
+                    .abs()
+                    .total_cmp(&matrix[b * n + col].abs())
+            })
+            .unwrap_or(col);
+        if pivot != col {
+            for k in 0..n {
+                matrix.swap(col * n + k, pivot * n + k);
+            }
+            rhs.swap(col, pivot);
+        }
+        let diagonal = matrix[col * n + col];
+        for row in col + 1..n {
+            let factor = matrix[row * n + col] / diagonal;
+            if factor != 0.0 {
+                for k in col..n {
+                    matrix[row * n + k] -= factor * matrix[col * n + k];
+                }
+                rhs[row] -= factor * rhs[col];
+            }
+        }
+    }
+    let mut solution = vec![0.0; n];
+    for row in (0..n).rev() {
+        let sum: f64 = (row + 1..n)
+            .map(|k| matrix[row * n + k] * solution[k])
+            .sum();
+        solution[row] = (rhs[row] - sum) / matrix[row * n + row];
+    }
+    solution
+}
+
+// ----------------------------------------------------------------------------
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct State {
+    /// Hash of all constraints, including the measured sizes.
+    key: u64,
+
+    /// The solved values, [`VARS_PER_ITEM`] per item.
+    values: Vec<f32>,
+}
+
+/// A container where each child is placed by [`Constraint`]s, see [the module docs](self).
+#[must_use = "You should call .show()"]
+pub struct ConstraintLayout {
+    id_source: Id,
+}
+
+impl ConstraintLayout {
+    /// The `id_source` must be locally unique.
+    pub fn new(id_source: impl std::hash::Hash) -> Self {
+        Self {
+            id_source: Id::new(id_source),
+        }
+    }
+
+    pub fn show<R>(
+        self,
+        ui: &mut Ui,
+        add_contents: impl FnOnce(&mut ConstraintUi<'_>) -> R,
+    ) -> InnerResponse<R> {
+        let id = ui.make_persistent_id(self.id_source);
+        let prev_state: Option<State> = ui.data_mut(|d| d.get_temp(id));
+
+        ui.scope(|ui| {
+            ui.set_visible(prev_state.is_some()); // Avoid visible first-frame jitter
+
+            let container = ui.available_rect_before_wrap();
+            let mut layout = ConstraintUi {
+                ui,
+                container,
+                prev_values: prev_state.as_ref().map(|s| s.values.clone()),
+                sizes: Vec::new(),
+                used_rect: Rect::NOTHING,
+                constraints: Vec::new(),
+            };
+            let inner = add_contents(&mut layout);
+
+            let ConstraintUi {
+                ui,
+                prev_values,
+                sizes,
+                used_rect,
+                mut constraints,
+                ..
+            } = layout;
+
+            for (i, size) in sizes.iter().enumerate() {
+                let item = Item(i);
+                constraints.extend([
+                    item.left()
+                        .equals(container.left())
+                        .strength(Strength::Weak),
+                    item.top().equals(container.top()).strength(Strength::Weak),
+                    item.width().equals(size.x).strength(Strength::Strong),
+                    item.height().equals(size.y).strength(Strength::Strong),
+                    item.width().at_least(0.0),
+                    item.height().at_least(0.0),
+                ]);
+            }
+
+            let key = crate::util::hash(&constraints);
+            let values = match &prev_state {
+                Some(prev_state) if prev_state.key == key => prev_state.values.clone(),
+                _ => {
+                    let mut values = prev_values.unwrap_or_default();
+                    values.truncate(sizes.len() * VARS_PER_ITEM);
+                    for size in &sizes[values.len() / VARS_PER_ITEM..] {
+                        values.extend([container.left(), container.top(), size.x, size.y]);
+                    }
+                    solve(&mut values, &constraints);
+                    values
+                }
+            };
+
+            let state = State { key, values };
+            if prev_state.as_ref() != Some(&state) {
+                ui.ctx().request_repaint();
+                ui.data_mut(|d| d.insert_temp(id, state));
+            }
+
+            if used_rect.is_positive() {
+                ui.advance_cursor_after_rect(used_rect);
+            }
+            inner
+        })
+    }
+}
+
+/// Passed to the closure of [`ConstraintLayout::show`], to add items and constraints.
+pub struct ConstraintUi<'a> {
+    ui: &'a mut Ui,
+
+    /// The space available to the layout.
+    container: Rect,
+
+    /// The solution from the previous frame.
+    prev_values: Option<Vec<f32>>,
+
+    /// The measured size of each item.
+    sizes: Vec<Vec2>,
+
+    /// The union of all items.
+    used_rect: Rect,
+
+    constraints: Vec<Constraint>,
+}
+
+impl<'a> ConstraintUi<'a> {
+    /// Add an item, to be placed according to the constraints.
+    ///
+    /// The contents are laid out left-to-right.
+    /// By default the item is as large as its contents, at the top left of the layout.
+    ///
+    /// The response covers the whole item, which can be larger than its contents,
+    /// e.g. because of `item.width().at_least(100.0)`.
+    pub fn add<R>(&mut self, add_contents: impl FnOnce(&mut Ui) -> R) -> (Item, InnerResponse<R>) {
+        let item = Item(self.sizes.len());
+        let solved = self
+            .prev_values
+            .as_ref()
+            .and_then(|values| values.get(item.0 * VARS_PER_ITEM..(item.0 + 1) * VARS_PER_ITEM));
+
+        // The height is left at zero, so that we measure the natural height of the contents:
+        let rect = solved.map_or(
+            Rect::from_min_size(self.container.min, vec2(self.container.width(), 0.0)),
+            |v| Rect::from_min_size(pos2(v[LEFT], v[TOP]), vec2(v[WIDTH], 0.0)),
+        );
+
+        let mut child_ui = self.ui.child_ui_with_id_source(
+            rect,
+            Layout::left_to_right(Align::Center),
+            ("constraint_item", item.0),
+        );
+        let inner = add_contents(&mut child_ui);
+        self.sizes.push(child_ui.min_rect().size());
+
+        if let Some(v) = solved {
+            child_ui.set_min_size(vec2(v[WIDTH], v[HEIGHT]));
+        }
+        let used = child_ui.min_rect();
+        self.used_rect = self.used_rect.union(used);
+
+        let response = self.ui.interact(used, child_ui.id(), Sense::hover());
+        (item, InnerResponse::new(inner, response))
+    }
+
+    /// Add a constraint between items, e.g. `a.right().equals(b.left() - 8.0)`.
+    pub fn constrain(&mut self, constraint: Constraint) {
+        self.constraints.push(constraint);
+    }
+
+    /// The left edge of the space available to the layout.
+    pub fn left(&self) -> Expr {
+        self.container.left().into()
+    }
+
+    /// The top edge of the space available to the layout.
+    pub fn top(&self) -> Expr {
+        self.container.top().into()
+    }
+
+    /// The right edge of the space available to the layout.
+    pub fn right(&self) -> Expr {
+        self.container.right().into()
+    }
+
+    /// The width of the space available to the layout.
+    pub fn width(&self) -> Expr {
+        self.container.width().into()
+    }
+
+    /// The [`Ui`] the layout is in, e.g. for [`Ui::style`].
+    pub fn ui(&self) -> &Ui {
+        self.ui
+    }
+}
+
+#[test]
+fn test_solve() {
+    let a = Item(0);
+    let b = Item(1);
+    let constraints = [
+        a.width().equals(50.0).strength(Strength::Strong),
+        b.width().equals(30.0).strength(Strength::Strong),
+        a.left().equals(10.0),
+        b.left().equals(a.right() + 8.0),
+        a.top().equals(b.top()),
+        b.width().at_least(100.0),
+    ];
+    let mut values = vec![0.0; 2 * VARS_PER_ITEM];
+    solve(&mut values, &constraints);
+
+    let value = |expr: Expr| expr.eval(&values);
+    assert!((value(a.left()) - 10.0).abs() < 0.1);
+    assert!((value(a.width()) - 50.0).abs() < 0.1);
+    assert!((value(b.left()) - 68.0).abs() < 0.1);
+    assert!(value(b.width()) >= 99.9, "required beats strong");
+    assert!((value(a.top()) - value(b.top())).abs() < 0.1);
+}
+
+#[test]
+fn test_constraint_layout() {
+    use crate::test_harness::Harness;
+
+    let container_left = std::cell::Cell::new(0.0);
+    let ok_rect = std::cell::Cell::new(Rect::NOTHING);
+    let harness = Harness::new_ui(|ui| {
+        ConstraintLayout::new("dialog").show(ui, |layout| {
+            container_left.set(layout.left().constant);
+            let (label, _) = layout.add(|ui| ui.label("Name:"));
+            let (ok, ok_response) = layout.add(|ui| ui.button("OK"));
+            ok_rect.set(ok_response.response.rect);
+
+            layout.constrain(label.left().equals(layout.left() + 20.0));
+            layout.constrain(ok.left().equals(label.right() + 8.0));
+            layout.constrain(ok.center_y().equals(label.center_y()));
+            layout.constrain(ok.width().at_least(100.0));
+        });
+    });
+
+    let rect = |label: &str| harness.query_by_label(label).unwrap().rect.rect;
+    let (label, ok) = (rect("Name:"), rect("OK"));
+    assert!((label.left() - (container_left.get() + 20.0)).abs() < 0.5);
+    assert!((ok.left() - (label.right() + 8.0)).abs() < 0.5);
+    assert!((ok.center().y - label.center().y).abs() < 0.5);
+
+    // The item is as wide as the solved width, and as tall as the button:
+    let ok_rect = ok_rect.get();
+    assert!((ok_rect.width() - 100.0).abs() < 0.5);
+    assert!((ok_rect.height() - ok.height()).abs() < 0.5);
+}
@@ -375,21 +375,28 @@
 #![cfg_attr(not(feature = "puffin"), forbid(unsafe_code))]
 
 mod animation_manager;
+mod color_debug;
 pub mod containers;
 mod context;
 mod data;
 pub mod debug_text;
+mod devtools;
 mod drag_and_drop;
+mod feature_flags;
+mod frame_profiler;
 mod frame_state;
+mod gate;
 pub(crate) mod grid;
 pub mod gui_zoom;
 mod hit_test;
 mod id;
+mod input_recording;
 mod input_state;
 mod interaction;
 pub mod introspection;
 pub mod layers;
 mod layout;
+pub mod layout_constraints;
 pub mod load;
 mod memory;
 pub mod menu;
@@ -399,7 +406,11 @@ pub(crate) mod placer;
 mod response;
 mod sense;
 pub mod style;
+pub mod test_harness;
 pub mod text_selection;
+mod theme;
+mod time_travel;
+mod toasts;
 mod ui;
 pub mod util;
 pub mod viewport;
@@ -411,9 +422,18 @@ pub mod widgets;
 #[cfg(debug_assertions)]
 mod callstack;
 
+#[cfg(feature = "snapshot")]
+pub mod snapshot;
+
+#[cfg(test)]
+mod golden;
+
 #[cfg(feature = "accesskit")]
 pub use accesskit;
 
+#[cfg(feature = "accesskit")]
+pub use context::AccessKitMatch;
+
 pub use ahash;
 
 pub use epaint;
@@ -437,14 +457,19 @@ pub use epaint::{
 pub mod text {
     pub use crate::text_selection::{CCursorRange, CursorRange};
     pub use epaint::text::{
-        cursor::CCursor, FontData, FontDefinitions, FontFamily, Fonts, Galley, LayoutJob,
-        LayoutSection, TextFormat, TextWrapping, TAB_SIZE,
+        cursor::CCursor, FontData, FontDefinitions, FontFamily, FontFeatures, FontVariations,
+        Fonts, Galley, LayoutJob, LayoutSection, LineBreak, TextFormat, TextWrapping, TAB_SIZE,
     };
 }
 
 pub use {
+    animation_manager::AnimationOptions,
+    color_debug::{contrast_ratio, ColorBlindness, ColorDebugOptions, ContrastLevel},
     containers::*,
-    context::{Context, RepaintCause, RequestRepaintInfo},
+    context::{
+        Context, ContextCallback, RepaintCause, RequestRepaintInfo, ShapeMapper,
+        TessellateCallback, TimeSource,
+    },
     data::{
         input::*,
         output::{
@@ -453,9 +478,13 @@ pub use {
         Key,
     },
     drag_and_drop::DragAndDrop,
+    frame_profiler::FrameProfile,
+    gate::Gate,
     grid::Grid,
-    id::{Id, IdMap},
-    input_state::{InputState, MultiTouchInfo, PointerState},
+    hit_test::WidgetHits,
+    id::{Id, IdMap, IdSet},
+    input_recording::{InputRecording, RecordedFrame},
+    input_state::{GamepadState, InputState, LongPressOptions, MultiTouchInfo, PointerState},
     layers::{LayerId, Order},
     layout::*,
     load::SizeHint,
@@ -463,8 +492,13 @@ pub use {
     painter::Painter,
     response::{InnerResponse, Response},
     sense::Sense,
-    style::{FontSelection, Margin, Style, TextStyle, Visuals},
+    style::{
+        Breakpoint, FontSelection, KineticScrolling, Margin, Style, TextCursorShape,
+        TextCursorStyle, TextStyle, Visuals,
+    },
     text::{Galley, TextFormat},
+    theme::{Theme, ThemePreference},
+    toasts::{Toast, ToastKind},
     ui::Ui,
     viewport::*,
     widget_rect::{WidgetRect, WidgetRects},
@@ -701,6 +735,15 @@ mod profiling_scopes {
         };
     }
     pub(crate) use profile_scope;
+
+    /// A [`tracing`](https://docs.rs/tracing) span until the end of the scope, for feature "tracing"
+    macro_rules! trace_span {
+        ($($arg: tt)*) => {
+            #[cfg(feature = "tracing")]
+            let _span = tracing::trace_span!($($arg)*).entered();
+        };
+    }
+    pub(crate) use trace_span;
 }
 
 #[allow(unused_imports)]
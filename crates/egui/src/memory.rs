@@ -4,7 +4,7 @@ use ahash::HashMap;
 use epaint::emath::TSTransform;
 
 use crate::{
-    area, vec2, EventFilter, Id, IdMap, LayerId, Order, Pos2, Rangef, Rect, Style, Vec2,
+    area, id::IdSet, vec2, EventFilter, Id, IdMap, LayerId, Order, Pos2, Rangef, Rect, Style, Vec2,
     ViewportId, ViewportIdMap, ViewportIdSet,
 };
 
@@ -78,10 +78,10 @@ pub struct Memory {
     #[cfg_attr(feature = "persistence", serde(skip))]
     pub(crate) viewport_id: ViewportId,
 
-    /// Which popup-window is open (if any)?
-    /// Could be a combo box, color picker, menu etc.
+    /// Which popups are open?
+    /// Could be combo boxes, color pickers etc.
     #[cfg_attr(feature = "persistence", serde(skip))]
-    popup: Option<Id>,
+    pub popups: crate::PopupManager,
 
     #[cfg_attr(feature = "persistence", serde(skip))]
     everything_is_visible: bool,
@@ -95,6 +95,10 @@ pub struct Memory {
 
     #[cfg_attr(feature = "persistence", serde(skip))]
     pub(crate) interactions: ViewportIdMap<InteractionState>,
+
+    /// The scroll offsets shared by [`crate::ScrollArea::sync_with`].
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    synced_scroll_offsets: IdMap<Vec2>,
 }
 
 impl Default for Memory {
@@ -108,8 +112,9 @@ impl Default for Memory {
             viewport_id: Default::default(),
             areas: Default::default(),
             layer_transforms: Default::default(),
-            popup: Default::default(),
+            popups: Default::default(),
             everything_is_visible: Default::default(),
+            synced_scroll_offsets: Default::default(),
         };
         slf.interactions.entry(slf.viewport_id).or_default();
         slf.areas.entry(slf.viewport_id).or_default();
@@ -217,6 +222,66 @@ pub struct Options {
     ///
     /// By default this is `true` in debug builds.
     pub warn_on_id_clash: bool,
+
+    /// If `true`, tooltips, combo-box popups and menus that would be clipped by the edge
+    /// of the viewport are shown in their own small borderless viewport instead.
+    ///
+    /// This has no effect when viewports are embedded (see [`crate::Context::embed_viewports`]),
+    /// in which case they are constrained to the viewport as usual.
+    ///
+    /// See also [`crate::Area::escape_viewport`].
+    ///
+    /// Default: `false`.
+    pub popups_escape_viewport: bool,
+
+    /// Use dark mode, light mode, or follow the theme of the operating system.
+    ///
+    /// When the resulting theme changes, egui fades [`Style::visuals`] to
+    /// [`Self::dark_visuals`] or [`Self::light_visuals`] (unless they are already of that theme),
+    /// and sends [`crate::Event::ThemeChanged`] so you can update your own colors.
+    ///
+    /// Default: [`crate::ThemePreference::System`].
+    pub theme_preference: crate::ThemePreference,
+
+    /// The [`crate::Visuals`] to switch to when the theme becomes dark.
+    ///
+    /// When switching away from dark mode, the current [`Style::visuals`] are stored here,
+    /// so any changes you made to them come back when switching back.
+    ///
+    /// Default: [`crate::Visuals::dark`].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub dark_visuals: crate::Visuals,
+
+    /// The [`crate::Visuals`] to switch to when the theme becomes light.
+    ///
+    /// When switching away from light mode, the current [`Style::visuals`] are stored here,
+    /// so any changes you made to them come back when switching back.
+    ///
+    /// Default: [`crate::Visuals::light`].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub light_visuals: crate::Visuals,
+
+    /// If `false`, everything that animates jumps straight to its new state,
+    /// e.g. collapsing headers, windows fading in and theme changes.
+    ///
+    /// Turn this off for tests and screenshots, or for users that prefer reduced motion.
+    /// To make animations faster or slower, change [`Style::animation_time`] instead.
+    ///
+    /// See also [`crate::AnimationOptions`].
+    ///
+    /// Default: `true`.
+    pub animations_enabled: bool,
+
+    /// Cleans up the [`crate::RawInput`] at the start of each frame.
+    ///
+    /// If you feed egui input from a source you don't trust, e.g. over the network,
+    /// set this to [`crate::InputSanitizer::strict`].
+    pub input_sanitizer: crate::InputSanitizer,
+
+    /// When holding down the primary button counts as a long-press.
+    ///
+    /// See [`crate::Response::long_pressed`].
+    pub long_press: crate::LongPressOptions,
 }
 
 impl Default for Options {
@@ -230,6 +295,13 @@ impl Default for Options {
             screen_reader: false,
             preload_font_glyphs: true,
             warn_on_id_clash: cfg!(debug_assertions),
+            popups_escape_viewport: false,
+            theme_preference: Default::default(),
+            dark_visuals: crate::Visuals::dark(),
+            light_visuals: crate::Visuals::light(),
+            animations_enabled: true,
+            input_sanitizer: Default::default(),
+            long_press: Default::default(),
         }
     }
 }
@@ -246,6 +318,13 @@ impl Options {
             screen_reader: _, // needs to come from the integration
             preload_font_glyphs: _,
             warn_on_id_clash,
+            popups_escape_viewport,
+            theme_preference,
+            dark_visuals: _,  // swapped in by the theme
+            light_visuals: _, // swapped in by the theme
+            animations_enabled,
+            input_sanitizer: _,
+            long_press,
         } = self;
 
         use crate::Widget as _;
@@ -264,12 +343,25 @@ impl Options {
                 );
 
                 ui.checkbox(warn_on_id_clash, "Warn if two widgets have the same Id");
+
+                ui.checkbox(
+                    popups_escape_viewport,
+                    "Show clipped popups in their own viewport",
+                );
+
+                ui.checkbox(animations_enabled, "Animations");
+
+                long_press.ui(ui);
             });
 
         use crate::containers::*;
         CollapsingHeader::new("🎑 Style")
             .default_open(true)
             .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Theme:");
+                    theme_preference.radio_buttons(ui);
+                });
                 std::sync::Arc::make_mut(style).ui(ui);
             });
 
@@ -309,6 +401,10 @@ pub(crate) struct InteractionState {
     /// (at least if the widget is interesated in both clicks and drags).
     pub potential_drag_id: Option<Id>,
 
+    /// When the current multi-touch gesture started,
+    /// and the widget that claimed it (if any widget wanted it).
+    pub gesture_claim: Option<(f64, Option<Id>)>,
+
     pub focus: Focus,
 }
 
@@ -339,6 +435,14 @@ pub(crate) struct Focus {
 
     /// A cache of widget ids that are interested in focus with their corresponding rectangles.
     focus_widgets_cache: IdMap<Rect>,
+
+    /// Widgets that don't take the focus away from the focused widget when clicked or dragged,
+    /// see [`Memory::keep_focus_when_interacting`].
+    keep_focus_this_frame: IdSet,
+
+    /// Like [`Self::keep_focus_this_frame`], but for the previous frame,
+    /// so that it doesn't matter if the focused widget is shown first.
+    keep_focus_prev_frame: IdSet,
 }
 
 /// The widget with focus.
@@ -390,6 +494,7 @@ impl Focus {
 
     fn begin_frame(&mut self, new_input: &crate::data::input::RawInput) {
         self.id_previous_frame = self.focused();
+        self.keep_focus_prev_frame = std::mem::take(&mut self.keep_focus_this_frame);
         if let Some(id) = self.id_next_frame.take() {
             self.focused_widget = Some(FocusWidget::new(id));
         }
@@ -471,6 +576,11 @@ impl Focus {
         self.id_previous_frame == Some(id)
     }
 
+    /// Does interacting with this widget leave the focus where it is?
+    pub(crate) fn keeps_focus(&self, id: Id) -> bool {
+        self.keep_focus_this_frame.contains(&id) || self.keep_focus_prev_frame.contains(&id)
+    }
+
     fn interested_in_focus(&mut self, id: Id) {
         #[cfg(feature = "accesskit")]
         {
@@ -633,6 +743,23 @@ impl Memory {
         self.viewport_id = viewport_id;
     }
 
+    /// Go back to an earlier snapshot, but only for the given viewport.
+    ///
+    /// The areas and interactions of all other viewports are kept as they are now.
+    pub(crate) fn restore_viewport(&mut self, mut snapshot: Self, viewport_id: ViewportId) {
+        snapshot.areas.retain(|id, _| *id == viewport_id);
+        snapshot
+            .areas
+            .extend(self.areas.drain().filter(|(id, _)| *id != viewport_id));
+        snapshot.interactions.retain(|id, _| *id == viewport_id);
+        snapshot.interactions.extend(
+            self.interactions
+                .drain()
+                .filter(|(id, _)| *id != viewport_id),
+        );
+        *self = snapshot;
+    }
+
     /// Access memory of the [`Area`](crate::containers::area::Area)s, such as `Window`s.
     pub fn areas(&self) -> &Areas {
         self.areas
@@ -709,6 +836,11 @@ impl Memory {
         self.interaction_mut().focus.focused_widget = Some(FocusWidget::new(id));
     }
 
+    /// Keep the focus where it is, even if an arrow key or tab was pressed this frame.
+    pub(crate) fn cancel_focus_move(&mut self) {
+        self.interaction_mut().focus.focus_direction = FocusDirection::None;
+    }
+
     /// Surrender keyboard focus for a specific widget.
     /// See also [`crate::Response::surrender_focus`].
     #[inline(always)]
@@ -719,6 +851,30 @@ impl Memory {
         }
     }
 
+    /// Clicking or dragging this widget will not take the keyboard focus away from the widget that has it.
+    ///
+    /// This is for widgets that act on the focused widget,
+    /// e.g. the cells of a spreadsheet that can be clicked, or dragged over to select a range,
+    /// to insert references to them into the formula that is being edited.
+    ///
+    /// Call this every frame.
+    /// See also [`crate::Response::keep_focus_when_interacting`].
+    #[inline(always)]
+    pub fn keep_focus_when_interacting(&mut self, id: Id) {
+        self.interaction_mut()
+            .focus
+            .keep_focus_this_frame
+            .insert(id);
+    }
+
+    /// Does clicking or dragging this widget leave the keyboard focus where it is?
+    ///
+    /// See [`Self::keep_focus_when_interacting`].
+    #[inline(always)]
+    pub fn keeps_focus_when_interacting(&self, id: Id) -> bool {
+        self.interaction().focus.keeps_focus(id)
+    }
+
     /// Register this widget as being interested in getting keyboard focus.
     /// This will allow the user to select it with tab and shift-tab.
     /// This is normally done automatically when handling interactions,
@@ -805,6 +961,28 @@ impl Memory {
         self.areas().get(id.into()).map(|state| state.rect())
     }
 
+    /// The scroll offset shared by the [`crate::ScrollArea`]s that [`crate::ScrollArea::sync_with`] this id.
+    pub fn synced_scroll_offset(&self, sync_id: Id) -> Option<Vec2> {
+        self.synced_scroll_offsets.get(&sync_id).copied()
+    }
+
+    /// Scroll all the [`crate::ScrollArea`]s that [`crate::ScrollArea::sync_with`] this id.
+    ///
+    /// Each scroll area only uses the directions it can scroll in.
+    pub fn set_synced_scroll_offset(&mut self, sync_id: Id, offset: Vec2) {
+        self.synced_scroll_offsets.insert(sync_id, offset);
+    }
+
+    /// The widget that has claimed the current multi-touch gesture, if any.
+    ///
+    /// See [`crate::Response::multi_touch`].
+    pub fn multi_touch_capture(&self) -> Option<Id> {
+        self.interactions
+            .get(&self.viewport_id)
+            .and_then(|interaction| interaction.gesture_claim)
+            .and_then(|(_, id)| id)
+    }
+
     pub(crate) fn interaction(&self) -> &InteractionState {
         self.interactions
             .get(&self.viewport_id)
@@ -818,37 +996,33 @@ impl Memory {
 
 /// ## Popups
 /// Popups are things like combo-boxes, color pickers, menus etc.
-/// Only one can be be open at a time.
+///
+/// Popups opened from within another popup stay open together with it,
+/// otherwise only one can be open at a time. See [`crate::PopupManager`].
 impl Memory {
     /// Is the given popup open?
     pub fn is_popup_open(&self, popup_id: Id) -> bool {
-        self.popup == Some(popup_id) || self.everything_is_visible()
+        self.popups.is_open(popup_id) || self.everything_is_visible()
     }
 
     /// Is any popup open?
     pub fn any_popup_open(&self) -> bool {
-        self.popup.is_some() || self.everything_is_visible()
+        self.popups.any_open() || self.everything_is_visible()
     }
 
-    /// Open the given popup, and close all other.
+    /// Open the given popup, and close all other (except the popups it is shown in).
     pub fn open_popup(&mut self, popup_id: Id) {
-        self.popup = Some(popup_id);
+        self.popups.open(popup_id);
     }
 
-    /// Close the open popup, if any.
+    /// Close all open popups.
     pub fn close_popup(&mut self) {
-        self.popup = None;
+        self.popups.close_all();
     }
 
     /// Toggle the given popup between closed and open.
-    ///
-    /// Note: at most one popup can be open at one time.
     pub fn toggle_popup(&mut self, popup_id: Id) {
-        if self.is_popup_open(popup_id) {
-            self.close_popup();
-        } else {
-            self.open_popup(popup_id);
-        }
+        self.popups.toggle(popup_id);
     }
 
     /// If true, all windows, menus, tooltips etc are to be visible at once.
@@ -1012,3 +1186,35 @@ fn memory_impl_send_sync() {
     fn assert_send_sync<T: Send + Sync>() {}
     assert_send_sync::<Memory>();
 }
+
+#[test]
+fn test_keep_focus_when_interacting() {
+    use crate::{test_harness::Harness, Button, Label, Sense, TextEdit};
+
+    let mut text = String::new();
+    let text_edit_id = Id::new("formula");
+    let mut harness = Harness::new_ui(|ui| {
+        ui.add(TextEdit::singleline(&mut text).id(text_edit_id));
+        // A cell that can be clicked, or dragged over to select a range:
+        ui.add(Label::new("Cell A1").sense(Sense::click_and_drag()))
+            .keep_focus_when_interacting();
+        ui.add(Button::new("Elsewhere"));
+    });
+    let has_focus = |harness: &Harness<'_>| harness.ctx().memory(|mem| mem.has_focus(text_edit_id));
+
+    harness
+        .ctx()
+        .memory_mut(|mem| mem.request_focus(text_edit_id));
+    harness.run();
+    assert!(has_focus(&harness));
+
+    harness.get_by_label("Cell A1").click();
+    assert!(has_focus(&harness));
+
+    let cell = harness.get_by_label("Cell A1").interact_rect();
+    harness.drag(cell.left_center(), cell.right_center());
+    assert!(has_focus(&harness));
+
+    harness.get_by_label("Elsewhere").click();
+    assert!(!has_focus(&harness));
+}
@@ -14,6 +14,14 @@
 //!     });
 //! }
 //! ```
+//!
+//! ## Keyboard
+//! Put a `&` before a letter of a menu title to make it the accelerator of the menu:
+//! `"&File"` is shown as "File", and Alt+F opens it, with the F underlined while Alt is held down.
+//! Use `&&` for a literal `&`.
+//!
+//! While a menu of a bar is open, the left and right arrow keys move to the neighboring menus,
+//! and the up and down arrow keys move between the items.
 
 use super::{
     style::WidgetVisuals, Align, Context, Id, InnerResponse, PointerState, Pos2, Rect, Response,
@@ -27,11 +35,22 @@ use std::sync::Arc;
 #[derive(Clone, Default)]
 pub(crate) struct BarState {
     open_menu: MenuRootManager,
+
+    /// The buttons of the top level menus of the bar, in order,
+    /// for moving between them with the arrow keys.
+    menu_buttons: Vec<(Id, Rect)>,
+
+    /// [`Self::menu_buttons`] of the previous frame, which is complete.
+    prev_menu_buttons: Vec<(Id, Rect)>,
+
+    frame_nr: u64,
 }
 
 impl BarState {
     fn load(ctx: &Context, bar_id: Id) -> Self {
-        ctx.data_mut(|d| d.get_temp::<Self>(bar_id).unwrap_or_default())
+        let mut state = ctx.data_mut(|d| d.get_temp::<Self>(bar_id).unwrap_or_default());
+        state.open_menu.sync_with_popups(ctx);
+        state
     }
 
     fn store(self, ctx: &Context, bar_id: Id) {
@@ -52,6 +71,52 @@ impl BarState {
     pub(crate) fn has_root(&self) -> bool {
         self.open_menu.inner.is_some()
     }
+
+    /// The id of the open menu in [`crate::PopupManager`], if any.
+    pub(crate) fn root_popup_id(&self) -> Option<Id> {
+        self.open_menu.inner.as_ref().map(MenuRoot::popup_id)
+    }
+
+    fn add_menu_button(&mut self, ctx: &Context, button: &Response) {
+        let frame_nr = ctx.frame_nr();
+        if self.frame_nr != frame_nr {
+            self.frame_nr = frame_nr;
+            self.prev_menu_buttons = std::mem::take(&mut self.menu_buttons);
+        }
+        self.menu_buttons.push((button.id, button.rect));
+    }
+
+    fn open_with_keyboard(&mut self, ctx: &Context, button_id: Id, button_rect: Rect) {
+        let menu_response = MenuResponse::Create(button_rect.left_bottom(), button_id);
+        MenuRoot::handle_menu_response(ctx, &mut self.open_menu, menu_response);
+        ctx.memory_mut(|mem| {
+            mem.request_focus(button_id);
+            mem.cancel_focus_move();
+        });
+    }
+
+    /// Move to the previous or next menu of the bar with the left and right arrow keys.
+    fn arrow_key_navigation(&mut self, ctx: &Context, open_button_id: Id) {
+        let (left, right) = ctx.input_mut(|i| {
+            (
+                i.consume_key(Modifiers::NONE, Key::ArrowLeft),
+                i.consume_key(Modifiers::NONE, Key::ArrowRight),
+            )
+        });
+        let buttons = &self.prev_menu_buttons;
+        let Some(index) = buttons.iter().position(|(id, _)| *id == open_button_id) else {
+            return;
+        };
+        let next = if right {
+            (index + 1) % buttons.len()
+        } else if left {
+            (index + buttons.len() - 1) % buttons.len()
+        } else {
+            return;
+        };
+        let (button_id, button_rect) = buttons[next];
+        self.open_with_keyboard(ctx, button_id, button_rect);
+    }
 }
 
 impl std::ops::Deref for BarState {
@@ -148,9 +213,11 @@ pub(crate) fn menu_ui<'c, R>(
         .order(Order::Foreground)
         .fixed_pos(pos)
         .constrain_to(ctx.screen_rect())
-        .interactable(true);
+        .interactable(true)
+        .escape_viewport(ctx.options(|o| o.popups_escape_viewport));
+    let escapes_viewport = area.escapes_viewport(ctx);
 
-    area.show(ctx, |ui| {
+    let inner_response = area.show(ctx, |ui| {
         set_menu_style(ui.style_mut());
 
         let frame = Frame::menu(ui.style()).show(ui, |ui| {
@@ -163,7 +230,14 @@ pub(crate) fn menu_ui<'c, R>(
         menu_state_arc.write().rect = frame.response.rect;
 
         frame.inner
-    })
+    });
+
+    if escapes_viewport {
+        // The rect above is in the coordinates of the new viewport:
+        menu_state_arc.write().rect = inner_response.response.rect;
+    }
+
+    inner_response
 }
 
 /// Build a top level menu with a button.
@@ -174,26 +248,107 @@ fn stationary_menu_impl<'c, R>(
     title: impl Into<WidgetText>,
     add_contents: Box<dyn FnOnce(&mut Ui) -> R + 'c>,
 ) -> InnerResponse<Option<R>> {
-    let title = title.into();
     let bar_id = ui.id();
-    let menu_id = bar_id.with(title.text());
+    let button_id = ui.next_auto_id();
 
     let mut bar_state = BarState::load(ui.ctx(), bar_id);
 
+    let underline = ui.input(|i| i.modifiers.alt) || bar_state.has_root();
+    let (title, accelerator) = accelerator_title(ui, title.into(), underline);
     let mut button = Button::new(title);
 
-    if bar_state.open_menu.is_menu_open(menu_id) {
+    if bar_state.open_menu.is_menu_open(button_id) {
         button = button.fill(ui.visuals().widgets.open.weak_bg_fill);
         button = button.stroke(ui.visuals().widgets.open.bg_stroke);
     }
 
     let button_response = ui.add(button);
+    bar_state.add_menu_button(ui.ctx(), &button_response);
+
+    if let Some(key) = accelerator {
+        if ui.input_mut(|i| i.consume_key(Modifiers::ALT, key)) {
+            bar_state.open_with_keyboard(ui.ctx(), button_response.id, button_response.rect);
+        }
+    }
+    if bar_state.open_menu.is_menu_open(button_response.id) {
+        bar_state.arrow_key_navigation(ui.ctx(), button_response.id);
+    }
+
     let inner = bar_state.bar_menu(&button_response, add_contents);
 
     bar_state.store(ui.ctx(), bar_id);
     InnerResponse::new(inner.map(|r| r.inner), button_response)
 }
 
+/// Remove the `&` that marks the accelerator letter of a menu title like `"&File"`,
+/// and underline the letter if `underline` is set.
+///
+/// Returns the key of the accelerator, if any.
+fn accelerator_title(ui: &Ui, title: WidgetText, underline: bool) -> (WidgetText, Option<Key>) {
+    let WidgetText::RichText(rich_text) = &title else {
+        return (title, None);
+    };
+    let Some((text, accelerator)) = parse_accelerator(rich_text.text()) else {
+        return (title, None);
+    };
+
+    let mut job = title.into_layout_job(ui.style(), TextStyle::Button.into(), Align::Center);
+    let format = job.sections[0].format.clone();
+    job.text = text;
+    let mut sections = vec![(0..job.text.len(), format.clone())];
+    if let (Some((range, _)), true) = (&accelerator, underline) {
+        let mut underlined = format.clone();
+        underlined.underline = Stroke::new(1.0, Color32::PLACEHOLDER);
+        sections = vec![
+            (0..range.start, format.clone()),
+            (range.clone(), underlined),
+            (range.end..job.text.len(), format),
+        ];
+    }
+    job.sections = sections
+        .into_iter()
+        .map(|(byte_range, format)| text::LayoutSection {
+            leading_space: 0.0,
+            byte_range,
+            format,
+        })
+        .collect();
+
+    let key = accelerator.and_then(|(_, c)| Key::from_name(&c.to_uppercase().to_string()));
+    (job.into(), key)
+}
+
+/// `"&File"` becomes `"File"`, with the accelerator `F` at byte range `0..1`.
+///
+/// `&&` becomes a literal `&`. Returns `None` if there is no `&` in the text.
+fn parse_accelerator(text: &str) -> Option<(String, Option<(std::ops::Range<usize>, char)>)> {
+    if !text.contains('&') {
+        return None;
+    }
+
+    let mut stripped = String::with_capacity(text.len());
+    let mut accelerator = None;
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '&' {
+            stripped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('&') => stripped.push('&'),
+            Some(c) => {
+                if accelerator.is_none() {
+                    let start = stripped.len();
+                    accelerator = Some((start..start + c.len_utf8(), c));
+                }
+                stripped.push(c);
+            }
+            None => {}
+        }
+    }
+    Some((stripped, accelerator))
+}
+
 /// Build a top level menu with an image button.
 ///
 /// Responds to primary clicks.
@@ -229,6 +384,162 @@ pub(crate) fn context_menu(
     inner_response
 }
 
+/// A menu built from a list of items, each with an action, e.g. a context menu.
+///
+/// Keyboard shortcuts are shown right-aligned, formatted with [`Context::format_shortcut`].
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// use egui::{menu::ContextMenu, Key, KeyboardShortcut, Modifiers};
+///
+/// enum Action {
+///     Copy,
+///     Paste,
+///     ZoomIn,
+///     ZoomOut,
+/// }
+///
+/// let response = ui.button("Right-click me");
+/// let action = ContextMenu::new()
+///     .item("Copy", KeyboardShortcut::new(Modifiers::COMMAND, Key::C), Action::Copy)
+///     .item("Paste", KeyboardShortcut::new(Modifiers::COMMAND, Key::V), Action::Paste)
+///     .separator()
+///     .submenu(
+///         "Zoom",
+///         ContextMenu::new()
+///             .item("In", None, Action::ZoomIn)
+///             .item("Out", None, Action::ZoomOut),
+///     )
+///     .show(&response);
+///
+/// match action {
+///     Some(Action::Copy) => { /* … */ }
+///     _ => {}
+/// }
+/// # });
+/// ```
+#[must_use = "You should call .show() or .ui()"]
+pub struct ContextMenu<Action> {
+    entries: Vec<ContextMenuEntry<Action>>,
+}
+
+enum ContextMenuEntry<Action> {
+    Item {
+        text: WidgetText,
+        shortcut: Option<KeyboardShortcut>,
+        action: Action,
+        enabled: bool,
+    },
+    Separator,
+    Submenu {
+        text: WidgetText,
+        menu: ContextMenu<Action>,
+    },
+}
+
+impl<Action> Default for ContextMenu<Action> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Action> ContextMenu<Action> {
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+
+    /// Add an item which picks `action` when clicked.
+    ///
+    /// The `shortcut` is only shown, it is up to you to check for it.
+    #[inline]
+    pub fn item(
+        mut self,
+        text: impl Into<WidgetText>,
+        shortcut: impl Into<Option<KeyboardShortcut>>,
+        action: Action,
+    ) -> Self {
+        self.entries.push(ContextMenuEntry::Item {
+            text: text.into(),
+            shortcut: shortcut.into(),
+            action,
+            enabled: true,
+        });
+        self
+    }
+
+    /// If `false`, the item added last is grayed out and can't be picked.
+    #[inline]
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        if let Some(ContextMenuEntry::Item { enabled: e, .. }) = self.entries.last_mut() {
+            *e = enabled;
+        }
+        self
+    }
+
+    /// Add a line between items.
+    #[inline]
+    pub fn separator(mut self) -> Self {
+        self.entries.push(ContextMenuEntry::Separator);
+        self
+    }
+
+    /// Add a sub-menu, which opens when hovered.
+    #[inline]
+    pub fn submenu(mut self, text: impl Into<WidgetText>, menu: Self) -> Self {
+        self.entries.push(ContextMenuEntry::Submenu {
+            text: text.into(),
+            menu,
+        });
+        self
+    }
+
+    /// Show the menu when `response` is secondary-clicked (right-clicked).
+    ///
+    /// Returns the action of the item that was picked this frame, if any.
+    pub fn show(self, response: &Response) -> Option<Action> {
+        let mut picked = None;
+        response.context_menu(|ui| picked = self.ui(ui));
+        picked
+    }
+
+    /// Add the items to a menu that is already open, e.g. in [`Ui::menu_button`].
+    ///
+    /// Returns the action of the item that was picked this frame, if any.
+    pub fn ui(self, ui: &mut Ui) -> Option<Action> {
+        let mut picked = None;
+        for entry in self.entries {
+            match entry {
+                ContextMenuEntry::Item {
+                    text,
+                    shortcut,
+                    action,
+                    enabled,
+                } => {
+                    let mut button = Button::new(text);
+                    if let Some(shortcut) = &shortcut {
+                        button = button.shortcut_text(ui.ctx().format_shortcut(shortcut));
+                    }
+                    if ui.add_enabled(enabled, button).clicked() {
+                        picked = Some(action);
+                        ui.close_menu();
+                    }
+                }
+                ContextMenuEntry::Separator => {
+                    ui.separator();
+                }
+                ContextMenuEntry::Submenu { text, menu } => {
+                    if let Some(action) = ui.menu_button(text, |ui| menu.ui(ui)).inner.flatten() {
+                        picked = Some(action);
+                    }
+                }
+            }
+        }
+        picked
+    }
+}
+
 /// Stores the state for the context menu.
 #[derive(Clone, Default)]
 pub(crate) struct MenuRootManager {
@@ -246,7 +557,7 @@ impl MenuRootManager {
         if let Some(root) = self.inner.as_mut() {
             let (menu_response, inner_response) = root.show(response, add_contents);
             if MenuResponse::Close == menu_response {
-                self.inner = None;
+                MenuRoot::handle_menu_response(&response.ctx, self, menu_response);
             }
             inner_response
         } else {
@@ -257,6 +568,17 @@ impl MenuRootManager {
     fn is_menu_open(&self, id: Id) -> bool {
         self.inner.as_ref().map(|m| m.id) == Some(id)
     }
+
+    /// Forget the open menu if it was closed in the [`crate::PopupManager`],
+    /// e.g. because a combo box or another menu was opened.
+    fn sync_with_popups(&mut self, ctx: &Context) {
+        if let Some(root) = &self.inner {
+            let popup_id = root.popup_id();
+            if !ctx.memory(|mem| mem.popups.is_open(popup_id)) {
+                self.inner = None;
+            }
+        }
+    }
 }
 
 impl std::ops::Deref for MenuRootManager {
@@ -294,8 +616,14 @@ impl MenuRoot {
         add_contents: impl FnOnce(&mut Ui) -> R,
     ) -> (MenuResponse, Option<InnerResponse<R>>) {
         if self.id == response.id {
-            let inner_response =
-                MenuState::show(&response.ctx, &self.menu_state, self.id, add_contents);
+            let ctx = &response.ctx;
+            let popup_id = self.popup_id();
+
+            // Popups opened from within the menu become its children:
+            ctx.memory_mut(|mem| mem.popups.begin_showing(popup_id));
+            let inner_response = MenuState::show(ctx, &self.menu_state, popup_id, add_contents);
+            ctx.memory_mut(|mem| mem.popups.end_showing());
+
             let menu_state = self.menu_state.read();
 
             if menu_state.response.is_close() {
@@ -305,6 +633,12 @@ impl MenuRoot {
         (MenuResponse::Stay, None)
     }
 
+    /// The id of the menu in [`crate::PopupManager`], which is also the id of its area.
+    fn popup_id(&self) -> Id {
+        // The area of the menu can't have the same id as the widget it belongs to:
+        self.id.with("menu")
+    }
+
     /// Interaction with a stationary menu, i.e. fixed in another Ui.
     ///
     /// Responds to primary clicks.
@@ -360,6 +694,11 @@ impl MenuRoot {
     /// Interaction with a context menu (secondary clicks).
     fn context_interaction(response: &Response, root: &mut Option<Self>, id: Id) -> MenuResponse {
         let response = response.interact(Sense::click());
+        let is_touch_os = matches!(
+            response.ctx.os(),
+            crate::os::OperatingSystem::Android | crate::os::OperatingSystem::IOS
+        );
+        let long_pressed = is_touch_os && response.long_pressed();
         response.ctx.input(|input| {
             let pointer = &input.pointer;
             if let Some(pos) = pointer.interact_pos() {
@@ -370,9 +709,13 @@ impl MenuRoot {
                     destroy = !in_old_menu && pointer.any_pressed() && root.id == response.id;
                 }
                 if !in_old_menu {
-                    if response.hovered() && response.secondary_clicked() {
+                    if response.hovered() && (response.secondary_clicked() || long_pressed) {
                         return MenuResponse::Create(pos, id);
-                    } else if (response.hovered() && pointer.primary_down()) || destroy {
+                    } else if (response.hovered()
+                        && pointer.primary_down()
+                        && !pointer.is_long_pressing())
+                        || destroy
+                    {
                         return MenuResponse::Close;
                     }
                 }
@@ -381,12 +724,30 @@ impl MenuRoot {
         })
     }
 
-    fn handle_menu_response(root: &mut MenuRootManager, menu_response: MenuResponse) {
+    /// Open or close the menu, both here and in [`crate::PopupManager`],
+    /// so that opening a menu closes other popups, and vice versa.
+    fn handle_menu_response(
+        ctx: &Context,
+        root: &mut MenuRootManager,
+        menu_response: MenuResponse,
+    ) {
         match menu_response {
             MenuResponse::Create(pos, id) => {
-                root.inner = Some(Self::new(pos, id));
+                let menu = Self::new(pos, id);
+                let popup_id = menu.popup_id();
+                ctx.memory_mut(|mem| {
+                    // Re-opening an open menu would close the popups opened from within it:
+                    if !mem.popups.is_open(popup_id) {
+                        mem.open_popup(popup_id);
+                    }
+                });
+                root.inner = Some(menu);
+            }
+            MenuResponse::Close => {
+                if let Some(menu) = root.inner.take() {
+                    ctx.memory_mut(|mem| mem.popups.close(menu.popup_id()));
+                }
             }
-            MenuResponse::Close => root.inner = None,
             MenuResponse::Stay => {}
         }
     }
@@ -394,13 +755,18 @@ impl MenuRoot {
     /// Respond to secondary (right) clicks.
     pub fn context_click_interaction(response: &Response, root: &mut MenuRootManager, id: Id) {
         let menu_response = Self::context_interaction(response, root, id);
-        Self::handle_menu_response(root, menu_response);
+        if matches!(menu_response, MenuResponse::Create(..)) {
+            response
+                .ctx
+                .emit_haptic(crate::output::HapticKind::MenuOpened);
+        }
+        Self::handle_menu_response(&response.ctx, root, menu_response);
     }
 
     // Responds to primary clicks.
     pub fn stationary_click_interaction(response: &Response, root: &mut MenuRootManager, id: Id) {
         let menu_response = Self::stationary_interaction(response, root, id);
-        Self::handle_menu_response(root, menu_response);
+        Self::handle_menu_response(&response.ctx, root, menu_response);
     }
 }
 
@@ -605,12 +971,25 @@ impl MenuState {
     fn submenu_button_interaction(&mut self, ui: &Ui, sub_id: Id, button: &Response) {
         let pointer = ui.input(|i| i.pointer.clone());
         let open = self.is_open(sub_id);
-        if self.moving_towards_current_submenu(&pointer) {
+        let interaction = &ui.style().interaction;
+
+        if !open && button.clicked() {
+            // Also opens with the keyboard:
+            self.open_submenu(sub_id, button.rect.right_top());
+        } else if interaction.submenu_hover_intent && self.moving_towards_current_submenu(&pointer)
+        {
             // ensure to repaint once even when pointer is not moving
             ui.ctx().request_repaint();
         } else if !open && button.hovered() {
-            let pos = button.rect.right_top();
-            self.open_submenu(sub_id, pos);
+            let remaining = interaction.submenu_open_delay - ui.ctx().hover_duration(button.id);
+            if remaining <= 0.0 {
+                self.open_submenu(sub_id, button.rect.right_top());
+            } else {
+                // An infinite (or NaN) delay means never:
+                let delay = std::time::Duration::try_from_secs_f32(remaining)
+                    .unwrap_or(std::time::Duration::MAX);
+                ui.ctx().request_repaint_after(delay);
+            }
         }
     }
 
@@ -667,3 +1046,233 @@ impl MenuState {
         }
     }
 }
+
+#[test]
+fn test_parse_accelerator() {
+    assert_eq!(parse_accelerator("Plain"), None);
+    assert_eq!(
+        parse_accelerator("&File"),
+        Some(("File".to_owned(), Some((0..1, 'F'))))
+    );
+    assert_eq!(
+        parse_accelerator("Save && &Quit"),
+        Some(("Save & Quit".to_owned(), Some((7..8, 'Q'))))
+    );
+    assert_eq!(
+        parse_accelerator("A && B"),
+        Some(("A & B".to_owned(), None))
+    );
+}
+
+#[test]
+fn test_menu_keyboard_navigation() {
+    use crate::test_harness::Harness;
+
+    let mut harness = Harness::new_ui(|ui| {
+        bar(ui, |ui| {
+            ui.menu_button("&File", |ui| {
+                let _ = ui.button("Open");
+            });
+            ui.menu_button("&Edit", |ui| {
+                let _ = ui.button("Undo");
+            });
+        });
+    });
+    let is_shown = |harness: &Harness<'_>, label| harness.query_by_label(label).is_some();
+    assert!(is_shown(&harness, "File"));
+    assert!(!is_shown(&harness, "Open"));
+
+    // Alt and the accelerator opens a menu:
+    harness.set_modifiers(Modifiers::ALT);
+    harness.press_key(Key::F);
+    harness.set_modifiers(Modifiers::NONE);
+    assert!(is_shown(&harness, "Open"));
+
+    // The arrow keys move between the menus of the bar:
+    harness.press_key(Key::ArrowRight);
+    assert!(!is_shown(&harness, "Open"));
+    assert!(is_shown(&harness, "Undo"));
+    harness.press_key(Key::ArrowRight);
+    assert!(is_shown(&harness, "Open"));
+
+    harness.press_key(Key::Escape);
+    assert!(!is_shown(&harness, "Open"));
+}
+
+#[test]
+fn test_menus_and_popups_close_each_other() {
+    use crate::test_harness::Harness;
+
+    let popup_id = Id::new("popup");
+    let child_id = Id::new("child");
+    let mut harness = Harness::new_ui(|ui| {
+        bar(ui, |ui| {
+            ui.menu_button("&File", |ui| {
+                let response = ui.button("Open child");
+                if response.clicked() {
+                    ui.memory_mut(|mem| mem.toggle_popup(child_id));
+                }
+                ui.popup(child_id, &response, |ui| ui.label("Child"));
+            });
+        });
+        ComboBox::from_id_source("combo").show_ui(ui, |ui| {
+            ui.label("Combo item");
+        });
+        let response = ui.button("Open popup");
+        if response.clicked() {
+            ui.memory_mut(|mem| mem.toggle_popup(popup_id));
+        }
+        let options = PopupOptions {
+            close_behavior: PopupCloseBehavior::IgnoreClicks,
+            ..Default::default()
+        };
+        ui.popup_with_options(popup_id, &response, options, |ui| ui.label("Popup"));
+    });
+    let is_shown = |harness: &Harness<'_>, label| harness.query_by_label(label).is_some();
+    let open_menu = |harness: &mut Harness<'_>| {
+        harness.set_modifiers(Modifiers::ALT);
+        harness.press_key(Key::F);
+        harness.set_modifiers(Modifiers::NONE);
+    };
+
+    // Opening a menu closes an open combo box:
+    harness
+        .get_by(|info| info.typ == WidgetType::ComboBox)
+        .click();
+    assert!(is_shown(&harness, "Combo item"));
+    open_menu(&mut harness);
+    assert!(is_shown(&harness, "Open child"));
+    assert!(!is_shown(&harness, "Combo item"));
+    harness.press_key(Key::Escape);
+
+    // …and a popup that ignores clicks:
+    harness.get_by_label("Open popup").click();
+    assert!(is_shown(&harness, "Popup"));
+    open_menu(&mut harness);
+    assert!(is_shown(&harness, "Open child"));
+    assert!(!is_shown(&harness, "Popup"));
+
+    // Popups opened from within the menu are its children:
+    harness.get_by_label("Open child").click();
+    assert!(is_shown(&harness, "Open child"));
+    assert!(is_shown(&harness, "Child"));
+    harness.press_key(Key::Escape);
+    assert!(!is_shown(&harness, "Open child"));
+    assert!(!is_shown(&harness, "Child"));
+
+    // Opening another popup closes the menu:
+    open_menu(&mut harness);
+    assert!(is_shown(&harness, "Open child"));
+    harness.ctx().memory_mut(|mem| mem.open_popup(popup_id));
+    harness.run();
+    assert!(is_shown(&harness, "Popup"));
+    assert!(!is_shown(&harness, "Open child"));
+}
+
+#[test]
+fn test_submenu_open_delay() {
+    use crate::test_harness::Harness;
+
+    let mut harness = Harness::new_ui(|ui| {
+        bar(ui, |ui| {
+            ui.menu_button("File", |ui| {
+                ui.menu_button("Recent", |ui| {
+                    let _ = ui.button("notes.txt");
+                });
+            });
+        });
+    });
+
+    harness
+        .ctx()
+        .style_mut(|style| style.interaction.submenu_open_delay = 3.0);
+    harness.get_by_label("File").click();
+    harness.get_by_label("Recent").hover();
+    assert!(harness.query_by_label("notes.txt").is_none());
+    for _ in 0..180 {
+        harness.step();
+    }
+    assert!(harness.query_by_label("notes.txt").is_some());
+}
+
+#[test]
+fn test_context_menu_actions() {
+    use crate::test_harness::Harness;
+    use std::cell::RefCell;
+
+    #[derive(Debug, PartialEq)]
+    enum Action {
+        Copy,
+        Paste,
+        ZoomIn,
+    }
+
+    let picked = RefCell::new(vec![]);
+    let mut harness = Harness::new_ui(|ui| {
+        bar(ui, |ui| {
+            ui.menu_button("Edit", |ui| {
+                let menu = ContextMenu::new()
+                    .item(
+                        "Copy",
+                        KeyboardShortcut::new(Modifiers::COMMAND, Key::C),
+                        Action::Copy,
+                    )
+                    .item("Paste", None, Action::Paste)
+                    .enabled(false)
+                    .separator()
+                    .submenu("Zoom", ContextMenu::new().item("In", None, Action::ZoomIn));
+                picked.borrow_mut().extend(menu.ui(ui));
+            });
+        });
+    });
+
+    harness.get_by_label("Edit").click();
+    harness.get_by_label("Paste").click();
+    assert!(picked.borrow().is_empty(), "disabled items can't be picked");
+
+    harness.get_by_label("Copy").click();
+    assert_eq!(*picked.borrow(), vec![Action::Copy]);
+    assert!(harness.query_by_label("Copy").is_none(), "the menu closes");
+
+    harness.get_by_label("Edit").click();
+    harness.get_by_label("Zoom").click();
+    harness.get_by_label("In").click();
+    assert_eq!(*picked.borrow(), vec![Action::Copy, Action::ZoomIn]);
+}
+
+#[test]
+fn test_long_press_context_menu() {
+    use crate::os::OperatingSystem;
+    use crate::test_harness::Harness;
+    use std::cell::Cell;
+
+    let clicked = Cell::new(false);
+    let long_pressed = Cell::new(false);
+    let mut harness = Harness::new_ui(|ui| {
+        let response = ui.button("Item");
+        clicked.set(clicked.get() | response.clicked());
+        long_pressed.set(long_pressed.get() | response.long_pressed());
+        response.context_menu(|ui| {
+            ui.label("Context menu");
+        });
+    });
+    let item_pos = harness.query_by_label("Item").unwrap().rect.rect.center();
+
+    // A long-press is not a click, and only opens context menus on touch screen platforms:
+    harness.ctx().set_os(OperatingSystem::Windows);
+    harness.long_press(item_pos);
+    assert!(long_pressed.take());
+    assert!(!clicked.get());
+    assert!(harness.query_by_label("Context menu").is_none());
+
+    harness.ctx().set_os(OperatingSystem::Android);
+    harness.long_press(item_pos);
+    assert!(long_pressed.take());
+    assert!(!clicked.get());
+    assert!(harness.query_by_label("Context menu").is_some());
+
+    // A short tap is still a click:
+    harness.click(item_pos);
+    assert!(clicked.get());
+    assert!(!long_pressed.get());
+}
@@ -179,10 +179,32 @@ impl Painter {
         } else {
             let mut shape = shape.into();
             self.transform_shape(&mut shape);
+            #[cfg(debug_assertions)]
+            if let Shape::Text(text) = &shape {
+                self.debug_missing_glyphs(text);
+            }
             self.paint_list(|l| l.add(self.clip_rect, shape))
         }
     }
 
+    /// Outline the replacement characters, see [`crate::style::DebugOptions::show_missing_glyphs`].
+    #[cfg(debug_assertions)]
+    fn debug_missing_glyphs(&self, text: &epaint::TextShape) {
+        let galley = &text.galley;
+        if galley.missing_glyphs.is_empty() || !self.ctx.style().debug.show_missing_glyphs {
+            return;
+        }
+        let painter = self.ctx.debug_painter();
+        for row in &galley.rows {
+            for glyph in &row.glyphs {
+                if galley.missing_glyphs.binary_search(&glyph.chr).is_ok() {
+                    let rect = glyph.logical_rect().translate(text.pos.to_vec2());
+                    painter.rect_stroke(rect, 0.0, (1.0, Color32::RED));
+                }
+            }
+        }
+    }
+
     /// Add many shapes at once.
     ///
     /// Calling this once is generally faster than calling [`Self::add`] multiple times.
@@ -247,6 +269,36 @@ impl Painter {
         self.debug_text(pos, Align2::LEFT_TOP, color, format!("🔥 {text}"))
     }
 
+    /// Paint a ruler from `from` to `to`, labeled with the distance in points and in physical pixels.
+    ///
+    /// Useful for measuring the distances between widgets, e.g. on [`Context::overlay_painter`].
+    /// Returns the rect of the label.
+    pub fn measurement(&self, from: Pos2, to: Pos2) -> Rect {
+        let color = Color32::from_rgb(255, 0, 255);
+        let pixels_per_point = self.ctx.pixels_per_point();
+
+        // The horizontal and vertical legs, and the diagonal:
+        let corner = Pos2::new(to.x, from.y);
+        let legs = Stroke::new(1.0, color.linear_multiply(0.5));
+        self.line_segment([from, corner], legs);
+        self.line_segment([corner, to], legs);
+        self.line_segment([from, to], (1.0, color));
+        self.circle_filled(from, 2.0, color);
+        self.circle_filled(to, 2.0, color);
+
+        let delta = to - from;
+        let text = format!(
+            "{:.1} × {:.1} pt ({:.0} × {:.0} px)\n{:.1} pt ({:.0} px)",
+            delta.x.abs(),
+            delta.y.abs(),
+            delta.x.abs() * pixels_per_point,
+            delta.y.abs() * pixels_per_point,
+            delta.length(),
+            delta.length() * pixels_per_point,
+        );
+        self.debug_text(to + Vec2::splat(8.0), Align2::LEFT_TOP, color, text)
+    }
+
     /// Text with a background.
     ///
     /// See also [`Context::debug_text`].
@@ -5,6 +5,15 @@ pub(crate) struct Placer {
     grid: Option<grid::GridLayout>,
     layout: Layout,
     region: Region,
+
+    /// Widgets are made at least this wide, see [`Ui::horizontal_equal_width`].
+    min_item_width: f32,
+
+    /// Added to the spacing between widgets, see [`Ui::horizontal_justified`].
+    extra_item_spacing: f32,
+
+    /// The widths of the widgets placed so far, if we are measuring them.
+    item_widths: Option<Vec<f32>>,
 }
 
 impl Placer {
@@ -14,9 +23,29 @@ impl Placer {
             grid: None,
             layout,
             region,
+            min_item_width: 0.0,
+            extra_item_spacing: 0.0,
+            item_widths: None,
         }
     }
 
+    /// Make the widgets at least `min_item_width` wide,
+    /// and put `extra_item_spacing` between them on top of the normal item spacing.
+    pub(crate) fn set_item_distribution(&mut self, min_item_width: f32, extra_item_spacing: f32) {
+        self.min_item_width = min_item_width;
+        self.extra_item_spacing = extra_item_spacing;
+    }
+
+    /// Start recording the widths of the widgets, see [`Self::take_item_widths`].
+    pub(crate) fn measure_item_widths(&mut self) {
+        self.item_widths = Some(Vec::new());
+    }
+
+    /// The widths of the widgets placed since [`Self::measure_item_widths`], in order.
+    pub(crate) fn take_item_widths(&mut self) -> Vec<f32> {
+        self.item_widths.take().unwrap_or_default()
+    }
+
     #[inline(always)]
     pub(crate) fn set_grid(&mut self, grid: grid::GridLayout) {
         self.grid = Some(grid);
@@ -33,6 +62,11 @@ impl Placer {
         self.grid.as_ref()
     }
 
+    #[inline(always)]
+    pub(crate) fn grid_mut(&mut self) -> Option<&mut grid::GridLayout> {
+        self.grid.as_mut()
+    }
+
     #[inline(always)]
     pub(crate) fn is_grid(&self) -> bool {
         self.grid.is_some()
@@ -111,6 +145,7 @@ impl Placer {
         if let Some(grid) = &self.grid {
             grid.next_cell(self.region.cursor, child_size)
         } else {
+            let child_size = vec2(child_size.x.max(self.min_item_width), child_size.y);
             self.layout
                 .next_frame(&self.region, child_size, item_spacing)
         }
@@ -133,6 +168,7 @@ impl Placer {
         if let Some(grid) = &self.grid {
             grid.justify_and_align(rect, child_size)
         } else {
+            let child_size = vec2(child_size.x.max(self.min_item_width), child_size.y);
             self.layout.justify_and_align(rect, child_size)
         }
     }
@@ -162,6 +198,10 @@ impl Placer {
         egui_assert!(!widget_rect.any_nan());
         self.region.sanity_check();
 
+        if let Some(item_widths) = &mut self.item_widths {
+            item_widths.push(widget_rect.width());
+        }
+
         if let Some(grid) = &mut self.grid {
             grid.advance(&mut self.region.cursor, frame_rect, widget_rect);
         } else {
@@ -169,7 +209,7 @@ impl Placer {
                 &mut self.region.cursor,
                 frame_rect,
                 widget_rect,
-                item_spacing,
+                item_spacing + vec2(self.extra_item_spacing, 0.0),
             );
         }
 
@@ -2,8 +2,8 @@ use std::{any::Any, sync::Arc};
 
 use crate::{
     emath::{Align, Pos2, Rect, Vec2},
-    menu, Context, CursorIcon, Id, LayerId, PointerButton, Sense, Ui, WidgetRect, WidgetText,
-    NUM_POINTER_BUTTONS,
+    menu, Context, CursorIcon, Id, LayerId, MultiTouchInfo, PenState, PointerButton, PointerId,
+    Sense, Ui, WidgetRect, WidgetText, NUM_POINTER_BUTTONS,
 };
 
 // ----------------------------------------------------------------------------
@@ -162,6 +162,17 @@ impl Response {
         self.triple_clicked[button as usize]
     }
 
+    /// Returns true if the primary button was held down on this widget this frame
+    /// long enough, and still enough, to count as a long-press.
+    ///
+    /// This is how touch screen users open context menus.
+    /// Releasing a long-press does not also click the widget.
+    ///
+    /// The thresholds are set in [`crate::Options::long_press`].
+    pub fn long_pressed(&self) -> bool {
+        self.is_pointer_button_down_on && self.ctx.input(|i| i.pointer.long_press_started())
+    }
+
     /// `true` if there was a click *outside* this widget this frame.
     pub fn clicked_elsewhere(&self) -> bool {
         // We do not use self.clicked(), because we want to catch all clicks within our frame,
@@ -268,6 +279,14 @@ impl Response {
         self.ctx.memory_mut(|mem| mem.surrender_focus(self.id));
     }
 
+    /// Clicking or dragging this widget will not take the keyboard focus away from the widget that has it.
+    ///
+    /// See [`crate::Memory::keep_focus_when_interacting`].
+    pub fn keep_focus_when_interacting(&self) {
+        self.ctx
+            .memory_mut(|mem| mem.keep_focus_when_interacting(self.id));
+    }
+
     /// Did a drag on this widgets begin this frame?
     ///
     /// This is only true if the widget sense drags.
@@ -339,10 +358,169 @@ impl Response {
         self.drag_stopped_by(button)
     }
 
+    /// Is the given pointer hovering this widget?
+    ///
+    /// [`PointerId::PRIMARY`] is the same as [`Self::hovered`].
+    /// Other pointers come from [`crate::Event::ExtraPointer`] and are tracked separately,
+    /// so that e.g. several users of a touch table can use different widgets at the same time.
+    pub fn hovered_by_pointer(&self, pointer: PointerId) -> bool {
+        if pointer == PointerId::PRIMARY {
+            self.hovered()
+        } else {
+            self.is_interacted_by(pointer, |i| i.hovered.contains(&self.id))
+        }
+    }
+
+    /// Did the given pointer click this widget this frame?
+    ///
+    /// [`PointerId::PRIMARY`] is the same as [`Self::clicked`].
+    pub fn clicked_by_pointer(&self, pointer: PointerId) -> bool {
+        if pointer == PointerId::PRIMARY {
+            self.clicked()
+        } else {
+            self.sense.click && self.is_interacted_by(pointer, |i| i.clicked == Some(self.id))
+        }
+    }
+
+    /// Is the given pointer dragging this widget?
+    ///
+    /// [`PointerId::PRIMARY`] is the same as [`Self::dragged`].
+    pub fn dragged_by_pointer(&self, pointer: PointerId) -> bool {
+        if pointer == PointerId::PRIMARY {
+            self.dragged()
+        } else {
+            self.is_interacted_by(pointer, |i| i.dragged == Some(self.id))
+        }
+    }
+
+    /// Did the given pointer start dragging this widget this frame?
+    ///
+    /// [`PointerId::PRIMARY`] is the same as [`Self::drag_started`].
+    pub fn drag_started_by_pointer(&self, pointer: PointerId) -> bool {
+        if pointer == PointerId::PRIMARY {
+            self.drag_started()
+        } else {
+            self.is_interacted_by(pointer, |i| i.drag_started == Some(self.id))
+        }
+    }
+
+    /// Did the given pointer stop dragging this widget this frame?
+    ///
+    /// [`PointerId::PRIMARY`] is the same as [`Self::drag_stopped`].
+    pub fn drag_stopped_by_pointer(&self, pointer: PointerId) -> bool {
+        if pointer == PointerId::PRIMARY {
+            self.drag_stopped()
+        } else {
+            self.is_interacted_by(pointer, |i| i.drag_stopped == Some(self.id))
+        }
+    }
+
+    /// If dragged by the given pointer, how many points was it dragged and in what direction?
+    ///
+    /// [`PointerId::PRIMARY`] is the same as [`Self::drag_delta`].
+    pub fn pointer_drag_delta(&self, pointer: PointerId) -> Vec2 {
+        if pointer == PointerId::PRIMARY {
+            self.drag_delta()
+        } else if self.dragged_by_pointer(pointer) {
+            let delta = self
+                .ctx
+                .input(|i| i.pointer_state(pointer).map(|p| p.delta()))
+                .unwrap_or_default();
+            let scaling = self
+                .ctx
+                .memory(|m| m.layer_transforms.get(&self.layer_id).map(|t| t.scaling));
+            delta / scaling.unwrap_or(1.0)
+        } else {
+            Vec2::ZERO
+        }
+    }
+
+    /// Also count the hovers, clicks and drags of the pointers in [`crate::InputState::extra_pointers`],
+    /// as if they were made by the primary pointer.
+    ///
+    /// The built-in widgets like [`crate::Button`], [`crate::Checkbox`], [`crate::Slider`]
+    /// and [`crate::DragValue`] call this, so that e.g. several users of a touch table
+    /// can each use a different widget at the same time.
+    /// Call it in your own widgets too, right after sensing the interaction.
+    ///
+    /// Afterwards [`Self::interact_pointer_pos`] and [`Self::drag_delta`]
+    /// follow whichever pointer is interacting with the widget.
+    #[must_use]
+    pub fn with_extra_pointers(mut self) -> Self {
+        if !self.enabled {
+            return self;
+        }
+
+        let pointers: Vec<(PointerId, Option<Pos2>, bool)> = self.ctx.input(|i| {
+            i.extra_pointers
+                .iter()
+                .map(|(id, pointer)| (*id, pointer.interact_pos(), pointer.any_down()))
+                .collect()
+        });
+        for (pointer, interact_pos, any_down) in pointers {
+            let hovered = self.hovered_by_pointer(pointer);
+            let clicked = self.clicked_by_pointer(pointer);
+            let dragged = self.dragged_by_pointer(pointer);
+            let drag_stopped = self.drag_stopped_by_pointer(pointer);
+            let down_on = dragged || (hovered && any_down && self.sense.interactive());
+
+            if (clicked || dragged || drag_stopped || down_on)
+                && self.interact_pointer_pos.is_none()
+            {
+                self.interact_pointer_pos = interact_pos;
+            }
+            self.hovered |= hovered;
+            self.clicked[PointerButton::Primary as usize] |= clicked;
+            self.dragged |= dragged;
+            self.drag_started |= self.drag_started_by_pointer(pointer);
+            self.drag_stopped |= drag_stopped;
+            self.is_pointer_button_down_on |= down_on;
+        }
+        self
+    }
+
+    /// Which pointer is dragging this widget, if any.
+    ///
+    /// This is [`PointerId::PRIMARY`], unless the widget called [`Self::with_extra_pointers`]
+    /// and is only dragged by one of the [`crate::InputState::extra_pointers`].
+    pub fn dragging_pointer(&self) -> Option<PointerId> {
+        if !self.dragged() {
+            return None;
+        }
+        if self.ctx.dragged_id() == Some(self.id) {
+            return Some(PointerId::PRIMARY);
+        }
+        let pointers: Vec<PointerId> = self
+            .ctx
+            .input(|i| i.extra_pointers.keys().copied().collect());
+        let extra = pointers
+            .into_iter()
+            .find(|pointer| self.ctx.pointer_dragged_id(*pointer) == Some(self.id));
+        Some(extra.unwrap_or(PointerId::PRIMARY))
+    }
+
+    fn is_interacted_by(
+        &self,
+        pointer: PointerId,
+        reader: impl FnOnce(&crate::interaction::InteractionSnapshot) -> bool,
+    ) -> bool {
+        self.enabled
+            && self
+                .ctx
+                .pointer_interaction_snapshot(pointer, reader)
+                .unwrap_or(false)
+    }
+
     /// If dragged, how many points were we dragged and in what direction?
     #[inline]
     pub fn drag_delta(&self) -> Vec2 {
         if self.dragged() {
+            if let Some(pointer) = self
+                .dragging_pointer()
+                .filter(|pointer| *pointer != PointerId::PRIMARY)
+            {
+                return self.pointer_drag_delta(pointer);
+            }
             let mut delta = self.ctx.input(|i| i.pointer.delta());
             if let Some(scaling) = self
                 .ctx
@@ -356,6 +534,68 @@ impl Response {
         }
     }
 
+    /// The multi-touch gesture (e.g. pinch or rotate) this widget has claimed, if any.
+    ///
+    /// Each gesture goes to a single widget: the one that would be dragged where the center
+    /// of the fingers was when the gesture started (see [`MultiTouchInfo::start_center_pos`]).
+    /// So the widget must sense drags, like plots and scroll areas do.
+    /// The widget keeps the gesture until the fingers are lifted, even if they move outside it.
+    ///
+    /// A widget that only senses hovers (e.g. [`Sense::hover`]) never gets a gesture,
+    /// so use [`Sense::drag`] (or [`Sense::click_and_drag`]) for widgets that can be pinched or rotated.
+    ///
+    /// For the gesture anywhere on the screen, use [`crate::InputState::multi_touch`].
+    pub fn multi_touch(&self) -> Option<MultiTouchInfo> {
+        if !self.enabled {
+            return None;
+        }
+        let claimed = self.ctx.interaction_snapshot(|i| i.gesture) == Some(self.id);
+        if claimed {
+            self.ctx.input(|i| i.multi_touch())
+        } else {
+            None
+        }
+    }
+
+    /// How much the user pinch-zoomed this widget this frame.
+    ///
+    /// * `zoom = 1`: no change
+    /// * `zoom < 1`: pinch together
+    /// * `zoom > 1`: pinch spread
+    ///
+    /// See [`Self::multi_touch`] for which widget gets the gesture.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// # let mut scale = 1.0;
+    /// let response = ui.allocate_response(egui::vec2(200.0, 200.0), egui::Sense::drag());
+    /// scale *= response.pinch_zoom_delta();
+    /// # });
+    /// ```
+    pub fn pinch_zoom_delta(&self) -> f32 {
+        self.multi_touch().map_or(1.0, |info| info.zoom_delta)
+    }
+
+    /// How much (in radians) the user rotated this widget with two or more fingers this frame.
+    ///
+    /// See [`Self::multi_touch`] for which widget gets the gesture.
+    /// A trackpad rotation ([`crate::Event::Rotate`]) goes to the hovered widget.
+    pub fn rotation_delta(&self) -> f32 {
+        if let Some(info) = self.multi_touch() {
+            info.rotation_delta
+        } else if self.hovered() {
+            self.ctx.input(|i| {
+                if i.multi_touch().is_none() {
+                    i.rotation_delta()
+                } else {
+                    0.0
+                }
+            })
+        } else {
+            0.0
+        }
+    }
+
     /// If dragged, how far did the mouse move?
     /// This will use raw mouse movement if provided by the integration, otherwise will fall back to [`Response::drag_delta`]
     /// Raw mouse movement is unaccelerated and unclamped by screen boundaries, and does not relate to any position on the screen.
@@ -390,9 +630,7 @@ impl Response {
     /// and the user is drag-dropping something of this type.
     #[doc(alias = "drag and drop")]
     pub fn dnd_hover_payload<Payload: Any + Send + Sync>(&self) -> Option<Arc<Payload>> {
-        // NOTE: we use `response.contains_pointer` here instead of `hovered`, because
-        // `hovered` is always false when another widget is being dragged.
-        if self.contains_pointer() {
+        if self.dnd_contains_pointer() {
             crate::DragAndDrop::payload::<Payload>(&self.ctx)
         } else {
             None
@@ -406,15 +644,32 @@ impl Response {
     /// and they released it this frame
     #[doc(alias = "drag and drop")]
     pub fn dnd_release_payload<Payload: Any + Send + Sync>(&self) -> Option<Arc<Payload>> {
-        // NOTE: we use `response.contains_pointer` here instead of `hovered`, because
-        // `hovered` is always false when another widget is being dragged.
-        if self.contains_pointer() && self.ctx.input(|i| i.pointer.any_released()) {
-            crate::DragAndDrop::take_payload::<Payload>(&self.ctx)
+        let released = self.ctx.input(|i| i.pointer.any_released())
+            || crate::DragAndDrop::released_from_other_viewport(&self.ctx);
+        if self.dnd_contains_pointer() && released {
+            let payload = crate::DragAndDrop::take_payload::<Payload>(&self.ctx);
+            if payload.is_some() {
+                self.ctx.emit_haptic(crate::output::HapticKind::Drop);
+            }
+            payload
         } else {
             None
         }
     }
 
+    /// Is the drag-and-drop pointer over this widget?
+    ///
+    /// This is [`Self::contains_pointer`], but also true if something
+    /// dragged from another viewport is being held over this widget.
+    pub(crate) fn dnd_contains_pointer(&self) -> bool {
+        // NOTE: we use `response.contains_pointer` here instead of `hovered`, because
+        // `hovered` is always false when another widget is being dragged.
+        self.contains_pointer()
+            || crate::DragAndDrop::foreign_pointer_pos(&self.ctx).map_or(false, |pos| {
+                self.interact_rect.contains(pos) && self.ctx.layer_id_at(pos) == Some(self.layer_id)
+            })
+    }
+
     /// Where the pointer (mouse/touch) were when when this widget was clicked or dragged.
     ///
     /// `None` if the widget is not being interacted with.
@@ -423,6 +678,26 @@ impl Response {
         self.interact_pointer_pos
     }
 
+    /// How hard the pointer interacting with this widget is pressed, from `0.0` to `1.0`.
+    ///
+    /// Use this for brush dynamics in drawing apps: a pen reports its pressure,
+    /// and pointers without pressure (like a mouse) are `1.0` while pressed.
+    /// See also [`Self::interact_pen`] and [`crate::PointerState::pressure`].
+    ///
+    /// `None` if the widget is not being interacted with.
+    pub fn interact_pointer_pressure(&self) -> Option<f32> {
+        self.interact_pointer_pos?;
+        Some(self.ctx.input(|i| i.pointer.pressure()))
+    }
+
+    /// The pressure, tilt and eraser state of the pen interacting with this widget.
+    ///
+    /// `None` if the widget is not being interacted with, or the pointer is not a pen.
+    pub fn interact_pen(&self) -> Option<PenState> {
+        self.interact_pointer_pos?;
+        self.ctx.input(|i| i.pointer.pen())
+    }
+
     /// If it is a good idea to show a tooltip, where is pointer?
     ///
     /// None if the pointer is outside the response area.
@@ -542,23 +817,28 @@ impl Response {
             return false;
         }
 
-        if self.ctx.style().interaction.show_tooltips_only_when_still {
-            // We only show the tooltip when the mouse pointer is still,
-            // but once shown we keep showing it until the mouse leaves the parent.
+        let interaction = self.ctx.style().interaction.clone();
+        let time = self.ctx.input(|i| i.time);
+        let hover_duration = self.ctx.hover_duration(self.id);
+        let in_grace_period = self
+            .ctx
+            .hover_timer_mut(|timer| timer.in_grace_period(time, interaction.tooltip_grace_period));
 
-            if !self.ctx.input(|i| i.pointer.is_still()) && !self.is_tooltip_open() {
+        // Once shown, we keep showing the tooltip until the pointer leaves the widget,
+        // and right after a tooltip was shown, we show the next one right away:
+        if !self.is_tooltip_open() && !in_grace_period {
+            if interaction.show_tooltips_only_when_still
+                && !self.ctx.input(|i| i.pointer.is_still())
+            {
                 // wait for mouse to stop
                 self.ctx.request_repaint();
                 return false;
             }
-        }
 
-        if !self.is_tooltip_open() {
-            let time_til_tooltip = self.ctx.style().interaction.tooltip_delay
-                - self.ctx.input(|i| i.pointer.time_since_last_movement());
+            let time_til_tooltip = interaction.tooltip_delay - hover_duration;
 
             if 0.0 < time_til_tooltip {
-                // Wait until the mouse has been still for a while
+                // Wait until the widget has been hovered for a while
                 if let Ok(duration) = std::time::Duration::try_from_secs_f32(time_til_tooltip) {
                     self.ctx.request_repaint_after(duration);
                 }
@@ -575,6 +855,7 @@ impl Response {
             return false;
         }
 
+        self.ctx.hover_timer_mut(|timer| timer.tooltip_shown(time));
         true
     }
 
@@ -599,6 +880,50 @@ impl Response {
         })
     }
 
+    /// Make this widget behave like a [`crate::Hyperlink`] to `url`.
+    ///
+    /// Clicking it opens or copies the URL, and hovering it shows the URL or a preview,
+    /// as configured in [`crate::style::Interaction::hyperlinks`].
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// ui.button("Documentation").hyperlink("https://docs.rs/egui");
+    /// # });
+    /// ```
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn hyperlink(self, url: impl ToString) -> Self {
+        self.handle_hyperlink(&url.to_string(), false)
+    }
+
+    /// Open, copy or preview the URL, as configured in [`crate::style::Interaction::hyperlinks`].
+    pub(crate) fn handle_hyperlink(self, url: &str, new_tab: bool) -> Self {
+        use crate::style::HyperlinkAction;
+
+        let behavior = self.ctx.style().interaction.hyperlinks.clone();
+        let action = if self.clicked() {
+            let modifiers = self.ctx.input(|i| i.modifiers);
+            behavior.click_action(modifiers, new_tab)
+        } else if self.middle_clicked() {
+            behavior.middle_click
+        } else {
+            HyperlinkAction::Nothing
+        };
+        match action {
+            HyperlinkAction::OpenInSameTab => self.ctx.open_url(crate::OpenUrl::same_tab(url)),
+            HyperlinkAction::OpenInNewTab => self.ctx.open_url(crate::OpenUrl::new_tab(url)),
+            HyperlinkAction::CopyUrl => self.ctx.copy_text(url.to_owned()),
+            HyperlinkAction::Nothing => {}
+        }
+
+        if let Some(preview) = behavior.preview {
+            self.on_hover_ui(|ui| (preview.0)(ui, url))
+        } else if behavior.show_url_on_hover {
+            self.on_hover_text(url)
+        } else {
+            self
+        }
+    }
+
     /// Highlight this widget, to make it look like it is hovered, even if it isn't.
     ///
     /// The highlight takes one frame to take effect if you call this after the widget has been fully rendered.
@@ -636,6 +961,81 @@ impl Response {
         self
     }
 
+    /// Lock the mouse pointer to the window and hide it for as long as this is called each frame,
+    /// e.g. while dragging to look around in a 3D viewport.
+    ///
+    /// While locked the pointer position stays put, so this widget stays hovered and dragged.
+    /// Read the raw mouse motion with [`crate::PointerState::locked_delta`].
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// # let mut yaw = 0.0;
+    /// let response = ui.allocate_response(ui.available_size(), egui::Sense::drag());
+    /// if response.dragged() {
+    ///     response.request_pointer_lock();
+    ///     yaw += 0.01 * ui.input(|i| i.pointer.locked_delta().x);
+    /// }
+    /// # });
+    /// ```
+    ///
+    /// See also [`crate::PlatformOutput::pointer_lock`].
+    pub fn request_pointer_lock(&self) {
+        self.ctx.output_mut(|o| o.pointer_lock = true);
+    }
+
+    /// Call `prefetch` once the widget has been hovered for `delay` seconds.
+    ///
+    /// This is called once each time the widget is hovered for long enough,
+    /// and is a good place to start loading what the user is likely to look at next,
+    /// e.g. the images of a detail view with [`Context::try_load_image`],
+    /// without eagerly loading everything up front.
+    ///
+    /// Use a `delay` a bit below [`crate::style::Interaction::tooltip_delay`]
+    /// to have the data ready when a tooltip shows it.
+    ///
+    /// Only use this once per widget.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// let uri = "https://picsum.photos/seed/1.759706314/1024";
+    /// ui.button("Show details").on_hover_prefetch(0.15, |ctx| {
+    ///     ctx.try_load_image(uri, Default::default()).ok();
+    /// });
+    /// # });
+    /// ```
+    pub fn on_hover_prefetch(self, delay: f32, prefetch: impl FnOnce(&Context)) -> Self {
+        let id = self.id.with("hover_prefetch");
+        if !self.hovered() {
+            self.ctx.data_mut(|d| d.remove::<bool>(id));
+            return self;
+        }
+
+        let fired = self.ctx.data(|d| d.get_temp::<bool>(id).unwrap_or(false));
+        if fired {
+            return self;
+        }
+
+        let remaining = delay - self.ctx.hover_duration(self.id);
+        if remaining <= 0.0 {
+            prefetch(&self.ctx);
+            self.ctx.data_mut(|d| d.insert_temp(id, true));
+        } else {
+            // An infinite (or NaN) delay means never:
+            let delay = std::time::Duration::try_from_secs_f32(remaining)
+                .unwrap_or(std::time::Duration::MAX);
+            self.ctx.request_repaint_after(delay);
+        }
+        self
+    }
+
+    /// Request a repaint, recording this widget as the cause.
+    ///
+    /// See [`Context::request_repaint_from`].
+    #[track_caller]
+    pub fn request_repaint(&self) {
+        self.ctx.request_repaint_from(self.id);
+    }
+
     /// Sense more interactions (e.g. sense clicks on a [`Response`] returned from a label).
     ///
     /// The interaction will occur on the same plane as the original widget,
@@ -661,6 +1061,7 @@ impl Response {
     /// # });
     /// ```
     #[must_use]
+    #[track_caller]
     pub fn interact(&self, sense: Sense) -> Self {
         if (self.sense | sense) == self.sense {
             // Early-out: we already sense everything we need to sense.
@@ -702,11 +1103,39 @@ impl Response {
         });
     }
 
+    /// Like [`Self::scroll_to_me`], but smoothly scrolls over `duration` seconds,
+    /// and also scrolls any enclosing [`crate::ScrollArea`]s so that this UI becomes visible,
+    /// even when it is inside a scroll area that is itself scrolled out of view.
+    ///
+    /// Scrolling by hand stops the animation.
+    ///
+    /// See also: [`Ui::scroll_to_id`].
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// egui::ScrollArea::vertical().show(ui, |ui| {
+    ///     let jump = ui.button("Jump to the last item").clicked();
+    ///     for i in 0..1000 {
+    ///         let response = ui.label(format!("Item {i}"));
+    ///         if jump && i == 999 {
+    ///             response.scroll_to_me_animated(egui::Align::Center, 0.5);
+    ///         }
+    ///     }
+    /// });
+    /// # });
+    /// ```
+    pub fn scroll_to_me_animated(&self, align: Align, duration: f32) {
+        self.ctx.frame_state_mut(|state| {
+            state.animated_scroll_target = Some((self.rect, Some(align), duration));
+        });
+    }
+
     /// For accessibility.
     ///
     /// Call after interacting and potential calls to [`Self::mark_changed`].
     pub fn widget_info(&self, make_info: impl Fn() -> crate::WidgetInfo) {
         use crate::output::OutputEvent;
+        self.ctx.record_widget_info(self.id, &make_info);
         let event = if self.clicked() {
             Some(OutputEvent::Clicked(make_info()))
         } else if self.double_clicked() {
@@ -728,9 +1157,51 @@ impl Response {
                 self.fill_accesskit_node_from_widget_info(builder, make_info());
             });
         }
+
+        if self.ctx.style().interaction.emit_sounds {
+            self.emit_sounds(&make_info);
+        }
+    }
+
+    /// Emit the [`crate::output::UiSoundEvent`]s of this frame's interaction.
+    fn emit_sounds(&self, make_info: &impl Fn() -> crate::WidgetInfo) {
+        use crate::output::UiSoundEvent;
+
+        // Only when the hover starts, i.e. it wasn't hovered last frame:
+        let hover_started = self.hovered() && self.sense.interactive() && {
+            let id = self.id.with("__hover_sound");
+            let frame_nr = self.ctx.frame_nr();
+            self.ctx.data_mut(|d| {
+                let last_hovered_frame = d.get_temp::<u64>(id);
+                d.insert_temp(id, frame_nr);
+                last_hovered_frame.map_or(true, |last| last + 1 < frame_nr)
+            })
+        };
+
+        let sound = if self.changed || self.clicked() {
+            let info = make_info();
+            match info.selected {
+                Some(on) if self.changed => Some(UiSoundEvent::Toggle {
+                    widget_type: info.typ,
+                    on,
+                }),
+                _ if self.clicked() => Some(UiSoundEvent::Click(info.typ)),
+                _ => None,
+            }
+        } else if hover_started {
+            Some(UiSoundEvent::Hover(make_info().typ))
+        } else {
+            None
+        };
+
+        if let Some(sound) = sound {
+            self.ctx.emit_sound(sound);
+        }
     }
 
     pub fn output_event(&self, event: crate::output::OutputEvent) {
+        self.ctx
+            .record_widget_info(self.id, || event.widget_info().clone());
         #[cfg(feature = "accesskit")]
         self.ctx.accesskit_node_builder(self.id, |builder| {
             self.fill_accesskit_node_from_widget_info(builder, event.widget_info().clone());
@@ -827,8 +1298,38 @@ impl Response {
         self
     }
 
+    /// Make screen readers announce changes to this widget, even when it doesn't have focus.
+    ///
+    /// Use this for status messages, like "Saved" or "3 results found".
+    /// The announcements are polite, i.e. they wait until the screen reader is done talking.
+    ///
+    /// Remember to give the widget the same [`Id`] each frame,
+    /// e.g. by always showing the label at the same place in the ui.
+    ///
+    /// Only has an effect with the `accesskit` feature.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// # let num_results = 3;
+    /// ui.label(format!("{num_results} results found"))
+    ///     .accesskit_live_region();
+    /// # });
+    /// ```
+    pub fn accesskit_live_region(self) -> Self {
+        #[cfg(feature = "accesskit")]
+        self.ctx.accesskit_node_builder(self.id, |builder| {
+            builder.set_live(accesskit::Live::Polite);
+            builder.set_live_atomic();
+        });
+
+        self
+    }
+
     /// Response to secondary clicks (right-clicks) by showing the given menu.
     ///
+    /// On touch screen operating systems (see [`Context::os`]) a [long-press](Self::long_pressed)
+    /// opens the menu too.
+    ///
     /// Make sure the widget senses clicks (e.g. [`crate::Button`] does, [`crate::Label`] does not).
     ///
     /// ```
@@ -843,7 +1344,8 @@ impl Response {
     /// # });
     /// ```
     ///
-    /// See also: [`Ui::menu_button`] and [`Ui::close_menu`].
+    /// See also: [`Ui::menu_button`] and [`Ui::close_menu`],
+    /// and [`crate::menu::ContextMenu`] for a menu built from a list of items.
     pub fn context_menu(&self, add_contents: impl FnOnce(&mut Ui)) -> Option<InnerResponse<()>> {
         menu::context_menu(self, add_contents)
     }
@@ -1006,3 +1508,532 @@ impl<R> InnerResponse<R> {
         Self { inner, response }
     }
 }
+
+#[test]
+fn test_multi_touch_capture() {
+    use crate::{test_harness::Harness, vec2, Event, TouchDeviceId, TouchId, TouchPhase};
+    use std::cell::Cell;
+
+    let zoom = [Cell::new(1.0), Cell::new(1.0)];
+    let rotation = [Cell::new(0.0), Cell::new(0.0)];
+    let rects = [Cell::new(Rect::NOTHING), Cell::new(Rect::NOTHING)];
+
+    let mut harness = Harness::new_ui(|ui| {
+        ui.horizontal(|ui| {
+            for ((zoom, rotation), rect) in zoom.iter().zip(&rotation).zip(&rects) {
+                let response = ui.allocate_response(vec2(200.0, 200.0), Sense::drag());
+                zoom.set(zoom.get() * response.pinch_zoom_delta());
+                rotation.set(rotation.get() + response.rotation_delta());
+                rect.set(response.rect);
+            }
+        });
+    });
+
+    let touch = |id: u64, phase: TouchPhase, pos: Pos2| Event::Touch {
+        device_id: TouchDeviceId(0),
+        id: TouchId(id),
+        phase,
+        pos,
+        force: None,
+    };
+
+    // Spread two fingers on the first widget, until they are over the second one:
+    let center = rects[0].get().center();
+    let mut offset = vec2(20.0, 0.0);
+    harness.hover(center - offset);
+    harness.push_event(touch(0, TouchPhase::Start, center - offset));
+    harness.push_event(touch(1, TouchPhase::Start, center + offset));
+    harness.step();
+    for _ in 0..10 {
+        offset *= 1.2;
+        harness.push_event(touch(0, TouchPhase::Move, center - offset));
+        harness.push_event(touch(1, TouchPhase::Move, center + offset));
+        harness.step();
+    }
+    assert!(rects[1].get().contains(center + offset));
+    assert!((zoom[0].get() - 1.2_f32.powi(10)).abs() < 0.01);
+    assert_eq!(zoom[1].get(), 1.0);
+
+    // Rotate the fingers by a quarter turn:
+    for _ in 0..10 {
+        offset = Vec2::angled(offset.angle() + 0.05 * std::f32::consts::PI) * offset.length();
+        harness.push_event(touch(0, TouchPhase::Move, center - offset));
+        harness.push_event(touch(1, TouchPhase::Move, center + offset));
+        harness.step();
+    }
+    assert!((rotation[0].get() - 0.5 * std::f32::consts::PI).abs() < 0.01);
+    assert_eq!(rotation[1].get(), 0.0);
+
+    harness.push_event(touch(0, TouchPhase::End, center - offset));
+    harness.push_event(touch(1, TouchPhase::End, center + offset));
+    harness.step();
+
+    // A new gesture on the second widget goes to it:
+    let center = rects[1].get().center();
+    let offset = vec2(0.0, 20.0);
+    harness.hover(center - offset);
+    harness.push_event(touch(2, TouchPhase::Start, center - offset));
+    harness.push_event(touch(3, TouchPhase::Start, center + offset));
+    harness.step();
+    harness.push_event(touch(2, TouchPhase::Move, center - 2.0 * offset));
+    harness.push_event(touch(3, TouchPhase::Move, center + 2.0 * offset));
+    harness.step();
+    assert!((zoom[1].get() - 2.0).abs() < 0.01);
+
+    harness.push_event(touch(2, TouchPhase::End, center - 2.0 * offset));
+    harness.push_event(touch(3, TouchPhase::End, center + 2.0 * offset));
+    harness.step();
+
+    // The gesture goes to the widget under the center of the fingers, not under the first finger:
+    let zoom_before = [zoom[0].get(), zoom[1].get()];
+    let first = rects[0].get().right_center() - vec2(10.0, 0.0);
+    let second = rects[1].get().left_center() + vec2(40.0, 0.0);
+    assert!(rects[1].get().contains(first.lerp(second, 0.5)));
+    harness.hover(first);
+    harness.push_event(touch(4, TouchPhase::Start, first));
+    harness.push_event(touch(5, TouchPhase::Start, second));
+    harness.step();
+    harness.push_event(touch(5, TouchPhase::Move, second + (second - first)));
+    harness.step();
+    assert_eq!(zoom[0].get(), zoom_before[0]);
+    assert!((zoom[1].get() / zoom_before[1] - 2.0).abs() < 0.01);
+}
+
+#[test]
+fn test_extra_pointer_interaction() {
+    use crate::{test_harness::Harness, vec2, Event, Modifiers};
+    use std::cell::Cell;
+
+    let extra = PointerId(1);
+    let rects = [Cell::new(Rect::NOTHING), Cell::new(Rect::NOTHING)];
+    let dragged = [Cell::new(false), Cell::new(false)];
+    let clicked_by_extra = [Cell::new(false), Cell::new(false)];
+    let extra_drag_delta = Cell::new(Vec2::ZERO);
+
+    let mut harness = Harness::new_ui(|ui| {
+        ui.horizontal(|ui| {
+            for (i, rect) in rects.iter().enumerate() {
+                let response = ui.allocate_response(vec2(100.0, 100.0), Sense::click_and_drag());
+                rect.set(response.rect);
+                dragged[i].set(response.dragged());
+                clicked_by_extra[i].set(response.clicked_by_pointer(extra));
+                if i == 0 {
+                    extra_drag_delta.set(response.pointer_drag_delta(extra));
+                }
+            }
+        });
+    });
+
+    let extra_event = |event: Event| Event::ExtraPointer {
+        id: extra,
+        event: Box::new(event),
+    };
+    let button = |pos: Pos2, pressed: bool| Event::PointerButton {
+        pos,
+        button: PointerButton::Primary,
+        pressed,
+        modifiers: Modifiers::NONE,
+    };
+
+    // The primary pointer drags the first widget…
+    let start = rects[0].get().center();
+    harness.hover(start);
+    harness.push_event(button(start, true));
+    harness.step();
+    harness.hover(start + vec2(0.0, 20.0));
+    harness.step();
+    assert!(dragged[0].get());
+
+    // …while the extra pointer clicks the second one:
+    let pos = rects[1].get().center();
+    harness.push_event(extra_event(Event::PointerMoved(pos)));
+    harness.step();
+    assert!(harness
+        .ctx()
+        .input(|i| i.pointer_ids().eq([PointerId::PRIMARY, extra])));
+    harness.push_event(extra_event(button(pos, true)));
+    harness.step();
+    harness.push_event(extra_event(button(pos, false)));
+    harness.step();
+    assert!(clicked_by_extra[1].get());
+    assert!(!clicked_by_extra[0].get());
+    assert!(dragged[0].get());
+    assert!(!dragged[1].get());
+
+    // Then drags the first widget along with the primary pointer:
+    let start = rects[0].get().center();
+    harness.push_event(extra_event(Event::PointerMoved(start)));
+    harness.push_event(extra_event(button(start, true)));
+    harness.step();
+    harness.push_event(extra_event(Event::PointerMoved(start + vec2(30.0, 0.0))));
+    harness.step();
+    assert_eq!(
+        harness.ctx().pointer_dragged_id(extra),
+        harness.ctx().dragged_id()
+    );
+    assert_eq!(extra_drag_delta.get(), vec2(30.0, 0.0));
+
+    // Once gone, the extra pointer is forgotten:
+    harness.push_event(extra_event(button(start, false)));
+    harness.push_event(extra_event(Event::PointerGone));
+    harness.step();
+    harness.step();
+    assert!(harness.ctx().input(|i| i.extra_pointers.is_empty()));
+    assert_eq!(harness.ctx().pointer_dragged_id(extra), None);
+}
+
+#[test]
+fn test_hover_prefetch() {
+    use crate::test_harness::Harness;
+    use std::cell::Cell;
+
+    let num_prefetches = Cell::new(0);
+    let mut harness = Harness::new_ui(|ui| {
+        ui.button("Details")
+            .on_hover_prefetch(0.2, |_ctx| num_prefetches.set(num_prefetches.get() + 1));
+        let _ = ui.button("Other");
+    });
+
+    // Briefly passing over the widget does nothing:
+    harness.get_by_label("Details").hover();
+    harness.step();
+    harness.get_by_label("Other").hover();
+    harness.run();
+    assert_eq!(num_prefetches.get(), 0);
+
+    // Staying on it prefetches once, after the delay, even if the pointer is still:
+    harness.get_by_label("Details").hover();
+    for _ in 0..6 {
+        harness.step();
+    }
+    assert_eq!(num_prefetches.get(), 0);
+    assert!(harness.ctx().has_requested_repaint());
+    for _ in 0..20 {
+        harness.step();
+    }
+    assert_eq!(num_prefetches.get(), 1);
+
+    // Hovering it again prefetches again:
+    harness.get_by_label("Other").hover();
+    harness.get_by_label("Details").hover();
+    for _ in 0..20 {
+        harness.step();
+    }
+    assert_eq!(num_prefetches.get(), 2);
+}
+
+#[test]
+fn test_sound_events() {
+    use crate::{output::UiSoundEvent, test_harness::Harness, Key, WidgetType};
+    use std::cell::RefCell;
+
+    let mut checked = false;
+    let mut value = 1.0;
+    let sounds = RefCell::new(vec![]);
+    let mut harness = Harness::new_ui(|ui| {
+        let _ = ui.button("Button");
+        ui.checkbox(&mut checked, "Checkbox");
+        ui.add(crate::DragValue::new(&mut value));
+        sounds
+            .borrow_mut()
+            .extend(ui.output(|o| o.sound_events.clone()));
+    });
+    let take_sounds = || std::mem::take(&mut *sounds.borrow_mut());
+
+    // Nothing unless enabled:
+    harness.get_by_label("Button").click();
+    assert!(take_sounds().is_empty());
+    harness
+        .ctx()
+        .style_mut(|style| style.interaction.emit_sounds = true);
+
+    harness.get_by_label("Checkbox").hover();
+    harness.run();
+    assert_eq!(
+        take_sounds(),
+        vec![UiSoundEvent::Hover(WidgetType::Checkbox)]
+    );
+
+    harness.get_by_label("Checkbox").click();
+    assert_eq!(
+        take_sounds(),
+        vec![UiSoundEvent::Toggle {
+            widget_type: WidgetType::Checkbox,
+            on: true
+        }]
+    );
+
+    harness.get_by_label("Button").click();
+    assert_eq!(
+        take_sounds(),
+        vec![
+            UiSoundEvent::Hover(WidgetType::Button),
+            UiSoundEvent::Click(WidgetType::Button)
+        ]
+    );
+
+    // Entering text that is not a number:
+    harness
+        .get_by(|info| info.typ == WidgetType::DragValue)
+        .click();
+    harness.type_text("x");
+    harness.press_key(Key::Enter);
+    let errors = take_sounds()
+        .into_iter()
+        .filter(|sound| *sound == UiSoundEvent::Error)
+        .count();
+    assert_eq!(errors, 1);
+}
+
+#[test]
+fn test_haptics() {
+    use crate::{
+        output::HapticKind, test_harness::Harness, Event, PointerButton, Slider, WidgetType,
+    };
+    use std::cell::RefCell;
+
+    let mut stepped = 0;
+    let mut quiet = 0;
+    let haptics = RefCell::new(vec![]);
+    let mut harness = Harness::new_ui(|ui| {
+        ui.add(Slider::new(&mut stepped, 0..=10).text("Stepped"));
+        ui.add(Slider::new(&mut quiet, 0..=10).text("Quiet").haptics(false));
+        ui.button("Menu").context_menu(|ui| {
+            ui.label("Item");
+        });
+        haptics
+            .borrow_mut()
+            .extend(ui.output(|o| o.haptics.clone()));
+    });
+    let take_haptics = || std::mem::take(&mut *haptics.borrow_mut());
+    harness.run();
+    let slider_rect = |harness: &mut Harness<'_>, label: &str| {
+        harness
+            .get_by(|info| info.typ == WidgetType::Slider && info.label.as_deref() == Some(label))
+            .interact_rect()
+    };
+
+    let rect = slider_rect(&mut harness, "Stepped");
+    harness.drag(rect.left_center(), rect.right_center());
+    assert!(take_haptics().is_empty(), "Nothing unless enabled");
+    harness
+        .ctx()
+        .style_mut(|style| style.interaction.emit_haptics = true);
+
+    harness.drag(rect.right_center(), rect.center());
+    let detents = take_haptics();
+    assert!(!detents.is_empty());
+    assert!(detents.iter().all(|haptic| *haptic == HapticKind::Detent));
+
+    let rect = slider_rect(&mut harness, "Quiet");
+    harness.drag(rect.left_center(), rect.right_center());
+    assert!(take_haptics().is_empty(), "The slider opted out");
+
+    let pos = harness.get_by_label("Menu").interact_rect().center();
+    harness.hover(pos);
+    for pressed in [true, false] {
+        harness.push_event(Event::PointerButton {
+            pos,
+            button: PointerButton::Secondary,
+            pressed,
+            modifiers: Default::default(),
+        });
+        harness.step();
+    }
+    harness.run();
+    assert_eq!(take_haptics(), vec![HapticKind::MenuOpened]);
+}
+
+#[test]
+fn test_pointer_lock() {
+    use crate::{pos2, test_harness::Harness, vec2, Event, PointerButton};
+    use std::cell::Cell;
+
+    let yaw = Cell::new(0.0);
+    let mut harness = Harness::new_ui(|ui| {
+        let response = ui.allocate_response(vec2(100.0, 100.0), Sense::drag());
+        if response.dragged() {
+            response.request_pointer_lock();
+            yaw.set(yaw.get() + ui.input(|i| i.pointer.locked_delta().x));
+        }
+    });
+    let pos = pos2(50.0, 50.0);
+    let button = |pressed| Event::PointerButton {
+        pos,
+        button: PointerButton::Primary,
+        pressed,
+        modifiers: Default::default(),
+    };
+
+    // No raw motion while unlocked:
+    harness.push_event(Event::MouseMoved(vec2(10.0, 0.0)));
+    harness.hover(pos);
+    assert!(!harness.output().platform_output.pointer_lock);
+
+    harness.push_event(button(true));
+    harness.step();
+    assert!(harness.output().platform_output.pointer_lock);
+    assert_eq!(yaw.get(), 0.0);
+
+    // While locked the pointer stays put, and we get the raw motion:
+    harness.push_event(Event::MouseMoved(vec2(30.0, 5.0)));
+    harness.push_event(Event::PointerMoved(pos2(300.0, 50.0)));
+    harness.step();
+    assert!(harness.ctx().input(|i| i.pointer.is_locked()));
+    assert_eq!(harness.ctx().input(|i| i.pointer.latest_pos()), Some(pos));
+    assert_eq!(yaw.get(), 30.0);
+
+    // Releasing the button releases the lock:
+    harness.push_event(button(false));
+    harness.step();
+    assert!(!harness.output().platform_output.pointer_lock);
+    harness.step();
+    assert!(!harness.ctx().input(|i| i.pointer.is_locked()));
+}
+
+#[test]
+fn test_interact_pointer_pressure() {
+    use crate::{pos2, test_harness::Harness, vec2, Event, PointerButton};
+    use std::cell::Cell;
+
+    let pressure = Cell::new(None);
+    let eraser = Cell::new(false);
+    let mut harness = Harness::new_ui(|ui| {
+        let response = ui.allocate_response(vec2(100.0, 100.0), Sense::drag());
+        pressure.set(response.interact_pointer_pressure());
+        eraser.set(response.interact_pen().is_some_and(|pen| pen.eraser));
+    });
+    let pos = pos2(50.0, 50.0);
+    let button = |pressed| Event::PointerButton {
+        pos,
+        button: PointerButton::Primary,
+        pressed,
+        modifiers: Default::default(),
+    };
+    let pen = |pressure, eraser| {
+        Event::Pen(Some(PenState {
+            pressure,
+            tilt: vec2(0.3, 0.0),
+            eraser,
+        }))
+    };
+
+    // No pressure while only hovering:
+    harness.hover(pos);
+    assert_eq!(pressure.get(), None);
+
+    // A mouse is pressed all the way:
+    harness.push_event(button(true));
+    harness.step();
+    assert_eq!(pressure.get(), Some(1.0));
+    harness.push_event(button(false));
+    harness.run();
+
+    // A pen reports its pressure and eraser:
+    harness.push_event(pen(0.25, false));
+    harness.push_event(button(true));
+    harness.step();
+    assert_eq!(pressure.get(), Some(0.25));
+    assert!(!eraser.get());
+    harness.push_event(pen(2.0, true));
+    harness.push_event(Event::PointerMoved(pos2(60.0, 50.0)));
+    harness.step();
+    assert_eq!(pressure.get(), Some(1.0), "clamped by the input sanitizer");
+    assert!(eraser.get());
+
+    // Switching back to the mouse:
+    harness.push_event(button(false));
+    harness.run();
+    harness.push_event(Event::Pen(None));
+    harness.push_event(button(true));
+    harness.step();
+    assert_eq!(pressure.get(), Some(1.0));
+    assert!(!eraser.get());
+}
+
+#[test]
+fn test_trackpad_rotate_and_smart_zoom() {
+    use crate::{pos2, test_harness::Harness, vec2, Event};
+    use std::cell::Cell;
+
+    let rotation = [Cell::new(0.0), Cell::new(0.0)];
+    let smart_zooms = Cell::new(0);
+    let mut harness = Harness::new_ui(|ui| {
+        for rotation in &rotation {
+            let response = ui.allocate_response(vec2(100.0, 100.0), Sense::drag());
+            rotation.set(rotation.get() + response.rotation_delta());
+        }
+        if ui.input(|i| i.smart_zoom()) {
+            smart_zooms.set(smart_zooms.get() + 1);
+        }
+    });
+    harness.hover(pos2(50.0, 50.0));
+
+    // The rotation goes to the hovered widget:
+    harness.push_event(Event::Rotate(0.5));
+    harness.push_event(Event::Rotate(0.25));
+    harness.step();
+    assert_eq!(rotation[0].get(), 0.75);
+    assert_eq!(rotation[1].get(), 0.0);
+    harness.step();
+    assert_eq!(rotation[0].get(), 0.75, "only for one frame");
+
+    harness.push_event(Event::SmartZoom);
+    harness.step();
+    harness.step();
+    assert_eq!(smart_zooms.get(), 1);
+}
+
+#[test]
+fn test_extra_pointer_in_widgets() {
+    use crate::{test_harness::Harness, vec2, Event, Modifiers};
+    use std::cell::Cell;
+
+    let extra = PointerId(1);
+    let clicked = Cell::new(false);
+    let value = Cell::new(0.0);
+    let slider_rect = Cell::new(Rect::NOTHING);
+    let mut harness = Harness::new_ui(|ui| {
+        clicked.set(ui.button("Click me").clicked());
+        let mut v = value.get();
+        let slider = crate::Slider::new(&mut v, 0.0..=100.0).show_value(false);
+        slider_rect.set(ui.add(slider).rect);
+        value.set(v);
+    });
+
+    let extra_event = |event: Event| Event::ExtraPointer {
+        id: extra,
+        event: Box::new(event),
+    };
+    let button = |pos: Pos2, pressed: bool| {
+        extra_event(Event::PointerButton {
+            pos,
+            button: PointerButton::Primary,
+            pressed,
+            modifiers: Modifiers::NONE,
+        })
+    };
+
+    // The extra pointer clicks the button:
+    let pos = harness.get_by_label("Click me").interact_rect().center();
+    harness.push_event(extra_event(Event::PointerMoved(pos)));
+    harness.push_event(button(pos, true));
+    harness.step();
+    harness.push_event(button(pos, false));
+    harness.step();
+    assert!(clicked.get());
+
+    // …and drags the slider to its right end:
+    let slider_rect = slider_rect.get();
+    let start = slider_rect.left_center() + vec2(10.0, 0.0);
+    harness.push_event(extra_event(Event::PointerMoved(start)));
+    harness.push_event(button(start, true));
+    harness.step();
+    let end = slider_rect.right_center() + vec2(100.0, 0.0);
+    harness.push_event(extra_event(Event::PointerMoved(end)));
+    harness.step();
+    harness.push_event(button(end, false));
+    harness.step();
+    assert_eq!(value.get(), 100.0);
+    assert!(harness.ctx().input(|i| !i.pointer.any_down()));
+}
@@ -0,0 +1,676 @@
+//! Render egui frames to images, so that the look of widgets can be regression-tested.
+//!
+//! This uses a simple software rasterizer, so it works in CI without a GPU.
+//! The result is close to, but not exactly the same as, what the real integrations produce.
+//!
+//! ```no_run
+//! let image = egui::snapshot::render_ui(egui::vec2(200.0, 100.0), |ui| {
+//!     ui.button("Click me");
+//! });
+//! egui::assert_snapshot!("button", image);
+//! ```
+//!
+//! [`crate::assert_snapshot`] compares the image with `tests/snapshots/<name>.png` in the crate that calls it.
+//! Run your tests with `UPDATE_SNAPSHOTS=1` to create or update the snapshots.
+//!
+//! Requires the `snapshot` feature.
+
+use std::path::{Path, PathBuf};
+
+use epaint::{
+    textures::{TextureFilter, TextureWrapMode},
+    ClippedPrimitive, ImageData, Mesh, Primitive,
+};
+
+use crate::*;
+
+/// Renders [`ClippedPrimitive`]s into a [`ColorImage`] on the CPU.
+///
+/// Keeps track of the textures egui has uploaded,
+/// so use the same renderer for all frames of the same [`Context`].
+///
+/// Blending is done on premultiplied colors in gamma space,
+/// just like the `egui_glow` painter does on the web.
+#[derive(Default)]
+pub struct SoftwareRenderer {
+    textures: ahash::HashMap<TextureId, (ColorImage, TextureOptions)>,
+}
+
+impl SoftwareRenderer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Run one frame and render it.
+    ///
+    /// The size of the image is that of [`Context::screen_rect`], in physical pixels.
+    pub fn run(
+        &mut self,
+        ctx: &Context,
+        raw_input: RawInput,
+        run_ui: impl FnOnce(&Context),
+    ) -> ColorImage {
+        let full_output = ctx.run(raw_input, run_ui);
+        self.render_output(ctx, full_output)
+    }
+
+    /// Tessellate and render the output of [`Context::run`].
+    pub fn render_output(&mut self, ctx: &Context, full_output: FullOutput) -> ColorImage {
+        let FullOutput {
+            textures_delta,
+            shapes,
+            pixels_per_point,
+            ..
+        } = full_output;
+
+        let size = ctx.screen_rect().size() * pixels_per_point;
+        let size = [size.x.round() as usize, size.y.round() as usize];
+
+        self.set_textures(&textures_delta);
+        let primitives = ctx.tessellate(shapes, pixels_per_point);
+        let image = self.render(&primitives, size, pixels_per_point);
+        ctx.recycle_primitives(primitives);
+        self.free_textures(&textures_delta);
+
+        image
+    }
+
+    /// Apply [`TexturesDelta::set`]. Call before [`Self::render`].
+    pub fn set_textures(&mut self, textures_delta: &TexturesDelta) {
+        for (id, delta) in &textures_delta.set {
+            let image = match &delta.image {
+                ImageData::Color(image) => (**image).clone(),
+                ImageData::Font(image) => ColorImage {
+                    size: image.size,
+                    pixels: image.srgba_pixels(None).collect(),
+                },
+            };
+
+            if let Some([x, y]) = delta.pos {
+                let Some((texture, options)) = self.textures.get_mut(id) else {
+                    log_or_panic(&format!("Partial update of unknown texture {id:?}"));
+                    continue;
+                };
+                for row in 0..image.height() {
+                    for column in 0..image.width() {
+                        texture[(x + column, y + row)] = image[(column, row)];
+                    }
+                }
+                *options = delta.options;
+            } else {
+                self.textures.insert(*id, (image, delta.options));
+            }
+        }
+    }
+
+    /// Apply [`TexturesDelta::free`]. Call after [`Self::render`].
+    pub fn free_textures(&mut self, textures_delta: &TexturesDelta) {
+        for id in &textures_delta.free {
+            self.textures.remove(id);
+        }
+    }
+
+    /// Rasterize the primitives into a new image of the given size (in physical pixels).
+    ///
+    /// The background is transparent.
+    /// Paint callbacks are ignored.
+    pub fn render(
+        &self,
+        primitives: &[ClippedPrimitive],
+        size_in_pixels: [usize; 2],
+        pixels_per_point: f32,
+    ) -> ColorImage {
+        crate::profile_function!();
+
+        let mut target = ColorImage::new(size_in_pixels, Color32::TRANSPARENT);
+        let [width, height] = size_in_pixels;
+
+        for ClippedPrimitive {
+            clip_rect,
+            primitive,
+        } in primitives
+        {
+            let Primitive::Mesh(mesh) = primitive else {
+                continue;
+            };
+
+            // Same rounding as the real painters:
+            let clip_min_x = (pixels_per_point * clip_rect.min.x)
+                .round()
+                .clamp(0.0, width as f32);
+            let clip_min_y = (pixels_per_point * clip_rect.min.y)
+                .round()
+                .clamp(0.0, height as f32);
+            let clip_max_x = (pixels_per_point * clip_rect.max.x)
+                .round()
+                .clamp(clip_min_x, width as f32);
+            let clip_max_y = (pixels_per_point * clip_rect.max.y)
+                .round()
+                .clamp(clip_min_y, height as f32);
+            let clip =
+                Rect::from_min_max(pos2(clip_min_x, clip_min_y), pos2(clip_max_x, clip_max_y));
+
+            self.paint_mesh(&mut target, mesh, clip, pixels_per_point);
+        }
+
+        target
+    }
+
+    fn paint_mesh(&self, target: &mut ColorImage, mesh: &Mesh, clip: Rect, pixels_per_point: f32) {
+        let texture = self.textures.get(&mesh.texture_id);
+        if texture.is_none() {
+            log_or_panic(&format!("Missing texture {:?}", mesh.texture_id));
+        }
+
+        for indices in mesh.indices.chunks_exact(3) {
+            let mut v = [
+                mesh.vertices[indices[0] as usize],
+                mesh.vertices[indices[1] as usize],
+                mesh.vertices[indices[2] as usize],
+            ];
+            for vertex in &mut v {
+                vertex.pos = (pixels_per_point * vertex.pos.to_vec2()).to_pos2();
+            }
+
+            let mut area = edge(v[0].pos, v[1].pos, v[2].pos);
+            if area == 0.0 || !area.is_finite() {
+                continue;
+            }
+            if area < 0.0 {
+                v.swap(1, 2);
+                area = -area;
+            }
+
+            let bounds = Rect::from_points(&[v[0].pos, v[1].pos, v[2].pos]).intersect(clip);
+            if !bounds.is_positive() {
+                continue;
+            }
+
+            // Pixels whose center is exactly on an edge shared by two triangles
+            // must only be painted once, so we use the "top-left" rule like GPUs do.
+            let edges = [
+                (v[1].pos, v[2].pos),
+                (v[2].pos, v[0].pos),
+                (v[0].pos, v[1].pos),
+            ];
+            let is_top_left = edges.map(|(a, b)| {
+                let d = b - a;
+                d.y < 0.0 || (d.y == 0.0 && d.x > 0.0)
+            });
+
+            for y in bounds.min.y.floor() as usize..bounds.max.y.ceil() as usize {
+                for x in bounds.min.x.floor() as usize..bounds.max.x.ceil() as usize {
+                    let center = pos2(x as f32 + 0.5, y as f32 + 0.5);
+                    if !clip.contains(center) {
+                        continue;
+                    }
+
+                    let weights = edges.map(|(a, b)| edge(a, b, center));
+                    let inside =
+                        (0..3).all(|i| 0.0 < weights[i] || (weights[i] == 0.0 && is_top_left[i]));
+                    if !inside {
+                        continue;
+                    }
+                    let [w0, w1, w2] = weights.map(|w| w / area);
+
+                    let color = to_rgba(v[0].color) * w0
+                        + to_rgba(v[1].color) * w1
+                        + to_rgba(v[2].color) * w2;
+                    let uv =
+                        (w0 * v[0].uv.to_vec2() + w1 * v[1].uv.to_vec2() + w2 * v[2].uv.to_vec2())
+                            .to_pos2();
+
+                    let color = match texture {
+                        Some((image, options)) => color * sample(image, *options, uv),
+                        None => color,
+                    };
+
+                    blend(&mut target[(x, y)], color);
+                }
+            }
+        }
+    }
+}
+
+/// Twice the signed area of the triangle `a, b, c`.
+fn edge(a: Pos2, b: Pos2, c: Pos2) -> f32 {
+    (b.x - a.x) * (c.y - a.y) - (b.y - a.y) * (c.x - a.x)
+}
+
+/// We do all our math in gamma space, but [`Rgba`] is linear,
+/// so we just reinterpret the premultiplied gamma bytes as [`Rgba`].
+fn to_rgba(color: Color32) -> Rgba {
+    let [r, g, b, a] = color.to_array().map(|c| c as f32 / 255.0);
+    Rgba::from_rgba_premultiplied(r, g, b, a)
+}
+
+fn to_color32(color: Rgba) -> Color32 {
+    let [r, g, b, a] = color
+        .to_array()
+        .map(|c| (c * 255.0).round().clamp(0.0, 255.0) as u8);
+    Color32::from_rgba_premultiplied(r, g, b, a)
+}
+
+/// Premultiplied "over" blending.
+fn blend(dst: &mut Color32, src: Rgba) {
+    let dst_rgba = to_rgba(*dst);
+    *dst = to_color32(src + dst_rgba * (1.0 - src.a()));
+}
+
+fn sample(image: &ColorImage, options: TextureOptions, uv: Pos2) -> Rgba {
+    let [width, height] = image.size;
+    if width == 0 || height == 0 {
+        return Rgba::TRANSPARENT;
+    }
+
+    let texel = |x: i64, y: i64| {
+        let x = wrap(x, width, options.wrap_mode);
+        let y = wrap(y, height, options.wrap_mode);
+        to_rgba(image[(x, y)])
+    };
+
+    let x = uv.x * width as f32;
+    let y = uv.y * height as f32;
+
+    match options.magnification {
+        TextureFilter::Nearest => texel(x.floor() as i64, y.floor() as i64),
+        TextureFilter::Linear => {
+            let x = x - 0.5;
+            let y = y - 0.5;
+            let (x0, y0) = (x.floor(), y.floor());
+            let (tx, ty) = (x - x0, y - y0);
+            let (x0, y0) = (x0 as i64, y0 as i64);
+            let top = texel(x0, y0) * (1.0 - tx) + texel(x0 + 1, y0) * tx;
+            let bottom = texel(x0, y0 + 1) * (1.0 - tx) + texel(x0 + 1, y0 + 1) * tx;
+            top * (1.0 - ty) + bottom * ty
+        }
+    }
+}
+
+fn wrap(i: i64, size: usize, wrap_mode: TextureWrapMode) -> usize {
+    let size = size as i64;
+    let i = match wrap_mode {
+        TextureWrapMode::ClampToEdge => i.clamp(0, size - 1),
+        TextureWrapMode::Repeat => i.rem_euclid(size),
+        TextureWrapMode::MirroredRepeat => {
+            let i = i.rem_euclid(2 * size);
+            if i < size {
+                i
+            } else {
+                2 * size - 1 - i
+            }
+        }
+    };
+    i as usize
+}
+
+fn log_or_panic(message: &str) {
+    if cfg!(debug_assertions) {
+        panic!("{message}");
+    } else {
+        #[cfg(feature = "log")]
+        log::warn!("{message}");
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// Render some ui in a [`CentralPanel`] of the given size (in points), at one pixel per point.
+///
+/// This uses a fresh [`Context`] with animations turned off,
+/// and runs a few frames so that everything has settled before the last one is rendered.
+pub fn render_ui(size: Vec2, mut add_contents: impl FnMut(&mut Ui)) -> ColorImage {
+    let ctx = Context::default();
+    ctx.options_mut(|options| options.animations_enabled = false);
+
+    let mut renderer = SoftwareRenderer::new();
+    let mut image = ColorImage::default();
+    for _ in 0..3 {
+        let raw_input = RawInput {
+            screen_rect: Some(Rect::from_min_size(Pos2::ZERO, size)),
+            ..Default::default()
+        };
+        image = renderer.run(&ctx, raw_input, |ctx| {
+            CentralPanel::default().show(ctx, |ui| add_contents(ui));
+        });
+    }
+    image
+}
+
+// ----------------------------------------------------------------------------
+
+/// How to compare an image with a stored snapshot.
+#[derive(Clone, Debug)]
+pub struct SnapshotOptions {
+    /// Where the snapshots are stored.
+    pub output_dir: PathBuf,
+
+    /// A pixel is considered different if any of its channels differ by more than this.
+    ///
+    /// Default: 1 (to allow for rounding differences).
+    pub threshold: u8,
+
+    /// How many pixels may differ before the snapshot is considered failed.
+    ///
+    /// Default: 0.
+    pub failed_pixel_count_threshold: usize,
+}
+
+impl SnapshotOptions {
+    /// Store the snapshots in the given directory.
+    pub fn new(output_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            output_dir: output_dir.into(),
+            threshold: 1,
+            failed_pixel_count_threshold: 0,
+        }
+    }
+
+    #[inline]
+    pub fn threshold(mut self, threshold: u8) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    #[inline]
+    pub fn failed_pixel_count_threshold(mut self, failed_pixel_count_threshold: usize) -> Self {
+        self.failed_pixel_count_threshold = failed_pixel_count_threshold;
+        self
+    }
+}
+
+/// Why [`check_snapshot`] failed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SnapshotError {
+    /// There is no stored snapshot yet.
+    ///
+    /// The new image was written to `new_path`.
+    Missing { path: PathBuf, new_path: PathBuf },
+
+    /// The stored snapshot has a different size.
+    SizeMismatch {
+        path: PathBuf,
+        expected: [usize; 2],
+        actual: [usize; 2],
+    },
+
+    /// Too many pixels differ from the stored snapshot.
+    ///
+    /// The new image was written to `new_path`, and an image highlighting the differences to `diff_path`.
+    Mismatch {
+        path: PathBuf,
+        new_path: PathBuf,
+        diff_path: PathBuf,
+        num_differing_pixels: usize,
+    },
+
+    /// Failed to read or write a snapshot.
+    Io { path: PathBuf, error: String },
+}
+
+impl std::fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Missing { path, new_path } => write!(
+                f,
+                "Missing snapshot {}. The new image was written to {}. \
+                Run with UPDATE_SNAPSHOTS=1 to accept it.",
+                path.display(),
+                new_path.display()
+            ),
+            Self::SizeMismatch {
+                path,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Snapshot {} has size {expected:?}, but the new image has size {actual:?}. \
+                Run with UPDATE_SNAPSHOTS=1 to accept the new image.",
+                path.display()
+            ),
+            Self::Mismatch {
+                path,
+                new_path,
+                diff_path,
+                num_differing_pixels,
+            } => write!(
+                f,
+                "{num_differing_pixels} pixels differ from snapshot {}. \
+                See {} and {}. \
+                Run with UPDATE_SNAPSHOTS=1 to accept the new image.",
+                path.display(),
+                new_path.display(),
+                diff_path.display()
+            ),
+            Self::Io { path, error } => write!(f, "{}: {error}", path.display()),
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
+/// The result of [`diff_images`].
+#[derive(Clone, Debug)]
+pub struct ImageDiff {
+    /// Number of pixels that differ by more than the threshold.
+    pub num_differing_pixels: usize,
+
+    /// The expected image, dimmed, with the differing pixels in red.
+    pub image: ColorImage,
+}
+
+/// Compare two images of the same size.
+///
+/// A pixel differs if any of its channels differ by more than `threshold`.
+/// The comparison is done on the unmultiplied colors, i.e. what is stored in a png.
+pub fn diff_images(expected: &ColorImage, actual: &ColorImage, threshold: u8) -> ImageDiff {
+    assert_eq!(expected.size, actual.size, "Images must have the same size");
+
+    let mut num_differing_pixels = 0;
+    let pixels = expected
+        .pixels
+        .iter()
+        .zip(&actual.pixels)
+        .map(|(expected, actual)| {
+            let a = expected.to_srgba_unmultiplied();
+            let b = actual.to_srgba_unmultiplied();
+            let differs = a.iter().zip(&b).any(|(a, b)| a.abs_diff(*b) > threshold);
+            if differs {
+                num_differing_pixels += 1;
+                Color32::RED
+            } else {
+                let gray = (a[0] as u16 + a[1] as u16 + a[2] as u16) / 3;
+                let gray = (gray / 3) as u8;
+                Color32::from_gray(gray)
+            }
+        })
+        .collect();
+
+    ImageDiff {
+        num_differing_pixels,
+        image: ColorImage {
+            size: expected.size,
+            pixels,
+        },
+    }
+}
+
+/// Compare the image with the snapshot `<name>.png` in [`SnapshotOptions::output_dir`].
+///
+/// If the environment variable `UPDATE_SNAPSHOTS` is set, the snapshot is overwritten instead.
+///
+/// On failure, the new image is written to `<name>.new.png`, and the differences to `<name>.diff.png`.
+/// These are removed again once the snapshot matches.
+///
+/// You usually want to use [`crate::assert_snapshot`] instead.
+pub fn check_snapshot(
+    name: &str,
+    image: &ColorImage,
+    options: &SnapshotOptions,
+) -> Result<(), SnapshotError> {
+    let dir = &options.output_dir;
+    let path = dir.join(format!("{name}.png"));
+    let new_path = dir.join(format!("{name}.new.png"));
+    let diff_path = dir.join(format!("{name}.diff.png"));
+
+    // Clean up after earlier failures:
+    std::fs::remove_file(&new_path).ok();
+    std::fs::remove_file(&diff_path).ok();
+
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        return save_png(&path, image);
+    }
+
+    if !path.exists() {
+        save_png(&new_path, image)?;
+        return Err(SnapshotError::Missing { path, new_path });
+    }
+
+    let expected = load_png(&path)?;
+    if expected.size != image.size {
+        save_png(&new_path, image)?;
+        return Err(SnapshotError::SizeMismatch {
+            path,
+            expected: expected.size,
+            actual: image.size,
+        });
+    }
+
+    let diff = diff_images(&expected, image, options.threshold);
+    if options.failed_pixel_count_threshold < diff.num_differing_pixels {
+        save_png(&new_path, image)?;
+        save_png(&diff_path, &diff.image)?;
+        return Err(SnapshotError::Mismatch {
+            path,
+            new_path,
+            diff_path,
+            num_differing_pixels: diff.num_differing_pixels,
+        });
+    }
+
+    Ok(())
+}
+
+fn save_png(path: &Path, image: &ColorImage) -> Result<(), SnapshotError> {
+    let io_error = |error: String| SnapshotError::Io {
+        path: path.to_owned(),
+        error,
+    };
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|err| io_error(err.to_string()))?;
+    }
+
+    let rgba: Vec<u8> = image
+        .pixels
+        .iter()
+        .flat_map(|color| color.to_srgba_unmultiplied())
+        .collect();
+    image::save_buffer_with_format(
+        path,
+        &rgba,
+        image.width() as u32,
+        image.height() as u32,
+        image::ColorType::Rgba8,
+        image::ImageFormat::Png,
+    )
+    .map_err(|err| io_error(err.to_string()))
+}
+
+fn load_png(path: &Path) -> Result<ColorImage, SnapshotError> {
+    let image = image::open(path).map_err(|err| SnapshotError::Io {
+        path: path.to_owned(),
+        error: err.to_string(),
+    })?;
+    let image = image.to_rgba8();
+    let size = [image.width() as usize, image.height() as usize];
+    Ok(ColorImage::from_rgba_unmultiplied(size, image.as_raw()))
+}
+
+/// Compare an image with a stored snapshot, and panic if they differ.
+///
+/// The snapshots are stored in `tests/snapshots/` of the crate calling the macro.
+/// Run with `UPDATE_SNAPSHOTS=1` to create or update them.
+///
+/// You can pass [`snapshot::SnapshotOptions`] as an optional third argument.
+///
+/// See [`snapshot`] and [`snapshot::check_snapshot`].
+///
+/// Requires the `snapshot` feature.
+#[macro_export]
+macro_rules! assert_snapshot {
+    ($name:expr, $image:expr) => {
+        $crate::assert_snapshot!(
+            $name,
+            $image,
+            $crate::snapshot::SnapshotOptions::new(
+                ::std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/snapshots")
+            )
+        )
+    };
+    ($name:expr, $image:expr, $options:expr) => {
+        if let Err(err) = $crate::snapshot::check_snapshot($name, &$image, &$options) {
+            panic!("{err}");
+        }
+    };
+}
+
+#[test]
+fn test_rasterize_rect() {
+    let mut renderer = SoftwareRenderer::new();
+    let ctx = Context::default();
+    let raw_input = RawInput {
+        screen_rect: Some(Rect::from_min_size(Pos2::ZERO, vec2(20.0, 20.0))),
+        ..Default::default()
+    };
+    let color = Color32::from_rgba_unmultiplied(255, 0, 0, 128);
+    let image = renderer.run(&ctx, raw_input, |ctx| {
+        ctx.layer_painter(LayerId::background()).rect_filled(
+            Rect::from_min_max(pos2(5.0, 5.0), pos2(15.0, 15.0)),
+            0.0,
+            color,
+        );
+    });
+
+    assert_eq!(image.size, [20, 20]);
+    assert_eq!(image[(2, 2)], Color32::TRANSPARENT);
+    assert_eq!(image[(17, 10)], Color32::TRANSPARENT);
+
+    // Every pixel is painted exactly once, even on the diagonal where the two triangles of the rect meet:
+    for y in 6..14 {
+        for x in 6..14 {
+            assert_eq!(image[(x, y)], color, "pixel {x} {y}");
+        }
+    }
+}
+
+#[test]
+fn test_check_snapshot() {
+    let dir = tempfile::tempdir().unwrap();
+    let options = SnapshotOptions::new(dir.path());
+
+    let image = render_ui(vec2(120.0, 40.0), |ui| {
+        ui.label("Hello snapshot");
+    });
+    assert!(
+        image.pixels.iter().any(|c| *c != image.pixels[0]),
+        "Text should be visible"
+    );
+
+    let err = check_snapshot("label", &image, &options).unwrap_err();
+    assert!(matches!(err, SnapshotError::Missing { .. }));
+    std::fs::rename(
+        dir.path().join("label.new.png"),
+        dir.path().join("label.png"),
+    )
+    .unwrap();
+
+    check_snapshot("label", &image, &options).unwrap();
+
+    let other = render_ui(vec2(120.0, 40.0), |ui| {
+        ui.label("Goodbye snapshot");
+    });
+    let err = check_snapshot("label", &other, &options).unwrap_err();
+    assert!(matches!(err, SnapshotError::Mismatch { .. }));
+    assert!(dir.path().join("label.diff.png").exists());
+}
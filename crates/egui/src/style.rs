@@ -7,7 +7,8 @@ use std::collections::BTreeMap;
 use epaint::{Rounding, Shadow, Stroke};
 
 use crate::{
-    ecolor::*, emath::*, ComboBox, CursorIcon, FontFamily, FontId, Response, RichText, WidgetText,
+    ecolor::*, emath::*, ComboBox, CursorIcon, FontFamily, FontId, Modifiers, Response, RichText,
+    WidgetText,
 };
 
 // ----------------------------------------------------------------------------
@@ -199,6 +200,9 @@ pub struct Style {
     /// Colors etc.
     pub visuals: Visuals,
 
+    /// The widths at which [`crate::Ui::breakpoint`] changes.
+    pub breakpoints: Breakpoints,
+
     /// How many seconds a typical animation should last.
     pub animation_time: f32,
 
@@ -215,6 +219,9 @@ pub struct Style {
 
     /// If true and scrolling is enabled for only one direction, allow horizontal scrolling without pressing shift
     pub always_scroll_the_only_direction: bool,
+
+    /// Which corner (or edge) of the viewport [`crate::Context::toast`]s are shown in.
+    pub toast_anchor: Align2,
 }
 
 impl Style {
@@ -226,15 +233,16 @@ impl Style {
         self.visuals.widgets.style(response)
     }
 
+    /// Use this style for interactive things that can be selected, e.g. a [`crate::SelectableLabel`].
+    ///
+    /// Same as [`Self::interact_with_state`] with [`WidgetState::checked`].
     pub fn interact_selectable(&self, response: &Response, selected: bool) -> WidgetVisuals {
-        let mut visuals = *self.visuals.widgets.style(response);
-        if selected {
-            visuals.weak_bg_fill = self.visuals.selection.bg_fill;
-            visuals.bg_fill = self.visuals.selection.bg_fill;
-            // visuals.bg_stroke = self.visuals.selection.stroke;
-            visuals.fg_stroke = self.visuals.selection.stroke;
-        }
-        visuals
+        self.interact_with_state(WidgetState::from_response(response).checked(selected))
+    }
+
+    /// Use this style for a widget in the given [`WidgetState`].
+    pub fn interact_with_state(&self, state: WidgetState) -> WidgetVisuals {
+        self.visuals.widget_visuals(state)
     }
 
     /// Style to use for non-interactive widgets.
@@ -287,6 +295,9 @@ pub struct Spacing {
     /// Default width of a [`TextEdit`].
     pub text_edit_width: f32,
 
+    /// Size of the switch of a [`ToggleSwitch`].
+    pub toggle_switch_size: Vec2,
+
     /// Checkboxes, radio button and collapsing headers have an icon at the start.
     /// This is the width/height of the outer part of this icon (e.g. the BOX of the checkbox).
     pub icon_width: f32,
@@ -422,6 +433,34 @@ pub struct ScrollStyle {
     /// This is only for floating scroll bars.
     /// Solid scroll bars are always opaque.
     pub interact_handle_opacity: f32,
+
+    /// When to keep scrolling after the contents are dragged and let go,
+    /// slowing down gradually.
+    pub kinetic_scrolling: KineticScrolling,
+
+    /// How quickly kinetic scrolling slows down, in points per second squared.
+    pub kinetic_friction: f32,
+
+    /// Let the contents be dragged past their ends, with increasing resistance,
+    /// and bounce back when let go.
+    ///
+    /// Kinetic scrolling into an end also bounces.
+    pub overscroll: bool,
+}
+
+/// When to use kinetic scrolling, see [`ScrollStyle::kinetic_scrolling`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum KineticScrolling {
+    /// Stop scrolling as soon as the contents are let go.
+    Never,
+
+    /// Only when dragging with a touch screen, or on a mobile operating system.
+    Touch,
+
+    /// Also when dragging with a mouse.
+    #[default]
+    Always,
 }
 
 impl Default for ScrollStyle {
@@ -451,6 +490,10 @@ impl ScrollStyle {
             dormant_handle_opacity: 0.0,
             active_handle_opacity: 0.6,
             interact_handle_opacity: 1.0,
+
+            kinetic_scrolling: KineticScrolling::Always,
+            kinetic_friction: 1000.0,
+            overscroll: false,
         }
     }
 
@@ -531,6 +574,10 @@ impl ScrollStyle {
             dormant_handle_opacity,
             active_handle_opacity,
             interact_handle_opacity,
+
+            kinetic_scrolling,
+            kinetic_friction,
+            overscroll,
         } = self;
 
         ui.horizontal(|ui| {
@@ -599,6 +646,20 @@ impl ScrollStyle {
                 ui.label("Inner margin");
             });
         }
+
+        ui.horizontal(|ui| {
+            ui.label("Kinetic scrolling:");
+            ui.selectable_value(kinetic_scrolling, KineticScrolling::Never, "Never");
+            ui.selectable_value(kinetic_scrolling, KineticScrolling::Touch, "Touch");
+            ui.selectable_value(kinetic_scrolling, KineticScrolling::Always, "Always");
+        });
+        if *kinetic_scrolling != KineticScrolling::Never {
+            ui.horizontal(|ui| {
+                ui.add(DragValue::new(kinetic_friction).clamp_range(0.0..=10_000.0));
+                ui.label("Friction");
+            });
+        }
+        ui.checkbox(overscroll, "Overscroll");
     }
 }
 
@@ -704,6 +765,73 @@ impl std::ops::Add for Margin {
 
 // ----------------------------------------------------------------------------
 
+/// A coarse size class of the available width, see [`crate::Ui::breakpoint`].
+///
+/// Use this to adapt a layout between e.g. a phone-sized web canvas and a desktop window.
+/// The breakpoints are ordered, so you can compare them:
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// if ui.breakpoint() >= egui::Breakpoint::Medium {
+///     ui.columns(2, |columns| {
+///         columns[0].label("Side by side");
+///         columns[1].label("on larger screens");
+///     });
+/// } else {
+///     ui.label("Stacked");
+///     ui.label("on small screens");
+/// }
+/// # });
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum Breakpoint {
+    /// Narrower than [`Breakpoints::medium`], e.g. a phone.
+    Small,
+
+    /// At least [`Breakpoints::medium`] wide, e.g. a tablet.
+    Medium,
+
+    /// At least [`Breakpoints::large`] wide, e.g. a desktop window.
+    Large,
+}
+
+/// The widths (in points) at which the [`Breakpoint`] changes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Breakpoints {
+    /// From this width and up it is [`Breakpoint::Medium`].
+    pub medium: f32,
+
+    /// From this width and up it is [`Breakpoint::Large`].
+    pub large: f32,
+}
+
+impl Default for Breakpoints {
+    fn default() -> Self {
+        Self {
+            medium: 600.0,
+            large: 1024.0,
+        }
+    }
+}
+
+impl Breakpoints {
+    /// The breakpoint of the given width.
+    pub fn breakpoint(&self, width: f32) -> Breakpoint {
+        if width >= self.large {
+            Breakpoint::Large
+        } else if width >= self.medium {
+            Breakpoint::Medium
+        } else {
+            Breakpoint::Small
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
 /// How and when interaction happens.
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
@@ -724,9 +852,15 @@ pub struct Interaction {
     /// If `false`, tooltips will show up anytime you hover anything, even is mouse is still moving
     pub show_tooltips_only_when_still: bool,
 
-    /// Delay in seconds before showing tooltips after the mouse stops moving
+    /// How long (in seconds) the pointer must be over a widget before its tooltip is shown.
     pub tooltip_delay: f32,
 
+    /// If a tooltip was visible less than this many seconds ago,
+    /// the tooltip of the next hovered widget is shown right away, skipping [`Self::tooltip_delay`].
+    ///
+    /// This makes it easy to move between adjacent widgets and read all their tooltips.
+    pub tooltip_grace_period: f32,
+
     /// Can you select the text on a [`crate::Label`] by default?
     pub selectable_labels: bool,
 
@@ -735,6 +869,145 @@ pub struct Interaction {
     /// The default is `true`, but text seelction can be slightly glitchy,
     /// so you may want to disable it.
     pub multi_widget_text_select: bool,
+
+    /// What clicking a [`crate::Hyperlink`] does, and what is shown when hovering it.
+    pub hyperlinks: HyperlinkBehavior,
+
+    /// How long (in seconds) the pointer must be over the button of a sub-menu before it opens.
+    ///
+    /// Clicking the button opens it right away.
+    pub submenu_open_delay: f32,
+
+    /// Keep the open sub-menu open while the pointer moves towards it,
+    /// even if it passes over the buttons of other sub-menus on the way.
+    pub submenu_hover_intent: bool,
+
+    /// Let widgets emit [`crate::output::UiSoundEvent`]s into [`crate::PlatformOutput::sound_events`],
+    /// so that the integration can play sounds for them.
+    pub emit_sounds: bool,
+
+    /// Let widgets emit [`crate::output::HapticKind`]s into [`crate::PlatformOutput::haptics`],
+    /// so that the integration can vibrate on touch devices.
+    ///
+    /// A single [`crate::Slider`] can opt out with [`crate::Slider::haptics`].
+    pub emit_haptics: bool,
+
+    /// Show the full text of a widget in a tooltip when its text doesn't fit,
+    /// i.e. when it is elided or cut off by the clip rect.
+    ///
+    /// Saves you from adding `.on_hover_text` to every cell of a table or node of a tree.
+    /// See [`crate::Ui::on_hover_text_if_clipped`].
+    pub tooltip_for_clipped_text: bool,
+}
+
+/// What a click on a [`crate::Hyperlink`] does.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum HyperlinkAction {
+    /// Open the URL, replacing the current page on web.
+    OpenInSameTab,
+
+    /// Open the URL in a new browser tab.
+    OpenInNewTab,
+
+    /// Copy the URL to the clipboard.
+    CopyUrl,
+
+    /// Do nothing.
+    Nothing,
+}
+
+/// Shows a card with a preview of a URL, e.g. the title of the page, when hovering a [`crate::Hyperlink`].
+///
+/// The callback gets the [`crate::Ui`] of the tooltip and the URL.
+#[derive(Clone)]
+pub struct HyperlinkPreview(pub std::sync::Arc<dyn Fn(&mut crate::Ui, &str) + Send + Sync>);
+
+impl HyperlinkPreview {
+    pub fn new(preview: impl Fn(&mut crate::Ui, &str) + Send + Sync + 'static) -> Self {
+        Self(std::sync::Arc::new(preview))
+    }
+}
+
+impl std::fmt::Debug for HyperlinkPreview {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("HyperlinkPreview")
+    }
+}
+
+impl PartialEq for HyperlinkPreview {
+    fn eq(&self, other: &Self) -> bool {
+        std::sync::Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// How hyperlinks behave, see [`Interaction::hyperlinks`].
+///
+/// ```
+/// # egui::__run_test_ctx(|ctx| {
+/// use egui::style::{HyperlinkAction, HyperlinkPreview};
+///
+/// ctx.style_mut(|style| {
+///     let hyperlinks = &mut style.interaction.hyperlinks;
+///     hyperlinks.middle_click = HyperlinkAction::CopyUrl;
+///     hyperlinks.preview = Some(HyperlinkPreview::new(|ui, url| {
+///         ui.strong("External link");
+///         ui.weak(url);
+///     }));
+/// });
+/// # });
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct HyperlinkBehavior {
+    /// Open clicked links in a new browser tab, unless [`crate::Hyperlink::open_in_new_tab`] says otherwise.
+    ///
+    /// Clicking with a modifier key held down always opens a new tab.
+    pub open_in_new_tab: bool,
+
+    /// What a middle click does.
+    pub middle_click: HyperlinkAction,
+
+    /// Clicking with these modifiers held down copies the URL instead of opening it.
+    ///
+    /// See [`Modifiers::matches_logically`].
+    pub copy_modifiers: Option<Modifiers>,
+
+    /// Show the URL in a tooltip when hovering, if there is no [`Self::preview`].
+    pub show_url_on_hover: bool,
+
+    /// Show this card instead of the URL when hovering.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub preview: Option<HyperlinkPreview>,
+}
+
+impl Default for HyperlinkBehavior {
+    fn default() -> Self {
+        Self {
+            open_in_new_tab: false,
+            middle_click: HyperlinkAction::OpenInNewTab,
+            copy_modifiers: None,
+            show_url_on_hover: true,
+            preview: None,
+        }
+    }
+}
+
+impl HyperlinkBehavior {
+    /// What a primary click with these modifiers does.
+    pub fn click_action(&self, modifiers: Modifiers, new_tab: bool) -> HyperlinkAction {
+        if self
+            .copy_modifiers
+            .is_some_and(|copy_modifiers| modifiers.matches_logically(copy_modifiers))
+        {
+            HyperlinkAction::CopyUrl
+        } else if new_tab || self.open_in_new_tab || modifiers.any() {
+            HyperlinkAction::OpenInNewTab
+        } else {
+            HyperlinkAction::OpenInSameTab
+        }
+    }
 }
 
 /// Controls the visual style (colors etc) of egui.
@@ -795,6 +1068,9 @@ pub struct Visuals {
     /// A good color for error text (e.g. red).
     pub error_fg_color: Color32,
 
+    /// A good color for text about something that went well (e.g. green).
+    pub success_fg_color: Color32,
+
     pub window_rounding: Rounding,
     pub window_shadow: Shadow,
     pub window_fill: Color32,
@@ -812,10 +1088,17 @@ pub struct Visuals {
 
     pub resize_corner_size: f32,
 
-    /// The color and width of the text cursor
-    pub text_cursor: Stroke,
-
-    /// show where the text cursor would be if you clicked
+    /// The look and blinking of the text cursor.
+    ///
+    /// This used to be a [`Stroke`], which you can still assign with `stroke.into()`.
+    #[cfg_attr(
+        feature = "serde",
+        serde(deserialize_with = "deserialize_text_cursor_style")
+    )]
+    pub text_cursor: TextCursorStyle,
+
+    /// Show where the text cursor would be if you clicked.
+    #[deprecated = "Use `text_cursor.preview` instead"]
     pub text_cursor_preview: bool,
 
     /// Allow child widgets to be just on the border and still have a stroke with some thickness
@@ -844,6 +1127,12 @@ pub struct Visuals {
     /// Changing this will affect ALL sliders, and can be enabled/disabled per slider with [`Slider::handle_shape`].
     pub handle_shape: HandleShape,
 
+    /// Rounding of the outer corners of a [`SegmentedControl`].
+    pub segmented_control_rounding: Rounding,
+
+    /// Rounding of a [`Chip`].
+    pub chip_rounding: Rounding,
+
     /// Should the cursor change when the user hovers over an interactive/clickable item?
     ///
     /// This is consistent with a lot of browser-based applications (vscode, github
@@ -874,6 +1163,14 @@ impl Visuals {
         self.gray_out(self.text_color())
     }
 
+    /// Show where the text cursor would be if you clicked?
+    ///
+    /// Also respects the deprecated [`Self::text_cursor_preview`].
+    #[allow(deprecated)]
+    pub(crate) fn text_cursor_preview(&self) -> bool {
+        self.text_cursor.preview || self.text_cursor_preview
+    }
+
     #[inline(always)]
     pub fn strong_text_color(&self) -> Color32 {
         self.widgets.active.text_color()
@@ -902,6 +1199,30 @@ impl Visuals {
     pub fn gray_out(&self, color: Color32) -> Color32 {
         crate::ecolor::tint_color_towards(color, self.fade_out_to_color())
     }
+
+    /// The visuals of a widget in the given state.
+    ///
+    /// The interaction states pick one of [`Self::widgets`],
+    /// which is then tinted with [`Self::selection`] if checked (half as much if indeterminate),
+    /// and outlined with [`Self::error_fg_color`] if invalid.
+    pub fn widget_visuals(&self, state: WidgetState) -> WidgetVisuals {
+        let mut visuals = *self.widgets.for_state(&state);
+        if state.indeterminate {
+            let bg_fill = self.selection.bg_fill.gamma_multiply(0.5);
+            visuals.weak_bg_fill = bg_fill;
+            visuals.bg_fill = bg_fill;
+        } else if state.checked {
+            visuals.weak_bg_fill = self.selection.bg_fill;
+            visuals.bg_fill = self.selection.bg_fill;
+            // visuals.bg_stroke = self.selection.stroke;
+            visuals.fg_stroke = self.selection.stroke;
+        }
+        if state.invalid {
+            visuals.bg_stroke =
+                Stroke::new(visuals.bg_stroke.width.at_least(1.0), self.error_fg_color);
+        }
+        visuals
+    }
 }
 
 /// Selected text, selected elements etc
@@ -913,6 +1234,114 @@ pub struct Selection {
     pub stroke: Stroke,
 }
 
+/// What the text cursor looks like, see [`TextCursorStyle`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum TextCursorShape {
+    /// A vertical line before the next character.
+    #[default]
+    Line,
+
+    /// A box covering the next character.
+    Block,
+
+    /// A line under the next character.
+    Underline,
+}
+
+/// The look and blinking of the text cursor in e.g. [`crate::TextEdit`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct TextCursorStyle {
+    /// The color and width of the text cursor.
+    pub stroke: Stroke,
+
+    pub shape: TextCursorShape,
+
+    /// Show where the text cursor would be if you clicked.
+    pub preview: bool,
+
+    /// Should the text cursor blink?
+    ///
+    /// Turn this off for reduced motion.
+    pub blink: bool,
+
+    /// When blinking, how long the cursor is visible, in seconds.
+    pub on_duration: f32,
+
+    /// When blinking, how long the cursor is hidden, in seconds.
+    pub off_duration: f32,
+
+    /// Restart the blinking (with the cursor visible) whenever the text is edited or the cursor moves,
+    /// so that the cursor doesn't blink while typing.
+    pub pause_while_typing: bool,
+}
+
+impl Default for TextCursorStyle {
+    fn default() -> Self {
+        Self {
+            stroke: Stroke::new(2.0, Color32::from_rgb(192, 222, 255)), // Dark mode
+            shape: TextCursorShape::Line,
+            preview: false,
+            blink: true,
+            on_duration: 0.5,
+            off_duration: 0.5,
+            pause_while_typing: true,
+        }
+    }
+}
+
+impl From<Stroke> for TextCursorStyle {
+    /// The default text cursor, with the given color and width.
+    fn from(stroke: Stroke) -> Self {
+        Self {
+            stroke,
+            ..Default::default()
+        }
+    }
+}
+
+/// Also accepts the [`Stroke`] that [`Visuals::text_cursor`] used to be, so old styles still load.
+#[cfg(feature = "serde")]
+fn deserialize_text_cursor_style<'de, D>(deserializer: D) -> Result<TextCursorStyle, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::Deserialize as _;
+
+    #[derive(serde::Deserialize)]
+    #[serde(untagged)]
+    enum StrokeOrStyle {
+        Stroke(Stroke),
+        Style(TextCursorStyle),
+    }
+
+    Ok(match StrokeOrStyle::deserialize(deserializer)? {
+        StrokeOrStyle::Stroke(stroke) => stroke.into(),
+        StrokeOrStyle::Style(style) => style,
+    })
+}
+
+impl TextCursorStyle {
+    /// Is the cursor visible `time_since_reset` seconds after it was shown or last moved?
+    ///
+    /// Also returns how long until that changes, if it ever does.
+    pub fn is_visible(&self, time_since_reset: f64) -> (bool, Option<f32>) {
+        let period = (self.on_duration + self.off_duration) as f64;
+        if !self.blink || period <= 0.0 || self.off_duration <= 0.0 {
+            return (true, None);
+        }
+        let phase = time_since_reset.max(0.0) % period;
+        let on_duration = self.on_duration as f64;
+        if phase < on_duration {
+            (true, Some((on_duration - phase) as f32))
+        } else {
+            (false, Some((period - phase) as f32))
+        }
+    }
+}
+
 /// Shape of the handle for sliders and similar widgets.
 #[derive(Clone, Copy, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
@@ -955,12 +1384,21 @@ pub struct Widgets {
 
 impl Widgets {
     pub fn style(&self, response: &Response) -> &WidgetVisuals {
-        if !response.sense.interactive() {
+        self.for_state(&WidgetState::from_response(response))
+    }
+
+    /// The visuals for the interaction states of a widget,
+    /// ignoring [`WidgetState::checked`], [`WidgetState::indeterminate`] and [`WidgetState::invalid`].
+    ///
+    /// Use [`Visuals::widget_visuals`] to also take those into account.
+    pub fn for_state(&self, state: &WidgetState) -> &WidgetVisuals {
+        if !state.interactive {
             &self.noninteractive
-        } else if response.is_pointer_button_down_on() || response.has_focus() || response.clicked()
-        {
+        } else if state.open {
+            &self.open
+        } else if state.active || state.focused {
             &self.active
-        } else if response.hovered() || response.highlighted() {
+        } else if state.hovered {
             &self.hovered
         } else {
             &self.inactive
@@ -968,6 +1406,87 @@ impl Widgets {
     }
 }
 
+/// The pseudo-states of a widget, which decide what [`WidgetVisuals`] it is painted with.
+///
+/// Start with [`Self::from_response`], add the states only the widget itself knows about,
+/// and get the visuals with [`Style::interact_with_state`]:
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// # let value = "not a number";
+/// let response = ui.allocate_response(egui::vec2(60.0, 20.0), egui::Sense::click());
+/// let state = egui::style::WidgetState::from_response(&response).invalid(value.parse::<f32>().is_err());
+/// let visuals = ui.style().interact_with_state(state);
+/// ui.painter().rect(response.rect, visuals.rounding, visuals.bg_fill, visuals.bg_stroke);
+/// # });
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct WidgetState {
+    /// Does the widget sense clicks or drags? If not, the other interaction states are ignored.
+    pub interactive: bool,
+
+    /// The pointer is over the widget, or it is [highlighted](Response::highlighted).
+    pub hovered: bool,
+
+    /// The widget is being clicked or dragged.
+    pub active: bool,
+
+    /// The widget has keyboard focus. Looks like [`Self::active`].
+    pub focused: bool,
+
+    /// The widget has an open popup or menu, like an open [`ComboBox`].
+    pub open: bool,
+
+    /// The widget is checked or selected, e.g. a selected [`crate::SelectableLabel`].
+    pub checked: bool,
+
+    /// The widget is neither checked nor unchecked,
+    /// e.g. a "select all" [`crate::Checkbox`] when only some of the items are selected.
+    ///
+    /// Overrides [`Self::checked`].
+    pub indeterminate: bool,
+
+    /// The value of the widget is not valid, e.g. text that can't be parsed.
+    pub invalid: bool,
+}
+
+impl WidgetState {
+    /// The interaction states of the widget of this response.
+    pub fn from_response(response: &Response) -> Self {
+        Self {
+            interactive: response.sense.interactive(),
+            hovered: response.hovered() || response.highlighted(),
+            active: response.is_pointer_button_down_on() || response.clicked(),
+            focused: response.has_focus(),
+            ..Default::default()
+        }
+    }
+
+    #[inline]
+    pub fn open(mut self, open: bool) -> Self {
+        self.open = open;
+        self
+    }
+
+    #[inline]
+    pub fn checked(mut self, checked: bool) -> Self {
+        self.checked = checked;
+        self
+    }
+
+    #[inline]
+    pub fn indeterminate(mut self, indeterminate: bool) -> Self {
+        self.indeterminate = indeterminate;
+        self
+    }
+
+    #[inline]
+    pub fn invalid(mut self, invalid: bool) -> Self {
+        self.invalid = invalid;
+        self
+    }
+}
+
 /// bg = background, fg = foreground.
 #[derive(Clone, Copy, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
@@ -1049,6 +1568,14 @@ pub struct DebugOptions {
 
     /// Show interesting widgets under the mouse cursor.
     pub show_widget_hits: bool,
+
+    /// Measure the distance from where a drag starts to the mouse cursor, see [`crate::Painter::measurement`].
+    ///
+    /// The ends snap to the edges of nearby widgets.
+    pub measure: bool,
+
+    /// Highlight the characters that no font has a glyph for, see [`crate::Context::missing_glyphs`].
+    pub show_missing_glyphs: bool,
 }
 
 #[cfg(debug_assertions)]
@@ -1064,6 +1591,8 @@ impl Default for DebugOptions {
             show_resize: false,
             show_interactive_widgets: false,
             show_widget_hits: false,
+            measure: false,
+            show_missing_glyphs: false,
         }
     }
 }
@@ -1095,11 +1624,13 @@ impl Default for Style {
             spacing: Spacing::default(),
             interaction: Interaction::default(),
             visuals: Visuals::default(),
+            breakpoints: Breakpoints::default(),
             animation_time: 1.0 / 12.0,
             #[cfg(debug_assertions)]
             debug: Default::default(),
             explanation_tooltips: false,
             always_scroll_the_only_direction: false,
+            toast_anchor: Align2::RIGHT_BOTTOM,
         }
     }
 }
@@ -1116,6 +1647,7 @@ impl Default for Spacing {
             slider_width: 100.0,
             combo_width: 100.0,
             text_edit_width: 280.0,
+            toggle_switch_size: vec2(32.0, 18.0),
             icon_width: 14.0,
             icon_width_inner: 8.0,
             icon_spacing: 4.0,
@@ -1136,8 +1668,15 @@ impl Default for Interaction {
             interact_radius: 5.0,
             show_tooltips_only_when_still: true,
             tooltip_delay: 0.3,
+            tooltip_grace_period: 0.2,
             selectable_labels: true,
             multi_widget_text_select: true,
+            hyperlinks: HyperlinkBehavior::default(),
+            submenu_open_delay: 0.0,
+            submenu_hover_intent: true,
+            emit_sounds: false,
+            emit_haptics: false,
+            tooltip_for_clipped_text: false,
         }
     }
 }
@@ -1156,6 +1695,7 @@ impl Visuals {
             code_bg_color: Color32::from_gray(64),
             warn_fg_color: Color32::from_rgb(255, 143, 0), // orange
             error_fg_color: Color32::from_rgb(255, 0, 0),  // red
+            success_fg_color: Color32::from_rgb(0, 200, 0), // green
 
             window_rounding: Rounding::same(6.0),
             window_shadow: Shadow::big_dark(),
@@ -1169,7 +1709,8 @@ impl Visuals {
 
             popup_shadow: Shadow::small_dark(),
             resize_corner_size: 12.0,
-            text_cursor: Stroke::new(2.0, Color32::from_rgb(192, 222, 255)),
+            text_cursor: TextCursorStyle::default(),
+            #[allow(deprecated)]
             text_cursor_preview: false,
             clip_rect_margin: 3.0, // should be at least half the size of the widest frame stroke + max WidgetVisuals::expansion
             button_frame: true,
@@ -1181,6 +1722,9 @@ impl Visuals {
             slider_trailing_fill: false,
             handle_shape: HandleShape::Circle,
 
+            segmented_control_rounding: Rounding::same(4.0),
+            chip_rounding: Rounding::same(9.0),
+
             interact_cursor: None,
 
             image_loading_spinners: true,
@@ -1201,6 +1745,7 @@ impl Visuals {
             code_bg_color: Color32::from_gray(230),
             warn_fg_color: Color32::from_rgb(255, 100, 0), // slightly orange red. it's difficult to find a warning color that pops on bright background.
             error_fg_color: Color32::from_rgb(255, 0, 0),  // red
+            success_fg_color: Color32::from_rgb(0, 150, 0), // darker green, to be readable on bright background
 
             window_shadow: Shadow::big_light(),
             window_fill: Color32::from_gray(248),
@@ -1209,7 +1754,7 @@ impl Visuals {
             panel_fill: Color32::from_gray(248),
 
             popup_shadow: Shadow::small_light(),
-            text_cursor: Stroke::new(2.0, Color32::from_rgb(0, 83, 125)),
+            text_cursor: Stroke::new(2.0, Color32::from_rgb(0, 83, 125)).into(),
             ..Self::dark()
         }
     }
@@ -1356,11 +1901,13 @@ impl Style {
             spacing,
             interaction,
             visuals,
+            breakpoints,
             animation_time,
             #[cfg(debug_assertions)]
             debug,
             explanation_tooltips,
             always_scroll_the_only_direction,
+            toast_anchor,
         } = self;
 
         visuals.light_dark_radio_buttons(ui);
@@ -1415,12 +1962,36 @@ impl Style {
                     .suffix(" s"),
             );
             ui.end_row();
+
+            ui.label("Toast anchor:");
+            let anchors = [
+                (Align2::LEFT_TOP, "Top left"),
+                (Align2::CENTER_TOP, "Top"),
+                (Align2::RIGHT_TOP, "Top right"),
+                (Align2::LEFT_BOTTOM, "Bottom left"),
+                (Align2::CENTER_BOTTOM, "Bottom"),
+                (Align2::RIGHT_BOTTOM, "Bottom right"),
+            ];
+            crate::ComboBox::from_id_source("toast_anchor")
+                .selected_text(
+                    anchors
+                        .iter()
+                        .find(|(anchor, _)| anchor == toast_anchor)
+                        .map_or("Custom", |(_, name)| name),
+                )
+                .show_ui(ui, |ui| {
+                    for (anchor, name) in anchors {
+                        ui.selectable_value(toast_anchor, anchor, name);
+                    }
+                });
+            ui.end_row();
         });
 
         ui.collapsing("🔠 Text Styles", |ui| text_styles_ui(ui, text_styles));
         ui.collapsing("📏 Spacing", |ui| spacing.ui(ui));
         ui.collapsing("☝ Interaction", |ui| interaction.ui(ui));
         ui.collapsing("🎨 Visuals", |ui| visuals.ui(ui));
+        ui.collapsing("📱 Breakpoints", |ui| breakpoints.ui(ui));
 
         #[cfg(debug_assertions)]
         ui.collapsing("🐛 Debug", |ui| debug.ui(ui));
@@ -1465,6 +2036,7 @@ impl Spacing {
             slider_width,
             combo_width,
             text_edit_width,
+            toggle_switch_size,
             icon_width,
             icon_width_inner,
             icon_spacing,
@@ -1499,6 +2071,11 @@ impl Spacing {
             ui.add(DragValue::new(text_edit_width).clamp_range(0.0..=1000.0));
             ui.label("TextEdit width");
         });
+        ui.add(slider_vec2(
+            toggle_switch_size,
+            4.0..=60.0,
+            "Toggle switch size",
+        ));
 
         ui.collapsing("Scroll Area", |ui| {
             scroll.ui(ui);
@@ -1596,6 +2173,19 @@ fn margin_ui(ui: &mut Ui, text: &str, margin: &mut Margin) {
     });
 }
 
+impl Breakpoints {
+    pub fn ui(&mut self, ui: &mut crate::Ui) {
+        let Self { medium, large } = self;
+        ui.add(
+            Slider::new(medium, 0.0..=2000.0)
+                .suffix(" pt")
+                .text("medium"),
+        );
+        ui.add(Slider::new(large, 0.0..=2000.0).suffix(" pt").text("large"));
+        *large = large.at_least(*medium);
+    }
+}
+
 impl Interaction {
     pub fn ui(&mut self, ui: &mut crate::Ui) {
         let Self {
@@ -1604,8 +2194,15 @@ impl Interaction {
             resize_grab_radius_corner,
             show_tooltips_only_when_still,
             tooltip_delay,
+            tooltip_grace_period,
             selectable_labels,
             multi_widget_text_select,
+            hyperlinks,
+            submenu_open_delay,
+            submenu_hover_intent,
+            emit_sounds,
+            emit_haptics,
+            tooltip_for_clipped_text,
         } = self;
         ui.add(Slider::new(interact_radius, 0.0..=20.0).text("interact_radius"))
             .on_hover_text("Interact with the closest widget within this radius.");
@@ -1622,6 +2219,11 @@ impl Interaction {
                 .suffix(" s")
                 .text("tooltip_delay"),
         );
+        ui.add(
+            Slider::new(tooltip_grace_period, 0.0..=1.0)
+                .suffix(" s")
+                .text("tooltip_grace_period"),
+        );
 
         ui.horizontal(|ui| {
             ui.checkbox(selectable_labels, "Selectable text in labels");
@@ -1630,10 +2232,69 @@ impl Interaction {
             }
         });
 
+        ui.collapsing("Hyperlinks", |ui| hyperlinks.ui(ui));
+
+        ui.add(
+            Slider::new(submenu_open_delay, 0.0..=1.0)
+                .suffix(" s")
+                .text("submenu_open_delay"),
+        );
+        ui.checkbox(
+            submenu_hover_intent,
+            "Keep sub-menus open while moving towards them",
+        );
+        ui.checkbox(emit_sounds, "Emit sound events").on_hover_text(
+            "Let the integration play sounds for clicks, hovers, toggles and errors",
+        );
+        ui.checkbox(emit_haptics, "Emit haptics").on_hover_text(
+            "Let the integration vibrate on slider steps, drops and context menus on touch devices",
+        );
+        ui.checkbox(
+            tooltip_for_clipped_text,
+            "Show the full text of clipped widgets on hover",
+        );
+
         ui.vertical_centered(|ui| reset_button(ui, self));
     }
 }
 
+impl HyperlinkBehavior {
+    pub fn ui(&mut self, ui: &mut crate::Ui) {
+        let Self {
+            open_in_new_tab,
+            middle_click,
+            copy_modifiers,
+            show_url_on_hover,
+            preview: _,
+        } = self;
+
+        ui.checkbox(open_in_new_tab, "Open links in a new tab");
+        ui.horizontal(|ui| {
+            ui.label("Middle click");
+            for (action, text) in [
+                (HyperlinkAction::OpenInSameTab, "Open"),
+                (HyperlinkAction::OpenInNewTab, "Open in new tab"),
+                (HyperlinkAction::CopyUrl, "Copy URL"),
+                (HyperlinkAction::Nothing, "Nothing"),
+            ] {
+                ui.radio_value(middle_click, action, text);
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label("Copy URL with");
+            for (modifiers, text) in [
+                (None, "Nothing"),
+                (Some(Modifiers::ALT), "Alt-click"),
+                (Some(Modifiers::COMMAND), "Ctrl-click"),
+                (Some(Modifiers::SHIFT), "Shift-click"),
+            ] {
+                ui.radio_value(copy_modifiers, modifiers, text);
+            }
+        });
+        ui.checkbox(show_url_on_hover, "Show URL on hover");
+    }
+}
+
 impl Widgets {
     pub fn ui(&mut self, ui: &mut crate::Ui) {
         let Self {
@@ -1659,13 +2320,19 @@ impl Widgets {
             hovered.ui(ui);
         });
         ui.collapsing("Interactive and active", |ui| {
-            ui.label("The style of an interactive widget as you are clicking or dragging it.");
+            ui.label(
+                "The style of an interactive widget as you are clicking or dragging it, or when it has keyboard focus.",
+            );
             active.ui(ui);
         });
         ui.collapsing("Open menu", |ui| {
             ui.label("The style of an open combo-box or menu button");
             open.ui(ui);
         });
+        ui.label(
+            "Checked and indeterminate widgets are tinted with the selection color, \
+            and invalid ones are outlined with the error color.",
+        );
 
         // ui.vertical_centered(|ui| reset_button(ui, self));
     }
@@ -1680,6 +2347,43 @@ impl Selection {
     }
 }
 
+impl TextCursorStyle {
+    pub fn ui(&mut self, ui: &mut crate::Ui) {
+        let Self {
+            stroke,
+            shape,
+            preview,
+            blink,
+            on_duration,
+            off_duration,
+            pause_while_typing,
+        } = self;
+
+        stroke_ui(ui, stroke, "Stroke");
+        ui.horizontal(|ui| {
+            ui.label("Shape");
+            ui.selectable_value(shape, TextCursorShape::Line, "Line");
+            ui.selectable_value(shape, TextCursorShape::Block, "Block");
+            ui.selectable_value(shape, TextCursorShape::Underline, "Underline");
+        });
+        ui.checkbox(preview, "Preview text cursor on hover");
+        ui.checkbox(blink, "Blink");
+        ui.add_enabled_ui(*blink, |ui| {
+            ui.add(
+                Slider::new(on_duration, 0.1..=2.0)
+                    .suffix(" s")
+                    .text("On duration"),
+            );
+            ui.add(
+                Slider::new(off_duration, 0.1..=2.0)
+                    .suffix(" s")
+                    .text("Off duration"),
+            );
+            ui.checkbox(pause_while_typing, "Don't blink while typing");
+        });
+    }
+}
+
 impl WidgetVisuals {
     pub fn ui(&mut self, ui: &mut crate::Ui) {
         let Self {
@@ -1737,6 +2441,7 @@ impl Visuals {
         None
     }
 
+    #[allow(deprecated)] // text_cursor_preview
     pub fn ui(&mut self, ui: &mut crate::Ui) {
         let Self {
             dark_mode: _,
@@ -1749,6 +2454,7 @@ impl Visuals {
             code_bg_color,
             warn_fg_color,
             error_fg_color,
+            success_fg_color,
 
             window_rounding,
             window_shadow,
@@ -1764,7 +2470,7 @@ impl Visuals {
 
             resize_corner_size,
             text_cursor,
-            text_cursor_preview,
+            text_cursor_preview: _,
             clip_rect_margin,
             button_frame,
             collapsing_header_frame,
@@ -1774,6 +2480,8 @@ impl Visuals {
 
             slider_trailing_fill,
             handle_shape,
+            segmented_control_rounding,
+            chip_rounding,
             interact_cursor,
 
             image_loading_spinners,
@@ -1816,6 +2524,7 @@ impl Visuals {
             );
             ui_color(ui, warn_fg_color, RichText::new("Warnings"));
             ui_color(ui, error_fg_color, RichText::new("Errors"));
+            ui_color(ui, success_fg_color, RichText::new("Success"));
         });
 
         ui_color(ui, code_bg_color, RichText::new("Code background").code()).on_hover_ui(|ui| {
@@ -1828,10 +2537,9 @@ impl Visuals {
         });
 
         ui_color(ui, hyperlink_color, "hyperlink_color");
-        stroke_ui(ui, text_cursor, "Text Cursor");
+        ui.collapsing("Text cursor", |ui| text_cursor.ui(ui));
 
         ui.add(Slider::new(resize_corner_size, 0.0..=20.0).text("resize_corner_size"));
-        ui.checkbox(text_cursor_preview, "Preview text cursor on hover");
         ui.add(Slider::new(clip_rect_margin, 0.0..=20.0).text("clip_rect_margin"));
 
         ui.checkbox(button_frame, "Button has a frame");
@@ -1847,6 +2555,11 @@ impl Visuals {
 
         handle_shape.ui(ui);
 
+        ui.collapsing("Segmented controls", |ui| {
+            rounding_ui(ui, segmented_control_rounding)
+        });
+        ui.collapsing("Chips", |ui| rounding_ui(ui, chip_rounding));
+
         ComboBox::from_label("Interact Cursor")
             .selected_text(format!("{interact_cursor:?}"))
             .show_ui(ui, |ui| {
@@ -1881,6 +2594,8 @@ impl DebugOptions {
             show_resize,
             show_interactive_widgets,
             show_widget_hits,
+            measure,
+            show_missing_glyphs,
         } = self;
 
         {
@@ -1910,6 +2625,10 @@ impl DebugOptions {
 
         ui.checkbox(show_widget_hits, "Show widgets under mouse pointer");
 
+        ui.checkbox(measure, "Measure distances by dragging");
+
+        ui.checkbox(show_missing_glyphs, "Highlight missing glyphs");
+
         ui.vertical_centered(|ui| reset_button(ui, self));
     }
 }
@@ -2031,3 +2750,62 @@ impl std::fmt::Display for NumericColorSpace {
         }
     }
 }
+
+#[test]
+fn test_text_cursor_is_visible() {
+    let style = TextCursorStyle {
+        on_duration: 0.5,
+        off_duration: 0.25,
+        ..Default::default()
+    };
+    assert_eq!(style.is_visible(0.0), (true, Some(0.5)));
+    assert_eq!(style.is_visible(0.5), (false, Some(0.25)));
+    assert_eq!(style.is_visible(0.75), (true, Some(0.5)));
+
+    let style = TextCursorStyle {
+        blink: false,
+        ..style
+    };
+    assert_eq!(style.is_visible(0.6), (true, None));
+}
+
+#[test]
+fn test_widget_state_visuals() {
+    let visuals = Visuals::dark();
+    let widgets = &visuals.widgets;
+    let interactive = WidgetState {
+        interactive: true,
+        ..Default::default()
+    };
+
+    assert_eq!(
+        widgets.for_state(&WidgetState::default()),
+        &widgets.noninteractive
+    );
+    assert_eq!(widgets.for_state(&interactive), &widgets.inactive);
+    let hovered = WidgetState {
+        hovered: true,
+        ..interactive
+    };
+    assert_eq!(widgets.for_state(&hovered), &widgets.hovered);
+    let focused = WidgetState {
+        focused: true,
+        ..hovered
+    };
+    assert_eq!(widgets.for_state(&focused), &widgets.active);
+    assert_eq!(widgets.for_state(&focused.open(true)), &widgets.open);
+
+    let checked = visuals.widget_visuals(interactive.checked(true));
+    assert_eq!(checked.bg_fill, visuals.selection.bg_fill);
+    assert_eq!(checked.fg_stroke, visuals.selection.stroke);
+
+    let indeterminate = visuals.widget_visuals(interactive.checked(true).indeterminate(true));
+    assert_ne!(indeterminate.bg_fill, visuals.selection.bg_fill);
+    assert_ne!(indeterminate.bg_fill, widgets.inactive.bg_fill);
+    assert_eq!(indeterminate.fg_stroke, widgets.inactive.fg_stroke);
+
+    let invalid = visuals.widget_visuals(hovered.invalid(true));
+    assert_eq!(invalid.bg_stroke.color, visuals.error_fg_color);
+    assert_eq!(invalid.bg_fill, widgets.hovered.bg_fill);
+    assert!(invalid.bg_stroke.width >= 1.0);
+}
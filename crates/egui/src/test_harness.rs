@@ -0,0 +1,389 @@
+//! Drive egui from tests, without any integration or window.
+//!
+//! A [`Harness`] owns a [`Context`] and your app code,
+//! and turns high-level actions like "click here" or "type this"
+//! into the [`RawInput`] events that a real integration would send, spread out over several frames.
+//!
+//! ```
+//! use egui::test_harness::Harness;
+//!
+//! let mut checked = false;
+//! let mut harness = Harness::new_ui(|ui| {
+//!     ui.checkbox(&mut checked, "Check me");
+//! });
+//! harness.get_by_label("Check me").click();
+//! drop(harness);
+//! assert!(checked);
+//! ```
+//!
+//! Widgets are found by what they report with [`Response::widget_info`],
+//! so everything that is accessible to a screen reader can be found in a test.
+
+use crate::*;
+
+/// Simulated time between two frames, in seconds.
+const FRAME_TIME: f64 = 1.0 / 60.0;
+
+/// Runs frames with simulated input. See the [module docs](self).
+pub struct Harness<'a> {
+    ctx: Context,
+    app: Box<dyn FnMut(&Context) + 'a>,
+    screen_rect: Rect,
+
+    /// Where the root viewport is on screen, if we report it.
+    inner_rect: Option<Rect>,
+
+    time: f64,
+    modifiers: Modifiers,
+
+    /// Where the simulated pointer is.
+    pointer_pos: Pos2,
+
+    /// Events for the next frame.
+    events: Vec<Event>,
+
+    /// The output of the last frame.
+    output: FullOutput,
+}
+
+impl<'a> Harness<'a> {
+    /// Create a harness for a full app, and run it until it has settled.
+    ///
+    /// The screen is 800x600 points by default, see [`Self::set_size`].
+    pub fn new(app: impl FnMut(&Context) + 'a) -> Self {
+        let ctx = Context::default();
+        ctx.enable_widget_info_recording();
+
+        let mut harness = Self {
+            ctx,
+            app: Box::new(app),
+            screen_rect: Rect::from_min_size(Pos2::ZERO, vec2(800.0, 600.0)),
+            inner_rect: None,
+            time: 0.0,
+            modifiers: Modifiers::NONE,
+            pointer_pos: Pos2::ZERO,
+            events: Vec::new(),
+            output: FullOutput::default(),
+        };
+        harness.run();
+        harness
+    }
+
+    /// Create a harness that shows the given ui in a [`CentralPanel`].
+    pub fn new_ui(mut add_contents: impl FnMut(&mut Ui) + 'a) -> Self {
+        Self::new(move |ctx| {
+            CentralPanel::default().show(ctx, |ui| add_contents(ui));
+        })
+    }
+
+    /// The context driven by this harness.
+    ///
+    /// Use this to e.g. change the style before running more frames.
+    #[inline]
+    pub fn ctx(&self) -> &Context {
+        &self.ctx
+    }
+
+    /// The output of the last frame.
+    #[inline]
+    pub fn output(&self) -> &FullOutput {
+        &self.output
+    }
+
+    /// Change the size of the screen (in points), and run until settled.
+    pub fn set_size(&mut self, size: Vec2) {
+        self.screen_rect = Rect::from_min_size(Pos2::ZERO, size);
+        self.run();
+    }
+
+    /// Report that the root viewport is at this rect of the screen, like a native integration would,
+    /// and run until settled.
+    ///
+    /// By default no position is reported, as with an integration that can't tell.
+    pub fn set_viewport_inner_rect(&mut self, inner_rect: Rect) {
+        self.inner_rect = Some(inner_rect);
+        self.run();
+    }
+
+    /// Hold down these modifiers for all following events.
+    pub fn set_modifiers(&mut self, modifiers: Modifiers) {
+        self.modifiers = modifiers;
+    }
+
+    /// Queue an event for the next frame.
+    pub fn push_event(&mut self, event: Event) {
+        self.events.push(event);
+    }
+
+    /// Run a single frame with all the queued events.
+    pub fn step(&mut self) {
+        let mut viewports = ViewportIdMap::default();
+        viewports.insert(
+            ViewportId::ROOT,
+            ViewportInfo {
+                inner_rect: self.inner_rect,
+                ..Default::default()
+            },
+        );
+        let raw_input = RawInput {
+            viewports,
+            screen_rect: Some(self.screen_rect),
+            time: Some(self.time),
+            predicted_dt: FRAME_TIME as f32,
+            modifiers: self.modifiers,
+            events: std::mem::take(&mut self.events),
+            ..Default::default()
+        };
+        self.time += FRAME_TIME;
+
+        let app = &mut self.app;
+        self.output = self.ctx.run(raw_input, |ctx| app(ctx));
+    }
+
+    /// Run frames until egui no longer asks for an immediate repaint,
+    /// e.g. because all animations have finished.
+    ///
+    /// Runs at least one frame, and at most 100.
+    pub fn run(&mut self) {
+        for _ in 0..100 {
+            self.step();
+            let wants_repaint = self
+                .output
+                .viewport_output
+                .get(&ViewportId::ROOT)
+                .map_or(false, |output| output.repaint_delay.is_zero());
+            if !wants_repaint {
+                return;
+            }
+        }
+    }
+
+    /// Move the pointer, and run until settled.
+    pub fn hover(&mut self, pos: Pos2) {
+        self.pointer_pos = pos;
+        self.push_event(Event::PointerMoved(pos));
+        self.run();
+    }
+
+    /// Click the primary mouse button at the given position.
+    ///
+    /// The pointer is moved there first, and then pressed and released in separate frames.
+    pub fn click(&mut self, pos: Pos2) {
+        self.hover(pos);
+        self.pointer_button(PointerButton::Primary, true);
+        self.step();
+        self.pointer_button(PointerButton::Primary, false);
+        self.run();
+    }
+
+    /// Press the primary mouse button at the given position, hold it for a long-press, and release it.
+    ///
+    /// See [`Options::long_press`].
+    pub fn long_press(&mut self, pos: Pos2) {
+        self.hover(pos);
+        self.pointer_button(PointerButton::Primary, true);
+        let duration = self.ctx.options(|o| o.long_press.duration) as f64;
+        for _ in 0..=(duration / FRAME_TIME).ceil() as usize {
+            self.step();
+        }
+        self.pointer_button(PointerButton::Primary, false);
+        self.run();
+    }
+
+    /// Press the primary mouse button at `from`, move to `to` over a few frames, and release it there.
+    pub fn drag(&mut self, from: Pos2, to: Pos2) {
+        const STEPS: usize = 5;
+
+        self.hover(from);
+        self.pointer_button(PointerButton::Primary, true);
+        self.step();
+        for i in 1..=STEPS {
+            self.pointer_pos = from.lerp(to, i as f32 / STEPS as f32);
+            self.push_event(Event::PointerMoved(self.pointer_pos));
+            self.step();
+        }
+        self.pointer_button(PointerButton::Primary, false);
+        self.run();
+    }
+
+    /// Type some text into the focused widget, and run until settled.
+    pub fn type_text(&mut self, text: &str) {
+        self.push_event(Event::Text(text.to_owned()));
+        self.run();
+    }
+
+    /// Press and release a key, and run until settled.
+    pub fn press_key(&mut self, key: Key) {
+        for pressed in [true, false] {
+            self.push_event(Event::Key {
+                key,
+                physical_key: None,
+                pressed,
+                repeat: false,
+                modifiers: self.modifiers,
+            });
+        }
+        self.run();
+    }
+
+    fn pointer_button(&mut self, button: PointerButton, pressed: bool) {
+        self.push_event(Event::PointerButton {
+            pos: self.pointer_pos,
+            button,
+            pressed,
+            modifiers: self.modifiers,
+        });
+    }
+
+    /// All widgets of the last frame for which `predicate` is true, in no particular order.
+    pub fn query_all(&self, predicate: impl Fn(&WidgetInfo) -> bool) -> Vec<WidgetNode> {
+        self.ctx.widget_rects_prev_frame(|widgets| {
+            widgets
+                .layers()
+                .flat_map(|(_, rects)| rects.iter())
+                .filter_map(|rect| {
+                    let info = widgets.info(rect.id)?;
+                    predicate(info).then(|| WidgetNode {
+                        rect: *rect,
+                        info: info.clone(),
+                    })
+                })
+                .collect()
+        })
+    }
+
+    /// The only widget of the last frame for which `predicate` is true.
+    ///
+    /// # Panics
+    /// If there is no such widget, or more than one.
+    pub fn get_by(&mut self, predicate: impl Fn(&WidgetInfo) -> bool) -> WidgetQuery<'_, 'a> {
+        let mut nodes = self.query_all(predicate);
+        assert!(!nodes.is_empty(), "No widget matched the query");
+        assert!(
+            nodes.len() == 1,
+            "Expected one widget to match the query, but found {}: {:?}",
+            nodes.len(),
+            nodes.iter().map(|node| &node.info).collect::<Vec<_>>()
+        );
+        WidgetQuery {
+            harness: self,
+            node: nodes.remove(0),
+        }
+    }
+
+    /// The only widget of the last frame with this label, e.g. a button or a checkbox.
+    ///
+    /// # Panics
+    /// If there is no such widget, or more than one.
+    pub fn get_by_label(&mut self, label: &str) -> WidgetQuery<'_, 'a> {
+        self.get_by(|info| info.label.as_deref() == Some(label))
+    }
+
+    /// The widget of the last frame with this label, if there is exactly one.
+    pub fn query_by_label(&self, label: &str) -> Option<WidgetNode> {
+        let mut nodes = self.query_all(|info| info.label.as_deref() == Some(label));
+        (nodes.len() == 1).then(|| nodes.remove(0))
+    }
+}
+
+/// A widget found by a [`Harness`] query.
+#[derive(Clone, Debug)]
+pub struct WidgetNode {
+    /// Where the widget is.
+    pub rect: WidgetRect,
+
+    /// What the widget reported about itself.
+    pub info: WidgetInfo,
+}
+
+/// A widget found by e.g. [`Harness::get_by_label`], ready to be interacted with.
+pub struct WidgetQuery<'h, 'a> {
+    harness: &'h mut Harness<'a>,
+    node: WidgetNode,
+}
+
+impl<'h, 'a> WidgetQuery<'h, 'a> {
+    /// The widget that was found.
+    #[inline]
+    pub fn node(&self) -> &WidgetNode {
+        &self.node
+    }
+
+    /// What the widget reported about itself.
+    #[inline]
+    pub fn info(&self) -> &WidgetInfo {
+        &self.node.info
+    }
+
+    /// The part of the widget that can be interacted with.
+    #[inline]
+    pub fn interact_rect(&self) -> Rect {
+        self.node.rect.interact_rect
+    }
+
+    /// Click the center of the widget.
+    pub fn click(self) -> &'h mut Harness<'a> {
+        let pos = self.interact_rect().center();
+        self.harness.click(pos);
+        self.harness
+    }
+
+    /// Move the pointer to the center of the widget.
+    pub fn hover(self) -> &'h mut Harness<'a> {
+        let pos = self.interact_rect().center();
+        self.harness.hover(pos);
+        self.harness
+    }
+
+    /// Drag from the center of the widget to the given position.
+    pub fn drag_to(self, to: Pos2) -> &'h mut Harness<'a> {
+        let from = self.interact_rect().center();
+        self.harness.drag(from, to);
+        self.harness
+    }
+
+    /// Click the widget to focus it, then type the text.
+    pub fn type_text(self, text: &str) -> &'h mut Harness<'a> {
+        let harness = self.click();
+        harness.type_text(text);
+        harness
+    }
+}
+
+#[test]
+fn test_click_and_type() {
+    let mut clicks = 0;
+    let mut text = String::new();
+    let mut harness = Harness::new_ui(|ui| {
+        if ui.button("OK").clicked() {
+            clicks += 1;
+        }
+        ui.text_edit_singleline(&mut text);
+    });
+
+    harness.get_by_label("OK").click();
+    harness
+        .get_by(|info| info.typ == WidgetType::TextEdit)
+        .type_text("abc");
+    assert!(harness.query_by_label("Cancel").is_none());
+    drop(harness);
+
+    assert_eq!(clicks, 1);
+    assert_eq!(text, "abc");
+}
+
+#[test]
+fn test_drag() {
+    let mut value = 0.0;
+    let mut harness = Harness::new_ui(|ui| {
+        ui.add(DragValue::new(&mut value).speed(1.0));
+    });
+    let from = harness
+        .get_by(|info| info.typ == WidgetType::DragValue)
+        .interact_rect()
+        .center();
+    harness.drag(from, from + vec2(50.0, 0.0));
+    drop(harness);
+
+    assert!(0.0 < value, "value: {value}");
+}
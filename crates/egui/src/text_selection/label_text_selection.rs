@@ -4,7 +4,9 @@ use crate::{
 };
 
 use super::{
-    text_cursor_state::cursor_rect, visuals::paint_text_selection, CursorRange, TextCursorState,
+    text_cursor_state::{char_width_at, cursor_rect},
+    visuals::{paint_cursor_over_char, paint_text_selection},
+    CursorRange, TextCursorState,
 };
 
 /// Turn on to help debug this
@@ -488,6 +490,15 @@ impl LabelSelectionState {
             if response.contains_pointer() {
                 let cursor_at_pointer = galley.cursor_from_pos(pointer_pos - galley_pos);
 
+                if ui.visuals().text_cursor_preview() && ui.input(|i| i.pointer.is_moving()) {
+                    // Preview where the selection would start:
+                    let row_height = estimate_row_height(galley);
+                    let cursor_rect =
+                        cursor_rect(galley_pos, galley, &cursor_at_pointer, row_height);
+                    let char_width = char_width_at(galley, &cursor_at_pointer);
+                    paint_cursor_over_char(ui.painter(), ui.visuals(), cursor_rect, char_width);
+                }
+
                 // This is where we handle start-of-drag and double-click-to-select.
                 // Actual drag-to-select happens elsewhere.
                 let dragged = false;
@@ -343,3 +343,31 @@ pub fn cursor_rect(galley_pos: Pos2, galley: &Galley, cursor: &Cursor, row_heigh
     // slightly above/below row
     cursor_pos
 }
+
+/// The width of the character after the cursor, e.g. for a block cursor.
+///
+/// At the end of a row there is no such character, so we use half the row height.
+pub fn char_width_at(galley: &Galley, cursor: &Cursor) -> f32 {
+    let Some(row) = galley.rows.get(cursor.rcursor.row) else {
+        return 0.0;
+    };
+    row.glyphs
+        .get(cursor.rcursor.column)
+        .map_or(row.height() / 2.0, |glyph| glyph.size.x)
+}
+
+#[test]
+fn test_char_width_at() {
+    let ctx = Context::default();
+    let _ = ctx.run(Default::default(), |_ctx| {});
+    let galley = ctx.fonts(|fonts| {
+        fonts.layout_no_wrap("iW".to_owned(), FontId::monospace(14.0), Color32::WHITE)
+    });
+    let width_at = |index| char_width_at(&galley, &galley.from_ccursor(CCursor::new(index)));
+
+    assert_eq!(width_at(0), galley.rows[0].glyphs[0].size.x);
+    assert_eq!(width_at(1), galley.rows[0].glyphs[1].size.x);
+
+    // There is no character at the end of the row:
+    assert_eq!(width_at(2), galley.rows[0].height() / 2.0);
+}
@@ -51,25 +51,81 @@ pub fn paint_text_selection(
 }
 
 /// Paint one end of the selection, e.g. the primary cursor.
+///
+/// This ignores blinking. See [`paint_text_cursor`] for that.
+///
+/// We don't know how wide the character after the cursor is, so a block or underline cursor
+/// is as wide as half the row height. Use [`paint_cursor_over_char`] if you know the width.
 pub fn paint_cursor(painter: &Painter, visuals: &Visuals, cursor_rect: Rect) {
-    let stroke = visuals.text_cursor;
+    paint_cursor_over_char(painter, visuals, cursor_rect, cursor_rect.height() / 2.0);
+}
 
-    let top = cursor_rect.center_top();
-    let bottom = cursor_rect.center_bottom();
+/// Like [`paint_cursor`], but with the width of the character after the cursor,
+/// e.g. from [`super::text_cursor_state::char_width_at`].
+pub fn paint_cursor_over_char(
+    painter: &Painter,
+    visuals: &Visuals,
+    cursor_rect: Rect,
+    char_width: f32,
+) {
+    let TextCursorStyle { stroke, shape, .. } = visuals.text_cursor;
+    let left = cursor_rect.center().x;
 
-    painter.line_segment([top, bottom], (stroke.width, stroke.color));
+    match shape {
+        TextCursorShape::Line => {
+            painter.line_segment(
+                [cursor_rect.center_top(), cursor_rect.center_bottom()],
+                stroke,
+            );
+        }
+        TextCursorShape::Block => {
+            let rect = Rect::from_x_y_ranges(left..=left + char_width, cursor_rect.y_range());
+            // Painted on top of the text, so make it transparent:
+            painter.rect_filled(rect, 0.0, stroke.color.linear_multiply(0.5));
+        }
+        TextCursorShape::Underline => {
+            let y = cursor_rect.bottom() - stroke.width / 2.0;
+            painter.hline(left..=left + char_width, y, stroke);
+        }
+    }
+}
 
-    if false {
-        // Roof/floor:
-        let extrusion = 3.0;
-        let width = 1.0;
-        painter.line_segment(
-            [top - vec2(extrusion, 0.0), top + vec2(extrusion, 0.0)],
-            (width, stroke.color),
-        );
-        painter.line_segment(
-            [bottom - vec2(extrusion, 0.0), bottom + vec2(extrusion, 0.0)],
-            (width, stroke.color),
-        );
+/// Paint the primary cursor of a focused text widget, blinking as configured in [`Visuals::text_cursor`].
+///
+/// `char_width` is the width of the character after the cursor, for block and underline cursors.
+///
+/// `time_since_reset` is how long ago (in seconds) the blinking was restarted,
+/// e.g. because the widget got focus or the user typed something.
+///
+/// A repaint is only requested while the cursor is on screen and blinking,
+/// so that an idle app with a focused text field doesn't keep repainting in the background,
+/// and only for the part of the screen with the cursor, see [`Context::request_repaint_rect_after`].
+pub fn paint_text_cursor(
+    ui: &Ui,
+    painter: &Painter,
+    cursor_rect: Rect,
+    char_width: f32,
+    time_since_reset: f64,
+) {
+    let style = ui.visuals().text_cursor;
+    let (visible, time_until_toggle) = style.is_visible(time_since_reset);
+
+    if visible {
+        paint_cursor_over_char(painter, ui.visuals(), cursor_rect, char_width);
+    }
+
+    if let Some(time_until_toggle) = time_until_toggle {
+        let on_screen = painter.clip_rect().intersects(cursor_rect);
+        let window_focused = ui.input(|i| i.focused);
+        if on_screen && window_focused {
+            // Wide enough for all cursor shapes:
+            let repaint_rect = cursor_rect
+                .with_max_x(cursor_rect.center().x + char_width)
+                .expand(style.stroke.width);
+            ui.ctx().request_repaint_rect_after(
+                std::time::Duration::from_secs_f32(time_until_toggle),
+                repaint_rect,
+            );
+        }
     }
 }
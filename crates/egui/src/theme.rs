@@ -0,0 +1,310 @@
+//! Dark and light mode, and following the theme of the operating system.
+
+use crate::*;
+
+/// Dark or light mode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum Theme {
+    /// Dark mode: light text on a dark background.
+    Dark,
+
+    /// Light mode: dark text on a light background.
+    Light,
+}
+
+impl Theme {
+    /// The default [`Visuals`] for this theme.
+    pub fn default_visuals(self) -> Visuals {
+        match self {
+            Self::Dark => Visuals::dark(),
+            Self::Light => Visuals::light(),
+        }
+    }
+
+    /// Is this [`Self::Dark`]?
+    #[inline]
+    pub fn is_dark(self) -> bool {
+        self == Self::Dark
+    }
+}
+
+/// Which [`Theme`] to use, set in [`crate::Options::theme_preference`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum ThemePreference {
+    /// Always use dark mode.
+    Dark,
+
+    /// Always use light mode.
+    Light,
+
+    /// Follow [`RawInput::system_theme`].
+    ///
+    /// If the integration doesn't report the system theme, the visuals are left as they are.
+    #[default]
+    System,
+}
+
+impl ThemePreference {
+    /// The theme to use, given the theme of the operating system (if known).
+    pub fn theme(self, system_theme: Option<Theme>) -> Option<Theme> {
+        match self {
+            Self::Dark => Some(Theme::Dark),
+            Self::Light => Some(Theme::Light),
+            Self::System => system_theme,
+        }
+    }
+
+    /// Show radio buttons to switch between the preferences.
+    pub fn radio_buttons(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.selectable_value(self, Self::System, "💻 System");
+            ui.selectable_value(self, Self::Dark, "🌙 Dark");
+            ui.selectable_value(self, Self::Light, "☀ Light");
+        });
+    }
+}
+
+impl From<Theme> for ThemePreference {
+    fn from(theme: Theme) -> Self {
+        match theme {
+            Theme::Dark => Self::Dark,
+            Theme::Light => Self::Light,
+        }
+    }
+}
+
+/// A fade between the visuals of two themes.
+#[derive(Clone, Debug)]
+struct Transition {
+    from: Visuals,
+    to: Visuals,
+    start_time: f64,
+    duration: f32,
+}
+
+/// Keeps track of the current theme, and switches [`Visuals`] when it changes.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ThemeState {
+    /// The last theme reported by the integration.
+    system_theme: Option<Theme>,
+
+    /// The theme we are showing (or fading to). `None` before it is first known.
+    theme: Option<Theme>,
+
+    transition: Option<Transition>,
+}
+
+impl ThemeState {
+    /// Call at the start of each frame of the outermost viewport.
+    ///
+    /// Sends [`Event::ThemeChanged`] when the theme changes.
+    /// Returns `true` if we need to repaint to continue the transition.
+    pub(crate) fn begin_frame(&mut self, options: &mut Options, input: &mut InputState) -> bool {
+        if let Some(system_theme) = input.raw.system_theme {
+            self.system_theme = Some(system_theme);
+        }
+
+        let Some(theme) = options.theme_preference.theme(self.system_theme) else {
+            return false;
+        };
+
+        if self.theme != Some(theme) {
+            let is_first_frame = self.theme.is_none();
+            self.theme = Some(theme);
+            input.events.push(Event::ThemeChanged(theme));
+
+            let Options {
+                style,
+                dark_visuals,
+                light_visuals,
+                animations_enabled,
+                ..
+            } = options;
+            let style = std::sync::Arc::make_mut(style);
+
+            // Keep the visuals we are leaving (including any changes the app made to them),
+            // so we can go back to them later:
+            let current = match self.transition.take() {
+                Some(transition) => transition.to,
+                None => style.visuals.clone(),
+            };
+            if current.dark_mode == theme.is_dark() {
+                // Already of the right theme, e.g. set by the app before the first frame.
+                style.visuals = current;
+            } else {
+                let to = if theme.is_dark() {
+                    dark_visuals.clone()
+                } else {
+                    light_visuals.clone()
+                };
+                if current.dark_mode {
+                    *dark_visuals = current;
+                } else {
+                    *light_visuals = current;
+                }
+
+                // A bit slower than other animations, since it affects everything:
+                let duration = if *animations_enabled {
+                    3.0 * style.animation_time
+                } else {
+                    0.0
+                };
+                if is_first_frame || duration <= 0.0 {
+                    style.visuals = to;
+                } else {
+                    self.transition = Some(Transition {
+                        from: style.visuals.clone(),
+                        to,
+                        start_time: input.time,
+                        duration,
+                    });
+                }
+            }
+        }
+
+        let Some(transition) = &self.transition else {
+            return false;
+        };
+        let t = ((input.time - transition.start_time) as f32 / transition.duration).min(1.0);
+        let style = std::sync::Arc::make_mut(&mut options.style);
+        if t < 1.0 {
+            style.visuals = lerp_visuals(&transition.from, &transition.to, t);
+            true
+        } else {
+            style.visuals = transition.to.clone();
+            self.transition = None;
+            false
+        }
+    }
+}
+
+/// Fades all the colors. Everything else is taken from `to` right away.
+fn lerp_visuals(from: &Visuals, to: &Visuals, t: f32) -> Visuals {
+    let color = |from: Color32, to: Color32| {
+        let [r, g, b, a] =
+            std::array::from_fn(|i| emath::lerp(from[i] as f32..=to[i] as f32, t).round() as u8);
+        Color32::from_rgba_premultiplied(r, g, b, a)
+    };
+    let stroke = |from: Stroke, to: Stroke| Stroke::new(to.width, color(from.color, to.color));
+    let widget = |from: &style::WidgetVisuals, to: &style::WidgetVisuals| style::WidgetVisuals {
+        bg_fill: color(from.bg_fill, to.bg_fill),
+        weak_bg_fill: color(from.weak_bg_fill, to.weak_bg_fill),
+        bg_stroke: stroke(from.bg_stroke, to.bg_stroke),
+        fg_stroke: stroke(from.fg_stroke, to.fg_stroke),
+        ..*to
+    };
+
+    let mut visuals = to.clone();
+    visuals.override_text_color = match (from.override_text_color, to.override_text_color) {
+        (Some(from), Some(to)) => Some(color(from, to)),
+        (_, to) => to,
+    };
+    visuals.widgets = style::Widgets {
+        noninteractive: widget(&from.widgets.noninteractive, &to.widgets.noninteractive),
+        inactive: widget(&from.widgets.inactive, &to.widgets.inactive),
+        hovered: widget(&from.widgets.hovered, &to.widgets.hovered),
+        active: widget(&from.widgets.active, &to.widgets.active),
+        open: widget(&from.widgets.open, &to.widgets.open),
+    };
+    visuals.selection.bg_fill = color(from.selection.bg_fill, to.selection.bg_fill);
+    visuals.selection.stroke = stroke(from.selection.stroke, to.selection.stroke);
+    visuals.hyperlink_color = color(from.hyperlink_color, to.hyperlink_color);
+    visuals.faint_bg_color = color(from.faint_bg_color, to.faint_bg_color);
+    visuals.extreme_bg_color = color(from.extreme_bg_color, to.extreme_bg_color);
+    visuals.code_bg_color = color(from.code_bg_color, to.code_bg_color);
+    visuals.warn_fg_color = color(from.warn_fg_color, to.warn_fg_color);
+    visuals.error_fg_color = color(from.error_fg_color, to.error_fg_color);
+    visuals.window_shadow.color = color(from.window_shadow.color, to.window_shadow.color);
+    visuals.window_fill = color(from.window_fill, to.window_fill);
+    visuals.window_stroke = stroke(from.window_stroke, to.window_stroke);
+    visuals.panel_fill = color(from.panel_fill, to.panel_fill);
+    visuals.popup_shadow.color = color(from.popup_shadow.color, to.popup_shadow.color);
+    visuals.text_cursor.stroke = stroke(from.text_cursor.stroke, to.text_cursor.stroke);
+    visuals
+}
+
+#[test]
+fn test_follow_system_theme() {
+    let ctx = Context::default();
+    let run = |system_theme: Option<Theme>, time: f64| {
+        let mut theme_changed = None;
+        let _ = ctx.run(
+            RawInput {
+                system_theme,
+                time: Some(time),
+                ..Default::default()
+            },
+            |ctx| {
+                ctx.input(|i| {
+                    for event in &i.events {
+                        if let Event::ThemeChanged(theme) = event {
+                            theme_changed = Some(*theme);
+                        }
+                    }
+                });
+            },
+        );
+        theme_changed
+    };
+
+    // Unknown system theme: leave the visuals alone.
+    assert_eq!(run(None, 0.0), None);
+    assert!(ctx.style().visuals.dark_mode);
+
+    // The first known theme is applied right away:
+    assert_eq!(run(Some(Theme::Light), 1.0), Some(Theme::Light));
+    assert_eq!(ctx.style().visuals, Visuals::light());
+    assert_eq!(run(Some(Theme::Light), 2.0), None);
+
+    // Later changes fade in:
+    assert_eq!(run(Some(Theme::Dark), 3.0), Some(Theme::Dark));
+    assert!(ctx.style().visuals.dark_mode);
+    assert_ne!(ctx.style().visuals, Visuals::dark());
+    run(Some(Theme::Dark), 10.0);
+    assert_eq!(ctx.style().visuals, Visuals::dark());
+
+    // An explicit preference wins over the system theme:
+    ctx.options_mut(|o| o.theme_preference = ThemePreference::Light);
+    assert_eq!(run(Some(Theme::Dark), 11.0), Some(Theme::Light));
+}
+
+#[test]
+fn test_theme_change_keeps_custom_visuals() {
+    let ctx = Context::default();
+    ctx.options_mut(|o| o.animations_enabled = false);
+    let run = |system_theme: Theme| {
+        let _ = ctx.run(
+            RawInput {
+                system_theme: Some(system_theme),
+                ..Default::default()
+            },
+            |_ctx| {},
+        );
+    };
+
+    let mut custom_dark = Visuals::dark();
+    custom_dark.panel_fill = Color32::from_rgb(10, 20, 30);
+    ctx.set_visuals(custom_dark.clone());
+
+    // The first known theme matches the visuals, so they are left alone:
+    run(Theme::Dark);
+    assert_eq!(ctx.style().visuals, custom_dark);
+
+    // The same theme again is not a change:
+    ctx.style_mut(|style| style.visuals.hyperlink_color = Color32::RED);
+    custom_dark.hyperlink_color = Color32::RED;
+    run(Theme::Dark);
+    assert_eq!(ctx.style().visuals, custom_dark);
+
+    let mut custom_light = Visuals::light();
+    custom_light.panel_fill = Color32::from_rgb(200, 210, 220);
+    ctx.options_mut(|o| o.light_visuals = custom_light.clone());
+    run(Theme::Light);
+    assert_eq!(ctx.style().visuals, custom_light);
+
+    // Switching back restores the customized dark visuals:
+    run(Theme::Dark);
+    assert_eq!(ctx.style().visuals, custom_dark);
+}
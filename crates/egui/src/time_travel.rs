@@ -0,0 +1,277 @@
+//! Stepping back through recent frames, to find out why something flickered once.
+//!
+//! See [`Context::set_time_travel_capacity`] and [`Context::time_travel_ui`].
+
+use std::collections::VecDeque;
+
+use crate::*;
+
+/// What we need to re-run a frame of the root viewport.
+///
+/// Only the root viewport's part of the [`Memory`] is ever restored, see [`Memory::restore_viewport`].
+#[derive(Clone)]
+struct FrameSnapshot {
+    frame_nr: u64,
+    memory: Memory,
+    input: InputState,
+
+    /// The widgets of the frame before, for hit-testing.
+    widgets_prev_frame: WidgetRects,
+}
+
+#[derive(Default)]
+pub(crate) struct TimeTravel {
+    /// How many frames to keep. Zero means off.
+    pub capacity: usize,
+
+    /// The most recent frames, oldest first.
+    frames: VecDeque<FrameSnapshot>,
+
+    /// The index into [`Self::frames`] we are re-running, if any.
+    pub position: Option<usize>,
+
+    /// The live state, to go back to when we stop traveling.
+    live: Option<FrameSnapshot>,
+}
+
+impl TimeTravel {
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        while self.frames.len() > capacity {
+            self.frames.pop_front();
+        }
+        if self
+            .position
+            .is_some_and(|position| position >= self.frames.len())
+        {
+            self.position = self.frames.len().checked_sub(1);
+        }
+    }
+
+    /// The frame number of a recorded frame, and a description of its input.
+    pub fn describe(&self, position: usize) -> Option<(u64, String)> {
+        let frame = self.frames.get(position)?;
+        let description = format!(
+            "Pointer: {:?}\nEvents: {:#?}",
+            frame.input.pointer.latest_pos(),
+            frame.input.events
+        );
+        Some((frame.frame_nr, description))
+    }
+
+    /// Step with the real keys: left and right arrows step, escape goes back to live.
+    ///
+    /// Returns `true` if the position changed.
+    pub fn handle_keys(&mut self, events: &[Event]) -> bool {
+        let Some(position) = self.position else {
+            return false;
+        };
+        for event in events {
+            if let Event::Key {
+                key, pressed: true, ..
+            } = event
+            {
+                self.position = match key {
+                    Key::ArrowLeft => Some(position.saturating_sub(1)),
+                    Key::ArrowRight => Some((position + 1).min(self.frames.len() - 1)),
+                    Key::Escape => None,
+                    _ => continue,
+                };
+            }
+        }
+        self.position != Some(position)
+    }
+
+    /// Called at the start of the frame, before the new input is processed.
+    ///
+    /// Puts away the live state when we start traveling, and restores it when we stop.
+    pub fn begin_frame(
+        &mut self,
+        memory: &mut Memory,
+        input: &mut InputState,
+        widgets_prev_frame: &mut WidgetRects,
+    ) {
+        if self.position.is_some() && self.live.is_none() {
+            self.live = Some(FrameSnapshot {
+                frame_nr: 0,
+                memory: memory.clone(),
+                input: input.clone(),
+                widgets_prev_frame: widgets_prev_frame.clone(),
+            });
+        } else if self.position.is_none() {
+            if let Some(live) = self.live.take() {
+                memory.restore_viewport(live.memory, ViewportId::ROOT);
+                *input = live.input;
+                *widgets_prev_frame = live.widgets_prev_frame;
+            }
+        }
+    }
+
+    /// Called once the input of the frame is known.
+    ///
+    /// Records the frame, or replaces it with the one we are traveling to.
+    pub fn after_input(
+        &mut self,
+        frame_nr: u64,
+        memory: &mut Memory,
+        input: &mut InputState,
+        widgets_prev_frame: &mut WidgetRects,
+    ) {
+        if let Some(frame) = self.position.and_then(|position| self.frames.get(position)) {
+            memory.restore_viewport(frame.memory.clone(), ViewportId::ROOT);
+            *input = frame.input.clone();
+            *widgets_prev_frame = frame.widgets_prev_frame.clone();
+        } else if 0 < self.capacity {
+            if self.frames.len() == self.capacity {
+                self.frames.pop_front();
+            }
+            self.frames.push_back(FrameSnapshot {
+                frame_nr,
+                memory: memory.clone(),
+                input: input.clone(),
+                widgets_prev_frame: widgets_prev_frame.clone(),
+            });
+        }
+    }
+}
+
+/// `position` is the frame we are at, with what [`TimeTravel::describe`] says about it.
+pub(crate) fn time_travel_ui(
+    ctx: &Context,
+    ui: &mut Ui,
+    capacity: usize,
+    len: usize,
+    position: Option<(usize, u64, String)>,
+) {
+    if capacity == 0 {
+        ui.label("Time travel is off.");
+        if ui.button("Record the last 300 frames").clicked() {
+            ctx.set_time_travel_capacity(300);
+        }
+        return;
+    }
+
+    let Some((position, frame_nr, description)) = position else {
+        ui.label(format!(
+            "Recording the last {capacity} frames ({len} so far)."
+        ));
+        ui.horizontal(|ui| {
+            if ui
+                .add_enabled(0 < len, Button::new("⏸ Pause"))
+                .on_hover_text("Go back to the last recorded frame")
+                .clicked()
+            {
+                ctx.time_travel_to(len.checked_sub(1));
+            }
+            if ui.button("Stop recording").clicked() {
+                ctx.set_time_travel_capacity(0);
+            }
+        });
+        return;
+    };
+
+    // The input is from the past now, so the user can't click anything:
+    ui.strong(format!("Frame {frame_nr} ({}/{len})", position + 1));
+    ui.label("Use ← and → to step, and Esc to go back to live.");
+    ui.separator();
+    ui.monospace(description);
+}
+
+#[test]
+fn test_time_travel() {
+    use crate::test_harness::Harness;
+
+    let id = Id::new("counter");
+    let mut harness = Harness::new(|ctx| {
+        ctx.data_mut(|data| *data.get_temp_mut_or_default::<u32>(id) += 1);
+        CentralPanel::default().show(ctx, |_ui| {});
+    });
+    harness.ctx().set_time_travel_capacity(4);
+    for _ in 0..6 {
+        harness.step();
+    }
+    let counter = |harness: &Harness<'_>| harness.ctx().data(|data| data.get_temp::<u32>(id));
+    let press = |harness: &mut Harness<'_>, key| {
+        harness.push_event(Event::Key {
+            key,
+            physical_key: None,
+            pressed: true,
+            repeat: false,
+            modifiers: Modifiers::NONE,
+        });
+        harness.step();
+    };
+    // The ui ran `live` times, and we recorded the last four runs:
+    let live = counter(&harness).unwrap();
+    assert_eq!(harness.ctx().time_travel_frames(), 4);
+
+    // Re-running the second oldest frame, again and again:
+    harness.ctx().time_travel_to(Some(1));
+    harness.step();
+    assert_eq!(counter(&harness), Some(live - 2));
+    harness.step();
+    assert_eq!(counter(&harness), Some(live - 2));
+
+    // Step with the keys:
+    press(&mut harness, Key::ArrowLeft);
+    assert_eq!(harness.ctx().time_travel_position(), Some(0));
+    assert_eq!(counter(&harness), Some(live - 3));
+    press(&mut harness, Key::ArrowRight);
+    press(&mut harness, Key::ArrowRight);
+    assert_eq!(harness.ctx().time_travel_position(), Some(2));
+    assert_eq!(counter(&harness), Some(live - 1));
+
+    // Back to live, where we left off:
+    press(&mut harness, Key::Escape);
+    assert_eq!(harness.ctx().time_travel_position(), None);
+    assert_eq!(counter(&harness), Some(live + 1));
+}
+
+#[test]
+fn test_time_travel_keeps_other_viewports() {
+    let child = ViewportId::from_hash_of("child");
+    let window = LayerId::new(Order::Middle, Id::new("window"));
+    let window_state = crate::containers::area::State {
+        pivot_pos: Pos2::ZERO,
+        pivot: Align2::LEFT_TOP,
+        size: Vec2::splat(100.0),
+        interactable: true,
+        edges_padded_for_resize: false,
+    };
+    let num_areas = |memory: &mut Memory, viewport_id| {
+        memory.viewport_id = viewport_id;
+        let count = memory.areas_mut().count();
+        memory.viewport_id = ViewportId::ROOT;
+        count
+    };
+
+    let mut time_travel = TimeTravel::default();
+    time_travel.set_capacity(2);
+    let mut memory = Memory::default();
+    let mut input = InputState::default();
+    let mut widgets = WidgetRects::default();
+    time_travel.after_input(0, &mut memory, &mut input, &mut widgets);
+
+    // After the recorded frame, a window opens in both the root and the child viewport:
+    memory.areas_mut().set_state(window, window_state);
+    memory.viewport_id = child;
+    memory.areas_mut().set_state(window, window_state);
+    memory.viewport_id = ViewportId::ROOT;
+
+    // Traveling back only takes the root viewport back:
+    time_travel.position = Some(0);
+    time_travel.begin_frame(&mut memory, &mut input, &mut widgets);
+    time_travel.after_input(1, &mut memory, &mut input, &mut widgets);
+    assert_eq!(num_areas(&mut memory, ViewportId::ROOT), 0);
+    assert_eq!(num_areas(&mut memory, child), 1);
+
+    // Going back to live keeps the child viewport too:
+    time_travel.position = None;
+    time_travel.begin_frame(&mut memory, &mut input, &mut widgets);
+    assert_eq!(num_areas(&mut memory, ViewportId::ROOT), 1);
+    assert_eq!(num_areas(&mut memory, child), 1);
+}
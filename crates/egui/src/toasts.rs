@@ -0,0 +1,365 @@
+//! Toast notifications, see [`Context::toast`].
+
+use std::time::Duration;
+
+use crate::*;
+
+/// How long a [`Toast`] is shown by default, not counting the time the pointer is over it.
+const DEFAULT_DURATION: Duration = Duration::from_secs(4);
+
+/// Toasts wrap their text at this width.
+const MAX_WIDTH: f32 = 300.0;
+
+/// How far a [`Toast`] slides as it appears and disappears.
+const SLIDE_DISTANCE: f32 = 40.0;
+
+/// What a [`Toast`] is about, which decides its icon and color.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum ToastKind {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl ToastKind {
+    fn icon(self) -> &'static str {
+        match self {
+            Self::Info => "ℹ",
+            Self::Success => "✔",
+            Self::Warning => "⚠",
+            Self::Error => "🗙",
+        }
+    }
+
+    fn color(self, visuals: &Visuals) -> Color32 {
+        match self {
+            Self::Info => visuals.hyperlink_color,
+            Self::Success => visuals.success_fg_color,
+            Self::Warning => visuals.warn_fg_color,
+            Self::Error => visuals.error_fg_color,
+        }
+    }
+}
+
+/// A short notification in a corner of the viewport, shown with [`Context::toast`].
+///
+/// It goes away by itself after a while, but not while the pointer is over it.
+///
+/// ```
+/// # egui::__run_test_ctx(|ctx| {
+/// use std::time::Duration;
+///
+/// ctx.toast(egui::Toast::info("Saved").duration(Duration::from_secs(3)));
+///
+/// ctx.toast(
+///     egui::Toast::warning("Deleted 3 files")
+///         .duration(None) // Stay until closed
+///         .action("Undo", |ctx| {
+///             // Called when the button is clicked.
+///         }),
+/// );
+/// # });
+/// ```
+#[derive(Clone)]
+#[must_use = "You should show the toast with `ctx.toast(toast);`"]
+pub struct Toast {
+    kind: ToastKind,
+    text: WidgetText,
+    duration: Option<Duration>,
+    closable: bool,
+    actions: Vec<(WidgetText, ContextCallback)>,
+}
+
+impl Toast {
+    pub fn new(kind: ToastKind, text: impl Into<WidgetText>) -> Self {
+        Self {
+            kind,
+            text: text.into(),
+            duration: Some(DEFAULT_DURATION),
+            closable: true,
+            actions: Vec::new(),
+        }
+    }
+
+    pub fn info(text: impl Into<WidgetText>) -> Self {
+        Self::new(ToastKind::Info, text)
+    }
+
+    pub fn success(text: impl Into<WidgetText>) -> Self {
+        Self::new(ToastKind::Success, text)
+    }
+
+    pub fn warning(text: impl Into<WidgetText>) -> Self {
+        Self::new(ToastKind::Warning, text)
+    }
+
+    pub fn error(text: impl Into<WidgetText>) -> Self {
+        Self::new(ToastKind::Error, text)
+    }
+
+    /// How long to show the toast, not counting the time the pointer is over it.
+    ///
+    /// `None` shows it until it is closed.
+    ///
+    /// Default: 4 seconds.
+    #[inline]
+    pub fn duration(mut self, duration: impl Into<Option<Duration>>) -> Self {
+        self.duration = duration.into();
+        self
+    }
+
+    /// Show a close button. Default: `true`.
+    #[inline]
+    pub fn closable(mut self, closable: bool) -> Self {
+        self.closable = closable;
+        self
+    }
+
+    /// Add a button which closes the toast, and calls `callback` at the end of the frame it was clicked.
+    #[inline]
+    pub fn action(
+        mut self,
+        text: impl Into<WidgetText>,
+        callback: impl Fn(&Context) + Send + Sync + 'static,
+    ) -> Self {
+        self.actions
+            .push((text.into(), std::sync::Arc::new(callback)));
+        self
+    }
+}
+
+/// A toast that is being shown.
+#[derive(Clone)]
+struct ToastState {
+    id: Id,
+
+    /// Toasts are shown in the viewport they were added in.
+    viewport_id: ViewportId,
+
+    toast: Toast,
+
+    /// Seconds left to show it, if it goes away by itself.
+    time_left: Option<f32>,
+
+    /// Has it started to appear?
+    shown: bool,
+
+    /// Is it going away?
+    closing: bool,
+}
+
+/// The toasts of a [`Context`].
+#[derive(Clone, Default)]
+pub(crate) struct Toasts {
+    toasts: Vec<ToastState>,
+    next_id: u64,
+}
+
+impl Toasts {
+    pub(crate) fn register(ctx: &Context) {
+        ctx.on_end_frame("toasts", std::sync::Arc::new(Self::end_frame));
+    }
+
+    pub(crate) fn add(ctx: &Context, toast: Toast) {
+        let viewport_id = ctx.viewport_id();
+        ctx.data_mut(|data| {
+            let state = data.get_temp_mut_or_default::<Self>(Id::NULL);
+            state.toasts.push(ToastState {
+                id: Id::new("toast").with(state.next_id),
+                viewport_id,
+                time_left: toast.duration.map(|duration| duration.as_secs_f32()),
+                toast,
+                shown: false,
+                closing: false,
+            });
+            state.next_id += 1;
+        });
+        ctx.request_repaint_of(viewport_id);
+    }
+
+    fn end_frame(ctx: &Context) {
+        let Some(mut state) = ctx.data(|data| data.get_temp::<Self>(Id::NULL)) else {
+            return;
+        };
+        let viewport_id = ctx.viewport_id();
+        if !state.toasts.iter().any(|t| t.viewport_id == viewport_id) {
+            return;
+        }
+
+        let style = ctx.style();
+        let animation_time = style.animation_time;
+        let margin = vec2(
+            style.spacing.window_margin.right,
+            style.spacing.window_margin.bottom,
+        );
+        let dt = ctx.input(|i| i.stable_dt).min(0.1);
+
+        // Toasts are stacked away from the anchor, and slide in towards it:
+        let anchor = style.toast_anchor;
+        let outwards = -anchor.to_sign();
+        let stack_direction = if outwards.y == 0.0 { 1.0 } else { outwards.y };
+        let slide_direction = if outwards.x == 0.0 {
+            vec2(0.0, -stack_direction)
+        } else {
+            vec2(-outwards.x, 0.0)
+        };
+
+        let mut clicked_actions = vec![];
+        let mut stacked_height = 0.0;
+        for toast in &mut state.toasts {
+            if toast.viewport_id != viewport_id {
+                continue;
+            }
+
+            if !toast.shown {
+                // Make it appear gradually:
+                ctx.animate_bool_with_time(toast.id, false, animation_time);
+                toast.shown = true;
+            }
+            let how_shown = ctx.animate_bool_with_time(toast.id, !toast.closing, animation_time);
+            if toast.closing && how_shown == 0.0 {
+                continue;
+            }
+
+            let offset = outwards * margin
+                + vec2(0.0, stack_direction * stacked_height)
+                + (1.0 - how_shown) * SLIDE_DISTANCE * slide_direction;
+            let response = Area::new(toast.id)
+                .order(Order::Foreground)
+                .anchor(anchor, offset)
+                .show(ctx, |ui| {
+                    ui.set_opacity(how_shown);
+                    toast_ui(ui, &toast.toast)
+                });
+
+            let (close, action) = response.inner;
+            if close {
+                toast.closing = true;
+            }
+            if let Some(action) = action {
+                toast.closing = true;
+                clicked_actions.push(toast.toast.actions[action].1.clone());
+            }
+
+            // The timer is paused while the pointer is over the toast:
+            if let Some(time_left) = &mut toast.time_left {
+                if !response.response.contains_pointer() && !toast.closing {
+                    *time_left -= dt;
+                    if *time_left <= 0.0 {
+                        toast.closing = true;
+                    } else {
+                        ctx.request_repaint_after(Duration::from_secs_f32(*time_left));
+                    }
+                }
+            }
+
+            stacked_height +=
+                how_shown * (response.response.rect.height() + style.spacing.item_spacing.y);
+        }
+
+        // Forget about the toasts that are gone:
+        state.toasts.retain(|toast| {
+            toast.viewport_id != viewport_id
+                || !toast.closing
+                || ctx.animate_bool_with_time(toast.id, false, animation_time) > 0.0
+        });
+        ctx.data_mut(|data| data.insert_temp(Id::NULL, state));
+
+        for action in clicked_actions {
+            action(ctx);
+        }
+    }
+}
+
+/// Returns if the close button was clicked, and which action was clicked, if any.
+fn toast_ui(ui: &mut Ui, toast: &Toast) -> (bool, Option<usize>) {
+    let mut close = false;
+    let mut clicked_action = None;
+
+    Frame::popup(ui.style()).show(ui, |ui| {
+        ui.set_max_width(MAX_WIDTH);
+        ui.horizontal(|ui| {
+            let color = toast.kind.color(ui.visuals());
+            ui.label(RichText::new(toast.kind.icon()).color(color));
+            ui.add(Label::new(toast.text.clone()).wrap(true));
+
+            for (i, (text, _)) in toast.actions.iter().enumerate() {
+                if ui.button(text.clone()).clicked() {
+                    clicked_action = Some(i);
+                }
+            }
+
+            if toast.closable && ui.add(Button::new("🗙").frame(false)).clicked() {
+                close = true;
+            }
+        });
+    });
+
+    (close, clicked_action)
+}
+
+#[test]
+fn test_toasts() {
+    use crate::test_harness::Harness;
+    use std::sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    };
+
+    let undone = Arc::new(AtomicBool::new(false));
+    let mut harness = Harness::new(|ctx| {
+        CentralPanel::default().show(ctx, |ui| {
+            if ui.button("Save").clicked() {
+                ctx.toast(Toast::info("Saved").duration(Duration::from_secs(1)));
+            }
+            if ui.button("Delete").clicked() {
+                let undone = undone.clone();
+                ctx.toast(
+                    Toast::warning("Deleted")
+                        .duration(None)
+                        .action("Undo", move |_| undone.store(true, Ordering::Relaxed)),
+                );
+            }
+        });
+    });
+
+    // Toasts go away after their duration:
+    harness.get_by_label("Save").click();
+    assert!(harness.query_by_label("Saved").is_some());
+    for _ in 0..90 {
+        harness.step();
+    }
+    assert!(harness.query_by_label("Saved").is_none());
+
+    // …but not while the pointer is over them:
+    harness.get_by_label("Save").click();
+    harness.get_by_label("Saved").hover();
+    for _ in 0..90 {
+        harness.step();
+    }
+    assert!(harness.query_by_label("Saved").is_some());
+    harness.hover(Pos2::ZERO);
+    for _ in 0..90 {
+        harness.step();
+    }
+    assert!(harness.query_by_label("Saved").is_none());
+
+    // Actions close the toast, and call their callback:
+    harness.get_by_label("Delete").click();
+    harness.get_by_label("Undo").click();
+    assert!(undone.load(Ordering::Relaxed));
+    assert!(harness.query_by_label("Deleted").is_none());
+
+    // Toasts are shown in the corner the style says, the bottom right one by default:
+    harness.get_by_label("Save").click();
+    let bottom_right = harness.get_by_label("Saved").interact_rect();
+    assert!(400.0 < bottom_right.left() && 300.0 < bottom_right.top());
+    harness
+        .ctx()
+        .style_mut(|style| style.toast_anchor = Align2::LEFT_TOP);
+    harness.run();
+    let top_left = harness.get_by_label("Saved").interact_rect();
+    assert!(top_left.right() < 400.0 && top_left.bottom() < 300.0);
+}
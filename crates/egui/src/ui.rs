@@ -63,6 +63,12 @@ pub struct Ui {
 
     /// Indicates whether this Ui belongs to a Menu.
     menu_state: Option<Arc<RwLock<MenuState>>>,
+
+    /// If true, this [`Ui`] is only laid out to be measured, see [`Self::measure`].
+    measuring: bool,
+
+    /// If true, widgets show their values but reject edits, see [`Self::set_read_only`].
+    read_only: bool,
 }
 
 impl Ui {
@@ -73,6 +79,7 @@ impl Ui {
     ///
     /// Normally you would not use this directly, but instead use
     /// [`SidePanel`], [`TopBottomPanel`], [`CentralPanel`], [`Window`] or [`Area`].
+    #[track_caller]
     pub fn new(ctx: Context, layer_id: LayerId, id: Id, max_rect: Rect, clip_rect: Rect) -> Self {
         let style = ctx.style();
         let ui = Ui {
@@ -83,6 +90,8 @@ impl Ui {
             placer: Placer::new(max_rect, Layout::default()),
             enabled: true,
             menu_state: None,
+            measuring: false,
+            read_only: false,
         };
 
         // Register in the widget stack early, to ensure we are behind all widgets we contain:
@@ -100,11 +109,13 @@ impl Ui {
     }
 
     /// Create a new [`Ui`] at a specific region.
+    #[track_caller]
     pub fn child_ui(&mut self, max_rect: Rect, layout: Layout) -> Self {
         self.child_ui_with_id_source(max_rect, layout, "child")
     }
 
     /// Create a new [`Ui`] at a specific region with a specific id.
+    #[track_caller]
     pub fn child_ui_with_id_source(
         &mut self,
         max_rect: Rect,
@@ -122,22 +133,43 @@ impl Ui {
             placer: Placer::new(max_rect, layout),
             enabled: self.enabled,
             menu_state: self.menu_state.clone(),
+            measuring: self.measuring,
+            read_only: self.read_only,
         };
 
         // Register in the widget stack early, to ensure we are behind all widgets we contain:
-        let start_rect = Rect::NOTHING; // This will be overwritten when/if `interact_bg` is called
-        child_ui.ctx().create_widget(WidgetRect {
-            id: child_ui.id,
-            layer_id: child_ui.layer_id(),
-            rect: start_rect,
-            interact_rect: start_rect,
-            sense: Sense::hover(),
-            enabled: child_ui.enabled,
-        });
+        if !child_ui.measuring {
+            let start_rect = Rect::NOTHING; // This will be overwritten when/if `interact_bg` is called
+            child_ui.ctx().create_widget(WidgetRect {
+                id: child_ui.id,
+                layer_id: child_ui.layer_id(),
+                rect: start_rect,
+                interact_rect: start_rect,
+                sense: Sense::hover(),
+                enabled: child_ui.enabled,
+            });
+        }
 
         child_ui
     }
 
+    /// A [`Ui`] for [`Self::measure`], with the same id and layout as the next child of this one would get.
+    fn measuring_ui(&self, layout: Layout) -> Self {
+        let mut painter = self.painter.clone();
+        painter.set_invisible();
+        Ui {
+            id: self.id.with("child"),
+            next_auto_id_source: Id::new(self.next_auto_id_source).with("child").value(),
+            painter,
+            style: self.style.clone(),
+            placer: Placer::new(self.available_rect_before_wrap(), layout),
+            enabled: self.enabled,
+            menu_state: self.menu_state.clone(),
+            measuring: true,
+            read_only: self.read_only,
+        }
+    }
+
     // -------------------------------------------------
 
     /// A unique identity of this [`Ui`].
@@ -270,12 +302,43 @@ impl Ui {
         }
     }
 
+    /// If `true`, the widgets in this [`Ui`] show their values, but reject any edits.
+    ///
+    /// A read-only [`Ui`] is also not [enabled](Self::is_enabled).
+    #[inline]
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// Calling `set_read_only(true)` will make the widgets in this [`Ui`] deny all future interaction,
+    /// like [`Self::set_enabled`], but without graying them out,
+    /// so that they still show their values clearly.
+    ///
+    /// Usually it is more convenient to use [`Self::gate`] with [`Gate::read_only`].
+    ///
+    /// Calling `set_read_only(false)` has no effect - it will NOT make the [`Ui`] editable again.
+    pub fn set_read_only(&mut self, read_only: bool) {
+        if read_only {
+            self.read_only = true;
+            self.enabled = false;
+        }
+    }
+
     /// If `false`, any widgets added to the [`Ui`] will be invisible and non-interactive.
     #[inline]
     pub fn is_visible(&self) -> bool {
         self.painter.is_visible()
     }
 
+    /// If `true`, this [`Ui`] is only being measured by [`Self::measure`],
+    /// and will be shown again for real later.
+    ///
+    /// Widgets can check this to skip side effects, like changing their state.
+    #[inline]
+    pub fn is_measuring(&self) -> bool {
+        self.measuring
+    }
+
     /// Calling `set_visible(false)` will cause all further widgets to be invisible,
     /// yet still allocate space.
     ///
@@ -379,6 +442,30 @@ impl Ui {
     pub fn is_rect_visible(&self, rect: Rect) -> bool {
         self.is_visible() && rect.intersects(self.clip_rect())
     }
+
+    /// Show the full text in a tooltip when hovering `response`, if it didn't fit:
+    /// if it was elided, or if the clip rect cuts it off (e.g. in a table cell).
+    ///
+    /// Only does something if [`crate::style::Interaction::tooltip_for_clipped_text`] is on.
+    /// The built-in widgets do this for their text, so you only need it for custom widgets.
+    pub fn on_hover_text_if_clipped(
+        &self,
+        response: Response,
+        galley: &epaint::Galley,
+        text_pos: Pos2,
+    ) -> Response {
+        if !self.style().interaction.tooltip_for_clipped_text {
+            return response;
+        }
+        let text_rect = Rect::from_min_size(text_pos, galley.size());
+        // Allow for some rounding errors:
+        let clipped = !self.clip_rect().expand(0.5).contains_rect(text_rect);
+        if galley.elided || clipped {
+            response.on_hover_text(galley.text())
+        } else {
+            response
+        }
+    }
 }
 
 /// # Helpers for accessing the underlying [`Context`].
@@ -637,6 +724,43 @@ impl Ui {
     pub fn available_rect_before_wrap(&self) -> Rect {
         self.placer.available_rect_before_wrap()
     }
+
+    /// The size class of [`Self::available_width`], using the thresholds in [`Style::breakpoints`].
+    ///
+    /// Use this to adapt a layout to the screen size, see [`Breakpoint`].
+    pub fn breakpoint(&self) -> Breakpoint {
+        self.style().breakpoints.breakpoint(self.available_width())
+    }
+
+    /// Only add the contents if [`Self::breakpoint`] is the given one.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// use egui::Breakpoint;
+    /// ui.show_when(Breakpoint::Small, |ui| ui.label("☰"));
+    /// ui.show_when(Breakpoint::Large, |ui| ui.label("Welcome to the desktop version"));
+    /// # });
+    /// ```
+    ///
+    /// See also [`Self::show_from`].
+    pub fn show_when<R>(
+        &mut self,
+        breakpoint: Breakpoint,
+        add_contents: impl FnOnce(&mut Self) -> R,
+    ) -> Option<R> {
+        (self.breakpoint() == breakpoint).then(|| add_contents(self))
+    }
+
+    /// Only add the contents if [`Self::breakpoint`] is the given one or larger.
+    ///
+    /// See also [`Self::show_when`].
+    pub fn show_from<R>(
+        &mut self,
+        breakpoint: Breakpoint,
+        add_contents: impl FnOnce(&mut Self) -> R,
+    ) -> Option<R> {
+        (self.breakpoint() >= breakpoint).then(|| add_contents(self))
+    }
 }
 
 /// # [`Id`] creation
@@ -671,15 +795,23 @@ impl Ui {
 /// # Interaction
 impl Ui {
     /// Check for clicks, drags and/or hover on a specific region of this [`Ui`].
+    ///
+    /// While [measuring](Self::measure) the widget is neither registered nor interacted with.
+    #[track_caller]
     pub fn interact(&self, rect: Rect, id: Id, sense: Sense) -> Response {
-        self.ctx().create_widget(WidgetRect {
+        let widget_rect = WidgetRect {
             id,
             layer_id: self.layer_id(),
             rect,
             interact_rect: self.clip_rect().intersect(rect),
             sense,
             enabled: self.enabled,
-        })
+        };
+        if self.measuring {
+            self.ctx().inert_response(widget_rect)
+        } else {
+            self.ctx().create_widget(widget_rect)
+        }
     }
 
     /// Deprecated: use [`Self::interact`] instead.
@@ -698,6 +830,7 @@ impl Ui {
     /// i.e. behind all the widgets.
     ///
     /// The rectangle of the [`Response`] (and interactive area) will be [`Self::min_rect`].
+    #[track_caller]
     pub fn interact_bg(&self, sense: Sense) -> Response {
         // This will update the WidgetRect that was first created in `Ui::new`.
         self.interact(self.min_rect(), self.id, sense)
@@ -743,6 +876,7 @@ impl Ui {
     /// ui.painter().rect_stroke(response.rect, 0.0, (1.0, egui::Color32::WHITE));
     /// # });
     /// ```
+    #[track_caller]
     pub fn allocate_response(&mut self, desired_size: Vec2, sense: Sense) -> Response {
         let (id, rect) = self.allocate_space(desired_size);
         self.interact(rect, id, sense)
@@ -753,6 +887,7 @@ impl Ui {
     /// The response rect will be larger if this is part of a justified layout or similar.
     /// This means that if this is a narrow widget in a wide justified layout, then
     /// the widget will react to interactions outside the returned [`Rect`].
+    #[track_caller]
     pub fn allocate_exact_size(&mut self, desired_size: Vec2, sense: Sense) -> (Rect, Response) {
         let response = self.allocate_response(desired_size, sense);
         let rect = self
@@ -764,6 +899,7 @@ impl Ui {
     /// Allocate at least as much space as needed, and interact with that rect.
     ///
     /// The returned [`Rect`] will be the same size as `Response::rect`.
+    #[track_caller]
     pub fn allocate_at_least(&mut self, desired_size: Vec2, sense: Sense) -> (Rect, Response) {
         let response = self.allocate_response(desired_size, sense);
         (response.rect, response)
@@ -855,6 +991,7 @@ impl Ui {
     ///
     /// Ignore the layout of the [`Ui`]: just put my widget here!
     /// The layout cursor will advance to past this `rect`.
+    #[track_caller]
     pub fn allocate_rect(&mut self, rect: Rect, sense: Sense) -> Response {
         register_rect(self, rect);
         let id = self.advance_cursor_after_rect(rect);
@@ -1087,6 +1224,53 @@ impl Ui {
         self.ctx()
             .input_mut(|input| input.smooth_scroll_delta += delta);
     }
+
+    /// Smoothly scroll all [`ScrollArea`]s containing the widget with the given id
+    /// so that it becomes visible, e.g. to jump to an anchor in a document.
+    ///
+    /// The widget can be added before or after this call,
+    /// but must have been shown this frame or the previous one.
+    /// Returns `false` if there was no such widget.
+    ///
+    /// See also: [`Response::scroll_to_me_animated`].
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// let anchor = egui::Id::new("chapter_2");
+    /// if ui.button("Go to chapter 2").clicked() {
+    ///     ui.scroll_to_id(anchor);
+    /// }
+    /// egui::ScrollArea::vertical().show(ui, |ui| {
+    ///     for chapter in 1..=3 {
+    ///         let heading = ui.heading(format!("Chapter {chapter}"));
+    ///         if chapter == 2 {
+    ///             ui.interact(heading.rect, anchor, egui::Sense::hover());
+    ///         }
+    ///         for paragraph in 0..20 {
+    ///             ui.label(format!("Paragraph {paragraph}"));
+    ///         }
+    ///     }
+    /// });
+    /// # });
+    /// ```
+    pub fn scroll_to_id(&self, id: Id) -> bool {
+        /// How long it takes to scroll to the widget, in seconds.
+        const SCROLL_TO_ID_DURATION: f32 = 0.3;
+
+        let rect = self
+            .ctx()
+            .widget_rects_this_frame(|widgets| widgets.get(id).map(|widget| widget.rect))
+            .or_else(|| {
+                self.ctx()
+                    .widget_rects_prev_frame(|widgets| widgets.get(id).map(|widget| widget.rect))
+            });
+        if let Some(rect) = rect {
+            self.ctx().frame_state_mut(|state| {
+                state.animated_scroll_target = Some((rect, None, SCROLL_TO_ID_DURATION));
+            });
+        }
+        rect.is_some()
+    }
 }
 
 /// # Adding widgets
@@ -1106,7 +1290,10 @@ impl Ui {
     /// # });
     /// ```
     #[inline]
+    #[track_caller]
     pub fn add(&mut self, widget: impl Widget) -> Response {
+        #[cfg(debug_assertions)]
+        crate::trace_span!("egui::widget", widget = type_name_of(&widget));
         widget.ui(self)
     }
 
@@ -1156,6 +1343,7 @@ impl Ui {
     /// ui.add_enabled(false, egui::Button::new("Can't click this"));
     /// # });
     /// ```
+    #[track_caller]
     pub fn add_enabled(&mut self, enabled: bool, widget: impl Widget) -> Response {
         if self.is_enabled() && !enabled {
             let old_painter = self.painter.clone();
@@ -1174,7 +1362,8 @@ impl Ui {
     /// If you call `add_enabled_ui` from within an already disabled [`Ui`],
     /// the result will always be disabled, even if the `enabled` argument is true.
     ///
-    /// See also [`Self::add_enabled`] and [`Self::is_enabled`].
+    /// See also [`Self::add_enabled`], [`Self::is_enabled`],
+    /// and [`Self::gate`] for telling the user why it is disabled.
     ///
     /// ### Example
     /// ```
@@ -1213,6 +1402,7 @@ impl Ui {
     /// ui.add_visible(false, egui::Label::new("You won't see me!"));
     /// # });
     /// ```
+    #[track_caller]
     pub fn add_visible(&mut self, visible: bool, widget: impl Widget) -> Response {
         if self.is_visible() && !visible {
             // temporary make us invisible:
@@ -1261,6 +1451,45 @@ impl Ui {
         })
     }
 
+    /// Add a section that is enabled, disabled, read-only or hidden depending on the [`Gate`].
+    ///
+    /// If the gate has a [reason](Gate::reason), it is shown when the user hovers the section,
+    /// so that they know why they can't use it.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// # let is_admin = false;
+    /// ui.gate(egui::Gate::enabled_or(is_admin, "Needs admin"), |ui| {
+    ///     if ui.button("Delete user").clicked() {
+    ///         /* … */
+    ///     }
+    /// });
+    /// # });
+    /// ```
+    pub fn gate<R>(
+        &mut self,
+        gate: Gate,
+        add_contents: impl FnOnce(&mut Ui) -> R,
+    ) -> InnerResponse<R> {
+        let inner_response = self.scope(|ui| {
+            match &gate {
+                Gate::Enabled => {}
+                Gate::Disabled { .. } => ui.set_enabled(false),
+                Gate::ReadOnly { .. } => ui.set_read_only(true),
+                Gate::Hidden => ui.set_visible(false),
+            }
+            add_contents(ui)
+        });
+
+        if let Some(reason) = gate.reason() {
+            let mut response = inner_response.response.clone();
+            response.enabled = false;
+            response.on_disabled_hover_text(reason.clone());
+        }
+
+        inner_response
+    }
+
     /// Add extra space before the next widget.
     ///
     /// The direction is dependent on the layout.
@@ -1288,6 +1517,7 @@ impl Ui {
     /// # });
     /// ```
     #[inline]
+    #[track_caller]
     pub fn label(&mut self, text: impl Into<WidgetText>) -> Response {
         Label::new(text).ui(self)
     }
@@ -1295,6 +1525,7 @@ impl Ui {
     /// Show colored text.
     ///
     /// Shortcut for `ui.label(RichText::new(text).color(color))`
+    #[track_caller]
     pub fn colored_label(
         &mut self,
         color: impl Into<Color32>,
@@ -1306,6 +1537,7 @@ impl Ui {
     /// Show large text.
     ///
     /// Shortcut for `ui.label(RichText::new(text).heading())`
+    #[track_caller]
     pub fn heading(&mut self, text: impl Into<RichText>) -> Response {
         Label::new(text.into().heading()).ui(self)
     }
@@ -1313,6 +1545,7 @@ impl Ui {
     /// Show monospace (fixed width) text.
     ///
     /// Shortcut for `ui.label(RichText::new(text).monospace())`
+    #[track_caller]
     pub fn monospace(&mut self, text: impl Into<RichText>) -> Response {
         Label::new(text.into().monospace()).ui(self)
     }
@@ -1320,6 +1553,7 @@ impl Ui {
     /// Show text as monospace with a gray background.
     ///
     /// Shortcut for `ui.label(RichText::new(text).code())`
+    #[track_caller]
     pub fn code(&mut self, text: impl Into<RichText>) -> Response {
         Label::new(text.into().code()).ui(self)
     }
@@ -1327,6 +1561,7 @@ impl Ui {
     /// Show small text.
     ///
     /// Shortcut for `ui.label(RichText::new(text).small())`
+    #[track_caller]
     pub fn small(&mut self, text: impl Into<RichText>) -> Response {
         Label::new(text.into().small()).ui(self)
     }
@@ -1334,6 +1569,7 @@ impl Ui {
     /// Show text that stand out a bit (e.g. slightly brighter).
     ///
     /// Shortcut for `ui.label(RichText::new(text).strong())`
+    #[track_caller]
     pub fn strong(&mut self, text: impl Into<RichText>) -> Response {
         Label::new(text.into().strong()).ui(self)
     }
@@ -1341,6 +1577,7 @@ impl Ui {
     /// Show text that is weaker (fainter color).
     ///
     /// Shortcut for `ui.label(RichText::new(text).weak())`
+    #[track_caller]
     pub fn weak(&mut self, text: impl Into<RichText>) -> Response {
         Label::new(text.into().weak()).ui(self)
     }
@@ -1359,6 +1596,7 @@ impl Ui {
     ///
     /// See also [`Link`].
     #[must_use = "You should check if the user clicked this with `if ui.link(…).clicked() { … } "]
+    #[track_caller]
     pub fn link(&mut self, text: impl Into<WidgetText>) -> Response {
         Link::new(text).ui(self)
     }
@@ -1374,6 +1612,7 @@ impl Ui {
     /// ```
     ///
     /// See also [`Hyperlink`].
+    #[track_caller]
     pub fn hyperlink(&mut self, url: impl ToString) -> Response {
         Hyperlink::new(url).ui(self)
     }
@@ -1387,6 +1626,7 @@ impl Ui {
     /// ```
     ///
     /// See also [`Hyperlink`].
+    #[track_caller]
     pub fn hyperlink_to(&mut self, label: impl Into<WidgetText>, url: impl ToString) -> Response {
         Hyperlink::from_label_and_url(label, url).ui(self)
     }
@@ -1394,6 +1634,7 @@ impl Ui {
     /// No newlines (`\n`) allowed. Pressing enter key will result in the [`TextEdit`] losing focus (`response.lost_focus`).
     ///
     /// See also [`TextEdit`].
+    #[track_caller]
     pub fn text_edit_singleline<S: widgets::text_edit::TextBuffer>(
         &mut self,
         text: &mut S,
@@ -1404,6 +1645,7 @@ impl Ui {
     /// A [`TextEdit`] for multiple lines. Pressing enter key will create a new line.
     ///
     /// See also [`TextEdit`].
+    #[track_caller]
     pub fn text_edit_multiline<S: widgets::text_edit::TextBuffer>(
         &mut self,
         text: &mut S,
@@ -1416,6 +1658,7 @@ impl Ui {
     /// This will be multiline, monospace, and will insert tabs instead of moving focus.
     ///
     /// See also [`TextEdit::code_editor`].
+    #[track_caller]
     pub fn code_editor<S: widgets::text_edit::TextBuffer>(&mut self, text: &mut S) -> Response {
         self.add(TextEdit::multiline(text).code_editor())
     }
@@ -1440,6 +1683,7 @@ impl Ui {
     /// ```
     #[must_use = "You should check if the user clicked this with `if ui.button(…).clicked() { … } "]
     #[inline]
+    #[track_caller]
     pub fn button(&mut self, text: impl Into<WidgetText>) -> Response {
         Button::new(text).ui(self)
     }
@@ -1450,6 +1694,7 @@ impl Ui {
     ///
     /// Shortcut for `add(Button::new(text).small())`
     #[must_use = "You should check if the user clicked this with `if ui.small_button(…).clicked() { … } "]
+    #[track_caller]
     pub fn small_button(&mut self, text: impl Into<WidgetText>) -> Response {
         Button::new(text).small().ui(self)
     }
@@ -1458,15 +1703,24 @@ impl Ui {
     ///
     /// See also [`Self::toggle_value`].
     #[inline]
+    #[track_caller]
     pub fn checkbox(&mut self, checked: &mut bool, text: impl Into<WidgetText>) -> Response {
         Checkbox::new(checked, text).ui(self)
     }
 
+    /// Show a [`ToggleSwitch`], which works just like a checkbox.
+    #[inline]
+    #[track_caller]
+    pub fn toggle_switch(&mut self, on: &mut bool, text: impl Into<WidgetText>) -> Response {
+        ToggleSwitch::new(on, text).ui(self)
+    }
+
     /// Acts like a checkbox, but looks like a [`SelectableLabel`].
     ///
     /// Click to toggle to bool.
     ///
     /// See also [`Self::checkbox`].
+    #[track_caller]
     pub fn toggle_value(&mut self, selected: &mut bool, text: impl Into<WidgetText>) -> Response {
         let mut response = self.selectable_label(*selected, text);
         if response.clicked() {
@@ -1480,6 +1734,7 @@ impl Ui {
     /// Often you want to use [`Self::radio_value`] instead.
     #[must_use = "You should check if the user clicked this with `if ui.radio(…).clicked() { … } "]
     #[inline]
+    #[track_caller]
     pub fn radio(&mut self, selected: bool, text: impl Into<WidgetText>) -> Response {
         RadioButton::new(selected, text).ui(self)
     }
@@ -1503,6 +1758,7 @@ impl Ui {
     /// }
     /// # });
     /// ```
+    #[track_caller]
     pub fn radio_value<Value: PartialEq>(
         &mut self,
         current_value: &mut Value,
@@ -1521,6 +1777,7 @@ impl Ui {
     ///
     /// See also [`SelectableLabel`] and [`Self::toggle_value`].
     #[must_use = "You should check if the user clicked this with `if ui.selectable_label(…).clicked() { … } "]
+    #[track_caller]
     pub fn selectable_label(&mut self, checked: bool, text: impl Into<WidgetText>) -> Response {
         SelectableLabel::new(checked, text).ui(self)
     }
@@ -1531,6 +1788,7 @@ impl Ui {
     /// Example: `ui.selectable_value(&mut my_enum, Enum::Alternative, "Alternative")`.
     ///
     /// See also [`SelectableLabel`] and [`Self::toggle_value`].
+    #[track_caller]
     pub fn selectable_value<Value: PartialEq>(
         &mut self,
         current_value: &mut Value,
@@ -1549,6 +1807,7 @@ impl Ui {
     ///
     /// See also [`Separator`].
     #[inline]
+    #[track_caller]
     pub fn separator(&mut self) -> Response {
         Separator::default().ui(self)
     }
@@ -1557,12 +1816,14 @@ impl Ui {
     ///
     /// See also [`Spinner`].
     #[inline]
+    #[track_caller]
     pub fn spinner(&mut self) -> Response {
         Spinner::new().ui(self)
     }
 
     /// Modify an angle. The given angle should be in radians, but is shown to the user in degrees.
     /// The angle is NOT wrapped, so the user may select, for instance 720° = 2𝞃 = 4π
+    #[track_caller]
     pub fn drag_angle(&mut self, radians: &mut f32) -> Response {
         let mut degrees = radians.to_degrees();
         let mut response = self.add(DragValue::new(&mut degrees).speed(1.0).suffix("°"));
@@ -1579,6 +1840,7 @@ impl Ui {
     /// Modify an angle. The given angle should be in radians,
     /// but is shown to the user in fractions of one Tau (i.e. fractions of one turn).
     /// The angle is NOT wrapped, so the user may select, for instance 2𝞃 (720°)
+    #[track_caller]
     pub fn drag_angle_tau(&mut self, radians: &mut f32) -> Response {
         use std::f32::consts::TAU;
 
@@ -1626,6 +1888,7 @@ impl Ui {
     ///
     /// See also [`crate::Image`], [`crate::ImageSource`].
     #[inline]
+    #[track_caller]
     pub fn image<'a>(&mut self, source: impl Into<ImageSource<'a>>) -> Response {
         Image::new(source).ui(self)
     }
@@ -1735,6 +1998,50 @@ impl Ui {
         crate::Frame::group(self.style()).show(self, add_contents)
     }
 
+    /// Show the contents in a [`Self::group`] with a "Don't show this again" button,
+    /// until the user clicks that button.
+    ///
+    /// The dismissal is remembered across sessions (with the `persistence` feature),
+    /// which makes this useful for tips, banners and migration notices.
+    /// Use [`Context::set_dismissed`] to show it again.
+    ///
+    /// The `id_source` is used as is, so it must be globally unique.
+    ///
+    /// Returns `None` once dismissed.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// ui.dismissible("tip_drag_to_pan", |ui| {
+    ///     ui.label("Tip: drag with the middle mouse button to pan.");
+    /// });
+    /// # });
+    /// ```
+    pub fn dismissible<R>(
+        &mut self,
+        id_source: impl Hash,
+        add_contents: impl FnOnce(&mut Ui) -> R,
+    ) -> Option<InnerResponse<R>> {
+        if self.ctx().is_dismissed(&id_source) {
+            return None;
+        }
+
+        let response = self.group(|ui| {
+            ui.horizontal_top(|ui| {
+                let inner = ui.vertical(add_contents).inner;
+                if ui
+                    .add(Button::new("🗙").frame(false))
+                    .on_hover_text("Don't show this again")
+                    .clicked()
+                {
+                    ui.ctx().set_dismissed(&id_source, true);
+                }
+                inner
+            })
+            .inner
+        });
+        Some(response)
+    }
+
     /// Create a child Ui with an explicit [`Id`].
     ///
     /// ```
@@ -1786,6 +2093,80 @@ impl Ui {
         InnerResponse::new(ret, response)
     }
 
+    /// How much space the contents would take up, without adding them.
+    ///
+    /// The contents are laid out like they would be in a child [`Ui`] at the cursor,
+    /// but nothing is painted, no widget is interacted with or checked for id clashes,
+    /// and this [`Ui`] is left unchanged.
+    /// Use this to decide where to place the contents before adding them for real.
+    ///
+    /// Widgets still read and write their state in [`crate::Memory`],
+    /// which custom widgets can avoid by checking [`Self::is_measuring`].
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// let add_contents = |ui: &mut egui::Ui| {
+    ///     ui.label("Centered");
+    ///     ui.button("Click me");
+    /// };
+    /// let size = ui.measure(add_contents);
+    /// let rect = egui::Align2::CENTER_TOP.align_size_within_rect(size, ui.available_rect_before_wrap());
+    /// ui.allocate_ui_at_rect(rect, add_contents);
+    /// # });
+    /// ```
+    pub fn measure(&self, add_contents: impl FnOnce(&mut Ui)) -> Vec2 {
+        let mut measuring_ui = self.measuring_ui(*self.layout());
+        add_contents(&mut measuring_ui);
+        measuring_ui.min_size()
+    }
+
+    /// Add the contents with every widget at least as wide as the widest one,
+    /// e.g. to make all the buttons in a menu or all the rows of a form equally wide.
+    ///
+    /// The widgets are measured first, so `add_contents` is called twice:
+    /// first in an invisible [`Ui`] that doesn't interact (see [`Self::measure`] and [`Self::is_measuring`]),
+    /// and then for real.
+    /// The contents are laid out in the current layout.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// ui.measure_pass(|ui| {
+    ///     if ui.button("Open").clicked() {
+    ///         // …
+    ///     }
+    ///     if ui.button("Open recent project").clicked() {
+    ///         // …
+    ///     }
+    /// });
+    /// # });
+    /// ```
+    ///
+    /// See also [`Self::horizontal_equal_width`] and [`Self::measure`].
+    pub fn measure_pass<R>(
+        &mut self,
+        mut add_contents: impl FnMut(&mut Ui) -> R,
+    ) -> InnerResponse<R> {
+        let item_widths = self.measure_item_widths(*self.layout(), &mut add_contents);
+        let widest = item_widths.into_iter().fold(0.0, f32::max);
+
+        self.scope(|ui| {
+            ui.placer.set_item_distribution(widest, 0.0);
+            add_contents(ui)
+        })
+    }
+
+    /// Lay out the contents invisibly, and return the width of each widget.
+    fn measure_item_widths<R>(
+        &self,
+        layout: Layout,
+        add_contents: &mut dyn FnMut(&mut Ui) -> R,
+    ) -> Vec<f32> {
+        let mut measure_ui = self.measuring_ui(layout);
+        measure_ui.placer.measure_item_widths();
+        add_contents(&mut measure_ui);
+        measure_ui.placer.take_item_widths()
+    }
+
     /// Redirect shapes to another paint layer.
     pub fn with_layer_id<R>(
         &mut self,
@@ -1971,6 +2352,97 @@ impl Ui {
         self.allocate_ui_with_layout_dyn(initial_size, layout, add_contents)
     }
 
+    /// Like [`Self::horizontal`], but spreads out the widgets over the full available width,
+    /// with equal space between them.
+    ///
+    /// The widgets are measured first, so `add_contents` is called twice:
+    /// first in an invisible [`Ui`] that doesn't interact (see [`Self::is_measuring`]), and then for real.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// ui.horizontal_justified(|ui| {
+    ///     ui.label("Left");
+    ///     ui.label("Middle");
+    ///     ui.label("Right");
+    /// });
+    /// # });
+    /// ```
+    ///
+    /// See also [`Self::horizontal_equal_width`].
+    pub fn horizontal_justified<R>(
+        &mut self,
+        add_contents: impl FnMut(&mut Ui) -> R,
+    ) -> InnerResponse<R> {
+        self.horizontal_distributed_dyn(false, Box::new(add_contents))
+    }
+
+    /// Like [`Self::horizontal`], but makes all widgets as wide as the widest one,
+    /// e.g. for the buttons of a dialog or a toolbar.
+    ///
+    /// The widgets are measured first, so `add_contents` is called twice:
+    /// first in an invisible [`Ui`] that doesn't interact (see [`Self::is_measuring`]), and then for real.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// ui.horizontal_equal_width(|ui| {
+    ///     if ui.button("OK").clicked() {
+    ///         // …
+    ///     }
+    ///     if ui.button("Cancel").clicked() {
+    ///         // …
+    ///     }
+    /// });
+    /// # });
+    /// ```
+    pub fn horizontal_equal_width<R>(
+        &mut self,
+        add_contents: impl FnMut(&mut Ui) -> R,
+    ) -> InnerResponse<R> {
+        self.horizontal_distributed_dyn(true, Box::new(add_contents))
+    }
+
+    fn horizontal_distributed_dyn<'c, R>(
+        &mut self,
+        equal_width: bool,
+        mut add_contents: Box<dyn FnMut(&mut Ui) -> R + 'c>,
+    ) -> InnerResponse<R> {
+        let layout = if self.placer.prefer_right_to_left() {
+            Layout::right_to_left(Align::Center)
+        } else {
+            Layout::left_to_right(Align::Center)
+        };
+
+        // First pass: measure the widgets.
+        let item_widths = self.measure_item_widths(layout, &mut add_contents);
+
+        let num_items = item_widths.len();
+        let (min_item_width, extra_item_spacing) = if equal_width {
+            (item_widths.iter().copied().fold(0.0, f32::max), 0.0)
+        } else if 1 < num_items {
+            let used_width = item_widths.iter().sum::<f32>()
+                + (num_items - 1) as f32 * self.spacing().item_spacing.x;
+            let free_width = self.available_size_before_wrap().x - used_width;
+            (0.0, (free_width / (num_items - 1) as f32).at_least(0.0))
+        } else {
+            (0.0, 0.0)
+        };
+
+        // Second pass: place them.
+        let initial_size = vec2(
+            self.available_size_before_wrap().x,
+            self.spacing().interact_size.y,
+        );
+        self.allocate_ui_with_layout_dyn(
+            initial_size,
+            layout,
+            Box::new(|ui: &mut Ui| {
+                ui.placer
+                    .set_item_distribution(min_item_width, extra_item_spacing);
+                add_contents(ui)
+            }),
+        )
+    }
+
     /// Start a ui with vertical layout.
     /// Widgets will be left-justified.
     ///
@@ -2087,6 +2559,10 @@ impl Ui {
         self.placer.save_grid();
     }
 
+    pub(crate) fn grid_mut(&mut self) -> Option<&mut grid::GridLayout> {
+        self.placer.grid_mut()
+    }
+
     pub(crate) fn is_grid(&self) -> bool {
         self.placer.is_grid()
     }
@@ -2201,6 +2677,26 @@ impl Ui {
                 let delta = pointer_pos - response.rect.center();
                 self.ctx()
                     .transform_layer_shapes(layer_id, emath::TSTransform::from_translation(delta));
+
+                if !self.ctx().embed_viewports() {
+                    // Remember what we painted, so it can be shown in other viewports too:
+                    let to_pointer = emath::TSTransform::from_translation(-pointer_pos.to_vec2());
+                    let ghost = self.ctx().graphics(|g| {
+                        g.get(layer_id).map_or_else(Vec::new, |list| {
+                            list.all_entries()
+                                .map(|clipped| {
+                                    let mut shape = clipped.shape.clone();
+                                    shape.transform(to_pointer);
+                                    epaint::ClippedShape {
+                                        clip_rect: to_pointer * clipped.clip_rect,
+                                        shape,
+                                    }
+                                })
+                                .collect()
+                        })
+                    });
+                    crate::DragAndDrop::set_ghost(self.ctx(), ghost);
+                }
             }
 
             InnerResponse::new(inner, response)
@@ -2239,11 +2735,11 @@ impl Ui {
         let inner = add_contents(&mut frame.content_ui);
         let response = frame.allocate_space(self);
 
-        // NOTE: we use `response.contains_pointer` here instead of `hovered`, because
+        // NOTE: we use `response.dnd_contains_pointer` here instead of `hovered`, because
         // `hovered` is always false when another widget is being dragged.
         let style = if is_anything_being_dragged
             && can_accept_what_is_being_dragged
-            && response.contains_pointer()
+            && response.dnd_contains_pointer()
         {
             self.visuals().widgets.active
         } else {
@@ -2269,6 +2765,62 @@ impl Ui {
         (InnerResponse { inner, response }, payload)
     }
 
+    /// Show a popup below the `anchor` widget, if it is open.
+    ///
+    /// Open it with [`Memory::open_popup`] or [`Memory::toggle_popup`].
+    /// Popups opened from within the popup become its children, and stay open with it.
+    ///
+    /// The popup closes when the user clicks outside it or presses Escape.
+    /// Use [`Self::popup_with_options`] to change that, or to make it modal.
+    ///
+    /// Returns `None` if the popup is closed.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// let popup_id = ui.make_persistent_id("settings_popup");
+    /// let response = ui.button("Settings");
+    /// if response.clicked() {
+    ///     ui.memory_mut(|mem| mem.toggle_popup(popup_id));
+    /// }
+    /// ui.popup(popup_id, &response, |ui| {
+    ///     ui.label("Change some settings here");
+    /// });
+    /// # });
+    /// ```
+    pub fn popup<R>(
+        &self,
+        popup_id: Id,
+        anchor: &Response,
+        add_contents: impl FnOnce(&mut Ui) -> R,
+    ) -> Option<InnerResponse<R>> {
+        self.popup_with_options(popup_id, anchor, Default::default(), add_contents)
+    }
+
+    /// Like [`Self::popup`], but with control over when it closes, and if it is modal.
+    pub fn popup_with_options<R>(
+        &self,
+        popup_id: Id,
+        anchor: &Response,
+        options: PopupOptions,
+        add_contents: impl FnOnce(&mut Ui) -> R,
+    ) -> Option<InnerResponse<R>> {
+        let area = Area::new(popup_id)
+            .order(Order::Foreground)
+            .constrain(true)
+            .fixed_pos(anchor.rect.left_bottom())
+            .pivot(Align2::LEFT_TOP)
+            .escape_viewport(self.ctx().options(|o| o.popups_escape_viewport));
+
+        crate::popup::show_popup_area(self, popup_id, anchor, options, area, |ui| {
+            Frame::popup(ui.style())
+                .show(ui, |ui| {
+                    ui.set_min_width(anchor.rect.width());
+                    add_contents(ui)
+                })
+                .inner
+        })
+    }
+
     /// Close the menu we are in (including submenus), if any.
     ///
     /// See also: [`Self::menu_button`] and [`Response::context_menu`].
@@ -2358,7 +2910,9 @@ impl Ui {
 #[cfg(debug_assertions)]
 impl Drop for Ui {
     fn drop(&mut self) {
-        register_rect(self, self.min_rect());
+        if !self.measuring {
+            register_rect(self, self.min_rect());
+        }
     }
 }
 
@@ -2465,8 +3019,253 @@ fn register_rect(ui: &Ui, rect: Rect) {
 #[cfg(not(debug_assertions))]
 fn register_rect(_ui: &Ui, _rect: Rect) {}
 
+#[test]
+fn test_horizontal_justified_and_equal_width() {
+    use crate::test_harness::Harness;
+    use std::cell::RefCell;
+
+    let available = RefCell::new(Rect::NOTHING);
+    let justified = RefCell::new(Vec::new());
+    let equal_width = RefCell::new(Vec::new());
+    let natural_width = RefCell::new(0.0);
+
+    let _harness = Harness::new_ui(|ui| {
+        *available.borrow_mut() = ui.available_rect_before_wrap();
+        ui.horizontal_justified(|ui| {
+            let rects: Vec<Rect> = ["a", "bb", "ccc"].map(|text| ui.button(text).rect).into();
+            if !ui.is_measuring() {
+                *justified.borrow_mut() = rects;
+            }
+        });
+        ui.horizontal_equal_width(|ui| {
+            let rects: Vec<Rect> = ["OK", "Cancel"].map(|text| ui.button(text).rect).into();
+            if !ui.is_measuring() {
+                *equal_width.borrow_mut() = rects;
+            }
+        });
+        *natural_width.borrow_mut() = ui.button("Cancel").rect.width();
+    });
+
+    let available = *available.borrow();
+    let justified = justified.borrow();
+    assert_eq!(justified.len(), 3);
+    assert_eq!(justified[0].left(), available.left());
+    assert!((justified[2].right() - available.right()).abs() < 0.5);
+    let gaps = [
+        justified[1].left() - justified[0].right(),
+        justified[2].left() - justified[1].right(),
+    ];
+    assert!((gaps[0] - gaps[1]).abs() < 0.5);
+
+    let equal_width = equal_width.borrow();
+    assert_eq!(equal_width.len(), 2);
+    assert!((equal_width[0].width() - *natural_width.borrow()).abs() < 0.01);
+    assert!((equal_width[1].width() - *natural_width.borrow()).abs() < 0.01);
+}
+
+#[test]
+fn test_measure() {
+    use crate::test_harness::Harness;
+    use std::cell::RefCell;
+
+    let sizes = RefCell::new((Vec2::ZERO, Vec2::ZERO));
+    let harness = Harness::new_ui(|ui| {
+        let add_contents = |ui: &mut Ui| {
+            ui.label("Measure me");
+            let _ = ui.button("And me");
+        };
+        let cursor = ui.cursor();
+        let measured = ui.measure(|ui| {
+            add_contents(ui);
+            assert!(ui.is_measuring());
+            ui.interact(ui.min_rect(), Id::new("measured"), Sense::click());
+        });
+        assert_eq!(ui.cursor(), cursor);
+        let added = ui.scope(add_contents).response.rect.size();
+        *sizes.borrow_mut() = (measured, added);
+    });
+
+    let (measured, added) = *sizes.borrow();
+    assert_eq!(measured, added);
+    assert!(harness.ctx().read_response(Id::new("measured")).is_none());
+}
+
+/// For the span around [`Ui::add`].
+#[cfg(all(feature = "tracing", debug_assertions))]
+fn type_name_of<T>(_: &T) -> &'static str {
+    std::any::type_name::<T>()
+}
+
+#[test]
+fn test_dismissible() {
+    use crate::test_harness::Harness;
+
+    let mut harness = Harness::new_ui(|ui| {
+        ui.dismissible("tip", |ui| {
+            ui.label("A helpful tip");
+        });
+    });
+    assert!(harness.query_by_label("A helpful tip").is_some());
+
+    harness.get_by_label("🗙").click();
+    assert!(harness.query_by_label("A helpful tip").is_none());
+    assert!(harness.ctx().is_dismissed("tip"));
+
+    #[cfg(feature = "persistence")]
+    {
+        // The dismissal survives a save and restore of the memory:
+        let ron = harness.ctx().memory(|mem| ron::to_string(mem).unwrap());
+        let ctx = Context::default();
+        ctx.memory_mut(|mem| *mem = ron::from_str(&ron).unwrap());
+        assert!(ctx.is_dismissed("tip"));
+    }
+
+    harness.ctx().set_dismissed("tip", false);
+    harness.step();
+    assert!(harness.query_by_label("A helpful tip").is_some());
+}
+
+#[test]
+fn test_measure_pass() {
+    use crate::test_harness::Harness;
+
+    let harness = Harness::new_ui(|ui| {
+        ui.measure_pass(|ui| {
+            let _ = ui.button("A");
+            ui.horizontal(|ui| {
+                ui.label("A much wider row");
+                let _ = ui.button("B");
+            });
+            let _ = ui.button("C");
+        });
+    });
+
+    let rect = |label: &str| harness.query_by_label(label).unwrap().rect.rect;
+    let row_width = rect("B").right() - rect("A much wider row").left();
+    assert!((rect("A").width() - row_width).abs() < 0.5);
+    assert_eq!(rect("A").width(), rect("C").width());
+}
+
 #[test]
 fn ui_impl_send_sync() {
     fn assert_send_sync<T: Send + Sync>() {}
     assert_send_sync::<Ui>();
 }
+
+#[test]
+fn test_breakpoints() {
+    use crate::test_harness::Harness;
+    use std::cell::Cell;
+
+    let breakpoint = Cell::new(Breakpoint::Small);
+    let mut harness = Harness::new_ui(|ui| {
+        breakpoint.set(ui.breakpoint());
+        ui.show_when(Breakpoint::Small, |ui| ui.label("Small"));
+        ui.show_from(Breakpoint::Medium, |ui| ui.label("Medium or larger"));
+    });
+
+    harness.set_size(vec2(400.0, 600.0));
+    assert_eq!(breakpoint.get(), Breakpoint::Small);
+    assert!(harness.query_by_label("Small").is_some());
+    assert!(harness.query_by_label("Medium or larger").is_none());
+
+    harness.set_size(vec2(800.0, 600.0));
+    assert_eq!(breakpoint.get(), Breakpoint::Medium);
+    assert!(harness.query_by_label("Small").is_none());
+    assert!(harness.query_by_label("Medium or larger").is_some());
+
+    harness.set_size(vec2(1200.0, 600.0));
+    assert_eq!(breakpoint.get(), Breakpoint::Large);
+    assert!(harness.query_by_label("Medium or larger").is_some());
+
+    // The thresholds are part of the style:
+    harness
+        .ctx()
+        .style_mut(|style| style.breakpoints.large = 2000.0);
+    harness.run();
+    assert_eq!(breakpoint.get(), Breakpoint::Medium);
+}
+
+#[test]
+fn test_gate() {
+    use crate::test_harness::Harness;
+    use std::cell::Cell;
+
+    let gate = std::cell::RefCell::new(Gate::disabled_with_reason("Needs admin"));
+    let clicks = Cell::new(0);
+    let mut text = String::from("Ferris");
+    let read_only = Cell::new(false);
+    let mut harness = Harness::new_ui(|ui| {
+        ui.gate(gate.borrow().clone(), |ui| {
+            read_only.set(ui.is_read_only());
+            if ui.button("Delete").clicked() {
+                clicks.set(clicks.get() + 1);
+            }
+            ui.text_edit_singleline(&mut text);
+        });
+        ui.label("Below");
+    });
+
+    // Disabled with a reason that shows on hover:
+    harness.get_by_label("Delete").click();
+    for _ in 0..30 {
+        harness.step();
+    }
+    assert_eq!(clicks.get(), 0);
+    assert!(!read_only.get());
+    assert!(harness.query_by_label("Needs admin").is_some());
+
+    // Read-only widgets show their values, but can't be edited:
+    *gate.borrow_mut() = Gate::read_only();
+    harness.get_by_label("Below").hover();
+    harness.run();
+    assert!(read_only.get());
+    assert!(harness.query_by_label("Needs admin").is_none());
+    harness
+        .get_by(|info| info.current_text_value.as_deref() == Some("Ferris"))
+        .click();
+    harness.type_text("!");
+    harness.run();
+    assert_eq!(clicks.get(), 0);
+
+    // Enabled again:
+    *gate.borrow_mut() = Gate::enabled_or(true, "Needs admin");
+    harness.run();
+    harness.get_by_label("Delete").click();
+    harness.run();
+    assert_eq!(clicks.get(), 1);
+
+    drop(harness);
+    assert_eq!(text, "Ferris");
+}
+
+#[test]
+fn test_tooltip_for_clipped_text() {
+    use crate::test_harness::Harness;
+    use std::cell::Cell;
+
+    let enabled = Cell::new(true);
+    let mut harness = Harness::new_ui(|ui| {
+        ui.style_mut().interaction.tooltip_for_clipped_text = enabled.get();
+        let clip_rect = Rect::from_min_size(ui.min_rect().min, vec2(60.0, 100.0));
+        ui.set_clip_rect(clip_rect);
+        let _ = ui.button("OK");
+        let _ = ui.button("Much too long to fit");
+    });
+    let is_open = |harness: &mut Harness<'_>, label: &str| {
+        let rect = harness.get_by_label(label).node().rect;
+        harness.hover(rect.interact_rect.left_center() + vec2(5.0, 0.0));
+        for _ in 0..30 {
+            harness.step();
+        }
+        crate::popup::was_tooltip_open_last_frame(harness.ctx(), rect.id.with("__tooltip"))
+    };
+
+    assert!(!is_open(&mut harness, "OK"));
+    assert!(is_open(&mut harness, "Much too long to fit"));
+
+    // It is opt-in:
+    enabled.set(false);
+    harness.hover(pos2(500.0, 500.0));
+    assert!(!is_open(&mut harness, "Much too long to fit"));
+}
@@ -76,7 +76,7 @@ use crate::{Context, Id};
 // ----------------------------------------------------------------------------
 
 /// The different types of viewports supported by egui.
-#[derive(Clone, Copy, Default, Hash, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum ViewportClass {
     /// The root viewport; i.e. the original window.
@@ -260,6 +260,7 @@ pub type ImmediateViewportRendererCallback = dyn for<'a> Fn(&Context, ImmediateV
 /// The default values are implementation defined, so you may want to explicitly
 /// configure the size of the window, and what buttons are shown.
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[allow(clippy::option_option)]
 pub struct ViewportBuilder {
     /// The title of the viewport.
@@ -275,6 +276,9 @@ pub struct ViewportBuilder {
     pub min_inner_size: Option<Vec2>,
     pub max_inner_size: Option<Vec2>,
 
+    /// See [`Self::with_inner_size_to_content`].
+    pub inner_size_to_content: Option<bool>,
+
     pub fullscreen: Option<bool>,
     pub maximized: Option<bool>,
     pub resizable: Option<bool>,
@@ -464,6 +468,19 @@ impl ViewportBuilder {
         self
     }
 
+    /// Keep resizing the window to fit its contents, like [`crate::Window::auto_sized`].
+    ///
+    /// At the end of each frame egui measures the contents (see [`crate::Context::content_size`]),
+    /// and sends a [`ViewportCommand::InnerSize`] whenever that changes.
+    /// Use [`Self::with_inner_size`] for the size of the first frame.
+    ///
+    /// The default is `false`.
+    #[inline]
+    pub fn with_inner_size_to_content(mut self, value: bool) -> Self {
+        self.inner_size_to_content = Some(value);
+        self
+    }
+
     /// Sets the minimum dimensions a window can have.
     ///
     /// If this is not set, the window will have no minimum dimensions (aside
@@ -594,6 +611,7 @@ impl ViewportBuilder {
             inner_size: new_inner_size,
             min_inner_size: new_min_inner_size,
             max_inner_size: new_max_inner_size,
+            inner_size_to_content: new_inner_size_to_content,
             fullscreen: new_fullscreen,
             maximized: new_maximized,
             resizable: new_resizable,
@@ -652,6 +670,11 @@ impl ViewportBuilder {
             }
         }
 
+        if new_inner_size_to_content.is_some() {
+            // Handled by egui at the end of each frame:
+            self.inner_size_to_content = new_inner_size_to_content;
+        }
+
         if let Some(new_fullscreen) = new_fullscreen {
             if Some(new_fullscreen) != self.fullscreen {
                 self.fullscreen = Some(new_fullscreen);
@@ -817,6 +840,27 @@ pub enum SystemTheme {
     Dark,
 }
 
+/// How often a viewport repaints, see [`ViewportCommand::RepaintPolicy`].
+///
+/// This lets e.g. a viewport with a realtime plot run at full speed,
+/// while a settings window only repaints when something happens.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum RepaintPolicy {
+    /// Repaint on input, and when asked to with [`crate::Context::request_repaint`].
+    #[default]
+    Reactive,
+
+    /// Repaint every frame, as fast as the integration can (usually at the refresh rate of the monitor).
+    Continuous,
+
+    /// Like [`Self::Reactive`], but repaint at most this many times per second.
+    ///
+    /// This limits [`crate::Context::request_repaint`] and animations,
+    /// but integrations will still repaint right away on input.
+    Throttled(u32),
+}
+
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum CursorGrab {
@@ -881,6 +925,14 @@ pub enum ViewportCommand {
     /// Should be bigger than 0
     InnerSize(Vec2),
 
+    /// Resize the viewport to fit its contents, as measured at the end of the frame
+    /// (see [`crate::Context::content_size`]).
+    ///
+    /// egui replaces this with [`Self::InnerSize`] before handing the commands to the integration.
+    /// If the viewport has no contents yet, the command waits until after a frame that shows some.
+    /// To keep fitting the contents as they change, use [`ViewportBuilder::with_inner_size_to_content`].
+    InnerSizeToContent,
+
     /// Should be bigger than 0
     MinInnerSize(Vec2),
 
@@ -965,6 +1017,13 @@ pub enum ViewportCommand {
     ///
     /// The results are returned in `crate::Event::Screenshot`.
     Screenshot,
+
+    /// Change how often this viewport repaints.
+    ///
+    /// egui applies this to the repaint delays itself,
+    /// and passes it on in [`crate::RequestRepaintInfo::policy`] for integrations that can do better,
+    /// e.g. by turning vsync on or off.
+    RepaintPolicy(RepaintPolicy),
 }
 
 impl ViewportCommand {
@@ -995,6 +1054,7 @@ impl ViewportCommand {
 /// This is returned by [`crate::Context::run`] on each frame, and should be applied
 /// by the integration.
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct ViewportOutput {
     /// Id of our parent viewport.
     pub parent: ViewportId,
@@ -1015,6 +1075,9 @@ pub struct ViewportOutput {
     /// The user-code that shows the GUI, used for deferred viewports.
     ///
     /// `None` for immediate viewports and the ROOT viewport.
+    ///
+    /// This is not serialized, since it is arbitrary code.
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub viewport_ui_cb: Option<Arc<DeferredViewportUiCallback>>,
 
     /// Commands to change the viewport, e.g. window title and size.
@@ -1050,6 +1113,64 @@ impl ViewportOutput {
     }
 }
 
+// ----------------------------------------------------------------------------
+
+/// A snapshot of all live viewports, used to restore a multi-window session on the next startup.
+///
+/// Create one with [`Context::save_session`] before shutting down,
+/// persist it (e.g. with `serde`), and hand it to [`Context::restore_session`] on startup.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ViewportSession {
+    /// All viewports that were alive when the session was saved, parents before children.
+    pub viewports: Vec<ViewportSessionEntry>,
+}
+
+impl ViewportSession {
+    /// Is there nothing to restore?
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.viewports.is_empty()
+    }
+
+    /// Find the entry for the given viewport, if any.
+    pub fn get(&self, id: ViewportId) -> Option<&ViewportSessionEntry> {
+        self.viewports.iter().find(|entry| entry.id == id)
+    }
+}
+
+/// One viewport in a [`ViewportSession`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ViewportSessionEntry {
+    /// The id of the viewport.
+    ///
+    /// The same id will be used when the viewport is restored.
+    pub id: ViewportId,
+
+    /// The parent of the viewport.
+    ///
+    /// For the root viewport this is [`ViewportId::ROOT`].
+    pub parent: ViewportId,
+
+    /// What type of viewport this was.
+    ///
+    /// Restored viewports are always recreated as [`ViewportClass::Deferred`].
+    pub class: ViewportClass,
+
+    /// The builder of the viewport, with [`ViewportBuilder::position`] and
+    /// [`ViewportBuilder::inner_size`] set to where the window was when saving.
+    pub builder: ViewportBuilder,
+
+    /// Application-specific state for this viewport, e.g. which document it was showing.
+    ///
+    /// egui never looks at this.
+    pub app_state: Vec<u8>,
+}
+
+/// The user-code that shows the ui of a viewport restored with [`Context::restore_session`].
+pub type RestoredViewportUiCallback = dyn Fn(&Context, ViewportClass) + Sync + Send;
+
 /// Viewport for immediate rendering.
 pub struct ImmediateViewport<'a> {
     /// Id of us and our parent.
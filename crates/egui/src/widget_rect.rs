@@ -39,13 +39,23 @@ pub struct WidgetRect {
 ///
 /// All [`Ui`]s have a [`WidgetRects`], but whether or not their rects are correct
 /// depends on if [`Ui::interact_bg`] was ever called.
-#[derive(Default, Clone, PartialEq, Eq)]
+#[derive(Default, Clone, PartialEq)]
 pub struct WidgetRects {
     /// All widgets, in painting order.
     by_layer: HashMap<LayerId, Vec<WidgetRect>>,
 
     /// All widgets, by id, and their order in their respective layer
     by_id: IdMap<(usize, WidgetRect)>,
+
+    /// Where in the code each widget was created.
+    ///
+    /// Only recorded while [`Context::devtools_ui`] is in use.
+    callsites: IdMap<&'static std::panic::Location<'static>>,
+
+    /// What each widget reported with [`Response::widget_info`].
+    ///
+    /// Only recorded while a [`crate::test_harness::Harness`] is driving the [`Context`].
+    infos: IdMap<WidgetInfo>,
 }
 
 impl WidgetRects {
@@ -70,26 +80,77 @@ impl WidgetRects {
         self.by_id.contains_key(&id)
     }
 
+    /// Where in the code was the widget with this id created?
+    ///
+    /// Only recorded while [`Context::devtools_ui`] is in use.
+    #[inline]
+    pub fn callsite(&self, id: Id) -> Option<&'static std::panic::Location<'static>> {
+        self.callsites.get(&id).copied()
+    }
+
+    /// What the widget with this id reported with [`Response::widget_info`].
+    ///
+    /// Only recorded while a [`crate::test_harness::Harness`] is driving the [`Context`].
+    #[inline]
+    pub fn info(&self, id: Id) -> Option<&WidgetInfo> {
+        self.infos.get(&id)
+    }
+
+    /// Total number of widgets.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.by_id.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.by_id.is_empty()
+    }
+
     /// All widgets in this layer, sorted back-to-front.
     #[inline]
     pub fn get_layer(&self, layer_id: LayerId) -> impl Iterator<Item = &WidgetRect> + '_ {
         self.by_layer.get(&layer_id).into_iter().flatten()
     }
 
+    /// All widgets in the given layers, back-to-front.
+    ///
+    /// Use [`Context::widget_layer_order`] to get the layers in the order they are painted.
+    pub fn iter_back_to_front<'a>(
+        &'a self,
+        layer_order: &'a [LayerId],
+    ) -> impl Iterator<Item = &'a WidgetRect> + 'a {
+        layer_order
+            .iter()
+            .flat_map(|&layer_id| self.get_layer(layer_id))
+    }
+
     /// Clear the contents while retaining allocated memory.
     pub fn clear(&mut self) {
-        let Self { by_layer, by_id } = self;
+        let Self {
+            by_layer,
+            by_id,
+            callsites,
+            infos,
+        } = self;
 
         for rects in by_layer.values_mut() {
             rects.clear();
         }
 
         by_id.clear();
+        callsites.clear();
+        infos.clear();
     }
 
     /// Insert the given widget rect in the given layer.
     pub fn insert(&mut self, layer_id: LayerId, widget_rect: WidgetRect) {
-        let Self { by_layer, by_id } = self;
+        let Self {
+            by_layer,
+            by_id,
+            callsites: _,
+            infos: _,
+        } = self;
 
         let layer_widgets = by_layer.entry(layer_id).or_default();
 
@@ -122,4 +183,18 @@ impl WidgetRects {
             }
         }
     }
+
+    /// Remember where the widget was created, unless we already know.
+    pub(crate) fn set_callsite(
+        &mut self,
+        id: Id,
+        callsite: &'static std::panic::Location<'static>,
+    ) {
+        self.callsites.entry(id).or_insert(callsite);
+    }
+
+    /// Remember what the widget reported about itself. Last wins.
+    pub(crate) fn set_info(&mut self, id: Id, info: WidgetInfo) {
+        self.infos.insert(id, info);
+    }
 }
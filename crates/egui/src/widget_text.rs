@@ -1,8 +1,8 @@
 use std::{borrow::Cow, sync::Arc};
 
 use crate::{
-    text::LayoutJob, Align, Color32, FontFamily, FontSelection, Galley, Style, TextStyle, Ui,
-    Visuals,
+    text::{FontFeatures, FontVariations, LayoutJob, LineBreak},
+    Align, Color32, FontFamily, FontSelection, Galley, Style, TextStyle, Ui, Visuals,
 };
 
 /// Text and optional style choices for it.
@@ -25,6 +25,7 @@ pub struct RichText {
     text: String,
     size: Option<f32>,
     extra_letter_spacing: f32,
+    extra_word_spacing: f32,
     line_height: Option<f32>,
     family: Option<FontFamily>,
     text_style: Option<TextStyle>,
@@ -36,7 +37,12 @@ pub struct RichText {
     strikethrough: bool,
     underline: bool,
     italics: bool,
+    bold: bool,
+    small_caps: bool,
     raised: bool,
+    line_break: LineBreak,
+    font_features: FontFeatures,
+    font_variations: FontVariations,
 }
 
 impl From<&str> for RichText {
@@ -113,6 +119,15 @@ impl RichText {
         self
     }
 
+    /// Extra spacing after each space, in points.
+    ///
+    /// Default: 0.0.
+    #[inline]
+    pub fn extra_word_spacing(mut self, extra_word_spacing: f32) -> Self {
+        self.extra_word_spacing = extra_word_spacing;
+        self
+    }
+
     /// Explicit line height of the text in points.
     ///
     /// This is the distance between the bottom row of two subsequent lines of text.
@@ -220,6 +235,22 @@ impl RichText {
         self
     }
 
+    /// Thicker characters, by drawing each one twice, a pixel or so apart.
+    ///
+    /// Use this when the font has no bold variant. See also [`Self::strong`].
+    #[inline]
+    pub fn bold(mut self) -> Self {
+        self.bold = true;
+        self
+    }
+
+    /// Show lowercase letters as smaller uppercase letters.
+    #[inline]
+    pub fn small_caps(mut self) -> Self {
+        self.small_caps = true;
+        self
+    }
+
     /// Smaller text.
     #[inline]
     pub fn small(self) -> Self {
@@ -239,6 +270,40 @@ impl RichText {
         self
     }
 
+    /// Where rows may break inside this text when it is wrapped.
+    ///
+    /// For instance, use [`LineBreak::Never`] for a name that should stay on one row,
+    /// and [`LineBreak::Graphemes`] for a long hash.
+    #[inline]
+    pub fn line_break(mut self, line_break: LineBreak) -> Self {
+        self.line_break = line_break;
+        self
+    }
+
+    /// OpenType features of the font to use, e.g. `tnum` for numbers that line up in a table.
+    ///
+    /// ```
+    /// # use egui::{RichText, text::FontFeatures};
+    /// RichText::new("1 234.50").font_features(FontFeatures::default().enable(b"tnum"));
+    /// ```
+    #[inline]
+    pub fn font_features(mut self, font_features: FontFeatures) -> Self {
+        self.font_features = font_features;
+        self
+    }
+
+    /// Axis values for variable fonts, e.g. the weight.
+    ///
+    /// ```
+    /// # use egui::{RichText, text::FontVariations};
+    /// RichText::new("Semibold").font_variations(FontVariations::new().weight(600.0));
+    /// ```
+    #[inline]
+    pub fn font_variations(mut self, font_variations: FontVariations) -> Self {
+        self.font_variations = font_variations;
+        self
+    }
+
     /// Fill-color behind the text.
     #[inline]
     pub fn background_color(mut self, background_color: impl Into<Color32>) -> Self {
@@ -335,6 +400,7 @@ impl RichText {
             text,
             size,
             extra_letter_spacing,
+            extra_word_spacing,
             line_height,
             family,
             text_style,
@@ -346,7 +412,12 @@ impl RichText {
             strikethrough,
             underline,
             italics,
+            bold,
+            small_caps,
             raised,
+            line_break,
+            font_features,
+            font_variations,
         } = self;
 
         let line_color = text_color.unwrap_or_else(|| style.visuals.text_color());
@@ -394,13 +465,19 @@ impl RichText {
             crate::text::TextFormat {
                 font_id,
                 extra_letter_spacing,
+                extra_word_spacing,
                 line_height,
                 color: text_color,
                 background: background_color,
                 italics,
+                bold,
+                small_caps,
                 underline,
                 strikethrough,
                 valign,
+                line_break,
+                font_features,
+                font_variations,
             },
         )
     }
@@ -579,6 +656,22 @@ impl WidgetText {
         }
     }
 
+    /// Prefer using [`RichText`] directly!
+    pub fn bold(self) -> Self {
+        match self {
+            Self::RichText(text) => Self::RichText(text.bold()),
+            Self::LayoutJob(_) | Self::Galley(_) => self,
+        }
+    }
+
+    /// Prefer using [`RichText`] directly!
+    pub fn small_caps(self) -> Self {
+        match self {
+            Self::RichText(text) => Self::RichText(text.small_caps()),
+            Self::LayoutJob(_) | Self::Galley(_) => self,
+        }
+    }
+
     /// Prefer using [`RichText`] directly!
     pub fn small(self) -> Self {
         match self {
@@ -603,6 +696,30 @@ impl WidgetText {
         }
     }
 
+    /// Prefer using [`RichText`] directly!
+    pub fn line_break(self, line_break: LineBreak) -> Self {
+        match self {
+            Self::RichText(text) => Self::RichText(text.line_break(line_break)),
+            Self::LayoutJob(_) | Self::Galley(_) => self,
+        }
+    }
+
+    /// Prefer using [`RichText`] directly!
+    pub fn font_features(self, font_features: FontFeatures) -> Self {
+        match self {
+            Self::RichText(text) => Self::RichText(text.font_features(font_features)),
+            Self::LayoutJob(_) | Self::Galley(_) => self,
+        }
+    }
+
+    /// Prefer using [`RichText`] directly!
+    pub fn font_variations(self, font_variations: FontVariations) -> Self {
+        match self {
+            Self::RichText(text) => Self::RichText(text.font_variations(font_variations)),
+            Self::LayoutJob(_) | Self::Galley(_) => self,
+        }
+    }
+
     /// Prefer using [`RichText`] directly!
     pub fn background_color(self, background_color: impl Into<Color32>) -> Self {
         match self {
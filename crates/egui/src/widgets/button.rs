@@ -237,7 +237,8 @@ impl Widget for Button<'_> {
         }
         desired_size = desired_size.at_least(min_size);
 
-        let (rect, mut response) = ui.allocate_at_least(desired_size, sense);
+        let (rect, response) = ui.allocate_at_least(desired_size, sense);
+        let mut response = response.with_extra_pointers();
         response.widget_info(|| {
             if let Some(galley) = &galley {
                 WidgetInfo::labeled(WidgetType::Button, galley.text())
@@ -311,6 +312,7 @@ impl Widget for Button<'_> {
                         .align_size_within_rect(galley.size(), rect.shrink2(button_padding))
                         .min
                 };
+                response = ui.on_hover_text_if_clipped(response, &galley, text_pos);
                 ui.painter().galley(text_pos, galley, visuals.text_color());
             }
 
@@ -352,17 +354,45 @@ impl Widget for Button<'_> {
 /// ui.add(egui::Checkbox::new(&mut my_bool, "Checked"));
 /// # });
 /// ```
+///
+/// See also [`Self::tri_state`] and [`CheckboxGroup`].
 #[must_use = "You should put this widget in an ui with `ui.add(widget);`"]
 pub struct Checkbox<'a> {
-    checked: &'a mut bool,
+    checked: CheckboxValue<'a>,
     text: WidgetText,
     indeterminate: bool,
 }
 
+/// The value a [`Checkbox`] toggles.
+enum CheckboxValue<'a> {
+    Bool(&'a mut bool),
+    TriState(&'a mut Option<bool>),
+}
+
 impl<'a> Checkbox<'a> {
     pub fn new(checked: &'a mut bool, text: impl Into<WidgetText>) -> Self {
         Checkbox {
-            checked,
+            checked: CheckboxValue::Bool(checked),
+            text: text.into(),
+            indeterminate: false,
+        }
+    }
+
+    /// A checkbox that is either checked (`Some(true)`), unchecked (`Some(false)`)
+    /// or indeterminate (`None`).
+    ///
+    /// Clicking an indeterminate checkbox checks it.
+    /// The user can't make it indeterminate again, only your code can.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// let mut show_hidden_files: Option<bool> = None; // Not decided yet
+    /// ui.add(egui::Checkbox::tri_state(&mut show_hidden_files, "Show hidden files"));
+    /// # });
+    /// ```
+    pub fn tri_state(value: &'a mut Option<bool>, text: impl Into<WidgetText>) -> Self {
+        Checkbox {
+            checked: CheckboxValue::TriState(value),
             text: text.into(),
             indeterminate: false,
         }
@@ -386,7 +416,7 @@ impl<'a> Checkbox<'a> {
 impl<'a> Widget for Checkbox<'a> {
     fn ui(self, ui: &mut Ui) -> Response {
         let Checkbox {
-            checked,
+            mut checked,
             text,
             indeterminate,
         } = self;
@@ -411,12 +441,23 @@ impl<'a> Widget for Checkbox<'a> {
 
         desired_size = desired_size.at_least(Vec2::splat(spacing.interact_size.y));
         desired_size.y = desired_size.y.max(icon_width);
-        let (rect, mut response) = ui.allocate_exact_size(desired_size, Sense::click());
+        let (rect, response) = ui.allocate_exact_size(desired_size, Sense::click());
+        let mut response = response.with_extra_pointers();
 
         if response.clicked() {
-            *checked = !*checked;
+            match &mut checked {
+                CheckboxValue::Bool(checked) => **checked = !**checked,
+                // Clicking an indeterminate checkbox checks it:
+                CheckboxValue::TriState(value) => **value = Some(**value != Some(true)),
+            }
             response.mark_changed();
         }
+        let (checked, indeterminate) = match checked {
+            CheckboxValue::Bool(checked) => (*checked, indeterminate),
+            CheckboxValue::TriState(value) => {
+                (*value == Some(true), indeterminate || value.is_none())
+            }
+        };
         response.widget_info(|| {
             if indeterminate {
                 WidgetInfo::labeled(
@@ -426,15 +467,17 @@ impl<'a> Widget for Checkbox<'a> {
             } else {
                 WidgetInfo::selected(
                     WidgetType::Checkbox,
-                    *checked,
+                    checked,
                     galley.as_ref().map_or("", |x| x.text()),
                 )
             }
         });
 
         if ui.is_rect_visible(rect) {
-            // let visuals = ui.style().interact_selectable(&response, *checked); // too colorful
-            let visuals = ui.style().interact(&response);
+            let state = style::WidgetState::from_response(&response)
+                .checked(checked)
+                .indeterminate(indeterminate);
+            let visuals = ui.style().interact_with_state(state);
             let (small_icon_rect, big_icon_rect) = ui.spacing().icon_rectangles(rect);
             ui.painter().add(epaint::RectShape::new(
                 big_icon_rect.expand(visuals.expansion),
@@ -450,7 +493,7 @@ impl<'a> Widget for Checkbox<'a> {
                     small_icon_rect.center().y,
                     visuals.fg_stroke,
                 ));
-            } else if *checked {
+            } else if checked {
                 // Check mark:
                 ui.painter().add(Shape::line(
                     vec![
@@ -466,7 +509,9 @@ impl<'a> Widget for Checkbox<'a> {
                     rect.min.x + icon_width + icon_spacing,
                     rect.center().y - 0.5 * galley.size().y,
                 );
-                ui.painter().galley(text_pos, galley, visuals.text_color());
+                response = ui.on_hover_text_if_clipped(response, &galley, text_pos);
+                let text_color = ui.style().interact(&response).text_color();
+                ui.painter().galley(text_pos, galley, text_color);
             }
         }
 
@@ -476,6 +521,85 @@ impl<'a> Widget for Checkbox<'a> {
 
 // ----------------------------------------------------------------------------
 
+/// A parent [`Checkbox`] for a group of child checkboxes, e.g. "Select all" in a filter panel.
+///
+/// The parent is checked if all children are, unchecked if none are, and indeterminate otherwise
+/// (see [`Self::parent_state`]).
+/// Clicking the parent checks or unchecks all the children.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// let names = ["Read", "Write", "Delete"];
+/// let mut permissions = [true, true, false];
+/// egui::CheckboxGroup::new(&mut permissions, "All permissions").show(ui, |ui, i, checked| {
+///     ui.checkbox(checked, names[i])
+/// });
+/// # });
+/// ```
+#[must_use = "You should call .show()"]
+pub struct CheckboxGroup<'a> {
+    children: &'a mut [bool],
+    text: WidgetText,
+}
+
+impl<'a> CheckboxGroup<'a> {
+    pub fn new(children: &'a mut [bool], text: impl Into<WidgetText>) -> Self {
+        Self {
+            children,
+            text: text.into(),
+        }
+    }
+
+    /// The tri-state of a parent checkbox with these children:
+    /// `Some(true)` if all are checked, `Some(false)` if none are, and `None` otherwise.
+    pub fn parent_state(children: &[bool]) -> Option<bool> {
+        if children.iter().all(|&checked| checked) {
+            Some(true)
+        } else if children.iter().all(|&checked| !checked) {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    /// Show the parent checkbox, and below it, indented, the children using `add_child`.
+    ///
+    /// `add_child` gets the index of the child and its value, and usually shows a [`Checkbox`].
+    ///
+    /// The returned [`Response`] is [`Response::changed`] if any of the checkboxes changed.
+    pub fn show(
+        self,
+        ui: &mut Ui,
+        mut add_child: impl FnMut(&mut Ui, usize, &mut bool) -> Response,
+    ) -> Response {
+        let Self { children, text } = self;
+
+        let mut parent = Self::parent_state(children);
+        let mut response = ui.add(Checkbox::tri_state(&mut parent, text));
+        if response.changed() {
+            if let Some(checked) = parent {
+                children.fill(checked);
+            }
+        }
+
+        let id = response.id;
+        let children_response = ui.indent(id, |ui| {
+            let mut changed = false;
+            for (i, checked) in children.iter_mut().enumerate() {
+                changed |= add_child(ui, i, checked).changed();
+            }
+            changed
+        });
+        if children_response.inner {
+            response.mark_changed();
+        }
+
+        response | children_response.response
+    }
+}
+
+// ----------------------------------------------------------------------------
+
 /// One out of several alternatives, either selected or not.
 ///
 /// Usually you'd use [`Ui::radio_value`] or [`Ui::radio`] instead.
@@ -535,6 +659,7 @@ impl Widget for RadioButton {
         desired_size = desired_size.at_least(Vec2::splat(spacing.interact_size.y));
         desired_size.y = desired_size.y.max(icon_width);
         let (rect, response) = ui.allocate_exact_size(desired_size, Sense::click());
+        let mut response = response.with_extra_pointers();
 
         response.widget_info(|| {
             WidgetInfo::selected(
@@ -545,8 +670,8 @@ impl Widget for RadioButton {
         });
 
         if ui.is_rect_visible(rect) {
-            // let visuals = ui.style().interact_selectable(&response, checked); // too colorful
-            let visuals = ui.style().interact(&response);
+            let state = style::WidgetState::from_response(&response).checked(checked);
+            let visuals = ui.style().interact_with_state(state);
 
             let (small_icon_rect, big_icon_rect) = ui.spacing().icon_rectangles(rect);
 
@@ -574,7 +699,9 @@ impl Widget for RadioButton {
                     rect.min.x + icon_width + icon_spacing,
                     rect.center().y - 0.5 * galley.size().y,
                 );
-                ui.painter().galley(text_pos, galley, visuals.text_color());
+                response = ui.on_hover_text_if_clipped(response, &galley, text_pos);
+                let text_color = ui.style().interact(&response).text_color();
+                ui.painter().galley(text_pos, galley, text_color);
             }
         }
 
@@ -668,6 +795,7 @@ impl<'a> Widget for ImageButton<'a> {
 
         let padded_size = image_size + 2.0 * padding;
         let (rect, response) = ui.allocate_exact_size(padded_size, self.sense);
+        let response = response.with_extra_pointers();
         response.widget_info(|| WidgetInfo::new(WidgetType::ImageButton));
 
         if ui.is_rect_visible(rect) {
@@ -712,3 +840,102 @@ impl<'a> Widget for ImageButton<'a> {
         widgets::image::texture_load_result_response(self.image.source(), &tlr, response)
     }
 }
+
+#[test]
+fn test_checkbox_group() {
+    use crate::test_harness::Harness;
+    use std::cell::{Cell, RefCell};
+
+    assert_eq!(CheckboxGroup::parent_state(&[true, true]), Some(true));
+    assert_eq!(CheckboxGroup::parent_state(&[false, false]), Some(false));
+    assert_eq!(CheckboxGroup::parent_state(&[true, false]), None);
+
+    let children = RefCell::new([true, false, false]);
+    let changed = Cell::new(false);
+    let mut harness = Harness::new_ui(|ui| {
+        let mut children = children.borrow_mut();
+        let response = CheckboxGroup::new(&mut *children, "All").show(ui, |ui, i, checked| {
+            ui.checkbox(checked, format!("Child {i}"))
+        });
+        changed.set(changed.get() | response.changed());
+    });
+
+    // An indeterminate parent checks all children:
+    harness.get_by_label("All").click();
+    assert_eq!(*children.borrow(), [true, true, true]);
+    assert!(changed.take());
+
+    // A checked parent unchecks all children:
+    harness.get_by_label("All").click();
+    assert_eq!(*children.borrow(), [false, false, false]);
+    assert!(changed.take());
+
+    // Children can still be toggled individually:
+    harness.get_by_label("Child 1").click();
+    assert_eq!(*children.borrow(), [false, true, false]);
+    assert!(changed.take());
+}
+
+#[test]
+fn test_tri_state_checkbox() {
+    use crate::test_harness::Harness;
+    use std::cell::Cell;
+
+    let value = Cell::new(None);
+    let mut harness = Harness::new_ui(|ui| {
+        let mut v = value.get();
+        ui.add(Checkbox::tri_state(&mut v, "Maybe"));
+        value.set(v);
+    });
+
+    harness.get_by_label("Maybe").click();
+    assert_eq!(value.get(), Some(true));
+    harness.get_by_label("Maybe").click();
+    assert_eq!(value.get(), Some(false));
+}
+
+#[test]
+fn test_checkbox_and_radio_visuals_per_state() {
+    use crate::test_harness::Harness;
+
+    let mut harness = Harness::new_ui(|ui| {
+        ui.checkbox(&mut false, "Unchecked");
+        ui.checkbox(&mut true, "Checked");
+        ui.add(Checkbox::without_text(&mut true).indeterminate(true));
+        ui.radio(false, "Off");
+        ui.radio(true, "On");
+    });
+    harness.run();
+
+    let shapes: Vec<Shape> = harness
+        .output()
+        .shapes
+        .iter()
+        .map(|clipped| clipped.shape.clone())
+        .collect();
+    let icon = |rect: Rect| -> (Color32, Stroke) {
+        shapes
+            .iter()
+            .find_map(|shape| match shape {
+                Shape::Rect(r) if rect.contains_rect(r.rect) => Some((r.fill, r.stroke)),
+                Shape::Circle(c) if rect.contains(c.center) => Some((c.fill, c.stroke)),
+                _ => None,
+            })
+            .unwrap()
+    };
+    let rect_of = |label: &str| harness.query_by_label(label).unwrap().rect.rect;
+    let indeterminate = harness
+        .query_all(|info| info.typ == WidgetType::Checkbox && info.selected.is_none())
+        .remove(0)
+        .rect
+        .rect;
+
+    let visuals = Visuals::dark();
+    let (unchecked, checked) = (icon(rect_of("Unchecked")), icon(rect_of("Checked")));
+    assert_eq!(unchecked.0, visuals.widgets.inactive.bg_fill);
+    assert_eq!(checked.0, visuals.selection.bg_fill);
+    let indeterminate = icon(indeterminate);
+    assert_ne!(indeterminate.0, unchecked.0);
+    assert_ne!(indeterminate.0, checked.0);
+    assert_ne!(icon(rect_of("Off")).0, icon(rect_of("On")).0);
+}
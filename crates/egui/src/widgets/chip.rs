@@ -0,0 +1,166 @@
+use crate::*;
+
+/// A small, rounded piece of text for a tag, a filter, or an entered item like an e-mail recipient.
+///
+/// A chip can be clicked like a [`Button`], be shown as selected like a [`SelectableLabel`],
+/// and have a close button (`×`) to dismiss it.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// # let mut tags = vec!["rust".to_owned(), "gui".to_owned()];
+/// ui.horizontal(|ui| {
+///     tags.retain(|tag| {
+///         let mut open = true;
+///         ui.add(egui::Chip::new(tag.as_str()).open(&mut open));
+///         open
+///     });
+/// });
+/// # });
+/// ```
+#[must_use = "You should put this widget in an ui with `ui.add(widget);`"]
+pub struct Chip<'a> {
+    text: WidgetText,
+    selected: bool,
+    open: Option<&'a mut bool>,
+}
+
+impl<'a> Chip<'a> {
+    pub fn new(text: impl Into<WidgetText>) -> Self {
+        Self {
+            text: text.into(),
+            selected: false,
+            open: None,
+        }
+    }
+
+    /// Show the chip as selected, e.g. for a filter that is turned on.
+    #[inline]
+    pub fn selected(mut self, selected: bool) -> Self {
+        self.selected = selected;
+        self
+    }
+
+    /// Show a close button, which sets `*open` to `false` when clicked.
+    ///
+    /// Pressing backspace or delete while the chip has keyboard focus does the same.
+    /// It is up to you to stop showing the chip once it is closed.
+    #[inline]
+    pub fn open(mut self, open: &'a mut bool) -> Self {
+        self.open = Some(open);
+        self
+    }
+}
+
+impl<'a> Widget for Chip<'a> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let Self {
+            text,
+            selected,
+            open,
+        } = self;
+
+        let spacing = ui.spacing();
+        let padding = vec2(2.0 * spacing.button_padding.x, spacing.button_padding.y);
+        let close_size = spacing.icon_width;
+        let close_extra = if open.is_some() {
+            spacing.icon_spacing + close_size
+        } else {
+            0.0
+        };
+        let min_height = spacing.interact_size.y;
+
+        let galley = text.into_galley(ui, Some(false), f32::INFINITY, TextStyle::Button);
+        let mut desired_size = galley.size() + 2.0 * padding + vec2(close_extra, 0.0);
+        desired_size.y = desired_size.y.at_least(min_height);
+        let (rect, response) = ui.allocate_exact_size(desired_size, Sense::click());
+        let mut response = response.with_extra_pointers();
+        response.widget_info(|| {
+            WidgetInfo::selected(WidgetType::SelectableLabel, selected, galley.text())
+        });
+
+        let close_response = open.map(|open| {
+            let close_rect = Rect::from_center_size(
+                pos2(rect.right() - padding.x - 0.5 * close_size, rect.center().y),
+                Vec2::splat(close_size),
+            );
+            let close_response = ui
+                .interact(close_rect, response.id.with("close"), Sense::click())
+                .with_extra_pointers();
+            close_response.widget_info(|| WidgetInfo::labeled(WidgetType::Button, "Close"));
+
+            let delete_pressed = response.has_focus()
+                && ui.input(|i| i.key_pressed(Key::Backspace) || i.key_pressed(Key::Delete));
+            if close_response.clicked() || delete_pressed {
+                *open = false;
+                response.mark_changed();
+            }
+            close_response
+        });
+
+        if ui.is_rect_visible(rect) {
+            let visuals = ui.style().interact_selectable(&response, selected);
+            ui.painter().rect(
+                rect.expand(visuals.expansion),
+                ui.visuals().chip_rounding,
+                visuals.weak_bg_fill,
+                visuals.bg_stroke,
+            );
+
+            let text_pos = pos2(
+                rect.left() + padding.x,
+                rect.center().y - 0.5 * galley.size().y,
+            );
+            ui.painter().galley(text_pos, galley, visuals.text_color());
+
+            if let Some(close_response) = close_response {
+                // An `X`, like the close button of a `Window`:
+                let stroke = if close_response.hovered() {
+                    ui.style().interact(&close_response).fg_stroke
+                } else {
+                    visuals.fg_stroke
+                };
+                let close_rect = close_response.rect.shrink(3.0);
+                ui.painter()
+                    .line_segment([close_rect.left_top(), close_rect.right_bottom()], stroke);
+                ui.painter()
+                    .line_segment([close_rect.right_top(), close_rect.left_bottom()], stroke);
+            }
+        }
+
+        response
+    }
+}
+
+#[test]
+fn test_chip() {
+    use crate::test_harness::Harness;
+    use std::cell::RefCell;
+
+    let tags = RefCell::new(vec!["rust", "gui"]);
+    let clicked = RefCell::new(vec![]);
+    let mut harness = Harness::new_ui(|ui| {
+        ui.horizontal(|ui| {
+            tags.borrow_mut().retain(|tag| {
+                let mut open = true;
+                if ui.add(Chip::new(*tag).open(&mut open)).clicked() {
+                    clicked.borrow_mut().push(*tag);
+                }
+                open
+            });
+        });
+    });
+
+    harness.get_by_label("gui").click();
+    assert_eq!(*clicked.borrow(), ["gui"]);
+
+    let close_rect = harness.query_all(|info| info.label.as_deref() == Some("Close"))[0]
+        .rect
+        .interact_rect;
+    harness.click(close_rect.center());
+    assert_eq!(*tags.borrow(), ["gui"]);
+    assert_eq!(
+        *clicked.borrow(),
+        ["gui"],
+        "Closing isn't a click on the chip"
+    );
+}
@@ -1,5 +1,6 @@
 //! Color picker widgets.
 
+use crate::style::WidgetState;
 use crate::util::fixed_cache::FixedCache;
 use crate::*;
 use epaint::{ecolor::*, *};
@@ -78,17 +79,14 @@ pub fn show_color_at(painter: &Painter, color: Color32, rect: Rect) {
     }
 }
 
-fn color_button(ui: &mut Ui, color: Color32, open: bool) -> Response {
-    let size = ui.spacing().interact_size;
+fn color_button(ui: &mut Ui, color: Color32, size: Vec2, open: bool) -> Response {
     let (rect, response) = ui.allocate_exact_size(size, Sense::click());
     response.widget_info(|| WidgetInfo::new(WidgetType::ColorButton));
 
     if ui.is_rect_visible(rect) {
-        let visuals = if open {
-            &ui.visuals().widgets.open
-        } else {
-            ui.style().interact(&response)
-        };
+        let visuals = &ui
+            .style()
+            .interact_with_state(WidgetState::from_response(&response).open(open));
         let rect = rect.expand(visuals.expansion);
 
         show_color_at(ui.painter(), color, rect);
@@ -236,7 +234,7 @@ pub enum Alpha {
     BlendOrAdditive,
 }
 
-fn color_picker_hsvag_2d(ui: &mut Ui, hsvag: &mut HsvaGamma, alpha: Alpha) {
+fn color_picker_hsvag_2d(ui: &mut Ui, id: Id, hsvag: &mut HsvaGamma, alpha: Alpha) {
     use crate::style::NumericColorSpace;
 
     let alpha_control = if is_additive_alpha(hsvag.a) {
@@ -296,6 +294,8 @@ fn color_picker_hsvag_2d(ui: &mut Ui, hsvag: &mut HsvaGamma, alpha: Alpha) {
         }
     }
 
+    color_text_edit_ui(ui, id, hsvag, alpha);
+
     let current_color_size = vec2(ui.spacing().slider_width, ui.spacing().interact_size.y);
     show_color(ui, *hsvag, current_color_size).on_hover_text("Selected color");
 
@@ -358,6 +358,8 @@ fn color_picker_hsvag_2d(ui: &mut Ui, hsvag: &mut HsvaGamma, alpha: Alpha) {
             color_slider_1d(ui, a, |a| HsvaGamma { a, ..opaque }.into()).on_hover_text("Alpha");
         }
     }
+
+    saved_colors_ui(ui, id, hsvag, alpha);
 }
 
 fn input_type_button_ui(ui: &mut Ui) {
@@ -448,11 +450,13 @@ fn rgba_edit_ui(ui: &mut Ui, [r, g, b, a]: &mut [f32; 4], alpha: Alpha) -> bool
 ///
 /// Returns `true` on change.
 pub fn color_picker_hsva_2d(ui: &mut Ui, hsva: &mut Hsva, alpha: Alpha) -> bool {
+    let id = ui.next_auto_id().with("color_picker");
     let mut hsvag = HsvaGamma::from(*hsva);
     ui.vertical(|ui| {
-        color_picker_hsvag_2d(ui, &mut hsvag, alpha);
+        color_picker_hsvag_2d(ui, id, &mut hsvag, alpha);
     });
     let new_hasva = Hsva::from(hsvag);
+    remember_recent_color(ui.ctx(), id, new_hasva.into(), *hsva != new_hasva);
     if *hsva == new_hasva {
         false
     } else {
@@ -475,7 +479,8 @@ pub fn color_picker_color32(ui: &mut Ui, srgba: &mut Color32, alpha: Alpha) -> b
 pub fn color_edit_button_hsva(ui: &mut Ui, hsva: &mut Hsva, alpha: Alpha) -> Response {
     let popup_id = ui.auto_id_with("popup");
     let open = ui.memory(|mem| mem.is_popup_open(popup_id));
-    let mut button_response = color_button(ui, (*hsva).into(), open);
+    let size = ui.spacing().interact_size;
+    let mut button_response = color_button(ui, (*hsva).into(), size, open);
     if ui.style().explanation_tooltips {
         button_response = button_response.on_hover_text("Click to edit color");
     }
@@ -486,28 +491,19 @@ pub fn color_edit_button_hsva(ui: &mut Ui, hsva: &mut Hsva, alpha: Alpha) -> Res
 
     const COLOR_SLIDER_WIDTH: f32 = 275.0;
 
-    // TODO(emilk): make it easier to show a temporary popup that closes when you click outside it
-    if ui.memory(|mem| mem.is_popup_open(popup_id)) {
-        let area_response = Area::new(popup_id)
-            .order(Order::Foreground)
-            .fixed_pos(button_response.rect.max)
-            .constrain(true)
-            .show(ui.ctx(), |ui| {
-                ui.spacing_mut().slider_width = COLOR_SLIDER_WIDTH;
-                Frame::popup(ui.style()).show(ui, |ui| {
-                    if color_picker_hsva_2d(ui, hsva, alpha) {
-                        button_response.mark_changed();
-                    }
-                });
-            })
-            .response;
-
-        if !button_response.clicked()
-            && (ui.input(|i| i.key_pressed(Key::Escape)) || area_response.clicked_elsewhere())
-        {
-            ui.memory_mut(|mem| mem.close_popup());
-        }
-    }
+    let area = Area::new(popup_id)
+        .order(Order::Foreground)
+        .fixed_pos(button_response.rect.max)
+        .constrain(true);
+    let anchor = button_response.clone();
+    crate::popup::show_popup_area(ui, popup_id, &anchor, Default::default(), area, |ui| {
+        ui.spacing_mut().slider_width = COLOR_SLIDER_WIDTH;
+        Frame::popup(ui.style()).show(ui, |ui| {
+            if color_picker_hsva_2d(ui, hsva, alpha) {
+                button_response.mark_changed();
+            }
+        });
+    });
 
     button_response
 }
@@ -555,6 +551,336 @@ pub fn color_edit_button_rgb(ui: &mut Ui, rgb: &mut [f32; 3]) -> Response {
     response
 }
 
+// ----------------------------------------------------------------------------
+
+/// A named set of colors, shown in all color pickers.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ColorPalette {
+    /// Shown above the colors.
+    pub name: String,
+
+    pub colors: Vec<Color32>,
+}
+
+/// How the color pickers show colors as text.
+///
+/// Colors in any of the formats can be typed into the color pickers.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum ColorTextFormat {
+    /// `#ff8800`
+    #[default]
+    Hex,
+
+    /// `rgb(255, 136, 0)`
+    Rgb,
+
+    /// `hsl(32, 100%, 50%)`
+    Hsl,
+}
+
+/// What all color pickers share: saved palettes, recently picked colors, and the [`ColorTextFormat`].
+///
+/// This is persisted in [`crate::Memory`], so apps can also add palettes of their own:
+///
+/// ```
+/// # let ctx = egui::Context::default();
+/// use egui::{color_picker::{ColorPalette, ColorPickerMemory}, Color32};
+/// let mut memory = ColorPickerMemory::load(&ctx);
+/// memory.palettes.push(ColorPalette {
+///     name: "Traffic lights".to_owned(),
+///     colors: vec![Color32::RED, Color32::YELLOW, Color32::GREEN],
+/// });
+/// memory.store(&ctx);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ColorPickerMemory {
+    /// Palettes to pick colors from, and save colors to.
+    pub palettes: Vec<ColorPalette>,
+
+    /// The most recently picked colors, newest first.
+    pub recent: Vec<Color32>,
+
+    /// How colors are shown as text.
+    pub text_format: ColorTextFormat,
+}
+
+impl ColorPickerMemory {
+    /// At most this many recent colors are remembered.
+    pub const MAX_RECENT: usize = 12;
+
+    fn id() -> Id {
+        Id::new("color_picker_memory")
+    }
+
+    pub fn load(ctx: &Context) -> Self {
+        ctx.data_mut(|d| d.get_persisted(Self::id()))
+            .unwrap_or_default()
+    }
+
+    pub fn store(&self, ctx: &Context) {
+        ctx.data_mut(|d| d.insert_persisted(Self::id(), self.clone()));
+    }
+
+    /// Put this color first among the recent ones.
+    pub fn add_recent(&mut self, color: Color32) {
+        self.recent.retain(|&recent| recent != color);
+        self.recent.insert(0, color);
+        self.recent.truncate(Self::MAX_RECENT);
+    }
+}
+
+/// Remember the color among the recent ones once the user is done changing it,
+/// i.e. not while still dragging a slider or typing.
+fn remember_recent_color(ctx: &Context, id: Id, color: Color32, changed: bool) {
+    let pending_id = id.with("pending_recent");
+    let pending = changed
+        || ctx
+            .data(|d| d.get_temp::<bool>(pending_id))
+            .unwrap_or(false);
+    if !pending {
+        return;
+    }
+
+    let editing = ctx.input(|i| i.pointer.any_down())
+        || ctx.memory(|mem| mem.has_focus(id.with("text_edit")));
+    if editing {
+        ctx.data_mut(|d| d.insert_temp(pending_id, true));
+    } else {
+        ctx.data_mut(|d| d.remove::<bool>(pending_id));
+        let mut memory = ColorPickerMemory::load(ctx);
+        memory.add_recent(color);
+        memory.store(ctx);
+    }
+}
+
+/// Shows the color as text, and lets the user type in a new one.
+fn color_text_edit_ui(ui: &mut Ui, id: Id, hsvag: &mut HsvaGamma, alpha: Alpha) {
+    let mut memory = ColorPickerMemory::load(ui.ctx());
+    let srgba = Hsva::from(*hsvag).to_srgba_unmultiplied();
+    let text_edit_id = id.with("text_edit");
+
+    ui.horizontal(|ui| {
+        let mut text_format = memory.text_format;
+        ui.selectable_value(&mut text_format, ColorTextFormat::Hex, "Hex");
+        ui.selectable_value(&mut text_format, ColorTextFormat::Rgb, "RGB");
+        ui.selectable_value(&mut text_format, ColorTextFormat::Hsl, "HSL");
+        if text_format != memory.text_format {
+            memory.text_format = text_format;
+            memory.store(ui.ctx());
+        }
+
+        // Keep what the user is typing, instead of what it parses as:
+        let mut text = ui
+            .data(|d| d.get_temp::<String>(text_edit_id))
+            .unwrap_or_else(|| color_to_text(srgba, alpha, text_format));
+        let response = TextEdit::singleline(&mut text)
+            .id(text_edit_id)
+            .desired_width(ui.available_width())
+            .ui(ui)
+            .on_hover_text("Type a color like #ff8800, rgb(255, 136, 0) or hsl(32, 100%, 50%)");
+
+        if response.changed() {
+            if let Some(mut srgba) = color_from_text(&text) {
+                if alpha == Alpha::Opaque {
+                    srgba[3] = 255;
+                }
+                *hsvag = HsvaGamma::from(Hsva::from_srgba_unmultiplied(srgba));
+            }
+        }
+        if response.has_focus() {
+            ui.data_mut(|d| d.insert_temp(text_edit_id, text));
+        } else {
+            ui.data_mut(|d| d.remove::<String>(text_edit_id));
+        }
+    });
+}
+
+/// Recently picked colors and the saved palettes to pick from,
+/// and the eyedropper if the integration supports it.
+fn saved_colors_ui(ui: &mut Ui, id: Id, hsvag: &mut HsvaGamma, alpha: Alpha) {
+    let mut memory = ColorPickerMemory::load(ui.ctx());
+    let current_color = Color32::from(Hsva::from(*hsvag));
+    let swatch_size = Vec2::splat(ui.spacing().interact_size.y);
+    let mut picked = None;
+    let mut memory_changed = false;
+
+    if ui.input(|i| i.raw.eyedropper) {
+        eyedropper_ui(ui, id, &mut picked);
+    }
+
+    if !memory.recent.is_empty() {
+        ui.label("Recent colors:");
+        ui.horizontal_wrapped(|ui| {
+            for &color in &memory.recent {
+                if color_button(ui, color, swatch_size, false).clicked() {
+                    picked = Some(color);
+                }
+            }
+        });
+    }
+
+    for palette in &mut memory.palettes {
+        ui.label(format!("{}:", palette.name));
+        ui.horizontal_wrapped(|ui| {
+            let mut removed = None;
+            for (i, &color) in palette.colors.iter().enumerate() {
+                let response = color_button(ui, color, swatch_size, false)
+                    .on_hover_text("Click to use, right-click to remove");
+                if response.clicked() {
+                    picked = Some(color);
+                }
+                if response.secondary_clicked() {
+                    removed = Some(i);
+                }
+            }
+            if let Some(i) = removed {
+                palette.colors.remove(i);
+                memory_changed = true;
+            }
+            if ui
+                .small_button("+")
+                .on_hover_text("Save the selected color in this palette")
+                .clicked()
+            {
+                palette.colors.push(current_color);
+                memory_changed = true;
+            }
+        });
+    }
+
+    if memory.palettes.is_empty() && ui.button("Save color").clicked() {
+        memory.palettes.push(ColorPalette {
+            name: "Saved colors".to_owned(),
+            colors: vec![current_color],
+        });
+        memory_changed = true;
+    }
+
+    if memory_changed {
+        memory.store(ui.ctx());
+    }
+
+    if let Some(color) = picked {
+        let color = if alpha == Alpha::Opaque {
+            color.to_opaque()
+        } else {
+            color
+        };
+        *hsvag = HsvaGamma::from(Hsva::from(color));
+    }
+}
+
+/// A button asking the integration to let the user pick a color from the screen,
+/// see [`crate::PlatformOutput::request_eyedropper`].
+fn eyedropper_ui(ui: &mut Ui, id: Id, picked: &mut Option<Color32>) {
+    let waiting_id = id.with("eyedropper");
+    let mut waiting = ui.data(|d| d.get_temp::<bool>(waiting_id)).unwrap_or(false);
+
+    if waiting {
+        ui.input(|i| {
+            for event in &i.events {
+                if let Event::EyedropperColor(color) = event {
+                    *picked = picked.or(*color);
+                    waiting = false;
+                }
+            }
+        });
+    }
+
+    if ui.toggle_value(&mut waiting, "Pick from screen").clicked() && waiting {
+        ui.ctx().output_mut(|o| o.request_eyedropper = true);
+    }
+
+    ui.data_mut(|d| d.insert_temp(waiting_id, waiting));
+}
+
+fn color_to_text([r, g, b, a]: [u8; 4], alpha: Alpha, text_format: ColorTextFormat) -> String {
+    let opaque = alpha == Alpha::Opaque;
+    match text_format {
+        ColorTextFormat::Hex if opaque => format!("#{r:02x}{g:02x}{b:02x}"),
+        ColorTextFormat::Hex => format!("#{r:02x}{g:02x}{b:02x}{a:02x}"),
+        ColorTextFormat::Rgb if opaque => format!("rgb({r}, {g}, {b})"),
+        ColorTextFormat::Rgb => format!("rgba({r}, {g}, {b}, {:.2})", a as f32 / 255.0),
+        ColorTextFormat::Hsl => {
+            let [h, s, l] = hsl_from_rgb([r, g, b].map(|c| c as f32 / 255.0));
+            let (s, l) = (100.0 * s, 100.0 * l);
+            if opaque {
+                format!("hsl({h:.0}, {s:.0}%, {l:.0}%)")
+            } else {
+                format!("hsla({h:.0}, {s:.0}%, {l:.0}%, {:.2})", a as f32 / 255.0)
+            }
+        }
+    }
+}
+
+/// Parses `#rgb`, `#rrggbbaa` etc, `rgb(r, g, b)`, `rgba(r, g, b, a)`, `hsl(h, s%, l%)` and `hsla(h, s%, l%, a)`
+/// into unmultiplied sRGBA.
+fn color_from_text(text: &str) -> Option<[u8; 4]> {
+    let text = text.trim().to_lowercase();
+    if text.starts_with('#') {
+        return Color32::from_hex(&text)
+            .ok()
+            .map(|color| color.to_srgba_unmultiplied());
+    }
+
+    let (function, args) = text.strip_suffix(')')?.split_once('(')?;
+    let args = args
+        .split(',')
+        .map(|arg| arg.trim().trim_end_matches('%').parse::<f32>().ok())
+        .collect::<Option<Vec<_>>>()?;
+    let (rgb, a) = match (function.trim(), args.as_slice()) {
+        ("rgb", &[r, g, b]) => ([r, g, b].map(|c| c / 255.0), 1.0),
+        ("rgba", &[r, g, b, a]) => ([r, g, b].map(|c| c / 255.0), a),
+        ("hsl", &[h, s, l]) => (rgb_from_hsl([h, s / 100.0, l / 100.0]), 1.0),
+        ("hsla", &[h, s, l, a]) => (rgb_from_hsl([h, s / 100.0, l / 100.0]), a),
+        _ => return None,
+    };
+    let [r, g, b, a] =
+        [rgb[0], rgb[1], rgb[2], a].map(|c| (255.0 * c.clamp(0.0, 1.0)).round() as u8);
+    Some([r, g, b, a])
+}
+
+/// Hue in degrees, and saturation and lightness in 0-1, from sRGB in 0-1.
+fn hsl_from_rgb([r, g, b]: [f32; 3]) -> [f32; 3] {
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let lightness = (max + min) / 2.0;
+    let chroma = max - min;
+    if chroma <= 0.0 {
+        return [0.0, 0.0, lightness];
+    }
+    let saturation = chroma / (1.0 - (2.0 * lightness - 1.0).abs());
+    let hue = if max == r {
+        ((g - b) / chroma).rem_euclid(6.0)
+    } else if max == g {
+        (b - r) / chroma + 2.0
+    } else {
+        (r - g) / chroma + 4.0
+    };
+    [60.0 * hue, saturation, lightness]
+}
+
+/// sRGB in 0-1, from hue in degrees, and saturation and lightness in 0-1.
+fn rgb_from_hsl([hue, saturation, lightness]: [f32; 3]) -> [f32; 3] {
+    let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+    let hue = hue.rem_euclid(360.0) / 60.0;
+    let x = chroma * (1.0 - (hue % 2.0 - 1.0).abs());
+    let [r, g, b] = match hue as u32 {
+        0 => [chroma, x, 0.0],
+        1 => [x, chroma, 0.0],
+        2 => [0.0, chroma, x],
+        3 => [0.0, x, chroma],
+        4 => [x, 0.0, chroma],
+        _ => [chroma, 0.0, x],
+    };
+    let m = lightness - chroma / 2.0;
+    [r + m, g + m, b + m]
+}
+
 // To ensure we keep hue slider when `srgba` is gray we store the full [`Hsva`] in a cache:
 fn color_cache_get(ctx: &Context, rgba: impl Into<Rgba>) -> Hsva {
     let rgba = rgba.into();
@@ -571,3 +897,39 @@ fn color_cache_set(ctx: &Context, rgba: impl Into<Rgba>, hsva: Hsva) {
 fn use_color_cache<R>(ctx: &Context, f: impl FnOnce(&mut FixedCache<Rgba, Hsva>) -> R) -> R {
     ctx.data_mut(|d| f(d.get_temp_mut_or_default(Id::NULL)))
 }
+
+#[test]
+fn test_color_text() {
+    let orange = [255, 136, 0, 255];
+    for text in [
+        "#ff8800",
+        "#FF8800FF",
+        " rgb(255, 136, 0) ",
+        "hsl(32, 100%, 50%)",
+    ] {
+        assert_eq!(color_from_text(text), Some(orange), "{text}");
+    }
+    assert_eq!(
+        color_from_text("rgba(255, 136, 0, 0.5)"),
+        Some([255, 136, 0, 128])
+    );
+    assert_eq!(color_from_text("rgb(255, 136)"), None);
+    assert_eq!(color_from_text("orange"), None);
+
+    for text_format in [
+        ColorTextFormat::Hex,
+        ColorTextFormat::Rgb,
+        ColorTextFormat::Hsl,
+    ] {
+        for alpha in [Alpha::Opaque, Alpha::OnlyBlend] {
+            let text = color_to_text(orange, alpha, text_format);
+            assert_eq!(color_from_text(&text), Some(orange), "{text}");
+        }
+    }
+
+    let mut memory = ColorPickerMemory::default();
+    for color in [Color32::RED, Color32::GREEN, Color32::RED] {
+        memory.add_recent(color);
+    }
+    assert_eq!(memory.recent, vec![Color32::RED, Color32::GREEN]);
+}
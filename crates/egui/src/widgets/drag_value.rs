@@ -2,7 +2,7 @@
 
 use std::{cmp::Ordering, ops::RangeInclusive};
 
-use crate::*;
+use crate::{output::UiSoundEvent, *};
 
 // ----------------------------------------------------------------------------
 
@@ -43,6 +43,9 @@ pub struct DragValue<'a> {
     custom_formatter: Option<NumFormatter<'a>>,
     custom_parser: Option<NumParser<'a>>,
     update_while_editing: bool,
+    stepper_buttons: bool,
+    scroll_to_adjust: bool,
+    hold_to_repeat: bool,
 }
 
 impl<'a> DragValue<'a> {
@@ -75,6 +78,9 @@ impl<'a> DragValue<'a> {
             custom_formatter: None,
             custom_parser: None,
             update_while_editing: true,
+            stepper_buttons: false,
+            scroll_to_adjust: false,
+            hold_to_repeat: true,
         }
     }
 
@@ -354,10 +360,51 @@ impl<'a> DragValue<'a> {
         self.update_while_editing = update;
         self
     }
+
+    /// Show a "−" button before the value and a "+" button after it.
+    ///
+    /// Each click changes the value by [`Self::speed`], like the arrow keys do when editing it.
+    /// Hold `Shift` for a tenth of that.
+    ///
+    /// Default: `false`.
+    #[inline]
+    pub fn stepper_buttons(mut self, stepper_buttons: bool) -> Self {
+        self.stepper_buttons = stepper_buttons;
+        self
+    }
+
+    /// Change the value by scrolling with the mouse wheel over it, one [`Self::speed`] per notch.
+    /// Hold `Shift` for a tenth of that.
+    ///
+    /// Default: `false`.
+    #[inline]
+    pub fn scroll_to_adjust(mut self, scroll_to_adjust: bool) -> Self {
+        self.scroll_to_adjust = scroll_to_adjust;
+        self
+    }
+
+    /// Keep stepping, faster and faster, while a [`Self::stepper_buttons`] button is held down.
+    ///
+    /// Default: `true`.
+    #[inline]
+    pub fn hold_to_repeat(mut self, hold_to_repeat: bool) -> Self {
+        self.hold_to_repeat = hold_to_repeat;
+        self
+    }
 }
 
 impl<'a> Widget for DragValue<'a> {
     fn ui(self, ui: &mut Ui) -> Response {
+        if self.stepper_buttons {
+            ui.horizontal(|ui| self.add_contents(ui)).inner
+        } else {
+            self.add_contents(ui)
+        }
+    }
+}
+
+impl<'a> DragValue<'a> {
+    fn add_contents(self, ui: &mut Ui) -> Response {
         let Self {
             mut get_set_value,
             speed,
@@ -369,8 +416,17 @@ impl<'a> Widget for DragValue<'a> {
             custom_formatter,
             custom_parser,
             update_while_editing,
+            stepper_buttons,
+            scroll_to_adjust,
+            hold_to_repeat,
         } = self;
 
+        let decrements = if stepper_buttons {
+            super::stepper::stepper_button(ui, "−", hold_to_repeat)
+        } else {
+            0
+        };
+
         let shift = ui.input(|i| i.modifiers.shift_only());
         // The widget has the same ID whether it's in edit or button mode.
         let id = ui.next_auto_id();
@@ -415,6 +471,10 @@ impl<'a> Widget for DragValue<'a> {
                 // problematic.
                 change += input.count_and_consume_key(Modifiers::NONE, Key::ArrowUp) as f64
                     - input.count_and_consume_key(Modifiers::NONE, Key::ArrowDown) as f64;
+
+                // The gamepad stick works like dragging 200 points per second at full tilt:
+                let analog = input.gamepad.analog_adjust() * input.stable_dt.at_most(0.1);
+                change += (200.0 * analog) as f64;
             }
 
             #[cfg(feature = "accesskit")]
@@ -439,6 +499,10 @@ impl<'a> Widget for DragValue<'a> {
             });
         }
 
+        if is_kb_editing && ui.input(|i| i.gamepad.analog_adjust() != 0.0) {
+            ui.ctx().request_repaint_from(id); // Keep adjusting while the stick is held
+        }
+
         if change != 0.0 {
             value += speed * change;
             value = emath::round_to_decimals(value, auto_decimals);
@@ -475,6 +539,8 @@ impl<'a> Widget for DragValue<'a> {
                 if let Some(parsed_value) = parsed_value {
                     let parsed_value = clamp_to_range(parsed_value, clamp_range.clone());
                     set(&mut get_set_value, parsed_value);
+                } else {
+                    ui.ctx().emit_sound(UiSoundEvent::Error);
                 }
             }
         }
@@ -504,14 +570,21 @@ impl<'a> Widget for DragValue<'a> {
                 // Update only when the edit has lost focus.
                 response.lost_focus()
             };
-            if update {
+            if update || response.lost_focus() {
                 let parsed_value = match &custom_parser {
                     Some(parser) => parser(&value_text),
                     None => value_text.parse().ok(),
                 };
-                if let Some(parsed_value) = parsed_value {
-                    let parsed_value = clamp_to_range(parsed_value, clamp_range.clone());
-                    set(&mut get_set_value, parsed_value);
+                match parsed_value {
+                    Some(parsed_value) if update => {
+                        let parsed_value = clamp_to_range(parsed_value, clamp_range.clone());
+                        set(&mut get_set_value, parsed_value);
+                    }
+                    None if response.lost_focus() => {
+                        // The user is done editing, but we couldn't use what they typed:
+                        ui.ctx().emit_sound(UiSoundEvent::Error);
+                    }
+                    _ => {}
                 }
             }
             ui.data_mut(|data| data.insert_temp(id, value_text));
@@ -550,6 +623,8 @@ impl<'a> Widget for DragValue<'a> {
                     text::CCursor::default(),
                     text::CCursor::new(value_text.chars().count()),
                 )));
+                // Start blinking with the text cursor visible, see `Visuals::text_cursor`:
+                state.last_interaction_time = ui.input(|i| i.time);
                 state.store(ui.ctx(), response.id);
             } else if response.dragged() {
                 ui.ctx().set_cursor_icon(CursorIcon::ResizeHorizontal);
@@ -584,6 +659,31 @@ impl<'a> Widget for DragValue<'a> {
             response
         };
 
+        let increments = if stepper_buttons {
+            super::stepper::stepper_button(ui, "+", hold_to_repeat)
+        } else {
+            0
+        };
+        let mut steps = increments as f64 - decrements as f64;
+        if scroll_to_adjust {
+            steps += super::stepper::scroll_steps(ui, &response);
+        }
+        if steps != 0.0 {
+            let step = if shift { speed / 10.0 } else { speed };
+            // Make sure every step makes a difference to what is shown:
+            let step = step.max(10_f64.powi(-(max_decimals as i32)));
+            let decimals = (auto_decimals + shift as usize).at_most(max_decimals);
+            let new_value = get(&mut get_set_value) + step * steps;
+            let new_value = emath::round_to_decimals(new_value, decimals);
+            set(
+                &mut get_set_value,
+                clamp_to_range(new_value, clamp_range.clone()),
+            );
+            ui.data_mut(|data| data.remove::<String>(id));
+            // The text was already laid out with the old value:
+            ui.ctx().request_repaint_from(id);
+        }
+
         response.changed = get(&mut get_set_value) != old_value;
 
         response.widget_info(|| WidgetInfo::drag_value(value));
@@ -683,4 +783,24 @@ mod tests {
         total_assert_eq!(5.0_f64, clamp_to_range(15.0, 5.0..=1.0));
         total_assert_eq!(1.0_f64, clamp_to_range(-5.0, 5.0..=1.0));
     }
+
+    #[test]
+    fn test_stepper_buttons() {
+        use crate::{test_harness::Harness, DragValue, Modifiers};
+        use std::cell::Cell;
+
+        let value = Cell::new(1.0);
+        let mut harness = Harness::new_ui(|ui| {
+            let mut v = value.get();
+            ui.add(DragValue::new(&mut v).speed(0.5).stepper_buttons(true));
+            value.set(v);
+        });
+        harness.run();
+
+        harness.get_by_label("+").click();
+        assert_eq!(value.get(), 1.5);
+        harness.set_modifiers(Modifiers::SHIFT);
+        harness.get_by_label("−").click();
+        assert_eq!(value.get(), 1.45);
+    }
 }
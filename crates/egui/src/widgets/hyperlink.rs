@@ -122,20 +122,54 @@ impl Widget for Hyperlink {
         let Self { url, text, new_tab } = self;
 
         let response = ui.add(Link::new(text));
-
-        if response.clicked() {
-            let modifiers = ui.ctx().input(|i| i.modifiers);
-            ui.ctx().open_url(crate::OpenUrl {
-                url: url.clone(),
-                new_tab: new_tab || modifiers.any(),
-            });
-        }
-        if response.middle_clicked() {
-            ui.ctx().open_url(crate::OpenUrl {
-                url: url.clone(),
-                new_tab: true,
-            });
-        }
-        response.on_hover_text(url)
+        response.handle_hyperlink(&url, new_tab)
     }
 }
+
+#[test]
+fn test_hyperlink_behavior() {
+    use crate::test_harness::Harness;
+    use std::cell::RefCell;
+
+    let is_link = |info: &WidgetInfo| info.typ == WidgetType::Link;
+    let opened = RefCell::new(vec![]);
+    let mut harness = Harness::new_ui(|ui| {
+        ui.hyperlink("https://www.egui.rs");
+        ui.output(|o| {
+            if let Some(open_url) = &o.open_url {
+                opened
+                    .borrow_mut()
+                    .push(format!("{} {}", open_url.url, open_url.new_tab));
+            }
+            if !o.copied_text.is_empty() {
+                opened
+                    .borrow_mut()
+                    .push(format!("copied {}", o.copied_text));
+            }
+        });
+    });
+
+    harness.get_by(is_link).click();
+    assert_eq!(opened.take(), vec!["https://www.egui.rs false"]);
+
+    harness.set_modifiers(Modifiers::COMMAND);
+    harness.get_by(is_link).click();
+    assert_eq!(opened.take(), vec!["https://www.egui.rs true"]);
+
+    harness.ctx().style_mut(|style| {
+        let hyperlinks = &mut style.interaction.hyperlinks;
+        hyperlinks.copy_modifiers = Some(Modifiers::COMMAND);
+        hyperlinks.open_in_new_tab = true;
+    });
+    harness.get_by(is_link).click();
+    assert_eq!(opened.take(), vec!["copied https://www.egui.rs"]);
+
+    // On Linux and Windows, command is reported together with ctrl:
+    harness.set_modifiers(Modifiers::CTRL | Modifiers::COMMAND);
+    harness.get_by(is_link).click();
+    assert_eq!(opened.take(), vec!["copied https://www.egui.rs"]);
+
+    harness.set_modifiers(Modifiers::NONE);
+    harness.get_by(is_link).click();
+    assert_eq!(opened.take(), vec!["https://www.egui.rs true"]);
+}
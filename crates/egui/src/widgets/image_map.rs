@@ -0,0 +1,250 @@
+use crate::*;
+
+/// An [`Image`] with named regions (hotspots) that can be hovered and clicked,
+/// like the `<map>` of HTML.
+///
+/// Good for floor plans, anatomy diagrams, pinouts and the like.
+/// The regions are polygons in image coordinates (pixels of the original image),
+/// so they stay in place however large the image is shown.
+/// The region under the mouse is highlighted, and the last added region wins where they overlap.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// let map = egui::ImageMap::new(egui::Image::new(egui::include_image!("../../assets/ferris.png")))
+///     .rect_region("head", egui::Rect::from_min_max(egui::pos2(80.0, 20.0), egui::pos2(160.0, 80.0)))
+///     .region("claw", [egui::pos2(10.0, 60.0), egui::pos2(50.0, 40.0), egui::pos2(40.0, 90.0)])
+///     .show(ui);
+/// if let Some(clicked) = &map.clicked {
+///     println!("You clicked the {clicked}");
+/// }
+/// # });
+/// ```
+#[must_use = "You should put this widget in an ui with `ui.add(widget);`"]
+pub struct ImageMap<'a> {
+    image: Image<'a>,
+    regions: Vec<ImageMapRegion>,
+}
+
+/// A named polygon of an [`ImageMap`], in image coordinates.
+#[derive(Clone, Debug, PartialEq)]
+struct ImageMapRegion {
+    name: String,
+    points: Vec<Pos2>,
+}
+
+/// What [`ImageMap::show`] returns.
+pub struct ImageMapResponse {
+    /// The response of the whole image.
+    pub response: Response,
+
+    /// The name of the region under the mouse, if any.
+    pub hovered: Option<String>,
+
+    /// The name of the region that was clicked this frame, if any.
+    pub clicked: Option<String>,
+}
+
+impl<'a> ImageMap<'a> {
+    pub fn new(image: Image<'a>) -> Self {
+        Self {
+            image,
+            regions: vec![],
+        }
+    }
+
+    /// Add a region, with the corners of its outline in image coordinates.
+    ///
+    /// The polygon may be concave, but is only filled when hovered if it is convex.
+    #[inline]
+    pub fn region(
+        mut self,
+        name: impl Into<String>,
+        points: impl IntoIterator<Item = Pos2>,
+    ) -> Self {
+        self.regions.push(ImageMapRegion {
+            name: name.into(),
+            points: points.into_iter().collect(),
+        });
+        self
+    }
+
+    /// Add a rectangular region, in image coordinates.
+    #[inline]
+    pub fn rect_region(self, name: impl Into<String>, rect: Rect) -> Self {
+        self.region(
+            name,
+            [
+                rect.left_top(),
+                rect.right_top(),
+                rect.right_bottom(),
+                rect.left_bottom(),
+            ],
+        )
+    }
+
+    pub fn show(self, ui: &mut Ui) -> ImageMapResponse {
+        let Self { image, regions } = self;
+
+        let tlr = image.load_for_size(ui.ctx(), ui.available_size());
+        let original_image_size = tlr.as_ref().ok().and_then(|t| t.size());
+        let ui_size = image.calc_size(ui.available_size(), original_image_size);
+        let (rect, mut response) = ui.allocate_exact_size(ui_size, Sense::click());
+
+        // From image coordinates to the screen. Until the image is loaded we don't know its size:
+        let to_screen = original_image_size.map(|image_size| {
+            emath::RectTransform::from_to(Rect::from_min_size(Pos2::ZERO, image_size), rect)
+        });
+        let screen_regions: Vec<(&str, Vec<Pos2>)> = to_screen
+            .map(|to_screen| {
+                regions
+                    .iter()
+                    .map(|region| {
+                        let points = region.points.iter().map(|&p| to_screen * p).collect();
+                        (region.name.as_str(), points)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let hovered_index = response.hover_pos().and_then(|pos| {
+            screen_regions
+                .iter()
+                .rposition(|(_, points)| polygon_contains(points, pos))
+        });
+        let hovered = hovered_index.map(|i| screen_regions[i].0.to_owned());
+        let clicked = hovered.clone().filter(|_| response.clicked());
+
+        response.widget_info(|| {
+            WidgetInfo::labeled(WidgetType::Other, hovered.as_deref().unwrap_or_default())
+        });
+        if hovered.is_some() {
+            response = response.on_hover_cursor(CursorIcon::PointingHand);
+        }
+
+        if ui.is_rect_visible(rect) {
+            super::image::paint_texture_load_result(
+                ui,
+                &tlr,
+                rect,
+                image.show_loading_spinner,
+                image.image_options(),
+            );
+
+            if let Some(i) = hovered_index {
+                let points = screen_regions[i].1.clone();
+                let visuals = if response.is_pointer_button_down_on() {
+                    ui.visuals().widgets.active
+                } else {
+                    ui.visuals().widgets.hovered
+                };
+                let painter = ui.painter_at(rect);
+                if is_convex(&points) {
+                    let fill = ui.visuals().selection.bg_fill.gamma_multiply(0.5);
+                    painter.add(Shape::convex_polygon(points.clone(), fill, Stroke::NONE));
+                }
+                painter.add(Shape::closed_line(points, visuals.fg_stroke));
+            }
+        }
+
+        ImageMapResponse {
+            response,
+            hovered,
+            clicked,
+        }
+    }
+}
+
+impl<'a> Widget for ImageMap<'a> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        self.show(ui).response
+    }
+}
+
+/// Is the point inside the polygon? Uses the even-odd rule.
+fn polygon_contains(points: &[Pos2], p: Pos2) -> bool {
+    let mut inside = false;
+    let mut j = points.len().wrapping_sub(1);
+    for (i, a) in points.iter().enumerate() {
+        let b = points[j];
+        if (a.y > p.y) != (b.y > p.y) && p.x < a.x + (p.y - a.y) * (b.x - a.x) / (b.y - a.y) {
+            inside = !inside;
+        }
+        j = i;
+    }
+    inside
+}
+
+/// Do all the corners of the polygon turn the same way?
+fn is_convex(points: &[Pos2]) -> bool {
+    let n = points.len();
+    let mut sign = 0.0;
+    for i in 0..n {
+        let a = points[i];
+        let b = points[(i + 1) % n];
+        let c = points[(i + 2) % n];
+        let cross = (b - a).x * (c - b).y - (b - a).y * (c - b).x;
+        if cross != 0.0 {
+            if sign * cross < 0.0 {
+                return false;
+            }
+            sign = cross;
+        }
+    }
+    true
+}
+
+#[test]
+fn test_polygon_contains() {
+    // An L shape:
+    let points = [
+        pos2(0.0, 0.0),
+        pos2(10.0, 0.0),
+        pos2(10.0, 5.0),
+        pos2(5.0, 5.0),
+        pos2(5.0, 10.0),
+        pos2(0.0, 10.0),
+    ];
+    assert!(polygon_contains(&points, pos2(2.0, 8.0)));
+    assert!(polygon_contains(&points, pos2(8.0, 2.0)));
+    assert!(!polygon_contains(&points, pos2(8.0, 8.0)));
+    assert!(!polygon_contains(&points, pos2(-1.0, 2.0)));
+    assert!(!is_convex(&points));
+    assert!(is_convex(&points[..3]));
+}
+
+#[test]
+fn test_image_map() {
+    use crate::test_harness::Harness;
+    use std::cell::{Cell, RefCell};
+
+    let origin = Cell::new(Pos2::ZERO);
+    let clicked = RefCell::new(vec![]);
+    let hovered = RefCell::new(None);
+    let mut harness = Harness::new_ui(|ui| {
+        // Shown at twice the size of the image:
+        let image = Image::from_texture((TextureId::default(), vec2(100.0, 50.0)))
+            .fit_to_exact_size(vec2(200.0, 100.0));
+        let map = ImageMap::new(image)
+            .rect_region("left", Rect::from_min_max(pos2(0.0, 0.0), pos2(50.0, 50.0)))
+            .rect_region(
+                "right",
+                Rect::from_min_max(pos2(50.0, 0.0), pos2(100.0, 50.0)),
+            )
+            .region(
+                "on top",
+                [pos2(40.0, 10.0), pos2(60.0, 10.0), pos2(50.0, 30.0)],
+            )
+            .show(ui);
+        origin.set(map.response.rect.min);
+        clicked.borrow_mut().extend(map.clicked);
+        *hovered.borrow_mut() = map.hovered;
+    });
+    harness.run();
+    let origin = origin.get();
+
+    harness.hover(origin + vec2(20.0, 20.0));
+    assert_eq!(hovered.borrow().as_deref(), Some("left"));
+    harness.click(origin + vec2(180.0, 80.0));
+    harness.click(origin + vec2(100.0, 30.0));
+    assert_eq!(*clicked.borrow(), ["right", "on top"]);
+}
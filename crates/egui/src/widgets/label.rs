@@ -26,6 +26,7 @@ pub struct Label {
     truncate: bool,
     sense: Option<Sense>,
     selectable: Option<bool>,
+    vertical: bool,
 }
 
 impl Label {
@@ -36,6 +37,7 @@ impl Label {
             truncate: false,
             sense: None,
             selectable: None,
+            vertical: false,
         }
     }
 
@@ -105,14 +107,30 @@ impl Label {
         self.sense = Some(sense);
         self
     }
+
+    /// Write the text top-to-bottom, in columns from right to left,
+    /// e.g. for traditional Japanese and Chinese, or for the tabs on the side of a panel.
+    ///
+    /// CJK characters stay upright, while other text is turned a quarter turn clockwise.
+    /// Wrapping and truncation apply to the height of the columns.
+    /// Vertical text can't be selected.
+    ///
+    /// See [`crate::text::LayoutJob::vertical`].
+    #[inline]
+    pub fn vertical(mut self, vertical: bool) -> Self {
+        self.vertical = vertical;
+        self
+    }
 }
 
 impl Label {
     /// Do layout and position the galley in the ui, without painting it or adding widget info.
+    #[track_caller]
     pub fn layout_in_ui(self, ui: &mut Ui) -> (Pos2, Arc<Galley>, Response) {
-        let selectable = self
-            .selectable
-            .unwrap_or_else(|| ui.style().interaction.selectable_labels);
+        let selectable = !self.vertical
+            && self
+                .selectable
+                .unwrap_or_else(|| ui.style().interaction.selectable_labels);
 
         let mut sense = self.sense.unwrap_or_else(|| {
             if ui.memory(|mem| mem.options.screen_reader) {
@@ -160,6 +178,25 @@ impl Label {
         let wrap = !truncate && self.wrap.unwrap_or_else(|| ui.wrap_text());
         let available_width = ui.available_width();
 
+        if self.vertical {
+            layout_job.vertical = true;
+            layout_job.halign = Align::LEFT;
+            layout_job.justify = false;
+            let available_height = ui.available_height();
+            if truncate {
+                layout_job.wrap.max_width = available_height;
+                layout_job.wrap.max_rows = 1;
+                layout_job.wrap.break_anywhere = true;
+            } else if wrap && available_height.is_finite() {
+                layout_job.wrap.max_width = available_height;
+            } else {
+                layout_job.wrap.max_width = f32::INFINITY;
+            }
+            let galley = ui.fonts(|fonts| fonts.layout_job(layout_job));
+            let (rect, response) = ui.allocate_exact_size(galley.size(), sense);
+            return (rect.left_top(), galley, response);
+        }
+
         if wrap
             && ui.layout().main_dir() == Direction::LeftToRight
             && ui.layout().main_wrap()
@@ -230,7 +267,11 @@ impl Widget for Label {
         // the cursor is enough to communicate that.
         let interactive = self.sense.map_or(false, |sense| sense != Sense::hover());
 
-        let selectable = self.selectable;
+        let selectable = if self.vertical {
+            Some(false)
+        } else {
+            self.selectable
+        };
 
         let (galley_pos, galley, mut response) = self.layout_in_ui(ui);
         response.widget_info(|| WidgetInfo::labeled(WidgetType::Label, galley.text()));
@@ -239,6 +280,8 @@ impl Widget for Label {
             if galley.elided {
                 // Show the full (non-elided) text on hover:
                 response = response.on_hover_text(galley.text());
+            } else {
+                response = ui.on_hover_text_if_clipped(response, &galley, galley_pos);
             }
 
             let response_color = if interactive {
@@ -7,29 +7,38 @@
 use crate::*;
 
 mod button;
+mod chip;
 pub mod color_picker;
 pub(crate) mod drag_value;
 mod hyperlink;
 mod image;
+mod image_map;
 mod label;
 mod progress_bar;
+mod segmented_control;
 mod selected_label;
 mod separator;
 mod slider;
 mod spinner;
+mod stepper;
 pub mod text_edit;
+mod toggle_switch;
 
 pub use button::*;
+pub use chip::Chip;
 pub use drag_value::DragValue;
 pub use hyperlink::*;
 pub use image::{paint_texture_at, Image, ImageFit, ImageOptions, ImageSize, ImageSource};
+pub use image_map::{ImageMap, ImageMapResponse};
 pub use label::*;
 pub use progress_bar::ProgressBar;
+pub use segmented_control::SegmentedControl;
 pub use selected_label::SelectableLabel;
 pub use separator::Separator;
 pub use slider::*;
 pub use spinner::*;
-pub use text_edit::{TextBuffer, TextEdit};
+pub use text_edit::{RopeBuffer, TextBuffer, TextEdit};
+pub use toggle_switch::ToggleSwitch;
 
 // ----------------------------------------------------------------------------
 
@@ -55,6 +64,7 @@ pub trait Widget {
     /// [builders](https://doc.rust-lang.org/1.0.0/style/ownership/builders.html)
     ///
     /// Tip: you can `impl Widget for &mut YourObject { }`.
+    #[track_caller]
     fn ui(self, ui: &mut Ui) -> Response;
 }
 
@@ -115,7 +115,7 @@ impl Widget for ProgressBar {
 
         if ui.is_rect_visible(response.rect) {
             if animate {
-                ui.ctx().request_repaint();
+                ui.ctx().request_repaint_from(response.id);
             }
 
             let visuals = ui.style().visuals.clone();
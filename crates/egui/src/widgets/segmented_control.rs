@@ -0,0 +1,165 @@
+use crate::*;
+
+/// A row of joined buttons, for picking one out of a few values, e.g. the variants of an enum.
+///
+/// An alternative to a group of [`RadioButton`]s, or a [`ComboBox`].
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// #[derive(PartialEq)]
+/// enum View { List, Grid, Columns }
+/// let mut view = View::List;
+///
+/// ui.add(
+///     egui::SegmentedControl::new(&mut view)
+///         .segment(View::List, "List")
+///         .segment(View::Grid, "Grid")
+///         .segment(View::Columns, "Columns"),
+/// );
+/// # });
+/// ```
+#[must_use = "You should put this widget in an ui with `ui.add(widget);`"]
+pub struct SegmentedControl<'a, Value> {
+    current_value: &'a mut Value,
+    segments: Vec<(Value, WidgetText)>,
+}
+
+impl<'a, Value: PartialEq> SegmentedControl<'a, Value> {
+    pub fn new(current_value: &'a mut Value) -> Self {
+        Self {
+            current_value,
+            segments: Vec::new(),
+        }
+    }
+
+    /// Add a segment that selects `value` when clicked.
+    #[inline]
+    pub fn segment(mut self, value: Value, text: impl Into<WidgetText>) -> Self {
+        self.segments.push((value, text.into()));
+        self
+    }
+}
+
+impl<'a, Value: PartialEq> Widget for SegmentedControl<'a, Value> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let Self {
+            current_value,
+            segments,
+        } = self;
+
+        let button_padding = ui.spacing().button_padding;
+        let segments: Vec<(Value, std::sync::Arc<Galley>)> = segments
+            .into_iter()
+            .map(|(value, text)| {
+                let galley = text.into_galley(ui, Some(false), f32::INFINITY, TextStyle::Button);
+                (value, galley)
+            })
+            .collect();
+
+        let widths: Vec<f32> = segments
+            .iter()
+            .map(|(_, galley)| galley.size().x + 2.0 * button_padding.x)
+            .collect();
+        let height = segments
+            .iter()
+            .map(|(_, galley)| galley.size().y + 2.0 * button_padding.y)
+            .fold(ui.spacing().interact_size.y, f32::max);
+        let (rect, mut response) =
+            ui.allocate_exact_size(vec2(widths.iter().sum(), height), Sense::hover());
+
+        let rounding = ui.visuals().segmented_control_rounding;
+        let separator_stroke = ui.visuals().widgets.noninteractive.bg_stroke;
+        if ui.is_rect_visible(rect) {
+            ui.painter().rect(
+                rect,
+                rounding,
+                ui.visuals().widgets.inactive.weak_bg_fill,
+                separator_stroke,
+            );
+        }
+
+        let num_segments = segments.len();
+        let mut left = rect.left();
+        for (i, ((value, galley), width)) in segments.into_iter().zip(widths).enumerate() {
+            let segment_rect = Rect::from_x_y_ranges(left..=left + width, rect.y_range());
+            left += width;
+
+            let mut segment_response = ui
+                .interact(segment_rect, response.id.with(i), Sense::click())
+                .with_extra_pointers();
+            let mut selected = *current_value == value;
+            if segment_response.clicked() && !selected {
+                *current_value = value;
+                selected = true;
+                segment_response.mark_changed();
+            }
+            segment_response.widget_info(|| {
+                WidgetInfo::selected(WidgetType::SelectableLabel, selected, galley.text())
+            });
+
+            if ui.is_rect_visible(segment_rect) {
+                let visuals = ui.style().interact_selectable(&segment_response, selected);
+
+                if selected || segment_response.hovered() || segment_response.has_focus() {
+                    // Only the outer corners are rounded:
+                    let first = i == 0;
+                    let last = i + 1 == num_segments;
+                    let segment_rounding = Rounding {
+                        nw: if first { rounding.nw } else { 0.0 },
+                        sw: if first { rounding.sw } else { 0.0 },
+                        ne: if last { rounding.ne } else { 0.0 },
+                        se: if last { rounding.se } else { 0.0 },
+                    };
+                    ui.painter()
+                        .rect_filled(segment_rect, segment_rounding, visuals.weak_bg_fill);
+                }
+
+                if 0 < i {
+                    ui.painter().vline(
+                        segment_rect.left(),
+                        segment_rect.y_range(),
+                        separator_stroke,
+                    );
+                }
+
+                let text_pos = segment_rect.center() - galley.size() / 2.0;
+                ui.painter().galley(text_pos, galley, visuals.text_color());
+            }
+
+            response = response.union(segment_response);
+        }
+
+        response
+    }
+}
+
+#[test]
+fn test_segmented_control() {
+    use crate::test_harness::Harness;
+    use std::cell::Cell;
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    enum View {
+        List,
+        Grid,
+    }
+
+    let view = Cell::new(View::List);
+    let changed = Cell::new(false);
+    let mut harness = Harness::new_ui(|ui| {
+        let mut value = view.get();
+        let response = ui.add(
+            SegmentedControl::new(&mut value)
+                .segment(View::List, "List")
+                .segment(View::Grid, "Grid"),
+        );
+        changed.set(changed.get() || response.changed());
+        view.set(value);
+    });
+
+    assert_eq!(harness.get_by_label("List").info().selected, Some(true));
+    harness.get_by_label("Grid").click();
+    assert_eq!(view.get(), View::Grid);
+    assert!(changed.get());
+    assert_eq!(harness.get_by_label("Grid").info().selected, Some(true));
+}
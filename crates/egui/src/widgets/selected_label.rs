@@ -49,6 +49,7 @@ impl Widget for SelectableLabel {
         let mut desired_size = total_extra + galley.size();
         desired_size.y = desired_size.y.at_least(ui.spacing().interact_size.y);
         let (rect, response) = ui.allocate_at_least(desired_size, Sense::click());
+        let mut response = response.with_extra_pointers();
         response.widget_info(|| {
             WidgetInfo::selected(WidgetType::SelectableLabel, selected, galley.text())
         });
@@ -72,6 +73,7 @@ impl Widget for SelectableLabel {
                 );
             }
 
+            response = ui.on_hover_text_if_clipped(response, &galley, text_pos);
             ui.painter().galley(text_pos, galley, visuals.text_color());
         }
 
@@ -39,7 +39,21 @@ struct SliderSpec {
     largest_finite: f64,
 }
 
+/// A tick mark along a [`Slider`]. Only the major ones get a label.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Tick {
+    value: f64,
+    major: bool,
+}
+
+/// Tick marks closer together than this, in points, are left out.
+const MIN_TICK_SPACING: f64 = 4.0;
+
+/// Automatic labelled tick marks are about this far apart, in points.
+const TICK_LABEL_SPACING: f64 = 50.0;
+
 /// Specifies the orientation of a [`Slider`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum SliderOrientation {
     Horizontal,
     Vertical,
@@ -65,6 +79,9 @@ pub enum SliderOrientation {
 /// ```
 ///
 /// The default [`Slider`] size is set by [`crate::style::Spacing::slider_width`].
+///
+/// Use [`Slider::range_mode`] to pick a range of values with two handles,
+/// and [`Slider::ticks`] to show tick marks along the slider.
 #[must_use = "You should put this widget in an ui with `ui.add(widget);`"]
 pub struct Slider<'a> {
     get_set_value: GetSetValue<'a>,
@@ -88,6 +105,21 @@ pub struct Slider<'a> {
     custom_parser: Option<NumParser<'a>>,
     trailing_fill: Option<bool>,
     handle_shape: Option<HandleShape>,
+
+    /// The upper value of a [`Self::range_mode`] slider, whose lower value is `get_set_value`.
+    get_set_upper_value: Option<GetSetValue<'a>>,
+
+    /// Which handle of a [`Self::range_mode`] slider `get_value` and `set_value` are for.
+    upper_active: bool,
+
+    ticks: bool,
+    custom_ticks: Option<Vec<f64>>,
+    tick_labels: bool,
+    snap_to_ticks: bool,
+    haptics: bool,
+    stepper_buttons: bool,
+    scroll_to_adjust: bool,
+    hold_to_repeat: bool,
 }
 
 impl<'a> Slider<'a> {
@@ -135,9 +167,45 @@ impl<'a> Slider<'a> {
             custom_parser: None,
             trailing_fill: None,
             handle_shape: None,
+            get_set_upper_value: None,
+            upper_active: false,
+            ticks: false,
+            custom_ticks: None,
+            tick_labels: false,
+            snap_to_ticks: false,
+            haptics: true,
+            stepper_buttons: false,
+            scroll_to_adjust: false,
+            hold_to_repeat: true,
         }
     }
 
+    /// Creates a new horizontal slider with two handles, for picking a range of values.
+    ///
+    /// The first value is kept at most the second one.
+    /// The arrow keys move the handle that was pressed last.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// # let mut price_range: (f32, f32) = (20.0, 80.0);
+    /// ui.add(egui::Slider::range_mode(&mut price_range, 0.0..=100.0).text("Price"));
+    /// # });
+    /// ```
+    pub fn range_mode<Num: emath::Numeric>(
+        values: &'a mut (Num, Num),
+        range: RangeInclusive<Num>,
+    ) -> Self {
+        let (low, high) = values;
+        let mut slf = Self::new(low, range);
+        slf.get_set_upper_value = Some(Box::new(move |v: Option<f64>| {
+            if let Some(v) = v {
+                *high = Num::from_f64(v);
+            }
+            high.to_f64()
+        }));
+        slf
+    }
+
     /// Control whether or not the slider shows the current value.
     /// Default: `true`.
     #[inline]
@@ -314,6 +382,46 @@ impl<'a> Slider<'a> {
         self
     }
 
+    /// Show tick marks along the slider.
+    ///
+    /// They are at the powers of ten on a [logarithmic](Self::logarithmic) slider,
+    /// at each step of [`Self::step_by`] if there is room for them,
+    /// and otherwise at round numbers.
+    #[inline]
+    pub fn ticks(mut self, ticks: bool) -> Self {
+        self.ticks = ticks;
+        self
+    }
+
+    /// Show tick marks at these values, instead of the automatic ones.
+    ///
+    /// Implies [`Self::ticks`].
+    #[inline]
+    pub fn custom_ticks(mut self, ticks: impl IntoIterator<Item = f64>) -> Self {
+        self.ticks = true;
+        self.custom_ticks = Some(ticks.into_iter().collect());
+        self
+    }
+
+    /// Show the values of the tick marks next to them, formatted like the value of the slider.
+    ///
+    /// Implies [`Self::ticks`].
+    #[inline]
+    pub fn tick_labels(mut self, tick_labels: bool) -> Self {
+        self.ticks |= tick_labels;
+        self.tick_labels = tick_labels;
+        self
+    }
+
+    /// Only pick the values of the tick marks when dragging the slider or using the arrow keys.
+    ///
+    /// Typing a value into the value display is not affected.
+    #[inline]
+    pub fn snap_to_ticks(mut self, snap_to_ticks: bool) -> Self {
+        self.snap_to_ticks = snap_to_ticks;
+        self
+    }
+
     /// Set custom formatter defining how numbers are converted into text.
     ///
     /// A custom formatter takes a `f64` for the numeric value and a `RangeInclusive<usize>` representing
@@ -511,6 +619,47 @@ impl<'a> Slider<'a> {
         .custom_parser(|s| i64::from_str_radix(s, 16).map(|n| n as f64).ok())
     }
 
+    /// Emit a [`crate::output::HapticKind::Detent`] when dragging passes a step or tick?
+    ///
+    /// Default: `true`, but only if [`crate::style::Interaction::emit_haptics`] is set too.
+    #[inline]
+    pub fn haptics(mut self, haptics: bool) -> Self {
+        self.haptics = haptics;
+        self
+    }
+
+    /// Show a "−" button before the slider and a "+" button after it.
+    ///
+    /// Each click moves the value as far as the arrow keys do: one [`Self::step_by`],
+    /// to the next tick with [`Self::snap_to_ticks`], or else one point along the slider.
+    /// Without a step, hold `Shift` for a tenth of a point.
+    ///
+    /// Default: `false`.
+    #[inline]
+    pub fn stepper_buttons(mut self, stepper_buttons: bool) -> Self {
+        self.stepper_buttons = stepper_buttons;
+        self
+    }
+
+    /// Change the value by scrolling with the mouse wheel over the slider,
+    /// one arrow-key step per notch (see [`Self::stepper_buttons`]).
+    ///
+    /// Default: `false`.
+    #[inline]
+    pub fn scroll_to_adjust(mut self, scroll_to_adjust: bool) -> Self {
+        self.scroll_to_adjust = scroll_to_adjust;
+        self
+    }
+
+    /// Keep stepping, faster and faster, while a [`Self::stepper_buttons`] button is held down.
+    ///
+    /// Default: `true`.
+    #[inline]
+    pub fn hold_to_repeat(mut self, hold_to_repeat: bool) -> Self {
+        self.hold_to_repeat = hold_to_repeat;
+        self
+    }
+
     /// Helper: equivalent to `self.precision(0).smallest_positive(1.0)`.
     /// If you use one of the integer constructors (e.g. `Slider::i32`) this is called for you,
     /// but if you want to have a slider for picking integer values in an `Slider::f64`, use this.
@@ -518,8 +667,23 @@ impl<'a> Slider<'a> {
         self.fixed_decimals(0).smallest_positive(1.0).step_by(1.0)
     }
 
+    fn get_set(&mut self, upper: bool) -> &mut GetSetValue<'a> {
+        match &mut self.get_set_upper_value {
+            Some(get_set_upper_value) if upper => get_set_upper_value,
+            _ => &mut self.get_set_value,
+        }
+    }
+
+    fn is_range_mode(&self) -> bool {
+        self.get_set_upper_value.is_some()
+    }
+
     fn get_value(&mut self) -> f64 {
-        let value = get(&mut self.get_set_value);
+        self.get_handle_value(self.upper_active)
+    }
+
+    fn get_handle_value(&mut self, upper: bool) -> f64 {
+        let value = get(self.get_set(upper));
         if self.clamp_to_range {
             let start = *self.range.start();
             let end = *self.range.end();
@@ -542,7 +706,16 @@ impl<'a> Slider<'a> {
             let start = *self.range.start();
             value = start + ((value - start) / step).round() * step;
         }
-        set(&mut self.get_set_value, value);
+        if self.is_range_mode() {
+            // Keep the handles in order:
+            let other = self.get_handle_value(!self.upper_active);
+            value = if self.upper_active {
+                value.max(other)
+            } else {
+                value.min(other)
+            };
+        }
+        set(self.get_set(self.upper_active), value);
     }
 
     fn clamp_range(&self) -> RangeInclusive<f64> {
@@ -567,29 +740,237 @@ impl<'a> Slider<'a> {
         let normalized = normalized_from_value(value, self.range(), &self.spec);
         lerp(position_range, normalized as f32)
     }
+
+    fn tick_values(&self, position_range: Rangef) -> Vec<Tick> {
+        let (start, end) = (*self.range.start(), *self.range.end());
+        let (min, max) = (start.min(end), start.max(end));
+        let span = position_range.span().abs() as f64;
+
+        if let Some(custom_ticks) = &self.custom_ticks {
+            custom_ticks
+                .iter()
+                .filter(|value| (min..=max).contains(*value))
+                .map(|&value| Tick { value, major: true })
+                .collect()
+        } else if !(0.0 < span && min < max) {
+            vec![]
+        } else if self.spec.logarithmic {
+            logarithmic_ticks(min, max, &self.spec, span)
+        } else if let Some(step) = self.step.filter(|&step| {
+            0.0 < step && (max - min) / step * MIN_TICK_SPACING <= span && min.is_finite()
+        }) {
+            let num_steps = ((max - min) / step).floor() as i64;
+            let label_every = ((num_steps as f64 * TICK_LABEL_SPACING / span).ceil() as i64).max(1);
+            (0..=num_steps)
+                .map(|i| Tick {
+                    value: start.min(end) + i as f64 * step,
+                    major: i % label_every == 0,
+                })
+                .collect()
+        } else if min.is_finite() && max.is_finite() {
+            let step = nice_step((max - min) * TICK_LABEL_SPACING / span);
+            let first = (min / step).ceil() as i64;
+            let last = (max / step).floor() as i64;
+            (first..=last)
+                .map(|i| Tick {
+                    value: i as f64 * step,
+                    major: true,
+                })
+                .collect()
+        } else {
+            vec![]
+        }
+    }
+
+    /// The value of the tick closest to the position.
+    fn nearest_tick(&self, ticks: &[Tick], position: f32, position_range: Rangef) -> Option<f64> {
+        ticks
+            .iter()
+            .map(|tick| {
+                let distance =
+                    (self.position_from_value(tick.value, position_range) - position).abs();
+                (tick.value, distance)
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(value, _)| value)
+    }
+
+    /// Move the active handle this many steps towards larger values (or smaller, if negative),
+    /// as far as the arrow keys would move it.
+    fn step_value(
+        &mut self,
+        steps: f64,
+        ui: &Ui,
+        position_range: Rangef,
+        ticks: &[Tick],
+        snap_to_ticks: bool,
+    ) {
+        let prev_value = self.get_value();
+        let (start, end) = (*self.range.start(), *self.range.end());
+        // Which way along the slider the value gets larger:
+        let larger = if (start <= end) == (position_range.min <= position_range.max) {
+            1.0
+        } else {
+            -1.0
+        };
+        let position_steps = (larger * steps) as f32;
+
+        let new_value = match self.step {
+            _ if snap_to_ticks => {
+                self.tick_after(ticks, prev_value, position_steps, position_range)
+            }
+            Some(step) => prev_value + steps * step,
+            None => {
+                let prev_position = self.position_from_value(prev_value, position_range);
+                if ui.input(|i| i.modifiers.shift_only()) {
+                    let new_position = prev_position + 0.1 * position_steps;
+                    self.value_from_position(new_position, position_range)
+                } else {
+                    let new_position = prev_position + position_steps;
+                    let aim_radius = 0.49; // Chosen so we don't include `prev_value` in the search.
+                    emath::smart_aim::best_in_range_f64(
+                        self.value_from_position(new_position - aim_radius, position_range),
+                        self.value_from_position(new_position + aim_radius, position_range),
+                    )
+                }
+            }
+        };
+        self.set_value(new_value);
+    }
+
+    /// The value of the tick `steps` ticks away from `value`, in the direction of increasing position for positive `steps`.
+    fn tick_after(&self, ticks: &[Tick], value: f64, steps: f32, position_range: Rangef) -> f64 {
+        let mut value = value;
+        let mut position = self.position_from_value(value, position_range);
+        for _ in 0..steps.abs() as usize {
+            let next = ticks
+                .iter()
+                .map(|tick| {
+                    let tick_position = self.position_from_value(tick.value, position_range);
+                    (tick.value, tick_position)
+                })
+                .filter(|(_, tick_position)| (tick_position - position) * steps.signum() > 0.5)
+                .min_by(|a, b| (a.1 - position).abs().total_cmp(&(b.1 - position).abs()));
+            let Some((tick_value, tick_position)) = next else {
+                break;
+            };
+            value = tick_value;
+            position = tick_position;
+        }
+        value
+    }
+
+    fn format_tick(&self, value: f64) -> String {
+        let max_decimals = self.max_decimals.unwrap_or(6).max(self.min_decimals);
+        let decimals = self.min_decimals..=max_decimals;
+        if let Some(custom_formatter) = &self.custom_formatter {
+            custom_formatter(value, decimals)
+        } else {
+            emath::format_with_decimals_in_range(value, decimals)
+        }
+    }
 }
 
 impl<'a> Slider<'a> {
     /// Just the slider, no text
     fn allocate_slider_space(&self, ui: &mut Ui, thickness: f32) -> Response {
+        let thickness = thickness + self.tick_label_space(ui);
         let desired_size = match self.orientation {
             SliderOrientation::Horizontal => vec2(ui.spacing().slider_width, thickness),
             SliderOrientation::Vertical => vec2(thickness, ui.spacing().slider_width),
         };
         ui.allocate_response(desired_size, Sense::drag())
+            .with_extra_pointers()
+    }
+
+    /// The room for the tick labels, below a horizontal slider or to the right of a vertical one.
+    fn tick_label_space(&self, ui: &Ui) -> f32 {
+        if !self.tick_labels {
+            return 0.0;
+        }
+        let font_id = TextStyle::Small.resolve(ui.style());
+        match self.orientation {
+            SliderOrientation::Horizontal => ui.fonts(|fonts| fonts.row_height(&font_id)),
+            SliderOrientation::Vertical => {
+                let mut values = vec![*self.range.start(), *self.range.end()];
+                values.extend(self.custom_ticks.iter().flatten());
+                values
+                    .into_iter()
+                    .map(|value| {
+                        let text = self.format_tick(value);
+                        ui.fonts(|fonts| {
+                            fonts
+                                .layout_no_wrap(text, font_id.clone(), Color32::PLACEHOLDER)
+                                .size()
+                                .x
+                        })
+                    })
+                    .fold(0.0, f32::max)
+                    + ui.spacing().item_spacing.x
+            }
+        }
+    }
+
+    /// The part of the slider rect with the rail and handles, without the tick labels.
+    fn handle_area(&self, ui: &Ui, rect: Rect) -> Rect {
+        let mut rect = rect;
+        match self.orientation {
+            SliderOrientation::Horizontal => rect.max.y -= self.tick_label_space(ui),
+            SliderOrientation::Vertical => rect.max.x -= self.tick_label_space(ui),
+        }
+        rect
+    }
+
+    /// Decide which handle of a range slider the pointer and arrow keys move: the one pressed last.
+    fn pick_active_handle(&mut self, ui: &Ui, response: &Response, position_range: Rangef) {
+        let id = response.id.with("upper_active");
+        let pressed = ui.input(|i| i.pointer.any_pressed()) && response.is_pointer_button_down_on();
+        if let (true, Some(pointer_position_2d)) = (pressed, response.interact_pointer_pos()) {
+            let position = self.pointer_position(pointer_position_2d);
+            let low = self.get_handle_value(false);
+            let low_position = self.position_from_value(low, position_range);
+            let high = self.get_handle_value(true);
+            let high_position = self.position_from_value(high, position_range);
+            let (low_distance, high_distance) = (
+                (position - low_position).abs(),
+                (position - high_position).abs(),
+            );
+            // When the handles are on top of each other, pick the one on the side of the pointer:
+            let towards_high = (position - low_position) * position_range.span() > 0.0;
+            self.upper_active =
+                high_distance < low_distance || (high_distance == low_distance && towards_high);
+            ui.data_mut(|data| data.insert_temp(id, self.upper_active));
+        } else {
+            self.upper_active = ui.data(|data| data.get_temp(id)).unwrap_or(false);
+        }
     }
 
     /// Just the slider, no text
     fn slider_ui(&mut self, ui: &Ui, response: &Response) {
-        let rect = &response.rect;
+        let rect = &self.handle_area(ui, response.rect);
         let handle_shape = self
             .handle_shape
             .unwrap_or_else(|| ui.style().visuals.handle_shape);
         let position_range = self.position_range(rect, &handle_shape);
 
+        if self.is_range_mode() {
+            self.pick_active_handle(ui, response, position_range);
+        }
+
+        let ticks = if self.ticks || self.snap_to_ticks {
+            self.tick_values(position_range)
+        } else {
+            vec![]
+        };
+        let snap_to_ticks = self.snap_to_ticks && !ticks.is_empty();
+
         if let Some(pointer_position_2d) = response.interact_pointer_pos() {
+            let prev_value = self.get_value();
             let position = self.pointer_position(pointer_position_2d);
-            let new_value = if self.smart_aim {
+            let new_value = if snap_to_ticks {
+                self.nearest_tick(&ticks, position, position_range)
+                    .unwrap_or_default()
+            } else if self.smart_aim {
                 let aim_radius = ui.input(|i| i.aim_radius());
                 emath::smart_aim::best_in_range_f64(
                     self.value_from_position(position - aim_radius, position_range),
@@ -599,6 +980,12 @@ impl<'a> Slider<'a> {
                 self.value_from_position(position, position_range)
             };
             self.set_value(new_value);
+
+            // Only steps and ticks are detents; a free slider moves smoothly:
+            let has_detents = snap_to_ticks || self.step.is_some();
+            if self.haptics && has_detents && self.get_value() != prev_value {
+                ui.ctx().emit_haptic(crate::output::HapticKind::Detent);
+            }
         }
 
         let mut decrement = 0usize;
@@ -651,6 +1038,7 @@ impl<'a> Slider<'a> {
             let prev_position = self.position_from_value(prev_value, position_range);
             let new_position = prev_position + ui_point_per_step * kb_step;
             let new_value = match self.step {
+                _ if snap_to_ticks => self.tick_after(&ticks, prev_value, kb_step, position_range),
                 Some(step) => prev_value + (kb_step as f64 * step),
                 None if self.smart_aim => {
                     let aim_radius = 0.49 * ui_point_per_step; // Chosen so we don't include `prev_value` in the search.
@@ -664,6 +1052,30 @@ impl<'a> Slider<'a> {
             self.set_value(new_value);
         }
 
+        if self.scroll_to_adjust {
+            let steps = super::stepper::scroll_steps(ui, response);
+            if steps != 0.0 {
+                self.step_value(steps, ui, position_range, &ticks, snap_to_ticks);
+            }
+        }
+
+        if response.has_focus() {
+            let analog = ui.input(|i| i.gamepad.analog_adjust() * i.stable_dt.at_most(0.1));
+            if analog != 0.0 {
+                // At full tilt, the gamepad stick moves across the whole slider in two seconds.
+                // Note that vertical sliders have the highest value at the top:
+                let direction = match self.orientation {
+                    SliderOrientation::Horizontal => 1.0,
+                    SliderOrientation::Vertical => -1.0,
+                };
+                let prev_value = self.get_value();
+                let prev_position = self.position_from_value(prev_value, position_range);
+                let new_position = prev_position + direction * 0.5 * position_range.span() * analog;
+                self.set_value(self.value_from_position(new_position, position_range));
+                ui.ctx().request_repaint_from(response.id);
+            }
+        }
+
         #[cfg(feature = "accesskit")]
         {
             use accesskit::{Action, ActionData};
@@ -679,6 +1091,9 @@ impl<'a> Slider<'a> {
         // Paint it:
         if ui.is_rect_visible(response.rect) {
             let value = self.get_value();
+            let other_value = self
+                .is_range_mode()
+                .then(|| self.get_handle_value(!self.upper_active));
 
             let rail_radius = ui.painter().round_to_pixel(self.rail_radius_limit(rect));
             let rail_rect = self.rail_rect(rect, rail_radius);
@@ -700,52 +1115,138 @@ impl<'a> Slider<'a> {
                 .trailing_fill
                 .unwrap_or_else(|| ui.visuals().slider_trailing_fill);
 
-            // Paint trailing fill.
-            if trailing_fill {
-                let mut trailing_rail_rect = rail_rect;
+            let other_center = other_value.map(|other_value| {
+                self.marker_center(
+                    self.position_from_value(other_value, position_range),
+                    &rail_rect,
+                )
+            });
 
-                // The trailing rect has to be drawn differently depending on the orientation.
-                match self.orientation {
-                    SliderOrientation::Vertical => trailing_rail_rect.min.y = center.y,
-                    SliderOrientation::Horizontal => trailing_rail_rect.max.x = center.x,
+            // Paint trailing fill, or the fill between the handles of a range slider.
+            if trailing_fill || other_center.is_some() {
+                let mut fill_rect = rail_rect;
+
+                // The fill rect has to be drawn differently depending on the orientation.
+                match (self.orientation, other_center) {
+                    (SliderOrientation::Vertical, None) => fill_rect.min.y = center.y,
+                    (SliderOrientation::Horizontal, None) => fill_rect.max.x = center.x,
+                    (SliderOrientation::Vertical, Some(other_center)) => {
+                        fill_rect.min.y = center.y.min(other_center.y);
+                        fill_rect.max.y = center.y.max(other_center.y);
+                    }
+                    (SliderOrientation::Horizontal, Some(other_center)) => {
+                        fill_rect.min.x = center.x.min(other_center.x);
+                        fill_rect.max.x = center.x.max(other_center.x);
+                    }
                 };
 
                 ui.painter().rect_filled(
-                    trailing_rail_rect,
+                    fill_rect,
                     widget_visuals.inactive.rounding,
                     ui.visuals().selection.bg_fill,
                 );
             }
 
-            let radius = self.handle_radius(rect);
+            if self.ticks {
+                self.paint_ticks(ui, &ticks, position_range, &rail_rect, rect);
+            }
 
-            let handle_shape = self
-                .handle_shape
-                .unwrap_or_else(|| ui.style().visuals.handle_shape);
-            match handle_shape {
-                style::HandleShape::Circle => {
-                    ui.painter().add(epaint::CircleShape {
-                        center,
-                        radius: radius + visuals.expansion,
-                        fill: visuals.bg_fill,
-                        stroke: visuals.fg_stroke,
-                    });
-                }
-                style::HandleShape::Rect { aspect_ratio } => {
-                    let v = match self.orientation {
-                        SliderOrientation::Horizontal => Vec2::new(radius * aspect_ratio, radius),
-                        SliderOrientation::Vertical => Vec2::new(radius, radius * aspect_ratio),
-                    };
-                    let v = v + Vec2::splat(visuals.expansion);
-                    let rect = Rect::from_center_size(center, 2.0 * v);
-                    ui.painter().add(epaint::RectShape {
-                        fill: visuals.bg_fill,
-                        stroke: visuals.fg_stroke,
-                        rect,
-                        rounding: visuals.rounding,
-                        fill_texture_id: Default::default(),
-                        uv: Rect::ZERO,
-                    });
+            if let Some(other_center) = other_center {
+                let inactive_visuals = if response.hovered() || response.dragged() {
+                    &widget_visuals.hovered
+                } else {
+                    &widget_visuals.inactive
+                };
+                self.paint_handle(ui, other_center, rect, inactive_visuals);
+            }
+            // The active handle goes on top:
+            self.paint_handle(ui, center, rect, visuals);
+        }
+    }
+
+    fn paint_handle(&self, ui: &Ui, center: Pos2, rect: &Rect, visuals: &style::WidgetVisuals) {
+        let radius = self.handle_radius(rect);
+
+        let handle_shape = self
+            .handle_shape
+            .unwrap_or_else(|| ui.style().visuals.handle_shape);
+        match handle_shape {
+            style::HandleShape::Circle => {
+                ui.painter().add(epaint::CircleShape {
+                    center,
+                    radius: radius + visuals.expansion,
+                    fill: visuals.bg_fill,
+                    stroke: visuals.fg_stroke,
+                });
+            }
+            style::HandleShape::Rect { aspect_ratio } => {
+                let v = match self.orientation {
+                    SliderOrientation::Horizontal => Vec2::new(radius * aspect_ratio, radius),
+                    SliderOrientation::Vertical => Vec2::new(radius, radius * aspect_ratio),
+                };
+                let v = v + Vec2::splat(visuals.expansion);
+                let rect = Rect::from_center_size(center, 2.0 * v);
+                ui.painter().add(epaint::RectShape {
+                    fill: visuals.bg_fill,
+                    stroke: visuals.fg_stroke,
+                    rect,
+                    rounding: visuals.rounding,
+                    fill_texture_id: Default::default(),
+                    uv: Rect::ZERO,
+                });
+            }
+        }
+    }
+
+    /// Paint the tick marks next to the rail, and their labels in the room left for them by [`Self::handle_area`].
+    fn paint_ticks(
+        &self,
+        ui: &Ui,
+        ticks: &[Tick],
+        position_range: Rangef,
+        rail_rect: &Rect,
+        handle_area: &Rect,
+    ) {
+        let color = ui.visuals().weak_text_color();
+        let font_id = TextStyle::Small.resolve(ui.style());
+        let mut last_label_rect = Rect::NOTHING;
+        for tick in ticks {
+            let position = self.position_from_value(tick.value, position_range);
+            if !position.is_finite() {
+                continue;
+            }
+            let length = if tick.major { 4.0 } else { 2.0 };
+            let (start, end) = match self.orientation {
+                SliderOrientation::Horizontal => (
+                    pos2(position, rail_rect.bottom() + 1.0),
+                    pos2(position, rail_rect.bottom() + 1.0 + length),
+                ),
+                SliderOrientation::Vertical => (
+                    pos2(rail_rect.right() + 1.0, position),
+                    pos2(rail_rect.right() + 1.0 + length, position),
+                ),
+            };
+            ui.painter()
+                .line_segment([start, end], Stroke::new(1.0, color));
+
+            if self.tick_labels && tick.major {
+                let galley = ui.painter().layout_no_wrap(
+                    self.format_tick(tick.value),
+                    font_id.clone(),
+                    color,
+                );
+                let label_rect = match self.orientation {
+                    SliderOrientation::Horizontal => Align2::CENTER_TOP
+                        .anchor_size(pos2(position, handle_area.bottom()), galley.size()),
+                    SliderOrientation::Vertical => Align2::LEFT_CENTER.anchor_size(
+                        pos2(handle_area.right() + ui.spacing().item_spacing.x, position),
+                        galley.size(),
+                    ),
+                };
+                // Leave out labels that would overlap:
+                if !label_rect.intersects(last_label_rect.expand(2.0)) {
+                    ui.painter().galley(label_rect.min, galley, color);
+                    last_label_rect = label_rect;
                 }
             }
         }
@@ -859,16 +1360,43 @@ impl<'a> Slider<'a> {
     }
 
     fn add_contents(&mut self, ui: &mut Ui) -> Response {
-        let old_value = self.get_value();
+        let old_value = self.get_handle_value(false);
+        let old_upper_value = self.is_range_mode().then(|| self.get_handle_value(true));
 
         let thickness = ui
             .text_style_height(&TextStyle::Body)
             .at_least(ui.spacing().interact_size.y);
+        let decrements = if self.stepper_buttons {
+            super::stepper::stepper_button(ui, "−", self.hold_to_repeat)
+        } else {
+            0
+        };
         let mut response = self.allocate_slider_space(ui, thickness);
         self.slider_ui(ui, &response);
+        if self.stepper_buttons {
+            let increments = super::stepper::stepper_button(ui, "+", self.hold_to_repeat);
+            let steps = increments as f64 - decrements as f64;
+            if steps != 0.0 {
+                let handle_shape = self
+                    .handle_shape
+                    .unwrap_or_else(|| ui.style().visuals.handle_shape);
+                let position_range =
+                    self.position_range(&self.handle_area(ui, response.rect), &handle_shape);
+                let ticks = if self.ticks || self.snap_to_ticks {
+                    self.tick_values(position_range)
+                } else {
+                    vec![]
+                };
+                let snap_to_ticks = self.snap_to_ticks && !ticks.is_empty();
+                self.step_value(steps, ui, position_range, &ticks, snap_to_ticks);
+                // The handle was already painted at the old value:
+                ui.ctx().request_repaint_from(response.id);
+            }
+        }
 
-        let value = self.get_value();
-        response.changed = value != old_value;
+        let value = self.get_handle_value(false);
+        let upper_value = self.is_range_mode().then(|| self.get_handle_value(true));
+        response.changed = value != old_value || upper_value != old_upper_value;
         response.widget_info(|| WidgetInfo::slider(value, self.text.text()));
 
         #[cfg(feature = "accesskit")]
@@ -895,8 +1423,17 @@ impl<'a> Slider<'a> {
             let handle_shape = self
                 .handle_shape
                 .unwrap_or_else(|| ui.style().visuals.handle_shape);
-            let position_range = self.position_range(&response.rect, &handle_shape);
-            let value_response = self.value_ui(ui, position_range);
+            let position_range =
+                self.position_range(&self.handle_area(ui, response.rect), &handle_shape);
+            let value_response = if self.is_range_mode() {
+                self.upper_active = false;
+                let low_response = self.value_ui(ui, position_range);
+                self.upper_active = true;
+                let high_response = self.value_ui(ui, position_range);
+                low_response.union(high_response)
+            } else {
+                self.value_ui(ui, position_range)
+            };
             if value_response.gained_focus()
                 || value_response.has_focus()
                 || value_response.lost_focus()
@@ -948,6 +1485,65 @@ impl<'a> Widget for Slider<'a> {
 
 use std::f64::INFINITY;
 
+/// The smallest of 1, 2 or 5 times a power of ten that is at least `at_least`.
+fn nice_step(at_least: f64) -> f64 {
+    let power_of_ten = 10.0_f64.powf(at_least.log10().floor());
+    [1.0, 2.0, 5.0, 10.0]
+        .into_iter()
+        .map(|factor| factor * power_of_ten)
+        .find(|&step| at_least <= step)
+        .unwrap_or(10.0 * power_of_ten)
+}
+
+/// Tick marks at the powers of ten (and their negatives), with minor ones in between if there is room.
+fn logarithmic_ticks(min: f64, max: f64, spec: &SliderSpec, span: f64) -> Vec<Tick> {
+    let max_magnitude = max.abs().max(min.abs()).min(spec.largest_finite);
+    let min_magnitude = if min <= 0.0 && 0.0 <= max {
+        spec.smallest_positive
+    } else {
+        max.abs().min(min.abs()).max(spec.smallest_positive)
+    };
+    if !(min_magnitude.is_finite() && max_magnitude.is_finite() && 0.0 < min_magnitude) {
+        return vec![];
+    }
+    let first_exponent = min_magnitude.log10().floor() as i32;
+    let last_exponent = max_magnitude.log10().ceil() as i32;
+    let num_decades = (last_exponent - first_exponent).max(1) as f64;
+    let with_minor = 8.0 * MIN_TICK_SPACING <= span / num_decades;
+
+    let mut magnitudes = vec![];
+    for exponent in first_exponent..=last_exponent {
+        let power_of_ten = 10.0_f64.powi(exponent);
+        magnitudes.push(Tick {
+            value: power_of_ten,
+            major: true,
+        });
+        if with_minor {
+            magnitudes.extend((2..10).map(|factor| Tick {
+                value: factor as f64 * power_of_ten,
+                major: false,
+            }));
+        }
+    }
+
+    let mut ticks = vec![];
+    if min <= 0.0 && 0.0 <= max {
+        ticks.push(Tick {
+            value: 0.0,
+            major: true,
+        });
+    }
+    for tick in magnitudes {
+        for value in [-tick.value, tick.value] {
+            if (min..=max).contains(&value) {
+                ticks.push(Tick { value, ..tick });
+            }
+        }
+    }
+    ticks.sort_by(|a, b| a.value.total_cmp(&b.value));
+    ticks
+}
+
 /// When the user asks for an infinitely large range (e.g. logarithmic from zero),
 /// give a scale that this many orders of magnitude in size.
 const INF_RANGE_MAGNITUDE: f64 = 10.0;
@@ -1093,3 +1689,133 @@ fn logarithmic_zero_cutoff(min: f64, max: f64) -> f64 {
     crate::egui_assert!(0.0 <= cutoff && cutoff <= 1.0);
     cutoff
 }
+
+#[test]
+fn test_slider_ticks() {
+    let mut value = 1.0;
+    let position_range = Rangef::new(0.0, 200.0);
+    let values = |slider: Slider<'_>| -> Vec<(f64, bool)> {
+        let ticks = slider.tick_values(position_range);
+        ticks.iter().map(|tick| (tick.value, tick.major)).collect()
+    };
+
+    let linear = Slider::new(&mut value, 0.0..=100.0);
+    assert_eq!(
+        values(linear),
+        vec![(0.0, true), (50.0, true), (100.0, true)]
+    );
+
+    let stepped = Slider::new(&mut value, 0.0..=100.0).step_by(10.0);
+    let ticks = values(stepped);
+    assert_eq!(ticks.len(), 11);
+    assert_eq!(ticks[3], (30.0, true));
+    assert_eq!(ticks[4], (40.0, false));
+
+    let logarithmic = Slider::new(&mut value, 1.0..=1000.0).logarithmic(true);
+    let major: Vec<f64> = values(logarithmic)
+        .into_iter()
+        .filter(|(_, major)| *major)
+        .map(|(value, _)| value)
+        .collect();
+    assert_eq!(major, vec![1.0, 10.0, 100.0, 1000.0]);
+}
+
+#[test]
+fn test_range_slider() {
+    use crate::test_harness::Harness;
+    use std::cell::RefCell;
+
+    let values = RefCell::new((20.0_f64, 80.0_f64));
+    let response = RefCell::new(None);
+    let snap = RefCell::new(false);
+    let mut harness = Harness::new_ui(|ui| {
+        let mut values = values.borrow_mut();
+        let mut slider = Slider::range_mode(&mut values, 0.0..=100.0)
+            .show_value(false)
+            .step_by(1.0);
+        if *snap.borrow() {
+            slider = slider
+                .custom_ticks([0.0, 25.0, 50.0, 75.0, 100.0])
+                .snap_to_ticks(true);
+        }
+        *response.borrow_mut() = Some(ui.add(slider));
+    });
+
+    let (rect, id) = {
+        let response = response.borrow();
+        let response = response.as_ref().unwrap();
+        (response.rect, response.id)
+    };
+    let position_range = rect.x_range().shrink(rect.height() / 2.5);
+    let pos = |value: f32| pos2(lerp(position_range, value / 100.0), rect.center().y);
+
+    // Dragging the upper handle leaves the lower one be:
+    harness.drag(pos(80.0), pos(100.0));
+    assert_eq!(*values.borrow(), (20.0, 100.0));
+
+    // The lower handle can't go past the upper one:
+    harness.drag(pos(20.0), pos(60.0));
+    assert_eq!(*values.borrow(), (60.0, 100.0));
+    harness.drag(pos(60.0), pos(100.0));
+    assert_eq!(*values.borrow(), (100.0, 100.0));
+
+    // When they are on top of each other, dragging picks the one on the side of the pointer:
+    harness.drag(pos(99.0), pos(30.0));
+    assert_eq!(*values.borrow(), (30.0, 100.0));
+
+    // The arrow keys move the handle pressed last:
+    harness.ctx().memory_mut(|memory| memory.request_focus(id));
+    harness.press_key(Key::ArrowRight);
+    assert_eq!(*values.borrow(), (31.0, 100.0));
+
+    *snap.borrow_mut() = true;
+    harness.drag(pos(30.0), pos(60.0));
+    assert_eq!(*values.borrow(), (50.0, 100.0));
+}
+
+#[test]
+fn test_stepper_buttons_and_scroll() {
+    use crate::test_harness::Harness;
+    use std::cell::Cell;
+
+    let value = Cell::new(0);
+    let mut harness = Harness::new_ui(|ui| {
+        let mut v = value.get();
+        ui.add(
+            Slider::new(&mut v, 0..=100)
+                .stepper_buttons(true)
+                .scroll_to_adjust(true),
+        );
+        value.set(v);
+    });
+    harness.run();
+
+    let plus = harness.get_by_label("+").interact_rect().center();
+    let minus = harness.get_by_label("−").interact_rect().center();
+    harness.click(plus);
+    harness.click(plus);
+    harness.click(minus);
+    assert_eq!(value.get(), 1);
+
+    // Two notches of the mouse wheel, spread over a few frames like the smooth scrolling does:
+    let slider = harness
+        .get_by(|info| info.typ == WidgetType::Slider)
+        .interact_rect();
+    harness.hover(slider.center());
+    harness.push_event(Event::Scroll(vec2(0.0, 100.0)));
+    harness.run();
+    assert_eq!(value.get(), 3);
+
+    // Holding the button down repeats, faster and faster:
+    harness.hover(plus);
+    harness.push_event(Event::PointerButton {
+        pos: plus,
+        button: PointerButton::Primary,
+        pressed: true,
+        modifiers: Modifiers::NONE,
+    });
+    for _ in 0..90 {
+        harness.step();
+    }
+    assert!(20 < value.get(), "{}", value.get());
+}
@@ -0,0 +1,100 @@
+//! Changing the value of a [`DragValue`] or [`Slider`] in steps:
+//! with the stepper buttons next to it, or by scrolling over it.
+
+use crate::*;
+
+/// Scrolling this many points is one step: one notch of a mouse wheel.
+const POINTS_PER_STEP: f32 = 50.0;
+
+/// How many steps to take because the user scrolled over the widget this frame.
+///
+/// Scrolling up (or right) is positive. The scroll is consumed, so that a surrounding
+/// [`ScrollArea`] stays put. What is left after rounding to whole steps is kept for the next frame,
+/// since smooth scrolling spreads each notch over several frames.
+pub(crate) fn scroll_steps(ui: &Ui, response: &Response) -> f64 {
+    if !response.hovered() {
+        return 0.0;
+    }
+    let delta = ui.input_mut(|input| {
+        let delta = input.smooth_scroll_delta.x + input.smooth_scroll_delta.y;
+        input.smooth_scroll_delta = Vec2::ZERO;
+        delta
+    });
+    if delta == 0.0 {
+        return 0.0;
+    }
+
+    let id = response.id.with("__scroll_steps");
+    ui.data_mut(|data| {
+        let steps = data.get_temp::<f32>(id).unwrap_or_default() + delta / POINTS_PER_STEP;
+        let whole_steps = steps.round();
+        data.insert_temp(id, steps - whole_steps);
+        whole_steps as f64
+    })
+}
+
+/// A small square button, e.g. `"−"` or `"+"`.
+///
+/// Returns how many steps to take this frame: one when it is pressed,
+/// and then, with `hold_to_repeat`, more and more often for as long as it is held down.
+pub(crate) fn stepper_button(ui: &mut Ui, text: &str, hold_to_repeat: bool) -> u64 {
+    let size = Vec2::splat(ui.spacing().interact_size.y);
+    // A press that lasts longer than a click is a drag, so sense that too:
+    let button = Button::new(text)
+        .min_size(size)
+        .sense(Sense::click_and_drag());
+    let response = ui.add(button);
+
+    let id = response.id.with("__repeat");
+    if !response.is_pointer_button_down_on() {
+        ui.data_mut(|data| data.remove::<(f64, u64)>(id));
+        return 0;
+    }
+
+    let now = ui.input(|i| i.time);
+    if ui.input(|i| i.pointer.any_pressed()) {
+        ui.data_mut(|data| data.insert_temp(id, (now, 0_u64)));
+        return 1;
+    }
+    if !hold_to_repeat {
+        return 0;
+    }
+
+    ui.ctx().request_repaint_from(id);
+    ui.data_mut(|data| {
+        let (pressed_time, repeats) = data.get_temp::<(f64, u64)>(id).unwrap_or((now, 0));
+        let new_repeats = num_repeats(now - pressed_time);
+        data.insert_temp(id, (pressed_time, new_repeats.max(repeats)));
+        new_repeats.saturating_sub(repeats)
+    })
+}
+
+/// How many times a stepper button has repeated after being held for this many seconds.
+///
+/// After a short delay it starts repeating, and then ever faster, up to a limit.
+fn num_repeats(held_time: f64) -> u64 {
+    const DELAY: f64 = 0.4;
+    const START_RATE: f64 = 8.0;
+    const MAX_RATE: f64 = 40.0;
+    const ACCELERATION: f64 = 16.0;
+
+    let t = held_time - DELAY;
+    if t <= 0.0 {
+        return 0;
+    }
+    let t_max_rate = (MAX_RATE - START_RATE) / ACCELERATION;
+    let accelerating = t.min(t_max_rate);
+    let repeats = START_RATE * accelerating
+        + 0.5 * ACCELERATION * accelerating * accelerating
+        + MAX_RATE * (t - t_max_rate).max(0.0);
+    repeats as u64
+}
+
+#[test]
+fn test_num_repeats() {
+    assert_eq!(num_repeats(0.3), 0);
+    assert_eq!(num_repeats(0.55), 1);
+    let per_second = |t: f64| num_repeats(t + 1.0) - num_repeats(t);
+    assert!(per_second(0.5) < per_second(1.5), "It accelerates");
+    assert_eq!(per_second(10.0), 40);
+}
@@ -6,8 +6,8 @@ use crate::{
     os::OperatingSystem,
     output::OutputEvent,
     text_selection::{
-        text_cursor_state::cursor_rect,
-        visuals::{paint_cursor, paint_text_selection},
+        text_cursor_state::{char_width_at, cursor_rect},
+        visuals::{paint_cursor_over_char, paint_text_cursor, paint_text_selection},
         CCursorRange, CursorRange,
     },
     *,
@@ -69,6 +69,7 @@ pub struct TextEdit<'t> {
     layouter: Option<&'t mut dyn FnMut(&Ui, &str, f32) -> Arc<Galley>>,
     password: bool,
     frame: bool,
+    invalid: bool,
     margin: Margin,
     multiline: bool,
     interactive: bool,
@@ -80,6 +81,7 @@ pub struct TextEdit<'t> {
     align: Align2,
     clip_text: bool,
     char_limit: usize,
+    large_paste_threshold: usize,
 }
 
 impl<'t> WidgetWithState for TextEdit<'t> {
@@ -119,6 +121,7 @@ impl<'t> TextEdit<'t> {
             layouter: None,
             password: false,
             frame: true,
+            invalid: false,
             margin: Margin::symmetric(4.0, 2.0),
             multiline: true,
             interactive: true,
@@ -136,6 +139,7 @@ impl<'t> TextEdit<'t> {
             align: Align2::LEFT_TOP,
             clip_text: false,
             char_limit: usize::MAX,
+            large_paste_threshold: usize::MAX,
         }
     }
 
@@ -261,6 +265,16 @@ impl<'t> TextEdit<'t> {
         self
     }
 
+    /// Show that the text is not a valid value, e.g. a number that can't be parsed,
+    /// by outlining the frame with [`crate::Visuals::error_fg_color`].
+    ///
+    /// Default is `false`.
+    #[inline]
+    pub fn invalid(mut self, invalid: bool) -> Self {
+        self.invalid = invalid;
+        self
+    }
+
     /// Set margin of text. Default is `Margin::symmetric(4.0, 2.0)`
     #[inline]
     pub fn margin(mut self, margin: impl Into<Margin>) -> Self {
@@ -328,6 +342,37 @@ impl<'t> TextEdit<'t> {
         self
     }
 
+    /// Pastes of more than this many bytes are not inserted right away,
+    /// but held back until the app accepts them.
+    ///
+    /// Use this to e.g. ask the user before inserting a multi-megabyte paste.
+    /// See [`TextEditState::pending_paste`].
+    ///
+    /// Default: no limit.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// # let mut text = String::new();
+    /// let mut output = egui::TextEdit::multiline(&mut text)
+    ///     .large_paste_threshold(1_000_000)
+    ///     .show(ui);
+    /// if let Some(paste) = output.state.pending_paste() {
+    ///     ui.label(format!("Paste {} bytes?", paste.len()));
+    ///     if ui.button("Paste").clicked() {
+    ///         output.state.accept_pending_paste();
+    ///     } else if ui.button("Cancel").clicked() {
+    ///         output.state.discard_pending_paste();
+    ///     }
+    ///     output.state.store(ui.ctx(), output.response.id);
+    /// }
+    /// # });
+    /// ```
+    #[inline]
+    pub fn large_paste_threshold(mut self, num_bytes: usize) -> Self {
+        self.large_paste_threshold = num_bytes;
+        self
+    }
+
     /// Set the horizontal align of the inner text.
     #[inline]
     pub fn horizontal_align(mut self, align: Align) -> Self {
@@ -377,6 +422,7 @@ impl<'t> TextEdit<'t> {
     pub fn show(self, ui: &mut Ui) -> TextEditOutput {
         let is_mutable = self.text.is_mutable();
         let frame = self.frame;
+        let invalid = self.invalid;
         let interactive = self.interactive;
         let where_to_put_background = ui.painter().add(Shape::Noop);
 
@@ -398,10 +444,11 @@ impl<'t> TextEdit<'t> {
         }
 
         if frame {
-            let visuals = ui.style().interact(&output.response);
+            let state = crate::style::WidgetState::from_response(&output.response).invalid(invalid);
+            let visuals = ui.style().interact_with_state(state);
             let frame_rect = frame_rect.expand(visuals.expansion);
             let shape = if is_mutable {
-                if output.response.has_focus() {
+                if output.response.has_focus() && !invalid {
                     epaint::RectShape::new(
                         frame_rect,
                         visuals.rounding,
@@ -442,6 +489,7 @@ impl<'t> TextEdit<'t> {
             layouter,
             password,
             frame: _,
+            invalid: _,
             margin,
             multiline,
             interactive,
@@ -453,6 +501,7 @@ impl<'t> TextEdit<'t> {
             align,
             clip_text,
             char_limit,
+            large_paste_threshold,
         } = self;
 
         let text_color = text_color
@@ -541,14 +590,15 @@ impl<'t> TextEdit<'t> {
                 let cursor_at_pointer =
                     galley.cursor_from_pos(pointer_pos - response.rect.min + singleline_offset);
 
-                if ui.visuals().text_cursor_preview
+                if ui.visuals().text_cursor_preview()
                     && response.hovered()
                     && ui.input(|i| i.pointer.is_moving())
                 {
                     // preview:
                     let cursor_rect =
                         cursor_rect(response.rect.min, &galley, &cursor_at_pointer, row_height);
-                    paint_cursor(&painter, ui.visuals(), cursor_rect);
+                    let char_width = char_width_at(&galley, &cursor_at_pointer);
+                    paint_cursor_over_char(&painter, ui.visuals(), cursor_rect, char_width);
                 }
 
                 let is_being_dragged = ui.ctx().is_being_dragged(response.id);
@@ -570,6 +620,19 @@ impl<'t> TextEdit<'t> {
             ui.ctx().set_cursor_icon(CursorIcon::Text);
         }
 
+        if let Some(text_to_insert) = state.take_accepted_paste() {
+            // Inserted even without focus, since accepting the paste may have moved the focus elsewhere:
+            let ccursor_range = state
+                .cursor
+                .char_range()
+                .unwrap_or_else(|| CCursorRange::one(galley.end().ccursor));
+            let new_ccursor_range =
+                insert_paste(&mut state, text, ccursor_range, &text_to_insert, char_limit);
+            galley = layouter(ui, text.as_str(), wrap_width);
+            state.cursor.set_char_range(Some(new_ccursor_range));
+            response.mark_changed();
+        }
+
         let mut cursor_range = None;
         let prev_cursor_range = state.cursor.range(&galley);
         if interactive && ui.memory(|mem| mem.has_focus(id)) {
@@ -593,6 +656,7 @@ impl<'t> TextEdit<'t> {
                 password,
                 default_cursor_range,
                 char_limit,
+                large_paste_threshold,
                 event_filter,
             );
 
@@ -644,6 +708,16 @@ impl<'t> TextEdit<'t> {
             false
         };
 
+        #[cfg(feature = "accesskit")]
+        let placeholder = hint_text.text().to_owned();
+
+        let now = ui.input(|i| i.time);
+        let pause_blinking =
+            ui.visuals().text_cursor.pause_while_typing && (response.changed || selection_changed);
+        if response.gained_focus() || pause_blinking {
+            state.last_interaction_time = now;
+        }
+
         if ui.is_rect_visible(rect) {
             painter.galley(galley_pos, galley.clone(), text_color);
 
@@ -680,7 +754,13 @@ impl<'t> TextEdit<'t> {
                     }
 
                     if text.is_mutable() {
-                        paint_cursor(&painter, ui.visuals(), primary_cursor_rect);
+                        paint_text_cursor(
+                            ui,
+                            &painter,
+                            primary_cursor_rect,
+                            char_width_at(&galley, &cursor_range.primary),
+                            now - state.last_interaction_time,
+                        );
 
                         if interactive {
                             // For IME, so only set it when text is editable and visible!
@@ -741,6 +821,15 @@ impl<'t> TextEdit<'t> {
                 galley_pos,
                 &galley,
             );
+
+            ui.ctx().accesskit_node_builder(id, |builder| {
+                if !interactive {
+                    builder.set_read_only();
+                }
+                if !placeholder.is_empty() {
+                    builder.set_placeholder(placeholder);
+                }
+            });
         }
 
         TextEditOutput {
@@ -784,6 +873,7 @@ fn events(
     password: bool,
     default_cursor_range: CursorRange,
     char_limit: usize,
+    large_paste_threshold: usize,
     event_filter: EventFilter,
 ) -> (bool, CursorRange) {
     let os = ui.ctx().os();
@@ -805,8 +895,65 @@ fn events(
 
     let mut any_change = false;
 
+    // Typing and pasting only need the character cursors, so after those we wait with
+    // laying out the text again until an event needs the galley (or we are done),
+    // instead of doing it once per event.
+    let mut needs_layout: Option<CCursorRange> = None;
+    let mut layout = |galley: &mut Arc<Galley>,
+                      cursor_range: &mut CursorRange,
+                      new_ccursor_range: CCursorRange,
+                      text: &dyn TextBuffer| {
+        // Layout again to keep `text` and `galley` in sync.
+        *galley = layouter(ui, text.as_str(), wrap_width);
+
+        // Set cursor_range using new galley:
+        *cursor_range = CursorRange {
+            primary: galley.from_ccursor(new_ccursor_range.primary),
+            secondary: galley.from_ccursor(new_ccursor_range.secondary),
+        };
+    };
+
     let events = ui.input(|i| i.filtered_events(&event_filter));
     for event in &events {
+        let ccursor_range = needs_layout.unwrap_or_else(|| cursor_range.as_ccursor_range());
+        match event {
+            Event::Paste {
+                text: text_to_insert,
+                ..
+            } if !text_to_insert.is_empty() => {
+                if large_paste_threshold < text_to_insert.len() {
+                    state.set_pending_paste(text_to_insert.clone());
+                } else {
+                    needs_layout = Some(insert_paste(
+                        state,
+                        text,
+                        ccursor_range,
+                        text_to_insert,
+                        char_limit,
+                    ));
+                    any_change = true;
+                }
+                continue;
+            }
+            // Newlines are handled by `Key::Enter`.
+            Event::Text(text_to_insert)
+                if !text_to_insert.is_empty()
+                    && text_to_insert != "\n"
+                    && text_to_insert != "\r" =>
+            {
+                let mut ccursor = text.delete_selected_ccursor_range(ccursor_range.sorted());
+                text.insert_text_at(&mut ccursor, text_to_insert, char_limit);
+                needs_layout = Some(CCursorRange::one(ccursor));
+                any_change = true;
+                continue;
+            }
+            _ => {}
+        }
+
+        if let Some(new_ccursor_range) = needs_layout.take() {
+            layout(galley, &mut cursor_range, new_ccursor_range, text);
+        }
+
         let did_mutate_text = match event {
             // First handle events that only changes the selection cursor, not the text:
             event if cursor_range.on_event(os, event, galley, id) => None,
@@ -828,29 +975,6 @@ fn events(
                     Some(CCursorRange::one(text.delete_selected(&cursor_range)))
                 }
             }
-            Event::Paste(text_to_insert) => {
-                if !text_to_insert.is_empty() {
-                    let mut ccursor = text.delete_selected(&cursor_range);
-
-                    text.insert_text_at(&mut ccursor, text_to_insert, char_limit);
-
-                    Some(CCursorRange::one(ccursor))
-                } else {
-                    None
-                }
-            }
-            Event::Text(text_to_insert) => {
-                // Newlines are handled by `Key::Enter`.
-                if !text_to_insert.is_empty() && text_to_insert != "\n" && text_to_insert != "\r" {
-                    let mut ccursor = text.delete_selected(&cursor_range);
-
-                    text.insert_text_at(&mut ccursor, text_to_insert, char_limit);
-
-                    Some(CCursorRange::one(ccursor))
-                } else {
-                    None
-                }
-            }
             Event::Key {
                 key: Key::Tab,
                 pressed: true,
@@ -966,17 +1090,15 @@ fn events(
         if let Some(new_ccursor_range) = did_mutate_text {
             any_change = true;
 
-            // Layout again to avoid frame delay, and to keep `text` and `galley` in sync.
-            *galley = layouter(ui, text.as_str(), wrap_width);
-
-            // Set cursor_range using new galley:
-            cursor_range = CursorRange {
-                primary: galley.from_ccursor(new_ccursor_range.primary),
-                secondary: galley.from_ccursor(new_ccursor_range.secondary),
-            };
+            // Layout again to avoid frame delay:
+            layout(galley, &mut cursor_range, new_ccursor_range, text);
         }
     }
 
+    if let Some(new_ccursor_range) = needs_layout {
+        layout(galley, &mut cursor_range, new_ccursor_range, text);
+    }
+
     state.cursor.set_range(Some(cursor_range));
 
     state.undoer.lock().feed_state(
@@ -987,6 +1109,29 @@ fn events(
     (any_change, cursor_range)
 }
 
+/// Replace the selection with a paste, as a single undo step.
+fn insert_paste(
+    state: &mut TextEditState,
+    text: &mut dyn TextBuffer,
+    ccursor_range: CCursorRange,
+    text_to_insert: &str,
+    char_limit: usize,
+) -> CCursorRange {
+    let mut undoer = state.undoer.lock();
+
+    // Save whatever was typed before the paste as its own undo step:
+    undoer.add_undo(&(ccursor_range, text.as_str().to_owned()));
+
+    let mut ccursor = text.delete_selected_ccursor_range(ccursor_range.sorted());
+    text.insert_text_at(&mut ccursor, text_to_insert, char_limit);
+    let new_ccursor_range = CCursorRange::one(ccursor);
+
+    // …and don't merge the paste with whatever is typed after it:
+    undoer.add_undo(&(new_ccursor_range, text.as_str().to_owned()));
+
+    new_ccursor_range
+}
+
 // ----------------------------------------------------------------------------
 
 /// Returns `Some(new_cursor)` if we did mutate `text`.
@@ -1062,3 +1207,127 @@ fn check_for_mutating_key_press(
         _ => None,
     }
 }
+
+#[test]
+fn test_text_cursor_blink_repaint() {
+    use crate::test_harness::Harness;
+
+    let mut text = String::from("Hello");
+    let mut harness = Harness::new_ui(|ui| {
+        ui.text_edit_singleline(&mut text);
+    });
+    let repaint_delay =
+        |harness: &Harness<'_>| harness.output().viewport_output[&ViewportId::ROOT].repaint_delay;
+
+    // Nothing blinks before the text edit has focus:
+    assert_eq!(repaint_delay(&harness), std::time::Duration::MAX);
+
+    harness
+        .get_by(|info| info.typ == WidgetType::TextEdit)
+        .click();
+    let delay = repaint_delay(&harness).as_secs_f32();
+    assert!(0.0 < delay && delay <= 0.5, "delay: {delay}");
+
+    // No blinking, no repaints:
+    harness
+        .ctx()
+        .style_mut(|style| style.visuals.text_cursor.blink = false);
+    harness.run();
+    assert_eq!(repaint_delay(&harness), std::time::Duration::MAX);
+}
+
+#[test]
+fn test_paste_is_single_undo_step() {
+    use crate::test_harness::Harness;
+
+    let mut text = String::new();
+    let mut harness = Harness::new_ui(|ui| {
+        ui.text_edit_singleline(&mut text);
+    });
+    harness
+        .get_by(|info| info.typ == WidgetType::TextEdit)
+        .type_text("ab");
+    harness.push_event(Event::Paste {
+        text: "cd".to_owned(),
+        source: PasteSource::Clipboard,
+    });
+    harness.run();
+    harness.type_text("ef");
+
+    harness.set_modifiers(Modifiers::COMMAND);
+    harness.press_key(Key::Z);
+    harness.press_key(Key::Z);
+    drop(harness);
+
+    assert_eq!(text, "ab");
+}
+
+#[test]
+fn test_large_paste_threshold() {
+    use crate::test_harness::Harness;
+
+    let mut text = String::new();
+    let mut harness = Harness::new_ui(|ui| {
+        let mut output = TextEdit::singleline(&mut text)
+            .large_paste_threshold(4)
+            .show(ui);
+        if output.state.pending_paste().is_some() && ui.button("Paste").clicked() {
+            output.state.accept_pending_paste();
+            output.state.store(ui.ctx(), output.response.id);
+        }
+    });
+    harness
+        .get_by(|info| info.typ == WidgetType::TextEdit)
+        .type_text("ab");
+    harness.push_event(Event::Paste {
+        text: "Hello world".to_owned(),
+        source: PasteSource::Clipboard,
+    });
+    harness.run();
+
+    // Held back until accepted:
+    harness.get_by_label("Paste").click();
+
+    assert!(harness.query_by_label("Paste").is_none());
+    drop(harness);
+
+    assert_eq!(text, "abHello world");
+}
+
+#[test]
+fn test_invalid_text_edit_frame() {
+    use crate::test_harness::Harness;
+
+    let mut number = String::from("12");
+    let mut harness = Harness::new_ui(|ui| {
+        let invalid = number.parse::<f32>().is_err();
+        ui.add(TextEdit::singleline(&mut number).invalid(invalid));
+    });
+    let frame_stroke = |harness: &Harness<'_>| {
+        harness
+            .output()
+            .shapes
+            .iter()
+            .find_map(|clipped| match &clipped.shape {
+                Shape::Rect(rect)
+                    if rect.fill == harness.ctx().style().visuals.extreme_bg_color =>
+                {
+                    Some(rect.stroke)
+                }
+                _ => None,
+            })
+            .unwrap()
+    };
+    let error_color = harness.ctx().style().visuals.error_fg_color;
+    assert_ne!(frame_stroke(&harness).color, error_color);
+
+    harness
+        .get_by(|info| info.typ == WidgetType::TextEdit)
+        .type_text("x");
+    harness.run();
+    assert_eq!(
+        frame_stroke(&harness).color,
+        error_color,
+        "also when focused"
+    );
+}
@@ -1,9 +1,10 @@
 mod builder;
 mod output;
+mod rope_buffer;
 mod state;
 mod text_buffer;
 
 pub use {
     crate::text_selection::TextCursorState, builder::TextEdit, output::TextEditOutput,
-    state::TextEditState, text_buffer::TextBuffer,
+    rope_buffer::RopeBuffer, state::TextEditState, text_buffer::TextBuffer,
 };
@@ -0,0 +1,275 @@
+use std::ops::Range;
+
+use crate::text_selection::text_cursor_state::byte_index_from_char_index;
+
+use super::TextBuffer;
+
+/// Chunks are split when they grow beyond twice this many bytes.
+const CHUNK_LEN: usize = 4 * 1024;
+
+/// A [`TextBuffer`] for large texts, e.g. editing a multi-megabyte document in a [`crate::TextEdit`].
+///
+/// The text is stored as a list of chunks of a few kilobytes each (ending at a newline where possible),
+/// together with how many characters each chunk holds.
+/// Inserting or deleting text therefore only moves the bytes of the chunks it touches,
+/// and going from a character offset to a byte offset only scans a single chunk,
+/// instead of the whole text.
+///
+/// [`TextBuffer::as_str`] joins the chunks into one string the first time it is called after an edit,
+/// and then keeps it until the next edit.
+/// egui lays out large texts one paragraph at a time from that string,
+/// so that an edit only lays out the paragraphs that changed.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// # let mut document = egui::RopeBuffer::from("Hello\nworld");
+/// ui.add(egui::TextEdit::multiline(&mut document).code_editor());
+/// # });
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct RopeBuffer {
+    /// The pieces of the text, in order. None of them is empty.
+    chunks: Vec<Chunk>,
+
+    /// Total length of all chunks, in bytes.
+    len: usize,
+
+    /// Total number of characters in all chunks.
+    num_chars: usize,
+
+    /// All the chunks joined together, created on demand by [`TextBuffer::as_str`].
+    joined: std::sync::OnceLock<String>,
+}
+
+#[derive(Clone, Debug, Default)]
+struct Chunk {
+    text: String,
+    num_chars: usize,
+}
+
+impl RopeBuffer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Length of the text, in bytes.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The pieces the text is stored in, in order.
+    pub fn chunks(&self) -> impl Iterator<Item = &str> + '_ {
+        self.chunks.iter().map(|chunk| chunk.text.as_str())
+    }
+
+    /// Which chunk the character index is in, the byte offset of that chunk,
+    /// and the character index within that chunk.
+    ///
+    /// An index between two chunks is at the end of the first one.
+    fn locate(&self, char_index: usize) -> (usize, usize, usize) {
+        let mut byte_start = 0;
+        let mut char_start = 0;
+        for (i, chunk) in self.chunks.iter().enumerate() {
+            if char_index <= char_start + chunk.num_chars {
+                return (i, byte_start, char_index - char_start);
+            }
+            byte_start += chunk.text.len();
+            char_start += chunk.num_chars;
+        }
+        match self.chunks.last() {
+            Some(last) => (
+                self.chunks.len() - 1,
+                byte_start - last.text.len(),
+                last.num_chars,
+            ),
+            None => (0, 0, 0),
+        }
+    }
+}
+
+/// Split the text in chunks of at most [`CHUNK_LEN`] bytes, preferably ending after a newline.
+fn split_into_chunks(mut text: &str) -> Vec<Chunk> {
+    let mut chunks = Vec::with_capacity(text.len() / CHUNK_LEN + 1);
+    while !text.is_empty() {
+        let mut end = text.len();
+        if CHUNK_LEN < end {
+            end = CHUNK_LEN;
+            while !text.is_char_boundary(end) {
+                end -= 1;
+            }
+            if let Some(newline) = text[..end].rfind('\n') {
+                end = newline + 1;
+            }
+        }
+        let (chunk, rest) = text.split_at(end);
+        chunks.push(Chunk {
+            text: chunk.to_owned(),
+            num_chars: chunk.chars().count(),
+        });
+        text = rest;
+    }
+    chunks
+}
+
+impl From<String> for RopeBuffer {
+    fn from(text: String) -> Self {
+        let chunks = split_into_chunks(&text);
+        let num_chars = chunks.iter().map(|chunk| chunk.num_chars).sum();
+        Self {
+            chunks,
+            len: text.len(),
+            num_chars,
+            joined: text.into(),
+        }
+    }
+}
+
+impl From<&str> for RopeBuffer {
+    fn from(text: &str) -> Self {
+        Self::from(text.to_owned())
+    }
+}
+
+impl std::fmt::Display for RopeBuffer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.chunks().try_for_each(|chunk| f.write_str(chunk))
+    }
+}
+
+impl TextBuffer for RopeBuffer {
+    fn is_mutable(&self) -> bool {
+        true
+    }
+
+    fn as_str(&self) -> &str {
+        self.joined.get_or_init(|| self.chunks().collect())
+    }
+
+    fn insert_text(&mut self, text: &str, char_index: usize) -> usize {
+        if text.is_empty() {
+            return 0;
+        }
+        if self.chunks.is_empty() {
+            self.chunks.push(Chunk::default());
+        }
+        self.joined.take();
+
+        let (chunk_index, _, char_in_chunk) = self.locate(char_index);
+        let chunk = &mut self.chunks[chunk_index];
+        let byte_idx = byte_index_from_char_index(&chunk.text, char_in_chunk);
+        chunk.text.insert_str(byte_idx, text);
+
+        let num_chars = text.chars().count();
+        chunk.num_chars += num_chars;
+        self.num_chars += num_chars;
+        self.len += text.len();
+
+        if 2 * CHUNK_LEN < chunk.text.len() {
+            let split = split_into_chunks(&chunk.text);
+            self.chunks.splice(chunk_index..=chunk_index, split);
+        }
+
+        num_chars
+    }
+
+    fn delete_char_range(&mut self, char_range: Range<usize>) {
+        assert!(char_range.start <= char_range.end);
+        if char_range.is_empty() {
+            return;
+        }
+        self.joined.take();
+
+        let mut char_start = 0;
+        for chunk in &mut self.chunks {
+            let char_end = char_start + chunk.num_chars;
+            let start = char_range.start.clamp(char_start, char_end);
+            let end = char_range.end.clamp(char_start, char_end);
+            if start < end {
+                let start_byte = byte_index_from_char_index(&chunk.text, start - char_start);
+                let end_byte = byte_index_from_char_index(&chunk.text, end - char_start);
+                chunk.text.drain(start_byte..end_byte);
+                chunk.num_chars -= end - start;
+                self.num_chars -= end - start;
+                self.len -= end_byte - start_byte;
+            }
+            if char_range.end <= char_end {
+                break;
+            }
+            char_start = char_end;
+        }
+        self.chunks.retain(|chunk| !chunk.text.is_empty());
+    }
+
+    fn char_count(&self) -> usize {
+        self.num_chars
+    }
+
+    fn byte_index_from_char_index(&self, char_index: usize) -> usize {
+        let (chunk_index, chunk_start, char_in_chunk) = self.locate(char_index);
+        match self.chunks.get(chunk_index) {
+            Some(chunk) => chunk_start + byte_index_from_char_index(&chunk.text, char_in_chunk),
+            None => 0,
+        }
+    }
+
+    fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    fn replace_with(&mut self, text: &str) {
+        *self = Self::from(text);
+    }
+
+    fn take(&mut self) -> String {
+        let mut rope = std::mem::take(self);
+        match rope.joined.take() {
+            Some(text) => text,
+            None => rope.chunks().collect(),
+        }
+    }
+}
+
+#[test]
+fn test_rope_buffer_edits() {
+    // Do the same edits to a `String` and a `RopeBuffer` with many chunks:
+    let line = "Hello wörld, this is a line of text with ünicode 🦀\n";
+    let mut string = line.repeat(2000);
+    let mut rope = RopeBuffer::from(string.as_str());
+    assert!(10 < rope.chunks().count());
+
+    let num_chars = string.chars().count();
+    let long_text = "a whole lot of text ".repeat(1000);
+    let edits = [
+        (0..0, "Start "),
+        (100..100, "inserted"),
+        (num_chars / 2..num_chars / 2 + 5000, ""),
+        (10..20, long_text.as_str()),
+        (5000..5001, "🦀"),
+    ];
+    for (char_range, text) in edits {
+        string.delete_char_range(char_range.clone());
+        string.insert_text(text, char_range.start);
+        rope.delete_char_range(char_range.clone());
+        rope.insert_text(text, char_range.start);
+
+        assert_eq!(rope.to_string(), string);
+        assert_eq!(rope.as_str(), string);
+        assert_eq!(rope.char_count(), string.chars().count());
+        assert_eq!(rope.len(), string.len());
+        assert_eq!(
+            rope.byte_index_from_char_index(12345),
+            string.byte_index_from_char_index(12345)
+        );
+    }
+    assert!(rope
+        .chunks()
+        .all(|chunk| !chunk.is_empty() && chunk.len() <= 2 * CHUNK_LEN));
+
+    assert_eq!(rope.take(), string);
+    assert!(rope.is_empty());
+    assert_eq!(rope.as_str(), "");
+}
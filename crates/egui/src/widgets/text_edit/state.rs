@@ -47,6 +47,14 @@ pub struct TextEditState {
     // Visual offset when editing singleline text bigger than the width.
     #[cfg_attr(feature = "serde", serde(skip))]
     pub(crate) singleline_offset: f32,
+
+    /// A large paste waiting to be accepted, see [`crate::TextEdit::large_paste_threshold`].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pending_paste: Option<PendingPaste>,
+
+    /// When the text cursor last got focus or moved, for blinking, see [`crate::TextCursorStyle`].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) last_interaction_time: f64,
 }
 
 impl TextEditState {
@@ -75,6 +83,46 @@ impl TextEditState {
         self.cursor.set_range(cursor_range);
     }
 
+    /// A paste that was larger than [`crate::TextEdit::large_paste_threshold`],
+    /// and that is waiting for [`Self::accept_pending_paste`] or [`Self::discard_pending_paste`].
+    pub fn pending_paste(&self) -> Option<&str> {
+        self.pending_paste
+            .as_ref()
+            .filter(|paste| !paste.accepted)
+            .map(|paste| &*paste.text)
+    }
+
+    /// Insert the [`Self::pending_paste`] the next time the [`crate::TextEdit`] is shown.
+    ///
+    /// Remember to [`Self::store`] the state afterwards.
+    pub fn accept_pending_paste(&mut self) {
+        if let Some(paste) = &mut self.pending_paste {
+            paste.accepted = true;
+        }
+    }
+
+    /// Forget about the [`Self::pending_paste`].
+    ///
+    /// Remember to [`Self::store`] the state afterwards.
+    pub fn discard_pending_paste(&mut self) {
+        self.pending_paste = None;
+    }
+
+    pub(crate) fn set_pending_paste(&mut self, text: String) {
+        self.pending_paste = Some(PendingPaste {
+            text: text.into(),
+            accepted: false,
+        });
+    }
+
+    pub(crate) fn take_accepted_paste(&mut self) -> Option<Arc<str>> {
+        if self.pending_paste.as_ref()?.accepted {
+            self.pending_paste.take().map(|paste| paste.text)
+        } else {
+            None
+        }
+    }
+
     pub fn undoer(&self) -> TextEditUndoer {
         self.undoer.lock().clone()
     }
@@ -92,3 +140,10 @@ impl TextEditState {
         self.cursor.range(galley)
     }
 }
+
+#[derive(Clone)]
+struct PendingPaste {
+    /// Wrapped in Arc for cheaper clones.
+    text: Arc<str>,
+    accepted: bool,
+}
@@ -42,6 +42,11 @@ pub trait TextBuffer {
     /// `char_range` is a *character range*, not a byte range.
     fn delete_char_range(&mut self, char_range: Range<usize>);
 
+    /// The number of characters in this buffer.
+    fn char_count(&self) -> usize {
+        self.as_str().chars().count()
+    }
+
     /// Reads the given character range.
     fn char_range(&self, char_range: Range<usize>) -> &str {
         slice_char_range(self.as_str(), char_range)
@@ -73,7 +78,7 @@ pub trait TextBuffer {
         if char_limit < usize::MAX {
             let mut new_string = text_to_insert;
             // Avoid subtract with overflow panic
-            let cutoff = char_limit.saturating_sub(self.as_str().chars().count());
+            let cutoff = char_limit.saturating_sub(self.char_count());
 
             new_string = match new_string.char_indices().nth(cutoff) {
                 None => new_string,
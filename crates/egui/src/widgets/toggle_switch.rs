@@ -0,0 +1,124 @@
+use crate::*;
+
+/// iOS-style on/off switch, with an optional text label.
+///
+/// Works just like a [`Checkbox`].
+/// Usually you'd use [`Ui::toggle_switch`] instead.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// # let mut dark_mode = true;
+/// // These are equivalent:
+/// ui.toggle_switch(&mut dark_mode, "Dark mode");
+/// ui.add(egui::ToggleSwitch::new(&mut dark_mode, "Dark mode"));
+/// # });
+/// ```
+#[must_use = "You should put this widget in an ui with `ui.add(widget);`"]
+pub struct ToggleSwitch<'a> {
+    on: &'a mut bool,
+    text: WidgetText,
+}
+
+impl<'a> ToggleSwitch<'a> {
+    pub fn new(on: &'a mut bool, text: impl Into<WidgetText>) -> Self {
+        Self {
+            on,
+            text: text.into(),
+        }
+    }
+
+    pub fn without_text(on: &'a mut bool) -> Self {
+        Self::new(on, WidgetText::default())
+    }
+}
+
+impl<'a> Widget for ToggleSwitch<'a> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let Self { on, text } = self;
+
+        let spacing = &ui.spacing();
+        let switch_size = spacing.toggle_switch_size;
+        let icon_spacing = spacing.icon_spacing;
+        let min_height = spacing.interact_size.y.max(switch_size.y);
+
+        let (galley, mut desired_size) = if text.is_empty() {
+            (None, switch_size)
+        } else {
+            let total_extra = vec2(switch_size.x + icon_spacing, 0.0);
+
+            let wrap_width = ui.available_width() - total_extra.x;
+            let galley = text.into_galley(ui, None, wrap_width, TextStyle::Button);
+
+            let desired_size = total_extra + galley.size();
+            (Some(galley), desired_size)
+        };
+
+        desired_size.y = desired_size.y.max(min_height);
+        let (rect, response) = ui.allocate_exact_size(desired_size, Sense::click());
+        let mut response = response.with_extra_pointers();
+
+        if response.clicked() {
+            *on = !*on;
+            response.mark_changed();
+        }
+        response.widget_info(|| {
+            WidgetInfo::selected(
+                WidgetType::Checkbox,
+                *on,
+                galley.as_ref().map_or("", |x| x.text()),
+            )
+        });
+
+        if ui.is_rect_visible(rect) {
+            let how_on = ui.ctx().animate_bool(response.id, *on);
+            let visuals = ui.style().interact_selectable(&response, *on);
+
+            let switch_rect = Rect::from_min_size(
+                pos2(rect.left(), rect.center().y - 0.5 * switch_size.y),
+                switch_size,
+            )
+            .expand(visuals.expansion);
+            let radius = 0.5 * switch_rect.height();
+            ui.painter()
+                .rect(switch_rect, radius, visuals.bg_fill, visuals.bg_stroke);
+
+            // The knob slides from left to right as it turns on:
+            let knob_x = lerp(
+                (switch_rect.left() + radius)..=(switch_rect.right() - radius),
+                how_on,
+            );
+            let center = pos2(knob_x, switch_rect.center().y);
+            ui.painter()
+                .circle(center, 0.75 * radius, visuals.bg_fill, visuals.fg_stroke);
+
+            if let Some(galley) = galley {
+                let text_pos = pos2(
+                    rect.min.x + switch_size.x + icon_spacing,
+                    rect.center().y - 0.5 * galley.size().y,
+                );
+                let text_color = ui.style().interact(&response).text_color();
+                ui.painter().galley(text_pos, galley, text_color);
+            }
+        }
+
+        response
+    }
+}
+
+#[test]
+fn test_toggle_switch() {
+    use crate::test_harness::Harness;
+    use std::cell::Cell;
+
+    let on = Cell::new(false);
+    let mut harness = Harness::new_ui(|ui| {
+        let mut value = on.get();
+        ui.toggle_switch(&mut value, "Dark mode");
+        on.set(value);
+    });
+
+    harness.get_by_label("Dark mode").click();
+    assert!(on.get());
+    harness.get_by_label("Dark mode").click();
+    assert!(!on.get());
+}
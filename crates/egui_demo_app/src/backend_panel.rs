@@ -321,6 +321,8 @@ struct EguiWindows {
     // egui stuff:
     settings: bool,
     inspection: bool,
+    devtools: bool,
+    time_travel: bool,
     memory: bool,
     output_events: bool,
 
@@ -339,6 +341,8 @@ impl EguiWindows {
         Self {
             settings: false,
             inspection: false,
+            devtools: false,
+            time_travel: false,
             memory: false,
             output_events: false,
             output_event_history: Default::default(),
@@ -349,6 +353,8 @@ impl EguiWindows {
         let Self {
             settings,
             inspection,
+            devtools,
+            time_travel,
             memory,
             output_events,
             output_event_history: _,
@@ -356,6 +362,8 @@ impl EguiWindows {
 
         ui.checkbox(settings, "🔧 Settings");
         ui.checkbox(inspection, "🔍 Inspection");
+        ui.checkbox(devtools, "🛠 Devtools");
+        ui.checkbox(time_travel, "⏪ Time travel");
         ui.checkbox(memory, "📝 Memory");
         ui.checkbox(output_events, "📤 Output Events");
     }
@@ -364,6 +372,8 @@ impl EguiWindows {
         let Self {
             settings,
             inspection,
+            devtools,
+            time_travel,
             memory,
             output_events,
             output_event_history,
@@ -392,6 +402,20 @@ impl EguiWindows {
                 ctx.inspection_ui(ui);
             });
 
+        egui::Window::new("🛠 Devtools")
+            .open(devtools)
+            .default_width(520.0)
+            .show(ctx, |ui| {
+                ctx.devtools_ui(ui);
+            });
+
+        egui::Window::new("⏪ Time travel")
+            .open(time_travel)
+            .vscroll(true)
+            .show(ctx, |ui| {
+                ctx.time_travel_ui(ui);
+            });
+
         egui::Window::new("📝 Memory")
             .open(memory)
             .resizable(false)
@@ -93,6 +93,10 @@ impl super::View for MultiTouch {
                 // touch pressure will make the arrow thicker (not all touch devices support this):
                 stroke_width += 10. * multi_touch.force;
 
+                self.last_touch_time = ui.input(|i| i.time);
+            } else if response.rotation_delta() != 0.0 {
+                // Trackpads can rotate too:
+                self.rotation += response.rotation_delta();
                 self.last_touch_time = ui.input(|i| i.time);
             } else {
                 self.slowly_reset(ui);
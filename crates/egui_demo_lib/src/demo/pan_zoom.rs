@@ -66,6 +66,17 @@ impl super::View for PanZoom {
 
                 // Pan:
                 self.transform = TSTransform::from_translation(pan_delta) * self.transform;
+
+                // Smart zoom toggles between the default view and zooming in on the pointer:
+                if ui.ctx().input(|i| i.smart_zoom()) {
+                    self.transform = if self.transform == TSTransform::default() {
+                        TSTransform::from_translation(pointer_in_layer.to_vec2())
+                            * TSTransform::from_scaling(2.0)
+                            * TSTransform::from_translation(-pointer_in_layer.to_vec2())
+                    } else {
+                        TSTransform::default()
+                    };
+                }
             }
         }
 
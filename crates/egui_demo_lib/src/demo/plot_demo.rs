@@ -4,9 +4,10 @@ use std::ops::RangeInclusive;
 use egui::*;
 
 use egui_plot::{
-    Arrows, AxisHints, Bar, BarChart, BoxElem, BoxPlot, BoxSpread, CoordinatesFormatter, Corner,
-    GridInput, GridMark, HLine, Legend, Line, LineStyle, MarkerShape, Plot, PlotImage, PlotPoint,
-    PlotPoints, PlotResponse, Points, Polygon, Text, VLine,
+    Arrows, AxisHints, Bar, BarChart, Binning, BoxElem, BoxPlot, BoxSpread, Candle, CandleStyle,
+    CandlestickChart, CoordinatesFormatter, Corner, GridInput, GridMark, HLine, Histogram, Legend,
+    Line, LineInterpolation, LineStyle, MarkerShape, Plot, PlotImage, PlotPoint, PlotPoints,
+    PlotResponse, Points, Polygon, Text, VLine,
 };
 
 // ----------------------------------------------------------------------------
@@ -435,6 +436,10 @@ impl LegendDemo {
                     .clamp_range(0.0..=1.0),
             );
             ui.end_row();
+
+            ui.label("Reorderable:");
+            ui.checkbox(&mut config.reorderable, "Drag the entries to reorder them");
+            ui.end_row();
         });
         let legend_plot = Plot::new("legend_demo")
             .y_axis_width(2)
@@ -864,6 +869,8 @@ enum Chart {
     GaussBars,
     StackedBars,
     BoxPlot,
+    Distribution,
+    Candlestick,
 }
 
 impl Default for Chart {
@@ -876,6 +883,7 @@ impl Default for Chart {
 struct ChartsDemo {
     chart: Chart,
     vertical: bool,
+    ohlc: bool,
     allow_zoom: Vec2b,
     allow_drag: Vec2b,
     allow_scroll: Vec2b,
@@ -885,6 +893,7 @@ impl Default for ChartsDemo {
     fn default() -> Self {
         Self {
             vertical: true,
+            ohlc: false,
             chart: Chart::default(),
             allow_zoom: true.into(),
             allow_drag: true.into(),
@@ -902,12 +911,22 @@ impl ChartsDemo {
                     ui.selectable_value(&mut self.chart, Chart::GaussBars, "Histogram");
                     ui.selectable_value(&mut self.chart, Chart::StackedBars, "Stacked Bar Chart");
                     ui.selectable_value(&mut self.chart, Chart::BoxPlot, "Box Plot");
+                    ui.selectable_value(&mut self.chart, Chart::Distribution, "Distribution");
+                    ui.selectable_value(&mut self.chart, Chart::Candlestick, "Candlestick");
                 });
-                ui.label("Orientation:");
-                ui.horizontal(|ui| {
-                    ui.selectable_value(&mut self.vertical, true, "Vertical");
-                    ui.selectable_value(&mut self.vertical, false, "Horizontal");
-                });
+                if self.chart == Chart::Candlestick {
+                    ui.label("Style:");
+                    ui.horizontal(|ui| {
+                        ui.selectable_value(&mut self.ohlc, false, "Candlesticks");
+                        ui.selectable_value(&mut self.ohlc, true, "OHLC bars");
+                    });
+                } else {
+                    ui.label("Orientation:");
+                    ui.horizontal(|ui| {
+                        ui.selectable_value(&mut self.vertical, true, "Vertical");
+                        ui.selectable_value(&mut self.vertical, false, "Horizontal");
+                    });
+                }
             });
             ui.vertical(|ui| {
                 ui.group(|ui| {
@@ -935,6 +954,8 @@ impl ChartsDemo {
             Chart::GaussBars => self.bar_gauss(ui),
             Chart::StackedBars => self.bar_stacked(ui),
             Chart::BoxPlot => self.box_plot(ui),
+            Chart::Distribution => self.distribution(ui),
+            Chart::Candlestick => self.candlestick(ui),
         }
     }
 
@@ -1077,6 +1098,108 @@ impl ChartsDemo {
             })
             .response
     }
+
+    fn distribution(&self, ui: &mut Ui) -> Response {
+        // Made up measurements, roughly normally distributed, with a few far off:
+        let mut seed = 12345_u32;
+        let mut random = move || {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            (seed >> 8) as f64 / (1 << 24) as f64
+        };
+        let mut values: Vec<f64> = (0..500)
+            .map(|_| 10.0 + 2.0 * (random() + random() + random() - 1.5))
+            .collect();
+        values.extend([3.0, 16.5, 17.0]);
+
+        let mut histogram = Histogram::new(values.iter().copied())
+            .binning(Binning::Width(0.25))
+            .color(Color32::LIGHT_BLUE)
+            .name("Measurements");
+        // The box goes below (or to the left of) the bars:
+        let box_elem = BoxElem::from_values(-10.0, values)
+            .box_width(12.0)
+            .whisker_width(8.0)
+            .name("Measurements");
+        let mut box_plot = BoxPlot::new(vec![box_elem])
+            .color(Color32::LIGHT_BLUE)
+            .name("Measurements");
+        if !self.vertical {
+            histogram = histogram.horizontal();
+        } else {
+            box_plot = box_plot.horizontal();
+        }
+
+        Plot::new("Distribution Demo")
+            .legend(Legend::default())
+            .allow_zoom(self.allow_zoom)
+            .allow_drag(self.allow_drag)
+            .allow_scroll(self.allow_scroll)
+            .show(ui, |plot_ui| {
+                plot_ui.histogram(histogram);
+                plot_ui.box_plot(box_plot);
+            })
+            .response
+    }
+
+    fn candlestick(&self, ui: &mut Ui) -> Response {
+        // A made up price, wobbling up and down:
+        let mut close = 100.0;
+        let candles: Vec<Candle> = (0..60)
+            .map(|day| {
+                let x = day as f64;
+                let open = close;
+                close = open + 3.0 * (0.7 * x).sin() + 2.0 * (0.13 * x).cos();
+                let high = open.max(close) + 1.0 + (1.9 * x).sin().abs() * 2.0;
+                let low = open.min(close) - 1.0 - (2.3 * x).cos().abs() * 2.0;
+                let volume = 1000.0 + 800.0 * (1.1 * x).sin().abs() + 100.0 * (open - close).abs();
+                Candle::new(x, open, high, low, close)
+                    .name(format!("Day {}", day + 1))
+                    .volume(volume.round())
+            })
+            .collect();
+        let closes = Line::new(
+            candles
+                .iter()
+                .map(|candle| [candle.x, candle.close])
+                .collect::<PlotPoints>(),
+        )
+        .interpolation(LineInterpolation::StepAfter)
+        .color(Color32::GRAY)
+        .name("Close");
+
+        let style = if self.ohlc {
+            CandleStyle::Ohlc
+        } else {
+            CandleStyle::Candlestick
+        };
+        let chart = CandlestickChart::new(candles).style(style).name("ACME");
+        let volume = chart.volume_bars();
+
+        let link_group = Id::new("candlestick_demo_x");
+        let height = ui.available_height();
+        ui.vertical(|ui| {
+            Plot::new("Candlestick Demo")
+                .legend(Legend::default())
+                .link_axis(link_group, true, false)
+                .link_cursor(link_group, true, false)
+                .height(0.7 * height)
+                .allow_zoom(self.allow_zoom)
+                .allow_drag(self.allow_drag)
+                .allow_scroll(self.allow_scroll)
+                .show(ui, |plot_ui| {
+                    plot_ui.line(closes);
+                    plot_ui.candlestick_chart(chart);
+                });
+            Plot::new("Candlestick Volume Demo")
+                .link_axis(link_group, true, false)
+                .link_cursor(link_group, true, false)
+                .allow_zoom(self.allow_zoom)
+                .allow_drag(self.allow_drag)
+                .allow_scroll(self.allow_scroll)
+                .show(ui, |plot_ui| plot_ui.bar_chart(volume));
+        })
+        .response
+    }
 }
 
 fn is_approx_zero(val: f64) -> bool {
@@ -18,6 +18,8 @@ pub struct Sliders {
     pub value: f64,
     pub trailing_fill: bool,
     pub handle_shape: HandleShape,
+    pub ticks: bool,
+    pub range: (f64, f64),
 }
 
 impl Default for Sliders {
@@ -35,6 +37,8 @@ impl Default for Sliders {
             value: 10.0,
             trailing_fill: false,
             handle_shape: HandleShape::Circle,
+            ticks: false,
+            range: (2500.0, 7500.0),
         }
     }
 }
@@ -70,6 +74,8 @@ impl super::View for Sliders {
             value,
             trailing_fill,
             handle_shape,
+            ticks,
+            range,
         } = self;
 
         ui.label("You can click a slider value to edit it with the keyboard.");
@@ -103,7 +109,8 @@ impl super::View for Sliders {
                     .text("i32 demo slider")
                     .step_by(istep)
                     .trailing_fill(*trailing_fill)
-                    .handle_shape(*handle_shape),
+                    .handle_shape(*handle_shape)
+                    .tick_labels(*ticks),
             );
             *value = value_i32 as f64;
         } else {
@@ -116,7 +123,20 @@ impl super::View for Sliders {
                     .text("f64 demo slider")
                     .step_by(istep)
                     .trailing_fill(*trailing_fill)
-                    .handle_shape(*handle_shape),
+                    .handle_shape(*handle_shape)
+                    .tick_labels(*ticks),
+            );
+
+            ui.add(
+                Slider::range_mode(range, (*min)..=(*max))
+                    .logarithmic(*logarithmic)
+                    .clamp_to_range(*clamp_to_range)
+                    .smart_aim(*smart_aim)
+                    .orientation(orientation)
+                    .text("range slider")
+                    .step_by(istep)
+                    .handle_shape(*handle_shape)
+                    .tick_labels(*ticks),
             );
 
             ui.label(
@@ -151,6 +171,11 @@ impl super::View for Sliders {
 
         ui.separator();
 
+        ui.checkbox(ticks, "Show tick marks");
+        ui.label("Tick marks are at the powers of ten on logarithmic sliders, and at each step if there is room.");
+
+        ui.separator();
+
         ui.checkbox(trailing_fill, "Toggle trailing color");
         ui.label("When enabled, trailing color will be painted up until the handle.");
 
@@ -14,11 +14,11 @@ pub struct TableDemo {
     demo: DemoType,
     striped: bool,
     resizable: bool,
-    clickable: bool,
+    selectable: bool,
     num_rows: usize,
     scroll_to_row_slider: usize,
     scroll_to_row: Option<usize>,
-    selection: std::collections::HashSet<usize>,
+    selection: egui_extras::TableSelection,
     checked: bool,
 }
 
@@ -28,11 +28,11 @@ impl Default for TableDemo {
             demo: DemoType::Manual,
             striped: true,
             resizable: true,
-            clickable: true,
+            selectable: true,
             num_rows: 10_000,
             scroll_to_row_slider: 0,
             scroll_to_row: None,
-            selection: Default::default(),
+            selection: egui_extras::TableSelection::multiple(),
             checked: false,
         }
     }
@@ -62,7 +62,7 @@ impl super::View for TableDemo {
             ui.horizontal(|ui| {
                 ui.checkbox(&mut self.striped, "Striped");
                 ui.checkbox(&mut self.resizable, "Resizable columns");
-                ui.checkbox(&mut self.clickable, "Clickable rows");
+                ui.checkbox(&mut self.selectable, "Selectable rows");
             });
 
             ui.label("Table type:");
@@ -147,8 +147,8 @@ impl TableDemo {
             .column(Column::remainder())
             .min_scrolled_height(0.0);
 
-        if self.clickable {
-            table = table.sense(egui::Sense::click());
+        if self.selectable {
+            table = table.selection(&mut self.selection);
         }
 
         if let Some(row_index) = self.scroll_to_row.take() {
@@ -179,8 +179,6 @@ impl TableDemo {
                         let is_thick = thick_row(row_index);
                         let row_height = if is_thick { 30.0 } else { 18.0 };
                         body.row(row_height, |mut row| {
-                            row.set_selected(self.selection.contains(&row_index));
-
                             row.col(|ui| {
                                 ui.label(row_index.to_string());
                             });
@@ -201,16 +199,12 @@ impl TableDemo {
                                     ui.label("Normal row");
                                 }
                             });
-
-                            self.toggle_row_selection(row_index, &row.response());
                         });
                     }
                 }
                 DemoType::ManyHomogeneous => {
                     body.rows(text_height, self.num_rows, |mut row| {
                         let row_index = row.index();
-                        row.set_selected(self.selection.contains(&row_index));
-
                         row.col(|ui| {
                             ui.label(row_index.to_string());
                         });
@@ -228,16 +222,12 @@ impl TableDemo {
                                 egui::Label::new("Thousands of rows of even height").wrap(false),
                             );
                         });
-
-                        self.toggle_row_selection(row_index, &row.response());
                     });
                 }
                 DemoType::ManyHeterogenous => {
                     let row_height = |i: usize| if thick_row(i) { 30.0 } else { 18.0 };
                     body.heterogeneous_rows((0..self.num_rows).map(row_height), |mut row| {
                         let row_index = row.index();
-                        row.set_selected(self.selection.contains(&row_index));
-
                         row.col(|ui| {
                             ui.label(row_index.to_string());
                         });
@@ -258,22 +248,10 @@ impl TableDemo {
                                 ui.label("Normal row");
                             }
                         });
-
-                        self.toggle_row_selection(row_index, &row.response());
                     });
                 }
             });
     }
-
-    fn toggle_row_selection(&mut self, row_index: usize, row_response: &egui::Response) {
-        if row_response.clicked() {
-            if self.selection.contains(&row_index) {
-                self.selection.remove(&row_index);
-            } else {
-                self.selection.insert(row_index);
-            }
-        }
-    }
 }
 
 fn expanding_content(ui: &mut egui::Ui) {
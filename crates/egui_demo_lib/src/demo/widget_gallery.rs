@@ -7,6 +7,21 @@ enum Enum {
 }
 
 /// Shows off one example of each major type of widget.
+///
+/// Each widget has a link to its documentation and a button to copy the code for it,
+/// and the widgets can be searched by name.
+///
+/// Use [`Self::ui`] to embed the gallery in your own app,
+/// e.g. as a live preview of your style in a settings or developer menu:
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// # let mut gallery = egui_demo_lib::WidgetGallery::default();
+/// ui.collapsing("Widget gallery", |ui| {
+///     gallery.ui(ui);
+/// });
+/// # });
+/// ```
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct WidgetGallery {
     enabled: bool,
@@ -19,6 +34,9 @@ pub struct WidgetGallery {
     color: egui::Color32,
     animate_progress_bar: bool,
 
+    #[cfg_attr(feature = "serde", serde(skip))]
+    search: String,
+
     #[cfg(feature = "chrono")]
     #[cfg_attr(feature = "serde", serde(skip))]
     date: Option<chrono::NaiveDate>,
@@ -36,6 +54,7 @@ impl Default for WidgetGallery {
             string: Default::default(),
             color: egui::Color32::LIGHT_BLUE.linear_multiply(0.5),
             animate_progress_bar: false,
+            search: Default::default(),
             #[cfg(feature = "chrono")]
             date: None,
         }
@@ -53,7 +72,6 @@ impl super::Demo for WidgetGallery {
             .resizable(true)
             .default_width(280.0)
             .show(ctx, |ui| {
-                use super::View as _;
                 self.ui(ui);
             });
     }
@@ -61,17 +79,44 @@ impl super::Demo for WidgetGallery {
 
 impl super::View for WidgetGallery {
     fn ui(&mut self, ui: &mut egui::Ui) {
+        WidgetGallery::ui(self, ui);
+    }
+}
+
+impl WidgetGallery {
+    /// Show the gallery, its search field and its options.
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Search:");
+            ui.add(
+                egui::TextEdit::singleline(&mut self.search)
+                    .hint_text("Widget name or code")
+                    .desired_width(160.0),
+            );
+            if ui
+                .add_enabled(!self.search.is_empty(), egui::Button::new("✖").small())
+                .on_hover_text("Clear search")
+                .clicked()
+            {
+                self.search.clear();
+            }
+        });
+
+        ui.separator();
+
         ui.add_enabled_ui(self.enabled, |ui| {
             ui.set_visible(self.visible);
             ui.set_opacity(self.opacity);
 
-            egui::Grid::new("my_grid")
-                .num_columns(2)
+            let num_shown = egui::Grid::new("my_grid")
+                .num_columns(3)
                 .spacing([40.0, 4.0])
                 .striped(true)
-                .show(ui, |ui| {
-                    self.gallery_grid_contents(ui);
-                });
+                .show(ui, |ui| self.gallery_grid_contents(ui))
+                .inner;
+            if num_shown == 0 {
+                ui.weak("No widget matches the search.");
+            }
         });
 
         ui.separator();
@@ -104,7 +149,8 @@ impl super::View for WidgetGallery {
 }
 
 impl WidgetGallery {
-    fn gallery_grid_contents(&mut self, ui: &mut egui::Ui) {
+    /// Returns how many widgets matched the search.
+    fn gallery_grid_contents(&mut self, ui: &mut egui::Ui) -> usize {
         let Self {
             enabled: _,
             visible: _,
@@ -115,151 +161,409 @@ impl WidgetGallery {
             string,
             color,
             animate_progress_bar,
+            search,
             #[cfg(feature = "chrono")]
             date,
         } = self;
 
-        ui.add(doc_link_label("Label", "label"));
-        ui.label("Welcome to the widget gallery!");
-        ui.end_row();
+        let mut rows = GalleryRows::new(search);
 
-        ui.add(doc_link_label("Hyperlink", "Hyperlink"));
-        use egui::special_emojis::GITHUB;
-        ui.hyperlink_to(
-            format!("{GITHUB} egui on GitHub"),
-            "https://github.com/emilk/egui",
+        rows.show(
+            ui,
+            "Label",
+            "label",
+            r#"ui.label("Welcome to the widget gallery!");"#,
+            |ui| {
+                ui.label("Welcome to the widget gallery!");
+            },
         );
-        ui.end_row();
 
-        ui.add(doc_link_label("TextEdit", "TextEdit"));
-        ui.add(egui::TextEdit::singleline(string).hint_text("Write something here"));
-        ui.end_row();
+        rows.show(
+            ui,
+            "Hyperlink",
+            "Hyperlink",
+            r#"ui.hyperlink_to("egui on GitHub", "https://github.com/emilk/egui");"#,
+            |ui| {
+                use egui::special_emojis::GITHUB;
+                ui.hyperlink_to(
+                    format!("{GITHUB} egui on GitHub"),
+                    "https://github.com/emilk/egui",
+                );
+            },
+        );
 
-        ui.add(doc_link_label("Button", "button"));
-        if ui.button("Click me!").clicked() {
-            *boolean = !*boolean;
-        }
-        ui.end_row();
+        rows.show(
+            ui,
+            "TextEdit",
+            "TextEdit",
+            r#"ui.add(egui::TextEdit::singleline(&mut string).hint_text("Write something here"));"#,
+            |ui| {
+                ui.add(egui::TextEdit::singleline(string).hint_text("Write something here"));
+            },
+        );
 
-        ui.add(doc_link_label("Link", "link"));
-        if ui.link("Click me!").clicked() {
-            *boolean = !*boolean;
-        }
-        ui.end_row();
+        rows.show(
+            ui,
+            "Button",
+            "button",
+            r#"if ui.button("Click me!").clicked() {
+    boolean = !boolean;
+}"#,
+            |ui| {
+                if ui.button("Click me!").clicked() {
+                    *boolean = !*boolean;
+                }
+            },
+        );
 
-        ui.add(doc_link_label("Checkbox", "checkbox"));
-        ui.checkbox(boolean, "Checkbox");
-        ui.end_row();
+        rows.show(
+            ui,
+            "Link",
+            "link",
+            r#"if ui.link("Click me!").clicked() {
+    boolean = !boolean;
+}"#,
+            |ui| {
+                if ui.link("Click me!").clicked() {
+                    *boolean = !*boolean;
+                }
+            },
+        );
 
-        ui.add(doc_link_label("RadioButton", "radio"));
-        ui.horizontal(|ui| {
-            ui.radio_value(radio, Enum::First, "First");
-            ui.radio_value(radio, Enum::Second, "Second");
-            ui.radio_value(radio, Enum::Third, "Third");
-        });
-        ui.end_row();
+        rows.show(
+            ui,
+            "Checkbox",
+            "checkbox",
+            r#"ui.checkbox(&mut boolean, "Checkbox");"#,
+            |ui| {
+                ui.checkbox(boolean, "Checkbox");
+            },
+        );
 
-        ui.add(doc_link_label("SelectableLabel", "SelectableLabel"));
-        ui.horizontal(|ui| {
-            ui.selectable_value(radio, Enum::First, "First");
-            ui.selectable_value(radio, Enum::Second, "Second");
-            ui.selectable_value(radio, Enum::Third, "Third");
-        });
-        ui.end_row();
+        rows.show(
+            ui,
+            "ToggleSwitch",
+            "ToggleSwitch",
+            r#"ui.toggle_switch(&mut boolean, "Toggle switch");"#,
+            |ui| {
+                ui.toggle_switch(boolean, "Toggle switch");
+            },
+        );
 
-        ui.add(doc_link_label("ComboBox", "ComboBox"));
+        rows.show(
+            ui,
+            "RadioButton",
+            "radio",
+            r#"ui.horizontal(|ui| {
+    ui.radio_value(&mut radio, Enum::First, "First");
+    ui.radio_value(&mut radio, Enum::Second, "Second");
+    ui.radio_value(&mut radio, Enum::Third, "Third");
+});"#,
+            |ui| {
+                ui.horizontal(|ui| {
+                    ui.radio_value(radio, Enum::First, "First");
+                    ui.radio_value(radio, Enum::Second, "Second");
+                    ui.radio_value(radio, Enum::Third, "Third");
+                });
+            },
+        );
 
-        egui::ComboBox::from_label("Take your pick")
-            .selected_text(format!("{radio:?}"))
-            .show_ui(ui, |ui| {
-                ui.style_mut().wrap = Some(false);
-                ui.set_min_width(60.0);
-                ui.selectable_value(radio, Enum::First, "First");
-                ui.selectable_value(radio, Enum::Second, "Second");
-                ui.selectable_value(radio, Enum::Third, "Third");
-            });
-        ui.end_row();
+        rows.show(
+            ui,
+            "SelectableLabel",
+            "SelectableLabel",
+            r#"ui.horizontal(|ui| {
+    ui.selectable_value(&mut radio, Enum::First, "First");
+    ui.selectable_value(&mut radio, Enum::Second, "Second");
+    ui.selectable_value(&mut radio, Enum::Third, "Third");
+});"#,
+            |ui| {
+                ui.horizontal(|ui| {
+                    ui.selectable_value(radio, Enum::First, "First");
+                    ui.selectable_value(radio, Enum::Second, "Second");
+                    ui.selectable_value(radio, Enum::Third, "Third");
+                });
+            },
+        );
 
-        ui.add(doc_link_label("Slider", "Slider"));
-        ui.add(egui::Slider::new(scalar, 0.0..=360.0).suffix("°"));
-        ui.end_row();
+        rows.show(
+            ui,
+            "SegmentedControl",
+            "SegmentedControl",
+            r#"ui.add(
+    egui::SegmentedControl::new(&mut radio)
+        .segment(Enum::First, "First")
+        .segment(Enum::Second, "Second")
+        .segment(Enum::Third, "Third"),
+);"#,
+            |ui| {
+                ui.add(
+                    egui::SegmentedControl::new(radio)
+                        .segment(Enum::First, "First")
+                        .segment(Enum::Second, "Second")
+                        .segment(Enum::Third, "Third"),
+                );
+            },
+        );
 
-        ui.add(doc_link_label("DragValue", "DragValue"));
-        ui.add(egui::DragValue::new(scalar).speed(1.0));
-        ui.end_row();
+        rows.show(
+            ui,
+            "Chip",
+            "Chip",
+            r#"if ui.add(egui::Chip::new("Filter").selected(boolean)).clicked() {
+    boolean = !boolean;
+}"#,
+            |ui| {
+                if ui
+                    .add(egui::Chip::new("Filter").selected(*boolean))
+                    .clicked()
+                {
+                    *boolean = !*boolean;
+                }
+            },
+        );
 
-        ui.add(doc_link_label("ProgressBar", "ProgressBar"));
-        let progress = *scalar / 360.0;
-        let progress_bar = egui::ProgressBar::new(progress)
-            .show_percentage()
-            .animate(*animate_progress_bar);
-        *animate_progress_bar = ui
-            .add(progress_bar)
-            .on_hover_text("The progress bar can be animated!")
-            .hovered();
-        ui.end_row();
+        rows.show(
+            ui,
+            "ComboBox",
+            "ComboBox",
+            r#"egui::ComboBox::from_label("Take your pick")
+    .selected_text(format!("{radio:?}"))
+    .show_ui(ui, |ui| {
+        ui.selectable_value(&mut radio, Enum::First, "First");
+        ui.selectable_value(&mut radio, Enum::Second, "Second");
+        ui.selectable_value(&mut radio, Enum::Third, "Third");
+    });"#,
+            |ui| {
+                egui::ComboBox::from_label("Take your pick")
+                    .selected_text(format!("{radio:?}"))
+                    .show_ui(ui, |ui| {
+                        ui.style_mut().wrap = Some(false);
+                        ui.set_min_width(60.0);
+                        ui.selectable_value(radio, Enum::First, "First");
+                        ui.selectable_value(radio, Enum::Second, "Second");
+                        ui.selectable_value(radio, Enum::Third, "Third");
+                    });
+            },
+        );
 
-        ui.add(doc_link_label("Color picker", "color_edit"));
-        ui.color_edit_button_srgba(color);
-        ui.end_row();
+        rows.show(
+            ui,
+            "Slider",
+            "Slider",
+            r#"ui.add(egui::Slider::new(&mut scalar, 0.0..=360.0).suffix("°"));"#,
+            |ui| {
+                ui.add(egui::Slider::new(scalar, 0.0..=360.0).suffix("°"));
+            },
+        );
+
+        rows.show(
+            ui,
+            "DragValue",
+            "DragValue",
+            "ui.add(egui::DragValue::new(&mut scalar).speed(1.0));",
+            |ui| {
+                ui.add(egui::DragValue::new(scalar).speed(1.0));
+            },
+        );
+
+        rows.show(
+            ui,
+            "ProgressBar",
+            "ProgressBar",
+            "ui.add(egui::ProgressBar::new(progress).show_percentage());",
+            |ui| {
+                let progress = *scalar / 360.0;
+                let progress_bar = egui::ProgressBar::new(progress)
+                    .show_percentage()
+                    .animate(*animate_progress_bar);
+                *animate_progress_bar = ui
+                    .add(progress_bar)
+                    .on_hover_text("The progress bar can be animated!")
+                    .hovered();
+            },
+        );
+
+        rows.show(
+            ui,
+            "Color picker",
+            "color_edit",
+            "ui.color_edit_button_srgba(&mut color);",
+            |ui| {
+                ui.color_edit_button_srgba(color);
+            },
+        );
 
-        ui.add(doc_link_label("Image", "Image"));
         let egui_icon = egui::include_image!("../../data/icon.png");
-        ui.add(egui::Image::new(egui_icon.clone()));
-        ui.end_row();
 
-        ui.add(doc_link_label(
+        rows.show(
+            ui,
+            "Image",
+            "Image",
+            r#"ui.add(egui::Image::new(egui::include_image!("icon.png")));"#,
+            |ui| {
+                ui.add(egui::Image::new(egui_icon.clone()));
+            },
+        );
+
+        rows.show(
+            ui,
             "Button with image",
             "Button::image_and_text",
-        ));
-        if ui
-            .add(egui::Button::image_and_text(egui_icon, "Click me!"))
-            .clicked()
-        {
-            *boolean = !*boolean;
-        }
-        ui.end_row();
+            r#"if ui
+    .add(egui::Button::image_and_text(egui::include_image!("icon.png"), "Click me!"))
+    .clicked()
+{
+    boolean = !boolean;
+}"#,
+            |ui| {
+                if ui
+                    .add(egui::Button::image_and_text(egui_icon, "Click me!"))
+                    .clicked()
+                {
+                    *boolean = !*boolean;
+                }
+            },
+        );
 
         #[cfg(feature = "chrono")]
-        {
-            let date = date.get_or_insert_with(|| chrono::offset::Utc::now().date_naive());
-            ui.add(doc_link_label_with_crate(
-                "egui_extras",
-                "DatePickerButton",
-                "DatePickerButton",
-            ));
-            ui.add(egui_extras::DatePickerButton::new(date));
-            ui.end_row();
-        }
-
-        ui.add(doc_link_label("Separator", "separator"));
-        ui.separator();
-        ui.end_row();
+        rows.show_with_crate(
+            ui,
+            "egui_extras",
+            "DatePickerButton",
+            "DatePickerButton",
+            "ui.add(egui_extras::DatePickerButton::new(&mut date));",
+            |ui| {
+                let date = date.get_or_insert_with(|| chrono::offset::Utc::now().date_naive());
+                ui.add(egui_extras::DatePickerButton::new(date));
+            },
+        );
 
-        ui.add(doc_link_label("CollapsingHeader", "collapsing"));
-        ui.collapsing("Click to see what is hidden!", |ui| {
-            ui.horizontal_wrapped(|ui| {
-                ui.spacing_mut().item_spacing.x = 0.0;
-                ui.label("It's a ");
-                ui.add(doc_link_label("Spinner", "spinner"));
-                ui.add_space(4.0);
-                ui.add(egui::Spinner::new());
-            });
+        rows.show(ui, "Separator", "separator", "ui.separator();", |ui| {
+            ui.separator();
         });
-        ui.end_row();
 
-        ui.add(doc_link_label_with_crate("egui_plot", "Plot", "plot"));
-        example_plot(ui);
-        ui.end_row();
+        rows.show(
+            ui,
+            "CollapsingHeader",
+            "collapsing",
+            r#"ui.collapsing("Click to see what is hidden!", |ui| {
+    ui.add(egui::Spinner::new());
+});"#,
+            |ui| {
+                ui.collapsing("Click to see what is hidden!", |ui| {
+                    ui.horizontal_wrapped(|ui| {
+                        ui.spacing_mut().item_spacing.x = 0.0;
+                        ui.label("It's a ");
+                        ui.add(doc_link_label("Spinner", "spinner"));
+                        ui.add_space(4.0);
+                        ui.add(egui::Spinner::new());
+                    });
+                });
+            },
+        );
 
-        ui.hyperlink_to(
-            "Custom widget:",
-            super::toggle_switch::url_to_file_source_code(),
+        rows.show_with_crate(
+            ui,
+            "egui_plot",
+            "Plot",
+            "plot",
+            r#"egui_plot::Plot::new("example_plot")
+    .height(32.0)
+    .show(ui, |plot_ui| plot_ui.line(line));"#,
+            |ui| {
+                example_plot(ui);
+            },
         );
-        ui.add(super::toggle_switch::toggle(boolean)).on_hover_text(
-            "It's easy to create your own widgets!\n\
-            This toggle switch is just 15 lines of code.",
+
+        rows.show_with_label(
+            ui,
+            "Custom widget",
+            |ui: &mut egui::Ui| {
+                ui.hyperlink_to(
+                    "Custom widget:",
+                    super::toggle_switch::url_to_file_source_code(),
+                )
+            },
+            "ui.add(toggle(&mut boolean));",
+            |ui| {
+                ui.add(super::toggle_switch::toggle(boolean)).on_hover_text(
+                    "It's easy to create your own widgets!\n\
+                    This toggle switch is just 15 lines of code.",
+                );
+            },
         );
+
+        rows.num_shown
+    }
+}
+
+/// The rows of the gallery that match the search.
+struct GalleryRows {
+    search: String,
+    num_shown: usize,
+}
+
+impl GalleryRows {
+    fn new(search: &str) -> Self {
+        Self {
+            search: search.trim().to_lowercase(),
+            num_shown: 0,
+        }
+    }
+
+    /// Show one row for an egui widget, see [`Self::show_with_label`].
+    fn show(
+        &mut self,
+        ui: &mut egui::Ui,
+        title: &str,
+        search_term: &str,
+        code: &str,
+        add_widget: impl FnOnce(&mut egui::Ui),
+    ) {
+        self.show_with_crate(ui, "egui", title, search_term, code, add_widget);
+    }
+
+    /// Show one row for a widget from another crate, see [`Self::show_with_label`].
+    fn show_with_crate(
+        &mut self,
+        ui: &mut egui::Ui,
+        crate_name: &str,
+        title: &str,
+        search_term: &str,
+        code: &str,
+        add_widget: impl FnOnce(&mut egui::Ui),
+    ) {
+        let label = doc_link_label_with_crate(crate_name, title, search_term);
+        self.show_with_label(ui, title, label, code, add_widget);
+    }
+
+    /// Show one row: the label, the widget itself, and a button to copy the code for it.
+    ///
+    /// The row is skipped if neither the title nor the code matches the search.
+    fn show_with_label(
+        &mut self,
+        ui: &mut egui::Ui,
+        title: &str,
+        label: impl egui::Widget,
+        code: &str,
+        add_widget: impl FnOnce(&mut egui::Ui),
+    ) {
+        let matches = |text: &str| text.to_lowercase().contains(&self.search);
+        if !matches(title) && !matches(code) {
+            return;
+        }
+        self.num_shown += 1;
+
+        ui.add(label);
+        add_widget(ui);
+        let copy_button = ui.small_button("📋").on_hover_ui(|ui| {
+            ui.label("Copy the code:");
+            ui.code(code);
+        });
+        if copy_button.clicked() {
+            ui.ctx().copy_text(code.to_owned());
+        }
         ui.end_row();
     }
 }
@@ -303,3 +607,33 @@ fn doc_link_label_with_crate<'a>(
         })
     }
 }
+
+#[test]
+fn test_widget_gallery_search_and_copy() {
+    use egui::test_harness::Harness;
+
+    let mut gallery = WidgetGallery {
+        search: "slid".to_owned(),
+        ..Default::default()
+    };
+    let copied_text = std::cell::RefCell::new(String::new());
+    let mut harness = Harness::new(|ctx| {
+        egui::CentralPanel::default().show(ctx, |ui| gallery.ui(ui));
+        ctx.output(|o| {
+            if !o.copied_text.is_empty() {
+                *copied_text.borrow_mut() = o.copied_text.clone();
+            }
+        });
+    });
+    harness.run();
+
+    // Only the slider matches the search:
+    assert!(harness.query_by_label("Slider:").is_some());
+    assert!(harness.query_by_label("Button:").is_none());
+
+    harness.get_by_label("📋").click();
+    assert_eq!(
+        *copied_text.borrow(),
+        r#"ui.add(egui::Slider::new(&mut scalar, 0.0..=360.0).suffix("°"));"#
+    );
+}
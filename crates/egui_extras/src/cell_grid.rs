@@ -0,0 +1,501 @@
+//! A spreadsheet-like grid of cells, with a formula bar above it.
+
+use std::{hash::Hash, ops::Range};
+
+use egui::{
+    text::{CCursor, CCursorRange},
+    text_edit::TextEditState,
+    vec2, Align2, Id, Rect, Response, ScrollArea, Sense, TextBuffer as _, TextEdit, TextStyle, Ui,
+};
+
+/// A cell of a [`CellGrid`], shown as e.g. `B3`.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, Hash, serde::Deserialize, serde::Serialize,
+)]
+pub struct CellRef {
+    /// Starting at zero, for column `A`.
+    pub column: usize,
+
+    /// Starting at zero, for row `1`.
+    pub row: usize,
+}
+
+impl CellRef {
+    pub fn new(column: usize, row: usize) -> Self {
+        Self { column, row }
+    }
+}
+
+impl std::fmt::Display for CellRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", column_name(self.column), self.row + 1)
+    }
+}
+
+/// `A`, `B`, …, `Z`, `AA`, `AB`, …
+fn column_name(mut column: usize) -> String {
+    let mut name = vec![];
+    loop {
+        name.push(b'A' + (column % 26) as u8);
+        if column < 26 {
+            break;
+        }
+        column = column / 26 - 1;
+    }
+    name.reverse();
+    String::from_utf8(name).unwrap_or_default()
+}
+
+/// A rectangular range of cells of a [`CellGrid`], shown as e.g. `B3:C5`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CellRange {
+    /// The top left cell.
+    pub min: CellRef,
+
+    /// The bottom right cell, included in the range.
+    pub max: CellRef,
+}
+
+impl CellRange {
+    /// The range spanned by two opposite corners, in any order.
+    pub fn new(a: CellRef, b: CellRef) -> Self {
+        Self {
+            min: CellRef::new(a.column.min(b.column), a.row.min(b.row)),
+            max: CellRef::new(a.column.max(b.column), a.row.max(b.row)),
+        }
+    }
+
+    pub fn contains(&self, cell: CellRef) -> bool {
+        (self.min.column..=self.max.column).contains(&cell.column)
+            && (self.min.row..=self.max.row).contains(&cell.row)
+    }
+}
+
+impl std::fmt::Display for CellRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.min == self.max {
+            self.min.fmt(f)
+        } else {
+            write!(f, "{}:{}", self.min, self.max)
+        }
+    }
+}
+
+/// What the user did with a [`CellGrid`], returned by [`CellGrid::show`].
+pub struct CellGridResponse {
+    /// The response of the cells.
+    pub response: Response,
+
+    /// The cell the formula bar is for.
+    pub selected: CellRef,
+
+    /// Did the user select another cell this frame?
+    ///
+    /// If so, you probably want to keep the formula of the previously selected cell,
+    /// and show the formula of [`Self::selected`] instead.
+    pub selection_changed: bool,
+
+    /// Did the user edit the formula this frame, by typing or by picking cells?
+    pub formula_changed: bool,
+}
+
+// ----------------------------------------------------------------------------
+
+/// A spreadsheet-like grid of cells, with a formula bar above it.
+///
+/// The formula bar stays where it is while the cells are scrolled.
+/// Clicking a cell selects it, and the formula bar shows the formula you pass in for it.
+///
+/// While the formula in the formula bar is being edited and starts with `=`,
+/// clicking a cell instead inserts a reference to it (e.g. `B3`) at the text cursor,
+/// and dragging over cells inserts a reference to the range (e.g. `B3:C5`),
+/// all without taking the keyboard focus away from the formula bar.
+///
+/// Which cell is selected is remembered in [`egui::Memory`], under the id of the grid.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// # let mut formulas = std::collections::HashMap::<egui_extras::CellRef, String>::new();
+/// # let mut formula = String::new();
+/// let response = egui_extras::CellGrid::new("sheet", 10, 100).show(ui, &mut formula, |cell| {
+///     formulas.get(&cell).cloned().unwrap_or_default()
+/// });
+/// # let selected = response.selected;
+/// # let _ = selected;
+/// # });
+/// ```
+#[must_use = "You should call .show()"]
+pub struct CellGrid {
+    id_source: Id,
+    num_columns: usize,
+    num_rows: usize,
+    column_width: Option<f32>,
+    row_height: Option<f32>,
+}
+
+impl CellGrid {
+    /// The id of the grid is derived from `id_source`, and must be unique within the [`Ui`].
+    pub fn new(id_source: impl Hash, num_columns: usize, num_rows: usize) -> Self {
+        Self {
+            id_source: Id::new(id_source),
+            num_columns,
+            num_rows,
+            column_width: None,
+            row_height: None,
+        }
+    }
+
+    /// The width of each column.
+    ///
+    /// Default: [`egui::style::Spacing::interact_size`]`.x` times two.
+    #[inline]
+    pub fn column_width(mut self, column_width: f32) -> Self {
+        self.column_width = Some(column_width);
+        self
+    }
+
+    /// The height of each row.
+    ///
+    /// Default: [`egui::style::Spacing::interact_size`]`.y`.
+    #[inline]
+    pub fn row_height(mut self, row_height: f32) -> Self {
+        self.row_height = Some(row_height);
+        self
+    }
+
+    /// Show the formula bar and the cells.
+    ///
+    /// `formula` is the formula of the selected cell, edited in the formula bar,
+    /// and `cell_text` gives the text to show in each visible cell.
+    pub fn show(
+        self,
+        ui: &mut Ui,
+        formula: &mut String,
+        cell_text: impl Fn(CellRef) -> String,
+    ) -> CellGridResponse {
+        let Self {
+            id_source,
+            num_columns,
+            num_rows,
+            column_width,
+            row_height,
+        } = self;
+
+        let id = ui.make_persistent_id(id_source);
+        let mut state = ui
+            .data_mut(|d| d.get_persisted::<CellGridState>(id))
+            .unwrap_or_default();
+
+        let column_width = column_width.unwrap_or(2.0 * ui.spacing().interact_size.x);
+        let row_height = row_height.unwrap_or(ui.spacing().interact_size.y);
+
+        // The formula bar, outside of the scroll area:
+        let formula_id = id.with("formula");
+        let picking = formula.starts_with('=') && ui.memory(|mem| mem.has_focus(formula_id));
+        let mut formula_changed = ui
+            .horizontal(|ui| {
+                ui.add_sized(
+                    vec2(column_width, row_height),
+                    egui::Label::new(state.selected.to_string()),
+                );
+                ui.add(
+                    TextEdit::singleline(formula)
+                        .id(formula_id)
+                        .desired_width(f32::INFINITY),
+                )
+                .changed()
+            })
+            .inner;
+        if !picking {
+            state.picking = None;
+        }
+
+        let header_width = column_width / 2.0;
+        let output = ScrollArea::both()
+            .id_source(id.with("scroll"))
+            .auto_shrink(false)
+            .show(ui, |ui| {
+                let size = vec2(
+                    header_width + num_columns as f32 * column_width,
+                    (num_rows + 1) as f32 * row_height,
+                );
+                let (rect, _) = ui.allocate_exact_size(size, Sense::hover());
+                let cells_rect =
+                    Rect::from_min_max(rect.min + vec2(header_width, row_height), rect.max);
+                let response = ui.interact(cells_rect, id.with("cells"), Sense::click_and_drag());
+                if picking {
+                    // Clicking the cells must leave the focus in the formula bar:
+                    response.keep_focus_when_interacting();
+                }
+
+                let cell_at = |pos: egui::Pos2| {
+                    let column = ((pos.x - cells_rect.min.x) / column_width).floor();
+                    let row = ((pos.y - cells_rect.min.y) / row_height).floor();
+                    CellRef::new(
+                        (column.max(0.0) as usize).min(num_columns.saturating_sub(1)),
+                        (row.max(0.0) as usize).min(num_rows.saturating_sub(1)),
+                    )
+                };
+                let cell_rect = |cell: CellRef| {
+                    Rect::from_min_size(
+                        cells_rect.min
+                            + vec2(
+                                cell.column as f32 * column_width,
+                                cell.row as f32 * row_height,
+                            ),
+                        vec2(column_width, row_height),
+                    )
+                };
+
+                let mut selection_changed = false;
+                if 0 < num_columns && 0 < num_rows {
+                    let press_origin = ui.input(|i| i.pointer.press_origin());
+                    let started = response.clicked() || response.drag_started();
+                    if let (true, Some(origin), Some(pos)) =
+                        (started, press_origin, response.interact_pointer_pos())
+                    {
+                        let anchor = cell_at(origin);
+                        if picking {
+                            let range = CellRange::new(anchor, cell_at(pos));
+                            let inserted =
+                                insert_reference(ui, formula_id, formula, None, &range.to_string());
+                            state.picking = Some(Picking { anchor, inserted });
+                            formula_changed = true;
+                        } else {
+                            selection_changed = state.selected != anchor;
+                            state.selected = anchor;
+                        }
+                    } else if let (true, Some(picked), Some(pos)) = (
+                        response.dragged(),
+                        &mut state.picking,
+                        response.interact_pointer_pos(),
+                    ) {
+                        // Replace the reference we inserted when the drag started:
+                        let range = CellRange::new(picked.anchor, cell_at(pos));
+                        let range_text = range.to_string();
+                        let replaced = formula.char_range(picked.inserted.clone());
+                        if replaced != range_text {
+                            picked.inserted = insert_reference(
+                                ui,
+                                formula_id,
+                                formula,
+                                Some(picked.inserted.clone()),
+                                &range_text,
+                            );
+                            formula_changed = true;
+                        }
+                    }
+                    if response.clicked() || response.drag_stopped() {
+                        state.picking = None;
+                    }
+                }
+
+                // Paint only the visible cells:
+                let visuals = ui.visuals();
+                let painter = ui.painter();
+                let clip_rect = ui.clip_rect();
+                let font_id = TextStyle::Body.resolve(ui.style());
+                let text_color = visuals.text_color();
+                let grid_stroke = visuals.widgets.noninteractive.bg_stroke;
+
+                let visible = clip_rect.intersect(cells_rect);
+                let (first, last) = if visible.is_positive() {
+                    (cell_at(visible.min), cell_at(visible.max))
+                } else {
+                    (CellRef::default(), CellRef::default())
+                };
+                let picked_range = state.picking.as_ref().and_then(|picked| {
+                    let pos = response.interact_pointer_pos()?;
+                    Some(CellRange::new(picked.anchor, cell_at(pos)))
+                });
+
+                for column in first.column..=last.column.min(num_columns.saturating_sub(1)) {
+                    for row in first.row..=last.row.min(num_rows.saturating_sub(1)) {
+                        let cell = CellRef::new(column, row);
+                        let rect = cell_rect(cell);
+                        if picked_range.map_or(false, |range| range.contains(cell)) {
+                            painter.rect_filled(rect, 0.0, visuals.selection.bg_fill);
+                        }
+                        painter.rect_stroke(rect, 0.0, grid_stroke);
+                        let text = cell_text(cell);
+                        if !text.is_empty() {
+                            painter.with_clip_rect(rect.intersect(clip_rect)).text(
+                                rect.left_center() + vec2(ui.spacing().button_padding.x, 0.0),
+                                Align2::LEFT_CENTER,
+                                text,
+                                font_id.clone(),
+                                text_color,
+                            );
+                        }
+                    }
+                }
+                if 0 < num_columns && 0 < num_rows {
+                    painter.rect_stroke(cell_rect(state.selected), 0.0, visuals.selection.stroke);
+                }
+
+                // The column and row headers:
+                for column in first.column..=last.column.min(num_columns.saturating_sub(1)) {
+                    let rect = cell_rect(CellRef::new(column, 0)).translate(vec2(0.0, -row_height));
+                    painter.rect(rect, 0.0, visuals.faint_bg_color, grid_stroke);
+                    painter.text(
+                        rect.center(),
+                        Align2::CENTER_CENTER,
+                        column_name(column),
+                        font_id.clone(),
+                        text_color,
+                    );
+                }
+                for row in first.row..=last.row.min(num_rows.saturating_sub(1)) {
+                    let rect = Rect::from_min_size(
+                        cell_rect(CellRef::new(0, row)).min - vec2(header_width, 0.0),
+                        vec2(header_width, row_height),
+                    );
+                    painter.rect(rect, 0.0, visuals.faint_bg_color, grid_stroke);
+                    painter.text(
+                        rect.center(),
+                        Align2::CENTER_CENTER,
+                        (row + 1).to_string(),
+                        font_id.clone(),
+                        text_color,
+                    );
+                }
+
+                (response, selection_changed)
+            });
+        let (response, selection_changed) = output.inner;
+
+        let selected = state.selected;
+        ui.data_mut(|d| d.insert_persisted(id, state));
+
+        CellGridResponse {
+            response,
+            selected,
+            selection_changed,
+            formula_changed,
+        }
+    }
+}
+
+/// Put `reference` in the formula, either instead of the `replace` character range,
+/// or instead of the selection of the formula bar.
+///
+/// Returns the character range of the inserted reference.
+fn insert_reference(
+    ui: &Ui,
+    formula_id: Id,
+    formula: &mut String,
+    replace: Option<Range<usize>>,
+    reference: &str,
+) -> Range<usize> {
+    let mut text_state = TextEditState::load(ui.ctx(), formula_id).unwrap_or_default();
+    let range = replace.unwrap_or_else(|| match text_state.cursor.char_range() {
+        Some(cursor_range) => {
+            let [min, max] = cursor_range.sorted();
+            min.index..max.index
+        }
+        None => {
+            let end = formula.chars().count();
+            end..end
+        }
+    });
+
+    formula.delete_char_range(range.clone());
+    let end = range.start + formula.insert_text(reference, range.start);
+    text_state
+        .cursor
+        .set_char_range(Some(CCursorRange::one(CCursor::new(end))));
+    text_state.store(ui.ctx(), formula_id);
+    range.start..end
+}
+
+/// The state of a [`CellGrid`] that is remembered between frames.
+#[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize)]
+struct CellGridState {
+    selected: CellRef,
+
+    /// The cells that are being picked to insert a reference to them into the formula.
+    #[serde(skip)]
+    picking: Option<Picking>,
+}
+
+#[derive(Clone, Debug)]
+struct Picking {
+    /// Where the drag started.
+    anchor: CellRef,
+
+    /// The characters of the formula with the reference we inserted.
+    inserted: Range<usize>,
+}
+
+#[test]
+fn test_column_name() {
+    assert_eq!(column_name(0), "A");
+    assert_eq!(column_name(25), "Z");
+    assert_eq!(column_name(26), "AA");
+    assert_eq!(column_name(27), "AB");
+    assert_eq!(column_name(26 + 26 * 26), "AAA");
+    assert_eq!(CellRef::new(1, 2).to_string(), "B3");
+    let range = CellRange::new(CellRef::new(2, 4), CellRef::new(1, 2));
+    assert_eq!(range.to_string(), "B3:C5");
+}
+
+#[test]
+fn test_cell_grid_reference_picking() {
+    use egui::{pos2, test_harness::Harness, WidgetType};
+    use std::cell::{Cell, RefCell};
+
+    let formula = RefCell::new(String::new());
+    let selected = Cell::new(CellRef::default());
+    let cells_rect = Cell::new(Rect::NOTHING);
+    let (column_width, row_height) = (60.0, 20.0);
+
+    let mut harness = Harness::new_ui(|ui| {
+        let response = CellGrid::new("sheet", 5, 10)
+            .column_width(column_width)
+            .row_height(row_height)
+            .show(ui, &mut formula.borrow_mut(), |cell| cell.to_string());
+        selected.set(response.selected);
+        cells_rect.set(response.response.rect);
+    });
+    let cell_center = |column: f32, row: f32| {
+        cells_rect.get().min + vec2((column + 0.5) * column_width, (row + 0.5) * row_height)
+    };
+    let formula_id = harness
+        .get_by(|info| info.typ == WidgetType::TextEdit)
+        .node()
+        .rect
+        .id;
+    let formula_has_focus =
+        |harness: &Harness<'_>| harness.ctx().memory(|mem| mem.has_focus(formula_id));
+
+    // Without a formula being edited, clicking a cell selects it:
+    harness.click(cell_center(2.0, 1.0));
+    assert_eq!(selected.get(), CellRef::new(2, 1));
+
+    harness
+        .get_by(|info| info.typ == WidgetType::TextEdit)
+        .type_text("=SUM(");
+    assert!(formula_has_focus(&harness));
+
+    // Clicking a cell while editing inserts a reference to it, and leaves the focus where it is:
+    harness.click(cell_center(1.0, 1.0));
+    assert_eq!(*formula.borrow(), "=SUM(B2");
+    assert!(formula_has_focus(&harness));
+    assert_eq!(selected.get(), CellRef::new(2, 1));
+
+    // Dragging inserts a range:
+    harness.type_text(",");
+    harness.drag(cell_center(0.0, 0.0), cell_center(1.0, 2.0));
+    assert_eq!(*formula.borrow(), "=SUM(B2,A1:B3");
+    assert!(formula_has_focus(&harness));
+
+    harness.type_text(")");
+    assert_eq!(*formula.borrow(), "=SUM(B2,A1:B3)");
+
+    // Once the formula bar loses the focus, clicking a cell selects it again:
+    harness.click(pos2(1.0, 1.0));
+    assert!(!formula_has_focus(&harness));
+    harness.click(cell_center(0.0, 3.0));
+    assert_eq!(selected.get(), CellRef::new(0, 3));
+    assert_eq!(*formula.borrow(), "=SUM(B2,A1:B3)");
+}
@@ -1,5 +1,6 @@
-use super::popup::DatePickerPopup;
-use chrono::NaiveDate;
+use super::{popup::DatePickerPopup, PickableDate, PickableTime, Ymd, JAPANESE_ERAS};
+use std::sync::Arc;
+
 use egui::{Area, Button, Frame, InnerResponse, Key, Order, RichText, Ui, Widget};
 
 #[derive(Default, Clone, serde::Deserialize, serde::Serialize)]
@@ -7,9 +8,256 @@ pub(crate) struct DatePickerButtonState {
     pub picker_visible: bool,
 }
 
+/// How the years are shown by a [`DatePickerButton`], see [`DatePickerLocale::calendar`].
+///
+/// The dates themselves are always in the Gregorian calendar.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DisplayCalendar {
+    /// Years like "2024".
+    #[default]
+    Gregorian,
+
+    /// Years in the Japanese eras since 1868, like "Reiwa 6" for 2024.
+    ///
+    /// Earlier years are shown as in [`Self::Gregorian`].
+    JapaneseEra,
+}
+
+/// How the weeks are numbered in the calendar of a [`DatePickerButton`], see [`DatePickerLocale::week_numbering`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum WeekNumbering {
+    /// ISO 8601 weeks: weeks start on Monday, and week 1 is the one with the first Thursday of the year in it.
+    #[default]
+    Iso,
+
+    /// Weeks start on [`DatePickerLocale::first_weekday`], and week 1 is the one with January 1st in it,
+    /// as is common in e.g. the US.
+    Simple,
+}
+
+/// The time zone in which a [`DatePickerButton`] circles today's date, see [`DatePickerLocale::time_zone`].
+///
+/// egui does not know the time zone of the user, nor the rules of any time zone,
+/// so for one with daylight saving time, look up its offset with [`Self::from_fn`].
+#[derive(Clone)]
+pub struct TimeZone(TimeZoneInner);
+
+#[derive(Clone)]
+enum TimeZoneInner {
+    Fixed(i32),
+    Fn(Arc<dyn Fn(i64) -> i32 + Send + Sync>),
+}
+
+impl TimeZone {
+    /// Coordinated Universal Time.
+    pub fn utc() -> Self {
+        Self::fixed(0)
+    }
+
+    /// A time zone that is always this many minutes east of UTC, e.g. `9 * 60` for Japan Standard Time.
+    pub fn fixed(utc_offset_minutes: i32) -> Self {
+        Self(TimeZoneInner::Fixed(utc_offset_minutes))
+    }
+
+    /// A time zone whose offset can change, e.g. with daylight saving time.
+    ///
+    /// The function is called with a time in seconds since the Unix epoch,
+    /// and returns how many minutes east of UTC the time zone is at that time.
+    ///
+    /// ```
+    /// # #[cfg(feature = "chrono")] {
+    /// use chrono::TimeZone as _;
+    /// let local = egui_extras::TimeZone::from_fn(|unix_seconds| {
+    ///     chrono::Local
+    ///         .timestamp_opt(unix_seconds, 0)
+    ///         .single()
+    ///         .map_or(0, |time| time.offset().local_minus_utc() / 60)
+    /// });
+    /// # }
+    /// ```
+    pub fn from_fn(utc_offset_minutes: impl Fn(i64) -> i32 + Send + Sync + 'static) -> Self {
+        Self(TimeZoneInner::Fn(Arc::new(utc_offset_minutes)))
+    }
+
+    /// How many minutes east of UTC the time zone is at this time, in seconds since the Unix epoch.
+    pub fn utc_offset_minutes(&self, unix_seconds: i64) -> i32 {
+        match &self.0 {
+            TimeZoneInner::Fixed(minutes) => *minutes,
+            TimeZoneInner::Fn(utc_offset_minutes) => utc_offset_minutes(unix_seconds),
+        }
+    }
+}
+
+impl Default for TimeZone {
+    fn default() -> Self {
+        Self::utc()
+    }
+}
+
+impl std::fmt::Debug for TimeZone {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.0 {
+            TimeZoneInner::Fixed(minutes) => write!(f, "TimeZone::fixed({minutes})"),
+            TimeZoneInner::Fn(_) => write!(f, "TimeZone::from_fn(…)"),
+        }
+    }
+}
+
+impl PartialEq for TimeZone {
+    fn eq(&self, other: &Self) -> bool {
+        match (&self.0, &other.0) {
+            (TimeZoneInner::Fixed(a), TimeZoneInner::Fixed(b)) => a == b,
+            (TimeZoneInner::Fn(a), TimeZoneInner::Fn(b)) => Arc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+}
+
+impl Eq for TimeZone {}
+
+/// The texts shown by a [`DatePickerButton`], see [`DatePickerButton::locale`].
+///
+/// The default is English, with weeks starting on Monday, ISO week numbers,
+/// Gregorian years, and today's date in UTC.
+///
+/// egui does not look up the locale of the user, so this is all up to you:
+/// fill it in from whatever locale source your app uses, or start from a preset like [`Self::japanese`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DatePickerLocale {
+    /// The weekday names in the calendar header, starting with Monday.
+    pub weekday_names: [String; 7],
+
+    /// The month names, starting with January.
+    pub month_names: [String; 12],
+
+    /// The weekday shown in the first calendar column, with 0 for Monday and 6 for Sunday.
+    pub first_weekday: usize,
+
+    /// Header of the calendar week column.
+    pub week: String,
+
+    /// Label in front of the time of day spinners.
+    pub time: String,
+
+    /// The button closing the popup without picking.
+    pub cancel: String,
+
+    /// The button picking the date and closing the popup.
+    pub save: String,
+
+    /// How the years are shown.
+    pub calendar: DisplayCalendar,
+
+    /// The names of the Japanese eras Meiji, Taishō, Shōwa, Heisei and Reiwa,
+    /// including any space before the year number, for [`DisplayCalendar::JapaneseEra`].
+    pub era_names: [String; 5],
+
+    /// Written after year numbers, e.g. "年" in Japanese.
+    pub year_suffix: String,
+
+    /// How the weeks are numbered in the week column, see [`DatePickerButton::calendar_week`].
+    pub week_numbering: WeekNumbering,
+
+    /// The time zone in which today's date is circled in the calendar.
+    pub time_zone: TimeZone,
+}
+
+impl Default for DatePickerLocale {
+    fn default() -> Self {
+        Self {
+            weekday_names: ["Mo", "Tu", "We", "Th", "Fr", "Sa", "Su"].map(String::from),
+            month_names: [
+                "January",
+                "February",
+                "March",
+                "April",
+                "May",
+                "June",
+                "July",
+                "August",
+                "September",
+                "October",
+                "November",
+                "December",
+            ]
+            .map(String::from),
+            first_weekday: 0,
+            week: "Week".to_owned(),
+            time: "Time".to_owned(),
+            cancel: "Cancel".to_owned(),
+            save: "Save".to_owned(),
+            calendar: DisplayCalendar::Gregorian,
+            era_names: ["Meiji ", "Taishō ", "Shōwa ", "Heisei ", "Reiwa "].map(String::from),
+            year_suffix: String::new(),
+            week_numbering: WeekNumbering::Iso,
+            time_zone: TimeZone::utc(),
+        }
+    }
+}
+
+impl DatePickerLocale {
+    /// Japanese texts, weeks starting on Sunday, years in the Japanese eras, and Japan Standard Time.
+    ///
+    /// The default fonts of egui have no Japanese glyphs,
+    /// so you need to add a font that has them, see [`egui::FontDefinitions`].
+    pub fn japanese() -> Self {
+        Self {
+            weekday_names: ["月", "火", "水", "木", "金", "土", "日"].map(String::from),
+            month_names: std::array::from_fn(|i| format!("{}月", i + 1)),
+            first_weekday: 6,
+            week: "週".to_owned(),
+            time: "時刻".to_owned(),
+            cancel: "キャンセル".to_owned(),
+            save: "保存".to_owned(),
+            calendar: DisplayCalendar::JapaneseEra,
+            era_names: ["明治", "大正", "昭和", "平成", "令和"].map(String::from),
+            year_suffix: "年".to_owned(),
+            week_numbering: WeekNumbering::Simple,
+            time_zone: TimeZone::fixed(9 * 60),
+        }
+    }
+
+    /// How the year of this date is shown, e.g. "2024" or "Reiwa 6".
+    pub fn year_label(&self, year: i32, month: u32, day: u32) -> String {
+        let date = Ymd { year, month, day };
+        match self.calendar {
+            DisplayCalendar::Gregorian => format!("{year}{}", self.year_suffix),
+            DisplayCalendar::JapaneseEra => {
+                if let Some(era) = JAPANESE_ERAS.iter().rposition(|start| *start <= date) {
+                    let era_year = year - JAPANESE_ERAS[era].year + 1;
+                    format!("{}{era_year}{}", self.era_names[era], self.year_suffix)
+                } else {
+                    format!("{year}{}", self.year_suffix)
+                }
+            }
+        }
+    }
+}
+
 /// Shows a date, and will open a date picker popup when clicked.
+///
+/// Works with [`chrono::NaiveDate`] (feature `chrono`) and [`time::Date`] (feature `time`),
+/// or any other [`PickableDate`].
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// # #[cfg(feature = "chrono")] {
+/// # let mut date = chrono::NaiveDate::from_ymd_opt(2024, 5, 17).unwrap();
+/// # let mut time = chrono::NaiveTime::from_hms_opt(12, 30, 0).unwrap();
+/// ui.add(
+///     egui_extras::DatePickerButton::new(&mut date)
+///         .time(&mut time)
+///         .min_date(chrono::NaiveDate::from_ymd_opt(2024, 1, 1).unwrap()),
+/// );
+/// # }
+/// # });
+/// ```
 pub struct DatePickerButton<'a> {
-    selection: &'a mut NaiveDate,
+    selection: &'a mut dyn PickableDate,
+    time: Option<&'a mut dyn PickableTime>,
+    min_date: Option<Ymd>,
+    max_date: Option<Ymd>,
+    locale: Option<DatePickerLocale>,
     id_source: Option<&'a str>,
     combo_boxes: bool,
     arrows: bool,
@@ -19,9 +267,13 @@ pub struct DatePickerButton<'a> {
 }
 
 impl<'a> DatePickerButton<'a> {
-    pub fn new(selection: &'a mut NaiveDate) -> Self {
+    pub fn new(selection: &'a mut dyn PickableDate) -> Self {
         Self {
             selection,
+            time: None,
+            min_date: None,
+            max_date: None,
+            locale: None,
             id_source: None,
             combo_boxes: true,
             arrows: true,
@@ -73,6 +325,37 @@ impl<'a> DatePickerButton<'a> {
         self.show_icon = show_icon;
         self
     }
+
+    /// Also pick a time of day, with hour and minute spinners in the popup.
+    ///
+    /// The seconds are left as they are.
+    #[inline]
+    pub fn time(mut self, time: &'a mut dyn PickableTime) -> Self {
+        self.time = Some(time);
+        self
+    }
+
+    /// Days before this one can't be picked.
+    #[inline]
+    pub fn min_date(mut self, min_date: impl PickableDate) -> Self {
+        self.min_date = Some(Ymd::of(&min_date));
+        self
+    }
+
+    /// Days after this one can't be picked.
+    #[inline]
+    pub fn max_date(mut self, max_date: impl PickableDate) -> Self {
+        self.max_date = Some(Ymd::of(&max_date));
+        self
+    }
+
+    /// The names of weekdays and months, the first day of the week, how years and weeks are numbered,
+    /// and the time zone of today's date. (Default: English, see [`DatePickerLocale`])
+    #[inline]
+    pub fn locale(mut self, locale: DatePickerLocale) -> Self {
+        self.locale = Some(locale);
+        self
+    }
 }
 
 impl<'a> Widget for DatePickerButton<'a> {
@@ -82,11 +365,21 @@ impl<'a> Widget for DatePickerButton<'a> {
             .data_mut(|data| data.get_persisted::<DatePickerButtonState>(id))
             .unwrap_or_default();
 
-        let mut text = if self.show_icon {
-            RichText::new(format!("{} 📆", self.selection.format("%Y-%m-%d")))
-        } else {
-            RichText::new(format!("{}", self.selection.format("%Y-%m-%d")))
-        };
+        let (year, month, day) = self.selection.ymd();
+        let mut text = format!("{year:04}-{month:02}-{day:02}");
+        if let Some(locale) = &self.locale {
+            if locale.calendar != DisplayCalendar::Gregorian {
+                text += &format!(" ({})", locale.year_label(year, month, day));
+            }
+        }
+        if let Some(time) = &self.time {
+            let (hour, minute, _) = time.hms();
+            text += &format!(" {hour:02}:{minute:02}");
+        }
+        if self.show_icon {
+            text += " 📆";
+        }
+        let mut text = RichText::new(text);
         let visuals = ui.visuals().widgets.open;
         if button_state.picker_visible {
             text = text.color(visuals.text_color());
@@ -133,6 +426,10 @@ impl<'a> Widget for DatePickerButton<'a> {
 
                             DatePickerPopup {
                                 selection: self.selection,
+                                time: self.time,
+                                min_date: self.min_date,
+                                max_date: self.max_date,
+                                locale: self.locale.unwrap_or_default(),
                                 button_id: id,
                                 combo_boxes: self.combo_boxes,
                                 arrows: self.arrows,
@@ -1,34 +1,370 @@
 mod button;
 mod popup;
 
-pub use button::DatePickerButton;
-use chrono::{Datelike, Duration, NaiveDate, Weekday};
+pub use button::{DatePickerButton, DatePickerLocale, DisplayCalendar, TimeZone, WeekNumbering};
 
-#[derive(Debug)]
-struct Week {
-    number: u8,
-    days: Vec<NaiveDate>,
+/// A date that a [`DatePickerButton`] can edit.
+///
+/// Implemented for [`chrono::NaiveDate`] with the `chrono` feature,
+/// and for [`time::Date`] with the `time` feature.
+pub trait PickableDate {
+    /// The year, the month (1-12) and the day of the month (1-31).
+    fn ymd(&self) -> (i32, u32, u32);
+
+    /// Change to the given date, which is always a valid one.
+    fn set_ymd(&mut self, year: i32, month: u32, day: u32);
+}
+
+/// A time of day that a [`DatePickerButton`] can edit, see [`DatePickerButton::time`].
+///
+/// Implemented for [`chrono::NaiveTime`] with the `chrono` feature,
+/// and for [`time::Time`] with the `time` feature.
+pub trait PickableTime {
+    /// The hour (0-23), minute (0-59) and second (0-59).
+    fn hms(&self) -> (u32, u32, u32);
+
+    /// Change to the given time, which is always a valid one.
+    fn set_hms(&mut self, hour: u32, minute: u32, second: u32);
 }
 
-fn month_data(year: i32, month: u32) -> Vec<Week> {
-    let first = NaiveDate::from_ymd_opt(year, month, 1).expect("Could not create NaiveDate");
-    let mut start = first;
-    while start.weekday() != Weekday::Mon {
-        start = start.checked_sub_signed(Duration::days(1)).unwrap();
+#[cfg(feature = "chrono")]
+impl PickableDate for chrono::NaiveDate {
+    fn ymd(&self) -> (i32, u32, u32) {
+        use chrono::Datelike as _;
+        (self.year(), self.month(), self.day())
     }
-    let mut weeks = vec![];
-    let mut week = vec![];
-    while start < first || start.month() == first.month() || start.weekday() != Weekday::Mon {
-        week.push(start);
-
-        if start.weekday() == Weekday::Sun {
-            weeks.push(Week {
-                number: start.iso_week().week() as u8,
-                days: std::mem::take(&mut week),
-            });
+
+    fn set_ymd(&mut self, year: i32, month: u32, day: u32) {
+        if let Some(date) = Self::from_ymd_opt(year, month, day) {
+            *self = date;
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl PickableTime for chrono::NaiveTime {
+    fn hms(&self) -> (u32, u32, u32) {
+        use chrono::Timelike as _;
+        (self.hour(), self.minute(), self.second())
+    }
+
+    fn set_hms(&mut self, hour: u32, minute: u32, second: u32) {
+        if let Some(time) = Self::from_hms_opt(hour, minute, second) {
+            *self = time;
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+impl PickableDate for time::Date {
+    fn ymd(&self) -> (i32, u32, u32) {
+        (self.year(), self.month() as u32, self.day() as u32)
+    }
+
+    fn set_ymd(&mut self, year: i32, month: u32, day: u32) {
+        let Ok(month) = time::Month::try_from(month as u8) else {
+            return;
+        };
+        if let Ok(date) = Self::from_calendar_date(year, month, day as u8) {
+            *self = date;
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+impl PickableTime for time::Time {
+    fn hms(&self) -> (u32, u32, u32) {
+        (
+            self.hour() as u32,
+            self.minute() as u32,
+            self.second() as u32,
+        )
+    }
+
+    fn set_hms(&mut self, hour: u32, minute: u32, second: u32) {
+        if let Ok(time) = Self::from_hms(hour as u8, minute as u8, second as u8) {
+            *self = time;
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// A date in the proleptic Gregorian calendar, which the date picker does its calculations with,
+/// so that it works the same with all [`PickableDate`]s.
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, serde::Deserialize, serde::Serialize,
+)]
+pub(crate) struct Ymd {
+    year: i32,
+    month: u32,
+    day: u32,
+}
+
+impl Ymd {
+    fn of(date: &dyn PickableDate) -> Self {
+        let (year, month, day) = date.ymd();
+        Self { year, month, day }
+    }
+
+    /// Today's date in the given time zone.
+    fn today(time_zone: &TimeZone) -> Self {
+        #[cfg(feature = "chrono")]
+        let today = {
+            let now = chrono::offset::Utc::now();
+            let offset_minutes = time_zone.utc_offset_minutes(now.timestamp()) as i64;
+            Self::of(&(now + chrono::Duration::minutes(offset_minutes)).date_naive())
+        };
+        #[cfg(all(feature = "time", not(feature = "chrono")))]
+        let today = {
+            let now = time::OffsetDateTime::now_utc();
+            let offset_minutes = time_zone.utc_offset_minutes(now.unix_timestamp()) as i64;
+            Self::of(&(now + time::Duration::minutes(offset_minutes)).date())
+        };
+        today
+    }
+
+    fn days_in_month(year: i32, month: u32) -> u32 {
+        match month {
+            2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
         }
-        start = start.checked_add_signed(Duration::days(1)).unwrap();
     }
 
+    /// Days since 1970-01-01.
+    fn days(self) -> i64 {
+        // See http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+        let year = self.year as i64 - (self.month <= 2) as i64;
+        let era = year.div_euclid(400);
+        let year_of_era = year.rem_euclid(400);
+        let month = self.month as i64;
+        let day_of_year =
+            (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + self.day as i64 - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    /// The date this many days after 1970-01-01.
+    fn from_days(days: i64) -> Self {
+        // See http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+        let days = days + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let mp = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+        let year = (year_of_era + era * 400) as i32 + (month <= 2) as i32;
+        Self { year, month, day }
+    }
+
+    fn add_days(self, days: i64) -> Self {
+        Self::from_days(self.days() + days)
+    }
+
+    /// Move by whole months, keeping the day if that month has it.
+    fn add_months(self, months: i32) -> Self {
+        let month_index = self.year * 12 + self.month as i32 - 1 + months;
+        let year = month_index.div_euclid(12);
+        let month = month_index.rem_euclid(12) as u32 + 1;
+        Self {
+            year,
+            month,
+            day: self.day.min(Self::days_in_month(year, month)),
+        }
+    }
+
+    /// 0 for Monday, 6 for Sunday.
+    fn weekday(self) -> usize {
+        // 1970-01-01 was a Thursday:
+        (self.days() + 3).rem_euclid(7) as usize
+    }
+
+    /// The ISO 8601 week number.
+    fn iso_week(self) -> u32 {
+        // A week belongs to the year its Thursday is in:
+        let thursday = self.days() - self.weekday() as i64 + 3;
+        let year = Self::from_days(thursday).year;
+        let first_day = Self {
+            year,
+            month: 1,
+            day: 1,
+        };
+        ((thursday - first_day.days()) / 7 + 1) as u32
+    }
+
+    /// The week number with [`WeekNumbering::Simple`]:
+    /// weeks start on `first_weekday` (0 for Monday), and week 1 is the one with January 1st in it.
+    fn simple_week(self, first_weekday: usize) -> u32 {
+        let week_start =
+            |date: Self| date.days() - ((date.weekday() + 7 - first_weekday % 7) % 7) as i64;
+        let start = week_start(self);
+        // A week belongs to the year its last day is in:
+        let year = Self::from_days(start + 6).year;
+        let first_week_start = week_start(Self {
+            year,
+            month: 1,
+            day: 1,
+        });
+        ((start - first_week_start) / 7 + 1) as u32
+    }
+
+    fn clamp_to(self, min: Option<Self>, max: Option<Self>) -> Self {
+        let date = min.map_or(self, |min| self.max(min));
+        max.map_or(date, |max| date.min(max))
+    }
+}
+
+/// The first days of the Japanese eras Meiji, Taishō, Shōwa, Heisei and Reiwa.
+pub(crate) const JAPANESE_ERAS: [Ymd; 5] = [
+    Ymd {
+        year: 1868,
+        month: 10,
+        day: 23,
+    },
+    Ymd {
+        year: 1912,
+        month: 7,
+        day: 30,
+    },
+    Ymd {
+        year: 1926,
+        month: 12,
+        day: 25,
+    },
+    Ymd {
+        year: 1989,
+        month: 1,
+        day: 8,
+    },
+    Ymd {
+        year: 2019,
+        month: 5,
+        day: 1,
+    },
+];
+
+#[derive(Debug)]
+struct Week {
+    number: u32,
+    days: Vec<Ymd>,
+}
+
+/// The weeks shown in the calendar for this month, starting on `first_weekday` (0 for Monday).
+fn month_data(
+    year: i32,
+    month: u32,
+    first_weekday: usize,
+    week_numbering: WeekNumbering,
+) -> Vec<Week> {
+    let first = Ymd {
+        year,
+        month,
+        day: 1,
+    };
+    let offset = (first.weekday() + 7 - first_weekday % 7) % 7;
+    let mut start = first.add_days(-(offset as i64));
+
+    let mut weeks = vec![];
+    while weeks.is_empty() || (start.year, start.month) <= (year, month) {
+        let days: Vec<Ymd> = (0..7).map(|i| start.add_days(i)).collect();
+        let number = match week_numbering {
+            WeekNumbering::Iso => {
+                let thursday = days.iter().find(|day| day.weekday() == 3).copied();
+                thursday.unwrap_or(start).iso_week()
+            }
+            WeekNumbering::Simple => start.simple_week(first_weekday),
+        };
+        weeks.push(Week { number, days });
+        start = start.add_days(7);
+    }
     weeks
 }
+
+#[cfg(feature = "chrono")]
+#[test]
+fn test_calendar_math() {
+    use chrono::Datelike as _;
+
+    let mut date = chrono::NaiveDate::from_ymd_opt(1890, 1, 1).unwrap();
+    let last = chrono::NaiveDate::from_ymd_opt(2110, 12, 31).unwrap();
+    while date <= last {
+        let ymd = Ymd::of(&date);
+        let days = date
+            .signed_duration_since(chrono::NaiveDate::from_ymd_opt(1970, 1, 1).unwrap())
+            .num_days();
+        assert_eq!(ymd.days(), days);
+        assert_eq!(Ymd::from_days(days), ymd);
+        assert_eq!(
+            ymd.weekday(),
+            date.weekday().num_days_from_monday() as usize
+        );
+        assert_eq!(ymd.iso_week(), date.iso_week().week());
+        date = date.succ_opt().unwrap();
+    }
+
+    let january = Ymd {
+        year: 2024,
+        month: 1,
+        day: 31,
+    };
+    assert_eq!(
+        january.add_months(1),
+        Ymd {
+            year: 2024,
+            month: 2,
+            day: 29
+        }
+    );
+    assert_eq!(january.add_months(-13).year, 2022);
+
+    // May 2024 starts on a Wednesday, and takes five weeks from Monday, or five from Sunday:
+    let weeks = month_data(2024, 5, 0, WeekNumbering::Iso);
+    assert_eq!(weeks.len(), 5);
+    assert_eq!(weeks[0].days[0].day, 29);
+    assert_eq!(weeks[0].number, 18);
+    assert_eq!(
+        month_data(2024, 5, 6, WeekNumbering::Iso)[0].days[0].day,
+        28
+    );
+}
+
+#[test]
+fn test_localization() {
+    let locale = DatePickerLocale {
+        calendar: DisplayCalendar::JapaneseEra,
+        ..Default::default()
+    };
+    assert_eq!(locale.year_label(2024, 5, 17), "Reiwa 6");
+    assert_eq!(locale.year_label(2019, 4, 30), "Heisei 31");
+    assert_eq!(locale.year_label(2019, 5, 1), "Reiwa 1");
+    assert_eq!(locale.year_label(1850, 1, 1), "1850");
+    assert_eq!(
+        DatePickerLocale::japanese().year_label(1989, 1, 7),
+        "昭和64年"
+    );
+    assert_eq!(DatePickerLocale::default().year_label(2024, 1, 1), "2024");
+
+    // A time zone with daylight saving time from the unix time 1000 on:
+    let time_zone = TimeZone::from_fn(|unix_seconds| if unix_seconds < 1000 { 60 } else { 120 });
+    assert_eq!(time_zone.utc_offset_minutes(0), 60);
+    assert_eq!(time_zone.utc_offset_minutes(1000), 120);
+    assert_eq!(time_zone, time_zone.clone());
+    assert_ne!(time_zone, TimeZone::fixed(60));
+    assert_eq!(
+        DatePickerLocale::japanese().time_zone,
+        TimeZone::fixed(9 * 60)
+    );
+
+    // Weeks starting on Sunday, with week 1 having January 1st in it:
+    let weeks = month_data(2024, 1, 6, WeekNumbering::Simple);
+    assert_eq!((weeks[0].days[0].month, weeks[0].days[0].day), (12, 31));
+    assert_eq!(weeks[0].number, 1);
+    let weeks = month_data(2024, 12, 6, WeekNumbering::Simple);
+    assert_eq!(weeks[0].number, 49);
+    assert_eq!(weeks.last().unwrap().number, 1);
+}
@@ -1,8 +1,11 @@
-use chrono::{Datelike, NaiveDate, Weekday};
+use egui::{
+    Align, Button, Color32, ComboBox, Direction, DragValue, Id, Layout, RichText, Ui, Vec2,
+};
 
-use egui::{Align, Button, Color32, ComboBox, Direction, Id, Layout, RichText, Ui, Vec2};
-
-use super::{button::DatePickerButtonState, month_data};
+use super::{
+    button::{DatePickerButtonState, DatePickerLocale},
+    month_data, PickableDate, PickableTime, Ymd,
+};
 
 use crate::{Column, Size, StripBuilder, TableBuilder};
 
@@ -11,23 +14,39 @@ struct DatePickerPopupState {
     year: i32,
     month: u32,
     day: u32,
+    hour: u32,
+    minute: u32,
     setup: bool,
 }
 
 impl DatePickerPopupState {
     fn last_day_of_month(&self) -> u32 {
-        let date: NaiveDate =
-            NaiveDate::from_ymd_opt(self.year, self.month, 1).expect("Could not create NaiveDate");
-        date.with_day(31)
-            .map(|_| 31)
-            .or_else(|| date.with_day(30).map(|_| 30))
-            .or_else(|| date.with_day(29).map(|_| 29))
-            .unwrap_or(28)
+        Ymd::days_in_month(self.year, self.month)
+    }
+
+    fn date(&self) -> Ymd {
+        Ymd {
+            year: self.year,
+            month: self.month,
+            day: self.day.min(self.last_day_of_month()),
+        }
+    }
+
+    /// Go to `date`, or the closest day to it that may be picked.
+    fn set_date(&mut self, date: Ymd, min_date: Option<Ymd>, max_date: Option<Ymd>) {
+        let date = date.clamp_to(min_date, max_date);
+        self.year = date.year;
+        self.month = date.month;
+        self.day = date.day;
     }
 }
 
 pub(crate) struct DatePickerPopup<'a> {
-    pub selection: &'a mut NaiveDate,
+    pub selection: &'a mut dyn PickableDate,
+    pub time: Option<&'a mut dyn PickableTime>,
+    pub min_date: Option<Ymd>,
+    pub max_date: Option<Ymd>,
+    pub locale: DatePickerLocale,
     pub button_id: Id,
     pub combo_boxes: bool,
     pub arrows: bool,
@@ -39,19 +58,25 @@ impl<'a> DatePickerPopup<'a> {
     /// Returns `true` if user pressed `Save` button.
     pub fn draw(&mut self, ui: &mut Ui) -> bool {
         let id = ui.make_persistent_id("date_picker");
-        let today = chrono::offset::Utc::now().date_naive();
+        let today = Ymd::today(&self.locale.time_zone);
+        let (min_date, max_date) = (self.min_date, self.max_date);
         let mut popup_state = ui
             .data_mut(|data| data.get_persisted::<DatePickerPopupState>(id))
             .unwrap_or_default();
         if !popup_state.setup {
-            popup_state.year = self.selection.year();
-            popup_state.month = self.selection.month();
-            popup_state.day = self.selection.day();
+            popup_state.set_date(Ymd::of(self.selection), min_date, max_date);
+            if let Some(time) = &self.time {
+                (popup_state.hour, popup_state.minute, _) = time.hms();
+            }
             popup_state.setup = true;
-            ui.data_mut(|data| data.insert_persisted(id, popup_state.clone()));
         }
 
-        let weeks = month_data(popup_state.year, popup_state.month);
+        let weeks = month_data(
+            popup_state.year,
+            popup_state.month,
+            self.locale.first_weekday,
+            self.locale.week_numbering,
+        );
         let (mut close, mut saved) = (false, false);
         let height = 20.0;
         let spacing = 2.0;
@@ -70,55 +95,63 @@ impl<'a> DatePickerPopup<'a> {
                 Size::exact((spacing + height) * (weeks.len() + 1) as f32),
                 self.calendar as usize,
             )
+            .sizes(Size::exact(height), self.time.is_some() as usize)
             .size(Size::exact(height))
             .vertical(|mut strip| {
                 if self.combo_boxes {
                     strip.strip(|builder| {
                         builder.sizes(Size::remainder(), 3).horizontal(|mut strip| {
                             strip.cell(|ui| {
+                                let first_year =
+                                    min_date.map_or(today.year - 100, |min_date| min_date.year);
+                                let last_year =
+                                    max_date.map_or(today.year + 9, |max_date| max_date.year);
+                                let (month, day) = (popup_state.month, popup_state.day);
+                                let year_label = |year| {
+                                    let day = day.min(Ymd::days_in_month(year, month));
+                                    self.locale.year_label(year, month, day)
+                                };
                                 ComboBox::from_id_source("date_picker_year")
-                                    .selected_text(popup_state.year.to_string())
+                                    .selected_text(year_label(popup_state.year))
                                     .show_ui(ui, |ui| {
-                                        for year in today.year() - 100..today.year() + 10 {
+                                        for year in first_year..=last_year {
                                             if ui
                                                 .selectable_value(
                                                     &mut popup_state.year,
                                                     year,
-                                                    year.to_string(),
+                                                    year_label(year),
                                                 )
                                                 .changed()
                                             {
-                                                popup_state.day = popup_state
-                                                    .day
-                                                    .min(popup_state.last_day_of_month());
-                                                ui.memory_mut(|mem| {
-                                                    mem.data
-                                                        .insert_persisted(id, popup_state.clone());
-                                                });
+                                                popup_state.set_date(
+                                                    popup_state.date(),
+                                                    min_date,
+                                                    max_date,
+                                                );
                                             }
                                         }
                                     });
                             });
                             strip.cell(|ui| {
                                 ComboBox::from_id_source("date_picker_month")
-                                    .selected_text(month_name(popup_state.month))
+                                    .selected_text(
+                                        &self.locale.month_names[popup_state.month as usize - 1],
+                                    )
                                     .show_ui(ui, |ui| {
                                         for month in 1..=12 {
                                             if ui
                                                 .selectable_value(
                                                     &mut popup_state.month,
                                                     month,
-                                                    month_name(month),
+                                                    &self.locale.month_names[month as usize - 1],
                                                 )
                                                 .changed()
                                             {
-                                                popup_state.day = popup_state
-                                                    .day
-                                                    .min(popup_state.last_day_of_month());
-                                                ui.memory_mut(|mem| {
-                                                    mem.data
-                                                        .insert_persisted(id, popup_state.clone());
-                                                });
+                                                popup_state.set_date(
+                                                    popup_state.date(),
+                                                    min_date,
+                                                    max_date,
+                                                );
                                             }
                                         }
                                     });
@@ -136,10 +169,11 @@ impl<'a> DatePickerPopup<'a> {
                                                 )
                                                 .changed()
                                             {
-                                                ui.memory_mut(|mem| {
-                                                    mem.data
-                                                        .insert_persisted(id, popup_state.clone());
-                                                });
+                                                popup_state.set_date(
+                                                    popup_state.date(),
+                                                    min_date,
+                                                    max_date,
+                                                );
                                             }
                                         }
                                     });
@@ -151,106 +185,30 @@ impl<'a> DatePickerPopup<'a> {
                 if self.arrows {
                     strip.strip(|builder| {
                         builder.sizes(Size::remainder(), 6).horizontal(|mut strip| {
-                            strip.cell(|ui| {
-                                ui.with_layout(Layout::top_down_justified(Align::Center), |ui| {
-                                    if ui
-                                        .button("<<<")
-                                        .on_hover_text("subtract one year")
-                                        .clicked()
-                                    {
-                                        popup_state.year -= 1;
-                                        popup_state.day =
-                                            popup_state.day.min(popup_state.last_day_of_month());
-                                        ui.data_mut(|data| {
-                                            data.insert_persisted(id, popup_state.clone());
-                                        });
-                                    }
-                                });
-                            });
-                            strip.cell(|ui| {
-                                ui.with_layout(Layout::top_down_justified(Align::Center), |ui| {
-                                    if ui
-                                        .button("<<")
-                                        .on_hover_text("subtract one month")
-                                        .clicked()
-                                    {
-                                        popup_state.month -= 1;
-                                        if popup_state.month == 0 {
-                                            popup_state.month = 12;
-                                            popup_state.year -= 1;
-                                        }
-                                        popup_state.day =
-                                            popup_state.day.min(popup_state.last_day_of_month());
-                                        ui.data_mut(|data| {
-                                            data.insert_persisted(id, popup_state.clone());
-                                        });
-                                    }
-                                });
-                            });
-                            strip.cell(|ui| {
-                                ui.with_layout(Layout::top_down_justified(Align::Center), |ui| {
-                                    if ui.button("<").on_hover_text("subtract one day").clicked() {
-                                        popup_state.day -= 1;
-                                        if popup_state.day == 0 {
-                                            popup_state.month -= 1;
-                                            if popup_state.month == 0 {
-                                                popup_state.year -= 1;
-                                                popup_state.month = 12;
+                            let arrows: [(&str, &str, fn(Ymd) -> Ymd); 6] = [
+                                ("<<<", "subtract one year", |date| date.add_months(-12)),
+                                ("<<", "subtract one month", |date| date.add_months(-1)),
+                                ("<", "subtract one day", |date| date.add_days(-1)),
+                                (">", "add one day", |date| date.add_days(1)),
+                                (">>", "add one month", |date| date.add_months(1)),
+                                (">>>", "add one year", |date| date.add_months(12)),
+                            ];
+                            for (text, hover_text, step) in arrows {
+                                strip.cell(|ui| {
+                                    ui.with_layout(
+                                        Layout::top_down_justified(Align::Center),
+                                        |ui| {
+                                            if ui.button(text).on_hover_text(hover_text).clicked() {
+                                                popup_state.set_date(
+                                                    step(popup_state.date()),
+                                                    min_date,
+                                                    max_date,
+                                                );
                                             }
-                                            popup_state.day = popup_state.last_day_of_month();
-                                        }
-                                        ui.data_mut(|data| {
-                                            data.insert_persisted(id, popup_state.clone());
-                                        });
-                                    }
+                                        },
+                                    );
                                 });
-                            });
-                            strip.cell(|ui| {
-                                ui.with_layout(Layout::top_down_justified(Align::Center), |ui| {
-                                    if ui.button(">").on_hover_text("add one day").clicked() {
-                                        popup_state.day += 1;
-                                        if popup_state.day > popup_state.last_day_of_month() {
-                                            popup_state.day = 1;
-                                            popup_state.month += 1;
-                                            if popup_state.month > 12 {
-                                                popup_state.month = 1;
-                                                popup_state.year += 1;
-                                            }
-                                        }
-                                        ui.data_mut(|data| {
-                                            data.insert_persisted(id, popup_state.clone());
-                                        });
-                                    }
-                                });
-                            });
-                            strip.cell(|ui| {
-                                ui.with_layout(Layout::top_down_justified(Align::Center), |ui| {
-                                    if ui.button(">>").on_hover_text("add one month").clicked() {
-                                        popup_state.month += 1;
-                                        if popup_state.month > 12 {
-                                            popup_state.month = 1;
-                                            popup_state.year += 1;
-                                        }
-                                        popup_state.day =
-                                            popup_state.day.min(popup_state.last_day_of_month());
-                                        ui.data_mut(|data| {
-                                            data.insert_persisted(id, popup_state.clone());
-                                        });
-                                    }
-                                });
-                            });
-                            strip.cell(|ui| {
-                                ui.with_layout(Layout::top_down_justified(Align::Center), |ui| {
-                                    if ui.button(">>>").on_hover_text("add one year").clicked() {
-                                        popup_state.year += 1;
-                                        popup_state.day =
-                                            popup_state.day.min(popup_state.last_day_of_month());
-                                        ui.data_mut(|data| {
-                                            data.insert_persisted(id, popup_state.clone());
-                                        });
-                                    }
-                                });
-                            });
+                            }
                         });
                     });
                 }
@@ -267,19 +225,19 @@ impl<'a> DatePickerPopup<'a> {
                                         ui.with_layout(
                                             Layout::centered_and_justified(Direction::TopDown),
                                             |ui| {
-                                                ui.label("Week");
+                                                ui.label(&self.locale.week);
                                             },
                                         );
                                     });
                                 }
 
-                                //TODO(elwerene): Locale
-                                for name in ["Mo", "Tu", "We", "Th", "Fr", "Sa", "Su"] {
+                                for i in 0..7 {
+                                    let weekday = (self.locale.first_weekday + i) % 7;
                                     header.col(|ui| {
                                         ui.with_layout(
                                             Layout::centered_and_justified(Direction::TopDown),
                                             |ui| {
-                                                ui.label(name);
+                                                ui.label(&self.locale.weekday_names[weekday]);
                                             },
                                         );
                                     });
@@ -298,23 +256,18 @@ impl<'a> DatePickerPopup<'a> {
                                                 ui.with_layout(
                                                     Layout::top_down_justified(Align::Center),
                                                     |ui| {
-                                                        let fill_color = if popup_state.year
-                                                            == day.year()
-                                                            && popup_state.month == day.month()
-                                                            && popup_state.day == day.day()
-                                                        {
-                                                            ui.visuals().selection.bg_fill
-                                                        } else if day.weekday() == Weekday::Sat
-                                                            || day.weekday() == Weekday::Sun
-                                                        {
-                                                            if ui.visuals().dark_mode {
-                                                                Color32::DARK_RED
+                                                        let fill_color =
+                                                            if popup_state.date() == day {
+                                                                ui.visuals().selection.bg_fill
+                                                            } else if day.weekday() >= 5 {
+                                                                if ui.visuals().dark_mode {
+                                                                    Color32::DARK_RED
+                                                                } else {
+                                                                    Color32::LIGHT_RED
+                                                                }
                                                             } else {
-                                                                Color32::LIGHT_RED
-                                                            }
-                                                        } else {
-                                                            ui.visuals().extreme_bg_color
-                                                        };
+                                                                ui.visuals().extreme_bg_color
+                                                            };
 
                                                         let mut text_color = ui
                                                             .visuals()
@@ -322,17 +275,18 @@ impl<'a> DatePickerPopup<'a> {
                                                             .inactive
                                                             .text_color();
 
-                                                        if day.month() != popup_state.month {
+                                                        if day.month != popup_state.month {
                                                             text_color =
                                                                 text_color.linear_multiply(0.5);
                                                         };
 
-                                                        let button_response = ui.add(
+                                                        let enabled =
+                                                            day.clamp_to(min_date, max_date) == day;
+                                                        let button_response = ui.add_enabled(
+                                                            enabled,
                                                             Button::new(
-                                                                RichText::new(
-                                                                    day.day().to_string(),
-                                                                )
-                                                                .color(text_color),
+                                                                RichText::new(day.day.to_string())
+                                                                    .color(text_color),
                                                             )
                                                             .fill(fill_color),
                                                         );
@@ -352,15 +306,8 @@ impl<'a> DatePickerPopup<'a> {
                                                         }
 
                                                         if button_response.clicked() {
-                                                            popup_state.year = day.year();
-                                                            popup_state.month = day.month();
-                                                            popup_state.day = day.day();
-                                                            ui.data_mut(|data| {
-                                                                data.insert_persisted(
-                                                                    id,
-                                                                    popup_state.clone(),
-                                                                );
-                                                            });
+                                                            popup_state
+                                                                .set_date(day, min_date, max_date);
                                                         }
                                                     },
                                                 );
@@ -372,25 +319,44 @@ impl<'a> DatePickerPopup<'a> {
                     });
                 }
 
+                if self.time.is_some() {
+                    strip.cell(|ui| {
+                        ui.horizontal_centered(|ui| {
+                            ui.label(&self.locale.time);
+                            ui.add(
+                                DragValue::new(&mut popup_state.hour)
+                                    .clamp_range(0..=23)
+                                    .custom_formatter(|hour, _| format!("{hour:02}")),
+                            );
+                            ui.label(":");
+                            ui.add(
+                                DragValue::new(&mut popup_state.minute)
+                                    .clamp_range(0..=59)
+                                    .custom_formatter(|minute, _| format!("{minute:02}")),
+                            );
+                        });
+                    });
+                }
+
                 strip.strip(|builder| {
                     builder.sizes(Size::remainder(), 3).horizontal(|mut strip| {
                         strip.empty();
                         strip.cell(|ui| {
                             ui.with_layout(Layout::top_down_justified(Align::Center), |ui| {
-                                if ui.button("Cancel").clicked() {
+                                if ui.button(&self.locale.cancel).clicked() {
                                     close = true;
                                 }
                             });
                         });
                         strip.cell(|ui| {
                             ui.with_layout(Layout::top_down_justified(Align::Center), |ui| {
-                                if ui.button("Save").clicked() {
-                                    *self.selection = NaiveDate::from_ymd_opt(
-                                        popup_state.year,
-                                        popup_state.month,
-                                        popup_state.day,
-                                    )
-                                    .expect("Could not create NaiveDate");
+                                if ui.button(&self.locale.save).clicked() {
+                                    let date = popup_state.date();
+                                    self.selection.set_ymd(date.year, date.month, date.day);
+                                    if let Some(time) = &mut self.time {
+                                        let (_, _, second) = time.hms();
+                                        time.set_hms(popup_state.hour, popup_state.minute, second);
+                                    }
                                     saved = true;
                                     close = true;
                                 }
@@ -402,31 +368,15 @@ impl<'a> DatePickerPopup<'a> {
 
         if close {
             popup_state.setup = false;
-            ui.data_mut(|data| {
-                data.insert_persisted(id, popup_state);
+        }
+        ui.data_mut(|data| {
+            data.insert_persisted(id, popup_state);
+            if close {
                 data.get_persisted_mut_or_default::<DatePickerButtonState>(self.button_id)
                     .picker_visible = false;
-            });
-        }
+            }
+        });
 
         saved && close
     }
 }
-
-fn month_name(i: u32) -> &'static str {
-    match i {
-        1 => "January",
-        2 => "February",
-        3 => "March",
-        4 => "April",
-        5 => "May",
-        6 => "June",
-        7 => "July",
-        8 => "August",
-        9 => "September",
-        10 => "October",
-        11 => "November",
-        12 => "December",
-        _ => panic!("Unknown month: {i}"),
-    }
-}
@@ -33,6 +33,24 @@ pub(crate) struct StripLayoutFlags {
     pub(crate) striped: bool,
     pub(crate) hovered: bool,
     pub(crate) selected: bool,
+
+    /// The cell is in one of the [`FrozenColumns`], so it doesn't scroll horizontally.
+    pub(crate) frozen: bool,
+}
+
+/// The first columns of a [`Table`](crate::Table), which stay in view when it scrolls horizontally.
+#[derive(Clone, Copy, Default)]
+pub(crate) struct FrozenColumns {
+    /// How many columns are frozen.
+    pub(crate) count: usize,
+
+    /// How far to move the frozen cells to the right, to undo the horizontal scrolling.
+    pub(crate) offset_x: f32,
+
+    /// The right edge of the frozen columns, in screen space.
+    ///
+    /// The other cells are clipped to the right of this.
+    pub(crate) right: f32,
 }
 
 /// Positions cells in [`CellDirection`] and starts a new line on [`StripLayout::end_line`]
@@ -48,6 +66,14 @@ pub struct StripLayout<'l> {
 
     cell_layout: egui::Layout,
     sense: Sense,
+
+    pub(crate) frozen_columns: FrozenColumns,
+
+    /// Move the cells down by this much, without moving the cursor, e.g. to pin a sticky row.
+    pub(crate) offset_y: f32,
+
+    /// The cells are clipped below this, so they don't cover a sticky row.
+    pub(crate) clip_top: f32,
 }
 
 impl<'l> StripLayout<'l> {
@@ -68,6 +94,9 @@ impl<'l> StripLayout<'l> {
             max: pos,
             cell_layout,
             sense,
+            frozen_columns: Default::default(),
+            offset_y: 0.0,
+            clip_top: f32::NEG_INFINITY,
         }
     }
 
@@ -116,14 +145,24 @@ impl<'l> StripLayout<'l> {
         child_ui_id_source: Id,
         add_cell_contents: impl FnOnce(&mut Ui),
     ) -> (Rect, Response) {
-        let max_rect = self.cell_rect(&width, &height);
+        let layout_rect = self.cell_rect(&width, &height);
+        let offset_x = if flags.frozen {
+            self.frozen_columns.offset_x
+        } else {
+            0.0
+        };
+        let offset = egui::vec2(offset_x, self.offset_y);
+        let max_rect = layout_rect.translate(offset);
+
+        let clip_rect = self.cell_clip_rect(flags);
+        let painter = self.ui.painter_at(clip_rect);
 
         // Make sure we don't have a gap in the stripe/frame/selection background:
         let item_spacing = self.ui.spacing().item_spacing;
         let gapless_rect = max_rect.expand2(0.5 * item_spacing);
 
         if flags.striped {
-            self.ui.painter().rect_filled(
+            painter.rect_filled(
                 gapless_rect,
                 egui::Rounding::ZERO,
                 self.ui.visuals().faint_bg_color,
@@ -131,7 +170,7 @@ impl<'l> StripLayout<'l> {
         }
 
         if flags.selected {
-            self.ui.painter().rect_filled(
+            painter.rect_filled(
                 gapless_rect,
                 egui::Rounding::ZERO,
                 self.ui.visuals().selection.bg_fill,
@@ -139,23 +178,29 @@ impl<'l> StripLayout<'l> {
         }
 
         if flags.hovered && !flags.selected && self.sense.interactive() {
-            self.ui.painter().rect_filled(
+            painter.rect_filled(
                 gapless_rect,
                 egui::Rounding::ZERO,
                 self.ui.visuals().widgets.hovered.bg_fill,
             );
         }
 
-        let child_ui = self.cell(flags, max_rect, child_ui_id_source, add_cell_contents);
+        let child_ui = self.cell(
+            flags,
+            max_rect,
+            clip_rect,
+            child_ui_id_source,
+            add_cell_contents,
+        );
 
         let used_rect = child_ui.min_rect();
 
-        self.set_pos(max_rect);
+        self.set_pos(layout_rect);
 
         let allocation_rect = if flags.clip {
-            max_rect
+            layout_rect
         } else {
-            max_rect.union(used_rect)
+            layout_rect.union(used_rect.translate(-offset))
         };
 
         self.ui.advance_cursor_after_rect(allocation_rect);
@@ -187,17 +232,29 @@ impl<'l> StripLayout<'l> {
         self.ui.allocate_rect(rect, Sense::hover());
     }
 
+    /// Where a cell may paint, so that it doesn't cover the frozen columns or a sticky row.
+    fn cell_clip_rect(&self, flags: StripLayoutFlags) -> Rect {
+        let mut clip_rect = self.ui.clip_rect();
+        clip_rect.min.y = clip_rect.min.y.max(self.clip_top);
+        if !flags.frozen && 0 < self.frozen_columns.count {
+            clip_rect.min.x = clip_rect.min.x.max(self.frozen_columns.right);
+        }
+        clip_rect
+    }
+
     /// Return the Ui to which the contents where added
     fn cell(
         &mut self,
         flags: StripLayoutFlags,
         rect: Rect,
+        clip_rect: Rect,
         child_ui_id_source: egui::Id,
         add_cell_contents: impl FnOnce(&mut Ui),
     ) -> Ui {
         let mut child_ui =
             self.ui
                 .child_ui_with_id_source(rect, self.cell_layout, child_ui_id_source);
+        child_ui.set_clip_rect(clip_rect);
 
         if flags.clip {
             let margin = egui::Vec2::splat(self.ui.visuals().clip_rect_margin);
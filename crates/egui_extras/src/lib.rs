@@ -11,11 +11,12 @@
 #![cfg_attr(feature = "puffin", deny(unsafe_code))]
 #![cfg_attr(not(feature = "puffin"), forbid(unsafe_code))]
 
-#[cfg(feature = "chrono")]
+#[cfg(any(feature = "chrono", feature = "time"))]
 mod datepicker;
 
 pub mod syntax_highlighting;
 
+mod cell_grid;
 #[doc(hidden)]
 pub mod image;
 mod layout;
@@ -23,10 +24,15 @@ mod loaders;
 mod sizing;
 mod strip;
 mod table;
+mod tree_view;
 
-#[cfg(feature = "chrono")]
-pub use crate::datepicker::DatePickerButton;
+#[cfg(any(feature = "chrono", feature = "time"))]
+pub use crate::datepicker::{
+    DatePickerButton, DatePickerLocale, DisplayCalendar, PickableDate, PickableTime, TimeZone,
+    WeekNumbering,
+};
 
+pub use crate::cell_grid::*;
 #[doc(hidden)]
 #[allow(deprecated)]
 pub use crate::image::RetainedImage;
@@ -34,6 +40,7 @@ pub(crate) use crate::layout::StripLayout;
 pub use crate::sizing::Size;
 pub use crate::strip::*;
 pub use crate::table::*;
+pub use crate::tree_view::*;
 
 pub use loaders::install_image_loaders;
 
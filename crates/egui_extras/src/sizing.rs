@@ -11,6 +11,11 @@ pub enum Size {
 
     /// Multiple remainders each get the same space.
     Remainder { range: Rangef },
+
+    /// Fit the contents, as measured the last time the [`crate::Strip`] was shown.
+    ///
+    /// Until it has been measured, it is sized like [`Self::Remainder`].
+    Auto { range: Rangef },
 }
 
 impl Size {
@@ -31,6 +36,8 @@ impl Size {
     }
 
     /// Relative size relative to all available space. Values must be in range `0.0..=1.0`.
+    ///
+    /// Combine with [`Self::at_least`] and [`Self::at_most`] for e.g. "30%, but at least 120 points".
     pub fn relative(fraction: f32) -> Self {
         egui::egui_assert!(0.0 <= fraction && fraction <= 1.0);
         Self::Relative {
@@ -46,13 +53,23 @@ impl Size {
         }
     }
 
+    /// Just big enough for the contents of the cell, see [`Self::Auto`].
+    ///
+    /// Text in the cell won't wrap in a horizontal [`crate::Strip`].
+    pub fn auto() -> Self {
+        Self::Auto {
+            range: Rangef::new(0.0, f32::INFINITY),
+        }
+    }
+
     /// Won't shrink below this size (in points).
     #[inline]
     pub fn at_least(mut self, minimum: f32) -> Self {
         match &mut self {
             Self::Absolute { range, .. }
             | Self::Relative { range, .. }
-            | Self::Remainder { range, .. } => {
+            | Self::Remainder { range, .. }
+            | Self::Auto { range } => {
                 range.min = minimum;
             }
         }
@@ -65,7 +82,8 @@ impl Size {
         match &mut self {
             Self::Absolute { range, .. }
             | Self::Relative { range, .. }
-            | Self::Remainder { range, .. } => {
+            | Self::Remainder { range, .. }
+            | Self::Auto { range } => {
                 range.max = maximum;
             }
         }
@@ -77,7 +95,8 @@ impl Size {
         match self {
             Self::Absolute { range, .. }
             | Self::Relative { range, .. }
-            | Self::Remainder { range, .. } => range,
+            | Self::Remainder { range, .. }
+            | Self::Auto { range } => range,
         }
     }
 }
@@ -107,7 +126,7 @@ impl Sizing {
                     assert!(0.0 <= fraction && fraction <= 1.0);
                     range.clamp(length * fraction)
                 }
-                Size::Remainder { .. } => {
+                Size::Remainder { .. } | Size::Auto { .. } => {
                     remainders += 1;
                     0.0
                 }
@@ -121,7 +140,7 @@ impl Sizing {
             let mut remainder_length = length - sum_non_remainder;
             let avg_remainder_length = 0.0f32.max(remainder_length / remainders as f32).floor();
             self.sizes.iter().for_each(|&size| {
-                if let Size::Remainder { range } = size {
+                if let Size::Remainder { range } | Size::Auto { range } = size {
                     if avg_remainder_length < range.min {
                         remainder_length -= range.min;
                         remainders -= 1;
@@ -140,10 +159,36 @@ impl Sizing {
             .map(|&size| match size {
                 Size::Absolute { initial, .. } => initial,
                 Size::Relative { fraction, range } => range.clamp(length * fraction),
-                Size::Remainder { range } => range.clamp(avg_remainder_length),
+                Size::Remainder { range } | Size::Auto { range } => {
+                    range.clamp(avg_remainder_length)
+                }
             })
             .collect()
     }
+
+    /// Are there any [`Size::Auto`]?
+    pub(crate) fn has_auto(&self) -> bool {
+        self.sizes
+            .iter()
+            .any(|size| matches!(size, Size::Auto { .. }))
+    }
+
+    /// Give each [`Size::Auto`] the size its contents used last time.
+    ///
+    /// `measured` has one length per size.
+    pub(crate) fn with_measured(mut self, measured: &[f32]) -> Self {
+        if measured.len() == self.sizes.len() {
+            for (size, &length) in self.sizes.iter_mut().zip(measured) {
+                if let Size::Auto { range } = *size {
+                    *size = Size::Absolute {
+                        initial: range.clamp(length),
+                        range,
+                    };
+                }
+            }
+        }
+        self
+    }
 }
 
 impl From<Vec<Size>> for Sizing {
@@ -172,4 +217,18 @@ fn test_sizing() {
     assert_eq!(sizing.to_lengths(30.0, 0.0), vec![15.0, 10.0]);
     assert_eq!(sizing.to_lengths(20.0, 0.0), vec![10.0, 10.0]);
     assert_eq!(sizing.to_lengths(10.0, 0.0), vec![10.0, 10.0]);
+
+    let sizing: Sizing = vec![
+        Size::relative(0.3).at_least(120.0).at_most(400.0),
+        Size::remainder(),
+    ]
+    .into();
+    assert_eq!(sizing.to_lengths(1000.0, 0.0), vec![300.0, 700.0]);
+    assert_eq!(sizing.to_lengths(200.0, 0.0), vec![120.0, 80.0]);
+    assert_eq!(sizing.to_lengths(2000.0, 0.0), vec![400.0, 1600.0]);
+
+    let sizing: Sizing = vec![Size::auto().at_most(30.0), Size::remainder()].into();
+    assert_eq!(sizing.to_lengths(100.0, 0.0), vec![30.0, 50.0]);
+    let sizing = sizing.with_measured(&[20.0, 80.0]);
+    assert_eq!(sizing.to_lengths(100.0, 0.0), vec![20.0, 80.0]);
 }
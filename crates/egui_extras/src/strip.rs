@@ -107,7 +107,8 @@ impl<'a> StripBuilder<'a> {
     where
         F: for<'b> FnOnce(Strip<'a, 'b>),
     {
-        let widths = self.sizing.to_lengths(
+        let auto_sizes = AutoSizes::load(self.ui, &self.sizing);
+        let widths = auto_sizes.sizing(self.sizing).to_lengths(
             self.ui.available_rect_before_wrap().width(),
             self.ui.spacing().item_spacing.x,
         );
@@ -123,6 +124,7 @@ impl<'a> StripBuilder<'a> {
             clip: self.clip,
             sizes: widths,
             size_index: 0,
+            auto_sizes,
         });
         layout.allocate_rect()
     }
@@ -135,7 +137,8 @@ impl<'a> StripBuilder<'a> {
     where
         F: for<'b> FnOnce(Strip<'a, 'b>),
     {
-        let heights = self.sizing.to_lengths(
+        let auto_sizes = AutoSizes::load(self.ui, &self.sizing);
+        let heights = auto_sizes.sizing(self.sizing).to_lengths(
             self.ui.available_rect_before_wrap().height(),
             self.ui.spacing().item_spacing.y,
         );
@@ -151,11 +154,65 @@ impl<'a> StripBuilder<'a> {
             clip: self.clip,
             sizes: heights,
             size_index: 0,
+            auto_sizes,
         });
         layout.allocate_rect()
     }
 }
 
+/// Remembers how big the contents of each cell was, for [`Size::auto`].
+struct AutoSizes {
+    /// `None` if there are no [`Size::Auto`].
+    id: Option<egui::Id>,
+
+    /// Which cells are [`Size::Auto`].
+    is_auto: Vec<bool>,
+
+    /// How much space each cell used last time, if it was shown before.
+    measured: Option<Vec<f32>>,
+
+    /// How much space each cell used this time.
+    used: Vec<f32>,
+}
+
+impl AutoSizes {
+    fn load(ui: &Ui, sizing: &Sizing) -> Self {
+        let id = sizing
+            .has_auto()
+            .then(|| ui.next_auto_id().with("__strip_auto_sizes"));
+        let measured = id
+            .and_then(|id| ui.data(|d| d.get_temp::<Vec<f32>>(id)))
+            .filter(|measured| measured.len() == sizing.sizes.len());
+        Self {
+            id,
+            is_auto: sizing
+                .sizes
+                .iter()
+                .map(|size| matches!(size, Size::Auto { .. }))
+                .collect(),
+            measured,
+            used: Vec::with_capacity(sizing.sizes.len()),
+        }
+    }
+
+    fn sizing(&self, sizing: Sizing) -> Sizing {
+        match &self.measured {
+            Some(measured) => sizing.with_measured(measured),
+            None => sizing,
+        }
+    }
+
+    fn store(&self, ui: &Ui) {
+        if let Some(id) = self.id {
+            ui.data_mut(|d| d.insert_temp(id, self.used.clone()));
+            if self.measured.is_none() {
+                // The auto-sized cells were invisible while we measured them:
+                ui.ctx().request_repaint();
+            }
+        }
+    }
+}
+
 /// A Strip of cells which go in one direction. Each cell has a fixed size.
 /// In contrast to normal egui behavior, strip cells do *not* grow with its children!
 pub struct Strip<'a, 'b> {
@@ -164,6 +221,7 @@ pub struct Strip<'a, 'b> {
     clip: bool,
     sizes: Vec<f32>,
     size_index: usize,
+    auto_sizes: AutoSizes,
 }
 
 impl<'a, 'b> Strip<'a, 'b> {
@@ -189,18 +247,33 @@ impl<'a, 'b> Strip<'a, 'b> {
     /// Add cell contents.
     #[cfg_attr(debug_assertions, track_caller)]
     pub fn cell(&mut self, add_contents: impl FnOnce(&mut Ui)) {
+        let is_auto = self.auto_sizes.is_auto.get(self.size_index) == Some(&true);
+        let is_measured = self.auto_sizes.measured.is_some();
+        let is_horizontal = matches!(self.direction, CellDirection::Horizontal);
         let (width, height) = self.next_cell_size();
         let flags = StripLayoutFlags {
             clip: self.clip,
             ..Default::default()
         };
-        self.layout.add(
-            flags,
-            width,
-            height,
-            egui::Id::new(self.size_index),
-            add_contents,
-        );
+        let (used_rect, _) =
+            self.layout
+                .add(flags, width, height, egui::Id::new(self.size_index), |ui| {
+                    if is_auto {
+                        if !is_measured {
+                            // Hide first-frame-jitters when auto-sizing.
+                            ui.set_visible(false);
+                        }
+                        if is_horizontal {
+                            ui.style_mut().wrap = Some(false);
+                        }
+                    }
+                    add_contents(ui);
+                });
+        self.auto_sizes.used.push(if is_horizontal {
+            used_rect.width()
+        } else {
+            used_rect.height()
+        });
     }
 
     /// Add an empty cell.
@@ -208,6 +281,7 @@ impl<'a, 'b> Strip<'a, 'b> {
     pub fn empty(&mut self) {
         let (width, height) = self.next_cell_size();
         self.layout.empty(width, height);
+        self.auto_sizes.used.push(0.0);
     }
 
     /// Add a strip as cell.
@@ -224,5 +298,38 @@ impl<'a, 'b> Drop for Strip<'a, 'b> {
         while self.size_index < self.sizes.len() {
             self.empty();
         }
+        self.auto_sizes.store(self.layout.ui);
     }
 }
+
+#[test]
+fn test_auto_size() {
+    use egui::{test_harness::Harness, vec2, Rect, Sense};
+    use std::cell::Cell;
+
+    let contents_rect = Cell::new(Rect::NOTHING);
+    let next_rect = Cell::new(Rect::NOTHING);
+    let mut harness = Harness::new_ui(|ui| {
+        StripBuilder::new(ui)
+            .size(Size::auto())
+            .size(Size::remainder())
+            .horizontal(|mut strip| {
+                strip.cell(|ui| {
+                    let (rect, _) = ui.allocate_exact_size(vec2(250.0, 10.0), Sense::hover());
+                    contents_rect.set(rect);
+                });
+                strip.cell(|ui| {
+                    next_rect.set(ui.max_rect());
+                });
+            });
+    });
+    harness.set_size(vec2(1000.0, 400.0));
+    harness.run();
+
+    // The auto-sized cell fits its contents:
+    let spacing = harness.ctx().style().spacing.item_spacing.x;
+    assert_eq!(
+        next_rect.get().left(),
+        contents_rect.get().right() + spacing
+    );
+}
@@ -3,13 +3,15 @@
 //! | fixed size | all available space/minimum | 30% of available width | fixed size |
 //! Takes all available height, so if you want something below the table, put it in a strip.
 
+use std::{cmp::Ordering, collections::BTreeSet, sync::Arc};
+
 use egui::{
     scroll_area::ScrollBarVisibility, Align, NumExt as _, Rangef, Rect, Response, ScrollArea, Ui,
     Vec2, Vec2b,
 };
 
 use crate::{
-    layout::{CellDirection, CellSize, StripLayoutFlags},
+    layout::{CellDirection, CellSize, FrozenColumns, StripLayoutFlags},
     StripLayout,
 };
 
@@ -23,13 +25,19 @@ enum InitialColumnSize {
     /// Base on content
     Automatic(f32),
 
+    /// This fraction of the available width
+    Relative(f32),
+
     /// Take all available space
     Remainder,
 }
 
+/// Compares two rows of a table, see [`Column::sortable`].
+type RowComparator<'a> = Arc<dyn Fn(usize, usize) -> Ordering + 'a>;
+
 /// Specifies the properties of a column, like its width range.
-#[derive(Clone, Copy, Debug, PartialEq)]
-pub struct Column {
+#[derive(Clone)]
+pub struct Column<'a> {
     initial_width: InitialColumnSize,
 
     width_range: Rangef,
@@ -38,9 +46,39 @@ pub struct Column {
     clip: bool,
 
     resizable: Option<bool>,
+
+    /// Set for a sortable column.
+    compare: Option<RowComparator<'a>>,
+}
+
+impl std::fmt::Debug for Column<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Column")
+            .field("initial_width", &self.initial_width)
+            .field("width_range", &self.width_range)
+            .field("clip", &self.clip)
+            .field("resizable", &self.resizable)
+            .field("sortable", &self.compare.is_some())
+            .finish()
+    }
+}
+
+impl PartialEq for Column<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        let same_compare = match (&self.compare, &other.compare) {
+            (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+            (None, None) => true,
+            _ => false,
+        };
+        self.initial_width == other.initial_width
+            && self.width_range == other.width_range
+            && self.clip == other.clip
+            && self.resizable == other.resizable
+            && same_compare
+    }
 }
 
-impl Column {
+impl<'a> Column<'a> {
     /// Automatically sized based on content.
     ///
     /// If you have many thousands of rows and are therefore using [`TableBody::rows`]
@@ -71,6 +109,15 @@ impl Column {
             .clip(true)
     }
 
+    /// This fraction of the available width, e.g. `0.3` for 30%. Must be in range `0.0..=1.0`.
+    ///
+    /// Combine with [`Self::at_least`] and [`Self::at_most`] for e.g. "30%, but at least 120 points".
+    /// Unless it is [`Self::resizable`], it follows the available width when that changes.
+    pub fn relative(fraction: f32) -> Self {
+        egui::egui_assert!(0.0 <= fraction && fraction <= 1.0);
+        Self::new(InitialColumnSize::Relative(fraction))
+    }
+
     /// Take all the space remaining after the other columns have
     /// been sized.
     ///
@@ -86,6 +133,7 @@ impl Column {
             width_range: Rangef::new(0.0, f32::INFINITY),
             resizable: None,
             clip: false,
+            compare: None,
         }
     }
 
@@ -99,6 +147,58 @@ impl Column {
         self
     }
 
+    /// Let the user sort the table by this column, by clicking its header.
+    ///
+    /// `compare` is given the indices of two rows of your data,
+    /// i.e. the [`TableRow::index`] they have in [`TableBody::rows`] or [`TableBody::heterogeneous_rows`].
+    ///
+    /// Clicking the header sorts the table by this column in [`SortOrder::Ascending`] order,
+    /// and clicking it again reverses the order. The sorted column gets an arrow in its header.
+    /// The sorting is remembered like the column widths, and applied every frame,
+    /// so the rows stay sorted when your data changes.
+    /// Rows added with [`TableBody::row`] are not sorted, see [`Table::sort`] for that.
+    ///
+    /// ### Example
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// use egui_extras::{TableBuilder, Column};
+    /// let fruits = vec![("Banana", 3), ("Apple", 5), ("Cherry", 1)];
+    /// TableBuilder::new(ui)
+    ///     .column(Column::auto().sortable_by_key(|row| fruits[row].0))
+    ///     .column(Column::remainder().sortable(|a, b| fruits[a].1.cmp(&fruits[b].1)))
+    ///     .header(20.0, |mut header| {
+    ///         header.col(|ui| {
+    ///             ui.strong("Fruit");
+    ///         });
+    ///         header.col(|ui| {
+    ///             ui.strong("Count");
+    ///         });
+    ///     })
+    ///     .body(|body| {
+    ///         body.rows(18.0, fruits.len(), |mut row| {
+    ///             let (name, count) = fruits[row.index()];
+    ///             row.col(|ui| {
+    ///                 ui.label(name);
+    ///             });
+    ///             row.col(|ui| {
+    ///                 ui.label(count.to_string());
+    ///             });
+    ///         });
+    ///     });
+    /// # });
+    /// ```
+    #[inline]
+    pub fn sortable(mut self, compare: impl Fn(usize, usize) -> Ordering + 'a) -> Self {
+        self.compare = Some(Arc::new(compare));
+        self
+    }
+
+    /// Like [`Self::sortable`], comparing the rows by the key `key` gives for their index.
+    #[inline]
+    pub fn sortable_by_key<K: Ord>(self, key: impl Fn(usize) -> K + 'a) -> Self {
+        self.sortable(move |a, b| key(a).cmp(&key(b)))
+    }
+
     /// If `true`: Allow the column to shrink enough to clip the contents.
     /// If `false`: The column will always be wide enough to contain all its content.
     ///
@@ -141,12 +241,14 @@ impl Column {
     fn is_auto(&self) -> bool {
         match self.initial_width {
             InitialColumnSize::Automatic(_) => true,
-            InitialColumnSize::Absolute(_) | InitialColumnSize::Remainder => false,
+            InitialColumnSize::Absolute(_)
+            | InitialColumnSize::Relative(_)
+            | InitialColumnSize::Remainder => false,
         }
     }
 }
 
-fn to_sizing(columns: &[Column]) -> crate::sizing::Sizing {
+fn to_sizing(columns: &[Column<'_>]) -> crate::sizing::Sizing {
     use crate::Size;
 
     let mut sizing = crate::sizing::Sizing::default();
@@ -154,6 +256,7 @@ fn to_sizing(columns: &[Column]) -> crate::sizing::Sizing {
         let size = match column.initial_width {
             InitialColumnSize::Absolute(width) => Size::exact(width),
             InitialColumnSize::Automatic(suggested_width) => Size::initial(suggested_width),
+            InitialColumnSize::Relative(fraction) => Size::relative(fraction),
             InitialColumnSize::Remainder => Size::remainder(),
         }
         .at_least(column.width_range.min)
@@ -165,7 +268,186 @@ fn to_sizing(columns: &[Column]) -> crate::sizing::Sizing {
 
 // -----------------------------------------------------------------=----------
 
+/// Which way a [`Table`] is sorted, see [`Column::sortable`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum SortOrder {
+    /// Smallest first.
+    Ascending,
+
+    /// Largest first.
+    Descending,
+}
+
+impl SortOrder {
+    /// The other way around.
+    #[inline]
+    pub fn reversed(self) -> Self {
+        match self {
+            Self::Ascending => Self::Descending,
+            Self::Descending => Self::Ascending,
+        }
+    }
+}
+
+// -----------------------------------------------------------------=----------
+
+/// Which rows of a [`Table`] are selected, see [`TableBuilder::selection`].
+///
+/// Keep this around between frames, e.g. in your app state.
+/// The rows are identified by their index, see [`TableRow::index`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub struct TableSelection {
+    /// Can more than one row be selected?
+    multiple: bool,
+
+    selected: BTreeSet<usize>,
+
+    /// Where a range selected with shift starts.
+    anchor: Option<usize>,
+
+    /// The row that was last clicked, or moved to with the arrow keys.
+    cursor: Option<usize>,
+}
+
+impl TableSelection {
+    /// Only one row can be selected at a time.
+    pub fn single() -> Self {
+        Self::default()
+    }
+
+    /// Any number of rows can be selected, using ctrl/cmd and shift.
+    pub fn multiple() -> Self {
+        Self {
+            multiple: true,
+            ..Default::default()
+        }
+    }
+
+    #[inline]
+    pub fn is_selected(&self, row_index: usize) -> bool {
+        self.selected.contains(&row_index)
+    }
+
+    /// The indices of the selected rows, in order.
+    pub fn selected_rows(&self) -> impl Iterator<Item = usize> + '_ {
+        self.selected.iter().copied()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.selected.is_empty()
+    }
+
+    /// The row that was last clicked, or moved to with the arrow keys.
+    #[inline]
+    pub fn cursor(&self) -> Option<usize> {
+        self.cursor
+    }
+
+    /// Select only this row.
+    pub fn select(&mut self, row_index: usize) {
+        self.selected.clear();
+        self.selected.insert(row_index);
+        self.anchor = Some(row_index);
+        self.cursor = Some(row_index);
+    }
+
+    /// Add the row to the selection, or remove it from it.
+    ///
+    /// With [`Self::single`], selecting a row deselects all others.
+    pub fn set_selected(&mut self, row_index: usize, selected: bool) {
+        if !selected {
+            self.selected.remove(&row_index);
+        } else if self.multiple {
+            self.selected.insert(row_index);
+        } else {
+            self.select(row_index);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.selected.clear();
+        self.anchor = None;
+        self.cursor = None;
+    }
+
+    /// The user clicked the row.
+    ///
+    /// A range selected with shift goes by the `order` the rows are shown in.
+    fn click(&mut self, row_index: usize, modifiers: egui::Modifiers, order: Option<&RowOrder>) {
+        if self.multiple && modifiers.shift {
+            let anchor = self.anchor.unwrap_or(row_index);
+            if !modifiers.command {
+                self.selected.clear();
+            }
+            if let Some(order) = order {
+                let (a, b) = (order.position_of(anchor), order.position_of(row_index));
+                let range = a.min(b)..=a.max(b);
+                self.selected
+                    .extend(range.map(|position| order.row_at(position)));
+            } else {
+                self.selected
+                    .extend(anchor.min(row_index)..=anchor.max(row_index));
+            }
+            self.cursor = Some(row_index);
+        } else if self.multiple && modifiers.command {
+            if !self.selected.remove(&row_index) {
+                self.selected.insert(row_index);
+            }
+            self.anchor = Some(row_index);
+            self.cursor = Some(row_index);
+        } else {
+            self.select(row_index);
+        }
+    }
+
+    /// The user moved to the row with the arrow keys, holding shift to `extend` the selection.
+    fn move_cursor(&mut self, row_index: usize, extend: bool, order: Option<&RowOrder>) {
+        if self.multiple && extend {
+            self.click(row_index, egui::Modifiers::SHIFT, order);
+        } else {
+            self.select(row_index);
+        }
+    }
+}
+
+/// The order [`TableBody::rows`] and [`TableBody::heterogeneous_rows`] show the rows in,
+/// when the table is sorted by a [`Column::sortable`].
+struct RowOrder {
+    /// The index of the row shown at each position.
+    rows: Vec<usize>,
+
+    /// The position each row is shown at, the inverse of [`Self::rows`].
+    positions: Vec<usize>,
+}
+
+impl RowOrder {
+    fn sorted(num_rows: usize, compare: &RowComparator<'_>, order: SortOrder) -> Self {
+        let mut rows: Vec<usize> = (0..num_rows).collect();
+        match order {
+            SortOrder::Ascending => rows.sort_by(|&a, &b| compare(a, b)),
+            SortOrder::Descending => rows.sort_by(|&a, &b| compare(b, a)),
+        }
+        let mut positions = vec![0; num_rows];
+        for (position, &row_index) in rows.iter().enumerate() {
+            positions[row_index] = position;
+        }
+        Self { rows, positions }
+    }
+
+    fn row_at(&self, position: usize) -> usize {
+        self.rows.get(position).copied().unwrap_or(position)
+    }
+
+    fn position_of(&self, row_index: usize) -> usize {
+        self.positions.get(row_index).copied().unwrap_or(row_index)
+    }
+}
+
+// -----------------------------------------------------------------=----------
+
 struct TableScrollOptions {
+    hscroll: bool,
     vscroll: bool,
     drag_to_scroll: bool,
     stick_to_bottom: bool,
@@ -180,6 +462,7 @@ struct TableScrollOptions {
 impl Default for TableScrollOptions {
     fn default() -> Self {
         Self {
+            hscroll: false,
             vscroll: true,
             drag_to_scroll: true,
             stick_to_bottom: false,
@@ -200,7 +483,8 @@ impl Default for TableScrollOptions {
 /// You must pre-allocate all columns with [`Self::column`]/[`Self::columns`].
 ///
 /// If you have multiple [`Table`]:s in the same [`Ui`]
-/// you will need to give them unique id:s by surrounding them with [`Ui::push_id`].
+/// you will need to give them unique id:s with [`TableBuilder::id_source`]
+/// or by surrounding them with [`Ui::push_id`].
 ///
 /// ### Example
 /// ```
@@ -231,12 +515,16 @@ impl Default for TableScrollOptions {
 /// ```
 pub struct TableBuilder<'a> {
     ui: &'a mut Ui,
-    columns: Vec<Column>,
+    columns: Vec<Column<'a>>,
     striped: Option<bool>,
     resizable: bool,
     cell_layout: egui::Layout,
     scroll_options: TableScrollOptions,
     sense: egui::Sense,
+    frozen_columns: usize,
+    id_source: Option<egui::Id>,
+    reorderable: bool,
+    selection: Option<&'a mut TableSelection>,
 }
 
 impl<'a> TableBuilder<'a> {
@@ -250,9 +538,23 @@ impl<'a> TableBuilder<'a> {
             cell_layout,
             scroll_options: Default::default(),
             sense: egui::Sense::hover(),
+            frozen_columns: 0,
+            id_source: None,
+            reorderable: false,
+            selection: None,
         }
     }
 
+    /// Tell this table apart from other tables in the same [`Ui`].
+    ///
+    /// The column widths, order and sorting are stored in [`egui::Memory::data`] under this id,
+    /// so they are remembered between frames, and between runs if egui persistence is on.
+    #[inline]
+    pub fn id_source(mut self, id_source: impl std::hash::Hash) -> Self {
+        self.id_source = Some(egui::Id::new(id_source));
+        self
+    }
+
     /// Enable striped row background for improved readability.
     ///
     /// Default is whatever is in [`egui::Visuals::striped`].
@@ -269,6 +571,41 @@ impl<'a> TableBuilder<'a> {
         self
     }
 
+    /// Let the user select rows of the body, see [`TableSelection`].
+    ///
+    /// * Click a row to select it.
+    /// * Ctrl/cmd-click a row to add it to the selection, or remove it ([`TableSelection::multiple`] only).
+    /// * Shift-click a row to select all rows from the last clicked one ([`TableSelection::multiple`] only).
+    /// * Once the table has keyboard focus, the up/down arrow keys move the selection,
+    ///   and extend it while holding shift.
+    ///
+    /// The selected rows are highlighted, on top of the stripes.
+    /// You can override this with [`TableRow::set_selected`].
+    ///
+    /// ### Example
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// use egui_extras::{TableBuilder, TableSelection, Column};
+    /// # let mut selection = TableSelection::multiple();
+    /// TableBuilder::new(ui)
+    ///     .column(Column::remainder())
+    ///     .selection(&mut selection)
+    ///     .body(|body| {
+    ///         body.rows(18.0, 100, |mut row| {
+    ///             let row_index = row.index();
+    ///             row.col(|ui| {
+    ///                 ui.label(format!("Row {row_index}"));
+    ///             });
+    ///         });
+    ///     });
+    /// # });
+    /// ```
+    #[inline]
+    pub fn selection(mut self, selection: &'a mut TableSelection) -> Self {
+        self.selection = Some(selection);
+        self
+    }
+
     /// Make the columns resizable by dragging.
     ///
     /// You can set this for individual columns with [`Column::resizable`].
@@ -285,6 +622,63 @@ impl<'a> TableBuilder<'a> {
         self
     }
 
+    /// Let the user reorder the columns by dragging their headers (default: `false`).
+    ///
+    /// The new order is remembered, like the column widths.
+    /// You still add the cells in the order you added the columns with [`Self::column`].
+    #[inline]
+    pub fn reorderable(mut self, reorderable: bool) -> Self {
+        self.reorderable = reorderable;
+        self
+    }
+
+    /// Enable horizontal scrolling, for when the columns don't fit in the available width
+    /// (default: `false`).
+    ///
+    /// The header scrolls along with the body.
+    #[inline]
+    pub fn hscroll(mut self, hscroll: bool) -> Self {
+        self.scroll_options.hscroll = hscroll;
+        self
+    }
+
+    /// Keep the first `count` columns in view when the table scrolls horizontally,
+    /// like the frozen columns of a spreadsheet.
+    ///
+    /// This turns on [`Self::hscroll`].
+    ///
+    /// ### Example
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// use egui_extras::{TableBuilder, Column};
+    /// TableBuilder::new(ui)
+    ///     .column(Column::exact(80.0))
+    ///     .columns(Column::exact(120.0), 10)
+    ///     .freeze_columns(1)
+    ///     .body(|mut body| {
+    ///         body.rows(18.0, 100, |mut row| {
+    ///             let row_index = row.index();
+    ///             row.col(|ui| {
+    ///                 ui.label(format!("Row {row_index}"));
+    ///             });
+    ///             for col_index in 1..=10 {
+    ///                 row.col(|ui| {
+    ///                     ui.label(format!("Cell {col_index}"));
+    ///                 });
+    ///             }
+    ///         });
+    ///     });
+    /// # });
+    /// ```
+    #[inline]
+    pub fn freeze_columns(mut self, count: usize) -> Self {
+        self.frozen_columns = count;
+        if 0 < count {
+            self.scroll_options.hscroll = true;
+        }
+        self
+    }
+
     /// Enable vertical scrolling in body (default: `true`)
     #[inline]
     pub fn vscroll(mut self, vscroll: bool) -> Self {
@@ -385,16 +779,16 @@ impl<'a> TableBuilder<'a> {
 
     /// Allocate space for one column.
     #[inline]
-    pub fn column(mut self, column: Column) -> Self {
+    pub fn column(mut self, column: Column<'a>) -> Self {
         self.columns.push(column);
         self
     }
 
     /// Allocate space for several columns at once.
     #[inline]
-    pub fn columns(mut self, column: Column, count: usize) -> Self {
+    pub fn columns(mut self, column: Column<'a>, count: usize) -> Self {
         for _ in 0..count {
-            self.columns.push(column);
+            self.columns.push(column.clone());
         }
         self
     }
@@ -422,29 +816,48 @@ impl<'a> TableBuilder<'a> {
             cell_layout,
             scroll_options,
             sense,
+            frozen_columns,
+            id_source,
+            reorderable,
+            selection,
         } = self;
 
         let striped = striped.unwrap_or(ui.visuals().striped);
 
-        let state_id = ui.id().with("__table_state");
+        let state_id = table_state_id(ui, id_source);
 
         let initial_widths =
             to_sizing(&columns).to_lengths(available_width, ui.spacing().item_spacing.x);
         let mut max_used_widths = vec![0.0; initial_widths.len()];
-        let (had_state, state) = TableState::load(ui, initial_widths, state_id);
+        let (had_state, mut state) = TableState::load(ui, initial_widths, state_id);
         let is_first_frame = !had_state;
         let first_frame_auto_size_columns = is_first_frame && columns.iter().any(|c| c.is_auto());
 
         let table_top = ui.cursor().top();
 
-        // Hide first-frame-jitters when auto-sizing.
-        ui.add_visible_ui(!first_frame_auto_size_columns, |ui| {
+        let positions = ColumnPositions::new(
+            &state.column_order,
+            &state.column_widths,
+            ui.spacing().item_spacing.x,
+        );
+        let mut header_interaction = HeaderInteraction {
+            id: state_id.with("__table_header"),
+            reorderable,
+            sort: state.sort,
+            dragged: None,
+            cell_ranges: Vec::new(),
+        };
+
+        let add_header = |ui: &mut Ui, scroll_offset_x: f32| {
             let mut layout = StripLayout::new(ui, CellDirection::Horizontal, cell_layout, sense);
+            layout.frozen_columns = freeze(&layout, frozen_columns, &positions, scroll_offset_x);
             let mut response: Option<Response> = None;
             add_header_row(TableRow {
                 layout: &mut layout,
                 columns: &columns,
                 widths: &state.column_widths,
+                positions: &positions,
+                header: Some(&mut header_interaction),
                 max_used_widths: &mut max_used_widths,
                 row_index: 0,
                 col_index: 0,
@@ -455,8 +868,31 @@ impl<'a> TableBuilder<'a> {
                 response: &mut response,
             });
             layout.allocate_rect();
+        };
+
+        // Hide first-frame-jitters when auto-sizing.
+        ui.add_visible_ui(!first_frame_auto_size_columns, |ui| {
+            if scroll_options.hscroll {
+                // Follow the horizontal scrolling of the body:
+                ScrollArea::horizontal()
+                    .id_source("__table_header")
+                    .sync_with(state_id.with("__table_scroll"))
+                    .max_width(available_width)
+                    .auto_shrink(scroll_options.auto_shrink)
+                    .drag_to_scroll(scroll_options.drag_to_scroll)
+                    .scroll_bar_visibility(ScrollBarVisibility::AlwaysHidden)
+                    .show_viewport(ui, |ui, viewport| add_header(ui, viewport.min.x));
+            } else {
+                add_header(ui, 0.0);
+            }
         });
 
+        if header_interaction.reorder(&mut state.column_order) {
+            // The header was shown in the old order:
+            ui.ctx().request_repaint();
+        }
+        state.sort = header_interaction.sort;
+
         Table {
             ui,
             table_top,
@@ -471,6 +907,8 @@ impl<'a> TableBuilder<'a> {
             cell_layout,
             scroll_options,
             sense,
+            frozen_columns,
+            selection,
         }
     }
 
@@ -489,11 +927,15 @@ impl<'a> TableBuilder<'a> {
             cell_layout,
             scroll_options,
             sense,
+            frozen_columns,
+            id_source,
+            reorderable: _,
+            selection,
         } = self;
 
         let striped = striped.unwrap_or(ui.visuals().striped);
 
-        let state_id = ui.id().with("__table_state");
+        let state_id = table_state_id(ui, id_source);
 
         let initial_widths =
             to_sizing(&columns).to_lengths(available_width, ui.spacing().item_spacing.x);
@@ -518,6 +960,8 @@ impl<'a> TableBuilder<'a> {
             cell_layout,
             scroll_options,
             sense,
+            frozen_columns,
+            selection,
         }
         .body(add_body_contents);
     }
@@ -525,9 +969,24 @@ impl<'a> TableBuilder<'a> {
 
 // ----------------------------------------------------------------------------
 
+/// The id under which the [`TableState`] is stored.
+fn table_state_id(ui: &Ui, id_source: Option<egui::Id>) -> egui::Id {
+    id_source
+        .map_or_else(|| ui.id(), |id_source| ui.make_persistent_id(id_source))
+        .with("__table_state")
+}
+
 #[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
 struct TableState {
     column_widths: Vec<f32>,
+
+    /// The order the columns are shown in, as indices into [`Self::column_widths`].
+    #[serde(default)]
+    column_order: Vec<usize>,
+
+    /// The column the table is sorted by, if any.
+    #[serde(default)]
+    sort: Option<(usize, SortOrder)>,
 }
 
 impl TableState {
@@ -536,9 +995,22 @@ impl TableState {
         let rect = Rect::from_min_size(ui.available_rect_before_wrap().min, Vec2::ZERO);
         ui.ctx().check_for_id_clash(state_id, rect, "Table");
 
-        if let Some(state) = ui.data_mut(|d| d.get_persisted::<Self>(state_id)) {
+        let num_columns = default_widths.len();
+
+        if let Some(mut state) = ui.data_mut(|d| d.get_persisted::<Self>(state_id)) {
             // make sure that the stored widths aren't out-dated
-            if state.column_widths.len() == default_widths.len() {
+            if state.column_widths.len() == num_columns {
+                let mut sorted_order = state.column_order.clone();
+                sorted_order.sort_unstable();
+                if !sorted_order.iter().copied().eq(0..num_columns) {
+                    state.column_order = (0..num_columns).collect();
+                }
+                if state
+                    .sort
+                    .map_or(false, |(column, _)| num_columns <= column)
+                {
+                    state.sort = None;
+                }
                 return (true, state);
             }
         }
@@ -547,6 +1019,8 @@ impl TableState {
             false,
             Self {
                 column_widths: default_widths,
+                column_order: (0..num_columns).collect(),
+                sort: None,
             },
         )
     }
@@ -556,6 +1030,186 @@ impl TableState {
     }
 }
 
+/// Where the columns are shown, which is not the order they were added in
+/// if the user has reordered them.
+struct ColumnPositions {
+    /// Which column is shown where, from left to right.
+    order: Vec<usize>,
+
+    /// Where each column is shown, from left to right.
+    display_index: Vec<usize>,
+
+    /// How far the left edge of each column is from the left edge of the table.
+    left: Vec<f32>,
+
+    /// The width of all columns, including the spacing after each.
+    width: f32,
+}
+
+impl ColumnPositions {
+    fn new(order: &[usize], widths: &[f32], spacing_x: f32) -> Self {
+        let mut display_index = vec![0; widths.len()];
+        let mut left = vec![0.0; widths.len()];
+        let mut x = 0.0;
+        for (i, &column) in order.iter().enumerate() {
+            display_index[column] = i;
+            left[column] = x;
+            x += widths[column] + spacing_x;
+        }
+        Self {
+            order: order.to_vec(),
+            display_index,
+            left,
+            width: x,
+        }
+    }
+
+    /// Where the column shown at `display_index` starts, or where the last column ends.
+    fn left_of_display_index(&self, display_index: usize) -> f32 {
+        self.order
+            .get(display_index)
+            .map_or(self.width, |&column| self.left[column])
+    }
+}
+
+/// Where the first `count` columns go when the table has scrolled `scroll_offset_x` to the right.
+fn freeze(
+    layout: &StripLayout<'_>,
+    count: usize,
+    positions: &ColumnPositions,
+    scroll_offset_x: f32,
+) -> FrozenColumns {
+    let spacing_x = layout.ui.spacing().item_spacing.x;
+    FrozenColumns {
+        count,
+        offset_x: scroll_offset_x,
+        right: layout.rect.left() + scroll_offset_x + positions.left_of_display_index(count)
+            - 0.5 * spacing_x,
+    }
+}
+
+/// Sorting and reordering by clicking and dragging the header cells.
+struct HeaderInteraction {
+    id: egui::Id,
+    reorderable: bool,
+    sort: Option<(usize, SortOrder)>,
+
+    /// The column being dragged, and where the pointer is.
+    dragged: Option<(usize, f32)>,
+
+    /// Where each header cell is, in screen space.
+    cell_ranges: Vec<(usize, Rangef)>,
+}
+
+impl HeaderInteraction {
+    fn interact(&mut self, ui: &Ui, col_index: usize, sortable: bool, rect: Rect) {
+        self.cell_ranges.push((col_index, rect.x_range()));
+
+        let sense = match (sortable, self.reorderable) {
+            (true, true) => egui::Sense::click_and_drag(),
+            (true, false) => egui::Sense::click(),
+            (false, true) => egui::Sense::drag(),
+            (false, false) => return,
+        };
+        let response = ui.interact(rect, self.id.with(col_index), sense);
+
+        if response.clicked() {
+            let order = match self.sort {
+                Some((column, order)) if column == col_index => order.reversed(),
+                _ => SortOrder::Ascending,
+            };
+            self.sort = Some((col_index, order));
+        }
+        if let Some((column, order)) = self.sort {
+            if column == col_index {
+                paint_sort_indicator(ui, rect, order);
+            }
+        }
+
+        if response.dragged() {
+            ui.ctx().set_cursor_icon(egui::CursorIcon::Grabbing);
+            if let Some(pointer_pos) = response.interact_pointer_pos() {
+                self.dragged = Some((col_index, pointer_pos.x));
+            }
+        } else if response.hovered() && self.reorderable {
+            ui.ctx().set_cursor_icon(egui::CursorIcon::Grab);
+        }
+    }
+
+    /// Move the dragged column to where it was dragged.
+    ///
+    /// Returns `true` if the order changed.
+    fn reorder(&self, column_order: &mut Vec<usize>) -> bool {
+        let Some((dragged, pointer_x)) = self.dragged else {
+            return false;
+        };
+        let range_of = |column: usize| {
+            self.cell_ranges
+                .iter()
+                .find(|(c, _)| *c == column)
+                .map(|(_, range)| *range)
+        };
+        let Some(dragged_range) = range_of(dragged) else {
+            return false;
+        };
+        let Some(&(target, target_range)) = self
+            .cell_ranges
+            .iter()
+            .find(|(c, range)| *c != dragged && range.contains(pointer_x))
+        else {
+            return false;
+        };
+
+        // Only swap places once the pointer would be over the dragged column after the swap,
+        // or the columns would keep swapping back and forth:
+        let dragged_width = dragged_range.span();
+        let far_enough = if dragged_range.min < target_range.min {
+            target_range.max - dragged_width <= pointer_x
+        } else {
+            pointer_x <= target_range.min + dragged_width
+        };
+        if !far_enough {
+            return false;
+        }
+
+        let from = column_order.iter().position(|&c| c == dragged);
+        let to = column_order.iter().position(|&c| c == target);
+        if let (Some(from), Some(to)) = (from, to) {
+            let column = column_order.remove(from);
+            column_order.insert(to, column);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// An arrow at the right of a header cell, showing which way the table is sorted.
+fn paint_sort_indicator(ui: &Ui, rect: Rect, order: SortOrder) {
+    let size = 0.5 * ui.spacing().icon_width;
+    let icon_rect = Rect::from_center_size(
+        egui::pos2(rect.right() - size, rect.center().y),
+        Vec2::splat(size),
+    );
+    let points = match order {
+        SortOrder::Ascending => vec![
+            icon_rect.center_top(),
+            icon_rect.right_bottom(),
+            icon_rect.left_bottom(),
+        ],
+        SortOrder::Descending => vec![
+            icon_rect.left_top(),
+            icon_rect.right_top(),
+            icon_rect.center_bottom(),
+        ],
+    };
+    ui.painter().add(egui::Shape::convex_polygon(
+        points,
+        ui.visuals().strong_text_color(),
+        egui::Stroke::NONE,
+    ));
+}
+
 // ----------------------------------------------------------------------------
 
 /// Table struct which can construct a [`TableBody`].
@@ -565,7 +1219,7 @@ pub struct Table<'a> {
     ui: &'a mut Ui,
     table_top: f32,
     state_id: egui::Id,
-    columns: Vec<Column>,
+    columns: Vec<Column<'a>>,
     available_width: f32,
     state: TableState,
 
@@ -580,6 +1234,10 @@ pub struct Table<'a> {
     scroll_options: TableScrollOptions,
 
     sense: egui::Sense,
+
+    frozen_columns: usize,
+
+    selection: Option<&'a mut TableSelection>,
 }
 
 impl<'a> Table<'a> {
@@ -590,6 +1248,16 @@ impl<'a> Table<'a> {
         self.ui
     }
 
+    /// The column the table is sorted by, and in which order, if any.
+    ///
+    /// Set by clicking the header of a [`Column::sortable`].
+    /// [`TableBody::rows`] and [`TableBody::heterogeneous_rows`] are sorted for you,
+    /// but you can use this to sort the rows you add with [`TableBody::row`] yourself.
+    #[inline]
+    pub fn sort(&self) -> Option<(usize, SortOrder)> {
+        self.state.sort
+    }
+
     /// Create table body after adding a header row
     pub fn body<F>(self, add_body_contents: F)
     where
@@ -609,9 +1277,12 @@ impl<'a> Table<'a> {
             cell_layout,
             scroll_options,
             sense,
+            frozen_columns,
+            selection,
         } = self;
 
         let TableScrollOptions {
+            hscroll,
             vscroll,
             drag_to_scroll,
             stick_to_bottom,
@@ -623,9 +1294,28 @@ impl<'a> Table<'a> {
             scroll_bar_visibility,
         } = scroll_options;
 
+        let selection_id = state_id.with("__table_selection");
+        let mut sense = sense;
+        let mut scroll_to_row = scroll_to_row;
+        let selection_is_some = selection.is_some();
+        if selection_is_some {
+            sense = sense.union(egui::Sense::click());
+            let event_filter = egui::EventFilter {
+                vertical_arrows: true,
+                ..Default::default()
+            };
+            ui.memory_mut(|mem| mem.set_focus_lock_filter(selection_id, event_filter));
+            if scroll_to_row.is_none() {
+                // Follow the arrow keys:
+                scroll_to_row = ui
+                    .data_mut(|data| data.remove_temp::<usize>(selection_id))
+                    .map(|row| (row, None));
+            }
+        }
+
         let cursor_position = ui.cursor().min;
 
-        let mut scroll_area = ScrollArea::new([false, vscroll])
+        let mut scroll_area = ScrollArea::new([hscroll, vscroll])
             .auto_shrink(true)
             .drag_to_scroll(drag_to_scroll)
             .stick_to_bottom(stick_to_bottom)
@@ -637,12 +1327,19 @@ impl<'a> Table<'a> {
         if let Some(scroll_offset_y) = scroll_offset_y {
             scroll_area = scroll_area.vertical_scroll_offset(scroll_offset_y);
         }
+        if hscroll {
+            scroll_area = scroll_area.sync_with(state_id.with("__table_scroll"));
+        }
+
+        let spacing_x = ui.spacing().item_spacing.x;
+        let positions = ColumnPositions::new(&state.column_order, &state.column_widths, spacing_x);
 
         let columns_ref = &columns;
         let widths_ref = &state.column_widths;
+        let positions_ref = &positions;
         let max_used_widths_ref = &mut max_used_widths;
 
-        scroll_area.show(ui, move |ui| {
+        let scroll_output = scroll_area.show_viewport(ui, move |ui, viewport| {
             let mut scroll_to_y_range = None;
 
             let clip_rect = ui.clip_rect();
@@ -653,12 +1350,22 @@ impl<'a> Table<'a> {
                 let hovered_row_index =
                     ui.data_mut(|data| data.remove_temp::<usize>(hovered_row_index_id));
 
-                let layout = StripLayout::new(ui, CellDirection::Horizontal, cell_layout, sense);
+                let sticky_rows_id = state_id.with("__table_sticky_rows");
+                let prev_sticky_row_tops = ui
+                    .data(|data| data.get_temp::<Vec<f32>>(sticky_rows_id))
+                    .unwrap_or_default();
+
+                let mut layout =
+                    StripLayout::new(ui, CellDirection::Horizontal, cell_layout, sense);
+                layout.frozen_columns =
+                    freeze(&layout, frozen_columns, positions_ref, viewport.min.x);
+                let visible_top = layout.rect.top() + viewport.min.y;
 
                 add_body_contents(TableBody {
                     layout,
                     columns: columns_ref,
                     widths: widths_ref,
+                    positions: positions_ref,
                     max_used_widths: max_used_widths_ref,
                     striped,
                     row_index: 0,
@@ -668,6 +1375,15 @@ impl<'a> Table<'a> {
                     scroll_to_y_range: &mut scroll_to_y_range,
                     hovered_row_index,
                     hovered_row_index_id,
+                    visible_top,
+                    sticky_row_tops: Vec::new(),
+                    prev_sticky_row_tops,
+                    sticky_rows_id,
+                    selection,
+                    selection_id,
+                    num_rows: 0,
+                    sort: state.sort,
+                    row_order: None,
                 });
 
                 if scroll_to_row.is_some() && scroll_to_y_range.is_none() {
@@ -686,13 +1402,30 @@ impl<'a> Table<'a> {
 
         let bottom = ui.min_rect().bottom();
 
-        let spacing_x = ui.spacing().item_spacing.x;
+        if selection_is_some {
+            ui.interact(
+                scroll_output.inner_rect,
+                selection_id,
+                egui::Sense::focusable_noninteractive(),
+            );
+        }
+
+        let scroll_offset_x = scroll_output.state.offset.x;
+        let frozen_right =
+            cursor_position.x + positions.left_of_display_index(frozen_columns) - spacing_x * 0.5;
         let mut x = cursor_position.x - spacing_x * 0.5;
-        for (i, column_width) in state.column_widths.iter_mut().enumerate() {
+        for (display_index, &i) in state.column_order.iter().enumerate() {
+            let column_width = &mut state.column_widths[i];
             let column = &columns[i];
             let column_is_resizable = column.resizable.unwrap_or(resizable);
             let width_range = column.width_range;
 
+            if let InitialColumnSize::Relative(fraction) = column.initial_width {
+                if !column_is_resizable {
+                    *column_width = fraction * available_width;
+                }
+            }
+
             if !column.clip {
                 // Unless we clip we don't want to shrink below the
                 // size that was actually used:
@@ -700,7 +1433,7 @@ impl<'a> Table<'a> {
             }
             *column_width = width_range.clamp(*column_width);
 
-            let is_last_column = i + 1 == columns.len();
+            let is_last_column = display_index + 1 == columns.len();
 
             if is_last_column && column.initial_width == InitialColumnSize::Remainder {
                 // If the last column is 'remainder', then let it fill the remainder!
@@ -715,11 +1448,18 @@ impl<'a> Table<'a> {
 
             x += *column_width + spacing_x;
 
+            // Where the resize handle is, after scrolling:
+            let is_frozen = display_index < frozen_columns;
+            let handle_x = if is_frozen { x } else { x - scroll_offset_x };
+            let handle_is_visible = is_frozen
+                || (frozen_right < handle_x && handle_x <= scroll_output.inner_rect.right());
+
             if column.is_auto() && (first_frame_auto_size_columns || !column_is_resizable) {
                 *column_width = max_used_widths[i];
                 *column_width = width_range.clamp(*column_width);
-            } else if column_is_resizable {
-                let column_resize_id = ui.id().with("resize_column").with(i);
+            } else if column_is_resizable && handle_is_visible {
+                let x = handle_x;
+                let column_resize_id = state_id.with("resize_column").with(i);
 
                 let mut p0 = egui::pos2(x, table_top);
                 let mut p1 = egui::pos2(x, bottom);
@@ -789,11 +1529,13 @@ impl<'a> Table<'a> {
 pub struct TableBody<'a> {
     layout: StripLayout<'a>,
 
-    columns: &'a [Column],
+    columns: &'a [Column<'a>],
 
     /// Current column widths.
     widths: &'a [f32],
 
+    positions: &'a ColumnPositions,
+
     /// Accumulated maximum used widths for each column.
     max_used_widths: &'a mut [f32],
 
@@ -813,6 +1555,34 @@ pub struct TableBody<'a> {
 
     /// Used to store the hovered row index between frames.
     hovered_row_index_id: egui::Id,
+
+    /// The top of the visible part of the body, in screen space.
+    visible_top: f32,
+
+    /// Where each [`Self::sticky_row`] starts, relative to the top of the body.
+    sticky_row_tops: Vec<f32>,
+
+    /// [`Self::sticky_row_tops`] of the previous frame,
+    /// so a sticky row knows where the next one will push it away.
+    prev_sticky_row_tops: Vec<f32>,
+
+    /// Used to store the sticky row tops between frames.
+    sticky_rows_id: egui::Id,
+
+    selection: Option<&'a mut TableSelection>,
+
+    /// Has keyboard focus when the user navigates the selection with the arrow keys.
+    selection_id: egui::Id,
+
+    /// How many rows were added by [`Self::rows`] or [`Self::heterogeneous_rows`],
+    /// for moving the selection with the arrow keys.
+    num_rows: usize,
+
+    /// The column the table is sorted by, if any.
+    sort: Option<(usize, SortOrder)>,
+
+    /// The order [`Self::rows`] or [`Self::heterogeneous_rows`] show the rows in, if sorted.
+    row_order: Option<RowOrder>,
 }
 
 impl<'a> TableBody<'a> {
@@ -850,20 +1620,24 @@ impl<'a> TableBody<'a> {
     pub fn row(&mut self, height: f32, add_row_content: impl FnOnce(TableRow<'a, '_>)) {
         let mut response: Option<Response> = None;
         let top_y = self.layout.cursor.y;
+        let selected = self.is_selected(self.row_index);
         add_row_content(TableRow {
             layout: &mut self.layout,
             columns: self.columns,
             widths: self.widths,
+            positions: self.positions,
+            header: None,
             max_used_widths: self.max_used_widths,
             row_index: self.row_index,
             col_index: 0,
             height,
             striped: self.striped && self.row_index % 2 == 0,
             hovered: self.hovered_row_index == Some(self.row_index),
-            selected: false,
+            selected,
             response: &mut response,
         });
         self.capture_hover_state(&response, self.row_index);
+        self.capture_selection(&response, self.row_index);
         let bottom_y = self.layout.cursor.y;
 
         if Some(self.row_index) == self.scroll_to_row {
@@ -873,6 +1647,58 @@ impl<'a> TableBody<'a> {
         self.row_index += 1;
     }
 
+    /// Add a row that stays pinned to the top of the body while the rows after it scroll by,
+    /// e.g. the header of a group of rows.
+    ///
+    /// It is pushed away by the next sticky row, and covers the rows scrolling underneath it.
+    /// Use it together with [`Self::row`].
+    ///
+    /// ### Example
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// use egui_extras::{TableBuilder, Column};
+    /// TableBuilder::new(ui)
+    ///     .column(Column::remainder())
+    ///     .body(|mut body| {
+    ///         for group in ["Fruit", "Vegetables"] {
+    ///             body.sticky_row(20.0, |mut row| {
+    ///                 row.col(|ui| {
+    ///                     ui.strong(group);
+    ///                 });
+    ///             });
+    ///             for i in 0..20 {
+    ///                 body.row(18.0, |mut row| {
+    ///                     row.col(|ui| {
+    ///                         ui.label(format!("{group} {i}"));
+    ///                     });
+    ///                 });
+    ///             }
+    ///         }
+    ///     });
+    /// # });
+    /// ```
+    pub fn sticky_row(&mut self, height: f32, add_row_content: impl FnOnce(TableRow<'a, '_>)) {
+        let spacing_y = self.layout.ui.spacing().item_spacing.y;
+        let body_top = self.layout.rect.top();
+        let top_y = self.layout.cursor.y;
+
+        let sticky_index = self.sticky_row_tops.len();
+        self.sticky_row_tops.push(top_y - body_top);
+
+        let mut pinned_y = top_y.max(self.visible_top);
+        if let Some(next_top) = self.prev_sticky_row_tops.get(sticky_index + 1) {
+            // Make room for the next sticky row:
+            pinned_y = pinned_y.min(body_top + next_top - height - spacing_y);
+        }
+        let pinned_y = pinned_y.max(top_y);
+
+        self.layout.offset_y = pinned_y - top_y;
+        self.layout.clip_top = f32::NEG_INFINITY;
+        self.row(height, add_row_content);
+        self.layout.offset_y = 0.0;
+        self.layout.clip_top = pinned_y + height + 0.5 * spacing_y;
+    }
+
     /// Add many rows with same height.
     ///
     /// Is a lot more performant than adding each individual row as non visible rows must not be rendered.
@@ -905,8 +1731,11 @@ impl<'a> TableBody<'a> {
     ) {
         let spacing = self.layout.ui.spacing().item_spacing;
         let row_height_with_spacing = row_height_sans_spacing + spacing.y;
+        self.num_rows = total_rows;
+        self.sort_rows(total_rows);
 
         if let Some(scroll_to_row) = self.scroll_to_row {
+            let scroll_to_row = self.position_of(scroll_to_row);
             let scroll_to_row = scroll_to_row.at_most(total_rows.saturating_sub(1)) as f32;
             *self.scroll_to_y_range = Some(Rangef::new(
                 self.layout.cursor.y + scroll_to_row * row_height_with_spacing,
@@ -929,22 +1758,27 @@ impl<'a> TableBody<'a> {
             ((scroll_offset_y + max_height) / row_height_with_spacing).ceil() as usize + 1;
         let max_row = max_row.min(total_rows);
 
-        for row_index in min_row..max_row {
+        for position in min_row..max_row {
+            let row_index = self.row_at(position);
             let mut response: Option<Response> = None;
+            let selected = self.is_selected(row_index);
             add_row_content(TableRow {
                 layout: &mut self.layout,
                 columns: self.columns,
                 widths: self.widths,
+                positions: self.positions,
+                header: None,
                 max_used_widths: self.max_used_widths,
                 row_index,
                 col_index: 0,
                 height: row_height_sans_spacing,
-                striped: self.striped && (row_index + self.row_index) % 2 == 0,
+                striped: self.striped && (position + self.row_index) % 2 == 0,
                 hovered: self.hovered_row_index == Some(row_index),
-                selected: false,
+                selected,
                 response: &mut response,
             });
             self.capture_hover_state(&response, row_index);
+            self.capture_selection(&response, row_index);
         }
 
         if total_rows - max_row > 0 {
@@ -982,12 +1816,35 @@ impl<'a> TableBody<'a> {
     /// # });
     /// ```
     pub fn heterogeneous_rows(
+        mut self,
+        heights: impl Iterator<Item = f32>,
+        add_row_content: impl FnMut(TableRow<'_, '_>),
+    ) {
+        if self.sort.is_some() {
+            let heights: Vec<f32> = heights.collect();
+            self.sort_rows(heights.len());
+            let sorted_heights = self
+                .row_order
+                .as_ref()
+                .map(|order| order.rows.iter().map(|&i| heights[i]).collect());
+            let heights = sorted_heights.unwrap_or(heights);
+            self.heterogeneous_rows_in_order(heights.into_iter(), add_row_content);
+        } else {
+            self.heterogeneous_rows_in_order(heights, add_row_content);
+        }
+    }
+
+    /// [`Self::heterogeneous_rows`], with the `heights` in the order the rows are shown.
+    fn heterogeneous_rows_in_order(
         mut self,
         heights: impl Iterator<Item = f32>,
         mut add_row_content: impl FnMut(TableRow<'_, '_>),
     ) {
         let spacing = self.layout.ui.spacing().item_spacing;
-        let mut enumerated_heights = heights.enumerate();
+        let mut num_rows = 0;
+        let mut enumerated_heights = heights
+            .enumerate()
+            .inspect(|(position, _)| num_rows = position + 1);
 
         let max_height = self.end_y - self.start_y;
         let scroll_offset_y = self.scroll_offset_y() as f64;
@@ -997,7 +1854,8 @@ impl<'a> TableBody<'a> {
         let mut cursor_y: f64 = 0.0;
 
         // Skip the invisible rows, and populate the first non-virtual row.
-        for (row_index, row_height) in &mut enumerated_heights {
+        for (position, row_height) in &mut enumerated_heights {
+            let row_index = self.row_at(position);
             let old_cursor_y = cursor_y;
             cursor_y += (row_height + spacing.y) as f64;
 
@@ -1012,42 +1870,51 @@ impl<'a> TableBody<'a> {
                 // This row is visible:
                 self.add_buffer(old_cursor_y as f32); // skip all the invisible rows
                 let mut response: Option<Response> = None;
+                let selected = self.is_selected(row_index);
                 add_row_content(TableRow {
                     layout: &mut self.layout,
                     columns: self.columns,
                     widths: self.widths,
+                    positions: self.positions,
+                    header: None,
                     max_used_widths: self.max_used_widths,
                     row_index,
                     col_index: 0,
                     height: row_height,
-                    striped: self.striped && (row_index + self.row_index) % 2 == 0,
+                    striped: self.striped && (position + self.row_index) % 2 == 0,
                     hovered: self.hovered_row_index == Some(row_index),
-                    selected: false,
+                    selected,
                     response: &mut response,
                 });
                 self.capture_hover_state(&response, row_index);
+                self.capture_selection(&response, row_index);
                 break;
             }
         }
 
         // populate visible rows:
-        for (row_index, row_height) in &mut enumerated_heights {
+        for (position, row_height) in &mut enumerated_heights {
+            let row_index = self.row_at(position);
             let top_y = cursor_y;
             let mut response: Option<Response> = None;
+            let selected = self.is_selected(row_index);
             add_row_content(TableRow {
                 layout: &mut self.layout,
                 columns: self.columns,
                 widths: self.widths,
+                positions: self.positions,
+                header: None,
                 max_used_widths: self.max_used_widths,
                 row_index,
                 col_index: 0,
                 height: row_height,
-                striped: self.striped && (row_index + self.row_index) % 2 == 0,
+                striped: self.striped && (position + self.row_index) % 2 == 0,
                 hovered: self.hovered_row_index == Some(row_index),
-                selected: false,
+                selected,
                 response: &mut response,
             });
             self.capture_hover_state(&response, row_index);
+            self.capture_selection(&response, row_index);
             cursor_y += (row_height + spacing.y) as f64;
 
             if Some(row_index) == self.scroll_to_row {
@@ -1064,7 +1931,8 @@ impl<'a> TableBody<'a> {
 
         // calculate height below the visible table range:
         let mut height_below_visible: f64 = 0.0;
-        for (row_index, row_height) in enumerated_heights {
+        for (position, row_height) in enumerated_heights {
+            let row_index = self.row_at(position);
             height_below_visible += (row_height + spacing.y) as f64;
 
             let top_y = cursor_y;
@@ -1076,6 +1944,7 @@ impl<'a> TableBody<'a> {
                 ));
             }
         }
+        self.num_rows = num_rows;
 
         if self.scroll_to_row.is_some() && self.scroll_to_y_range.is_none() {
             // Catch desire to scroll past the end:
@@ -1090,6 +1959,29 @@ impl<'a> TableBody<'a> {
         }
     }
 
+    /// Sort the rows by the sorted [`Column::sortable`], if any.
+    fn sort_rows(&mut self, num_rows: usize) {
+        let columns = self.columns;
+        let compare = self
+            .sort
+            .and_then(|(column, order)| Some((columns.get(column)?.compare.as_ref()?, order)));
+        self.row_order = compare.map(|(compare, order)| RowOrder::sorted(num_rows, compare, order));
+    }
+
+    /// The index of the row shown at this position.
+    fn row_at(&self, position: usize) -> usize {
+        self.row_order
+            .as_ref()
+            .map_or(position, |order| order.row_at(position))
+    }
+
+    /// The position the row is shown at.
+    fn position_of(&self, row_index: usize) -> usize {
+        self.row_order
+            .as_ref()
+            .map_or(row_index, |order| order.position_of(row_index))
+    }
+
     // Create a table row buffer of the given height to represent the non-visible portion of the
     // table.
     fn add_buffer(&mut self, height: f32) {
@@ -1106,11 +1998,83 @@ impl<'a> TableBody<'a> {
                 .data_mut(|data| data.insert_temp(self.hovered_row_index_id, row_index));
         }
     }
+
+    fn is_selected(&self, row_index: usize) -> bool {
+        self.selection
+            .as_ref()
+            .map_or(false, |selection| selection.is_selected(row_index))
+    }
+
+    // Update the selection if the just created row was clicked.
+    fn capture_selection(&mut self, response: &Option<Response>, row_index: usize) {
+        let Some(selection) = self.selection.as_deref_mut() else {
+            return;
+        };
+        if response.as_ref().map_or(false, |r| r.clicked()) {
+            let ui = &mut self.layout.ui;
+            let modifiers = ui.input(|i| i.modifiers);
+            selection.click(row_index, modifiers, self.row_order.as_ref());
+            ui.memory_mut(|mem| mem.request_focus(self.selection_id));
+            ui.ctx().request_repaint();
+        }
+    }
+
+    /// Move the selection with the arrow keys.
+    fn navigate_selection(&mut self) {
+        let Some(selection) = self.selection.as_deref_mut() else {
+            return;
+        };
+        let ui = &mut self.layout.ui;
+        let num_rows = self.num_rows.max(self.row_index);
+        if num_rows == 0 || !ui.memory(|mem| mem.has_focus(self.selection_id)) {
+            return;
+        }
+
+        let (up, down, shift) = ui.input(|i| {
+            (
+                i.key_pressed(egui::Key::ArrowUp),
+                i.key_pressed(egui::Key::ArrowDown),
+                i.modifiers.shift,
+            )
+        });
+        if up == down {
+            return;
+        }
+
+        let order = self.row_order.as_ref();
+        let position = selection
+            .cursor
+            .map_or(0, |cursor| {
+                let position = order.map_or(cursor, |order| order.position_of(cursor));
+                if down {
+                    position + 1
+                } else {
+                    position.saturating_sub(1)
+                }
+            })
+            .min(num_rows - 1);
+        let row_index = order.map_or(position, |order| order.row_at(position));
+        selection.move_cursor(row_index, shift, order);
+
+        // Scroll to it next frame:
+        ui.data_mut(|data| data.insert_temp(self.selection_id, row_index));
+        ui.ctx().request_repaint();
+    }
 }
 
 impl<'a> Drop for TableBody<'a> {
     fn drop(&mut self) {
         self.layout.allocate_rect();
+        self.navigate_selection();
+
+        if self.sticky_row_tops != self.prev_sticky_row_tops {
+            // The sticky rows need to know where the next one is:
+            self.layout.ui.ctx().request_repaint();
+            let sticky_row_tops = std::mem::take(&mut self.sticky_row_tops);
+            self.layout
+                .ui
+                .data_mut(|data| data.insert_temp(self.sticky_rows_id, sticky_row_tops));
+        }
     }
 }
 
@@ -1118,8 +2082,12 @@ impl<'a> Drop for TableBody<'a> {
 /// Is created by [`TableRow`] for each created [`TableBody::row`] or each visible row in rows created by calling [`TableBody::rows`].
 pub struct TableRow<'a, 'b> {
     layout: &'b mut StripLayout<'a>,
-    columns: &'b [Column],
+    columns: &'b [Column<'b>],
     widths: &'b [f32],
+    positions: &'b ColumnPositions,
+
+    /// Set for the header row.
+    header: Option<&'b mut HeaderInteraction>,
 
     /// grows during building with the maximum widths
     max_used_widths: &'b mut [f32],
@@ -1156,6 +2124,11 @@ impl<'a, 'b> TableRow<'a, 'b> {
             8.0 // anything will look wrong, so pick something that is obviously wrong
         };
 
+        if let Some(left) = self.positions.left.get(col_index) {
+            // The columns may have been reordered:
+            self.layout.cursor.x = self.layout.rect.left() + left;
+        }
+
         let width = CellSize::Absolute(width);
         let height = CellSize::Absolute(self.height);
 
@@ -1164,6 +2137,11 @@ impl<'a, 'b> TableRow<'a, 'b> {
             striped: self.striped,
             hovered: self.hovered,
             selected: self.selected,
+            frozen: self
+                .positions
+                .display_index
+                .get(col_index)
+                .map_or(false, |&i| i < self.layout.frozen_columns.count),
         };
 
         let (used_rect, response) = self.layout.add(
@@ -1174,6 +2152,14 @@ impl<'a, 'b> TableRow<'a, 'b> {
             add_cell_contents,
         );
 
+        if let Some(header) = &mut self.header {
+            let sortable = self
+                .columns
+                .get(col_index)
+                .map_or(false, |c| c.compare.is_some());
+            header.interact(self.layout.ui, col_index, sortable, response.rect);
+        }
+
         if let Some(max_w) = self.max_used_widths.get_mut(col_index) {
             *max_w = max_w.max(used_rect.width());
         }
@@ -1203,6 +2189,9 @@ impl<'a, 'b> TableRow<'a, 'b> {
     }
 
     /// Returns the index of the row.
+    ///
+    /// When the table is sorted by a [`Column::sortable`], this is still the index into your data,
+    /// not the position the row is shown at.
     #[inline]
     pub fn index(&self) -> usize {
         self.row_index
@@ -1221,3 +2210,252 @@ impl<'a, 'b> Drop for TableRow<'a, 'b> {
         self.layout.end_line();
     }
 }
+
+#[test]
+fn test_frozen_columns_and_sticky_rows() {
+    use egui::test_harness::Harness;
+    use std::cell::Cell;
+
+    let group_rects = [Cell::new(Rect::NOTHING), Cell::new(Rect::NOTHING)];
+    let frozen_rect = Cell::new(Rect::NOTHING);
+    let scrolled_rect = Cell::new(Rect::NOTHING);
+
+    let mut harness = Harness::new_ui(|ui| {
+        TableBuilder::new(ui)
+            .columns(Column::exact(100.0), 10)
+            .freeze_columns(1)
+            .max_scroll_height(300.0)
+            .body(|mut body| {
+                for (group, group_rect) in group_rects.iter().enumerate() {
+                    body.sticky_row(20.0, |mut row| {
+                        group_rect.set(
+                            row.col(|ui| {
+                                ui.label(format!("Group {group}"));
+                            })
+                            .1
+                            .rect,
+                        );
+                    });
+                    for i in 0..50 {
+                        body.row(20.0, |mut row| {
+                            for col in 0..10 {
+                                let rect = row
+                                    .col(|ui| {
+                                        ui.label(format!("{i}, {col}"));
+                                    })
+                                    .1
+                                    .rect;
+                                if group == 0 && i == 20 && col == 0 {
+                                    frozen_rect.set(rect);
+                                } else if group == 0 && i == 20 && col == 5 {
+                                    scrolled_rect.set(rect);
+                                }
+                            }
+                        });
+                    }
+                }
+            });
+    });
+    harness.set_size(egui::vec2(600.0, 400.0));
+    harness.run();
+
+    let group_top = group_rects[0].get().top();
+    let frozen_before = frozen_rect.get();
+    let scrolled_before = scrolled_rect.get();
+
+    // Scroll down and to the right:
+    harness.hover(group_rects[0].get().center() + egui::vec2(150.0, 0.0));
+    harness.push_event(egui::Event::Scroll(egui::vec2(-200.0, -200.0)));
+    harness.run();
+
+    // The frozen column only scrolls vertically:
+    assert_eq!(frozen_rect.get().left(), frozen_before.left());
+    assert!(frozen_rect.get().top() < frozen_before.top());
+    assert!(scrolled_rect.get().left() < scrolled_before.left());
+
+    // The header of the first group stays at the top:
+    assert_eq!(group_rects[0].get().top(), group_top);
+
+    // Scroll to where the second group reaches the top, pushing the first one away:
+    harness.push_event(egui::Event::Scroll(egui::vec2(0.0, -1100.0)));
+    harness.run();
+    assert_eq!(group_rects[1].get().top(), group_top);
+    assert!(group_rects[0].get().bottom() <= group_rects[1].get().top());
+}
+
+#[test]
+fn test_sort_and_reorder_columns() {
+    use egui::test_harness::Harness;
+    use std::cell::{Cell, RefCell};
+
+    let data = RefCell::new(vec![("b", 3), ("c", 1), ("a", 2)]);
+    let shown_rows = RefCell::new(Vec::new());
+    let header_rects = [(); 3].map(|_| Cell::new(Rect::NOTHING));
+    let cell_rects = [(); 3].map(|_| Cell::new(Rect::NOTHING));
+    let current_sort = Cell::new(None);
+
+    let mut harness = Harness::new_ui(|ui| {
+        let data = data.borrow();
+        let table = TableBuilder::new(ui)
+            .id_source("my_table")
+            .column(Column::initial(100.0).sortable_by_key(|row| data[row].0))
+            .column(Column::initial(100.0).sortable(|a, b| data[a].1.cmp(&data[b].1)))
+            .column(Column::initial(100.0))
+            .reorderable(true)
+            .header(20.0, |mut header| {
+                for (name, rect) in ["A", "B", "C"].into_iter().zip(&header_rects) {
+                    rect.set(
+                        header
+                            .col(|ui| {
+                                ui.strong(name);
+                            })
+                            .1
+                            .rect,
+                    );
+                }
+            });
+        current_sort.set(table.sort());
+        shown_rows.borrow_mut().clear();
+        table.body(|body| {
+            body.rows(20.0, data.len(), |mut row| {
+                shown_rows.borrow_mut().push(row.index());
+                for rect in &cell_rects {
+                    rect.set(
+                        row.col(|ui| {
+                            ui.label("cell");
+                        })
+                        .1
+                        .rect,
+                    );
+                }
+            });
+        });
+    });
+    assert_eq!(*shown_rows.borrow(), vec![0, 1, 2]);
+
+    // Clicking a header sorts by it, and clicking it again reverses the order:
+    harness.click(header_rects[0].get().center());
+    assert_eq!(current_sort.get(), Some((0, SortOrder::Ascending)));
+    assert_eq!(*shown_rows.borrow(), vec![2, 0, 1]);
+    harness.click(header_rects[0].get().center());
+    assert_eq!(current_sort.get(), Some((0, SortOrder::Descending)));
+    assert_eq!(*shown_rows.borrow(), vec![1, 0, 2]);
+    harness.click(header_rects[1].get().center());
+    assert_eq!(current_sort.get(), Some((1, SortOrder::Ascending)));
+    assert_eq!(*shown_rows.borrow(), vec![1, 2, 0]);
+
+    // A column without a comparator can't be sorted by:
+    harness.click(header_rects[2].get().center());
+    assert_eq!(current_sort.get(), Some((1, SortOrder::Ascending)));
+
+    // The rows stay sorted when the data changes:
+    data.borrow_mut().push(("d", 0));
+    harness.run();
+    assert_eq!(*shown_rows.borrow(), vec![3, 1, 2, 0]);
+
+    // Drag the first column to the end:
+    harness.drag(
+        header_rects[0].get().center(),
+        header_rects[2].get().right_center() - egui::vec2(5.0, 0.0),
+    );
+    assert!(cell_rects[1].get().left() < cell_rects[2].get().left());
+    assert!(cell_rects[2].get().left() < cell_rects[0].get().left());
+    assert_eq!(
+        header_rects[0].get().x_range(),
+        cell_rects[0].get().x_range()
+    );
+    assert_eq!(current_sort.get(), Some((1, SortOrder::Ascending)));
+}
+
+#[test]
+fn test_row_selection() {
+    use egui::{test_harness::Harness, Key, Modifiers};
+    use std::cell::{Cell, RefCell};
+
+    let selection = RefCell::new(TableSelection::multiple());
+    let row_rects = [(); 10].map(|_| Cell::new(Rect::NOTHING));
+
+    let mut harness = Harness::new_ui(|ui| {
+        let mut selection = selection.borrow_mut();
+        TableBuilder::new(ui)
+            .column(Column::remainder())
+            .selection(&mut selection)
+            .body(|body| {
+                body.rows(20.0, row_rects.len(), |mut row| {
+                    let rect = &row_rects[row.index()];
+                    rect.set(
+                        row.col(|ui| {
+                            ui.label("row");
+                        })
+                        .1
+                        .rect,
+                    );
+                });
+            });
+    });
+    let selected = || selection.borrow().selected_rows().collect::<Vec<_>>();
+
+    harness.click(row_rects[2].get().center());
+    assert_eq!(selected(), vec![2]);
+
+    harness.set_modifiers(Modifiers::COMMAND);
+    harness.click(row_rects[5].get().center());
+    assert_eq!(selected(), vec![2, 5]);
+
+    // A range from the last clicked row:
+    harness.set_modifiers(Modifiers::SHIFT);
+    harness.click(row_rects[7].get().center());
+    assert_eq!(selected(), vec![5, 6, 7]);
+
+    // The arrow keys move the selection, and extend it while holding shift:
+    harness.set_modifiers(Modifiers::NONE);
+    harness.press_key(Key::ArrowDown);
+    assert_eq!(selected(), vec![8]);
+    harness.set_modifiers(Modifiers::SHIFT);
+    harness.press_key(Key::ArrowUp);
+    harness.press_key(Key::ArrowUp);
+    assert_eq!(selected(), vec![6, 7, 8]);
+
+    // Only one row at a time in single selection mode:
+    *selection.borrow_mut() = TableSelection::single();
+    harness.set_modifiers(Modifiers::COMMAND);
+    harness.click(row_rects[1].get().center());
+    harness.click(row_rects[3].get().center());
+    assert_eq!(selected(), vec![3]);
+}
+
+#[test]
+fn test_relative_column() {
+    use egui::test_harness::Harness;
+    use std::cell::Cell;
+
+    let width = Cell::new(0.0);
+    let mut harness = Harness::new_ui(|ui| {
+        TableBuilder::new(ui)
+            .vscroll(false)
+            .column(Column::relative(0.3).at_least(120.0).at_most(400.0))
+            .column(Column::remainder())
+            .body(|mut body| {
+                body.row(20.0, |mut row| {
+                    width.set(
+                        row.col(|ui| {
+                            ui.label("30%");
+                        })
+                        .1
+                        .rect
+                        .width(),
+                    );
+                    row.col(|ui| {
+                        ui.label("Rest");
+                    });
+                });
+            });
+    });
+
+    // The column follows the available width, within its range:
+    for (screen_width, expected) in [(1000.0, 0.3 * 984.0), (300.0, 120.0), (2000.0, 400.0)] {
+        harness.set_size(egui::vec2(screen_width, 400.0));
+        harness.run();
+        assert!((width.get() - expected).abs() < 0.5, "{}", width.get());
+    }
+}
@@ -0,0 +1,726 @@
+//! A tree of expandable nodes, like the files and directories of a file system.
+
+use std::hash::Hash;
+
+use egui::{
+    collapsing_header::paint_default_icon, pos2, vec2, Id, IdSet, Key, Modifiers, Rect, Response,
+    Sense, TextStyle, Ui, WidgetInfo, WidgetText, WidgetType,
+};
+
+/// Where dragged nodes were dropped, relative to the node they were dropped on.
+///
+/// See [`TreeView::reorderable`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DropPosition {
+    /// Right before the node, as its sibling.
+    Before,
+
+    /// Right after the node, as its sibling.
+    After,
+
+    /// Inside the node, as its last child.
+    ///
+    /// Only for nodes added with [`TreeViewNodes::dir`].
+    Inside,
+}
+
+/// The user dropped some nodes of a [`TreeView`] onto another node, see [`TreeView::reorderable`].
+///
+/// It is up to you to move the nodes in your data.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TreeViewDrop<N> {
+    /// The dragged nodes, in the order they are shown.
+    ///
+    /// These are the selected nodes if the user dragged one of them,
+    /// otherwise just the one the user dragged.
+    /// The children of a dragged node are not included.
+    pub nodes: Vec<N>,
+
+    /// The node they were dropped on.
+    pub target: N,
+
+    /// Where to put them, relative to [`Self::target`].
+    pub position: DropPosition,
+}
+
+/// What the user did with a [`TreeView`], returned by [`TreeView::show`].
+pub struct TreeViewResponse<N> {
+    /// The response of the whole tree.
+    pub response: Response,
+
+    /// The selected nodes that were shown this frame, in the order they are shown.
+    ///
+    /// The children of collapsed nodes are not shown, so they are not included.
+    pub selected: Vec<N>,
+
+    /// Did the user change the selection this frame?
+    pub selection_changed: bool,
+
+    /// Did the user drop some nodes this frame?
+    pub dropped: Option<TreeViewDrop<N>>,
+}
+
+// ----------------------------------------------------------------------------
+
+/// A tree of nodes that can be expanded and collapsed, like the files and directories of a file system.
+///
+/// You add the nodes each frame with [`TreeViewNodes::dir`] and [`TreeViewNodes::leaf`],
+/// identifying each node with something that implements [`Hash`], e.g. its path.
+/// The children of a node are only added when it is expanded,
+/// so you can load them lazily.
+///
+/// Which nodes are expanded and selected is remembered in [`egui::Memory`],
+/// under the id of the tree.
+///
+/// The user can:
+/// * Click the arrow of a node, or double-click the node, to expand or collapse it.
+/// * Click a node to select it, ctrl/cmd-click to add it to the selection,
+///   and shift-click to select all nodes from the last clicked one (see [`Self::multi_select`]).
+/// * Once the tree has keyboard focus, move through the nodes with the up and down arrow keys
+///   (holding shift to extend the selection),
+///   expand a node or go to its first child with the right arrow key,
+///   and collapse a node or go to its parent with the left arrow key.
+/// * Drag nodes onto other nodes, if the tree is [`Self::reorderable`].
+///
+/// ### Example
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// use egui_extras::TreeView;
+/// let response = TreeView::new("files").show(ui, |tree| {
+///     tree.dir("src", "src", |tree| {
+///         // Only called when `src` is expanded:
+///         for file in ["lib.rs", "main.rs"] {
+///             tree.leaf(file, file);
+///         }
+///     });
+///     tree.leaf("Cargo.toml", "Cargo.toml");
+/// });
+/// if response.selection_changed {
+///     println!("Selected: {:?}", response.selected);
+/// }
+/// # });
+/// ```
+#[must_use = "You should call .show()"]
+pub struct TreeView {
+    id_source: Id,
+    row_height: Option<f32>,
+    indent: Option<f32>,
+    multi_select: bool,
+    reorderable: bool,
+}
+
+impl TreeView {
+    /// The id of the tree is derived from `id_source`, and must be unique within the [`Ui`].
+    pub fn new(id_source: impl Hash) -> Self {
+        Self {
+            id_source: Id::new(id_source),
+            row_height: None,
+            indent: None,
+            multi_select: true,
+            reorderable: false,
+        }
+    }
+
+    /// The height of each node.
+    ///
+    /// Default: [`egui::style::Spacing::interact_size`]`.y`.
+    #[inline]
+    pub fn row_height(mut self, row_height: f32) -> Self {
+        self.row_height = Some(row_height);
+        self
+    }
+
+    /// How far the children of a node are indented.
+    ///
+    /// Default: [`egui::style::Spacing::indent`].
+    #[inline]
+    pub fn indent(mut self, indent: f32) -> Self {
+        self.indent = Some(indent);
+        self
+    }
+
+    /// Can the user select more than one node, with ctrl/cmd-click and shift-click?
+    ///
+    /// Default: `true`.
+    #[inline]
+    pub fn multi_select(mut self, multi_select: bool) -> Self {
+        self.multi_select = multi_select;
+        self
+    }
+
+    /// Can the user drag nodes onto other nodes?
+    ///
+    /// While dragging, a line shows where the nodes would be dropped,
+    /// before or after another node, or a frame around the node if they would be dropped inside it.
+    /// The drop is reported in [`TreeViewResponse::dropped`],
+    /// and it is up to you to actually move the nodes.
+    ///
+    /// Default: `false`.
+    #[inline]
+    pub fn reorderable(mut self, reorderable: bool) -> Self {
+        self.reorderable = reorderable;
+        self
+    }
+
+    pub fn show<N: Clone + Hash>(
+        self,
+        ui: &mut Ui,
+        add_nodes: impl FnOnce(&mut TreeViewNodes<'_, N>),
+    ) -> TreeViewResponse<N> {
+        let Self {
+            id_source,
+            row_height,
+            indent,
+            multi_select,
+            reorderable,
+        } = self;
+
+        let id = ui.make_persistent_id(id_source);
+        let state = ui
+            .data_mut(|d| d.get_persisted::<TreeViewState>(id))
+            .unwrap_or_default();
+
+        let event_filter = egui::EventFilter {
+            horizontal_arrows: true,
+            vertical_arrows: true,
+            ..Default::default()
+        };
+        ui.memory_mut(|mem| mem.set_focus_lock_filter(id, event_filter));
+        let has_focus = ui.memory(|mem| mem.has_focus(id));
+
+        let row_height = row_height.unwrap_or(ui.spacing().interact_size.y);
+        let indent = indent.unwrap_or(ui.spacing().indent);
+
+        let egui::InnerResponse {
+            inner: nodes,
+            response,
+        } = ui.scope(|ui| {
+            ui.spacing_mut().item_spacing.y = 0.0;
+            let mut nodes = TreeViewNodes {
+                ui,
+                id,
+                row_height,
+                indent,
+                reorderable,
+                has_focus,
+                state,
+                rows: Vec::new(),
+                depth: 0,
+                in_dragged: false,
+                clicked: None,
+                dropped: None,
+            };
+            add_nodes(&mut nodes);
+            let TreeViewNodes {
+                state,
+                rows,
+                clicked,
+                dropped,
+                ..
+            } = nodes;
+            (state, rows, clicked, dropped)
+        });
+        let (mut state, rows, clicked, dropped) = nodes;
+
+        ui.interact(response.rect, id, egui::Sense::focusable_noninteractive());
+
+        let ids: Vec<Id> = rows.iter().map(|row| row.id).collect();
+        let old_selection = state.selected.clone();
+
+        if let Some((index, modifiers)) = clicked {
+            state.click(&ids, index, modifiers, multi_select);
+            ui.memory_mut(|mem| mem.request_focus(id));
+        }
+        if has_focus {
+            navigate(ui, &mut state, &rows, &ids, multi_select);
+        }
+
+        let selection_changed = state.selected != old_selection;
+        if selection_changed {
+            ui.ctx().request_repaint();
+        }
+
+        let dropped = dropped.map(|(dragged_ids, target, position)| TreeViewDrop {
+            nodes: rows
+                .iter()
+                .filter(|row| dragged_ids.contains(&row.id))
+                .map(|row| row.node.clone())
+                .collect(),
+            target: rows[target].node.clone(),
+            position,
+        });
+
+        let selected = rows
+            .iter()
+            .filter(|row| state.selected.contains(&row.id))
+            .map(|row| row.node.clone())
+            .collect();
+
+        ui.data_mut(|d| d.insert_persisted(id, state));
+
+        TreeViewResponse {
+            response,
+            selected,
+            selection_changed,
+            dropped,
+        }
+    }
+}
+
+/// Move the cursor with the arrow keys.
+fn navigate<N>(
+    ui: &Ui,
+    state: &mut TreeViewState,
+    rows: &[Row<N>],
+    ids: &[Id],
+    multi_select: bool,
+) {
+    let (key, shift) = ui.input(|i| {
+        let key = [
+            Key::ArrowUp,
+            Key::ArrowDown,
+            Key::ArrowLeft,
+            Key::ArrowRight,
+        ]
+        .into_iter()
+        .find(|&key| i.key_pressed(key));
+        (key, i.modifiers.shift)
+    });
+    let Some(key) = key else {
+        return;
+    };
+    if rows.is_empty() {
+        return;
+    }
+
+    let cursor = state
+        .cursor
+        .and_then(|cursor| ids.iter().position(|&id| id == cursor));
+    let Some(cursor) = cursor else {
+        state.select(ids[0]);
+        state.scroll_to_cursor = true;
+        ui.ctx().request_repaint();
+        return;
+    };
+
+    let row = &rows[cursor];
+    let move_to = match key {
+        Key::ArrowUp => cursor.checked_sub(1),
+        Key::ArrowDown => Some(cursor + 1).filter(|&next| next < rows.len()),
+        Key::ArrowRight => {
+            if row.is_dir && !row.expanded {
+                state.expanded.insert(row.id);
+                None
+            } else {
+                // Go to the first child:
+                Some(cursor + 1)
+                    .filter(|&next| rows.get(next).map_or(false, |r| row.depth < r.depth))
+            }
+        }
+        Key::ArrowLeft => {
+            if row.expanded {
+                state.expanded.remove(&row.id);
+                None
+            } else {
+                // Go to the parent:
+                rows[..cursor].iter().rposition(|r| r.depth < row.depth)
+            }
+        }
+        _ => None,
+    };
+
+    if let Some(index) = move_to {
+        let extend = shift && matches!(key, Key::ArrowUp | Key::ArrowDown);
+        if extend {
+            state.click(ids, index, Modifiers::SHIFT, multi_select);
+        } else {
+            state.select(ids[index]);
+        }
+        state.scroll_to_cursor = true;
+    }
+    ui.ctx().request_repaint();
+}
+
+// ----------------------------------------------------------------------------
+
+/// Used to add the nodes of a [`TreeView`], see [`TreeView::show`].
+pub struct TreeViewNodes<'ui, N> {
+    ui: &'ui mut Ui,
+    id: Id,
+    row_height: f32,
+    indent: f32,
+    reorderable: bool,
+    has_focus: bool,
+
+    state: TreeViewState,
+
+    /// The nodes shown so far, in order.
+    rows: Vec<Row<N>>,
+
+    /// How deeply nested the next node is.
+    depth: usize,
+
+    /// Are we adding the children of a dragged node?
+    /// They can't be dropped into themselves.
+    in_dragged: bool,
+
+    /// The index of the node the user clicked this frame, if any.
+    clicked: Option<(usize, Modifiers)>,
+
+    /// The ids of the dropped nodes, and the index of the node they were dropped on.
+    dropped: Option<(Vec<Id>, usize, DropPosition)>,
+}
+
+struct Row<N> {
+    node: N,
+    id: Id,
+    depth: usize,
+    is_dir: bool,
+
+    /// Are the children of this node shown?
+    expanded: bool,
+}
+
+/// The payload of a drag-and-drop in a [`TreeView`].
+struct DraggedNodes {
+    tree_id: Id,
+    ids: Vec<Id>,
+}
+
+impl<'ui, N: Clone + Hash> TreeViewNodes<'ui, N> {
+    /// Add a node that can have children.
+    ///
+    /// `add_children` is only called when the node is expanded.
+    pub fn dir(
+        &mut self,
+        node: N,
+        label: impl Into<WidgetText>,
+        add_children: impl FnOnce(&mut Self),
+    ) -> Response {
+        let (response, expanded) = self.row(node, label.into(), true);
+        if expanded {
+            let in_dragged = self.in_dragged;
+            self.in_dragged |= self.is_dragged(response.id);
+            self.depth += 1;
+            add_children(self);
+            self.depth -= 1;
+            self.in_dragged = in_dragged;
+        }
+        response
+    }
+
+    /// Add a node without children.
+    pub fn leaf(&mut self, node: N, label: impl Into<WidgetText>) -> Response {
+        self.row(node, label.into(), false).0
+    }
+
+    /// Is this node selected?
+    pub fn is_selected(&self, node: &N) -> bool {
+        self.state.selected.contains(&self.id.with(node))
+    }
+
+    /// Is this node expanded?
+    pub fn is_expanded(&self, node: &N) -> bool {
+        self.state.expanded.contains(&self.id.with(node))
+    }
+
+    /// Expand or collapse a node, e.g. to reveal a node the user searched for.
+    pub fn set_expanded(&mut self, node: &N, expanded: bool) {
+        let id = self.id.with(node);
+        if expanded {
+            self.state.expanded.insert(id);
+        } else {
+            self.state.expanded.remove(&id);
+        }
+    }
+
+    /// The [`Ui`] the nodes are added to, e.g. to add something between them.
+    pub fn ui_mut(&mut self) -> &mut Ui {
+        self.ui
+    }
+
+    fn is_dragged(&self, id: Id) -> bool {
+        egui::DragAndDrop::payload::<DraggedNodes>(self.ui.ctx())
+            .map_or(false, |dragged| dragged.ids.contains(&id))
+    }
+
+    /// Add a node, and return its response and if it is expanded.
+    fn row(&mut self, node: N, label: WidgetText, is_dir: bool) -> (Response, bool) {
+        let id = self.id.with(&node);
+        let index = self.rows.len();
+        let ui = &mut *self.ui;
+
+        let (rect, _) =
+            ui.allocate_exact_size(vec2(ui.available_width(), self.row_height), Sense::hover());
+        let sense = if self.reorderable {
+            Sense::click_and_drag()
+        } else {
+            Sense::click()
+        };
+        let response = ui.interact(rect, id, sense);
+
+        let icon_width = ui.spacing().icon_width;
+        let icon_rect = Rect::from_center_size(
+            pos2(
+                rect.left() + self.depth as f32 * self.indent + 0.5 * icon_width,
+                rect.center().y,
+            ),
+            vec2(icon_width, icon_width),
+        );
+
+        let mut expanded = is_dir && self.state.expanded.contains(&id);
+        let icon_response =
+            is_dir.then(|| ui.interact(icon_rect, id.with("toggle"), Sense::click()));
+        if let Some(icon_response) = &icon_response {
+            if icon_response.clicked() || response.double_clicked() {
+                expanded = !expanded;
+                if expanded {
+                    self.state.expanded.insert(id);
+                } else {
+                    self.state.expanded.remove(&id);
+                }
+            }
+        }
+
+        if response.clicked() {
+            self.clicked = Some((index, ui.input(|i| i.modifiers)));
+        }
+
+        if self.reorderable {
+            if response.drag_started() {
+                let ids = if self.state.selected.contains(&id) {
+                    self.state.selected.iter().copied().collect()
+                } else {
+                    vec![id]
+                };
+                response.dnd_set_drag_payload(DraggedNodes {
+                    tree_id: self.id,
+                    ids,
+                });
+            }
+
+            if let Some(dragged) = response.dnd_hover_payload::<DraggedNodes>() {
+                let pointer_y = ui.input(|i| i.pointer.interact_pos()).map(|pos| pos.y);
+                if let Some(pointer_y) = pointer_y {
+                    if dragged.tree_id == self.id && !self.in_dragged && !dragged.ids.contains(&id)
+                    {
+                        let position = drop_position(rect, pointer_y, is_dir);
+                        paint_drop_indicator(ui, rect, icon_rect.left(), position);
+                        if response.dnd_release_payload::<DraggedNodes>().is_some() {
+                            self.dropped = Some((dragged.ids.clone(), index, position));
+                        }
+                    }
+                }
+            }
+        }
+
+        if self.state.scroll_to_cursor && self.state.cursor == Some(id) {
+            response.scroll_to_me(None);
+            self.state.scroll_to_cursor = false;
+        }
+
+        let selected = self.state.selected.contains(&id);
+        let text_left = icon_rect.right() + ui.spacing().icon_spacing;
+        let galley =
+            label.into_galley(ui, Some(false), rect.right() - text_left, TextStyle::Button);
+        response.widget_info(|| {
+            WidgetInfo::selected(WidgetType::SelectableLabel, selected, galley.text())
+        });
+
+        if ui.is_rect_visible(rect) {
+            let visuals = ui.style().interact_selectable(&response, selected);
+            if selected || response.hovered() || response.highlighted() {
+                ui.painter()
+                    .rect_filled(rect, visuals.rounding, visuals.weak_bg_fill);
+            }
+            if self.has_focus && self.state.cursor == Some(id) {
+                ui.painter()
+                    .rect_stroke(rect, visuals.rounding, ui.visuals().selection.stroke);
+            }
+
+            if let Some(icon_response) = &icon_response {
+                let openness = if expanded { 1.0 } else { 0.0 };
+                paint_default_icon(ui, openness, icon_response);
+            }
+
+            let text_pos = pos2(text_left, rect.center().y - 0.5 * galley.size().y);
+            ui.painter().galley(text_pos, galley, visuals.text_color());
+        }
+
+        self.rows.push(Row {
+            node,
+            id,
+            depth: self.depth,
+            is_dir,
+            expanded,
+        });
+
+        (response, expanded)
+    }
+}
+
+/// Where something dropped at `pointer_y` on the node with the given `rect` goes.
+fn drop_position(rect: Rect, pointer_y: f32, is_dir: bool) -> DropPosition {
+    let t = (pointer_y - rect.top()) / rect.height();
+    if is_dir {
+        if t < 0.25 {
+            DropPosition::Before
+        } else if t < 0.75 {
+            DropPosition::Inside
+        } else {
+            DropPosition::After
+        }
+    } else if t < 0.5 {
+        DropPosition::Before
+    } else {
+        DropPosition::After
+    }
+}
+
+fn paint_drop_indicator(ui: &Ui, rect: Rect, left: f32, position: DropPosition) {
+    let stroke = ui.visuals().selection.stroke;
+    let x_range = left..=rect.right();
+    match position {
+        DropPosition::Before => {
+            ui.painter().hline(x_range, rect.top(), stroke);
+        }
+        DropPosition::After => {
+            ui.painter().hline(x_range, rect.bottom(), stroke);
+        }
+        DropPosition::Inside => {
+            let rect = Rect::from_x_y_ranges(x_range, rect.y_range());
+            ui.painter()
+                .rect_stroke(rect, ui.visuals().widgets.hovered.rounding, stroke);
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// The state of a [`TreeView`] that is remembered between frames.
+#[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize)]
+struct TreeViewState {
+    expanded: IdSet,
+    selected: IdSet,
+
+    /// Where a range selected with shift starts.
+    anchor: Option<Id>,
+
+    /// The node that was last clicked, or moved to with the arrow keys.
+    cursor: Option<Id>,
+
+    /// Scroll to the cursor when it is shown next.
+    #[serde(skip)]
+    scroll_to_cursor: bool,
+}
+
+impl TreeViewState {
+    fn select(&mut self, id: Id) {
+        self.selected.clear();
+        self.selected.insert(id);
+        self.anchor = Some(id);
+        self.cursor = Some(id);
+    }
+
+    /// The user clicked the node at `index` of the shown nodes `ids`.
+    fn click(&mut self, ids: &[Id], index: usize, modifiers: Modifiers, multi_select: bool) {
+        let id = ids[index];
+        if multi_select && modifiers.shift {
+            let anchor = self
+                .anchor
+                .and_then(|anchor| ids.iter().position(|&id| id == anchor))
+                .unwrap_or(index);
+            if !modifiers.command {
+                self.selected.clear();
+            }
+            self.selected
+                .extend(&ids[anchor.min(index)..=anchor.max(index)]);
+            self.cursor = Some(id);
+        } else if multi_select && modifiers.command {
+            if !self.selected.remove(&id) {
+                self.selected.insert(id);
+            }
+            self.anchor = Some(id);
+            self.cursor = Some(id);
+        } else {
+            self.select(id);
+        }
+    }
+}
+
+#[test]
+fn test_tree_view() {
+    use egui::test_harness::Harness;
+    use std::cell::{Cell, RefCell};
+
+    let rects = RefCell::new(std::collections::HashMap::new());
+    let shown = RefCell::new(Vec::new());
+    let selected = RefCell::new(Vec::new());
+    let dropped = RefCell::new(None);
+    let children_added = Cell::new(0);
+
+    let mut harness = Harness::new_ui(|ui| {
+        let mut rects = rects.borrow_mut();
+        let response = TreeView::new("tree").reorderable(true).show(ui, |tree| {
+            let mut add = |name: &'static str, response: Response| {
+                rects.insert(name, response.rect);
+            };
+            let response = tree.dir("a", "a", |tree| {
+                children_added.set(children_added.get() + 1);
+                add("a1", tree.leaf("a1", "a1"));
+                add("a2", tree.leaf("a2", "a2"));
+            });
+            add("a", response);
+            let response = tree.dir("b", "b", |tree| {
+                add("b1", tree.leaf("b1", "b1"));
+            });
+            add("b", response);
+            add("c", tree.leaf("c", "c"));
+            *shown.borrow_mut() = tree.rows.iter().map(|row| row.node).collect::<Vec<_>>();
+        });
+        *selected.borrow_mut() = response.selected;
+        if response.dropped.is_some() {
+            *dropped.borrow_mut() = response.dropped;
+        }
+    });
+    let rect = |name: &str| rects.borrow()[name];
+
+    // The children are only added when expanded:
+    assert_eq!(*shown.borrow(), vec!["a", "b", "c"]);
+    assert_eq!(children_added.get(), 0);
+    let icon_pos = rect("a").left_center() + vec2(5.0, 0.0);
+    harness.click(icon_pos);
+    assert_eq!(*shown.borrow(), vec!["a", "a1", "a2", "b", "c"]);
+    assert!(0 < children_added.get());
+    assert!(selected.borrow().is_empty());
+
+    harness.click(rect("a1").center());
+    harness.set_modifiers(Modifiers::SHIFT);
+    harness.click(rect("b").center());
+    assert_eq!(*selected.borrow(), vec!["a1", "a2", "b"]);
+
+    // Keyboard navigation:
+    harness.set_modifiers(Modifiers::NONE);
+    harness.press_key(Key::ArrowDown);
+    assert_eq!(*selected.borrow(), vec!["c"]);
+    harness.press_key(Key::ArrowUp);
+    harness.press_key(Key::ArrowRight);
+    assert_eq!(*shown.borrow(), vec!["a", "a1", "a2", "b", "b1", "c"]);
+    harness.press_key(Key::ArrowRight);
+    assert_eq!(*selected.borrow(), vec!["b1"]);
+    harness.press_key(Key::ArrowLeft);
+    harness.press_key(Key::ArrowLeft);
+    assert_eq!(*selected.borrow(), vec!["b"]);
+    assert_eq!(*shown.borrow(), vec!["a", "a1", "a2", "b", "c"]);
+
+    // Drag `c` to right before `a1`:
+    harness.drag(rect("c").center(), rect("a1").center_top() + vec2(0.0, 2.0));
+    assert_eq!(
+        dropped.take(),
+        Some(TreeViewDrop {
+            nodes: vec!["c"],
+            target: "a1",
+            position: DropPosition::Before,
+        })
+    );
+}
@@ -115,6 +115,7 @@ impl EguiGlow {
         let dimensions: [u32; 2] = window.inner_size().into();
         self.painter
             .paint_primitives(dimensions, pixels_per_point, &clipped_primitives);
+        self.egui_ctx.recycle_primitives(clipped_primitives);
 
         for id in textures_delta.free.drain(..) {
             self.painter.free_texture(id);
@@ -44,10 +44,41 @@ impl BoxSpread {
             upper_whisker,
         }
     }
+
+    /// Compute the quartiles and whiskers of these values, along with the outliers.
+    ///
+    /// The quartiles are interpolated linearly between the values.
+    /// The whiskers reach the furthest values within 1.5 times the interquartile range from the box;
+    /// the values beyond them are the outliers.
+    /// Values that aren't finite are ignored. Without any values, everything is zero.
+    pub fn from_values(values: impl IntoIterator<Item = f64>) -> (Self, Vec<f64>) {
+        let mut values: Vec<f64> = values.into_iter().filter(|v| v.is_finite()).collect();
+        if values.is_empty() {
+            return (Self::new(0.0, 0.0, 0.0, 0.0, 0.0), vec![]);
+        }
+        values.sort_by(f64::total_cmp);
+
+        let quantile = |p: f64| {
+            let h = (values.len() - 1) as f64 * p;
+            let (below, above) = (values[h.floor() as usize], values[h.ceil() as usize]);
+            below + (h - h.floor()) * (above - below)
+        };
+        let (quartile1, median, quartile3) = (quantile(0.25), quantile(0.5), quantile(0.75));
+        let reach = 1.5 * (quartile3 - quartile1);
+        let is_outlier = |v: f64| v < quartile1 - reach || quartile3 + reach < v;
+
+        let mut inliers = values.iter().copied().filter(|&v| !is_outlier(v));
+        let lower_whisker = inliers.next().unwrap_or(quartile1);
+        let upper_whisker = inliers.last().unwrap_or(lower_whisker).max(lower_whisker);
+        let outliers = values.iter().copied().filter(|&v| is_outlier(v)).collect();
+
+        let spread = Self::new(lower_whisker, quartile1, median, quartile3, upper_whisker);
+        (spread, outliers)
+    }
 }
 
 /// A box in a [`BoxPlot`] diagram. This is a low level graphical element; it will not compute quartiles and whiskers,
-/// letting one use their preferred formula. Use [`Points`][`super::Points`] to draw the outliers.
+/// letting one use their preferred formula, unless you create it with [`Self::from_values`].
 #[derive(Clone, Debug, PartialEq)]
 pub struct BoxElem {
     /// Name of plot element in the diagram (annotated by default formatter).
@@ -73,6 +104,9 @@ pub struct BoxElem {
 
     /// Fill color
     pub fill: Color32,
+
+    /// See [`Self::outliers`].
+    outliers: Vec<f64>,
 }
 
 impl BoxElem {
@@ -89,9 +123,26 @@ impl BoxElem {
             whisker_width: 0.15,
             stroke: Stroke::new(1.0, Color32::TRANSPARENT),
             fill: Color32::TRANSPARENT,
+            outliers: Vec::new(),
         }
     }
 
+    /// Create a box element of these values, with the quartiles, whiskers and outliers
+    /// computed by [`BoxSpread::from_values`].
+    ///
+    /// ```
+    /// use egui_plot::BoxElem;
+    ///
+    /// let elem = BoxElem::from_values(0.0, [1.0, 2.0, 3.0, 4.0, 5.0, 40.0]);
+    /// assert_eq!(elem.spread.median, 3.5);
+    /// assert_eq!(elem.spread.upper_whisker, 5.0);
+    /// assert_eq!(elem.outlier_values(), [40.0]);
+    /// ```
+    pub fn from_values(argument: f64, values: impl IntoIterator<Item = f64>) -> Self {
+        let (spread, outliers) = BoxSpread::from_values(values);
+        Self::new(argument, spread).outliers(outliers)
+    }
+
     /// Name of this box element.
     #[allow(clippy::needless_pass_by_value)]
     #[inline]
@@ -114,6 +165,19 @@ impl BoxElem {
         self
     }
 
+    /// Set the values outside the whiskers, drawn as small circles.
+    #[inline]
+    pub fn outliers(mut self, outliers: impl Into<Vec<f64>>) -> Self {
+        self.outliers = outliers.into();
+        self
+    }
+
+    /// The values outside the whiskers, see [`Self::outliers`].
+    #[inline]
+    pub fn outlier_values(&self) -> &[f64] {
+        &self.outliers
+    }
+
     /// Set the box width.
     #[inline]
     pub fn box_width(mut self, width: f64) -> Self {
@@ -217,6 +281,11 @@ impl BoxElem {
                 shapes.push(low_whisker_end);
             }
         }
+
+        for &outlier in &self.outliers {
+            let center = transform.position_from_point(&self.point_at(self.argument, outlier));
+            shapes.push(Shape::circle_stroke(center, 2.5, stroke));
+        }
     }
 
     pub(super) fn add_rulers_and_text(
@@ -242,13 +311,19 @@ impl RectElement for BoxElem {
 
     fn bounds_min(&self) -> PlotPoint {
         let argument = self.argument - self.box_width.max(self.whisker_width) / 2.0;
-        let value = self.spread.lower_whisker;
+        let value = self
+            .outliers
+            .iter()
+            .fold(self.spread.lower_whisker, |min, &v| min.min(v));
         self.point_at(argument, value)
     }
 
     fn bounds_max(&self) -> PlotPoint {
         let argument = self.argument + self.box_width.max(self.whisker_width) / 2.0;
-        let value = self.spread.upper_whisker;
+        let value = self
+            .outliers
+            .iter()
+            .fold(self.spread.upper_whisker, |max, &v| max.max(v));
         self.point_at(argument, value)
     }
 
@@ -279,7 +354,7 @@ impl RectElement for BoxElem {
         let y_decimals = ((-scale.abs().log10()).ceil().at_least(0.0) as usize)
             .at_most(6)
             .at_least(1);
-        format!(
+        let mut text = format!(
             "Max = {max:.decimals$}\
              \nQuartile 3 = {q3:.decimals$}\
              \nMedian = {med:.decimals$}\
@@ -291,6 +366,45 @@ impl RectElement for BoxElem {
             q1 = self.spread.quartile1,
             min = self.spread.lower_whisker,
             decimals = y_decimals
-        )
+        );
+        if !self.outliers.is_empty() {
+            text += &format!("\nOutliers = {}", self.outliers.len());
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spread_of_no_values() {
+        let zero = BoxSpread::new(0.0, 0.0, 0.0, 0.0, 0.0);
+        assert_eq!(BoxSpread::from_values([]), (zero, vec![]));
+        assert_eq!(
+            BoxSpread::from_values([f64::NAN, f64::INFINITY]),
+            (zero, vec![])
+        );
+    }
+
+    #[test]
+    fn test_spread_of_equal_values() {
+        let (spread, outliers) = BoxSpread::from_values([2.0; 5]);
+        assert_eq!(spread, BoxSpread::new(2.0, 2.0, 2.0, 2.0, 2.0));
+        assert!(outliers.is_empty());
+    }
+
+    #[test]
+    fn test_whiskers_when_everything_outside_the_box_is_an_outlier() {
+        // The box has no height, so every value off it is an outlier,
+        // and the whiskers shrink onto the box instead of reaching for them:
+        let (spread, outliers) = BoxSpread::from_values([100.0, 5.0, 5.0, 5.0, 5.0, 5.0, -100.0]);
+        assert_eq!(spread, BoxSpread::new(5.0, 5.0, 5.0, 5.0, 5.0));
+        assert_eq!(outliers, [-100.0, 100.0]);
+
+        let elem = BoxElem::from_values(0.0, [100.0, 5.0, 5.0, 5.0, 5.0, 5.0, -100.0]);
+        assert_eq!(elem.bounds_min().y, -100.0);
+        assert_eq!(elem.bounds_max().y, 100.0);
     }
 }
@@ -0,0 +1,270 @@
+use egui::emath::NumExt as _;
+use egui::epaint::{Color32, RectShape, Rounding, Shape, Stroke};
+
+use crate::{CandlestickChart, Cursor, PlotPoint, PlotTransform};
+
+use super::{add_rulers_and_text, highlighted_color, Orientation, PlotConfig, RectElement};
+
+/// How to draw the elements of a [`CandlestickChart`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CandleStyle {
+    /// A filled body between open and close, with a wick from low to high.
+    #[default]
+    Candlestick,
+
+    /// A line from low to high, with a tick to the left at open and one to the right at close.
+    Ohlc,
+}
+
+/// One period (e.g. a day) in a [`CandlestickChart`]: the open, high, low and close values.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Candle {
+    /// Name of plot element in the diagram (annotated by default formatter).
+    pub name: String,
+
+    /// Position on the X axis, e.g. the time of the period.
+    pub x: f64,
+
+    /// The first value of the period.
+    pub open: f64,
+
+    /// The highest value of the period.
+    pub high: f64,
+
+    /// The lowest value of the period.
+    pub low: f64,
+
+    /// The last value of the period.
+    pub close: f64,
+
+    /// The volume traded in the period, see [`CandlestickChart::volume_bars`].
+    pub volume: f64,
+
+    /// Width of the body (or the ticks), in plot units.
+    pub width: f64,
+}
+
+impl Candle {
+    /// Create a candle at `x`. Its volume is zero.
+    pub fn new(x: f64, open: f64, high: f64, low: f64, close: f64) -> Self {
+        Self {
+            name: String::default(),
+            x,
+            open,
+            high,
+            low,
+            close,
+            volume: 0.0,
+            width: 0.6,
+        }
+    }
+
+    /// Name of this candle.
+    #[allow(clippy::needless_pass_by_value)]
+    #[inline]
+    pub fn name(mut self, name: impl ToString) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    /// Set the volume traded in the period.
+    #[inline]
+    pub fn volume(mut self, volume: f64) -> Self {
+        self.volume = volume;
+        self
+    }
+
+    /// Set the width of the body (or the ticks).
+    #[inline]
+    pub fn width(mut self, width: f64) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Did the value go up (or stay the same) during the period?
+    #[inline]
+    pub fn is_up(&self) -> bool {
+        self.open <= self.close
+    }
+
+    pub(super) fn add_shapes(
+        &self,
+        transform: &PlotTransform,
+        style: CandleStyle,
+        color: Color32,
+        highlighted: bool,
+        shapes: &mut Vec<Shape>,
+    ) {
+        let stroke = Stroke::new(1.0, color);
+        let (stroke, fill) = if highlighted {
+            highlighted_color(stroke, color)
+        } else {
+            (stroke, color)
+        };
+
+        let line_between = |x1, y1, x2, y2| {
+            Shape::line_segment(
+                [
+                    transform.position_from_point(&PlotPoint::new(x1, y1)),
+                    transform.position_from_point(&PlotPoint::new(x2, y2)),
+                ],
+                stroke,
+            )
+        };
+        let half_width = self.width / 2.0;
+
+        match style {
+            CandleStyle::Candlestick => {
+                let (body_bottom, body_top) =
+                    (self.open.min(self.close), self.open.max(self.close));
+                if self.high > body_top {
+                    shapes.push(line_between(self.x, body_top, self.x, self.high));
+                }
+                if self.low < body_bottom {
+                    shapes.push(line_between(self.x, self.low, self.x, body_bottom));
+                }
+                let rect = transform.rect_from_values(
+                    &PlotPoint::new(self.x - half_width, body_bottom),
+                    &PlotPoint::new(self.x + half_width, body_top),
+                );
+                shapes.push(Shape::Rect(RectShape::new(
+                    rect,
+                    Rounding::ZERO,
+                    fill,
+                    stroke,
+                )));
+            }
+            CandleStyle::Ohlc => {
+                shapes.push(line_between(self.x, self.low, self.x, self.high));
+                shapes.push(line_between(
+                    self.x - half_width,
+                    self.open,
+                    self.x,
+                    self.open,
+                ));
+                shapes.push(line_between(
+                    self.x,
+                    self.close,
+                    self.x + half_width,
+                    self.close,
+                ));
+            }
+        }
+    }
+
+    pub(super) fn add_rulers_and_text(
+        &self,
+        parent: &CandlestickChart,
+        plot: &PlotConfig<'_>,
+        shapes: &mut Vec<Shape>,
+        cursors: &mut Vec<Cursor>,
+    ) {
+        let text: Option<String> = parent
+            .element_formatter
+            .as_ref()
+            .map(|fmt| fmt(self, parent));
+
+        add_rulers_and_text(self, plot, text, shapes, cursors);
+    }
+}
+
+impl RectElement for Candle {
+    fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    fn bounds_min(&self) -> PlotPoint {
+        PlotPoint::new(self.x - self.width / 2.0, self.low)
+    }
+
+    fn bounds_max(&self) -> PlotPoint {
+        PlotPoint::new(self.x + self.width / 2.0, self.high)
+    }
+
+    fn arguments_with_ruler(&self) -> Vec<PlotPoint> {
+        vec![PlotPoint::new(self.x, self.close)]
+    }
+
+    fn values_with_ruler(&self) -> Vec<PlotPoint> {
+        vec![PlotPoint::new(self.x, self.close)]
+    }
+
+    fn orientation(&self) -> Orientation {
+        Orientation::Vertical
+    }
+
+    fn default_values_format(&self, transform: &PlotTransform) -> String {
+        let decimals = |scale: f64| {
+            ((-scale.abs().log10()).ceil().at_least(0.0) as usize)
+                .at_most(6)
+                .at_least(1)
+        };
+        let [x_scale, y_scale] = transform.dvalue_dpos();
+        let x_decimals = decimals(x_scale);
+        let decimals = decimals(y_scale);
+        let mut text = format!(
+            "x = {x:.x_decimals$}\
+             \nOpen = {open:.decimals$}\
+             \nHigh = {high:.decimals$}\
+             \nLow = {low:.decimals$}\
+             \nClose = {close:.decimals$}",
+            x = self.x,
+            open = self.open,
+            high = self.high,
+            low = self.low,
+            close = self.close,
+        );
+        if self.volume != 0.0 {
+            text += &format!("\nVolume = {}", self.volume);
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use egui::{pos2, Rect};
+
+    use crate::PlotBounds;
+
+    use super::super::PlotItem as _;
+    use super::*;
+
+    #[test]
+    fn test_candle_bounds() {
+        let candle = Candle::new(2.0, 10.0, 12.0, 9.0, 11.0).width(0.5);
+        assert_eq!(candle.bounds_min(), PlotPoint::new(1.75, 9.0));
+        assert_eq!(candle.bounds_max(), PlotPoint::new(2.25, 12.0));
+        assert!(candle.is_up());
+        assert!(!Candle::new(0.0, 10.0, 12.0, 9.0, 9.5).is_up());
+
+        let chart = CandlestickChart::new(vec![
+            candle,
+            Candle::new(3.0, 11.0, 15.0, 10.0, 14.0).width(0.5),
+        ]);
+        let bounds = chart.bounds();
+        assert_eq!(bounds.min(), [1.75, 9.0]);
+        assert_eq!(bounds.max(), [3.25, 15.0]);
+        assert_eq!(CandlestickChart::new(vec![]).bounds(), PlotBounds::NOTHING);
+    }
+
+    #[test]
+    fn test_candle_hover_text() {
+        // 0.1 plot units per point along x, and 1.0 along y:
+        let transform = PlotTransform::new(
+            Rect::from_min_max(pos2(0.0, 0.0), pos2(100.0, 100.0)),
+            PlotBounds::from_min_max([0.0, 0.0], [10.0, 100.0]),
+            false,
+            false,
+        );
+        let candle = Candle::new(2.0, 10.0, 12.25, 9.0, 11.5);
+        assert_eq!(
+            candle.default_values_format(&transform),
+            "x = 2.0\nOpen = 10.0\nHigh = 12.2\nLow = 9.0\nClose = 11.5"
+        );
+        assert!(candle
+            .volume(1200.0)
+            .default_values_format(&transform)
+            .ends_with("\nClose = 11.5\nVolume = 1200"));
+    }
+}
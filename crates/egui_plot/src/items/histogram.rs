@@ -0,0 +1,320 @@
+use std::ops::Range;
+
+use egui::emath::NumExt as _;
+use egui::{Color32, Id};
+
+use super::{Bar, BarChart, Orientation};
+
+/// Never make more bins than this, however small the [`Binning::Width`] or large the [`Binning::Count`].
+const MAX_BINS: usize = 10_000;
+
+/// How a [`Histogram`] divides its values into bins.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum Binning {
+    /// Pick the number of bins from the number of values (Sturges' rule),
+    /// spread evenly from the smallest to the largest value.
+    #[default]
+    Auto,
+
+    /// This many bins of equal width, from the smallest to the largest value.
+    ///
+    /// At most 10 000 bins are made.
+    Count(usize),
+
+    /// Bins of this width, starting at the smallest value.
+    ///
+    /// If that would make more than 10 000 bins, the bins are made wider.
+    Width(f64),
+
+    /// The edges of the bins: `n + 1` edges make `n` bins.
+    ///
+    /// The edges are sorted, and edges that aren't finite or are repeated are ignored.
+    /// Values outside the first and last edge are left out.
+    Edges(Vec<f64>),
+}
+
+/// One bin of a [`Histogram`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct HistogramBin {
+    /// The values in `start..end` are counted in this bin.
+    /// The last bin also counts the values equal to its `end`.
+    pub range: Range<f64>,
+
+    /// How many values are in the bin.
+    pub count: usize,
+}
+
+/// Counts how many values fall in each bin, and shows that as a [`BarChart`].
+///
+/// Hovering a bar shows the range of its bin and the count.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// use egui_plot::{Binning, Histogram, Plot};
+///
+/// let values = [1.0, 1.5, 2.2, 2.4, 2.5, 3.9, 4.0];
+/// let histogram = Histogram::new(values).binning(Binning::Width(1.0)).name("Waiting time");
+/// let counts: Vec<usize> = histogram.bins().iter().map(|bin| bin.count).collect();
+/// assert_eq!(counts, [2, 3, 2]);
+///
+/// Plot::new("histogram").show(ui, |plot_ui| plot_ui.histogram(histogram));
+/// # });
+/// ```
+pub struct Histogram {
+    values: Vec<f64>,
+    binning: Binning,
+    color: Color32,
+    name: String,
+    orientation: Orientation,
+    highlight: bool,
+    id: Option<Id>,
+}
+
+impl Histogram {
+    /// Create a histogram of these values. Values that aren't finite are ignored.
+    pub fn new(values: impl IntoIterator<Item = f64>) -> Self {
+        Self {
+            values: values.into_iter().filter(|v| v.is_finite()).collect(),
+            binning: Binning::Auto,
+            color: Color32::TRANSPARENT,
+            name: String::new(),
+            orientation: Orientation::Vertical,
+            highlight: false,
+            id: None,
+        }
+    }
+
+    /// How to divide the values into bins. Default is [`Binning::Auto`].
+    #[inline]
+    pub fn binning(mut self, binning: Binning) -> Self {
+        self.binning = binning;
+        self
+    }
+
+    /// Set the color of the bars.
+    /// Default is `Color32::TRANSPARENT` which means a color will be auto-assigned.
+    #[inline]
+    pub fn color(mut self, color: impl Into<Color32>) -> Self {
+        self.color = color.into();
+        self
+    }
+
+    /// Name of this histogram.
+    ///
+    /// This name will show up in the plot legend, if legends are turned on.
+    #[allow(clippy::needless_pass_by_value)]
+    #[inline]
+    pub fn name(mut self, name: impl ToString) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    /// Show the bins along the Y axis, with the bars going to the right.
+    #[inline]
+    pub fn horizontal(mut self) -> Self {
+        self.orientation = Orientation::Horizontal;
+        self
+    }
+
+    /// Highlight all bars.
+    #[inline]
+    pub fn highlight(mut self, highlight: bool) -> Self {
+        self.highlight = highlight;
+        self
+    }
+
+    /// Set the histogram's id which is used to identify it in the plot's response.
+    #[inline]
+    pub fn id(mut self, id: Id) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    /// Divide the values into bins, and count them.
+    pub fn bins(&self) -> Vec<HistogramBin> {
+        let edges = self.edges();
+        let mut counts = vec![0; edges.len().saturating_sub(1)];
+        let Some(last) = counts.len().checked_sub(1) else {
+            return vec![];
+        };
+        for &value in &self.values {
+            if value < edges[0] || edges[last + 1] < value {
+                continue;
+            }
+            // The index of the last edge that isn't larger than the value:
+            let index = edges.partition_point(|&edge| edge <= value) - 1;
+            counts[index.at_most(last)] += 1;
+        }
+        edges
+            .windows(2)
+            .zip(counts)
+            .map(|(edges, count)| HistogramBin {
+                range: edges[0]..edges[1],
+                count,
+            })
+            .collect()
+    }
+
+    fn edges(&self) -> Vec<f64> {
+        if let Binning::Edges(edges) = &self.binning {
+            let mut edges: Vec<f64> = edges.iter().copied().filter(|e| e.is_finite()).collect();
+            edges.sort_by(f64::total_cmp);
+            edges.dedup();
+            return edges;
+        }
+        if self.values.is_empty() {
+            return vec![];
+        }
+        let (min, max) = self
+            .values
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &v| {
+                (min.min(v), max.max(v))
+            });
+        if min == max {
+            // A single bin around the one value:
+            return vec![min - 0.5, max + 0.5];
+        }
+
+        let count = match self.binning {
+            Binning::Auto => (self.values.len() as f64).log2().ceil() as usize + 1,
+            Binning::Count(count) => count.at_least(1),
+            Binning::Width(width) if width > 0.0 => {
+                // The last bin includes its end, so a value on it needs no extra bin:
+                ((max - min) / width).ceil().at_least(1.0) as usize
+            }
+            Binning::Width(_) | Binning::Edges(_) => 1,
+        };
+        let count = count.at_most(MAX_BINS);
+        let width = match self.binning {
+            Binning::Width(width) if width > 0.0 && (max - min) / width <= MAX_BINS as f64 => width,
+            _ => (max - min) / count as f64,
+        };
+        let mut edges: Vec<f64> = (0..=count).map(|i| min + i as f64 * width).collect();
+        // Don't let rounding errors leave out the largest value:
+        edges[count] = edges[count].max(max);
+        edges
+    }
+
+    /// Turn into a [`BarChart`] with one bar per bin, like [`crate::PlotUi::histogram`] does.
+    pub fn into_bar_chart(self) -> BarChart {
+        let bars = self
+            .bins()
+            .into_iter()
+            .map(|bin| {
+                let middle = (bin.range.start + bin.range.end) / 2.0;
+                Bar::new(middle, bin.count as f64).width(bin.range.end - bin.range.start)
+            })
+            .collect();
+
+        let mut chart = BarChart::new(bars)
+            .name(&self.name)
+            .highlight(self.highlight)
+            .element_formatter(Box::new(format_bin));
+        if self.orientation == Orientation::Horizontal {
+            chart = chart.horizontal();
+        }
+        if self.color != Color32::TRANSPARENT {
+            chart = chart.color(self.color);
+        }
+        if let Some(id) = self.id {
+            chart = chart.id(id);
+        }
+        chart
+    }
+}
+
+/// Show the range of the bin and how many values are in it.
+fn format_bin(bar: &Bar, chart: &BarChart) -> String {
+    let width = bar.bar_width;
+    let decimals = ((-width.abs().log10()).ceil().at_least(0.0) as usize + 1).at_most(6);
+    let text = format!(
+        "{start:.decimals$} to {end:.decimals$}\nCount = {count}",
+        start = bar.argument - width / 2.0,
+        end = bar.argument + width / 2.0,
+        count = bar.value,
+    );
+    if chart.name.is_empty() {
+        text
+    } else {
+        format!("{}\n{text}", chart.name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counts(values: &[f64], binning: Binning) -> Vec<usize> {
+        let histogram = Histogram::new(values.iter().copied()).binning(binning);
+        histogram.bins().iter().map(|bin| bin.count).collect()
+    }
+
+    #[test]
+    fn test_single_value() {
+        let bins = Histogram::new([3.0]).bins();
+        assert_eq!(
+            bins,
+            vec![HistogramBin {
+                range: 2.5..3.5,
+                count: 1
+            }]
+        );
+        assert_eq!(counts(&[3.0, 3.0], Binning::Width(0.1)), [2]);
+        assert!(Histogram::new([]).bins().is_empty());
+    }
+
+    #[test]
+    fn test_value_on_last_edge() {
+        assert_eq!(counts(&[0.0, 1.0, 2.0], Binning::Count(2)), [1, 2]);
+        assert_eq!(
+            counts(&[0.0, 0.5, 2.0], Binning::Edges(vec![0.0, 1.0, 2.0])),
+            [2, 1]
+        );
+        assert_eq!(
+            counts(&[-1.0, 2.0, 3.0], Binning::Edges(vec![0.0, 1.0, 2.0])),
+            [0, 1],
+            "Values outside the edges are left out"
+        );
+    }
+
+    #[test]
+    fn test_tiny_width() {
+        let bins = Histogram::new([0.0, 1000.0])
+            .binning(Binning::Width(1e-9))
+            .bins();
+        assert_eq!(bins.len(), MAX_BINS);
+        assert_eq!(bins.iter().map(|bin| bin.count).sum::<usize>(), 2);
+        assert_eq!(bins.last().unwrap().range.end, 1000.0);
+
+        assert_eq!(
+            counts(&[0.0, 1.0], Binning::Count(usize::MAX)).len(),
+            MAX_BINS
+        );
+        assert_eq!(counts(&[0.0, 1.0], Binning::Width(0.0)), [2]);
+        assert_eq!(counts(&[0.0, 1.0], Binning::Width(f64::NAN)), [2]);
+    }
+
+    #[test]
+    fn test_bad_edges() {
+        let values = [0.5, 1.5, 2.5];
+        assert_eq!(
+            counts(&values, Binning::Edges(vec![2.0, 0.0, 1.0, 3.0])),
+            [1, 1, 1],
+            "Unsorted edges are sorted"
+        );
+        assert_eq!(
+            counts(&values, Binning::Edges(vec![f64::NAN, 0.0, 1.0, 1.0, 3.0])),
+            [1, 2],
+            "NaN and repeated edges are ignored"
+        );
+        assert!(Histogram::new(values)
+            .binning(Binning::Edges(vec![f64::NAN, 1.0]))
+            .bins()
+            .is_empty());
+        assert!(Histogram::new(values)
+            .binning(Binning::Edges(vec![]))
+            .bins()
+            .is_empty());
+    }
+}
@@ -13,10 +13,14 @@ use values::{ClosestElem, PlotGeometry};
 
 pub use bar::Bar;
 pub use box_elem::{BoxElem, BoxSpread};
-pub use values::{LineStyle, MarkerShape, Orientation, PlotPoint, PlotPoints};
+pub use candle_elem::{Candle, CandleStyle};
+pub use histogram::{Binning, Histogram, HistogramBin};
+pub use values::{LineInterpolation, LineStyle, MarkerShape, Orientation, PlotPoint, PlotPoints};
 
 mod bar;
 mod box_elem;
+mod candle_elem;
+mod histogram;
 mod rect_elem;
 mod values;
 
@@ -392,6 +396,7 @@ pub struct Line {
     pub(super) highlight: bool,
     pub(super) fill: Option<f32>,
     pub(super) style: LineStyle,
+    pub(super) interpolation: LineInterpolation,
     id: Option<Id>,
 }
 
@@ -404,6 +409,7 @@ impl Line {
             highlight: false,
             fill: None,
             style: LineStyle::Solid,
+            interpolation: LineInterpolation::Linear,
             id: None,
         }
     }
@@ -450,6 +456,14 @@ impl Line {
         self
     }
 
+    /// How to go from one point to the next, e.g. in steps.
+    /// Default is `LineInterpolation::Linear`.
+    #[inline]
+    pub fn interpolation(mut self, interpolation: LineInterpolation) -> Self {
+        self.interpolation = interpolation;
+        self
+    }
+
     /// Name of this line.
     ///
     /// This name will show up in the plot legend, if legends are turned on.
@@ -486,6 +500,7 @@ impl PlotItem for Line {
             highlight,
             mut fill,
             style,
+            interpolation,
             ..
         } = self;
 
@@ -494,6 +509,7 @@ impl PlotItem for Line {
             .iter()
             .map(|v| transform.position_from_point(v))
             .collect();
+        let values_tf = interpolation.interpolate(values_tf);
         let n_values = values_tf.len();
 
         // Fill the area between the line and a reference line, if required.
@@ -1787,6 +1803,215 @@ impl PlotItem for BoxPlot {
     }
 }
 
+/// A financial chart of [`Candle`]s, as candlesticks or OHLC bars.
+///
+/// The candles where the value went up are drawn in one color, the others in another.
+/// Show the volumes with [`Self::volume_bars`] in a second plot, with the x axis linked.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// use egui_plot::{Candle, CandlestickChart, Plot};
+///
+/// let candles = vec![
+///     Candle::new(0.0, 10.0, 12.0, 9.5, 11.5).volume(1200.0),
+///     Candle::new(1.0, 11.5, 11.8, 10.0, 10.2).volume(800.0),
+/// ];
+/// let chart = CandlestickChart::new(candles).name("ACME");
+/// let volume = chart.volume_bars();
+/// let group = egui::Id::new("prices");
+/// Plot::new("prices")
+///     .link_axis(group, true, false)
+///     .height(200.0)
+///     .show(ui, |plot_ui| plot_ui.candlestick_chart(chart));
+/// Plot::new("volume")
+///     .link_axis(group, true, false)
+///     .height(80.0)
+///     .show(ui, |plot_ui| plot_ui.bar_chart(volume));
+/// # });
+/// ```
+pub struct CandlestickChart {
+    pub(super) candles: Vec<Candle>,
+    pub(super) style: CandleStyle,
+    pub(super) up_color: Color32,
+    pub(super) down_color: Color32,
+    pub(super) name: String,
+
+    /// A custom element formatter
+    pub(super) element_formatter: Option<Box<dyn Fn(&Candle, &CandlestickChart) -> String>>,
+
+    highlight: bool,
+    id: Option<Id>,
+}
+
+impl CandlestickChart {
+    /// Create a chart of candlesticks, with the candles that went up in green and the others in red.
+    pub fn new(candles: Vec<Candle>) -> Self {
+        Self {
+            candles,
+            style: CandleStyle::Candlestick,
+            up_color: Color32::from_rgb(38, 166, 154),
+            down_color: Color32::from_rgb(239, 83, 80),
+            name: String::new(),
+            element_formatter: None,
+            highlight: false,
+            id: None,
+        }
+    }
+
+    /// Draw as candlesticks (the default) or as OHLC bars.
+    #[inline]
+    pub fn style(mut self, style: CandleStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// The color of the candles where the value went up (or stayed the same).
+    /// This is the color that shows up in the legend.
+    #[inline]
+    pub fn up_color(mut self, color: impl Into<Color32>) -> Self {
+        self.up_color = color.into();
+        self
+    }
+
+    /// The color of the candles where the value went down.
+    #[inline]
+    pub fn down_color(mut self, color: impl Into<Color32>) -> Self {
+        self.down_color = color.into();
+        self
+    }
+
+    /// Set the width of all candles, in plot units.
+    #[inline]
+    pub fn width(mut self, width: f64) -> Self {
+        for candle in &mut self.candles {
+            candle.width = width;
+        }
+        self
+    }
+
+    /// Name of this chart.
+    ///
+    /// This name will show up in the plot legend, if legends are turned on. Multiple charts may
+    /// share the same name, in which case they will also share an entry in the legend.
+    #[allow(clippy::needless_pass_by_value)]
+    #[inline]
+    pub fn name(mut self, name: impl ToString) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    /// Highlight all plot elements.
+    #[inline]
+    pub fn highlight(mut self, highlight: bool) -> Self {
+        self.highlight = highlight;
+        self
+    }
+
+    /// Add a custom way to format an element.
+    /// Can be used to display a set number of decimals or custom labels.
+    #[inline]
+    pub fn element_formatter(mut self, formatter: Box<dyn Fn(&Candle, &Self) -> String>) -> Self {
+        self.element_formatter = Some(formatter);
+        self
+    }
+
+    /// Set the chart's id which is used to identify it in the plot's response.
+    #[inline]
+    pub fn id(mut self, id: Id) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    /// A bar for the volume of each candle, at the same x and in the same color.
+    pub fn volume_bars(&self) -> BarChart {
+        let bars = self
+            .candles
+            .iter()
+            .map(|candle| {
+                let color = self.candle_color(candle);
+                Bar::new(candle.x, candle.volume)
+                    .name(&candle.name)
+                    .width(candle.width)
+                    .fill(color.linear_multiply(0.5))
+                    .stroke(Stroke::new(1.0, color))
+            })
+            .collect();
+        BarChart::new(bars).name(&self.name).color(self.up_color)
+    }
+
+    fn candle_color(&self, candle: &Candle) -> Color32 {
+        if candle.is_up() {
+            self.up_color
+        } else {
+            self.down_color
+        }
+    }
+}
+
+impl PlotItem for CandlestickChart {
+    fn shapes(&self, _ui: &Ui, transform: &PlotTransform, shapes: &mut Vec<Shape>) {
+        for candle in &self.candles {
+            let color = self.candle_color(candle);
+            candle.add_shapes(transform, self.style, color, self.highlight, shapes);
+        }
+    }
+
+    fn initialize(&mut self, _x_range: RangeInclusive<f64>) {
+        // nothing to do
+    }
+
+    fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    fn color(&self) -> Color32 {
+        self.up_color
+    }
+
+    fn highlight(&mut self) {
+        self.highlight = true;
+    }
+
+    fn highlighted(&self) -> bool {
+        self.highlight
+    }
+
+    fn geometry(&self) -> PlotGeometry<'_> {
+        PlotGeometry::Rects
+    }
+
+    fn bounds(&self) -> PlotBounds {
+        let mut bounds = PlotBounds::NOTHING;
+        for candle in &self.candles {
+            bounds.merge(&candle.bounds());
+        }
+        bounds
+    }
+
+    fn find_closest(&self, point: Pos2, transform: &PlotTransform) -> Option<ClosestElem> {
+        find_closest_rect(&self.candles, point, transform)
+    }
+
+    fn on_hover(
+        &self,
+        elem: ClosestElem,
+        shapes: &mut Vec<Shape>,
+        cursors: &mut Vec<Cursor>,
+        plot: &PlotConfig<'_>,
+        _: &LabelFormatter,
+    ) {
+        let candle = &self.candles[elem.index];
+        let color = self.candle_color(candle);
+
+        candle.add_shapes(plot.transform, self.style, color, true, shapes);
+        candle.add_rulers_and_text(self, plot, shapes, cursors);
+    }
+
+    fn id(&self) -> Option<Id> {
+        self.id
+    }
+}
+
 // ----------------------------------------------------------------------------
 // Helper functions
 
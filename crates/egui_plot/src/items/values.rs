@@ -1,6 +1,6 @@
 use std::ops::{Bound, RangeBounds, RangeInclusive};
 
-use egui::{Pos2, Shape, Stroke, Vec2};
+use egui::{pos2, Pos2, Shape, Stroke, Vec2};
 
 use crate::transform::PlotBounds;
 
@@ -47,6 +47,53 @@ impl PlotPoint {
 
 // ----------------------------------------------------------------------------
 
+/// How a [`crate::Line`] goes from one point to the next.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LineInterpolation {
+    /// A straight line.
+    #[default]
+    Linear,
+
+    /// Go to the value of the next point first, then along to it.
+    StepBefore,
+
+    /// Change to the value of the next point halfway between the two.
+    StepMiddle,
+
+    /// Keep the value until the next point, then go to its value.
+    ///
+    /// Good for values that hold until they change, like a price or a setting.
+    StepAfter,
+}
+
+impl LineInterpolation {
+    /// The corners of the line through these (screen) points.
+    pub(super) fn interpolate(self, points: Vec<Pos2>) -> Vec<Pos2> {
+        if self == Self::Linear || points.len() < 2 {
+            return points;
+        }
+        let mut corners = Vec::with_capacity(3 * points.len());
+        for w in points.windows(2) {
+            let (a, b) = (w[0], w[1]);
+            corners.push(a);
+            match self {
+                Self::Linear => {}
+                Self::StepBefore => corners.push(pos2(a.x, b.y)),
+                Self::StepMiddle => {
+                    let middle = (a.x + b.x) / 2.0;
+                    corners.push(pos2(middle, a.y));
+                    corners.push(pos2(middle, b.y));
+                }
+                Self::StepAfter => corners.push(pos2(b.x, a.y)),
+            }
+        }
+        corners.extend(points.last());
+        corners
+    }
+}
+
+// ----------------------------------------------------------------------------
+
 /// Solid, dotted, dashed, etc.
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum LineStyle {
@@ -432,3 +479,52 @@ pub struct ClosestElem {
     /// Squared distance from the mouse cursor (needed to compare against other PlotItems, which might be nearer)
     pub dist_sq: f32,
 }
+
+#[test]
+fn test_line_interpolation() {
+    let points = vec![pos2(0.0, 0.0), pos2(2.0, 1.0), pos2(4.0, 3.0)];
+    assert_eq!(
+        LineInterpolation::Linear.interpolate(points.clone()),
+        points
+    );
+    assert_eq!(
+        LineInterpolation::StepBefore.interpolate(points.clone()),
+        [
+            pos2(0.0, 0.0),
+            pos2(0.0, 1.0),
+            pos2(2.0, 1.0),
+            pos2(2.0, 3.0),
+            pos2(4.0, 3.0),
+        ]
+    );
+    assert_eq!(
+        LineInterpolation::StepAfter.interpolate(points.clone()),
+        [
+            pos2(0.0, 0.0),
+            pos2(2.0, 0.0),
+            pos2(2.0, 1.0),
+            pos2(4.0, 1.0),
+            pos2(4.0, 3.0),
+        ]
+    );
+    assert_eq!(
+        LineInterpolation::StepMiddle.interpolate(points),
+        [
+            pos2(0.0, 0.0),
+            pos2(1.0, 0.0),
+            pos2(1.0, 1.0),
+            pos2(2.0, 1.0),
+            pos2(3.0, 1.0),
+            pos2(3.0, 3.0),
+            pos2(4.0, 3.0),
+        ]
+    );
+
+    // Too few points to step between:
+    let single = vec![pos2(1.0, 2.0)];
+    assert_eq!(
+        LineInterpolation::StepAfter.interpolate(single.clone()),
+        single
+    );
+    assert!(LineInterpolation::StepMiddle.interpolate(vec![]).is_empty());
+}
@@ -1,5 +1,7 @@
 use std::{collections::BTreeMap, string::String};
 
+use egui::epaint::shape_transform::adjust_colors;
+
 use crate::*;
 
 use super::items::PlotItem;
@@ -33,6 +35,9 @@ pub struct Legend {
     pub background_alpha: f32,
     pub position: Corner,
 
+    /// Can the entries be dragged to reorder them?
+    pub reorderable: bool,
+
     /// Used for overriding the `hidden_items` set in [`LegendWidget`].
     hidden_items: Option<ahash::HashSet<String>>,
 }
@@ -43,6 +48,7 @@ impl Default for Legend {
             text_style: TextStyle::Body,
             background_alpha: 0.75,
             position: Corner::RightTop,
+            reorderable: false,
 
             hidden_items: None,
         }
@@ -71,6 +77,16 @@ impl Legend {
         self
     }
 
+    /// Let the user drag the entries up and down to reorder them. Default: `false`.
+    ///
+    /// The order is remembered in [`crate::PlotMemory::legend_order`],
+    /// and returned in [`crate::PlotResponse::legend_order`].
+    #[inline]
+    pub fn reorderable(mut self, reorderable: bool) -> Self {
+        self.reorderable = reorderable;
+        self
+    }
+
     /// Specifies hidden items in the legend configuration to override the existing ones. This
     /// allows the legend traces' visibility to be controlled from the application code.
     #[inline]
@@ -99,7 +115,7 @@ impl LegendEntry {
         }
     }
 
-    fn ui(&mut self, ui: &mut Ui, text: String, text_style: &TextStyle) -> Response {
+    fn ui(&mut self, ui: &mut Ui, text: String, text_style: &TextStyle, sense: Sense) -> Response {
         let Self {
             color,
             checked,
@@ -115,7 +131,9 @@ impl LegendEntry {
         let total_extra = vec2(icon_size + icon_spacing, 0.0);
 
         let desired_size = total_extra + galley.size();
-        let (rect, response) = ui.allocate_exact_size(desired_size, Sense::click());
+        let (rect, _) = ui.allocate_exact_size(desired_size, Sense::hover());
+        // The id must follow the entry when it is reordered:
+        let response = ui.interact(rect, ui.id().with(galley.text()), sense);
 
         response
             .widget_info(|| WidgetInfo::selected(WidgetType::Checkbox, *checked, galley.text()));
@@ -169,54 +187,106 @@ impl LegendEntry {
     }
 }
 
+/// A legend: named and colored entries that can be hidden by clicking them,
+/// and reordered by dragging them if [`Legend::reorderable`] is set.
+///
+/// [`crate::Plot`] shows one for its items, but you can also show one on its own.
+/// It is rebuilt every frame, so keep what [`Self::hidden_items`] and [`Self::order`]
+/// return, and pass it back in next frame:
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// use egui::{ahash::HashSet, Color32};
+/// use egui_plot::{Legend, LegendWidget};
+///
+/// let id = ui.id().with("legend");
+/// let (hidden_items, order): (HashSet<String>, Vec<String>) =
+///     ui.data_mut(|data| data.get_temp(id)).unwrap_or_default();
+/// let entries = [("sin".to_owned(), Color32::RED), ("cos".to_owned(), Color32::BLUE)];
+/// let config = Legend::default().reorderable(true);
+/// if let Some(mut legend) =
+///     LegendWidget::new(ui.max_rect(), config, entries, &hidden_items, &order)
+/// {
+///     ui.add(&mut legend);
+///     ui.data_mut(|data| data.insert_temp(id, (legend.hidden_items(), legend.order())));
+/// }
+/// # });
+/// ```
 #[derive(Clone)]
-pub(super) struct LegendWidget {
+pub struct LegendWidget {
     rect: Rect,
-    entries: BTreeMap<String, LegendEntry>,
+
+    /// In the order they are shown.
+    entries: Vec<(String, LegendEntry)>,
     config: Legend,
 }
 
 impl LegendWidget {
-    /// Create a new legend from items, the names of items that are hidden and the style of the
-    /// text. Returns `None` if the legend has no entries.
+    /// Create a new legend for the items of a plot. Returns `None` if none of them has a name.
     pub(super) fn try_new(
         rect: Rect,
         config: Legend,
         items: &[Box<dyn PlotItem>],
         hidden_items: &ahash::HashSet<String>, // Existing hiddent items in the plot memory.
+        order: &[String],
+    ) -> Option<Self> {
+        let entries = items
+            .iter()
+            .map(|item| (item.name().to_owned(), item.color()));
+        Self::new(rect, config, entries, hidden_items, order)
+    }
+
+    /// Create a new legend in a corner of `rect`, from the names and colors of its entries,
+    /// and the names of the entries that are hidden. Returns `None` if the legend has no entries.
+    ///
+    /// Entries without a name are left out. If multiple entries have the same name, they share a
+    /// checkbox. If their colors don't match, we pick a neutral color for the checkbox.
+    ///
+    /// The entries are sorted by name, except for those in `order`, which come first.
+    pub fn new(
+        rect: Rect,
+        config: Legend,
+        entries: impl IntoIterator<Item = (String, Color32)>,
+        hidden_items: &ahash::HashSet<String>,
+        order: &[String],
     ) -> Option<Self> {
         // If `config.hidden_items` is not `None`, it is used.
         let hidden_items = config.hidden_items.as_ref().unwrap_or(hidden_items);
 
-        // Collect the legend entries. If multiple items have the same name, they share a
-        // checkbox. If their colors don't match, we pick a neutral color for the checkbox.
-        let mut entries: BTreeMap<String, LegendEntry> = BTreeMap::new();
-        items
+        let mut by_name: BTreeMap<String, LegendEntry> = BTreeMap::new();
+        for (name, color) in entries {
+            if name.is_empty() {
+                continue;
+            }
+            let checked = !hidden_items.contains(&name);
+            by_name
+                .entry(name)
+                .and_modify(|entry| {
+                    if entry.color != color {
+                        // Multiple entries with different colors
+                        entry.color = Color32::TRANSPARENT;
+                    }
+                })
+                .or_insert_with(|| LegendEntry::new(color, checked));
+        }
+        let mut ordered: Vec<(String, LegendEntry)> = order
             .iter()
-            .filter(|item| !item.name().is_empty())
-            .for_each(|item| {
-                entries
-                    .entry(item.name().to_owned())
-                    .and_modify(|entry| {
-                        if entry.color != item.color() {
-                            // Multiple items with different colors
-                            entry.color = Color32::TRANSPARENT;
-                        }
-                    })
-                    .or_insert_with(|| {
-                        let color = item.color();
-                        let checked = !hidden_items.contains(item.name());
-                        LegendEntry::new(color, checked)
-                    });
-            });
-        (!entries.is_empty()).then_some(Self {
+            .filter_map(|name| Some((name.clone(), by_name.remove(name)?)))
+            .collect();
+        ordered.extend(by_name);
+        (!ordered.is_empty()).then_some(Self {
             rect,
-            entries,
+            entries: ordered,
             config,
         })
     }
 
-    // Get the names of the hidden items.
+    /// The names of the entries, in the order they are shown.
+    pub fn order(&self) -> Vec<String> {
+        self.entries.iter().map(|(name, _)| name.clone()).collect()
+    }
+
+    /// The names of the hidden entries.
     pub fn hidden_items(&self) -> ahash::HashSet<String> {
         self.entries
             .iter()
@@ -225,7 +295,7 @@ impl LegendWidget {
             .collect()
     }
 
-    // Get the name of the hovered items.
+    /// The name of the hovered entry, if any.
     pub fn hovered_item_name(&self) -> Option<String> {
         self.entries
             .iter()
@@ -234,6 +304,34 @@ impl LegendWidget {
     }
 }
 
+/// Move the entry being dragged to where the pointer is.
+fn reorder_dragged(
+    ui: &Ui,
+    entries: &mut Vec<(String, LegendEntry)>,
+    responses: &[Response],
+    from: usize,
+) {
+    ui.ctx().set_cursor_icon(CursorIcon::Grabbing);
+    let Some(pointer) = ui.ctx().pointer_interact_pos() else {
+        return;
+    };
+    let to = responses
+        .iter()
+        .position(|r| r.rect.y_range().contains(pointer.y));
+    if let Some(to) = to.filter(|&to| to != from) {
+        let entry = entries.remove(from);
+        entries.insert(to, entry);
+        ui.ctx().request_repaint();
+    }
+}
+
+/// Fade out the shapes of an item, while another one is highlighted from the legend.
+pub(super) fn dim_shapes(shapes: &mut [Shape]) {
+    for shape in shapes {
+        adjust_colors(shape, &|color| *color = color.gamma_multiply(0.25));
+    }
+}
+
 impl Widget for &mut LegendWidget {
     fn ui(self, ui: &mut Ui) -> Response {
         let LegendWidget {
@@ -267,14 +365,112 @@ impl Widget for &mut LegendWidget {
                 .multiply_with_opacity(config.background_alpha);
                 background_frame
                     .show(ui, |ui| {
-                        entries
+                        let sense = if config.reorderable {
+                            Sense::click_and_drag()
+                        } else {
+                            Sense::click()
+                        };
+                        let responses: Vec<Response> = entries
                             .iter_mut()
-                            .map(|(name, entry)| entry.ui(ui, name.clone(), &config.text_style))
-                            .reduce(|r1, r2| r1.union(r2))
-                            .unwrap()
+                            .map(|(name, entry)| {
+                                entry.ui(ui, name.clone(), &config.text_style, sense)
+                            })
+                            .collect();
+                        if let Some(from) = responses.iter().position(|r| r.dragged()) {
+                            reorder_dragged(ui, entries, &responses, from);
+                        }
+                        responses.into_iter().reduce(|r1, r2| r1.union(r2)).unwrap()
                     })
                     .inner
             })
             .inner
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use egui::test_harness::Harness;
+
+    use super::*;
+
+    fn names(legend: &LegendWidget) -> Vec<&str> {
+        legend
+            .entries
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_order_after_try_new() {
+        let items: Vec<Box<dyn PlotItem>> = vec![
+            Box::new(HLine::new(0.0).name("b").color(Color32::RED)),
+            Box::new(HLine::new(1.0).name("a").color(Color32::RED)),
+            Box::new(HLine::new(2.0).name("c").color(Color32::RED)),
+            Box::new(HLine::new(3.0).color(Color32::RED)),
+            Box::new(HLine::new(4.0).name("a").color(Color32::BLUE)),
+        ];
+        let hidden_items = ["b".to_owned()].into_iter().collect();
+        let order = ["c".to_owned(), "gone".to_owned()];
+        let rect = Rect::from_min_size(Pos2::ZERO, Vec2::splat(100.0));
+
+        let legend =
+            LegendWidget::try_new(rect, Legend::default(), &items, &hidden_items, &order).unwrap();
+        // The ones in `order` come first, then the rest by name. Unnamed items are left out:
+        assert_eq!(names(&legend), ["c", "a", "b"]);
+        assert_eq!(legend.order(), ["c", "a", "b"]);
+        assert_eq!(legend.hidden_items(), hidden_items);
+        // The two items called "a" share an entry with a neutral color:
+        assert_eq!(legend.entries[1].1.color, Color32::TRANSPARENT);
+
+        assert!(
+            LegendWidget::try_new(rect, Legend::default(), &items[3..4], &hidden_items, &[])
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_reorder_dragged() {
+        let id = Id::new("legend");
+        let mut harness = Harness::new_ui(|ui| {
+            let (hidden_items, order): (ahash::HashSet<String>, Vec<String>) =
+                ui.data_mut(|data| data.get_temp(id)).unwrap_or_default();
+            let entries = ["a", "b", "c"].map(|name| (name.to_owned(), Color32::RED));
+            let config = Legend::default().reorderable(true);
+            let mut legend =
+                LegendWidget::new(ui.max_rect(), config, entries, &hidden_items, &order).unwrap();
+            ui.add(&mut legend);
+            ui.data_mut(|data| data.insert_temp(id, (legend.hidden_items(), legend.order())));
+        });
+        harness.run();
+        let order = |harness: &Harness<'_>| {
+            harness
+                .ctx()
+                .data(|data| data.get_temp::<(ahash::HashSet<String>, Vec<String>)>(id))
+                .unwrap()
+                .1
+        };
+        assert_eq!(order(&harness), ["a", "b", "c"]);
+
+        let to = harness.get_by_label("c").interact_rect().center();
+        harness.get_by_label("a").drag_to(to);
+        assert_eq!(order(&harness), ["b", "c", "a"]);
+    }
+
+    #[test]
+    fn test_dim_shapes() {
+        let mut shapes = vec![
+            Shape::circle_filled(Pos2::ZERO, 1.0, Color32::RED),
+            Shape::line_segment([Pos2::ZERO, pos2(1.0, 1.0)], (1.0, Color32::BLUE)),
+        ];
+        dim_shapes(&mut shapes);
+        let Shape::Circle(circle) = &shapes[0] else {
+            panic!("Expected a circle, got {:?}", shapes[0]);
+        };
+        assert_eq!(circle.fill, Color32::RED.gamma_multiply(0.25));
+        let Shape::LineSegment { stroke, .. } = &shapes[1] else {
+            panic!("Expected a line segment, got {:?}", shapes[1]);
+        };
+        assert_eq!(stroke.color, Color32::BLUE.gamma_multiply(0.25));
+    }
+}
@@ -22,10 +22,12 @@ use epaint::{util::FloatOrd, Hsva};
 pub use crate::{
     axis::{Axis, AxisHints, HPlacement, Placement, VPlacement},
     items::{
-        Arrows, Bar, BarChart, BoxElem, BoxPlot, BoxSpread, HLine, Line, LineStyle, MarkerShape,
-        Orientation, PlotImage, PlotItem, PlotPoint, PlotPoints, Points, Polygon, Text, VLine,
+        Arrows, Bar, BarChart, Binning, BoxElem, BoxPlot, BoxSpread, Candle, CandleStyle,
+        CandlestickChart, HLine, Histogram, HistogramBin, Line, LineInterpolation, LineStyle,
+        MarkerShape, Orientation, PlotImage, PlotItem, PlotPoint, PlotPoints, Points, Polygon,
+        Text, VLine,
     },
-    legend::{Corner, Legend},
+    legend::{Corner, Legend, LegendWidget},
     memory::PlotMemory,
     plot_ui::PlotUi,
     transform::{PlotBounds, PlotTransform},
@@ -33,7 +35,6 @@ pub use crate::{
 
 use axis::AxisWidget;
 use items::{horizontal_line, rulers_color, vertical_line};
-use legend::LegendWidget;
 
 type LabelFormatterFn = dyn Fn(&str, &PlotPoint) -> String;
 pub type LabelFormatter = Option<Box<LabelFormatterFn>>;
@@ -121,6 +122,14 @@ pub struct PlotResponse<R> {
     ///
     /// This is `None` if either no item was hovered, or the hovered item didn't provide an id.
     pub hovered_plot_item: Option<Id>,
+
+    /// The names of the items hidden with the legend, see [`Legend::hidden_items`].
+    pub hidden_items: ahash::HashSet<String>,
+
+    /// The names of the legend entries, in the order they are shown.
+    ///
+    /// Empty if there is no legend. See [`Legend::reorderable`].
+    pub legend_order: Vec<String>,
 }
 
 // ----------------------------------------------------------------------------
@@ -826,6 +835,7 @@ impl Plot {
             auto_bounds: default_auto_bounds,
             hovered_legend_item: None,
             hidden_items: Default::default(),
+            legend_order: Default::default(),
             transform: PlotTransform::new(plot_rect, min_auto_bounds, center_axis.x, center_axis.y),
             last_click_pos_for_zoom: None,
             x_axis_thickness: Default::default(),
@@ -866,8 +876,15 @@ impl Plot {
         }
 
         // --- Legend ---
-        let legend = legend_config
-            .and_then(|config| LegendWidget::try_new(plot_rect, config, &items, &mem.hidden_items));
+        let legend = legend_config.and_then(|config| {
+            LegendWidget::try_new(
+                plot_rect,
+                config,
+                &items,
+                &mem.hidden_items,
+                &mem.legend_order,
+            )
+        });
         // Don't show hover cursor when hovering over legend.
         if mem.hovered_legend_item.is_some() {
             show_x = false;
@@ -875,7 +892,8 @@ impl Plot {
         }
         // Remove the deselected items.
         items.retain(|item| !mem.hidden_items.contains(item.name()));
-        // Highlight the hovered items.
+        // Highlight the hovered items, and dim the others.
+        let dim_unhighlighted = mem.hovered_legend_item.is_some();
         if let Some(hovered_name) = &mem.hovered_legend_item {
             items
                 .iter_mut()
@@ -1078,10 +1096,16 @@ impl Plot {
         let hover_pos = response.hover_pos();
         if let Some(hover_pos) = hover_pos {
             if allow_zoom.any() {
+                let (zoom_delta, zoom_delta_2d) = match response.multi_touch() {
+                    Some(touch) => (touch.zoom_delta, touch.zoom_delta_2d),
+                    // Pinching something else:
+                    None if ui.input(|i| i.multi_touch().is_some()) => (1.0, Vec2::splat(1.0)),
+                    None => ui.input(|i| (i.zoom_delta(), i.zoom_delta_2d())),
+                };
                 let mut zoom_factor = if data_aspect.is_some() {
-                    Vec2::splat(ui.input(|i| i.zoom_delta()))
+                    Vec2::splat(zoom_delta)
                 } else {
-                    ui.input(|i| i.zoom_delta_2d())
+                    zoom_delta_2d
                 };
                 if !allow_zoom.x {
                     zoom_factor.x = 1.0;
@@ -1151,6 +1175,7 @@ impl Plot {
 
         let prepared = PreparedPlot {
             items,
+            dim_unhighlighted,
             show_x,
             show_y,
             label_formatter,
@@ -1181,6 +1206,7 @@ impl Plot {
             ui.add(&mut legend);
             mem.hidden_items = legend.hidden_items();
             mem.hovered_legend_item = legend.hovered_item_name();
+            mem.legend_order = legend.order();
         }
 
         if let Some((id, _)) = linked_cursors.as_ref() {
@@ -1210,6 +1236,8 @@ impl Plot {
         }
 
         let transform = mem.transform;
+        let hidden_items = mem.hidden_items.clone();
+        let legend_order = mem.legend_order.clone();
         mem.store(ui.ctx(), plot_id);
 
         let response = if show_x || show_y {
@@ -1225,6 +1253,8 @@ impl Plot {
             response,
             transform,
             hovered_plot_item,
+            hidden_items,
+            legend_order,
         }
     }
 }
@@ -1433,6 +1463,10 @@ pub fn uniform_grid_spacer(spacer: impl Fn(GridInput) -> [f64; 3] + 'static) ->
 
 struct PreparedPlot {
     items: Vec<Box<dyn PlotItem>>,
+
+    /// Fade out the items that aren't highlighted, because one is hovered in the legend.
+    dim_unhighlighted: bool,
+
     show_x: bool,
     show_y: bool,
     label_formatter: LabelFormatter,
@@ -1464,14 +1498,18 @@ impl PreparedPlot {
         // Sort the axes by strength so that those with higher strength are drawn in front.
         axes_shapes.sort_by(|(_, strength1), (_, strength2)| strength1.total_cmp(strength2));
 
-        let mut shapes = axes_shapes.into_iter().map(|(shape, _)| shape).collect();
+        let mut shapes: Vec<Shape> = axes_shapes.into_iter().map(|(shape, _)| shape).collect();
 
         let transform = &self.transform;
 
         let mut plot_ui = ui.child_ui(*transform.frame(), Layout::default());
         plot_ui.set_clip_rect(transform.frame().intersect(ui.clip_rect()));
         for item in &self.items {
+            let first_shape = shapes.len();
             item.shapes(&plot_ui, transform, &mut shapes);
+            if self.dim_unhighlighted && !item.highlighted() {
+                legend::dim_shapes(&mut shapes[first_shape..]);
+            }
         }
 
         let hover_pos = response.hover_pos();
@@ -20,6 +20,12 @@ pub struct PlotMemory {
     /// Which items _not_ to show?
     pub hidden_items: ahash::HashSet<String>,
 
+    /// The names of the legend entries, in the order they are shown.
+    ///
+    /// Changed by dragging the entries of a [`crate::Legend::reorderable`] legend.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub legend_order: Vec<String>,
+
     /// The transform from last frame.
     pub(crate) transform: PlotTransform,
 
@@ -215,6 +215,19 @@ impl PlotUi {
         self.items.push(Box::new(box_plot));
     }
 
+    /// Add a histogram, as a bar chart with a bar per bin.
+    pub fn histogram(&mut self, histogram: Histogram) {
+        self.bar_chart(histogram.into_bar_chart());
+    }
+
+    /// Add a candlestick (or OHLC) chart.
+    pub fn candlestick_chart(&mut self, chart: CandlestickChart) {
+        if chart.candles.is_empty() {
+            return;
+        }
+        self.items.push(Box::new(chart));
+    }
+
     /// Add a bar chart.
     pub fn bar_chart(&mut self, mut chart: BarChart) {
         if chart.bars.is_empty() {
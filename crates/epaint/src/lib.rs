@@ -28,6 +28,7 @@
 mod bezier;
 pub mod image;
 mod mesh;
+mod mesh_pool;
 pub mod mutex;
 mod shadow;
 mod shape;
@@ -45,6 +46,7 @@ pub use {
     bezier::{CubicBezierShape, QuadraticBezierShape},
     image::{ColorImage, FontImage, ImageData, ImageDelta},
     mesh::{Mesh, Mesh16, Vertex},
+    mesh_pool::MeshPool,
     shadow::Shadow,
     shape::{
         CircleShape, PaintCallback, PaintCallbackInfo, PathShape, RectShape, Rounding, Shape,
@@ -105,6 +107,7 @@ impl Default for TextureId {
 ///
 /// Everything is using logical points.
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct ClippedShape {
     /// Clip / scissor rectangle.
     /// Only show the part of the [`Shape`] that falls within this.
@@ -206,6 +209,15 @@ mod profiling_scopes {
         };
     }
     pub(crate) use profile_scope;
+
+    /// A [`tracing`](https://docs.rs/tracing) span until the end of the scope, for feature "tracing"
+    macro_rules! trace_span {
+        ($($arg: tt)*) => {
+            #[cfg(feature = "tracing")]
+            let _span = tracing::trace_span!($($arg)*).entered();
+        };
+    }
+    pub(crate) use trace_span;
 }
 
 #[allow(unused_imports)]
@@ -0,0 +1,151 @@
+//! Reuse of tessellation buffers between frames.
+
+use crate::{stats::MeshPoolStats, ClippedPrimitive, Mesh, Primitive, TextureId, Vertex};
+
+/// A pool of [`Mesh`]es whose vertex and index buffers can be reused by the [`crate::Tessellator`].
+///
+/// Tessellating a frame produces a handful of large meshes.
+/// Allocating them anew each frame shows up in profiles,
+/// so instead the integration can hand the meshes back once they have been painted
+/// (see [`Self::recycle`]), and the next frame will reuse their allocations.
+///
+/// Use with [`crate::Tessellator::tessellate_shapes_with_pool`].
+#[derive(Default)]
+pub struct MeshPool {
+    /// Empty meshes, ready for reuse.
+    meshes: Vec<Mesh>,
+
+    /// An empty list, ready to be filled with the next frame's primitives.
+    primitives: Vec<ClippedPrimitive>,
+
+    /// Capacity (in bytes) of each mesh handed out since the last call to [`Self::track_growth`].
+    taken_capacities: Vec<usize>,
+
+    stats: MeshPoolStats,
+}
+
+impl MeshPool {
+    /// Don't hold on to more meshes than this.
+    const MAX_POOLED_MESHES: usize = 1024;
+
+    /// Number of meshes ready for reuse.
+    pub fn len(&self) -> usize {
+        self.meshes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.meshes.is_empty()
+    }
+
+    /// Get an empty mesh, reusing an old allocation if possible.
+    pub fn take_mesh(&mut self) -> Mesh {
+        let mesh = if let Some(mesh) = self.meshes.pop() {
+            self.stats.meshes_reused += 1;
+            mesh
+        } else {
+            self.stats.meshes_allocated += 1;
+            Mesh::default()
+        };
+        self.taken_capacities.push(capacity_bytes(&mesh));
+        mesh
+    }
+
+    /// Get an empty list to put primitives in, reusing an old allocation if possible.
+    pub fn take_primitives(&mut self) -> Vec<ClippedPrimitive> {
+        std::mem::take(&mut self.primitives)
+    }
+
+    /// Hand back a mesh once you are done with it.
+    pub fn recycle_mesh(&mut self, mut mesh: Mesh) {
+        if self.meshes.len() < Self::MAX_POOLED_MESHES {
+            mesh.indices.clear();
+            mesh.vertices.clear();
+            mesh.texture_id = TextureId::default();
+            self.meshes.push(mesh);
+        }
+    }
+
+    /// Hand back the output of the tessellator once it has been painted.
+    ///
+    /// Their buffers will be reused next frame.
+    pub fn recycle(&mut self, mut primitives: Vec<ClippedPrimitive>) {
+        crate::profile_function!();
+
+        for clipped_primitive in primitives.drain(..) {
+            if let Primitive::Mesh(mesh) = clipped_primitive.primitive {
+                self.recycle_mesh(mesh);
+            }
+        }
+        if self.primitives.capacity() < primitives.capacity() {
+            self.primitives = primitives;
+        }
+    }
+
+    /// Compare the meshes handed out by [`Self::take_mesh`] with what they ended up as.
+    ///
+    /// The primitives must contain exactly the meshes handed out, in the same order.
+    pub(crate) fn track_growth(&mut self, primitives: &[ClippedPrimitive]) {
+        let meshes = primitives.iter().filter_map(|p| match &p.primitive {
+            Primitive::Mesh(mesh) => Some(mesh),
+            Primitive::Callback(_) => None,
+        });
+        for (mesh, taken_capacity) in meshes.zip(self.taken_capacities.drain(..)) {
+            let capacity = capacity_bytes(mesh);
+            if capacity > taken_capacity {
+                if taken_capacity > 0 {
+                    self.stats.meshes_grown += 1;
+                }
+                self.stats.bytes_allocated += capacity - taken_capacity;
+            }
+        }
+        self.taken_capacities.clear();
+    }
+
+    /// Statistics since the last call to this function.
+    pub fn take_stats(&mut self) -> MeshPoolStats {
+        let stats = MeshPoolStats {
+            meshes_pooled: self.meshes.len(),
+            ..self.stats
+        };
+        self.stats = Default::default();
+        stats
+    }
+}
+
+fn capacity_bytes(mesh: &Mesh) -> usize {
+    mesh.indices.capacity() * std::mem::size_of::<u32>()
+        + mesh.vertices.capacity() * std::mem::size_of::<Vertex>()
+}
+
+#[test]
+fn test_mesh_pool_reuse() {
+    use crate::{pos2, Color32, Rect, Shape, Stroke, TessellationOptions, Tessellator};
+
+    let shapes = || {
+        vec![crate::ClippedShape {
+            clip_rect: Rect::EVERYTHING,
+            shape: Shape::line_segment(
+                [pos2(0.0, 0.0), pos2(100.0, 100.0)],
+                Stroke::new(2.0, Color32::WHITE),
+            ),
+        }]
+    };
+    let mut tessellator = Tessellator::new(1.0, TessellationOptions::default(), [1, 1], vec![]);
+    let mut pool = MeshPool::default();
+
+    let primitives = tessellator.tessellate_shapes_with_pool(shapes(), &mut pool);
+    assert_eq!(primitives.len(), 1);
+    let stats = pool.take_stats();
+    assert_eq!(stats.meshes_allocated, 1);
+    assert!(stats.bytes_allocated > 0);
+
+    pool.recycle(primitives);
+    assert_eq!(pool.len(), 1);
+
+    let primitives = tessellator.tessellate_shapes_with_pool(shapes(), &mut pool);
+    assert_eq!(primitives.len(), 1);
+    let stats = pool.take_stats();
+    assert_eq!(stats.meshes_allocated, 0);
+    assert_eq!(stats.meshes_reused, 1);
+    assert_eq!(stats.bytes_allocated, 0);
+}
@@ -17,8 +17,11 @@ pub use crate::{CubicBezierShape, QuadraticBezierShape};
 /// but storing them should also be fine with one exception:
 /// [`Shape::Text`] depends on the current `pixels_per_point` (dpi scale)
 /// and so must be recreated every time `pixels_per_point` changes.
+///
+/// With the `serde` feature, all shapes except [`Shape::Callback`] can be serialized.
 #[must_use = "Add a Shape to a Painter"]
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum Shape {
     /// Paint nothing. This can be useful as a placeholder.
     Noop,
@@ -57,6 +60,9 @@ pub enum Shape {
     CubicBezier(CubicBezierShape),
 
     /// Backend-specific painting.
+    ///
+    /// Trying to serialize this is an error, since the callback is arbitrary code.
+    #[cfg_attr(feature = "serde", serde(skip))]
     Callback(PaintCallback),
 }
 
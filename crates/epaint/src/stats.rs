@@ -174,6 +174,28 @@ pub struct PaintStats {
     pub clipped_primitives: AllocInfo,
     pub vertices: AllocInfo,
     pub indices: AllocInfo,
+
+    /// How many tessellation buffers had to be allocated this frame.
+    pub mesh_pool: MeshPoolStats,
+}
+
+/// Allocation churn of a [`crate::MeshPool`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MeshPoolStats {
+    /// Number of meshes whose buffers were reused from an earlier frame.
+    pub meshes_reused: usize,
+
+    /// Number of meshes that had to be created because the pool was empty.
+    pub meshes_allocated: usize,
+
+    /// Number of reused meshes whose buffers were too small and had to grow.
+    pub meshes_grown: usize,
+
+    /// Bytes of buffer capacity allocated by new and grown meshes.
+    pub bytes_allocated: usize,
+
+    /// Number of meshes waiting in the pool, ready for reuse.
+    pub meshes_pooled: usize,
 }
 
 impl PaintStats {
@@ -1146,6 +1146,15 @@ impl Tessellator {
         &mut self,
         clipped_shape: ClippedShape,
         out_primitives: &mut Vec<ClippedPrimitive>,
+    ) {
+        self.tessellate_clipped_shape_impl(clipped_shape, out_primitives, None);
+    }
+
+    fn tessellate_clipped_shape_impl(
+        &mut self,
+        clipped_shape: ClippedShape,
+        out_primitives: &mut Vec<ClippedPrimitive>,
+        mut pool: Option<&mut MeshPool>,
     ) {
         let ClippedShape { clip_rect, shape } = clipped_shape;
 
@@ -1155,7 +1164,11 @@ impl Tessellator {
 
         if let Shape::Vec(shapes) = shape {
             for shape in shapes {
-                self.tessellate_clipped_shape(ClippedShape { clip_rect, shape }, out_primitives);
+                self.tessellate_clipped_shape_impl(
+                    ClippedShape { clip_rect, shape },
+                    out_primitives,
+                    pool.as_deref_mut(),
+                );
             }
             return;
         }
@@ -1184,7 +1197,7 @@ impl Tessellator {
         if start_new_mesh {
             out_primitives.push(ClippedPrimitive {
                 clip_rect,
-                primitive: Primitive::Mesh(Mesh::default()),
+                primitive: Primitive::Mesh(pool.map_or_else(Mesh::default, MeshPool::take_mesh)),
             });
         }
 
@@ -1714,24 +1727,56 @@ impl Tessellator {
     ///
     /// ## Returns
     /// A list of clip rectangles with matching [`Mesh`].
+    pub fn tessellate_shapes(&mut self, shapes: Vec<ClippedShape>) -> Vec<ClippedPrimitive> {
+        self.tessellate_shapes_impl(shapes, None)
+    }
+
+    /// Like [`Self::tessellate_shapes`], but reuses the buffers in the given pool.
+    ///
+    /// Hand the result back to [`MeshPool::recycle`] once it has been painted,
+    /// so that the next frame can reuse the allocations.
+    pub fn tessellate_shapes_with_pool(
+        &mut self,
+        shapes: Vec<ClippedShape>,
+        pool: &mut MeshPool,
+    ) -> Vec<ClippedPrimitive> {
+        self.tessellate_shapes_impl(shapes, Some(pool))
+    }
+
     #[allow(unused_mut)]
-    pub fn tessellate_shapes(&mut self, mut shapes: Vec<ClippedShape>) -> Vec<ClippedPrimitive> {
+    fn tessellate_shapes_impl(
+        &mut self,
+        mut shapes: Vec<ClippedShape>,
+        mut pool: Option<&mut MeshPool>,
+    ) -> Vec<ClippedPrimitive> {
         crate::profile_function!();
+        crate::trace_span!("epaint::tessellate");
 
         #[cfg(feature = "rayon")]
         if self.options.parallel_tessellation {
             self.parallel_tessellation_of_large_shapes(&mut shapes);
         }
 
-        let mut clipped_primitives: Vec<ClippedPrimitive> = Vec::default();
+        let mut clipped_primitives: Vec<ClippedPrimitive> = pool
+            .as_deref_mut()
+            .map(MeshPool::take_primitives)
+            .unwrap_or_default();
 
         {
             crate::profile_scope!("tessellate");
             for clipped_shape in shapes {
-                self.tessellate_clipped_shape(clipped_shape, &mut clipped_primitives);
+                self.tessellate_clipped_shape_impl(
+                    clipped_shape,
+                    &mut clipped_primitives,
+                    pool.as_deref_mut(),
+                );
             }
         }
 
+        if let Some(pool) = pool {
+            pool.track_growth(&clipped_primitives);
+        }
+
         if self.options.debug_paint_clip_rects {
             clipped_primitives = self.add_clip_rects(clipped_primitives);
         }
@@ -1,11 +1,11 @@
 use crate::{
     mutex::{Mutex, RwLock},
-    text::FontTweak,
+    text::{FontFeatures, FontTweak, FontVariations},
     TextureAtlas,
 };
 use emath::{vec2, Vec2};
 use std::collections::BTreeSet;
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 
 // ----------------------------------------------------------------------------
 
@@ -58,6 +58,377 @@ impl Default for GlyphInfo {
     }
 }
 
+/// A glyph after applying [`FontFeatures`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct SubstitutedGlyph {
+    pub glyph_info: GlyphInfo,
+
+    /// Part of a ligature, but not the first character of it,
+    /// so it takes up room but the ligature is drawn by the first character.
+    pub ligature_component: bool,
+}
+
+// ----------------------------------------------------------------------------
+
+/// The font file a [`FontImpl`] was loaded from.
+///
+/// `ab_glyph` has no support for OpenType layout, so we read the `GSUB` table
+/// from this ourselves to apply [`FontFeatures`].
+#[derive(Clone)]
+pub struct FontFile {
+    bytes: FontBytes,
+    index: u32,
+
+    /// Read from the `GSUB` table when first needed,
+    /// and shared by the [`FontImpl`]s of all sizes.
+    substitutions: Arc<OnceLock<Substitutions>>,
+}
+
+#[derive(Clone)]
+enum FontBytes {
+    Static(&'static [u8]),
+
+    /// Shared with the `ab_glyph` font, so we don't keep two copies of it.
+    Owned(Arc<ab_glyph::FontVec>),
+}
+
+impl FontFile {
+    pub(crate) fn from_static(bytes: &'static [u8], index: u32) -> Self {
+        Self {
+            bytes: FontBytes::Static(bytes),
+            index,
+            substitutions: Default::default(),
+        }
+    }
+
+    pub(crate) fn from_font_vec(font: Arc<ab_glyph::FontVec>, index: u32) -> Self {
+        Self {
+            bytes: FontBytes::Owned(font),
+            index,
+            substitutions: Default::default(),
+        }
+    }
+
+    /// A new instance of a variable font, with these axis values.
+    ///
+    /// Axes the font doesn't have are ignored.
+    pub(crate) fn instance(&self, variations: &FontVariations) -> Option<ab_glyph::FontArc> {
+        fn set_variations(font: &mut impl ab_glyph::VariableFont, variations: &FontVariations) {
+            for (tag, value) in variations.iter() {
+                font.set_variation(&tag, value);
+            }
+        }
+
+        match &self.bytes {
+            FontBytes::Static(bytes) => {
+                let mut font =
+                    ab_glyph::FontRef::try_from_slice_and_index(bytes, self.index).ok()?;
+                set_variations(&mut font, variations);
+                Some(font.into())
+            }
+            FontBytes::Owned(font) => {
+                let varied = VariedFont {
+                    font: font.clone(),
+                    index: self.index,
+                    variations: variations.clone(),
+                };
+                varied.varied()?;
+                Some(ab_glyph::FontArc::new(varied))
+            }
+        }
+    }
+
+    fn face(&self) -> Option<ttf_parser::Face<'_>> {
+        let bytes = match &self.bytes {
+            FontBytes::Static(bytes) => bytes,
+            FontBytes::Owned(font) => font.as_slice(),
+        };
+        ttf_parser::Face::parse(bytes, self.index).ok()
+    }
+
+    fn substitutions(&self) -> &Substitutions {
+        self.substitutions.get_or_init(|| {
+            self.face()
+                .map_or_else(Substitutions::default, |face| Substitutions::read(&face))
+        })
+    }
+}
+
+/// An instance of a variable font that shares the bytes of the font file.
+///
+/// `ab_glyph::FontVec` owns its bytes, so instead of copying the whole font for every
+/// set of axis values, we parse it again when we need something that depends on them.
+/// [`FontImpl`] caches the glyphs, so this happens about once per glyph and size.
+struct VariedFont {
+    font: Arc<ab_glyph::FontVec>,
+    index: u32,
+    variations: FontVariations,
+}
+
+impl VariedFont {
+    fn varied(&self) -> Option<ab_glyph::FontRef<'_>> {
+        use ab_glyph::VariableFont as _;
+
+        let mut font =
+            ab_glyph::FontRef::try_from_slice_and_index(self.font.as_slice(), self.index).ok()?;
+        for (tag, value) in self.variations.iter() {
+            font.set_variation(&tag, value);
+        }
+        Some(font)
+    }
+
+    fn varied_or_default<R>(&self, f: impl FnOnce(&dyn ab_glyph::Font) -> R) -> R {
+        match self.varied() {
+            Some(font) => f(&font),
+            None => f(&*self.font),
+        }
+    }
+}
+
+impl ab_glyph::Font for VariedFont {
+    fn units_per_em(&self) -> Option<f32> {
+        self.font.units_per_em()
+    }
+
+    fn ascent_unscaled(&self) -> f32 {
+        self.varied_or_default(|font| font.ascent_unscaled())
+    }
+
+    fn descent_unscaled(&self) -> f32 {
+        self.varied_or_default(|font| font.descent_unscaled())
+    }
+
+    fn line_gap_unscaled(&self) -> f32 {
+        self.varied_or_default(|font| font.line_gap_unscaled())
+    }
+
+    fn glyph_id(&self, c: char) -> ab_glyph::GlyphId {
+        self.font.glyph_id(c)
+    }
+
+    fn h_advance_unscaled(&self, id: ab_glyph::GlyphId) -> f32 {
+        self.varied_or_default(|font| font.h_advance_unscaled(id))
+    }
+
+    fn h_side_bearing_unscaled(&self, id: ab_glyph::GlyphId) -> f32 {
+        self.varied_or_default(|font| font.h_side_bearing_unscaled(id))
+    }
+
+    fn v_advance_unscaled(&self, id: ab_glyph::GlyphId) -> f32 {
+        self.varied_or_default(|font| font.v_advance_unscaled(id))
+    }
+
+    fn v_side_bearing_unscaled(&self, id: ab_glyph::GlyphId) -> f32 {
+        self.varied_or_default(|font| font.v_side_bearing_unscaled(id))
+    }
+
+    fn kern_unscaled(&self, first: ab_glyph::GlyphId, second: ab_glyph::GlyphId) -> f32 {
+        self.font.kern_unscaled(first, second)
+    }
+
+    fn outline(&self, id: ab_glyph::GlyphId) -> Option<ab_glyph::Outline> {
+        self.varied_or_default(|font| font.outline(id))
+    }
+
+    fn glyph_count(&self) -> usize {
+        self.font.glyph_count()
+    }
+
+    fn codepoint_ids(&self) -> ab_glyph::CodepointIdIter<'_> {
+        self.font.codepoint_ids()
+    }
+
+    #[allow(deprecated)]
+    fn glyph_raster_image(
+        &self,
+        id: ab_glyph::GlyphId,
+        pixel_size: u16,
+    ) -> Option<ab_glyph::GlyphImage<'_>> {
+        self.font.glyph_raster_image(id, pixel_size)
+    }
+}
+
+/// The glyph substitutions of the default script of a font, read from its `GSUB` table.
+#[derive(Default)]
+struct Substitutions {
+    /// The lookups of each feature.
+    features: Vec<([u8; 4], Vec<u16>)>,
+
+    /// The subtables of each lookup of [`Self::features`], by lookup index.
+    lookups: ahash::HashMap<u16, Vec<GlyphSubstitutions>>,
+
+    /// The result of [`Self::enabled_lookups`] for the [`FontFeatures`] used so far.
+    enabled_lookups_cache: Mutex<ahash::HashMap<FontFeatures, Arc<[(u16, u16)]>>>,
+}
+
+/// What a subtable of a lookup substitutes, by the glyph it starts at.
+type GlyphSubstitutions = ahash::HashMap<u16, Substitution>;
+
+enum Substitution {
+    Single(u16),
+
+    /// Picked by the value of the feature, with 1 being the first one.
+    Alternates(Vec<u16>),
+
+    /// The glyphs following the first one, and the ligature replacing them all.
+    Ligatures(Vec<(Vec<u16>, u16)>),
+}
+
+impl Substitutions {
+    fn read(face: &ttf_parser::Face<'_>) -> Self {
+        use ttf_parser::Tag;
+
+        let Some(gsub) = face.tables().gsub else {
+            return Self::default();
+        };
+
+        // We don't know the script of the text, so we use the features of the default one:
+        let script = gsub
+            .scripts
+            .find(Tag::from_bytes(b"DFLT"))
+            .or_else(|| gsub.scripts.find(Tag::from_bytes(b"latn")))
+            .or_else(|| gsub.scripts.get(0));
+        let Some(language) = script.and_then(|script| script.default_language) else {
+            return Self::default();
+        };
+
+        let features: Vec<([u8; 4], Vec<u16>)> = language
+            .feature_indices
+            .into_iter()
+            .filter_map(|feature_index| gsub.features.get(feature_index))
+            .map(|feature| {
+                let lookups = feature.lookup_indices.into_iter().collect();
+                (feature.tag.0.to_be_bytes(), lookups)
+            })
+            .collect();
+
+        let mut lookups = ahash::HashMap::default();
+        for &lookup_index in features.iter().flat_map(|(_, lookups)| lookups) {
+            lookups.entry(lookup_index).or_insert_with(|| {
+                gsub.lookups
+                    .get(lookup_index)
+                    .map_or_else(Vec::new, |lookup| {
+                        lookup
+                            .subtables
+                            .into_iter::<ttf_parser::gsub::SubstitutionSubtable<'_>>()
+                            .map(read_subtable)
+                            .collect()
+                    })
+            });
+        }
+
+        Self {
+            features,
+            lookups,
+            enabled_lookups_cache: Default::default(),
+        }
+    }
+
+    /// The lookups of the enabled `features`, in the order they are applied,
+    /// each with the value of its feature.
+    fn enabled_lookups(&self, features: &FontFeatures) -> Arc<[(u16, u16)]> {
+        let mut cache = self.enabled_lookups_cache.lock();
+        if let Some(lookups) = cache.get(features) {
+            return lookups.clone();
+        }
+        let lookups: Arc<[(u16, u16)]> = self.find_enabled_lookups(features).into();
+        cache.insert(features.clone(), lookups.clone());
+        lookups
+    }
+
+    fn find_enabled_lookups(&self, features: &FontFeatures) -> Vec<(u16, u16)> {
+        // Lookups are applied in the order they are in the font, not in the order of the features:
+        let mut lookups: Vec<(u16, u16)> = self
+            .features
+            .iter()
+            .filter_map(|(tag, lookups)| {
+                let value = features.value(tag);
+                (value != 0).then_some((lookups, value))
+            })
+            .flat_map(|(lookups, value)| {
+                lookups
+                    .iter()
+                    .map(move |&lookup_index| (lookup_index, value))
+            })
+            .collect();
+        lookups.sort_by_key(|(lookup_index, _)| *lookup_index);
+        lookups.dedup_by_key(|(lookup_index, _)| *lookup_index);
+        lookups
+    }
+}
+
+/// Read what a subtable of a `GSUB` lookup substitutes for each glyph it covers.
+fn read_subtable(subtable: ttf_parser::gsub::SubstitutionSubtable<'_>) -> GlyphSubstitutions {
+    use ttf_parser::{
+        gsub::{SingleSubstitution, SubstitutionSubtable},
+        opentype_layout::Coverage,
+        GlyphId,
+    };
+
+    let coverage = match &subtable {
+        SubstitutionSubtable::Single(single) => single.coverage(),
+        SubstitutionSubtable::Alternate(alternate) => alternate.coverage,
+        SubstitutionSubtable::Ligature(ligature) => ligature.coverage,
+        _ => return Default::default(), // Multiple and contextual substitutions are not supported.
+    };
+
+    let substitution = |glyph: GlyphId, coverage_index: u16| -> Option<Substitution> {
+        match &subtable {
+            SubstitutionSubtable::Single(single) => {
+                let substitute = match single {
+                    SingleSubstitution::Format1 { delta, .. } => {
+                        glyph.0.wrapping_add_signed(*delta)
+                    }
+                    SingleSubstitution::Format2 { substitutes, .. } => {
+                        substitutes.get(coverage_index)?.0
+                    }
+                };
+                Some(Substitution::Single(substitute))
+            }
+            SubstitutionSubtable::Alternate(alternate) => {
+                let set = alternate.alternate_sets.get(coverage_index)?;
+                let alternates = set.alternates.into_iter().map(|glyph| glyph.0).collect();
+                Some(Substitution::Alternates(alternates))
+            }
+            SubstitutionSubtable::Ligature(ligature) => {
+                let ligature_set = ligature.ligature_sets.get(coverage_index)?;
+                let ligatures = ligature_set
+                    .into_iter()
+                    .map(|ligature| {
+                        let components = ligature.components.into_iter().map(|glyph| glyph.0);
+                        (components.collect(), ligature.glyph.0)
+                    })
+                    .collect();
+                Some(Substitution::Ligatures(ligatures))
+            }
+            _ => None,
+        }
+    };
+
+    // Only visit the glyphs the subtable covers, each with its index in the coverage table:
+    let mut substitutions = GlyphSubstitutions::default();
+    let mut add = |glyph: u16, coverage_index: u16| {
+        if let Some(substitution) = substitution(GlyphId(glyph), coverage_index) {
+            substitutions.insert(glyph, substitution);
+        }
+    };
+    match coverage {
+        Coverage::Format1 { glyphs } => {
+            for (coverage_index, glyph) in glyphs.into_iter().enumerate() {
+                add(glyph.0, coverage_index as u16);
+            }
+        }
+        Coverage::Format2 { records } => {
+            for record in records {
+                for glyph in record.start.0..=record.end.0 {
+                    add(glyph, record.value.wrapping_add(glyph - record.start.0));
+                }
+            }
+        }
+    }
+    substitutions
+}
+
 // ----------------------------------------------------------------------------
 
 /// A specific font with a size.
@@ -77,6 +448,10 @@ pub struct FontImpl {
     ascent: f32,
     pixels_per_point: f32,
     glyph_info_cache: RwLock<ahash::HashMap<char, GlyphInfo>>, // TODO(emilk): standard Mutex
+
+    /// Glyphs that are not for a character of their own, like ligatures.
+    substituted_glyph_cache: RwLock<ahash::HashMap<ab_glyph::GlyphId, GlyphInfo>>,
+    font_file: Option<FontFile>,
     atlas: Arc<Mutex<TextureAtlas>>,
 }
 
@@ -86,6 +461,7 @@ impl FontImpl {
         pixels_per_point: f32,
         name: String,
         ab_glyph_font: ab_glyph::FontArc,
+        font_file: Option<FontFile>,
         scale_in_pixels: f32,
         tweak: FontTweak,
     ) -> Self {
@@ -131,6 +507,8 @@ impl FontImpl {
             ascent: ascent + baseline_offset,
             pixels_per_point,
             glyph_info_cache: Default::default(),
+            substituted_glyph_cache: Default::default(),
+            font_file,
             atlas,
         }
     }
@@ -231,6 +609,58 @@ impl FontImpl {
         }
     }
 
+    /// A glyph we didn't get by looking up a character, like a ligature.
+    fn substituted_glyph_info(&self, glyph_id: ab_glyph::GlyphId) -> GlyphInfo {
+        {
+            if let Some(glyph_info) = self.substituted_glyph_cache.read().get(&glyph_id) {
+                return *glyph_info;
+            }
+        }
+
+        let glyph_info = if glyph_id.0 == 0 {
+            GlyphInfo::default()
+        } else {
+            self.allocate_glyph(glyph_id)
+        };
+        self.substituted_glyph_cache
+            .write()
+            .insert(glyph_id, glyph_info);
+        glyph_info
+    }
+
+    /// Does this font substitute any glyphs with these `features`?
+    fn has_substitutions(&self, features: &FontFeatures) -> bool {
+        self.font_file.as_ref().map_or(false, |file| {
+            !file.substitutions().enabled_lookups(features).is_empty()
+        })
+    }
+
+    /// Apply the enabled OpenType `features` to a run of glyphs of this font,
+    /// each paired with the number of characters it stands for.
+    ///
+    /// A ligature replaces several glyphs with one.
+    fn substitute(&self, glyphs: &mut Vec<(ab_glyph::GlyphId, usize)>, features: &FontFeatures) {
+        let Some(font_file) = &self.font_file else {
+            return;
+        };
+        let substitutions = font_file.substitutions();
+
+        for &(lookup_index, value) in substitutions.enabled_lookups(features).iter() {
+            let Some(subtables) = substitutions.lookups.get(&lookup_index) else {
+                continue;
+            };
+            let mut index = 0;
+            while index < glyphs.len() {
+                for subtable in subtables {
+                    if apply_substitution(subtable, glyphs, index, value) {
+                        break;
+                    }
+                }
+                index += 1;
+            }
+        }
+    }
+
     #[inline]
     pub fn pair_kerning(
         &self,
@@ -435,6 +865,11 @@ impl Font {
         self.glyph_info(c) != self.replacement_glyph // TODO(emilk): this is a false negative if the user asks about the replacement character itself 🤦‍♂️
     }
 
+    /// The glyph we show for characters that none of the fonts have.
+    pub(crate) fn replacement_glyph_info(&self) -> GlyphInfo {
+        self.replacement_glyph.1
+    }
+
     /// Can we display all the glyphs in this text?
     pub fn has_glyphs(&mut self, s: &str) -> bool {
         s.chars().all(|c| self.has_glyph(c))
@@ -462,6 +897,86 @@ impl Font {
         (Some(font_impl), glyph_info)
     }
 
+    /// Apply the OpenType `features` to the glyphs of `text`.
+    ///
+    /// Returns one entry per `char`, which is `None` where the glyph is unchanged.
+    /// A ligature is drawn by its first character, and its width is shared evenly by all of them.
+    pub(crate) fn substitute_glyphs(
+        &mut self,
+        text: &str,
+        features: &FontFeatures,
+    ) -> Vec<Option<SubstitutedGlyph>> {
+        if !self
+            .fonts
+            .iter()
+            .any(|font| font.has_substitutions(features))
+        {
+            return vec![];
+        }
+
+        let glyphs: Vec<(Option<FontIndex>, GlyphInfo)> = text
+            .chars()
+            .map(|c| {
+                let (font_index, glyph_info) = self.glyph_info(c);
+                if c.is_control() || glyph_info.id.0 == 0 {
+                    (None, glyph_info) // Nothing to substitute, and no ligatures across it
+                } else {
+                    (Some(font_index), glyph_info)
+                }
+            })
+            .collect();
+
+        let mut substituted = vec![None; glyphs.len()];
+
+        // Each run of glyphs from the same font is substituted separately:
+        let mut start = 0;
+        while start < glyphs.len() {
+            let font_index = glyphs[start].0;
+            let end = glyphs[start..]
+                .iter()
+                .position(|(index, _)| *index != font_index)
+                .map_or(glyphs.len(), |len| start + len);
+
+            if let Some(font_index) = font_index {
+                let font_impl = &self.fonts[font_index];
+                let mut run: Vec<(ab_glyph::GlyphId, usize)> = glyphs[start..end]
+                    .iter()
+                    .map(|(_, glyph_info)| (glyph_info.id, 1))
+                    .collect();
+                font_impl.substitute(&mut run, features);
+
+                let mut i = start;
+                for (glyph_id, num_chars) in run {
+                    if 1 < num_chars || glyph_id != glyphs[i].1.id {
+                        let glyph_info = font_impl.substituted_glyph_info(glyph_id);
+                        let glyph_info = GlyphInfo {
+                            advance_width: glyph_info.advance_width / num_chars as f32,
+                            ..glyph_info
+                        };
+                        substituted[i] = Some(SubstitutedGlyph {
+                            glyph_info,
+                            ligature_component: false,
+                        });
+                        for component in &mut substituted[i + 1..i + num_chars] {
+                            *component = Some(SubstitutedGlyph {
+                                glyph_info: GlyphInfo {
+                                    uv_rect: Default::default(),
+                                    ..glyph_info
+                                },
+                                ligature_component: true,
+                            });
+                        }
+                    }
+                    i += num_chars;
+                }
+            }
+
+            start = end;
+        }
+
+        substituted
+    }
+
     fn glyph_info_no_cache_or_fallback(&mut self, c: char) -> Option<(FontIndex, GlyphInfo)> {
         for (font_index, font_impl) in self.fonts.iter().enumerate() {
             if let Some(glyph_info) = font_impl.glyph_info(c) {
@@ -473,6 +988,52 @@ impl Font {
     }
 }
 
+/// Apply one subtable of a `GSUB` lookup to the glyph at `index`, if it covers it.
+///
+/// Returns `true` if it did.
+fn apply_substitution(
+    subtable: &GlyphSubstitutions,
+    glyphs: &mut Vec<(ab_glyph::GlyphId, usize)>,
+    index: usize,
+    value: u16,
+) -> bool {
+    let Some(substitution) = subtable.get(&glyphs[index].0 .0) else {
+        return false;
+    };
+    let substitute = match substitution {
+        Substitution::Single(substitute) => Some(*substitute),
+        Substitution::Alternates(alternates) => {
+            let last = alternates.len().saturating_sub(1);
+            alternates.get((value as usize - 1).min(last)).copied()
+        }
+        Substitution::Ligatures(ligatures) => {
+            for (components, ligature) in ligatures {
+                let num_components = components.len();
+                let following = &glyphs[index + 1..];
+                if num_components <= following.len()
+                    && components
+                        .iter()
+                        .zip(following)
+                        .all(|(component, (glyph_id, _))| *component == glyph_id.0)
+                {
+                    let components = index..=index + num_components;
+                    let num_chars = glyphs[components.clone()].iter().map(|(_, n)| n).sum();
+                    glyphs.splice(components, [(ab_glyph::GlyphId(*ligature), num_chars)]);
+                    return true;
+                }
+            }
+            None
+        }
+    };
+
+    if let Some(substitute) = substitute {
+        glyphs[index].0 = ab_glyph::GlyphId(substitute);
+        true
+    } else {
+        false
+    }
+}
+
 /// Code points that will always be invisible (zero width).
 ///
 /// See also [`FontImpl::ignore_character`].
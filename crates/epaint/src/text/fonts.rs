@@ -1,9 +1,12 @@
-use std::{collections::BTreeMap, sync::Arc};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+};
 
 use crate::{
     mutex::{Mutex, MutexGuard},
     text::{
-        font::{Font, FontImpl},
+        font::{Font, FontFile, FontImpl},
         Galley, LayoutJob,
     },
     TextureAtlas,
@@ -63,6 +66,102 @@ impl std::hash::Hash for FontId {
 
 // ----------------------------------------------------------------------------
 
+/// Axis values for a variable font, e.g. its weight or width.
+///
+/// A single variable font file can replace a whole set of fonts,
+/// e.g. a light, regular and bold one.
+///
+/// ```
+/// # use epaint::text::{FontVariations, TextFormat};
+/// let semibold = TextFormat {
+///     font_variations: FontVariations::new().weight(600.0),
+///     ..Default::default()
+/// };
+/// let condensed = FontVariations::new().width(75.0).slant(-10.0);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct FontVariations {
+    /// Sorted by tag.
+    settings: Vec<([u8; 4], f32)>,
+}
+
+impl Eq for FontVariations {} // NaN values are never stored.
+
+#[allow(clippy::derived_hash_with_manual_eq)]
+impl std::hash::Hash for FontVariations {
+    #[inline]
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        for (tag, value) in &self.settings {
+            tag.hash(state);
+            crate::f32_hash(state, *value);
+        }
+    }
+}
+
+impl FontVariations {
+    /// No axis values set, i.e. the defaults of the font.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            settings: Vec::new(),
+        }
+    }
+
+    /// Set the value of the axis with this tag, e.g. `b"wght"`.
+    ///
+    /// Values outside the range of the font are clamped to it. NaN is ignored.
+    pub fn set(mut self, tag: &[u8; 4], value: f32) -> Self {
+        if value.is_nan() {
+            return self;
+        }
+        match self.settings.binary_search_by_key(tag, |(tag, _)| *tag) {
+            Ok(index) => self.settings[index].1 = value,
+            Err(index) => self.settings.insert(index, (*tag, value)),
+        }
+        self
+    }
+
+    /// The weight (`wght`), from 1 to 1000: 400 is regular and 700 is bold.
+    #[inline]
+    pub fn weight(self, weight: f32) -> Self {
+        self.set(b"wght", weight)
+    }
+
+    /// The width (`wdth`) in percent of the normal width: 75 is condensed and 125 is expanded.
+    #[inline]
+    pub fn width(self, width: f32) -> Self {
+        self.set(b"wdth", width)
+    }
+
+    /// The slant (`slnt`) in degrees, where negative values lean to the right.
+    #[inline]
+    pub fn slant(self, slant: f32) -> Self {
+        self.set(b"slnt", slant)
+    }
+
+    /// The value of this axis, if it has been set.
+    pub fn get(&self, tag: &[u8; 4]) -> Option<f32> {
+        self.settings
+            .binary_search_by_key(tag, |(tag, _)| *tag)
+            .ok()
+            .map(|index| self.settings[index].1)
+    }
+
+    /// No axis values set?
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.settings.is_empty()
+    }
+
+    /// All axis values that have been set, ordered by tag.
+    pub fn iter(&self) -> impl Iterator<Item = ([u8; 4], f32)> + '_ {
+        self.settings.iter().copied()
+    }
+}
+
+// ----------------------------------------------------------------------------
+
 /// Font of unknown size.
 ///
 /// Which style of font: [`Monospace`][`FontFamily::Monospace`], [`Proportional`][`FontFamily::Proportional`],
@@ -189,15 +288,26 @@ impl Default for FontTweak {
 
 // ----------------------------------------------------------------------------
 
-fn ab_glyph_font_from_font_data(name: &str, data: &FontData) -> ab_glyph::FontArc {
+fn ab_glyph_font_from_font_data(name: &str, data: &FontData) -> (ab_glyph::FontArc, FontFile) {
     match &data.font {
         std::borrow::Cow::Borrowed(bytes) => {
-            ab_glyph::FontRef::try_from_slice_and_index(bytes, data.index)
-                .map(ab_glyph::FontArc::from)
+            ab_glyph::FontRef::try_from_slice_and_index(bytes, data.index).map(|font| {
+                (
+                    ab_glyph::FontArc::from(font),
+                    FontFile::from_static(bytes, data.index),
+                )
+            })
         }
         std::borrow::Cow::Owned(bytes) => {
-            ab_glyph::FontVec::try_from_vec_and_index(bytes.clone(), data.index)
-                .map(ab_glyph::FontArc::from)
+            ab_glyph::FontVec::try_from_vec_and_index(bytes.clone(), data.index).map(|font| {
+                let font = Arc::new(font);
+                (
+                    ab_glyph::FontArc::from(
+                        font.clone() as Arc<dyn ab_glyph::Font + Send + Sync + 'static>
+                    ),
+                    FontFile::from_font_vec(font, data.index),
+                )
+            })
         }
     }
     .unwrap_or_else(|err| panic!("Error parsing {name:?} TTF/OTF font file: {err}"))
@@ -382,6 +492,7 @@ impl Fonts {
         let fonts_and_cache = FontsAndCache {
             fonts: FontsImpl::new(pixels_per_point, max_texture_side, definitions),
             galley_cache: Default::default(),
+            missing_glyphs: Default::default(),
         };
         Self(Arc::new(Mutex::new(fonts_and_cache)))
     }
@@ -408,12 +519,20 @@ impl Fonts {
             *fonts_and_cache = FontsAndCache {
                 fonts: FontsImpl::new(pixels_per_point, max_texture_side, definitions),
                 galley_cache: Default::default(),
+                missing_glyphs: std::mem::take(&mut fonts_and_cache.missing_glyphs),
             };
         }
 
         fonts_and_cache.galley_cache.flush_cache();
     }
 
+    /// The characters without a glyph in any font, in all the text laid out since the last call.
+    ///
+    /// These are shown as a replacement character. See [`Galley::missing_glyphs`].
+    pub fn take_missing_glyphs(&self) -> BTreeSet<char> {
+        std::mem::take(&mut self.lock().missing_glyphs)
+    }
+
     /// Call at the end of each frame (before painting) to get the change to the font texture since last call.
     pub fn font_image_delta(&self) -> Option<crate::ImageDelta> {
         self.lock().fonts.atlas.lock().take_delta()
@@ -495,6 +614,8 @@ impl Fonts {
     /// [`Self::layout_delayed_color`].
     ///
     /// The implementation uses memoization so repeated calls are cheap.
+    /// Large texts are memoized one paragraph at a time,
+    /// so changing one paragraph only lays out that paragraph again.
     #[inline]
     pub fn layout_job(&self, job: LayoutJob) -> Arc<Galley> {
         self.lock().layout_job(job)
@@ -504,6 +625,12 @@ impl Fonts {
         self.lock().galley_cache.num_galleys_in_cache()
     }
 
+    /// Number of galleys laid out since the last call to [`Self::begin_frame`],
+    /// i.e. the ones that were not found in the cache.
+    pub fn num_galleys_laid_out(&self) -> usize {
+        self.lock().galley_cache.num_laid_out
+    }
+
     /// How full is the font atlas?
     ///
     /// This increases as new fonts and/or glyphs are used,
@@ -557,11 +684,16 @@ impl Fonts {
 pub struct FontsAndCache {
     pub fonts: FontsImpl,
     galley_cache: GalleyCache,
+
+    /// See [`Fonts::take_missing_glyphs`].
+    missing_glyphs: BTreeSet<char>,
 }
 
 impl FontsAndCache {
     fn layout_job(&mut self, job: LayoutJob) -> Arc<Galley> {
-        self.galley_cache.layout(&mut self.fonts, job)
+        let galley = self.galley_cache.layout(&mut self.fonts, job);
+        self.missing_glyphs.extend(&galley.missing_glyphs);
+        galley
     }
 }
 
@@ -591,7 +723,7 @@ pub struct FontsImpl {
     definitions: FontDefinitions,
     atlas: Arc<Mutex<TextureAtlas>>,
     font_impl_cache: FontImplCache,
-    sized_family: ahash::HashMap<(HashableF32, FontFamily), Font>,
+    sized_family: ahash::HashMap<(HashableF32, FontFamily, FontVariations), Font>,
 }
 
 impl FontsImpl {
@@ -638,10 +770,20 @@ impl FontsImpl {
 
     /// Get the right font implementation from size and [`FontFamily`].
     pub fn font(&mut self, font_id: &FontId) -> &mut Font {
+        self.font_with_variations(font_id, &FontVariations::default())
+    }
+
+    /// Get the right font implementation from size, [`FontFamily`] and the axis values
+    /// of variable fonts.
+    pub fn font_with_variations(
+        &mut self,
+        font_id: &FontId,
+        variations: &FontVariations,
+    ) -> &mut Font {
         let FontId { size, family } = font_id;
 
         self.sized_family
-            .entry((HashableF32(*size), family.clone()))
+            .entry((HashableF32(*size), family.clone(), variations.clone()))
             .or_insert_with(|| {
                 let fonts = &self.definitions.families.get(family);
                 let fonts = fonts
@@ -649,7 +791,7 @@ impl FontsImpl {
 
                 let fonts: Vec<Arc<FontImpl>> = fonts
                     .iter()
-                    .map(|font_name| self.font_impl_cache.font_impl(*size, font_name))
+                    .map(|font_name| self.font_impl_cache.font_impl(*size, font_name, variations))
                     .collect();
 
                 Font::new(fonts)
@@ -683,19 +825,86 @@ struct CachedGalley {
     /// When it was last used
     last_used: u32,
     galley: Arc<Galley>,
+
+    /// If the galley was stacked together from separately laid out paragraphs,
+    /// these are their hashes, so we can keep them in the cache too.
+    paragraphs: Arc<[u64]>,
 }
 
+/// Jobs with more text than this (in bytes) are laid out one paragraph at a time,
+/// so that editing a large text only has to lay out the paragraphs that changed.
+const MIN_LEN_FOR_PARAGRAPH_CACHING: usize = 1024;
+
 #[derive(Default)]
 struct GalleyCache {
     /// Frame counter used to do garbage collection on the cache
     generation: u32,
     cache: nohash_hasher::IntMap<u64, CachedGalley>,
+
+    /// Number of galleys laid out since the last [`Self::flush_cache`].
+    num_laid_out: usize,
 }
 
 impl GalleyCache {
     fn layout(&mut self, fonts: &mut FontsImpl, job: LayoutJob) -> Arc<Galley> {
         let hash = crate::util::hash(&job); // TODO(emilk): even faster hasher?
 
+        if let Some(cached) = self.cache.get_mut(&hash) {
+            cached.last_used = self.generation;
+            let galley = cached.galley.clone();
+            let paragraphs = cached.paragraphs.clone();
+            for paragraph_hash in paragraphs.iter() {
+                if let Some(paragraph) = self.cache.get_mut(paragraph_hash) {
+                    paragraph.last_used = self.generation;
+                }
+            }
+            return galley;
+        }
+
+        let paragraph_jobs = if MIN_LEN_FOR_PARAGRAPH_CACHING < job.text.len() {
+            super::text_layout::split_paragraphs(&job)
+        } else {
+            None
+        };
+
+        let (galley, paragraphs): (_, Arc<[u64]>) = if let Some(paragraph_jobs) = paragraph_jobs {
+            let mut hashes = Vec::with_capacity(paragraph_jobs.len());
+            let mut galleys = Vec::with_capacity(paragraph_jobs.len());
+            for (paragraph_job, section_map) in paragraph_jobs {
+                let paragraph_hash = crate::util::hash(&paragraph_job);
+                let galley = self.layout_paragraph(fonts, paragraph_job, paragraph_hash);
+                hashes.push(paragraph_hash);
+                galleys.push((galley, section_map));
+            }
+            let galley = super::text_layout::concat_paragraphs(
+                job.into(),
+                &galleys,
+                fonts.pixels_per_point(),
+            );
+            (Arc::new(galley), hashes.into())
+        } else {
+            self.num_laid_out += 1;
+            (Arc::new(super::layout(fonts, job.into())), Arc::new([]))
+        };
+
+        self.cache.insert(
+            hash,
+            CachedGalley {
+                last_used: self.generation,
+                galley: galley.clone(),
+                paragraphs,
+            },
+        );
+        galley
+    }
+
+    /// Lay out a single paragraph, unless it is already in the cache.
+    fn layout_paragraph(
+        &mut self,
+        fonts: &mut FontsImpl,
+        job: LayoutJob,
+        hash: u64,
+    ) -> Arc<Galley> {
         match self.cache.entry(hash) {
             std::collections::hash_map::Entry::Occupied(entry) => {
                 let cached = entry.into_mut();
@@ -705,9 +914,11 @@ impl GalleyCache {
             std::collections::hash_map::Entry::Vacant(entry) => {
                 let galley = super::layout(fonts, job.into());
                 let galley = Arc::new(galley);
+                self.num_laid_out += 1;
                 entry.insert(CachedGalley {
                     last_used: self.generation,
                     galley: galley.clone(),
+                    paragraphs: Arc::new([]),
                 });
                 galley
             }
@@ -725,6 +936,7 @@ impl GalleyCache {
             cached.last_used == current_generation // only keep those that were used this frame
         });
         self.generation = self.generation.wrapping_add(1);
+        self.num_laid_out = 0;
     }
 }
 
@@ -733,10 +945,13 @@ impl GalleyCache {
 struct FontImplCache {
     atlas: Arc<Mutex<TextureAtlas>>,
     pixels_per_point: f32,
-    ab_glyph_fonts: BTreeMap<String, (FontTweak, ab_glyph::FontArc)>,
+    ab_glyph_fonts: BTreeMap<String, (FontTweak, ab_glyph::FontArc, FontFile)>,
 
-    /// Map font pixel sizes and names to the cached [`FontImpl`].
-    cache: ahash::HashMap<(u32, String), Arc<FontImpl>>,
+    /// Instances of variable fonts, by font name and axis values.
+    variable_fonts: ahash::HashMap<(String, FontVariations), ab_glyph::FontArc>,
+
+    /// Map font pixel sizes, names and axis values to the cached [`FontImpl`].
+    cache: ahash::HashMap<(u32, String, FontVariations), Arc<FontImpl>>,
 }
 
 impl FontImplCache {
@@ -749,8 +964,8 @@ impl FontImplCache {
             .iter()
             .map(|(name, font_data)| {
                 let tweak = font_data.tweak;
-                let ab_glyph = ab_glyph_font_from_font_data(name, font_data);
-                (name.clone(), (tweak, ab_glyph))
+                let (ab_glyph, font_file) = ab_glyph_font_from_font_data(name, font_data);
+                (name.clone(), (tweak, ab_glyph, font_file))
             })
             .collect();
 
@@ -758,19 +973,33 @@ impl FontImplCache {
             atlas,
             pixels_per_point,
             ab_glyph_fonts,
+            variable_fonts: Default::default(),
             cache: Default::default(),
         }
     }
 
-    pub fn font_impl(&mut self, scale_in_points: f32, font_name: &str) -> Arc<FontImpl> {
+    pub fn font_impl(
+        &mut self,
+        scale_in_points: f32,
+        font_name: &str,
+        variations: &FontVariations,
+    ) -> Arc<FontImpl> {
         use ab_glyph::Font as _;
 
-        let (tweak, ab_glyph_font) = self
+        let (tweak, mut ab_glyph_font, font_file) = self
             .ab_glyph_fonts
             .get(font_name)
             .unwrap_or_else(|| panic!("No font data found for {font_name:?}"))
             .clone();
 
+        if !variations.is_empty() {
+            ab_glyph_font = self
+                .variable_fonts
+                .entry((font_name.to_owned(), variations.clone()))
+                .or_insert_with(|| font_file.instance(variations).unwrap_or(ab_glyph_font))
+                .clone();
+        }
+
         let scale_in_pixels = self.pixels_per_point * scale_in_points;
 
         // Scale the font properly (see https://github.com/emilk/egui/issues/2068).
@@ -784,6 +1013,7 @@ impl FontImplCache {
             .entry((
                 (scale_in_pixels * tweak.scale).round() as u32,
                 font_name.to_owned(),
+                variations.clone(),
             ))
             .or_insert_with(|| {
                 Arc::new(FontImpl::new(
@@ -791,6 +1021,7 @@ impl FontImplCache {
                     self.pixels_per_point,
                     font_name.to_owned(),
                     ab_glyph_font,
+                    Some(font_file),
                     scale_in_pixels,
                     tweak,
                 ))
@@ -798,3 +1029,63 @@ impl FontImplCache {
             .clone()
     }
 }
+
+#[test]
+fn test_paragraph_caching() {
+    let fonts = Fonts::new(1.0, 1024, FontDefinitions::default());
+    let layout = |text: &str| {
+        fonts.begin_frame(1.0, 1024);
+        let job = LayoutJob::simple(
+            text.to_owned(),
+            FontId::default(),
+            crate::Color32::WHITE,
+            100.0,
+        );
+        let galley = fonts.layout_job(job);
+        (galley.rows.len(), fonts.num_galleys_laid_out())
+    };
+
+    let paragraphs: Vec<String> = (0..100).map(|i| format!("Paragraph number {i}")).collect();
+    let (num_rows, num_laid_out) = layout(&paragraphs.join("\n"));
+    assert_eq!(num_laid_out, 100);
+
+    // Only the edited paragraph needs to be laid out again:
+    let mut edited = paragraphs.clone();
+    edited[50].push_str(", edited");
+    assert_eq!(layout(&edited.join("\n")), (num_rows + 1, 1));
+
+    // Undoing the edit is just as cheap:
+    assert_eq!(layout(&paragraphs.join("\n")), (num_rows, 1));
+}
+
+#[test]
+fn test_font_variations() {
+    let variations = FontVariations::new()
+        .weight(700.0)
+        .width(80.0)
+        .weight(600.0);
+    assert_eq!(variations.get(b"wght"), Some(600.0));
+    assert_eq!(variations.get(b"slnt"), None);
+    let tags: Vec<[u8; 4]> = variations.iter().map(|(tag, _)| tag).collect();
+    assert_eq!(tags, vec![*b"wdth", *b"wght"]);
+    assert!(FontVariations::new().set(b"wght", f32::NAN).is_empty());
+
+    // The default fonts aren't variable, so they ignore the axis values:
+    let fonts = Fonts::new(1.0, 1024, FontDefinitions::default());
+    let size = |font_variations: FontVariations| {
+        let mut job = LayoutJob::default();
+        job.append(
+            "Hello",
+            0.0,
+            crate::text::TextFormat {
+                font_variations,
+                ..Default::default()
+            },
+        );
+        fonts.layout_job(job).size()
+    };
+    assert_eq!(
+        size(FontVariations::new().weight(700.0)),
+        size(FontVariations::default())
+    );
+}
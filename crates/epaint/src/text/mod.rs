@@ -10,7 +10,9 @@ mod text_layout_types;
 pub const TAB_SIZE: usize = 4;
 
 pub use {
-    fonts::{FontData, FontDefinitions, FontFamily, FontId, FontTweak, Fonts, FontsImpl},
+    fonts::{
+        FontData, FontDefinitions, FontFamily, FontId, FontTweak, FontVariations, Fonts, FontsImpl,
+    },
     text_layout::layout,
     text_layout_types::*,
 };
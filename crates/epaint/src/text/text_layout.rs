@@ -1,11 +1,23 @@
+use std::collections::BTreeSet;
 use std::ops::RangeInclusive;
 use std::sync::Arc;
 
 use emath::*;
 
-use crate::{text::font::Font, Color32, Mesh, Stroke, Vertex};
+use crate::{
+    text::font::{Font, GlyphInfo},
+    Color32, FontId, Mesh, Stroke, Vertex,
+};
 
-use super::{FontsImpl, Galley, Glyph, LayoutJob, LayoutSection, Row, RowVisuals};
+use super::{FontsImpl, Galley, Glyph, LayoutJob, LayoutSection, LineBreak, Row, RowVisuals};
+
+const SOFT_HYPHEN: char = '\u{AD}';
+const ZERO_WIDTH_SPACE: char = '\u{200B}';
+const ZERO_WIDTH_JOINER: char = '\u{200D}';
+const WORD_JOINER: char = '\u{2060}';
+
+/// The size of synthesized small capitals, relative to the font size.
+const SMALL_CAPS_SCALE: f32 = 0.7;
 
 // ----------------------------------------------------------------------------
 
@@ -70,6 +82,8 @@ impl Paragraph {
 /// In most cases you should use [`crate::Fonts::layout_job`] instead
 /// since that memoizes the input, making subsequent layouting of the same text much faster.
 pub fn layout(fonts: &mut FontsImpl, job: Arc<LayoutJob>) -> Galley {
+    crate::trace_span!("epaint::layout");
+
     if job.wrap.max_rows == 0 {
         // Early-out: no text
         return Galley {
@@ -81,20 +95,30 @@ pub fn layout(fonts: &mut FontsImpl, job: Arc<LayoutJob>) -> Galley {
             num_indices: 0,
             pixels_per_point: fonts.pixels_per_point(),
             elided: true,
+            missing_glyphs: vec![],
         };
     }
 
     // For most of this we ignore the y coordinate:
 
     let mut paragraphs = vec![Paragraph::from_section_index(0)];
+    let mut missing_glyphs = BTreeSet::new();
     for (section_index, section) in job.sections.iter().enumerate() {
-        layout_section(fonts, &job, section_index as u32, section, &mut paragraphs);
+        layout_section(
+            fonts,
+            &job,
+            section_index as u32,
+            section,
+            &mut paragraphs,
+            &mut missing_glyphs,
+        );
     }
 
     let point_scale = PointScale::new(fonts.pixels_per_point());
 
     let mut elided = false;
     let mut rows = rows_from_paragraphs(paragraphs, &job, &mut elided);
+    show_soft_hyphens(fonts, &job, &mut rows);
     if elided {
         if let Some(last_row) = rows.last_mut() {
             replace_last_glyph_with_overflow_character(fonts, &job, last_row);
@@ -119,7 +143,9 @@ pub fn layout(fonts: &mut FontsImpl, job: Arc<LayoutJob>) -> Galley {
     }
 
     // Calculate the Y positions and tessellate the text:
-    galley_from_rows(point_scale, job, rows, elided)
+    let mut galley = galley_from_rows(point_scale, job, rows, elided);
+    galley.missing_glyphs = missing_glyphs.into_iter().collect();
+    galley
 }
 
 // Ignores the Y coordinate.
@@ -129,18 +155,56 @@ fn layout_section(
     section_index: u32,
     section: &LayoutSection,
     out_paragraphs: &mut Vec<Paragraph>,
+    missing_glyphs: &mut BTreeSet<char>,
 ) {
     let LayoutSection {
         leading_space,
         byte_range,
         format,
     } = section;
-    let font = fonts.font(&format.font_id);
+    let point_scale = PointScale::new(fonts.pixels_per_point());
+    let text = &job.text[byte_range.clone()];
+
+    // Synthesized small capitals are the uppercase glyphs of a smaller font:
+    let small_caps: Vec<Option<(f32, GlyphInfo)>> = if format.small_caps {
+        let small_font = fonts.font_with_variations(
+            &FontId::new(
+                format.font_id.size * SMALL_CAPS_SCALE,
+                format.font_id.family.clone(),
+            ),
+            &format.font_variations,
+        );
+        text.chars()
+            .map(|chr| {
+                let mut uppercase = chr.to_uppercase();
+                let (true, Some(upper), None) =
+                    (chr.is_lowercase(), uppercase.next(), uppercase.next())
+                else {
+                    return None;
+                };
+                let (font_impl, glyph_info) = small_font.font_impl_and_glyph_info(upper);
+                Some((font_impl.map_or(0.0, |font| font.ascent()), glyph_info))
+            })
+            .collect()
+    } else {
+        vec![]
+    };
+    let bold_offset = if format.bold {
+        faux_bold_offset(point_scale, format.font_id.size)
+    } else {
+        0.0
+    };
+
+    let font = fonts.font_with_variations(&format.font_id, &format.font_variations);
+    let replacement_glyph_info = font.replacement_glyph_info();
+    let substituted = font.substitute_glyphs(text, &format.font_features);
+    let kerning = format.font_features.value(b"kern") != 0;
     let line_height = section
         .format
         .line_height
         .unwrap_or_else(|| font.row_height());
     let extra_letter_spacing = section.format.extra_letter_spacing;
+    let extra_word_spacing = section.format.extra_word_spacing;
 
     let mut paragraph = out_paragraphs.last_mut().unwrap();
     if paragraph.glyphs.is_empty() {
@@ -149,38 +213,95 @@ fn layout_section(
 
     paragraph.cursor_x += leading_space;
 
+    // `Some(None)` after a small capital, which we don't kern with the glyphs of the regular font.
     let mut last_glyph_id = None;
 
-    for chr in job.text[byte_range.clone()].chars() {
+    for (i, chr) in text.chars().enumerate() {
         if job.break_on_newline && chr == '\n' {
             out_paragraphs.push(Paragraph::from_section_index(section_index));
             paragraph = out_paragraphs.last_mut().unwrap();
             paragraph.empty_paragraph_height = line_height; // TODO(emilk): replace this hack with actually including `\n` in the glyphs?
+            last_glyph_id = None; // No kerning across paragraphs
         } else {
             let (font_impl, glyph_info) = font.font_impl_and_glyph_info(chr);
-            if let Some(font_impl) = font_impl {
-                if let Some(last_glyph_id) = last_glyph_id {
-                    paragraph.cursor_x += font_impl.pair_kerning(last_glyph_id, glyph_info.id);
-                    paragraph.cursor_x += extra_letter_spacing;
+            if matches!(chr, SOFT_HYPHEN | ZERO_WIDTH_SPACE | WORD_JOINER) {
+                // Invisible, unless a row breaks at a soft hyphen (see `show_soft_hyphens`):
+                paragraph.glyphs.push(Glyph {
+                    chr,
+                    pos: pos2(paragraph.cursor_x, f32::NAN),
+                    size: vec2(0.0, line_height),
+                    ascent: font_impl.map_or(0.0, |font| font.ascent()),
+                    uv_rect: Default::default(),
+                    section_index,
+                });
+                continue;
+            }
+            if !chr.is_control() && glyph_info == replacement_glyph_info {
+                missing_glyphs.insert(chr);
+            }
+            let small_cap = small_caps.get(i).copied().flatten();
+            let (glyph_info, ligature_component) = match substituted.get(i).copied().flatten() {
+                Some(substituted) => (substituted.glyph_info, substituted.ligature_component),
+                None => (glyph_info, false),
+            };
+            if let Some(last_glyph_id) = last_glyph_id {
+                if let (Some(font_impl), Some(last_glyph_id), None, false) =
+                    (font_impl, last_glyph_id, small_cap, ligature_component)
+                {
+                    if kerning {
+                        paragraph.cursor_x += font_impl.pair_kerning(last_glyph_id, glyph_info.id);
+                    }
                 }
+                paragraph.cursor_x += extra_letter_spacing;
+            }
+
+            let (ascent, glyph_info) = small_cap.unwrap_or_else(|| {
+                (font_impl.map_or(0.0, |font| font.ascent()), glyph_info) // Failure to find the font here would be weird
+            });
+            let mut advance_width = glyph_info.advance_width + bold_offset;
+            if chr == ' ' {
+                advance_width += extra_word_spacing;
             }
 
             paragraph.glyphs.push(Glyph {
                 chr,
                 pos: pos2(paragraph.cursor_x, f32::NAN),
-                size: vec2(glyph_info.advance_width, line_height),
-                ascent: font_impl.map_or(0.0, |font| font.ascent()), // Failure to find the font here would be weird
+                size: vec2(advance_width, line_height),
+                ascent,
                 uv_rect: glyph_info.uv_rect,
                 section_index,
             });
 
-            paragraph.cursor_x += glyph_info.advance_width;
+            paragraph.cursor_x += advance_width;
             paragraph.cursor_x = font.round_to_pixel(paragraph.cursor_x);
-            last_glyph_id = Some(glyph_info.id);
+            last_glyph_id = Some(small_cap.is_none().then_some(glyph_info.id));
         }
     }
 }
 
+/// Show a hyphen at the end of the rows that were broken at a soft hyphen.
+fn show_soft_hyphens(fonts: &mut FontsImpl, job: &LayoutJob, rows: &mut [Row]) {
+    let num_rows = rows.len();
+    for row in &mut rows[..num_rows.saturating_sub(1)] {
+        if row.ends_with_newline {
+            continue;
+        }
+        let Some(glyph) = row.glyphs.last_mut() else {
+            continue;
+        };
+        if glyph.chr != SOFT_HYPHEN {
+            continue;
+        }
+        let format = &job.sections[glyph.section_index as usize].format;
+        let (_, hyphen) = fonts
+            .font_with_variations(&format.font_id, &format.font_variations)
+            .font_impl_and_glyph_info('-');
+        glyph.size.x = hyphen.advance_width;
+        glyph.uv_rect = hyphen.uv_rect;
+        row.rect.max.x = row.rect.max.x.max(glyph.max_x());
+    }
+}
+
 /// We ignore y at this stage
 fn rect_from_x_range(x_range: RangeInclusive<f32>) -> Rect {
     Rect::from_x_y_ranges(x_range, 0.0..=0.0)
@@ -302,7 +423,7 @@ fn line_break(paragraph: &Paragraph, job: &LayoutJob, out_rows: &mut Vec<Row>, e
             }
         }
 
-        row_break_candidates.add(i, &paragraph.glyphs[i..]);
+        row_break_candidates.add(i, &paragraph.glyphs[i..], &job.sections);
     }
 
     if row_start_idx < paragraph.glyphs.len() {
@@ -366,7 +487,8 @@ fn replace_last_glyph_with_overflow_character(
     if let Some(last_glyph) = row.glyphs.last() {
         let section_index = last_glyph.section_index;
         let section = &job.sections[section_index as usize];
-        let font = fonts.font(&section.format.font_id);
+        let font =
+            fonts.font_with_variations(&section.format.font_id, &section.format.font_variations);
         let line_height = row_height(section, font);
 
         let (_, last_glyph_info) = font.font_impl_and_glyph_info(last_glyph.chr);
@@ -394,7 +516,8 @@ fn replace_last_glyph_with_overflow_character(
     } else {
         let section_index = row.section_index_at_start;
         let section = &job.sections[section_index as usize];
-        let font = fonts.font(&section.format.font_id);
+        let font =
+            fonts.font_with_variations(&section.format.font_id, &section.format.font_variations);
         let line_height = row_height(section, font);
 
         let x = 0.0; // TODO(emilk): heed paragraph leading_space 😬
@@ -432,7 +555,8 @@ fn replace_last_glyph_with_overflow_character(
 
         let section = &job.sections[last_glyph.section_index as usize];
         let extra_letter_spacing = section.format.extra_letter_spacing;
-        let font = fonts.font(&section.format.font_id);
+        let font =
+            fonts.font_with_variations(&section.format.font_id, &section.format.font_variations);
         let line_height = row_height(section, font);
 
         if let Some(prev_glyph) = prev_glyph {
@@ -630,12 +754,17 @@ fn galley_from_rows(
         num_indices += row.visuals.mesh.indices.len();
     }
 
-    let rect = Rect::from_min_max(pos2(min_x, 0.0), pos2(max_x, cursor_y));
+    let mut rect = Rect::from_min_max(pos2(min_x, 0.0), pos2(max_x, cursor_y));
+
+    if job.vertical {
+        rows_to_columns(point_scale, &job, &mut rows, &mut rect, &mut mesh_bounds);
+    }
 
     Galley {
         job,
         rows,
         elided,
+        missing_glyphs: vec![],
         rect,
         mesh_bounds,
         num_vertices,
@@ -644,6 +773,73 @@ fn galley_from_rows(
     }
 }
 
+/// Turn the rows into columns, from right to left, for [`LayoutJob::vertical`].
+///
+/// Everything is turned a quarter turn clockwise, and then the upright glyphs (e.g. CJK) are turned back.
+fn rows_to_columns(
+    point_scale: PointScale,
+    job: &LayoutJob,
+    rows: &mut [Row],
+    rect: &mut Rect,
+    mesh_bounds: &mut Rect,
+) {
+    let height = rect.max.y;
+    let turn = |pos: Pos2| pos2(height - pos.y, pos.x);
+    let turn_rect = |rect: Rect| Rect::from_two_pos(turn(rect.min), turn(rect.max));
+
+    *mesh_bounds = Rect::NOTHING;
+    for row in rows {
+        let mesh = &mut row.visuals.mesh;
+        let mut positions: Vec<Pos2> = mesh.vertices.iter().map(|v| turn(v.pos)).collect();
+
+        // Each visible glyph has a quad of four vertices, or two for synthesized bold:
+        let mut quad_start = row.visuals.glyph_vertex_range.start;
+        for glyph in &row.glyphs {
+            if glyph.uv_rect.is_nothing() {
+                continue;
+            }
+            let num_vertices = if job.sections[glyph.section_index as usize].format.bold {
+                8
+            } else {
+                4
+            };
+            let quad = quad_start..quad_start + num_vertices;
+            quad_start += num_vertices;
+            if !is_upright_in_vertical_text(glyph.chr) {
+                continue;
+            }
+
+            // Keep the ink where it was in the cell of the glyph:
+            let cell = Rect::from_x_y_ranges(glyph.pos.x..=glyph.max_x(), row.rect.y_range());
+            let ink_center = mesh.vertices[quad.clone()]
+                .iter()
+                .fold(Vec2::ZERO, |sum, v| sum + v.pos.to_vec2())
+                / num_vertices as f32;
+            let mut offset = ink_center.to_pos2() - cell.center();
+            if is_vertical_corner_punctuation(glyph.chr) {
+                // From the bottom left corner to the top right one:
+                offset = -offset;
+            }
+            let delta = turn(cell.center()) + offset - ink_center.to_pos2();
+            let delta = vec2(
+                point_scale.round_to_pixel(delta.x),
+                point_scale.round_to_pixel(delta.y),
+            );
+            for i in quad {
+                positions[i] = mesh.vertices[i].pos + delta;
+            }
+        }
+
+        for (vertex, pos) in mesh.vertices.iter_mut().zip(positions) {
+            vertex.pos = pos;
+        }
+        row.visuals.mesh_bounds = mesh.calc_bounds();
+        *mesh_bounds = mesh_bounds.union(row.visuals.mesh_bounds);
+        row.rect = turn_rect(row.rect);
+    }
+    *rect = turn_rect(*rect);
+}
+
 #[derive(Default)]
 struct FormatSummary {
     any_background: bool,
@@ -651,6 +847,162 @@ struct FormatSummary {
     any_strikethrough: bool,
 }
 
+/// Split a job with many paragraphs into one job per paragraph,
+/// so that each paragraph can be laid out (and cached) on its own.
+///
+/// Each job comes with a map from its section indices to those of the original `job`.
+///
+/// Returns `None` if the job has only one paragraph,
+/// or if laying out the paragraphs separately could give a different result.
+pub(crate) fn split_paragraphs(job: &LayoutJob) -> Option<Vec<(LayoutJob, Vec<u32>)>> {
+    let LayoutJob {
+        text,
+        sections,
+        wrap,
+        first_row_min_height,
+        break_on_newline,
+        halign,
+        justify,
+        vertical,
+    } = job;
+
+    // Eliding depends on the rows of all paragraphs together:
+    if !break_on_newline || wrap.max_rows != usize::MAX {
+        return None;
+    }
+
+    // The paragraphs are stacked horizontally:
+    if *vertical {
+        return None;
+    }
+
+    // We need the sections to cover the text, in order:
+    let contiguous = sections.first()?.byte_range.start == 0
+        && sections.last()?.byte_range.end == text.len()
+        && sections
+            .windows(2)
+            .all(|w| w[0].byte_range.end == w[1].byte_range.start)
+        && sections
+            .iter()
+            .all(|s| s.byte_range.start <= s.byte_range.end);
+    if !contiguous {
+        return None;
+    }
+
+    let newlines = text.bytes().enumerate().filter(|(_, b)| *b == b'\n');
+    let mut paragraph_ranges = vec![];
+    let mut start = 0;
+    for (newline, _) in newlines {
+        paragraph_ranges.push(start..newline);
+        start = newline + 1;
+    }
+    if paragraph_ranges.is_empty() {
+        return None;
+    }
+    paragraph_ranges.push(start..text.len());
+
+    let mut jobs = Vec::with_capacity(paragraph_ranges.len());
+    let mut opener = 0; // The section containing the newline that starts the paragraph
+    for (i, range) in paragraph_ranges.into_iter().enumerate() {
+        let mut paragraph_sections = vec![];
+        let mut section_map = vec![];
+        let mut add_section = |index: usize, leading_space: f32| {
+            let section = &sections[index];
+            paragraph_sections.push(LayoutSection {
+                leading_space,
+                byte_range: section.byte_range.start.max(range.start) - range.start
+                    ..section.byte_range.end.min(range.end) - range.start,
+                format: section.format.clone(),
+            });
+            section_map.push(index as u32);
+        };
+
+        let mut next = 0;
+        if i > 0 {
+            while sections[opener].byte_range.end < range.start {
+                opener += 1;
+            }
+            // The leading space was added to the previous paragraph:
+            add_section(opener, 0.0);
+            next = opener + 1;
+        }
+        while next < sections.len() && sections[next].byte_range.start <= range.end {
+            add_section(next, sections[next].leading_space);
+            next += 1;
+        }
+
+        jobs.push((
+            LayoutJob {
+                text: text[range].to_owned(),
+                sections: paragraph_sections,
+                wrap: wrap.clone(),
+                first_row_min_height: if i == 0 { *first_row_min_height } else { 0.0 },
+                break_on_newline: true,
+                halign: *halign,
+                justify: *justify,
+                vertical: false,
+            },
+            section_map,
+        ));
+    }
+    Some(jobs)
+}
+
+/// Stack the galleys of the paragraphs from [`split_paragraphs`] into one galley for the whole `job`.
+pub(crate) fn concat_paragraphs(
+    job: Arc<LayoutJob>,
+    paragraphs: &[(Arc<Galley>, Vec<u32>)],
+    pixels_per_point: f32,
+) -> Galley {
+    let mut rows = Vec::with_capacity(paragraphs.iter().map(|(g, _)| g.rows.len()).sum());
+    let mut rect = Rect::ZERO;
+    let mut mesh_bounds = Rect::NOTHING;
+    let mut num_vertices = 0;
+    let mut num_indices = 0;
+    let mut missing_glyphs = BTreeSet::new();
+
+    for (i, (galley, section_map)) in paragraphs.iter().enumerate() {
+        let offset = vec2(0.0, rect.max.y);
+        // Unlike `Rect::translate`, this keeps `Rect::NOTHING` as is:
+        let translate = |rect: Rect| Rect::from_min_max(rect.min + offset, rect.max + offset);
+        for row in &galley.rows {
+            let mut row = row.clone();
+            row.section_index_at_start = section_map[row.section_index_at_start as usize];
+            for glyph in &mut row.glyphs {
+                glyph.pos += offset;
+                glyph.section_index = section_map[glyph.section_index as usize];
+            }
+            row.rect = translate(row.rect);
+            row.visuals.mesh.translate(offset);
+            row.visuals.mesh_bounds = translate(row.visuals.mesh_bounds);
+            rows.push(row);
+        }
+        if let Some(last_row) = rows.last_mut() {
+            last_row.ends_with_newline = i + 1 < paragraphs.len();
+        }
+
+        rect.min.x = rect.min.x.min(galley.rect.min.x);
+        rect.max.x = rect.max.x.max(galley.rect.max.x);
+        rect.max.y += galley.rect.max.y;
+        mesh_bounds = mesh_bounds.union(translate(galley.mesh_bounds));
+        num_vertices += galley.num_vertices;
+        num_indices += galley.num_indices;
+        missing_glyphs.extend(&galley.missing_glyphs);
+    }
+
+    Galley {
+        job,
+        rows,
+        elided: false,
+        missing_glyphs: missing_glyphs.into_iter().collect(),
+        rect,
+        mesh_bounds,
+        num_vertices,
+        num_indices,
+        pixels_per_point,
+    }
+}
+
 fn format_summary(job: &LayoutJob) -> FormatSummary {
     let mut format_summary = FormatSummary::default();
     for section in &job.sections {
@@ -774,40 +1126,60 @@ fn tessellate_glyphs(point_scale: PointScale, job: &LayoutJob, row: &Row, mesh:
 
             let color = format.color;
 
-            if format.italics {
-                let idx = mesh.vertices.len() as u32;
-                mesh.add_triangle(idx, idx + 1, idx + 2);
-                mesh.add_triangle(idx + 2, idx + 1, idx + 3);
-
-                let top_offset = rect.height() * 0.25 * Vec2::X;
-
-                mesh.vertices.push(Vertex {
-                    pos: rect.left_top() + top_offset,
-                    uv: uv.left_top(),
-                    color,
-                });
-                mesh.vertices.push(Vertex {
-                    pos: rect.right_top() + top_offset,
-                    uv: uv.right_top(),
-                    color,
-                });
-                mesh.vertices.push(Vertex {
-                    pos: rect.left_bottom(),
-                    uv: uv.left_bottom(),
-                    color,
-                });
-                mesh.vertices.push(Vertex {
-                    pos: rect.right_bottom(),
-                    uv: uv.right_bottom(),
-                    color,
-                });
-            } else {
-                mesh.add_rect_with_uv(rect, uv, color);
+            // Synthesized bold draws the glyph twice:
+            let copies = if format.bold { 2 } else { 1 };
+            for copy in 0..copies {
+                let rect = if copy == 0 {
+                    rect
+                } else {
+                    rect.translate(vec2(
+                        faux_bold_offset(point_scale, format.font_id.size),
+                        0.0,
+                    ))
+                };
+
+                if format.italics {
+                    let idx = mesh.vertices.len() as u32;
+                    mesh.add_triangle(idx, idx + 1, idx + 2);
+                    mesh.add_triangle(idx + 2, idx + 1, idx + 3);
+
+                    let top_offset = rect.height() * 0.25 * Vec2::X;
+
+                    mesh.vertices.push(Vertex {
+                        pos: rect.left_top() + top_offset,
+                        uv: uv.left_top(),
+                        color,
+                    });
+                    mesh.vertices.push(Vertex {
+                        pos: rect.right_top() + top_offset,
+                        uv: uv.right_top(),
+                        color,
+                    });
+                    mesh.vertices.push(Vertex {
+                        pos: rect.left_bottom(),
+                        uv: uv.left_bottom(),
+                        color,
+                    });
+                    mesh.vertices.push(Vertex {
+                        pos: rect.right_bottom(),
+                        uv: uv.right_bottom(),
+                        color,
+                    });
+                } else {
+                    mesh.add_rect_with_uv(rect, uv, color);
+                }
             }
         }
     }
 }
 
+/// How far apart the two copies of a [`super::TextFormat::bold`] glyph are drawn.
+fn faux_bold_offset(point_scale: PointScale, font_size: f32) -> f32 {
+    point_scale
+        .round_to_pixel(font_size / 24.0)
+        .max(1.0 / point_scale.pixels_per_point())
+}
+
 /// Add a horizontal line over a row of glyphs with a stroke and y decided by a callback.
 fn add_row_hline(
     point_scale: PointScale,
@@ -895,31 +1267,62 @@ struct RowBreakCandidates {
     /// example.com.
     punctuation: Option<usize>,
 
+    /// Anywhere in a [`LineBreak::Graphemes`] section.
+    grapheme: Option<usize>,
+
     /// Breaking after just random character is some
     /// times necessary.
     any: Option<usize>,
+
+    /// Inside a [`LineBreak::Never`] section or a grapheme,
+    /// only if there is no other way.
+    last_resort: Option<usize>,
 }
 
 impl RowBreakCandidates {
-    fn add(&mut self, index: usize, glyphs: &[Glyph]) {
+    fn add(&mut self, index: usize, glyphs: &[Glyph], sections: &[LayoutSection]) {
         let chr = glyphs[0].chr;
-        const NON_BREAKING_SPACE: char = '\u{A0}';
-        if chr.is_whitespace() && chr != NON_BREAKING_SPACE {
+        let next = glyphs.get(1).map(|glyph| glyph.chr);
+        let section_index = glyphs[0].section_index;
+        let line_break = sections[section_index as usize].format.line_break;
+
+        let in_unbreakable_section = line_break == LineBreak::Never
+            && glyphs
+                .get(1)
+                .map_or(false, |glyph| glyph.section_index == section_index);
+        let joined = chr == WORD_JOINER
+            || chr == ZERO_WIDTH_JOINER
+            || next.map_or(false, |next| {
+                next == WORD_JOINER || is_grapheme_extender(next)
+            });
+        if in_unbreakable_section || joined {
+            self.last_resort = Some(index);
+            return;
+        }
+
+        if (chr.is_whitespace() && !is_non_breaking_space(chr)) || chr == ZERO_WIDTH_SPACE {
             self.space = Some(index);
-        } else if is_cjk(chr) && (glyphs.len() == 1 || is_cjk_break_allowed(glyphs[1].chr)) {
+        } else if line_break == LineBreak::Graphemes {
+            self.grapheme = Some(index);
+        } else if is_cjk(chr) && next.map_or(true, is_cjk_break_allowed) {
             self.cjk = Some(index);
-        } else if chr == '-' {
+        } else if chr == '-' || chr == SOFT_HYPHEN {
             self.dash = Some(index);
-        } else if chr.is_ascii_punctuation() {
+        } else if is_opening_bracket(chr) {
+            // Keep it on the same row as what it opens.
+        } else if chr.is_ascii_punctuation()
+            || (chr.is_lowercase() && next.map_or(false, char::is_uppercase))
+            || next.map_or(false, is_opening_bracket)
+        {
             self.punctuation = Some(index);
-        } else if glyphs.len() > 1 && is_cjk(glyphs[1].chr) {
+        } else if next.map_or(false, |next| is_cjk(next) && is_cjk_break_allowed(next)) {
             self.pre_cjk = Some(index);
         }
         self.any = Some(index);
     }
 
     fn word_boundary(&self) -> Option<usize> {
-        [self.space, self.cjk, self.pre_cjk]
+        [self.space, self.cjk, self.pre_cjk, self.grapheme]
             .into_iter()
             .max()
             .flatten()
@@ -935,12 +1338,13 @@ impl RowBreakCandidates {
 
     fn get(&self, break_anywhere: bool) -> Option<usize> {
         if break_anywhere {
-            self.any
+            self.any.or(self.last_resort)
         } else {
             self.word_boundary()
                 .or(self.dash)
                 .or(self.punctuation)
                 .or(self.any)
+                .or(self.last_resort)
         }
     }
 }
@@ -967,7 +1371,51 @@ fn is_cjk(c: char) -> bool {
 #[inline]
 fn is_cjk_break_allowed(c: char) -> bool {
     // See: https://en.wikipedia.org/wiki/Line_breaking_rules_in_East_Asian_languages#Characters_not_permitted_on_the_start_of_a_line.
-    !")]｝〕〉》」』】〙〗〟'\"｠»ヽヾーァィゥェォッャュョヮヵヶぁぃぅぇぉっゃゅょゎゕゖㇰㇱㇲㇳㇴㇵㇶㇷㇸㇹㇺㇻㇼㇽㇾㇿ々〻‐゠–〜?!‼⁇⁈⁉・、:;,。.".contains(c)
+    !")]）］｝〕〉》」』】〙〗〟'\"｠»ヽヾーァィゥェォッャュョヮヵヶぁぃぅぇぉっゃゅょゎゕゖㇰㇱㇲㇳㇴㇵㇶㇷㇸㇹㇺㇻㇼㇽㇾㇿ々〻‐゠–〜?!‼⁇⁈⁉・、:;,。.".contains(c)
+}
+
+/// Characters that stay upright in [`LayoutJob::vertical`] text, instead of being turned.
+#[inline]
+fn is_upright_in_vertical_text(c: char) -> bool {
+    (is_cjk(c) && c != 'ー')
+        || ('\u{AC00}' <= c && c <= '\u{D7AF}') // Hangul syllables
+        || ('\u{3001}' <= c && c <= '\u{3007}') // 、。〃〄々〆〇
+        || ('\u{FF01}' <= c && c <= '\u{FF5E}' && !is_opening_bracket(c) && !"）］｝～".contains(c))
+    // Fullwidth forms
+}
+
+/// Punctuation that sits in the top right corner in vertical text, instead of the bottom left one.
+#[inline]
+fn is_vertical_corner_punctuation(c: char) -> bool {
+    matches!(c, '、' | '。' | '，' | '．')
+}
+
+/// Characters that should not end a row, because they belong with what follows them.
+///
+/// See: <https://en.wikipedia.org/wiki/Line_breaking_rules_in_East_Asian_languages#Characters_not_permitted_on_the_end_of_a_line>.
+#[inline]
+fn is_opening_bracket(c: char) -> bool {
+    "([{（［｛〔〈《「『【〘〖〝｟«".contains(c)
+}
+
+#[inline]
+fn is_non_breaking_space(c: char) -> bool {
+    matches!(c, '\u{A0}' | '\u{2007}' | '\u{202F}')
+}
+
+/// Characters that are part of the same grapheme as the character before them,
+/// like combining accents, variation selectors and skin tone modifiers.
+#[inline]
+fn is_grapheme_extender(c: char) -> bool {
+    ('\u{0300}' <= c && c <= '\u{036F}') // Combining diacritical marks
+        || ('\u{1AB0}' <= c && c <= '\u{1AFF}')
+        || ('\u{1DC0}' <= c && c <= '\u{1DFF}')
+        || ('\u{20D0}' <= c && c <= '\u{20FF}')
+        || ('\u{FE20}' <= c && c <= '\u{FE2F}')
+        || ('\u{3099}' <= c && c <= '\u{309A}') // Combining kana voicing marks
+        || ('\u{FE00}' <= c && c <= '\u{FE0F}') // Variation selectors
+        || ('\u{1F3FB}' <= c && c <= '\u{1F3FF}') // Skin tone modifiers
+        || c == ZERO_WIDTH_JOINER
 }
 
 // ----------------------------------------------------------------------------
@@ -985,6 +1433,16 @@ mod tests {
         assert_eq!(galley.rows.len(), 1);
     }
 
+    #[test]
+    fn test_missing_glyphs() {
+        let mut fonts = FontsImpl::new(1.0, 1024, FontDefinitions::default());
+        // The invisible characters don't count as missing, even though no default font has them:
+        let text = "a\u{AD}b\u{200B}c\u{2060}d\u{10FFFD}";
+        let layout_job = LayoutJob::single_section(text.into(), TextFormat::default());
+        let galley = layout(&mut fonts, layout_job.into());
+        assert_eq!(galley.missing_glyphs, vec!['\u{10FFFD}']);
+    }
+
     #[test]
     fn test_truncate_with_newline() {
         // No matter where we wrap, we should be appending the newline character.
@@ -1061,4 +1519,386 @@ mod tests {
             vec!["日本語とEnglish", "の混在した文章"]
         );
     }
+
+    #[test]
+    fn test_line_break() {
+        let mut fonts = FontsImpl::new(1.0, 1024, FontDefinitions::default());
+        let format = |line_break| TextFormat {
+            font_id: FontId::monospace(10.0),
+            line_break,
+            ..Default::default()
+        };
+        let char_width = layout(
+            &mut fonts,
+            LayoutJob::single_section("W".into(), format(LineBreak::Words)).into(),
+        )
+        .rect
+        .width();
+        let mut rows = |mut job: LayoutJob, max_chars: f32| {
+            job.wrap.max_width = (max_chars + 0.5) * char_width;
+            let galley = layout(&mut fonts, job.into());
+            galley.rows.iter().map(|row| row.text()).collect::<Vec<_>>()
+        };
+
+        // No-break span:
+        let mut job = LayoutJob::default();
+        job.append("Hello ", 0.0, format(LineBreak::Words));
+        job.append("New York", 0.0, format(LineBreak::Never));
+        assert_eq!(rows(job.clone(), 10.0), vec!["Hello ", "New York"]);
+        assert_eq!(rows(job, 6.0), vec!["Hello ", "New Yo", "rk"]);
+
+        // Graphemes:
+        let job =
+            |line_break| LayoutJob::single_section("ab cde\u{301}fgh".into(), format(line_break));
+        assert_eq!(
+            rows(job(LineBreak::Words), 4.0),
+            vec!["ab ", "cde\u{301}f", "gh"]
+        );
+        assert_eq!(
+            rows(job(LineBreak::Graphemes), 4.0),
+            vec!["ab c", "de\u{301}fg", "h"]
+        );
+        assert_eq!(
+            rows(job(LineBreak::Graphemes), 5.0),
+            vec!["ab cd", "e\u{301}fgh"]
+        );
+
+        // Identifiers break after punctuation and between camelCase humps:
+        let job = LayoutJob::single_section("someLongIdentifier".into(), format(LineBreak::Words));
+        assert_eq!(rows(job, 12.0), vec!["someLong", "Identifier"]);
+        let job = LayoutJob::single_section("foo(bar_baz)".into(), format(LineBreak::Words));
+        assert_eq!(rows(job, 6.0), vec!["foo", "(bar_", "baz)"]);
+
+        // Zero width spaces suggest breaks, word joiners prevent them:
+        let job = LayoutJob::single_section(
+            "path/\u{200B}to/\u{2060}file".into(),
+            format(LineBreak::Words),
+        );
+        assert_eq!(rows(job, 8.0), vec!["path/\u{200B}", "to/\u{2060}file"]);
+
+        // Soft hyphens are invisible, unless the row breaks there:
+        let mut width = |text: &str| {
+            let job = LayoutJob::single_section(text.into(), format(LineBreak::Words));
+            layout(&mut fonts, job.into()).rect.width()
+        };
+        let (unbroken_width, broken_width) = (width("Unbreakable"), width("Unbreak-"));
+        let mut job = LayoutJob::single_section("Unbreakable".into(), format(LineBreak::Words));
+        job.hyphenate(|_| vec![2, 7]);
+        let galley = layout(&mut fonts, job.clone().into());
+        assert_eq!(galley.rows.len(), 1);
+        assert_eq!(galley.rect.width(), unbroken_width);
+        job.wrap.max_width = 8.5 * char_width;
+        let galley = layout(&mut fonts, job.into());
+        assert_eq!(galley.rows[0].text(), "Un\u{AD}break\u{AD}");
+        assert_eq!(galley.rows[1].text(), "able");
+        assert_eq!(galley.rows[0].rect.width(), broken_width);
+    }
+
+    #[test]
+    fn test_kinsoku() {
+        let mut fonts = FontsImpl::new(1.0, 1024, FontDefinitions::default());
+        let char_width = layout(
+            &mut fonts,
+            LayoutJob::single_section("あ".into(), TextFormat::default()).into(),
+        )
+        .rect
+        .width();
+        let mut layout_job = LayoutJob::single_section("あ「いう」".into(), TextFormat::default());
+        layout_job.wrap.max_width = 2.5 * char_width;
+        let galley = layout(&mut fonts, layout_job.into());
+        // Opening brackets don't end rows, and closing ones don't start them:
+        assert_eq!(
+            galley.rows.iter().map(|row| row.text()).collect::<Vec<_>>(),
+            vec!["あ", "「い", "う」"]
+        );
+    }
+
+    #[test]
+    fn test_vertical() {
+        let mut fonts = FontsImpl::new(1.0, 1024, FontDefinitions::default());
+        let horizontal = layout(
+            &mut fonts,
+            LayoutJob::single_section("日本語\nab".into(), TextFormat::default()).into(),
+        );
+        let vertical = layout(
+            &mut fonts,
+            LayoutJob::single_section("日本語\nab".into(), TextFormat::default())
+                .vertical(true)
+                .into(),
+        );
+
+        // Rows become columns, from right to left:
+        assert_eq!(
+            vertical.size(),
+            vec2(horizontal.size().y, horizontal.size().x)
+        );
+        let [first, second] = [&vertical.rows[0], &vertical.rows[1]];
+        assert_eq!(first.rect.width(), horizontal.rows[0].rect.height());
+        assert_eq!(first.rect.height(), horizontal.rows[0].rect.width());
+        assert_eq!(first.rect.max.x, vertical.rect.max.x);
+        assert!(second.rect.max.x <= first.rect.min.x);
+        assert!(vertical.rect.contains_rect(vertical.mesh_bounds));
+
+        // CJK stays upright, so the top left of the glyph texture stays at the top left:
+        let quad = |row: &Row, i: usize| {
+            let start = row.visuals.glyph_vertex_range.start + 4 * i;
+            let vertices = &row.visuals.mesh.vertices[start..start + 4];
+            let rect = Rect::from_points(&vertices.iter().map(|v| v.pos).collect::<Vec<_>>());
+            let top_left_uv = vertices
+                .iter()
+                .min_by(|a, b| (a.uv.x + a.uv.y).total_cmp(&(b.uv.x + b.uv.y)))
+                .unwrap();
+            (rect, top_left_uv.pos)
+        };
+        let (rect, top_left) = quad(first, 0);
+        assert!(first.rect.contains_rect(rect));
+        assert_eq!(top_left, rect.left_top());
+        let (next_rect, _) = quad(first, 1);
+        assert!(rect.bottom() <= next_rect.top());
+
+        // Latin is turned a quarter turn clockwise, so it goes to the top right:
+        let (rect, top_left) = quad(second, 0);
+        assert!(second.rect.contains_rect(rect));
+        assert_eq!(top_left, rect.right_top());
+    }
+
+    #[test]
+    fn test_synthesized_styles() {
+        let mut fonts = FontsImpl::new(1.0, 1024, FontDefinitions::default());
+        let mut layout = |text: &str, format: TextFormat| {
+            layout(
+                &mut fonts,
+                LayoutJob::single_section(text.into(), format).into(),
+            )
+        };
+        let regular = layout("a b", TextFormat::default());
+
+        let spaced = layout(
+            "a b",
+            TextFormat {
+                extra_word_spacing: 10.0,
+                ..Default::default()
+            },
+        );
+        assert_eq!(spaced.size().x, regular.size().x + 10.0);
+
+        let bold = layout(
+            "a b",
+            TextFormat {
+                bold: true,
+                ..Default::default()
+            },
+        );
+        assert_eq!(bold.size().x, regular.size().x + 3.0);
+        assert_eq!(bold.num_vertices, 2 * regular.num_vertices);
+
+        let small_caps = layout(
+            "Ab",
+            TextFormat {
+                small_caps: true,
+                ..Default::default()
+            },
+        );
+        let small_b = layout(
+            "B",
+            TextFormat {
+                font_id: FontId::proportional(14.0 * SMALL_CAPS_SCALE),
+                ..Default::default()
+            },
+        );
+        assert_eq!(small_caps.text(), "Ab");
+        let glyphs = &small_caps.rows[0].glyphs;
+        assert_eq!(glyphs[1].uv_rect, small_b.rows[0].glyphs[0].uv_rect);
+        assert_eq!(glyphs[0].pos.y, glyphs[1].pos.y, "Same baseline");
+    }
+
+    #[test]
+    fn test_font_features() {
+        let mut fonts = FontsImpl::new(1.0, 1024, FontDefinitions::default());
+        let mut layout = |text: &str, font_features: FontFeatures| {
+            layout(
+                &mut fonts,
+                LayoutJob::single_section(
+                    text.into(),
+                    TextFormat {
+                        font_features,
+                        ..Default::default()
+                    },
+                )
+                .into(),
+            )
+        };
+        let widths = |galley: &Galley| -> Vec<f32> {
+            galley.rows[0].glyphs.iter().map(|g| g.size.x).collect()
+        };
+
+        // Tabular numbers all have the same width:
+        let tabular = layout("10", FontFeatures::default().enable(b"tnum"));
+        let tabular = widths(&tabular);
+        assert_eq!(tabular[0], tabular[1]);
+        let proportional = layout("10", FontFeatures::default().enable(b"pnum"));
+        assert_ne!(widths(&proportional)[0], widths(&proportional)[1]);
+
+        // Ligatures are on by default. A ligature is drawn by its first character,
+        // and shares its width with the rest:
+        let regular = layout("fit", FontFeatures::default().disable(b"liga"));
+        let ligature = layout("fit", FontFeatures::default());
+        assert_eq!(ligature.text(), "fit");
+        let glyphs = &ligature.rows[0].glyphs;
+        assert_ne!(glyphs[0].uv_rect, regular.rows[0].glyphs[0].uv_rect);
+        assert!(glyphs[1].uv_rect.is_nothing());
+        assert_eq!(glyphs[0].size.x, glyphs[1].size.x);
+        assert_eq!(glyphs[2].uv_rect, regular.rows[0].glyphs[2].uv_rect);
+
+        assert_eq!(
+            layout("fit", FontFeatures::default().enable(b"liga")).rows[0].glyphs,
+            *glyphs
+        );
+
+        // Features the font doesn't have change nothing:
+        let unknown = layout(
+            "fit",
+            FontFeatures::default().disable(b"liga").enable(b"smcp"),
+        );
+        assert_eq!(widths(&unknown), widths(&regular));
+    }
+
+    /// Add a `kern` table with the given pairs (in font units) to the font.
+    fn with_kern_table(font: &[u8], mut pairs: Vec<(u16, u16, i16)>) -> Vec<u8> {
+        let be16 = |at: usize| u16::from_be_bytes([font[at], font[at + 1]]);
+        let be32 = |at: usize| u32::from_be_bytes(font[at..at + 4].try_into().unwrap());
+
+        let mut kern = vec![];
+        pairs.sort_by_key(|&(left, right, _)| (left, right));
+        let subtable_len = 14 + 6 * pairs.len() as u16;
+        for value in [0, 1, 0, subtable_len, 0x0001, pairs.len() as u16, 0, 0, 0] {
+            kern.extend_from_slice(&u16::to_be_bytes(value));
+        }
+        for (left, right, value) in pairs {
+            kern.extend_from_slice(&left.to_be_bytes());
+            kern.extend_from_slice(&right.to_be_bytes());
+            kern.extend_from_slice(&value.to_be_bytes());
+        }
+
+        let num_tables = be16(4) as usize;
+        let mut tables: Vec<([u8; 4], &[u8])> = (0..num_tables)
+            .map(|i| {
+                let record = 12 + 16 * i;
+                let offset = be32(record + 8) as usize;
+                let len = be32(record + 12) as usize;
+                let tag = font[record..record + 4].try_into().unwrap();
+                (tag, &font[offset..offset + len])
+            })
+            .collect();
+        tables.push((*b"kern", &kern));
+        tables.sort_by_key(|(tag, _)| *tag);
+
+        let mut out = font[..12].to_vec();
+        out[4..6].copy_from_slice(&(tables.len() as u16).to_be_bytes());
+        let mut offset = 12 + 16 * tables.len();
+        for (tag, data) in &tables {
+            out.extend_from_slice(tag);
+            out.extend_from_slice(&[0; 4]); // Checksum
+            out.extend_from_slice(&(offset as u32).to_be_bytes());
+            out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+            offset += (data.len() + 3) & !3;
+        }
+        for (_, data) in &tables {
+            out.extend_from_slice(data);
+            out.resize((out.len() + 3) & !3, 0);
+        }
+        out
+    }
+
+    #[test]
+    fn test_no_kerning_across_paragraphs() {
+        use ab_glyph::Font as _;
+
+        // The default fonts have no `kern` table, so make one that kerns "AV" a lot:
+        let hack = include_bytes!("../../fonts/Hack-Regular.ttf");
+        let glyph_id = |chr| {
+            ab_glyph::FontRef::try_from_slice(hack)
+                .unwrap()
+                .glyph_id(chr)
+                .0
+        };
+        let kerned = with_kern_table(hack, vec![(glyph_id('A'), glyph_id('V'), -500)]);
+
+        let mut definitions = FontDefinitions::default();
+        definitions
+            .font_data
+            .insert("kerned".to_owned(), FontData::from_owned(kerned));
+        definitions
+            .families
+            .insert(FontFamily::Proportional, vec!["kerned".to_owned()]);
+        let mut fonts = FontsImpl::new(1.0, 1024, definitions);
+        let mut layout = |text: &str| {
+            let job = LayoutJob::single_section(text.into(), TextFormat::default());
+            layout(&mut fonts, job.into())
+        };
+
+        let a = layout("A");
+        let kerned = layout("AV");
+        let glyphs = &kerned.rows[0].glyphs;
+        assert!(glyphs[1].pos.x < a.rows[0].glyphs[0].size.x, "Kerned");
+
+        let paragraphs = layout("A\nV");
+        assert_eq!(
+            paragraphs.rows[1].glyphs[0].pos.x,
+            layout("V").rows[0].glyphs[0].pos.x,
+            "No kerning across paragraphs"
+        );
+    }
+
+    #[test]
+    fn test_split_paragraphs() {
+        for pixels_per_point in [1.0, 1.5] {
+            let mut fonts = FontsImpl::new(pixels_per_point, 1024, FontDefinitions::default());
+            let mut job = LayoutJob::default();
+            job.append("Hello\n\nworld ", 4.0, TextFormat::default());
+            job.append(
+                "a longer section\nwith some text that will wrap",
+                0.0,
+                TextFormat {
+                    font_id: FontId::monospace(20.0),
+                    ..Default::default()
+                },
+            );
+            job.append("", 2.0, TextFormat::default());
+            job.append("\nend\n", 3.0, TextFormat::default());
+            job.wrap.max_width = 120.0;
+            job.first_row_min_height = 30.0;
+            let job = Arc::new(job);
+
+            let paragraphs: Vec<_> = split_paragraphs(&job)
+                .unwrap()
+                .into_iter()
+                .map(|(job, section_map)| (Arc::new(layout(&mut fonts, job.into())), section_map))
+                .collect();
+            assert_eq!(paragraphs.len(), 6);
+
+            let split = concat_paragraphs(job.clone(), &paragraphs, pixels_per_point);
+            let whole = layout(&mut fonts, job);
+            // The same, up to rounding errors:
+            let close = |a: Pos2, b: Pos2| (a - b).length() < 1e-3;
+            assert_eq!(split.rows.len(), whole.rows.len());
+            for (a, b) in split.rows.iter().zip(&whole.rows) {
+                assert_eq!(a.section_index_at_start, b.section_index_at_start);
+                assert_eq!(a.ends_with_newline, b.ends_with_newline);
+                assert!(close(a.rect.min, b.rect.min) && close(a.rect.max, b.rect.max));
+                assert_eq!(a.glyphs.len(), b.glyphs.len());
+                for (a, b) in a.glyphs.iter().zip(&b.glyphs) {
+                    assert_eq!((a.chr, a.section_index), (b.chr, b.section_index));
+                    assert!(close(a.pos, b.pos));
+                }
+                assert_eq!(a.visuals.mesh.indices, b.visuals.mesh.indices);
+                for (a, b) in a.visuals.mesh.vertices.iter().zip(&b.visuals.mesh.vertices) {
+                    assert!(close(a.pos, b.pos));
+                }
+            }
+            assert!(close(split.rect.min, whole.rect.min) && close(split.rect.max, whole.rect.max));
+            assert_eq!(split.num_vertices, whole.num_vertices);
+            assert_eq!(split.num_indices, whole.num_indices);
+        }
+    }
 }
@@ -4,7 +4,7 @@
 use std::ops::Range;
 use std::sync::Arc;
 
-use super::{cursor::*, font::UvRect};
+use super::{cursor::*, font::UvRect, FontVariations};
 use crate::{Color32, FontId, Mesh, Stroke};
 use emath::*;
 
@@ -74,6 +74,17 @@ pub struct LayoutJob {
 
     /// Justify text so that word-wrapped rows fill the whole [`TextWrapping::max_width`].
     pub justify: bool,
+
+    /// Write top-to-bottom, in columns from right to left, like traditional Japanese and Chinese.
+    ///
+    /// CJK characters stay upright, while other text (e.g. Latin) is turned a quarter turn clockwise.
+    /// The rows of the text become the columns, so [`TextWrapping::max_width`] limits the height of the columns,
+    /// and [`Self::halign`] aligns the text within them.
+    ///
+    /// The cursor methods of [`Galley`] don't support vertical text.
+    ///
+    /// Default: `false`.
+    pub vertical: bool,
 }
 
 impl Default for LayoutJob {
@@ -87,6 +98,7 @@ impl Default for LayoutJob {
             break_on_newline: true,
             halign: Align::LEFT,
             justify: false,
+            vertical: false,
         }
     }
 }
@@ -142,6 +154,13 @@ impl LayoutJob {
         }
     }
 
+    /// Write top-to-bottom, in columns from right to left. See [`Self::vertical`].
+    #[inline]
+    pub fn vertical(mut self, vertical: bool) -> Self {
+        self.vertical = vertical;
+        self
+    }
+
     #[inline]
     pub fn is_empty(&self) -> bool {
         self.sections.is_empty()
@@ -167,6 +186,70 @@ impl LayoutJob {
         }
         max_height
     }
+
+    /// Let rows break inside words, at the byte offsets that `hyphenation_points` returns for each word,
+    /// e.g. using a hyphenation dictionary for your language.
+    ///
+    /// This inserts a soft hyphen (`\u{AD}`) at each point in sections with [`LineBreak::Words`].
+    /// It is invisible, unless the row breaks there, in which case it shows as a hyphen.
+    ///
+    /// ```
+    /// # use epaint::text::{LayoutJob, TextFormat};
+    /// let mut job = LayoutJob::single_section("Unbreakable".to_owned(), TextFormat::default());
+    /// job.hyphenate(|word| if word == "Unbreakable" { vec![2, 7] } else { vec![] });
+    /// assert_eq!(job.text, "Un\u{AD}break\u{AD}able");
+    /// ```
+    pub fn hyphenate(&mut self, hyphenation_points: impl Fn(&str) -> Vec<usize>) {
+        const SOFT_HYPHEN: char = '\u{AD}';
+
+        let mut points = vec![];
+        for section in &self.sections {
+            if section.format.line_break != LineBreak::Words {
+                continue;
+            }
+            let section_text = &self.text[section.byte_range.clone()];
+            let mut word_start = None;
+            let end = std::iter::once((section_text.len(), ' '));
+            for (i, chr) in section_text.char_indices().chain(end) {
+                match (word_start, chr.is_alphabetic()) {
+                    (None, true) => word_start = Some(i),
+                    (Some(start), false) => {
+                        let word = &section_text[start..i];
+                        points.extend(
+                            hyphenation_points(word)
+                                .into_iter()
+                                .filter(|&p| 0 < p && p < word.len() && word.is_char_boundary(p))
+                                .map(|p| section.byte_range.start + start + p),
+                        );
+                        word_start = None;
+                    }
+                    _ => {}
+                }
+            }
+        }
+        if points.is_empty() {
+            return;
+        }
+        points.sort_unstable();
+        points.dedup();
+
+        let mut text = String::with_capacity(self.text.len() + points.len() * 2);
+        let mut last = 0;
+        for &point in &points {
+            text += &self.text[last..point];
+            text.push(SOFT_HYPHEN);
+            last = point;
+        }
+        text += &self.text[last..];
+
+        let shift = |index: usize| {
+            index + points.partition_point(|&point| point < index) * SOFT_HYPHEN.len_utf8()
+        };
+        for section in &mut self.sections {
+            section.byte_range = shift(section.byte_range.start)..shift(section.byte_range.end);
+        }
+        self.text = text;
+    }
 }
 
 impl std::hash::Hash for LayoutJob {
@@ -180,6 +263,7 @@ impl std::hash::Hash for LayoutJob {
             break_on_newline,
             halign,
             justify,
+            vertical,
         } = self;
 
         text.hash(state);
@@ -189,6 +273,7 @@ impl std::hash::Hash for LayoutJob {
         break_on_newline.hash(state);
         halign.hash(state);
         justify.hash(state);
+        vertical.hash(state);
     }
 }
 
@@ -235,6 +320,11 @@ pub struct TextFormat {
     /// For even text it is recommended you round this to an even number of _pixels_.
     pub extra_letter_spacing: f32,
 
+    /// Extra spacing after each space, in points.
+    ///
+    /// Default: 0.0.
+    pub extra_word_spacing: f32,
+
     /// Explicit line height of the text in points.
     ///
     /// This is the distance between the bottom row of two subsequent lines of text.
@@ -249,8 +339,17 @@ pub struct TextFormat {
 
     pub background: Color32,
 
+    /// Slant the glyphs to the right.
     pub italics: bool,
 
+    /// Synthesized bold: each glyph is drawn twice, a pixel or so apart, and takes up that much more room.
+    ///
+    /// Use it when the font has no bold variant of its own.
+    pub bold: bool,
+
+    /// Synthesized small capitals: lowercase letters are shown as smaller uppercase letters.
+    pub small_caps: bool,
+
     pub underline: Stroke,
 
     pub strikethrough: Stroke,
@@ -259,6 +358,16 @@ pub struct TextFormat {
     /// can get the effect of raised text.
     pub valign: Align,
     // TODO(emilk): lowered
+    /// Where rows may break inside this section when the text is wrapped.
+    pub line_break: LineBreak,
+
+    /// OpenType features to apply, e.g. `tnum` for numbers that line up in a table.
+    pub font_features: FontFeatures,
+
+    /// Axis values for variable fonts, e.g. the weight.
+    ///
+    /// Fonts that aren't variable, or lack some of the axes, ignore them.
+    pub font_variations: FontVariations,
 }
 
 impl Default for TextFormat {
@@ -267,13 +376,19 @@ impl Default for TextFormat {
         Self {
             font_id: FontId::default(),
             extra_letter_spacing: 0.0,
+            extra_word_spacing: 0.0,
             line_height: None,
             color: Color32::GRAY,
             background: Color32::TRANSPARENT,
             italics: false,
+            bold: false,
+            small_caps: false,
             underline: Stroke::NONE,
             strikethrough: Stroke::NONE,
             valign: Align::BOTTOM,
+            line_break: LineBreak::default(),
+            font_features: FontFeatures::default(),
+            font_variations: FontVariations::default(),
         }
     }
 }
@@ -284,25 +399,37 @@ impl std::hash::Hash for TextFormat {
         let Self {
             font_id,
             extra_letter_spacing,
+            extra_word_spacing,
             line_height,
             color,
             background,
             italics,
+            bold,
+            small_caps,
             underline,
             strikethrough,
             valign,
+            line_break,
+            font_features,
+            font_variations,
         } = self;
         font_id.hash(state);
         crate::f32_hash(state, *extra_letter_spacing);
+        crate::f32_hash(state, *extra_word_spacing);
         if let Some(line_height) = *line_height {
             crate::f32_hash(state, line_height);
         }
         color.hash(state);
         background.hash(state);
         italics.hash(state);
+        bold.hash(state);
+        small_caps.hash(state);
         underline.hash(state);
         strikethrough.hash(state);
         valign.hash(state);
+        line_break.hash(state);
+        font_features.hash(state);
+        font_variations.hash(state);
     }
 }
 
@@ -317,6 +444,118 @@ impl TextFormat {
     }
 }
 
+/// Where rows may break inside a section of a wrapped [`LayoutJob`].
+///
+/// In any section, a row may also break at a zero width space (`\u{200B}`),
+/// and at a soft hyphen (`\u{AD}`), which then shows as a hyphen (see [`LayoutJob::hyphenate`]).
+/// A row never breaks at a non-breaking space (`\u{A0}`) or next to a word joiner (`\u{2060}`),
+/// unless there is no other way to fit it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum LineBreak {
+    /// Break between words, and between CJK characters following the
+    /// [kinsoku](https://en.wikipedia.org/wiki/Line_breaking_rules_in_East_Asian_languages) rules.
+    ///
+    /// A word too long for a row breaks after punctuation like `.`, `/` or `_`,
+    /// or between the humps of a `camelCase` identifier, before breaking anywhere.
+    #[default]
+    Words,
+
+    /// Break between any two characters, e.g. for hashes and other long tokens without words,
+    /// but not inside a grapheme, like an `e` followed by a combining accent.
+    Graphemes,
+
+    /// Don't break inside this section, e.g. for a name or a number with its unit.
+    ///
+    /// It is only broken if it doesn't fit on a row of its own.
+    Never,
+}
+
+// ----------------------------------------------------------------------------
+
+/// OpenType feature settings, like `tnum` (tabular numbers), `liga` (ligatures) or `ss01` (stylistic set 1).
+///
+/// Kerning (`kern`) and standard ligatures (`liga`) are on unless disabled.
+/// egui applies no other features unless asked to, so numbers get whatever widths the font gives them.
+///
+/// Only features that substitute glyphs one-by-one or form ligatures are supported
+/// (not contextual ones, like `calt`), and only for the default script of the font.
+/// Kerning comes from the `kern` table of the font.
+///
+/// ```
+/// # use epaint::text::FontFeatures;
+/// let code = FontFeatures::default().enable(b"zero").disable(b"kern").disable(b"liga");
+/// let table = FontFeatures::default().enable(b"tnum");
+/// let fancy = FontFeatures::default().enable(b"dlig").set(b"salt", 2);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct FontFeatures {
+    /// Sorted by tag.
+    settings: Vec<([u8; 4], u16)>,
+}
+
+impl FontFeatures {
+    /// The features that are on unless disabled.
+    const ON_BY_DEFAULT: [[u8; 4]; 2] = [*b"kern", *b"liga"];
+
+    /// Turn on the feature with this tag, e.g. `b"tnum"`.
+    #[inline]
+    pub fn enable(self, tag: &[u8; 4]) -> Self {
+        self.set(tag, 1)
+    }
+
+    /// Turn off the feature with this tag, e.g. `b"kern"`.
+    #[inline]
+    pub fn disable(self, tag: &[u8; 4]) -> Self {
+        self.set(tag, 0)
+    }
+
+    /// Set the value of a feature: 0 is off and 1 is on.
+    ///
+    /// For features with alternates, like `salt` or `cv01`, this picks the alternate,
+    /// with 1 being the first one.
+    pub fn set(mut self, tag: &[u8; 4], value: u16) -> Self {
+        match self.settings.binary_search_by_key(tag, |(tag, _)| *tag) {
+            Ok(index) => self.settings[index].1 = value,
+            Err(index) => self.settings.insert(index, (*tag, value)),
+        }
+        self
+    }
+
+    /// The value of this feature, if it has been set.
+    pub fn get(&self, tag: &[u8; 4]) -> Option<u16> {
+        self.settings
+            .binary_search_by_key(tag, |(tag, _)| *tag)
+            .ok()
+            .map(|index| self.settings[index].1)
+    }
+
+    /// The value egui uses for this feature: the one it is set to,
+    /// or else 1 for `kern` and `liga`, which are on by default, and 0 for the rest.
+    pub fn value(&self, tag: &[u8; 4]) -> u16 {
+        self.get(tag)
+            .unwrap_or_else(|| u16::from(Self::ON_BY_DEFAULT.contains(tag)))
+    }
+
+    /// Is the feature with this tag explicitly enabled?
+    #[inline]
+    pub fn is_enabled(&self, tag: &[u8; 4]) -> bool {
+        self.get(tag).map_or(false, |value| value != 0)
+    }
+
+    /// No features set?
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.settings.is_empty()
+    }
+
+    /// All features that have been set, with their values, ordered by tag.
+    pub fn iter(&self) -> impl Iterator<Item = ([u8; 4], u16)> + '_ {
+        self.settings.iter().copied()
+    }
+}
+
 // ----------------------------------------------------------------------------
 
 /// Controls the text wrapping and elision of a [`LayoutJob`].
@@ -448,6 +687,12 @@ pub struct Galley {
     /// Set to true the text was truncated due to [`TextWrapping::max_rows`].
     pub elided: bool,
 
+    /// The characters of the text that none of the fonts have a glyph for.
+    ///
+    /// These are shown as a replacement character (`◻`) instead.
+    /// Sorted, without duplicates.
+    pub missing_glyphs: Vec<char>,
+
     /// Bounding rect.
     ///
     /// `rect.top()` is always 0.0.